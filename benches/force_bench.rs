@@ -0,0 +1,46 @@
+//! Benchmarks for the many-body force: exact O(n²) sum vs Barnes-Hut
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use makepad_d3::layout::force::{Force, ManyBodyForce, SimulationNode};
+
+/// Deterministic spiral layout with uneven density
+fn spiral_nodes(n: usize) -> Vec<SimulationNode> {
+    (0..n)
+        .map(|i| {
+            let t = i as f64 * 0.1;
+            SimulationNode::at(i, t.cos() * (10.0 + t), t.sin() * (10.0 + t))
+        })
+        .collect()
+}
+
+fn many_body_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("many_body");
+    group.sample_size(10);
+
+    for n in [1_000, 5_000, 10_000] {
+        let nodes = spiral_nodes(n);
+
+        group.bench_with_input(BenchmarkId::new("barnes_hut", n), &nodes, |b, nodes| {
+            let force = ManyBodyForce::new();
+            b.iter(|| {
+                let mut nodes = nodes.clone();
+                force.apply(&mut nodes, 1.0);
+                black_box(nodes);
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("exact", n), &nodes, |b, nodes| {
+            let force = ManyBodyForce::new().exact();
+            b.iter(|| {
+                let mut nodes = nodes.clone();
+                force.apply(&mut nodes, 1.0);
+                black_box(nodes);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, many_body_benchmark);
+criterion_main!(benches);
@@ -5,6 +5,7 @@
 //! GPU-accelerated with proper animation and hover support.
 
 use makepad_widgets::*;
+use makepad_d3::layout::chord::ChordLayout;
 use std::f64::consts::PI;
 use super::draw_primitives::{DrawArc, DrawTriangle};
 use super::axis_renderer::DrawAxisText;
@@ -61,8 +62,6 @@ struct GroupArc {
     value: f64,
     color: Vec4,
     label: String,
-    /// Track how much of this group's angle has been consumed by chords (single offset for both in/out)
-    offset: f64,
 }
 
 /// Computed chord for layout
@@ -287,47 +286,21 @@ impl ChordDiagramWidget {
         self.groups.clear();
         self.chords.clear();
 
-        let n = self.chord_data.matrix.len();
-        if n == 0 {
-            return;
-        }
-
-        // Group totals depend on mode:
-        // - Directed: row_sum + column_sum (outgoing + incoming ribbons)
-        // - Undirected: row_sum only (ribbons are merged)
-        let mut group_totals: Vec<f64> = vec![0.0; n];
-        for i in 0..n {
-            for j in 0..n {
-                if i < self.chord_data.matrix.len() && j < self.chord_data.matrix[i].len() {
-                    // Outgoing from i
-                    group_totals[i] += self.chord_data.matrix[i][j];
-                }
-                if self.directed_mode {
-                    // In directed mode, also add incoming to i (column sum)
-                    if j < self.chord_data.matrix.len() && i < self.chord_data.matrix[j].len() {
-                        group_totals[i] += self.chord_data.matrix[j][i];
-                    }
-                }
-            }
-        }
-
-        let total_value: f64 = group_totals.iter().sum();
-        if total_value == 0.0 {
-            return;
-        }
-
-        // Calculate available angle (after gaps)
-        let total_gap = self.gap_angle * n as f64;
-        let available_angle = 2.0 * PI - total_gap;
-
-        // Scaling factor: maps flow values to angles
-        let k = available_angle / total_value;
-
-        // Assign angles to groups
-        let mut current_angle = -PI / 2.0;
-        for i in 0..n {
-            let group_angle = group_totals[i] * k;
-
+        let layout = if self.directed_mode {
+            ChordLayout::directed()
+        } else {
+            ChordLayout::new()
+        };
+        let set = match layout.pad_angle(self.gap_angle).compute(&self.chord_data.matrix) {
+            Ok(set) if set.groups.iter().any(|g| g.value > 0.0) => set,
+            _ => return,
+        };
+
+        // The layout puts 0 at 12 o'clock; drawing uses 0 at 3 o'clock
+        let offset = -PI / 2.0;
+
+        for group in &set.groups {
+            let i = group.index;
             let label = if i < self.chord_data.labels.len() {
                 self.chord_data.labels[i].clone()
             } else {
@@ -346,133 +319,25 @@ impl ChordDiagramWidget {
 
             self.groups.push(GroupArc {
                 index: i,
-                start_angle: current_angle,
-                end_angle: current_angle + group_angle,
-                value: group_totals[i],
+                start_angle: group.start_angle + offset,
+                end_angle: group.end_angle + offset,
+                value: group.value,
                 color,
                 label,
-                offset: 0.0,
             });
-
-            current_angle += group_angle + self.gap_angle;
         }
 
-        if self.directed_mode {
-            // DIRECTED MODE (d3.chordDirected):
-            // For each group, process INCOMING flows first, then OUTGOING flows
-            // Both consume angle within that group's arc
-            // This matches D3's approach where subgroupIndex includes both [-n..0) and [0..n)
-
-            // We need to track: for each (i,j) pair, where does source start and target start?
-            // Source angle is assigned when processing outgoing from i
-            // Target angle is assigned when processing incoming to j
-
-            // First pass: assign target angles (incoming flows to each group)
-            // For group j, incoming flows are matrix[i][j] for all i
-            let mut target_angles: Vec<Vec<(f64, f64)>> = vec![vec![(0.0, 0.0); n]; n];
-
-            for j in 0..n {
-                for i in 0..n {
-                    if i >= self.chord_data.matrix.len() || j >= self.chord_data.matrix[i].len() {
-                        continue;
-                    }
-                    let value = self.chord_data.matrix[i][j];
-                    if value <= 0.0 {
-                        continue;
-                    }
-
-                    let chord_angle = value * k;
-                    let target_start = self.groups[j].start_angle + self.groups[j].offset;
-                    let target_end = target_start + chord_angle;
-                    self.groups[j].offset += chord_angle;
-
-                    target_angles[i][j] = (target_start, target_end);
-                }
-            }
-
-            // Second pass: assign source angles (outgoing flows from each group) and create chords
-            for i in 0..n {
-                for j in 0..n {
-                    if i >= self.chord_data.matrix.len() || j >= self.chord_data.matrix[i].len() {
-                        continue;
-                    }
-                    let value = self.chord_data.matrix[i][j];
-                    if value <= 0.0 {
-                        continue;
-                    }
-
-                    let chord_angle = value * k;
-                    let source_start = self.groups[i].start_angle + self.groups[i].offset;
-                    let source_end = source_start + chord_angle;
-                    self.groups[i].offset += chord_angle;
-
-                    let (target_start, target_end) = target_angles[i][j];
-
-                    self.chords.push(Chord {
-                        source_index: i,
-                        target_index: j,
-                        source_start,
-                        source_end,
-                        target_start,
-                        target_end,
-                        value,
-                    });
-                }
-            }
-        } else {
-            // UNDIRECTED MODE (d3.chord): Merge [i][j] and [j][i] into single ribbon
-            use std::collections::HashMap;
-            let mut chord_map: HashMap<(usize, usize), Chord> = HashMap::new();
-
-            for i in 0..n {
-                for j in 0..n {
-                    if i >= self.chord_data.matrix.len() || j >= self.chord_data.matrix[i].len() {
-                        continue;
-                    }
-
-                    let value = self.chord_data.matrix[i][j];
-                    if value <= 0.0 {
-                        continue;
-                    }
-
-                    // Consume angle at group i
-                    let chord_angle = value * k;
-                    let angle_start = self.groups[i].start_angle + self.groups[i].offset;
-                    let angle_end = angle_start + chord_angle;
-                    self.groups[i].offset += chord_angle;
-
-                    // Key for merging: (smaller_index, larger_index)
-                    let key = if i <= j { (i, j) } else { (j, i) };
-
-                    let chord = chord_map.entry(key).or_insert(Chord {
-                        source_index: key.0,
-                        target_index: key.1,
-                        source_start: 0.0,
-                        source_end: 0.0,
-                        target_start: 0.0,
-                        target_end: 0.0,
-                        value: 0.0,
-                    });
-
-                    if i <= j {
-                        chord.source_start = angle_start;
-                        chord.source_end = angle_end;
-                        chord.value += value;
-                        if i == j {
-                            chord.target_start = angle_start;
-                            chord.target_end = angle_end;
-                        }
-                    } else {
-                        chord.target_start = angle_start;
-                        chord.target_end = angle_end;
-                    }
-                }
-            }
-
-            self.chords = chord_map.into_values()
-                .filter(|c| c.source_end > c.source_start || c.target_end > c.target_start)
-                .collect();
-        }
+        self.chords = set.chords.iter()
+            .map(|c| Chord {
+                source_index: c.source.index,
+                target_index: c.target.index,
+                source_start: c.source.start_angle + offset,
+                source_end: c.source.end_angle + offset,
+                target_start: c.target.start_angle + offset,
+                target_end: c.target.end_angle + offset,
+                value: c.source.value,
+            })
+            .collect();
     }
 
     fn draw_group_arcs(&mut self, cx: &mut Cx2d) {
@@ -4,6 +4,7 @@
 //! Features curved flow links, gradient colors, and staggered reveal animations.
 
 use makepad_widgets::*;
+use makepad_d3::layout::sankey::{SankeyAlign, SankeyLayout};
 use super::draw_primitives::{DrawChartLine, DrawTriangle, DrawPoint};
 use super::axis_renderer::DrawAxisText;
 use super::animation::{ChartAnimator, EasingType};
@@ -180,434 +181,35 @@ impl SankeyWidget {
     }
 
     fn compute_layout(&mut self) {
-        // Sankey layout algorithm with sankeyJustify alignment
-        // Key: node heights are proportional to ABSOLUTE flow, not relative to layer
-        // This creates gaps for bypass traffic
-
-        let n = self.nodes.len();
-        if n == 0 { return; }
-
-        // Build adjacency lists
-        let mut source_links: Vec<Vec<usize>> = vec![Vec::new(); n]; // outgoing
-        let mut target_links: Vec<Vec<usize>> = vec![Vec::new(); n]; // incoming
-
-        for (link_idx, link) in self.links.iter().enumerate() {
-            source_links[link.source].push(link_idx);
-            target_links[link.target].push(link_idx);
-        }
-
-        // Calculate incoming and outgoing totals
-        let mut incoming: Vec<f64> = vec![0.0; n];
-        let mut outgoing: Vec<f64> = vec![0.0; n];
-
-        for link in &self.links {
-            outgoing[link.source] += link.value;
-            incoming[link.target] += link.value;
-        }
-
-        // Compute depths using two passes:
-        // Pass 1: BFS from true sources (nodes with no incoming)
-        // Pass 2: Adjust source nodes to align with their targets
-        let mut depths: Vec<i32> = vec![-1; n];
-
-        // Find true sources (no incoming links) - temporarily set to 0
-        for i in 0..n {
-            if target_links[i].is_empty() {
-                depths[i] = 0;
-            }
-        }
-
-        // BFS to compute depths for non-source nodes
-        let mut changed = true;
-        let mut iterations = 0;
-        while changed && iterations < n * 2 {
-            changed = false;
-            iterations += 1;
-            for i in 0..n {
-                if !target_links[i].is_empty() && depths[i] < 0 {
-                    // Find max depth of sources
-                    let mut max_source_depth: i32 = -1;
-                    let mut all_sources_computed = true;
-
-                    for &link_idx in &target_links[i] {
-                        let source = self.links[link_idx].source;
-                        if depths[source] >= 0 {
-                            max_source_depth = max_source_depth.max(depths[source]);
-                        } else {
-                            all_sources_computed = false;
-                        }
-                    }
-
-                    if all_sources_computed && max_source_depth >= 0 {
-                        depths[i] = max_source_depth + 1;
-                        changed = true;
-                    }
-                }
-            }
-        }
-
-        // Handle any remaining uncomputed nodes
-        for i in 0..n {
-            if depths[i] < 0 {
-                depths[i] = 0;
-            }
-        }
-
-        // Pass 2: Adjust source nodes to be one layer before their minimum target
-        // This ensures nodes like Converse align with NIKE Brand (both feed Revenues)
-        for i in 0..n {
-            if target_links[i].is_empty() && !source_links[i].is_empty() {
-                // This is a source node with outgoing links
-                // Find the minimum depth of its targets
-                let mut min_target_depth = i32::MAX;
-                for &link_idx in &source_links[i] {
-                    let target = self.links[link_idx].target;
-                    if depths[target] < min_target_depth {
-                        min_target_depth = depths[target];
-                    }
-                }
-                if min_target_depth > 0 && min_target_depth < i32::MAX {
-                    // Place this source one layer before its earliest target
-                    depths[i] = min_target_depth - 1;
-                }
-            }
-        }
-
-        // Normalize depths so minimum is 0
-        let min_depth = depths.iter().copied().min().unwrap_or(0);
-        if min_depth < 0 {
-            for d in &mut depths {
-                *d -= min_depth;
-            }
-        }
-
-        // Node value calculation:
-        // - For sink nodes (no outgoing): use total incoming traffic
-        // - For source nodes (no incoming): use total outgoing traffic
-        // - For intermediate nodes: use max(incoming, outgoing)
-        for i in 0..n {
-            let is_sink = source_links[i].is_empty();
-            let is_source = target_links[i].is_empty();
-
-            let node_value = if is_sink {
-                // Sink nodes: size = total incoming traffic
-                incoming[i]
-            } else if is_source {
-                // Source nodes: size = total outgoing traffic
-                outgoing[i]
-            } else {
-                // Intermediate nodes: size = max flow through
-                incoming[i].max(outgoing[i])
-            };
-
-            self.nodes[i].value = if node_value > 0.0 { node_value } else { self.nodes[i].value };
-        }
-
-        // SANKEY JUSTIFY: Move sinks to rightmost layer, BUT keep sinks that receive
-        // from a single source layer at that layer (like Interest expense from Gross profit)
-        // Sinks receiving from multiple layers (like Losses) go to rightmost
-        let max_depth = depths.iter().copied().max().unwrap_or(0);
-
-        for i in 0..n {
-            if source_links[i].is_empty() && self.nodes[i].value > 0.0 {
-                // This is a sink - check how many different source layers it has
-                let source_layers: std::collections::HashSet<i32> = target_links[i].iter()
-                    .map(|&link_idx| depths[self.links[link_idx].source])
-                    .collect();
-
-                // If sink receives from multiple layers, move to rightmost (like Losses)
-                // If sink receives from single layer, keep it there (like Interest expense)
-                if source_layers.len() > 1 {
-                    depths[i] = max_depth;
-                }
-                // Otherwise, sink stays at its natural depth (max source depth + 1)
-            }
-        }
-
-        // Assign layers to nodes
-        for i in 0..n {
-            self.nodes[i].layer = depths[i] as usize;
-        }
-
-        let max_layer = self.nodes.iter().map(|n| n.layer).max().unwrap_or(0);
-
-        // Node padding as fixed ratio
-        let node_padding = 0.012; // Small fixed padding between nodes
-
-        // D3-style: compute global scale factor based on densest layer
-        // ky = min over all layers of: (available_height - padding) / layer_total_value
-        let mut ky = f64::MAX;
-        for layer in 0..=max_layer {
-            let layer_nodes: Vec<usize> = self.nodes.iter()
-                .enumerate()
-                .filter(|(_, nd)| nd.layer == layer && nd.value > 0.0 && !nd.name.is_empty())
-                .map(|(i, _)| i)
-                .collect();
-
-            if layer_nodes.is_empty() { continue; }
-
-            let layer_total: f64 = layer_nodes.iter().map(|&i| self.nodes[i].value).sum();
-            let num_gaps = if layer_nodes.len() > 1 { layer_nodes.len() - 1 } else { 0 };
-            let available = 1.0 - (node_padding * num_gaps as f64);
-
-            if layer_total > 0.0 {
-                let layer_ky = available / layer_total;
-                if layer_ky < ky {
-                    ky = layer_ky;
-                }
-            }
-        }
-
-        if ky == f64::MAX || ky <= 0.0 {
-            ky = 0.001; // Fallback
-        }
-
-        // Apply global scale to all node heights
-        for i in 0..n {
-            if self.nodes[i].value > 0.0 && !self.nodes[i].name.is_empty() {
-                self.nodes[i].height = self.nodes[i].value * ky;
-            } else {
-                self.nodes[i].height = 0.0;
-            }
-        }
-
-        // Group nodes by layer
-        let mut layers: Vec<Vec<usize>> = vec![Vec::new(); max_layer + 1];
-        for i in 0..n {
-            if self.nodes[i].value > 0.0 && !self.nodes[i].name.is_empty() {
-                layers[self.nodes[i].layer].push(i);
-            }
-        }
-
-        // Initial vertical positioning - stack nodes in each layer
-        for layer in 0..=max_layer {
-            self.initialize_layer_positions(&layers[layer], node_padding);
-        }
-
-        // D3-style relaxation with alpha decay
-        let iterations = 32;
-        for iteration in 0..iterations {
-            // Alpha decreases from 1.0 to near 0
-            let alpha = 1.0 - (iteration as f64 / iterations as f64);
-
-            // Left-to-right: move nodes toward weighted center of sources
-            for layer in 1..=max_layer {
-                self.relax_left_to_right(&layers[layer], alpha);
-                self.resolve_collisions(&mut layers[layer], node_padding);
-            }
-
-            // Right-to-left: move nodes toward weighted center of targets
-            for layer in (0..max_layer).rev() {
-                self.relax_right_to_left(&layers[layer], alpha);
-                self.resolve_collisions(&mut layers[layer], node_padding);
-            }
-        }
-
-        // Compute link positions
-        self.compute_link_positions(&incoming, &outgoing);
-    }
-
-    /// Initialize layer positions - stack nodes from top
-    fn initialize_layer_positions(&mut self, layer_nodes: &[usize], node_padding: f64) {
-        if layer_nodes.is_empty() { return; }
-
-        // Calculate total height this layer needs
-        let total_height: f64 = layer_nodes.iter().map(|&i| self.nodes[i].height).sum();
-        let num_gaps = if layer_nodes.len() > 1 { layer_nodes.len() - 1 } else { 0 };
-        let total_with_padding = total_height + node_padding * num_gaps as f64;
-
-        // Center the layer vertically
-        let start_y = (1.0 - total_with_padding) / 2.0;
-        let start_y = start_y.max(0.0);
-
-        let mut y = start_y;
-        for &idx in layer_nodes {
-            self.nodes[idx].y = y;
-            y += self.nodes[idx].height + node_padding;
-        }
-    }
-
-    /// D3-style left-to-right relaxation: move nodes toward weighted center of sources
-    fn relax_left_to_right(&mut self, layer_nodes: &[usize], alpha: f64) {
-        for &idx in layer_nodes {
-            // Get weighted center of source nodes (incoming links)
-            let mut weighted_sum = 0.0;
-            let mut total_weight = 0.0;
-
-            for link in &self.links {
-                if link.target == idx {
-                    let source = &self.nodes[link.source];
-                    // Weighted by link value, position is center of source node
-                    weighted_sum += (source.y + source.height / 2.0) * link.value;
-                    total_weight += link.value;
-                }
-            }
-
-            if total_weight > 0.0 {
-                let target_center = weighted_sum / total_weight;
-                let current_center = self.nodes[idx].y + self.nodes[idx].height / 2.0;
-                // Move toward target, scaled by alpha
-                let delta = (target_center - current_center) * alpha;
-                self.nodes[idx].y += delta;
-            }
-        }
-    }
-
-    /// D3-style right-to-left relaxation: move nodes toward weighted center of targets
-    fn relax_right_to_left(&mut self, layer_nodes: &[usize], alpha: f64) {
-        for &idx in layer_nodes {
-            // Get weighted center of target nodes (outgoing links)
-            let mut weighted_sum = 0.0;
-            let mut total_weight = 0.0;
-
-            for link in &self.links {
-                if link.source == idx {
-                    let target = &self.nodes[link.target];
-                    // Weighted by link value, position is center of target node
-                    weighted_sum += (target.y + target.height / 2.0) * link.value;
-                    total_weight += link.value;
-                }
-            }
-
-            if total_weight > 0.0 {
-                let target_center = weighted_sum / total_weight;
-                let current_center = self.nodes[idx].y + self.nodes[idx].height / 2.0;
-                // Move toward target, scaled by alpha
-                let delta = (target_center - current_center) * alpha;
-                self.nodes[idx].y += delta;
-            }
-        }
-    }
-
-    /// D3-style collision resolution: sort by Y, push apart, constrain to bounds
-    fn resolve_collisions(&mut self, layer_nodes: &mut Vec<usize>, node_padding: f64) {
-        if layer_nodes.is_empty() { return; }
-
-        // Sort by current Y position
-        layer_nodes.sort_by(|&a, &b| {
-            self.nodes[a].y.partial_cmp(&self.nodes[b].y)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        // Push overlapping nodes downward
-        let mut y0 = 0.0;
-        for &idx in layer_nodes.iter() {
-            let dy = y0 - self.nodes[idx].y;
-            if dy > 0.0 {
-                self.nodes[idx].y += dy;
-            }
-            y0 = self.nodes[idx].y + self.nodes[idx].height + node_padding;
-        }
-
-        // If we exceeded the bottom bound, push back up
-        if let Some(&last_idx) = layer_nodes.last() {
-            let overflow = y0 - node_padding - 1.0;
-            if overflow > 0.0 {
-                // Push last node up
-                self.nodes[last_idx].y -= overflow;
-                y0 = self.nodes[last_idx].y;
-
-                // Cascade upward: push overlapping nodes up
-                for i in (0..layer_nodes.len() - 1).rev() {
-                    let idx = layer_nodes[i];
-                    let next_idx = layer_nodes[i + 1];
-                    let dy = self.nodes[idx].y + self.nodes[idx].height + node_padding - self.nodes[next_idx].y;
-                    if dy > 0.0 {
-                        self.nodes[idx].y -= dy;
-                    }
-                }
-            }
-        }
-
-        // Ensure no node goes above y=0
-        let mut y0 = 0.0;
-        for &idx in layer_nodes.iter() {
-            if self.nodes[idx].y < y0 {
-                self.nodes[idx].y = y0;
-            }
-            y0 = self.nodes[idx].y + self.nodes[idx].height + node_padding;
-        }
-    }
-
-    fn compute_link_positions(&mut self, _incoming: &[f64], _outgoing: &[f64]) {
-        let n = self.nodes.len();
-
-        // Sort links by source position then target position
-        let mut link_indices: Vec<usize> = (0..self.links.len()).collect();
-        link_indices.sort_by(|&a, &b| {
-            let link_a = &self.links[a];
-            let link_b = &self.links[b];
-            let a_src = &self.nodes[link_a.source];
-            let b_src = &self.nodes[link_b.source];
-            let a_tgt = &self.nodes[link_a.target];
-            let b_tgt = &self.nodes[link_b.target];
-
-            a_src.layer.cmp(&b_src.layer)
-                .then(a_src.y.partial_cmp(&b_src.y).unwrap_or(std::cmp::Ordering::Equal))
-                .then(a_tgt.y.partial_cmp(&b_tgt.y).unwrap_or(std::cmp::Ordering::Equal))
-        });
-
-        let mut source_offsets: Vec<f64> = vec![0.0; n];
-        let mut target_offsets: Vec<f64> = vec![0.0; n];
-
-        for &i in &link_indices {
-            let source_idx = self.links[i].source;
-            let target_idx = self.links[i].target;
-            let link_value = self.links[i].value;
-
-            // Use node.value for consistent scaling (same value used for height computation)
-            let source_value = self.nodes[source_idx].value;
-            let target_value = self.nodes[target_idx].value;
-
-            self.links[i].source_y = self.nodes[source_idx].y + source_offsets[source_idx];
-            self.links[i].target_y = self.nodes[target_idx].y + target_offsets[target_idx];
-
-            if source_value > 0.0 {
-                source_offsets[source_idx] += link_value / source_value * self.nodes[source_idx].height;
-            }
-            if target_value > 0.0 {
-                target_offsets[target_idx] += link_value / target_value * self.nodes[target_idx].height;
-            }
-        }
-    }
-
-    /// Calculate weighted position of a node based on its source connections
-    fn get_weighted_source_position(&self, node_idx: usize) -> f64 {
-        let mut total_weight = 0.0;
-        let mut weighted_pos = 0.0;
-
-        for link in &self.links {
-            if link.target == node_idx {
-                let source = &self.nodes[link.source];
-                weighted_pos += (source.y + source.height / 2.0) * link.value;
-                total_weight += link.value;
-            }
-        }
-
-        if total_weight > 0.0 {
-            weighted_pos / total_weight
-        } else {
-            0.5
-        }
-    }
-
-    /// Calculate weighted position of a node based on its target connections
-    fn get_weighted_target_position(&self, node_idx: usize) -> f64 {
-        let mut total_weight = 0.0;
-        let mut weighted_pos = 0.0;
+        // Layout in normalized 0..1 coordinates; draw_sankey scales to the chart rect
+        let names: Vec<&str> = self.nodes.iter().map(|n| n.name.as_str()).collect();
+        let links: Vec<(usize, usize, f64)> = self.links.iter()
+            .map(|l| (l.source, l.target, l.value))
+            .collect();
+
+        let graph = match SankeyLayout::new()
+            .size(1.0, 1.0)
+            .node_width(0.0)
+            .node_padding(0.012)
+            .align(SankeyAlign::Justify)
+            .iterations(32)
+            .layout(&names, &links)
+        {
+            Ok(graph) => graph,
+            Err(_) => return,
+        };
 
-        for link in &self.links {
-            if link.source == node_idx {
-                let target = &self.nodes[link.target];
-                weighted_pos += (target.y + target.height / 2.0) * link.value;
-                total_weight += link.value;
+        for (node, laid_out) in self.nodes.iter_mut().zip(&graph.nodes) {
+            node.layer = laid_out.layer;
+            if laid_out.value > 0.0 {
+                node.value = laid_out.value;
             }
+            node.y = laid_out.y0;
+            node.height = laid_out.y1 - laid_out.y0;
         }
-
-        if total_weight > 0.0 {
-            weighted_pos / total_weight
-        } else {
-            0.5
+        for (link, laid_out) in self.links.iter_mut().zip(&graph.links) {
+            link.source_y = laid_out.y0 - laid_out.width / 2.0;
+            link.target_y = laid_out.y1 - laid_out.width / 2.0;
         }
     }
 
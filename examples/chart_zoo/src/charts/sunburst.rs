@@ -136,7 +136,7 @@ impl SunburstWidget {
         let radius = 464.0;
 
         // Create partition layout matching D3: size([2 * Math.PI, radius])
-        let layout = PartitionLayout::new().size(2.0 * PI, radius);
+        let layout = PartitionLayout::new().radial(radius);
         self.partition = Some(layout.layout(&root));
 
         // Generate rainbow colors for top-level children
@@ -144,311 +144,8 @@ impl SunburstWidget {
     }
 
     fn build_flare_hierarchy(&self) -> HierarchyNode<String> {
-        let mut root = HierarchyNode::new("flare".to_string(), 0.0);
-
-        // analytics
-        let mut analytics = HierarchyNode::new("analytics".to_string(), 0.0);
-        let mut cluster = HierarchyNode::new("cluster".to_string(), 0.0);
-        cluster.add_child(HierarchyNode::new("AgglomerativeCluster".to_string(), 3938.0));
-        cluster.add_child(HierarchyNode::new("CommunityStructure".to_string(), 3812.0));
-        cluster.add_child(HierarchyNode::new("HierarchicalCluster".to_string(), 6714.0));
-        cluster.add_child(HierarchyNode::new("MergeEdge".to_string(), 743.0));
-        analytics.add_child(cluster);
-        let mut graph = HierarchyNode::new("graph".to_string(), 0.0);
-        graph.add_child(HierarchyNode::new("BetweennessCentrality".to_string(), 3534.0));
-        graph.add_child(HierarchyNode::new("LinkDistance".to_string(), 5731.0));
-        graph.add_child(HierarchyNode::new("MaxFlowMinCut".to_string(), 7840.0));
-        graph.add_child(HierarchyNode::new("ShortestPaths".to_string(), 5914.0));
-        graph.add_child(HierarchyNode::new("SpanningTree".to_string(), 3416.0));
-        analytics.add_child(graph);
-        let mut optimization = HierarchyNode::new("optimization".to_string(), 0.0);
-        optimization.add_child(HierarchyNode::new("AspectRatioBanker".to_string(), 7074.0));
-        analytics.add_child(optimization);
-        root.add_child(analytics);
-
-        // animate
-        let mut animate = HierarchyNode::new("animate".to_string(), 0.0);
-        animate.add_child(HierarchyNode::new("Easing".to_string(), 17010.0));
-        animate.add_child(HierarchyNode::new("FunctionSequence".to_string(), 5842.0));
-        let mut interpolate = HierarchyNode::new("interpolate".to_string(), 0.0);
-        interpolate.add_child(HierarchyNode::new("ArrayInterpolator".to_string(), 1983.0));
-        interpolate.add_child(HierarchyNode::new("ColorInterpolator".to_string(), 2047.0));
-        interpolate.add_child(HierarchyNode::new("DateInterpolator".to_string(), 1375.0));
-        interpolate.add_child(HierarchyNode::new("Interpolator".to_string(), 8746.0));
-        interpolate.add_child(HierarchyNode::new("MatrixInterpolator".to_string(), 2202.0));
-        interpolate.add_child(HierarchyNode::new("NumberInterpolator".to_string(), 1382.0));
-        interpolate.add_child(HierarchyNode::new("ObjectInterpolator".to_string(), 1629.0));
-        interpolate.add_child(HierarchyNode::new("PointInterpolator".to_string(), 1675.0));
-        interpolate.add_child(HierarchyNode::new("RectangleInterpolator".to_string(), 2042.0));
-        animate.add_child(interpolate);
-        animate.add_child(HierarchyNode::new("ISchedulable".to_string(), 1041.0));
-        animate.add_child(HierarchyNode::new("Parallel".to_string(), 5176.0));
-        animate.add_child(HierarchyNode::new("Pause".to_string(), 449.0));
-        animate.add_child(HierarchyNode::new("Scheduler".to_string(), 5593.0));
-        animate.add_child(HierarchyNode::new("Sequence".to_string(), 5534.0));
-        animate.add_child(HierarchyNode::new("Transition".to_string(), 9201.0));
-        animate.add_child(HierarchyNode::new("Transitioner".to_string(), 19975.0));
-        animate.add_child(HierarchyNode::new("TransitionEvent".to_string(), 1116.0));
-        animate.add_child(HierarchyNode::new("Tween".to_string(), 6006.0));
-        root.add_child(animate);
-
-        // data
-        let mut data = HierarchyNode::new("data".to_string(), 0.0);
-        let mut converters = HierarchyNode::new("converters".to_string(), 0.0);
-        converters.add_child(HierarchyNode::new("Converters".to_string(), 721.0));
-        converters.add_child(HierarchyNode::new("DelimitedTextConverter".to_string(), 4294.0));
-        converters.add_child(HierarchyNode::new("GraphMLConverter".to_string(), 9800.0));
-        converters.add_child(HierarchyNode::new("IDataConverter".to_string(), 1314.0));
-        converters.add_child(HierarchyNode::new("JSONConverter".to_string(), 2220.0));
-        data.add_child(converters);
-        data.add_child(HierarchyNode::new("DataField".to_string(), 1759.0));
-        data.add_child(HierarchyNode::new("DataSchema".to_string(), 2165.0));
-        data.add_child(HierarchyNode::new("DataSet".to_string(), 586.0));
-        data.add_child(HierarchyNode::new("DataSource".to_string(), 3331.0));
-        data.add_child(HierarchyNode::new("DataTable".to_string(), 772.0));
-        data.add_child(HierarchyNode::new("DataUtil".to_string(), 3322.0));
-        root.add_child(data);
-
-        // display
-        let mut display = HierarchyNode::new("display".to_string(), 0.0);
-        display.add_child(HierarchyNode::new("DirtySprite".to_string(), 8833.0));
-        display.add_child(HierarchyNode::new("LineSprite".to_string(), 1732.0));
-        display.add_child(HierarchyNode::new("RectSprite".to_string(), 3623.0));
-        display.add_child(HierarchyNode::new("TextSprite".to_string(), 10066.0));
-        root.add_child(display);
-
-        // flex
-        let mut flex = HierarchyNode::new("flex".to_string(), 0.0);
-        flex.add_child(HierarchyNode::new("FlareVis".to_string(), 4116.0));
-        root.add_child(flex);
-
-        // physics
-        let mut physics = HierarchyNode::new("physics".to_string(), 0.0);
-        physics.add_child(HierarchyNode::new("DragForce".to_string(), 1082.0));
-        physics.add_child(HierarchyNode::new("GravityForce".to_string(), 1336.0));
-        physics.add_child(HierarchyNode::new("IForce".to_string(), 319.0));
-        physics.add_child(HierarchyNode::new("NBodyForce".to_string(), 10498.0));
-        physics.add_child(HierarchyNode::new("Particle".to_string(), 2822.0));
-        physics.add_child(HierarchyNode::new("Simulation".to_string(), 9983.0));
-        physics.add_child(HierarchyNode::new("Spring".to_string(), 2213.0));
-        physics.add_child(HierarchyNode::new("SpringForce".to_string(), 1681.0));
-        root.add_child(physics);
-
-        // query
-        let mut query = HierarchyNode::new("query".to_string(), 0.0);
-        query.add_child(HierarchyNode::new("AggregateExpression".to_string(), 1616.0));
-        query.add_child(HierarchyNode::new("And".to_string(), 1027.0));
-        query.add_child(HierarchyNode::new("Arithmetic".to_string(), 3891.0));
-        query.add_child(HierarchyNode::new("Average".to_string(), 891.0));
-        query.add_child(HierarchyNode::new("BinaryExpression".to_string(), 2893.0));
-        query.add_child(HierarchyNode::new("Comparison".to_string(), 5103.0));
-        query.add_child(HierarchyNode::new("CompositeExpression".to_string(), 3677.0));
-        query.add_child(HierarchyNode::new("Count".to_string(), 781.0));
-        query.add_child(HierarchyNode::new("DateUtil".to_string(), 4141.0));
-        query.add_child(HierarchyNode::new("Distinct".to_string(), 933.0));
-        query.add_child(HierarchyNode::new("Expression".to_string(), 5130.0));
-        query.add_child(HierarchyNode::new("ExpressionIterator".to_string(), 3617.0));
-        query.add_child(HierarchyNode::new("Fn".to_string(), 3240.0));
-        query.add_child(HierarchyNode::new("If".to_string(), 2732.0));
-        query.add_child(HierarchyNode::new("IsA".to_string(), 2039.0));
-        query.add_child(HierarchyNode::new("Literal".to_string(), 1214.0));
-        query.add_child(HierarchyNode::new("Match".to_string(), 3748.0));
-        query.add_child(HierarchyNode::new("Maximum".to_string(), 843.0));
-        let mut methods = HierarchyNode::new("methods".to_string(), 0.0);
-        methods.add_child(HierarchyNode::new("add".to_string(), 593.0));
-        methods.add_child(HierarchyNode::new("and".to_string(), 330.0));
-        methods.add_child(HierarchyNode::new("average".to_string(), 287.0));
-        methods.add_child(HierarchyNode::new("count".to_string(), 277.0));
-        methods.add_child(HierarchyNode::new("distinct".to_string(), 292.0));
-        methods.add_child(HierarchyNode::new("div".to_string(), 595.0));
-        methods.add_child(HierarchyNode::new("eq".to_string(), 594.0));
-        methods.add_child(HierarchyNode::new("fn".to_string(), 460.0));
-        methods.add_child(HierarchyNode::new("gt".to_string(), 603.0));
-        methods.add_child(HierarchyNode::new("gte".to_string(), 625.0));
-        methods.add_child(HierarchyNode::new("iff".to_string(), 748.0));
-        methods.add_child(HierarchyNode::new("isa".to_string(), 461.0));
-        methods.add_child(HierarchyNode::new("lt".to_string(), 597.0));
-        methods.add_child(HierarchyNode::new("lte".to_string(), 619.0));
-        methods.add_child(HierarchyNode::new("max".to_string(), 283.0));
-        methods.add_child(HierarchyNode::new("min".to_string(), 283.0));
-        methods.add_child(HierarchyNode::new("mod".to_string(), 591.0));
-        methods.add_child(HierarchyNode::new("mul".to_string(), 603.0));
-        methods.add_child(HierarchyNode::new("neq".to_string(), 599.0));
-        methods.add_child(HierarchyNode::new("not".to_string(), 386.0));
-        methods.add_child(HierarchyNode::new("or".to_string(), 323.0));
-        methods.add_child(HierarchyNode::new("orderby".to_string(), 307.0));
-        methods.add_child(HierarchyNode::new("range".to_string(), 772.0));
-        methods.add_child(HierarchyNode::new("select".to_string(), 296.0));
-        methods.add_child(HierarchyNode::new("stddev".to_string(), 363.0));
-        methods.add_child(HierarchyNode::new("sub".to_string(), 600.0));
-        methods.add_child(HierarchyNode::new("sum".to_string(), 280.0));
-        methods.add_child(HierarchyNode::new("update".to_string(), 307.0));
-        methods.add_child(HierarchyNode::new("variance".to_string(), 335.0));
-        methods.add_child(HierarchyNode::new("where".to_string(), 299.0));
-        methods.add_child(HierarchyNode::new("xor".to_string(), 354.0));
-        methods.add_child(HierarchyNode::new("_".to_string(), 264.0));
-        query.add_child(methods);
-        query.add_child(HierarchyNode::new("Minimum".to_string(), 843.0));
-        query.add_child(HierarchyNode::new("Not".to_string(), 1554.0));
-        query.add_child(HierarchyNode::new("Or".to_string(), 970.0));
-        query.add_child(HierarchyNode::new("Query".to_string(), 13896.0));
-        query.add_child(HierarchyNode::new("Range".to_string(), 1594.0));
-        query.add_child(HierarchyNode::new("StringUtil".to_string(), 4130.0));
-        query.add_child(HierarchyNode::new("Sum".to_string(), 791.0));
-        query.add_child(HierarchyNode::new("Variable".to_string(), 1124.0));
-        query.add_child(HierarchyNode::new("Variance".to_string(), 1876.0));
-        query.add_child(HierarchyNode::new("Xor".to_string(), 1101.0));
-        root.add_child(query);
-
-        // scale
-        let mut scale = HierarchyNode::new("scale".to_string(), 0.0);
-        scale.add_child(HierarchyNode::new("IScaleMap".to_string(), 2105.0));
-        scale.add_child(HierarchyNode::new("LinearScale".to_string(), 1316.0));
-        scale.add_child(HierarchyNode::new("LogScale".to_string(), 3151.0));
-        scale.add_child(HierarchyNode::new("OrdinalScale".to_string(), 3770.0));
-        scale.add_child(HierarchyNode::new("QuantileScale".to_string(), 2435.0));
-        scale.add_child(HierarchyNode::new("QuantitativeScale".to_string(), 4839.0));
-        scale.add_child(HierarchyNode::new("RootScale".to_string(), 1756.0));
-        scale.add_child(HierarchyNode::new("Scale".to_string(), 4268.0));
-        scale.add_child(HierarchyNode::new("ScaleType".to_string(), 1821.0));
-        scale.add_child(HierarchyNode::new("TimeScale".to_string(), 5833.0));
-        root.add_child(scale);
-
-        // util
-        let mut util = HierarchyNode::new("util".to_string(), 0.0);
-        util.add_child(HierarchyNode::new("Arrays".to_string(), 8258.0));
-        util.add_child(HierarchyNode::new("Colors".to_string(), 10001.0));
-        util.add_child(HierarchyNode::new("Dates".to_string(), 8217.0));
-        util.add_child(HierarchyNode::new("Displays".to_string(), 12555.0));
-        util.add_child(HierarchyNode::new("Filter".to_string(), 2324.0));
-        util.add_child(HierarchyNode::new("Geometry".to_string(), 10993.0));
-        let mut heap = HierarchyNode::new("heap".to_string(), 0.0);
-        heap.add_child(HierarchyNode::new("FibonacciHeap".to_string(), 9354.0));
-        heap.add_child(HierarchyNode::new("HeapNode".to_string(), 1233.0));
-        util.add_child(heap);
-        util.add_child(HierarchyNode::new("IEvaluable".to_string(), 335.0));
-        util.add_child(HierarchyNode::new("IPredicate".to_string(), 383.0));
-        util.add_child(HierarchyNode::new("IValueProxy".to_string(), 874.0));
-        let mut math = HierarchyNode::new("math".to_string(), 0.0);
-        math.add_child(HierarchyNode::new("DenseMatrix".to_string(), 3165.0));
-        math.add_child(HierarchyNode::new("IMatrix".to_string(), 2815.0));
-        math.add_child(HierarchyNode::new("SparseMatrix".to_string(), 3366.0));
-        util.add_child(math);
-        util.add_child(HierarchyNode::new("Maths".to_string(), 17705.0));
-        util.add_child(HierarchyNode::new("Orientation".to_string(), 1486.0));
-        let mut palette = HierarchyNode::new("palette".to_string(), 0.0);
-        palette.add_child(HierarchyNode::new("ColorPalette".to_string(), 6367.0));
-        palette.add_child(HierarchyNode::new("Palette".to_string(), 1229.0));
-        palette.add_child(HierarchyNode::new("ShapePalette".to_string(), 2059.0));
-        palette.add_child(HierarchyNode::new("SizePalette".to_string(), 2291.0));
-        util.add_child(palette);
-        util.add_child(HierarchyNode::new("Property".to_string(), 5559.0));
-        util.add_child(HierarchyNode::new("Shapes".to_string(), 19118.0));
-        util.add_child(HierarchyNode::new("Sort".to_string(), 6887.0));
-        util.add_child(HierarchyNode::new("Stats".to_string(), 6557.0));
-        util.add_child(HierarchyNode::new("Strings".to_string(), 22026.0));
-        root.add_child(util);
-
-        // vis
-        let mut vis = HierarchyNode::new("vis".to_string(), 0.0);
-        let mut axis = HierarchyNode::new("axis".to_string(), 0.0);
-        axis.add_child(HierarchyNode::new("Axes".to_string(), 1302.0));
-        axis.add_child(HierarchyNode::new("Axis".to_string(), 24593.0));
-        axis.add_child(HierarchyNode::new("AxisGridLine".to_string(), 652.0));
-        axis.add_child(HierarchyNode::new("AxisLabel".to_string(), 636.0));
-        axis.add_child(HierarchyNode::new("CartesianAxes".to_string(), 6703.0));
-        vis.add_child(axis);
-        let mut controls = HierarchyNode::new("controls".to_string(), 0.0);
-        controls.add_child(HierarchyNode::new("AnchorControl".to_string(), 2138.0));
-        controls.add_child(HierarchyNode::new("ClickControl".to_string(), 3824.0));
-        controls.add_child(HierarchyNode::new("Control".to_string(), 1353.0));
-        controls.add_child(HierarchyNode::new("ControlList".to_string(), 4665.0));
-        controls.add_child(HierarchyNode::new("DragControl".to_string(), 2649.0));
-        controls.add_child(HierarchyNode::new("ExpandControl".to_string(), 2832.0));
-        controls.add_child(HierarchyNode::new("HoverControl".to_string(), 4896.0));
-        controls.add_child(HierarchyNode::new("IControl".to_string(), 763.0));
-        controls.add_child(HierarchyNode::new("PanZoomControl".to_string(), 5222.0));
-        controls.add_child(HierarchyNode::new("SelectionControl".to_string(), 7862.0));
-        controls.add_child(HierarchyNode::new("TooltipControl".to_string(), 8435.0));
-        vis.add_child(controls);
-        let mut vis_data = HierarchyNode::new("data".to_string(), 0.0);
-        vis_data.add_child(HierarchyNode::new("Data".to_string(), 20544.0));
-        vis_data.add_child(HierarchyNode::new("DataList".to_string(), 19788.0));
-        vis_data.add_child(HierarchyNode::new("DataSprite".to_string(), 10349.0));
-        vis_data.add_child(HierarchyNode::new("EdgeSprite".to_string(), 3301.0));
-        vis_data.add_child(HierarchyNode::new("NodeSprite".to_string(), 19382.0));
-        let mut render = HierarchyNode::new("render".to_string(), 0.0);
-        render.add_child(HierarchyNode::new("ArrowType".to_string(), 698.0));
-        render.add_child(HierarchyNode::new("EdgeRenderer".to_string(), 5569.0));
-        render.add_child(HierarchyNode::new("IRenderer".to_string(), 353.0));
-        render.add_child(HierarchyNode::new("ShapeRenderer".to_string(), 2247.0));
-        vis_data.add_child(render);
-        vis_data.add_child(HierarchyNode::new("ScaleBinding".to_string(), 11275.0));
-        vis_data.add_child(HierarchyNode::new("Tree".to_string(), 7147.0));
-        vis_data.add_child(HierarchyNode::new("TreeBuilder".to_string(), 9930.0));
-        vis.add_child(vis_data);
-        let mut events = HierarchyNode::new("events".to_string(), 0.0);
-        events.add_child(HierarchyNode::new("DataEvent".to_string(), 2313.0));
-        events.add_child(HierarchyNode::new("SelectionEvent".to_string(), 1880.0));
-        events.add_child(HierarchyNode::new("TooltipEvent".to_string(), 1701.0));
-        events.add_child(HierarchyNode::new("VisualizationEvent".to_string(), 1117.0));
-        vis.add_child(events);
-        let mut legend = HierarchyNode::new("legend".to_string(), 0.0);
-        legend.add_child(HierarchyNode::new("Legend".to_string(), 20859.0));
-        legend.add_child(HierarchyNode::new("LegendItem".to_string(), 4614.0));
-        legend.add_child(HierarchyNode::new("LegendRange".to_string(), 10530.0));
-        vis.add_child(legend);
-        let mut operator = HierarchyNode::new("operator".to_string(), 0.0);
-        let mut distortion = HierarchyNode::new("distortion".to_string(), 0.0);
-        distortion.add_child(HierarchyNode::new("BifocalDistortion".to_string(), 4461.0));
-        distortion.add_child(HierarchyNode::new("Distortion".to_string(), 6314.0));
-        distortion.add_child(HierarchyNode::new("FisheyeDistortion".to_string(), 3444.0));
-        operator.add_child(distortion);
-        let mut encoder = HierarchyNode::new("encoder".to_string(), 0.0);
-        encoder.add_child(HierarchyNode::new("ColorEncoder".to_string(), 3179.0));
-        encoder.add_child(HierarchyNode::new("Encoder".to_string(), 4060.0));
-        encoder.add_child(HierarchyNode::new("PropertyEncoder".to_string(), 4138.0));
-        encoder.add_child(HierarchyNode::new("ShapeEncoder".to_string(), 1690.0));
-        encoder.add_child(HierarchyNode::new("SizeEncoder".to_string(), 1830.0));
-        operator.add_child(encoder);
-        let mut filter = HierarchyNode::new("filter".to_string(), 0.0);
-        filter.add_child(HierarchyNode::new("FisheyeTreeFilter".to_string(), 5219.0));
-        filter.add_child(HierarchyNode::new("GraphDistanceFilter".to_string(), 3165.0));
-        filter.add_child(HierarchyNode::new("VisibilityFilter".to_string(), 3509.0));
-        operator.add_child(filter);
-        operator.add_child(HierarchyNode::new("IOperator".to_string(), 1286.0));
-        let mut label = HierarchyNode::new("label".to_string(), 0.0);
-        label.add_child(HierarchyNode::new("Labeler".to_string(), 9956.0));
-        label.add_child(HierarchyNode::new("RadialLabeler".to_string(), 3899.0));
-        label.add_child(HierarchyNode::new("StackedAreaLabeler".to_string(), 3202.0));
-        operator.add_child(label);
-        let mut layout = HierarchyNode::new("layout".to_string(), 0.0);
-        layout.add_child(HierarchyNode::new("AxisLayout".to_string(), 6725.0));
-        layout.add_child(HierarchyNode::new("BundledEdgeRouter".to_string(), 3727.0));
-        layout.add_child(HierarchyNode::new("CircleLayout".to_string(), 9317.0));
-        layout.add_child(HierarchyNode::new("CirclePackingLayout".to_string(), 12003.0));
-        layout.add_child(HierarchyNode::new("DendrogramLayout".to_string(), 4853.0));
-        layout.add_child(HierarchyNode::new("ForceDirectedLayout".to_string(), 8411.0));
-        layout.add_child(HierarchyNode::new("IcicleTreeLayout".to_string(), 4864.0));
-        layout.add_child(HierarchyNode::new("IndentedTreeLayout".to_string(), 3174.0));
-        layout.add_child(HierarchyNode::new("Layout".to_string(), 7881.0));
-        layout.add_child(HierarchyNode::new("NodeLinkTreeLayout".to_string(), 12870.0));
-        layout.add_child(HierarchyNode::new("PieLayout".to_string(), 2728.0));
-        layout.add_child(HierarchyNode::new("RadialTreeLayout".to_string(), 12348.0));
-        layout.add_child(HierarchyNode::new("RandomLayout".to_string(), 870.0));
-        layout.add_child(HierarchyNode::new("StackedAreaLayout".to_string(), 9121.0));
-        layout.add_child(HierarchyNode::new("TreeMapLayout".to_string(), 9191.0));
-        operator.add_child(layout);
-        operator.add_child(HierarchyNode::new("Operator".to_string(), 2490.0));
-        operator.add_child(HierarchyNode::new("OperatorList".to_string(), 5248.0));
-        operator.add_child(HierarchyNode::new("OperatorSequence".to_string(), 4190.0));
-        operator.add_child(HierarchyNode::new("OperatorSwitch".to_string(), 2581.0));
-        operator.add_child(HierarchyNode::new("SortOperator".to_string(), 2023.0));
-        vis.add_child(operator);
-        vis.add_child(HierarchyNode::new("Visualization".to_string(), 16540.0));
-        root.add_child(vis);
-
-        root
+        HierarchyNode::from_path_values(FLARE.iter().copied(), '/')
+            .expect("flare paths are well formed")
     }
 
     /// D3's interpolateRainbow using cubehelix color space
@@ -676,3 +373,227 @@ impl SunburstWidgetRef {
         }
     }
 }
+
+/// Leaves of flare-2.json as (path, size), in document order
+const FLARE: &[(&str, f64)] = &[
+    ("flare/analytics/cluster/AgglomerativeCluster", 3938.0),
+    ("flare/analytics/cluster/CommunityStructure", 3812.0),
+    ("flare/analytics/cluster/HierarchicalCluster", 6714.0),
+    ("flare/analytics/cluster/MergeEdge", 743.0),
+    ("flare/analytics/graph/BetweennessCentrality", 3534.0),
+    ("flare/analytics/graph/LinkDistance", 5731.0),
+    ("flare/analytics/graph/MaxFlowMinCut", 7840.0),
+    ("flare/analytics/graph/ShortestPaths", 5914.0),
+    ("flare/analytics/graph/SpanningTree", 3416.0),
+    ("flare/analytics/optimization/AspectRatioBanker", 7074.0),
+    ("flare/animate/Easing", 17010.0),
+    ("flare/animate/FunctionSequence", 5842.0),
+    ("flare/animate/interpolate/ArrayInterpolator", 1983.0),
+    ("flare/animate/interpolate/ColorInterpolator", 2047.0),
+    ("flare/animate/interpolate/DateInterpolator", 1375.0),
+    ("flare/animate/interpolate/Interpolator", 8746.0),
+    ("flare/animate/interpolate/MatrixInterpolator", 2202.0),
+    ("flare/animate/interpolate/NumberInterpolator", 1382.0),
+    ("flare/animate/interpolate/ObjectInterpolator", 1629.0),
+    ("flare/animate/interpolate/PointInterpolator", 1675.0),
+    ("flare/animate/interpolate/RectangleInterpolator", 2042.0),
+    ("flare/animate/ISchedulable", 1041.0),
+    ("flare/animate/Parallel", 5176.0),
+    ("flare/animate/Pause", 449.0),
+    ("flare/animate/Scheduler", 5593.0),
+    ("flare/animate/Sequence", 5534.0),
+    ("flare/animate/Transition", 9201.0),
+    ("flare/animate/Transitioner", 19975.0),
+    ("flare/animate/TransitionEvent", 1116.0),
+    ("flare/animate/Tween", 6006.0),
+    ("flare/data/converters/Converters", 721.0),
+    ("flare/data/converters/DelimitedTextConverter", 4294.0),
+    ("flare/data/converters/GraphMLConverter", 9800.0),
+    ("flare/data/converters/IDataConverter", 1314.0),
+    ("flare/data/converters/JSONConverter", 2220.0),
+    ("flare/data/DataField", 1759.0),
+    ("flare/data/DataSchema", 2165.0),
+    ("flare/data/DataSet", 586.0),
+    ("flare/data/DataSource", 3331.0),
+    ("flare/data/DataTable", 772.0),
+    ("flare/data/DataUtil", 3322.0),
+    ("flare/display/DirtySprite", 8833.0),
+    ("flare/display/LineSprite", 1732.0),
+    ("flare/display/RectSprite", 3623.0),
+    ("flare/display/TextSprite", 10066.0),
+    ("flare/flex/FlareVis", 4116.0),
+    ("flare/physics/DragForce", 1082.0),
+    ("flare/physics/GravityForce", 1336.0),
+    ("flare/physics/IForce", 319.0),
+    ("flare/physics/NBodyForce", 10498.0),
+    ("flare/physics/Particle", 2822.0),
+    ("flare/physics/Simulation", 9983.0),
+    ("flare/physics/Spring", 2213.0),
+    ("flare/physics/SpringForce", 1681.0),
+    ("flare/query/AggregateExpression", 1616.0),
+    ("flare/query/And", 1027.0),
+    ("flare/query/Arithmetic", 3891.0),
+    ("flare/query/Average", 891.0),
+    ("flare/query/BinaryExpression", 2893.0),
+    ("flare/query/Comparison", 5103.0),
+    ("flare/query/CompositeExpression", 3677.0),
+    ("flare/query/Count", 781.0),
+    ("flare/query/DateUtil", 4141.0),
+    ("flare/query/Distinct", 933.0),
+    ("flare/query/Expression", 5130.0),
+    ("flare/query/ExpressionIterator", 3617.0),
+    ("flare/query/Fn", 3240.0),
+    ("flare/query/If", 2732.0),
+    ("flare/query/IsA", 2039.0),
+    ("flare/query/Literal", 1214.0),
+    ("flare/query/Match", 3748.0),
+    ("flare/query/Maximum", 843.0),
+    ("flare/query/methods/add", 593.0),
+    ("flare/query/methods/and", 330.0),
+    ("flare/query/methods/average", 287.0),
+    ("flare/query/methods/count", 277.0),
+    ("flare/query/methods/distinct", 292.0),
+    ("flare/query/methods/div", 595.0),
+    ("flare/query/methods/eq", 594.0),
+    ("flare/query/methods/fn", 460.0),
+    ("flare/query/methods/gt", 603.0),
+    ("flare/query/methods/gte", 625.0),
+    ("flare/query/methods/iff", 748.0),
+    ("flare/query/methods/isa", 461.0),
+    ("flare/query/methods/lt", 597.0),
+    ("flare/query/methods/lte", 619.0),
+    ("flare/query/methods/max", 283.0),
+    ("flare/query/methods/min", 283.0),
+    ("flare/query/methods/mod", 591.0),
+    ("flare/query/methods/mul", 603.0),
+    ("flare/query/methods/neq", 599.0),
+    ("flare/query/methods/not", 386.0),
+    ("flare/query/methods/or", 323.0),
+    ("flare/query/methods/orderby", 307.0),
+    ("flare/query/methods/range", 772.0),
+    ("flare/query/methods/select", 296.0),
+    ("flare/query/methods/stddev", 363.0),
+    ("flare/query/methods/sub", 600.0),
+    ("flare/query/methods/sum", 280.0),
+    ("flare/query/methods/update", 307.0),
+    ("flare/query/methods/variance", 335.0),
+    ("flare/query/methods/where", 299.0),
+    ("flare/query/methods/xor", 354.0),
+    ("flare/query/methods/_", 264.0),
+    ("flare/query/Minimum", 843.0),
+    ("flare/query/Not", 1554.0),
+    ("flare/query/Or", 970.0),
+    ("flare/query/Query", 13896.0),
+    ("flare/query/Range", 1594.0),
+    ("flare/query/StringUtil", 4130.0),
+    ("flare/query/Sum", 791.0),
+    ("flare/query/Variable", 1124.0),
+    ("flare/query/Variance", 1876.0),
+    ("flare/query/Xor", 1101.0),
+    ("flare/scale/IScaleMap", 2105.0),
+    ("flare/scale/LinearScale", 1316.0),
+    ("flare/scale/LogScale", 3151.0),
+    ("flare/scale/OrdinalScale", 3770.0),
+    ("flare/scale/QuantileScale", 2435.0),
+    ("flare/scale/QuantitativeScale", 4839.0),
+    ("flare/scale/RootScale", 1756.0),
+    ("flare/scale/Scale", 4268.0),
+    ("flare/scale/ScaleType", 1821.0),
+    ("flare/scale/TimeScale", 5833.0),
+    ("flare/util/Arrays", 8258.0),
+    ("flare/util/Colors", 10001.0),
+    ("flare/util/Dates", 8217.0),
+    ("flare/util/Displays", 12555.0),
+    ("flare/util/Filter", 2324.0),
+    ("flare/util/Geometry", 10993.0),
+    ("flare/util/heap/FibonacciHeap", 9354.0),
+    ("flare/util/heap/HeapNode", 1233.0),
+    ("flare/util/IEvaluable", 335.0),
+    ("flare/util/IPredicate", 383.0),
+    ("flare/util/IValueProxy", 874.0),
+    ("flare/util/math/DenseMatrix", 3165.0),
+    ("flare/util/math/IMatrix", 2815.0),
+    ("flare/util/math/SparseMatrix", 3366.0),
+    ("flare/util/Maths", 17705.0),
+    ("flare/util/Orientation", 1486.0),
+    ("flare/util/palette/ColorPalette", 6367.0),
+    ("flare/util/palette/Palette", 1229.0),
+    ("flare/util/palette/ShapePalette", 2059.0),
+    ("flare/util/palette/SizePalette", 2291.0),
+    ("flare/util/Property", 5559.0),
+    ("flare/util/Shapes", 19118.0),
+    ("flare/util/Sort", 6887.0),
+    ("flare/util/Stats", 6557.0),
+    ("flare/util/Strings", 22026.0),
+    ("flare/vis/axis/Axes", 1302.0),
+    ("flare/vis/axis/Axis", 24593.0),
+    ("flare/vis/axis/AxisGridLine", 652.0),
+    ("flare/vis/axis/AxisLabel", 636.0),
+    ("flare/vis/axis/CartesianAxes", 6703.0),
+    ("flare/vis/controls/AnchorControl", 2138.0),
+    ("flare/vis/controls/ClickControl", 3824.0),
+    ("flare/vis/controls/Control", 1353.0),
+    ("flare/vis/controls/ControlList", 4665.0),
+    ("flare/vis/controls/DragControl", 2649.0),
+    ("flare/vis/controls/ExpandControl", 2832.0),
+    ("flare/vis/controls/HoverControl", 4896.0),
+    ("flare/vis/controls/IControl", 763.0),
+    ("flare/vis/controls/PanZoomControl", 5222.0),
+    ("flare/vis/controls/SelectionControl", 7862.0),
+    ("flare/vis/controls/TooltipControl", 8435.0),
+    ("flare/vis/data/Data", 20544.0),
+    ("flare/vis/data/DataList", 19788.0),
+    ("flare/vis/data/DataSprite", 10349.0),
+    ("flare/vis/data/EdgeSprite", 3301.0),
+    ("flare/vis/data/NodeSprite", 19382.0),
+    ("flare/vis/data/render/ArrowType", 698.0),
+    ("flare/vis/data/render/EdgeRenderer", 5569.0),
+    ("flare/vis/data/render/IRenderer", 353.0),
+    ("flare/vis/data/render/ShapeRenderer", 2247.0),
+    ("flare/vis/data/ScaleBinding", 11275.0),
+    ("flare/vis/data/Tree", 7147.0),
+    ("flare/vis/data/TreeBuilder", 9930.0),
+    ("flare/vis/events/DataEvent", 2313.0),
+    ("flare/vis/events/SelectionEvent", 1880.0),
+    ("flare/vis/events/TooltipEvent", 1701.0),
+    ("flare/vis/events/VisualizationEvent", 1117.0),
+    ("flare/vis/legend/Legend", 20859.0),
+    ("flare/vis/legend/LegendItem", 4614.0),
+    ("flare/vis/legend/LegendRange", 10530.0),
+    ("flare/vis/operator/distortion/BifocalDistortion", 4461.0),
+    ("flare/vis/operator/distortion/Distortion", 6314.0),
+    ("flare/vis/operator/distortion/FisheyeDistortion", 3444.0),
+    ("flare/vis/operator/encoder/ColorEncoder", 3179.0),
+    ("flare/vis/operator/encoder/Encoder", 4060.0),
+    ("flare/vis/operator/encoder/PropertyEncoder", 4138.0),
+    ("flare/vis/operator/encoder/ShapeEncoder", 1690.0),
+    ("flare/vis/operator/encoder/SizeEncoder", 1830.0),
+    ("flare/vis/operator/filter/FisheyeTreeFilter", 5219.0),
+    ("flare/vis/operator/filter/GraphDistanceFilter", 3165.0),
+    ("flare/vis/operator/filter/VisibilityFilter", 3509.0),
+    ("flare/vis/operator/IOperator", 1286.0),
+    ("flare/vis/operator/label/Labeler", 9956.0),
+    ("flare/vis/operator/label/RadialLabeler", 3899.0),
+    ("flare/vis/operator/label/StackedAreaLabeler", 3202.0),
+    ("flare/vis/operator/layout/AxisLayout", 6725.0),
+    ("flare/vis/operator/layout/BundledEdgeRouter", 3727.0),
+    ("flare/vis/operator/layout/CircleLayout", 9317.0),
+    ("flare/vis/operator/layout/CirclePackingLayout", 12003.0),
+    ("flare/vis/operator/layout/DendrogramLayout", 4853.0),
+    ("flare/vis/operator/layout/ForceDirectedLayout", 8411.0),
+    ("flare/vis/operator/layout/IcicleTreeLayout", 4864.0),
+    ("flare/vis/operator/layout/IndentedTreeLayout", 3174.0),
+    ("flare/vis/operator/layout/Layout", 7881.0),
+    ("flare/vis/operator/layout/NodeLinkTreeLayout", 12870.0),
+    ("flare/vis/operator/layout/PieLayout", 2728.0),
+    ("flare/vis/operator/layout/RadialTreeLayout", 12348.0),
+    ("flare/vis/operator/layout/RandomLayout", 870.0),
+    ("flare/vis/operator/layout/StackedAreaLayout", 9121.0),
+    ("flare/vis/operator/layout/TreeMapLayout", 9191.0),
+    ("flare/vis/operator/Operator", 2490.0),
+    ("flare/vis/operator/OperatorList", 5248.0),
+    ("flare/vis/operator/OperatorSequence", 4190.0),
+    ("flare/vis/operator/OperatorSwitch", 2581.0),
+    ("flare/vis/operator/SortOperator", 2023.0),
+    ("flare/vis/Visualization", 16540.0),
+];
@@ -231,6 +231,9 @@ impl TreemapWidget {
         root.add_child(energy);
         root.add_child(consumer);
 
+        // Squarify keeps child order, so put the largest cells first
+        root.sum();
+        root.sort_by_value();
         self.tree = Some(root);
         self.node_count = 15; // Total leaf nodes
 
@@ -273,7 +276,7 @@ impl TreemapWidget {
                 (rect.size.x - padding * 2.0) as f64,
                 (rect.size.y - padding * 2.0) as f64,
             )
-            .padding(4.0)
+            .padding_inner(4.0)
             .padding_top(0.0)
             .tiling(TilingMethod::Squarify);
 
@@ -497,6 +500,9 @@ impl TreemapWidget {
         vis.add_child(HierarchyNode::new(TreeNode { name: "TreeBuilder".to_string(), color_index: 5, depth: 2, leaf_index: { leaf_idx += 1; leaf_idx - 1 } }, 9930.0));
         root.add_child(vis);
 
+        // Squarify keeps child order, so put the largest cells first
+        root.sum();
+        root.sort_by_value();
         self.tree = Some(root);
         self.node_count = leaf_idx;
 
@@ -0,0 +1,246 @@
+//! Easing functions
+//!
+//! Ports of the common `d3-ease` curves. Each maps normalized time in
+//! `[0, 1]` to eased progress, with `0 → 0` and `1 → 1`; back and elastic
+//! curves overshoot in between.
+
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+const BACK_OVERSHOOT: f64 = 1.70158;
+const ELASTIC_PERIOD: f64 = 0.3 / TAU;
+
+/// Easing curve, like the `d3.ease*` functions
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Identity (`d3.easeLinear`)
+    Linear,
+    /// Quadratic ease-in
+    QuadIn,
+    /// Quadratic ease-out
+    QuadOut,
+    /// Quadratic ease-in-out
+    QuadInOut,
+    /// Cubic ease-in
+    CubicIn,
+    /// Cubic ease-out
+    CubicOut,
+    /// Cubic ease-in-out (`d3.easeCubic`, the d3 transition default)
+    #[default]
+    CubicInOut,
+    /// Polynomial ease-in with the given exponent
+    PolyIn(f64),
+    /// Polynomial ease-out with the given exponent
+    PolyOut(f64),
+    /// Polynomial ease-in-out with the given exponent
+    PolyInOut(f64),
+    /// Sinusoidal ease-in
+    SinIn,
+    /// Sinusoidal ease-out
+    SinOut,
+    /// Sinusoidal ease-in-out
+    SinInOut,
+    /// Exponential ease-in
+    ExpIn,
+    /// Exponential ease-out
+    ExpOut,
+    /// Exponential ease-in-out
+    ExpInOut,
+    /// Circular ease-in
+    CircleIn,
+    /// Circular ease-out
+    CircleOut,
+    /// Circular ease-in-out
+    CircleInOut,
+    /// Anticipatory ease-in (dips below 0 first)
+    BackIn,
+    /// Overshooting ease-out
+    BackOut,
+    /// Anticipatory and overshooting ease-in-out
+    BackInOut,
+    /// Elastic ease-in
+    ElasticIn,
+    /// Elastic ease-out
+    ElasticOut,
+    /// Elastic ease-in-out
+    ElasticInOut,
+    /// Bouncing ease-in
+    BounceIn,
+    /// Bouncing ease-out
+    BounceOut,
+    /// Bouncing ease-in-out
+    BounceInOut,
+}
+
+impl Easing {
+    /// Eased progress for normalized time `t` (clamped to `[0, 1]`)
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::animation::Easing;
+    ///
+    /// assert_eq!(Easing::Linear.apply(0.25), 0.25);
+    /// assert_eq!(Easing::QuadIn.apply(0.5), 0.25);
+    /// assert_eq!(Easing::CubicInOut.apply(1.0), 1.0);
+    /// ```
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match *self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut => in_out(t, |t| t * t),
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => in_out(t, |t| t * t * t),
+            Easing::PolyIn(e) => t.powf(e),
+            Easing::PolyOut(e) => 1.0 - (1.0 - t).powf(e),
+            Easing::PolyInOut(e) => in_out(t, |t| t.powf(e)),
+            Easing::SinIn => {
+                if t >= 1.0 {
+                    1.0
+                } else {
+                    1.0 - (t * FRAC_PI_2).cos()
+                }
+            }
+            Easing::SinOut => (t * FRAC_PI_2).sin(),
+            Easing::SinInOut => (1.0 - (PI * t).cos()) / 2.0,
+            Easing::ExpIn => tpmt(1.0 - t),
+            Easing::ExpOut => 1.0 - tpmt(t),
+            Easing::ExpInOut => in_out(t, |t| tpmt(1.0 - t)),
+            Easing::CircleIn => 1.0 - (1.0 - t * t).sqrt(),
+            Easing::CircleOut => (1.0 - (t - 1.0) * (t - 1.0)).sqrt(),
+            Easing::CircleInOut => in_out(t, |t| 1.0 - (1.0 - t * t).sqrt()),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => 1.0 - back_in(1.0 - t),
+            Easing::BackInOut => in_out(t, back_in),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => 1.0 - elastic_in(1.0 - t),
+            Easing::ElasticInOut => in_out(t, elastic_in),
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => in_out(t, |t| 1.0 - bounce_out(1.0 - t)),
+        }
+    }
+}
+
+/// Build an ease-in-out curve from an ease-in curve by symmetry
+fn in_out(t: f64, ease_in: impl Fn(f64) -> f64) -> f64 {
+    if t <= 0.5 {
+        ease_in(t * 2.0) / 2.0
+    } else {
+        1.0 - ease_in((1.0 - t) * 2.0) / 2.0
+    }
+}
+
+/// `2^(-10x)` rescaled so that `tpmt(0) = 1` and `tpmt(1) = 0`
+fn tpmt(x: f64) -> f64 {
+    ((-10.0 * x).exp2() - 0.0009765625) * 1.0009775171065494
+}
+
+fn back_in(t: f64) -> f64 {
+    t * t * ((BACK_OVERSHOOT + 1.0) * t - BACK_OVERSHOOT)
+}
+
+fn elastic_in(t: f64) -> f64 {
+    let s = FRAC_PI_2 * ELASTIC_PERIOD;
+    let t = t - 1.0;
+    tpmt(-t) * ((s - t) / ELASTIC_PERIOD).sin()
+}
+
+fn bounce_out(t: f64) -> f64 {
+    const B0: f64 = 121.0 / 16.0;
+    if t < 4.0 / 11.0 {
+        B0 * t * t
+    } else if t < 8.0 / 11.0 {
+        let t = t - 6.0 / 11.0;
+        B0 * t * t + 3.0 / 4.0
+    } else if t < 10.0 / 11.0 {
+        let t = t - 9.0 / 11.0;
+        B0 * t * t + 15.0 / 16.0
+    } else {
+        let t = t - 21.0 / 22.0;
+        B0 * t * t + 63.0 / 64.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 28] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::PolyIn(2.5),
+        Easing::PolyOut(2.5),
+        Easing::PolyInOut(2.5),
+        Easing::SinIn,
+        Easing::SinOut,
+        Easing::SinInOut,
+        Easing::ExpIn,
+        Easing::ExpOut,
+        Easing::ExpInOut,
+        Easing::CircleIn,
+        Easing::CircleOut,
+        Easing::CircleInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    #[test]
+    fn test_endpoints() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() < 1e-9, "{:?} at 0", easing);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-9, "{:?} at 1", easing);
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0));
+            assert_eq!(easing.apply(2.0), easing.apply(1.0));
+        }
+    }
+
+    #[test]
+    fn test_matches_d3_values() {
+        // Reference values from d3-ease
+        let cases = [
+            (Easing::CubicInOut, 0.25, 0.0625),
+            (Easing::CubicOut, 0.5, 0.875),
+            (Easing::QuadInOut, 0.75, 0.875),
+            (Easing::SinInOut, 0.5, 0.5),
+            (Easing::ExpOut, 0.5, 0.969697),
+            (Easing::BackIn, 0.5, -0.0876975),
+            (Easing::BounceOut, 0.5, 0.765625),
+            (Easing::ElasticOut, 0.5, 1.015152),
+        ];
+        for (easing, t, expected) in cases {
+            let value = easing.apply(t);
+            assert!((value - expected).abs() < 1e-6, "{:?}({}) = {}", easing, t, value);
+        }
+    }
+
+    #[test]
+    fn test_in_out_symmetry() {
+        let symmetric = [
+            Easing::QuadInOut,
+            Easing::CubicInOut,
+            Easing::CircleInOut,
+            Easing::BounceInOut,
+        ];
+        for easing in symmetric {
+            for i in 0..=10 {
+                let t = i as f64 / 10.0;
+                let sum = easing.apply(t) + easing.apply(1.0 - t);
+                assert!((sum - 1.0).abs() < 1e-9, "{:?} at {}", easing, t);
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Animated highlight and dimming driven by selection state
+//!
+//! A [`HighlightController`] maps each series' [`Emphasis`] in a
+//! [`SelectionModel`] to a [`HighlightStyle`] and eases between styles
+//! whenever the focus changes, so every chart type dims and highlights the
+//! same way.
+
+use super::ease::Easing;
+use super::transition::{Interpolatable, TransitionManager};
+use crate::interaction::{Emphasis, SelectionModel};
+
+/// Visual emphasis applied to a series
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HighlightStyle {
+    /// Opacity multiplier for fills and strokes
+    pub opacity: f64,
+    /// Multiplier for the series' own stroke width
+    pub stroke_scale: f64,
+}
+
+impl HighlightStyle {
+    /// Create a style
+    pub fn new(opacity: f64, stroke_scale: f64) -> Self {
+        Self {
+            opacity: opacity.clamp(0.0, 1.0),
+            stroke_scale: stroke_scale.max(0.0),
+        }
+    }
+}
+
+impl Default for HighlightStyle {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
+impl Interpolatable for HighlightStyle {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Self {
+            opacity: self.opacity.interpolate(&to.opacity, t),
+            stroke_scale: self.stroke_scale.interpolate(&to.stroke_scale, t),
+        }
+    }
+}
+
+/// Eases series styles toward the emphasis a [`SelectionModel`] gives them
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::animation::{Easing, HighlightController};
+/// use makepad_d3::interaction::SelectionModel;
+///
+/// let mut model = SelectionModel::new();
+/// let mut highlight = HighlightController::new()
+///     .with_duration(100.0)
+///     .with_easing(Easing::Linear);
+/// highlight.set_series(["cpu", "memory"]);
+///
+/// model.hover(Some("cpu"));
+/// highlight.update(&model);
+/// highlight.tick(50.0);
+/// // Halfway from full opacity to the dimmed 0.25
+/// assert_eq!(highlight.opacity("memory"), 0.625);
+///
+/// highlight.tick(100.0);
+/// assert_eq!(highlight.opacity("memory"), 0.25);
+/// assert_eq!(highlight.stroke_width("cpu", 2.0), 3.0);
+/// assert!(!highlight.is_active());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HighlightController {
+    /// Style with nothing in focus
+    normal: HighlightStyle,
+    /// Style of focused series
+    highlighted: HighlightStyle,
+    /// Style of the other series while something is in focus
+    dimmed: HighlightStyle,
+    /// Length of each style change
+    duration: f64,
+    /// Easing of each style change
+    easing: Easing,
+    /// Current style transition per series
+    styles: TransitionManager<String, HighlightStyle>,
+    /// Model version the targets were last computed from
+    version: Option<u64>,
+}
+
+impl Default for HighlightController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HighlightController {
+    /// Create a controller that dims to 25% and widens focused strokes by half
+    pub fn new() -> Self {
+        Self {
+            normal: HighlightStyle::default(),
+            highlighted: HighlightStyle::new(1.0, 1.5),
+            dimmed: HighlightStyle::new(0.25, 1.0),
+            duration: 150.0,
+            easing: Easing::CubicInOut,
+            styles: TransitionManager::new(),
+            version: None,
+        }
+    }
+
+    /// Set the style with nothing in focus
+    pub fn with_normal(mut self, style: HighlightStyle) -> Self {
+        self.normal = style;
+        self
+    }
+
+    /// Set the style of focused series
+    pub fn with_highlighted(mut self, style: HighlightStyle) -> Self {
+        self.highlighted = style;
+        self
+    }
+
+    /// Set the style of unfocused series while something is in focus
+    pub fn with_dimmed(mut self, style: HighlightStyle) -> Self {
+        self.dimmed = style;
+        self
+    }
+
+    /// Set the transition duration
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.duration = duration.max(0.0);
+        self
+    }
+
+    /// Set the transition easing
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Target style for an emphasis
+    pub fn style_for(&self, emphasis: Emphasis) -> HighlightStyle {
+        match emphasis {
+            Emphasis::Normal => self.normal,
+            Emphasis::Highlighted => self.highlighted,
+            Emphasis::Dimmed => self.dimmed,
+        }
+    }
+
+    /// Replace the tracked series; new series start at the normal style
+    ///
+    /// Series that stay keep their current animation.
+    pub fn set_series<I, S>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let keys: Vec<String> = keys.into_iter().map(Into::into).collect();
+        let stale: Vec<String> = self
+            .styles
+            .values()
+            .into_keys()
+            .filter(|k| !keys.contains(k))
+            .collect();
+        for key in stale {
+            self.styles.remove(&key);
+        }
+        for key in keys {
+            if self.styles.get(&key).is_none() {
+                self.styles.transition_to(key, self.normal, 0.0, self.easing);
+            }
+        }
+        // Recompute targets for the new series on the next update
+        self.version = None;
+    }
+
+    /// Retarget every series whose emphasis changed since the last update
+    ///
+    /// Cheap to call every frame: nothing happens until the model changes.
+    /// Returns true if any series started a new transition.
+    pub fn update(&mut self, model: &SelectionModel) -> bool {
+        if self.version == Some(model.version()) {
+            return false;
+        }
+        self.version = Some(model.version());
+
+        let keys: Vec<String> = self.styles.values().into_keys().collect();
+        let mut changed = false;
+        for key in keys {
+            let target = self.style_for(model.emphasis(&key));
+            if self.styles.get(&key).is_some_and(|t| *t.to() != target) {
+                self.styles.transition_to(key, target, self.duration, self.easing);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Advance to `time`; returns true while any series is still animating
+    pub fn tick(&mut self, time: f64) -> bool {
+        self.styles.tick(time)
+    }
+
+    /// Check if any series is still animating
+    pub fn is_active(&self) -> bool {
+        self.styles.is_active()
+    }
+
+    /// Current style of a series (normal for unknown series)
+    pub fn style(&self, key: &str) -> HighlightStyle {
+        self.styles.value(&key.to_string()).unwrap_or(self.normal)
+    }
+
+    /// Current opacity multiplier of a series
+    pub fn opacity(&self, key: &str) -> f64 {
+        self.style(key).opacity
+    }
+
+    /// Current stroke width of a series drawn at `base` width
+    pub fn stroke_width(&self, key: &str, base: f64) -> f64 {
+        base * self.style(key).stroke_scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight_follows_selection() {
+        let mut model = SelectionModel::new();
+        let mut highlight = HighlightController::new().with_duration(100.0).with_easing(Easing::Linear);
+        highlight.set_series(["a", "b", "c"]);
+        assert!(!highlight.update(&model));
+        assert_eq!(highlight.opacity("a"), 1.0);
+
+        model.select("a");
+        assert!(highlight.update(&model));
+        // Unchanged model: no restart
+        highlight.tick(50.0);
+        assert!(!highlight.update(&model));
+        assert_eq!(highlight.style("a").stroke_scale, 1.25);
+
+        // Hover moves focus mid-transition; b eases back from where it is
+        model.hover(Some("b"));
+        highlight.update(&model);
+        assert!((highlight.opacity("b") - 0.625).abs() < 1e-12);
+        highlight.tick(150.0);
+        assert_eq!(highlight.opacity("b"), 1.0);
+        assert_eq!(highlight.opacity("c"), 0.25);
+        assert_eq!(highlight.style("a"), HighlightStyle::new(1.0, 1.5));
+        assert!(!highlight.tick(150.0));
+
+        // Unknown series draw normally; removed series are dropped
+        highlight.set_series(["a", "d"]);
+        assert_eq!(highlight.opacity("d"), 1.0);
+        assert_eq!(highlight.opacity("b"), 1.0);
+        highlight.update(&model);
+        highlight.tick(250.0);
+        assert_eq!(highlight.opacity("d"), 0.25);
+    }
+}
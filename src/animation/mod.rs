@@ -0,0 +1,46 @@
+//! Animation timing utilities
+//!
+//! This module provides renderer-independent helpers for animating chart
+//! elements. Widgets drive them with a global progress value and read back
+//! per-element progress.
+//!
+//! # Features
+//!
+//! - **Stagger Planning**: Per-element start times ordered by index, value,
+//!   position or a seeded shuffle
+//! - **Playback**: Play/pause/seek over keyed data snapshots with loop modes
+//!   and enter/update/exit interpolation
+//! - **Transitions**: Eased, delayed interpolation of values, colors, zoom
+//!   transforms and paths, with a keyed [`TransitionManager`] ticked by
+//!   timestamp
+//! - **Timelines**: Named tween tracks with delays, repeat/yoyo and
+//!   completion callbacks on one clock with play/pause/seek
+//! - **Highlighting**: Eased per-series opacity and stroke emphasis that
+//!   follows hover and selection in a
+//!   [`SelectionModel`](crate::interaction::SelectionModel)
+//!
+//! # Example
+//! ```
+//! use makepad_d3::animation::{StaggerPlan, StaggerOrder};
+//!
+//! let plan = StaggerPlan::new(4, 800.0, 400.0)
+//!     .with_order(StaggerOrder::Reverse);
+//!
+//! // Local progress of every bar halfway through the animation
+//! let progress = plan.progress_all(0.5);
+//! assert_eq!(progress.len(), 4);
+//! ```
+
+mod stagger;
+mod playback;
+mod ease;
+mod transition;
+mod timeline;
+mod highlight;
+
+pub use stagger::{StaggerOrder, StaggerPlan};
+pub use playback::{LoopMode, Playback, PlaybackFrame};
+pub use ease::Easing;
+pub use transition::{Interpolatable, Transition, TransitionManager};
+pub use timeline::{Repeat, Timeline, Tween};
+pub use highlight::{HighlightController, HighlightStyle};
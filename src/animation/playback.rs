@@ -0,0 +1,527 @@
+//! Timeline playback over keyed data snapshots
+//!
+//! Drives time-animated charts (e.g. a scatter plot animated over years):
+//! a playhead moves across keyframes with play/pause/seek, speed and loop
+//! modes, and the dataset for the current playhead is interpolated between
+//! the surrounding snapshots using a keyed [`DataJoin`].
+
+use crate::data::{DataJoin, DataPoint, Dataset};
+use crate::error::{D3Error, D3Result};
+
+/// What happens when the playhead reaches the end of the timeline
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoopMode {
+    /// Stop at the end
+    #[default]
+    Once,
+    /// Jump back to the start and continue
+    Loop,
+    /// Reverse direction at each end
+    PingPong,
+}
+
+/// Interpolated data at the playhead
+#[derive(Clone, Debug)]
+pub struct PlaybackFrame {
+    /// Interpolated dataset, labelled like the earlier snapshot
+    pub dataset: Dataset,
+    /// Presence of each point in `[0, 1]`
+    ///
+    /// 1 for points in both snapshots; entering points ramp up from 0 and
+    /// exiting points ramp down to 0. Renderers typically map it to opacity.
+    pub presence: Vec<f64>,
+    /// Index of the keyframe at or before the playhead
+    pub keyframe: usize,
+    /// Progress between that keyframe and the next, in `[0, 1]`
+    pub t: f64,
+}
+
+/// Playback controller for keyed data snapshots
+///
+/// Keyframe times are in timeline units (years, milliseconds, ...); speed is
+/// timeline units per second of wall time. Points are matched across
+/// snapshots by [`DataPoint::label`], falling back to position.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::animation::{LoopMode, Playback};
+/// use makepad_d3::data::{DataPoint, Dataset};
+///
+/// let snapshot = |year: f64, gdp: f64| {
+///     Dataset::new(format!("{}", year))
+///         .with_points(vec![DataPoint::bubble(gdp, 70.0, 5.0).with_label("FR")])
+/// };
+///
+/// let mut playback = Playback::new(vec![
+///     (2000.0, snapshot(2000.0, 20.0)),
+///     (2010.0, snapshot(2010.0, 40.0)),
+/// ])
+/// .unwrap()
+/// .with_speed(5.0) // 5 years per second
+/// .with_loop_mode(LoopMode::Loop);
+///
+/// playback.play();
+/// playback.tick(1.0);
+/// assert_eq!(playback.playhead(), 2005.0);
+/// assert_eq!(playback.current().dataset.data[0].x, Some(30.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Playback {
+    /// Keyframe times, strictly increasing
+    times: Vec<f64>,
+    /// Snapshot at each keyframe
+    snapshots: Vec<Dataset>,
+    /// Join from each snapshot to the next
+    joins: Vec<DataJoin>,
+    playhead: f64,
+    speed: f64,
+    loop_mode: LoopMode,
+    playing: bool,
+    /// Playing backwards (ping-pong return leg)
+    reversed: bool,
+}
+
+impl Playback {
+    /// Create a paused playback at the first keyframe
+    ///
+    /// Snapshots are sorted by time. Fails for an empty list and for
+    /// non-finite or duplicate times.
+    pub fn new(mut frames: Vec<(f64, Dataset)>) -> D3Result<Self> {
+        if frames.is_empty() {
+            return Err(D3Error::invalid_data("playback needs at least one snapshot"));
+        }
+        if let Some((t, _)) = frames.iter().find(|(t, _)| !t.is_finite()) {
+            return Err(D3Error::non_finite("playback keyframe time", *t));
+        }
+        frames.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some(w) = frames.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(D3Error::invalid_data(format!(
+                "duplicate playback keyframe time {}",
+                w[0].0
+            )));
+        }
+
+        let (times, snapshots): (Vec<f64>, Vec<Dataset>) = frames.into_iter().unzip();
+        let joins = snapshots
+            .windows(2)
+            .map(|w| DataJoin::points(&w[0].data, &w[1].data))
+            .collect();
+        Ok(Self {
+            playhead: times[0],
+            times,
+            snapshots,
+            joins,
+            speed: 1.0,
+            loop_mode: LoopMode::Once,
+            playing: false,
+            reversed: false,
+        })
+    }
+
+    /// Set speed in timeline units per second (negative values are ignored)
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    /// Set the loop mode
+    pub fn with_loop_mode(mut self, mode: LoopMode) -> Self {
+        self.loop_mode = mode;
+        self
+    }
+
+    /// Set speed in timeline units per second (negative values are ignored)
+    pub fn set_speed(&mut self, speed: f64) {
+        if speed.is_finite() && speed >= 0.0 {
+            self.speed = speed;
+        }
+    }
+
+    /// Speed in timeline units per second
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Current loop mode
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// Set the loop mode
+    pub fn set_loop_mode(&mut self, mode: LoopMode) {
+        self.loop_mode = mode;
+    }
+
+    /// Start playing; restarts from the beginning if a one-shot run finished
+    pub fn play(&mut self) {
+        if self.loop_mode == LoopMode::Once && self.is_finished() {
+            self.playhead = self.start();
+            self.reversed = false;
+        }
+        self.playing = true;
+    }
+
+    /// Pause at the current playhead
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Toggle between playing and paused
+    pub fn toggle(&mut self) {
+        if self.playing {
+            self.pause();
+        } else {
+            self.play();
+        }
+    }
+
+    /// Check if playing
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Check if a one-shot run reached the end
+    pub fn is_finished(&self) -> bool {
+        self.loop_mode == LoopMode::Once && self.playhead >= self.end()
+    }
+
+    /// First keyframe time
+    pub fn start(&self) -> f64 {
+        self.times[0]
+    }
+
+    /// Last keyframe time
+    pub fn end(&self) -> f64 {
+        self.times[self.times.len() - 1]
+    }
+
+    /// Keyframe times
+    pub fn times(&self) -> &[f64] {
+        &self.times
+    }
+
+    /// Current playhead time
+    pub fn playhead(&self) -> f64 {
+        self.playhead
+    }
+
+    /// Playhead as a fraction of the timeline, for a scrubber
+    pub fn progress(&self) -> f64 {
+        let span = self.end() - self.start();
+        if span <= 0.0 {
+            1.0
+        } else {
+            (self.playhead - self.start()) / span
+        }
+    }
+
+    /// Move the playhead to a time (clamped to the timeline)
+    pub fn seek(&mut self, time: f64) {
+        if time.is_finite() {
+            self.playhead = time.clamp(self.start(), self.end());
+        }
+    }
+
+    /// Move the playhead to a fraction of the timeline
+    pub fn seek_progress(&mut self, progress: f64) {
+        self.seek(self.start() + progress * (self.end() - self.start()));
+    }
+
+    /// Move the playhead to a keyframe
+    pub fn seek_keyframe(&mut self, index: usize) {
+        if let Some(&time) = self.times.get(index) {
+            self.playhead = time;
+        }
+    }
+
+    /// Jump to the next keyframe after the playhead
+    pub fn step_forward(&mut self) {
+        if let Some(&time) = self.times.iter().find(|&&t| t > self.playhead) {
+            self.playhead = time;
+        }
+    }
+
+    /// Jump to the previous keyframe before the playhead
+    pub fn step_backward(&mut self) {
+        if let Some(&time) = self.times.iter().rev().find(|&&t| t < self.playhead) {
+            self.playhead = time;
+        }
+    }
+
+    /// Advance by `dt` seconds of wall time; returns true if the playhead moved
+    pub fn tick(&mut self, dt: f64) -> bool {
+        if !self.playing || dt.is_nan() || dt <= 0.0 || self.speed == 0.0 {
+            return false;
+        }
+        let (start, end) = (self.start(), self.end());
+        let span = end - start;
+        if span <= 0.0 {
+            self.playing = self.loop_mode != LoopMode::Once;
+            return false;
+        }
+
+        let before = self.playhead;
+        let delta = dt * self.speed;
+        match self.loop_mode {
+            LoopMode::Once => {
+                self.playhead = (self.playhead + delta).min(end);
+                if self.playhead >= end {
+                    self.playing = false;
+                }
+            }
+            LoopMode::Loop => {
+                self.playhead = start + (self.playhead - start + delta).rem_euclid(span);
+            }
+            LoopMode::PingPong => {
+                // Unfold onto a 2 * span cycle, then fold back
+                let offset = if self.reversed {
+                    2.0 * span - (self.playhead - start)
+                } else {
+                    self.playhead - start
+                };
+                let cycle = (offset + delta).rem_euclid(2.0 * span);
+                self.reversed = cycle > span;
+                self.playhead = start + if self.reversed { 2.0 * span - cycle } else { cycle };
+            }
+        }
+        self.playhead != before
+    }
+
+    /// Keyframe at or before the playhead and progress toward the next
+    pub fn position(&self) -> (usize, f64) {
+        let last = self.times.len() - 1;
+        let i = self.times.partition_point(|&t| t <= self.playhead).saturating_sub(1).min(last);
+        if i == last {
+            return (last, 0.0);
+        }
+        let (t0, t1) = (self.times[i], self.times[i + 1]);
+        (i, ((self.playhead - t0) / (t1 - t0)).clamp(0.0, 1.0))
+    }
+
+    /// Interpolated data at the playhead
+    pub fn current(&self) -> PlaybackFrame {
+        let (i, t) = self.position();
+        self.interpolate(i, t)
+    }
+
+    /// Interpolated data between keyframe `index` and the next at `t`
+    ///
+    /// Updated points are tweened, entering points appear at their target
+    /// with radius growing from zero, and exiting points stay at their source
+    /// with radius shrinking to zero (dropped at `t = 1`).
+    pub fn interpolate(&self, index: usize, t: f64) -> PlaybackFrame {
+        let index = index.min(self.snapshots.len() - 1);
+        let from = &self.snapshots[index];
+        let (Some(to), Some(join)) = (self.snapshots.get(index + 1), self.joins.get(index)) else {
+            return PlaybackFrame {
+                presence: vec![1.0; from.data.len()],
+                dataset: from.clone(),
+                keyframe: index,
+                t: 0.0,
+            };
+        };
+        let t = t.clamp(0.0, 1.0);
+
+        let mut points = Vec::with_capacity(join.update.len() + join.enter.len() + join.exit.len());
+        let mut presence = Vec::with_capacity(points.capacity());
+        for &(a, b) in &join.update {
+            points.push(lerp_point(&from.data[a], &to.data[b], t));
+            presence.push(1.0);
+        }
+        for &b in &join.enter {
+            let mut p = to.data[b].clone();
+            p.r = p.r.map(|r| r * t);
+            points.push(p);
+            presence.push(t);
+        }
+        if t < 1.0 {
+            for &a in &join.exit {
+                let mut p = from.data[a].clone();
+                p.r = p.r.map(|r| r * (1.0 - t));
+                points.push(p);
+                presence.push(1.0 - t);
+            }
+        }
+
+        let mut dataset = from.clone();
+        dataset.data = points;
+        PlaybackFrame { dataset, presence, keyframe: index, t }
+    }
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+fn lerp_opt(a: Option<f64>, b: Option<f64>, t: f64) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(lerp(a, b, t)),
+        (a, b) => if t < 1.0 { a.or(b) } else { b.or(a) },
+    }
+}
+
+fn lerp_point(a: &DataPoint, b: &DataPoint, t: f64) -> DataPoint {
+    DataPoint {
+        x: lerp_opt(a.x, b.x, t),
+        y: lerp(a.y, b.y, t),
+        y_min: lerp_opt(a.y_min, b.y_min, t),
+        r: lerp_opt(a.r, b.r, t),
+        label: b.label.clone().or_else(|| a.label.clone()),
+        meta: if t < 0.5 { a.meta.clone() } else { b.meta.clone() },
+        size: lerp_opt(a.size, b.size, t),
+        color_value: lerp_opt(a.color_value, b.color_value, t),
+        category: if t < 0.5 { a.category.clone() } else { b.category.clone() },
+        id: b.id.clone().or_else(|| a.id.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames() -> Vec<(f64, Dataset)> {
+        vec![
+            (
+                2000.0,
+                Dataset::new("World").with_points(vec![
+                    DataPoint::bubble(10.0, 60.0, 4.0).with_label("A"),
+                    DataPoint::bubble(20.0, 70.0, 8.0).with_label("B"),
+                ]),
+            ),
+            (
+                2010.0,
+                Dataset::new("World").with_points(vec![
+                    DataPoint::bubble(30.0, 64.0, 6.0).with_label("A"),
+                    DataPoint::bubble(5.0, 50.0, 2.0).with_label("C"),
+                ]),
+            ),
+            (
+                2020.0,
+                Dataset::new("World").with_points(vec![
+                    DataPoint::bubble(50.0, 68.0, 6.0).with_label("A"),
+                ]),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_new_validates() {
+        assert!(Playback::new(vec![]).is_err());
+        let dup = vec![(1.0, Dataset::new("a")), (1.0, Dataset::new("b"))];
+        assert!(Playback::new(dup).is_err());
+        let nan = vec![(f64::NAN, Dataset::new("a"))];
+        assert!(matches!(Playback::new(nan), Err(D3Error::NonFiniteValue { .. })));
+    }
+
+    #[test]
+    fn test_sorts_keyframes() {
+        let mut f = frames();
+        f.reverse();
+        let playback = Playback::new(f).unwrap();
+        assert_eq!(playback.times(), &[2000.0, 2010.0, 2020.0]);
+        assert_eq!(playback.playhead(), 2000.0);
+    }
+
+    #[test]
+    fn test_interpolation_enter_update_exit() {
+        let mut playback = Playback::new(frames()).unwrap();
+        playback.seek(2005.0);
+        let frame = playback.current();
+        assert_eq!((frame.keyframe, frame.t), (0, 0.5));
+
+        let data = &frame.dataset.data;
+        // Update: A tweened
+        assert_eq!(data[0].label.as_deref(), Some("A"));
+        assert_eq!(data[0].x, Some(20.0));
+        assert_eq!(data[0].r, Some(5.0));
+        // Enter: C at target, growing
+        assert_eq!(data[1].label.as_deref(), Some("C"));
+        assert_eq!(data[1].x, Some(5.0));
+        assert_eq!(data[1].r, Some(1.0));
+        // Exit: B at source, shrinking
+        assert_eq!(data[2].label.as_deref(), Some("B"));
+        assert_eq!(data[2].r, Some(4.0));
+        assert_eq!(frame.presence, vec![1.0, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_exact_keyframes() {
+        let mut playback = Playback::new(frames()).unwrap();
+        playback.seek(2010.0);
+        let frame = playback.current();
+        assert_eq!((frame.keyframe, frame.t), (1, 0.0));
+        assert_eq!(frame.dataset.data.len(), 2);
+
+        playback.seek(2020.0);
+        let frame = playback.current();
+        assert_eq!(frame.keyframe, 2);
+        assert_eq!(frame.dataset.data[0].x, Some(50.0));
+    }
+
+    #[test]
+    fn test_play_once_stops_at_end() {
+        let mut playback = Playback::new(frames()).unwrap().with_speed(10.0);
+        assert!(!playback.tick(1.0));
+        playback.play();
+        assert!(playback.tick(1.5));
+        assert_eq!(playback.playhead(), 2015.0);
+        playback.tick(10.0);
+        assert_eq!(playback.playhead(), 2020.0);
+        assert!(!playback.is_playing());
+        assert!(playback.is_finished());
+
+        // Playing again restarts
+        playback.play();
+        assert_eq!(playback.playhead(), 2000.0);
+    }
+
+    #[test]
+    fn test_loop_wraps() {
+        let mut playback = Playback::new(frames())
+            .unwrap()
+            .with_speed(10.0)
+            .with_loop_mode(LoopMode::Loop);
+        playback.play();
+        playback.tick(2.5);
+        assert_eq!(playback.playhead(), 2005.0);
+        assert!(playback.is_playing());
+    }
+
+    #[test]
+    fn test_ping_pong_reverses() {
+        let mut playback = Playback::new(frames())
+            .unwrap()
+            .with_speed(10.0)
+            .with_loop_mode(LoopMode::PingPong);
+        playback.play();
+        playback.tick(2.5);
+        assert_eq!(playback.playhead(), 2015.0);
+        playback.tick(1.0);
+        assert_eq!(playback.playhead(), 2005.0);
+        playback.tick(1.0);
+        assert_eq!(playback.playhead(), 2005.0);
+        playback.tick(0.5);
+        assert_eq!(playback.playhead(), 2010.0);
+    }
+
+    #[test]
+    fn test_pause_and_seek() {
+        let mut playback = Playback::new(frames()).unwrap();
+        playback.play();
+        playback.pause();
+        assert!(!playback.tick(1.0));
+
+        playback.seek(1990.0);
+        assert_eq!(playback.playhead(), 2000.0);
+        playback.seek_progress(0.75);
+        assert_eq!(playback.playhead(), 2015.0);
+        playback.step_forward();
+        assert_eq!(playback.playhead(), 2020.0);
+        playback.step_backward();
+        playback.step_backward();
+        assert_eq!(playback.playhead(), 2000.0);
+        playback.seek_keyframe(1);
+        assert_eq!(playback.progress(), 0.5);
+    }
+}
@@ -0,0 +1,261 @@
+//! Staggered per-element animation timing
+//!
+//! Computes the local progress of each element in a staggered reveal from a
+//! single global progress value, replacing hand-rolled expressions such as
+//! `((progress - i * 0.08) / 0.5).clamp(0.0, 1.0)`.
+
+use crate::rng::seeded_shuffle;
+
+/// Order in which elements start animating
+#[derive(Clone, Debug, PartialEq)]
+pub enum StaggerOrder {
+    /// By element index (first element starts first)
+    Index,
+    /// By reverse element index (last element starts first)
+    Reverse,
+    /// By ascending value (smallest value starts first)
+    ByValue(Vec<f64>),
+    /// By descending value (largest value starts first)
+    ByValueDescending(Vec<f64>),
+    /// By distance of each position from an origin (closest starts first)
+    ByPosition(Vec<(f64, f64)>, (f64, f64)),
+    /// Shuffled with a deterministic seed
+    Random(u64),
+}
+
+/// Start times for a staggered animation
+///
+/// The global timeline runs from 0 to `total_duration`; each element animates
+/// for `element_duration`, and start times are spread so that the last
+/// element finishes exactly at the end.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::animation::{StaggerPlan, StaggerOrder};
+///
+/// // 5 bars, 1000ms total, each bar takes 600ms
+/// let plan = StaggerPlan::new(5, 1000.0, 600.0);
+///
+/// assert_eq!(plan.progress(0, 0.0), 0.0);
+/// assert_eq!(plan.progress(0, 0.6), 1.0);  // first bar done at 600ms
+/// assert_eq!(plan.progress(4, 0.4), 0.0);  // last bar starts at 400ms
+/// assert_eq!(plan.progress(4, 1.0), 1.0);
+///
+/// // Reveal tallest bars first
+/// let plan = StaggerPlan::new(3, 1000.0, 500.0)
+///     .with_order(StaggerOrder::ByValueDescending(vec![10.0, 30.0, 20.0]));
+/// assert_eq!(plan.start(1), 0.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StaggerPlan {
+    /// Number of elements
+    count: usize,
+    /// Total duration of the animation
+    total_duration: f64,
+    /// Duration of each element's animation
+    element_duration: f64,
+    /// Start slot (rank) of each element
+    ranks: Vec<usize>,
+}
+
+impl StaggerPlan {
+    /// Create a plan in index order
+    ///
+    /// Durations may be in any unit (e.g. milliseconds); `element_duration`
+    /// is clamped to `[0, total_duration]`.
+    pub fn new(count: usize, total_duration: f64, element_duration: f64) -> Self {
+        let total_duration = total_duration.max(0.0);
+        Self {
+            count,
+            total_duration,
+            element_duration: element_duration.clamp(0.0, total_duration),
+            ranks: (0..count).collect(),
+        }
+    }
+
+    /// Set the start order
+    ///
+    /// Value and position orders with a length different from the element
+    /// count fall back to index order for the missing elements.
+    pub fn with_order(mut self, order: StaggerOrder) -> Self {
+        self.ranks = compute_ranks(self.count, &order);
+        self
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Check if the plan has no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Time between consecutive element starts
+    pub fn delay_step(&self) -> f64 {
+        if self.count > 1 {
+            (self.total_duration - self.element_duration) / (self.count - 1) as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Start time of an element
+    pub fn start(&self, index: usize) -> f64 {
+        self.ranks.get(index).map_or(0.0, |&r| r as f64 * self.delay_step())
+    }
+
+    /// End time of an element
+    pub fn end(&self, index: usize) -> f64 {
+        self.start(index) + self.element_duration
+    }
+
+    /// Local progress (0..=1) of an element at global progress `t` (0..=1)
+    pub fn progress(&self, index: usize, t: f64) -> f64 {
+        let time = t.clamp(0.0, 1.0) * self.total_duration;
+        let start = self.start(index);
+        if self.element_duration <= 0.0 {
+            return if time >= start { 1.0 } else { 0.0 };
+        }
+        ((time - start) / self.element_duration).clamp(0.0, 1.0)
+    }
+
+    /// Local progress of every element at global progress `t`
+    pub fn progress_all(&self, t: f64) -> Vec<f64> {
+        (0..self.count).map(|i| self.progress(i, t)).collect()
+    }
+
+    /// Check if every element has finished at global progress `t`
+    pub fn is_complete(&self, t: f64) -> bool {
+        (0..self.count).all(|i| self.progress(i, t) >= 1.0)
+    }
+}
+
+/// Rank of each element in start order
+fn compute_ranks(count: usize, order: &StaggerOrder) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..count).collect();
+
+    match order {
+        StaggerOrder::Index => {}
+        StaggerOrder::Reverse => indices.reverse(),
+        StaggerOrder::ByValue(values) => {
+            indices.sort_by(|&a, &b| sort_key(values, a).total_cmp(&sort_key(values, b)));
+        }
+        StaggerOrder::ByValueDescending(values) => {
+            indices.sort_by(|&a, &b| sort_key(values, b).total_cmp(&sort_key(values, a)));
+        }
+        StaggerOrder::ByPosition(positions, (ox, oy)) => {
+            let distances: Vec<f64> = positions
+                .iter()
+                .map(|(x, y)| ((x - ox).powi(2) + (y - oy).powi(2)).sqrt())
+                .collect();
+            indices.sort_by(|&a, &b| sort_key(&distances, a).total_cmp(&sort_key(&distances, b)));
+        }
+        StaggerOrder::Random(seed) => {
+            let mut state = *seed;
+            seeded_shuffle(&mut indices, &mut state);
+        }
+    }
+
+    let mut ranks = vec![0; count];
+    for (rank, &index) in indices.iter().enumerate() {
+        ranks[index] = rank;
+    }
+    ranks
+}
+
+/// Sort key for an element; missing or NaN values sort last
+fn sort_key(values: &[f64], index: usize) -> f64 {
+    match values.get(index) {
+        Some(v) if !v.is_nan() => *v,
+        _ => f64::INFINITY,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_order_timing() {
+        let plan = StaggerPlan::new(3, 1000.0, 500.0);
+        assert_eq!(plan.delay_step(), 250.0);
+        assert_eq!(plan.start(0), 0.0);
+        assert_eq!(plan.start(1), 250.0);
+        assert_eq!(plan.start(2), 500.0);
+        assert_eq!(plan.end(2), 1000.0);
+        assert_eq!(plan.progress(1, 0.5), 0.5);
+        assert!(plan.is_complete(1.0));
+        assert!(!plan.is_complete(0.9));
+    }
+
+    #[test]
+    fn test_progress_clamped() {
+        let plan = StaggerPlan::new(2, 100.0, 50.0);
+        assert_eq!(plan.progress(0, -1.0), 0.0);
+        assert_eq!(plan.progress(1, 2.0), 1.0);
+        assert_eq!(plan.progress_all(0.25), vec![0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_reverse_order() {
+        let plan = StaggerPlan::new(3, 300.0, 100.0).with_order(StaggerOrder::Reverse);
+        assert_eq!(plan.start(2), 0.0);
+        assert_eq!(plan.start(0), 200.0);
+    }
+
+    #[test]
+    fn test_value_orders() {
+        let values = vec![3.0, 1.0, 2.0];
+        let asc = StaggerPlan::new(3, 300.0, 100.0).with_order(StaggerOrder::ByValue(values.clone()));
+        assert_eq!(asc.start(1), 0.0);
+        assert_eq!(asc.start(2), 100.0);
+        assert_eq!(asc.start(0), 200.0);
+
+        let desc = StaggerPlan::new(3, 300.0, 100.0)
+            .with_order(StaggerOrder::ByValueDescending(values));
+        assert_eq!(desc.start(0), 0.0);
+        assert_eq!(desc.start(1), 200.0);
+    }
+
+    #[test]
+    fn test_position_order() {
+        let positions = vec![(10.0, 0.0), (0.0, 1.0), (5.0, 5.0)];
+        let plan = StaggerPlan::new(3, 300.0, 100.0)
+            .with_order(StaggerOrder::ByPosition(positions, (0.0, 0.0)));
+        assert_eq!(plan.start(1), 0.0);
+        assert_eq!(plan.start(2), 100.0);
+        assert_eq!(plan.start(0), 200.0);
+    }
+
+    #[test]
+    fn test_random_order_is_deterministic_permutation() {
+        let a = StaggerPlan::new(10, 1.0, 0.1).with_order(StaggerOrder::Random(7));
+        let b = StaggerPlan::new(10, 1.0, 0.1).with_order(StaggerOrder::Random(7));
+        assert_eq!(a, b);
+
+        let mut starts: Vec<f64> = (0..10).map(|i| a.start(i)).collect();
+        starts.sort_by(f64::total_cmp);
+        let expected: Vec<f64> = (0..10).map(|r| r as f64 * a.delay_step()).collect();
+        assert_eq!(starts, expected);
+    }
+
+    #[test]
+    fn test_degenerate_durations() {
+        // Element duration equals total: everything animates together
+        let plan = StaggerPlan::new(4, 100.0, 200.0);
+        assert_eq!(plan.delay_step(), 0.0);
+        assert_eq!(plan.progress(3, 0.5), 0.5);
+
+        // Zero element duration: elements pop in at their start time
+        let plan = StaggerPlan::new(3, 100.0, 0.0);
+        assert_eq!(plan.progress(1, 0.49), 0.0);
+        assert_eq!(plan.progress(1, 0.5), 1.0);
+
+        let empty = StaggerPlan::new(0, 100.0, 10.0);
+        assert!(empty.is_empty());
+        assert!(empty.progress_all(0.5).is_empty());
+    }
+}
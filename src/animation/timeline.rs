@@ -0,0 +1,499 @@
+//! Multi-track tween timelines
+//!
+//! A [`Timeline`] runs several named [`Tween`] tracks against one clock, so
+//! an intro animation (bars growing, then labels fading in, then an axis
+//! color pulse) is declared once instead of juggling separate timers. Each
+//! track has its own delay, duration, easing and repeat/yoyo behavior and
+//! may interpolate any [`Interpolatable`] type; the timeline as a whole
+//! supports play/pause/seek and completion callbacks.
+//!
+//! All times are in milliseconds, like [`Transition`](super::Transition).
+//!
+//! # Example
+//! ```
+//! use makepad_d3::animation::{Easing, Timeline, Tween};
+//! use makepad_d3::color::Rgba;
+//!
+//! let mut timeline = Timeline::new();
+//! timeline.add("height", Tween::new(0.0, 120.0).with_duration(400.0));
+//! timeline.append(
+//!     "label",
+//!     Tween::new(Rgba::TRANSPARENT, Rgba::BLACK)
+//!         .with_duration(200.0)
+//!         .with_easing(Easing::Linear),
+//! );
+//! assert_eq!(timeline.duration(), 600.0);
+//!
+//! timeline.play();
+//! timeline.tick(400.0);
+//! assert_eq!(timeline.value::<f64>("height"), Some(120.0));
+//! assert_eq!(timeline.value::<Rgba>("label"), Some(Rgba::TRANSPARENT));
+//!
+//! timeline.tick(200.0);
+//! assert!(timeline.is_finished());
+//! ```
+
+use std::any::Any;
+use std::fmt;
+
+use super::ease::Easing;
+use super::transition::Interpolatable;
+
+/// How many times a track plays after its first run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Repeat {
+    /// Play the given number of extra times (0 = play once)
+    Count(u32),
+    /// Repeat until the timeline is paused or cleared
+    Forever,
+}
+
+/// A tween track: two values plus timing
+#[derive(Clone, Debug)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    timing: TrackTiming,
+}
+
+/// Timing shared by all tracks, independent of the value type
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TrackTiming {
+    delay: f64,
+    duration: f64,
+    easing: Easing,
+    repeat: Repeat,
+    yoyo: bool,
+}
+
+impl<T: Interpolatable> Tween<T> {
+    /// Create a tween with d3's defaults: 250 duration, no delay, cubic easing
+    pub fn new(from: T, to: T) -> Self {
+        Self {
+            from,
+            to,
+            timing: TrackTiming {
+                delay: 0.0,
+                duration: 250.0,
+                easing: Easing::default(),
+                repeat: Repeat::Count(0),
+                yoyo: false,
+            },
+        }
+    }
+
+    /// Set the duration of one run
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.timing.duration = duration.max(0.0);
+        self
+    }
+
+    /// Set the delay before the first run
+    pub fn with_delay(mut self, delay: f64) -> Self {
+        self.timing.delay = delay.max(0.0);
+        self
+    }
+
+    /// Set the easing curve
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.timing.easing = easing;
+        self
+    }
+
+    /// Play the given number of extra times after the first run
+    pub fn with_repeat(mut self, count: u32) -> Self {
+        self.timing.repeat = Repeat::Count(count);
+        self
+    }
+
+    /// Repeat indefinitely
+    pub fn with_repeat_forever(mut self) -> Self {
+        self.timing.repeat = Repeat::Forever;
+        self
+    }
+
+    /// Run every other repetition backwards (`to` back to `from`)
+    pub fn with_yoyo(mut self, yoyo: bool) -> Self {
+        self.timing.yoyo = yoyo;
+        self
+    }
+}
+
+impl TrackTiming {
+    /// Time from the timeline start until the track ends (infinite if repeating forever)
+    fn end(&self) -> f64 {
+        match self.repeat {
+            Repeat::Count(n) => self.delay + self.duration * (n as f64 + 1.0),
+            Repeat::Forever if self.duration > 0.0 => f64::INFINITY,
+            Repeat::Forever => self.delay,
+        }
+    }
+
+    /// Linear progress of the current run at timeline `time`, with yoyo applied
+    fn phase(&self, time: f64) -> f64 {
+        let local = time - self.delay;
+        if local < 0.0 {
+            return 0.0;
+        }
+        let (run, phase) = if self.duration <= 0.0 {
+            (0.0, 1.0)
+        } else if time >= self.end() {
+            let runs = match self.repeat {
+                Repeat::Count(n) => n as f64 + 1.0,
+                Repeat::Forever => 1.0,
+            };
+            (runs - 1.0, 1.0)
+        } else {
+            let cycles = local / self.duration;
+            let run = cycles.floor();
+            (run, cycles - run)
+        };
+        if self.yoyo && run % 2.0 == 1.0 {
+            1.0 - phase
+        } else {
+            phase
+        }
+    }
+}
+
+type Callback = Box<dyn FnMut() + Send>;
+
+struct Track {
+    name: String,
+    timing: TrackTiming,
+    /// The tween's `(from, to)` pair, downcast by [`Timeline::value`]
+    values: Box<dyn Any + Send + Sync>,
+    on_complete: Option<Callback>,
+    completed: bool,
+}
+
+/// Named tween tracks driven by a single clock
+///
+/// Completion callbacks fire from [`tick`](Self::tick) when the clock first
+/// passes a track's end (and the timeline's end). Seeking moves the clock
+/// silently: tracks seeked past are treated as already completed, and tracks
+/// seeked back before their end will fire again.
+pub struct Timeline {
+    tracks: Vec<Track>,
+    time: f64,
+    playing: bool,
+    on_complete: Option<Callback>,
+    completed: bool,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Timeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeline")
+            .field("tracks", &self.tracks.iter().map(|t| &t.name).collect::<Vec<_>>())
+            .field("time", &self.time)
+            .field("playing", &self.playing)
+            .finish()
+    }
+}
+
+impl Timeline {
+    /// Create an empty, paused timeline at time 0
+    pub fn new() -> Self {
+        Self {
+            tracks: Vec::new(),
+            time: 0.0,
+            playing: false,
+            on_complete: None,
+            completed: false,
+        }
+    }
+
+    /// Add a track whose delay counts from the timeline start
+    ///
+    /// A track with an existing name is replaced.
+    pub fn add<T>(&mut self, name: impl Into<String>, tween: Tween<T>)
+    where
+        T: Interpolatable + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let track = Track {
+            completed: self.time >= tween.timing.end(),
+            name: name.clone(),
+            timing: tween.timing,
+            values: Box::new((tween.from, tween.to)),
+            on_complete: None,
+        };
+        match self.tracks.iter_mut().find(|t| t.name == name) {
+            Some(existing) => *existing = track,
+            None => self.tracks.push(track),
+        }
+        self.completed = self.completed && self.time >= self.duration();
+    }
+
+    /// Add a track that starts when every existing finite track has ended
+    ///
+    /// The tween's own delay is added on top, allowing gaps or, with a
+    /// subsequent [`add`](Self::add), overlaps.
+    pub fn append<T>(&mut self, name: impl Into<String>, mut tween: Tween<T>)
+    where
+        T: Interpolatable + Send + Sync + 'static,
+    {
+        let start = self
+            .tracks
+            .iter()
+            .map(|t| t.timing.end())
+            .filter(|end| end.is_finite())
+            .fold(0.0, f64::max);
+        tween.timing.delay += start;
+        self.add(name, tween);
+    }
+
+    /// Remove a track; returns true if it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.tracks.len();
+        self.tracks.retain(|t| t.name != name);
+        self.tracks.len() != len
+    }
+
+    /// Register a callback fired when a track completes
+    ///
+    /// Returns false if no track has that name.
+    pub fn on_track_complete(&mut self, name: &str, callback: impl FnMut() + Send + 'static) -> bool {
+        match self.tracks.iter_mut().find(|t| t.name == name) {
+            Some(track) => {
+                track.on_complete = Some(Box::new(callback));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Register a callback fired when every track has completed
+    pub fn on_complete(&mut self, callback: impl FnMut() + Send + 'static) {
+        self.on_complete = Some(Box::new(callback));
+    }
+
+    /// Track names in insertion order
+    pub fn track_names(&self) -> impl Iterator<Item = &str> {
+        self.tracks.iter().map(|t| t.name.as_str())
+    }
+
+    /// Number of tracks
+    pub fn len(&self) -> usize {
+        self.tracks.len()
+    }
+
+    /// Check if there are no tracks
+    pub fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    /// End time of the last track (infinite if any track repeats forever)
+    pub fn duration(&self) -> f64 {
+        self.tracks.iter().map(|t| t.timing.end()).fold(0.0, f64::max)
+    }
+
+    /// Current clock time
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Start or resume playing; restarts from 0 if the timeline finished
+    pub fn play(&mut self) {
+        if self.is_finished() {
+            self.seek(0.0);
+        }
+        self.playing = true;
+    }
+
+    /// Pause at the current time
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Check if playing
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Check if the clock reached the end of every track
+    pub fn is_finished(&self) -> bool {
+        !self.tracks.is_empty() && self.time >= self.duration()
+    }
+
+    /// Move the clock to `time` (clamped to the timeline) without firing callbacks
+    pub fn seek(&mut self, time: f64) {
+        if time.is_nan() {
+            return;
+        }
+        self.time = time.clamp(0.0, self.duration());
+        for track in &mut self.tracks {
+            track.completed = self.time >= track.timing.end();
+        }
+        self.completed = self.is_finished();
+    }
+
+    /// Advance the clock by `dt` while playing and fire completion callbacks
+    ///
+    /// Returns true while the timeline is still playing.
+    pub fn tick(&mut self, dt: f64) -> bool {
+        if !self.playing || dt.is_nan() || dt <= 0.0 {
+            return self.playing;
+        }
+        self.time = (self.time + dt).min(self.duration());
+
+        for track in &mut self.tracks {
+            if !track.completed && self.time >= track.timing.end() {
+                track.completed = true;
+                if let Some(callback) = track.on_complete.as_mut() {
+                    callback();
+                }
+            }
+        }
+        if !self.completed && self.is_finished() {
+            self.completed = true;
+            self.playing = false;
+            if let Some(callback) = self.on_complete.as_mut() {
+                callback();
+            }
+        }
+        self.playing
+    }
+
+    /// Eased progress of a track at the current time
+    ///
+    /// Runs from 0 to 1, and back down on yoyo repetitions.
+    pub fn progress(&self, name: &str) -> Option<f64> {
+        let track = self.tracks.iter().find(|t| t.name == name)?;
+        Some(track.timing.easing.apply(track.timing.phase(self.time)))
+    }
+
+    /// Interpolated value of a track at the current time
+    ///
+    /// Returns None if there is no such track or it holds a different type.
+    pub fn value<T: Interpolatable + 'static>(&self, name: &str) -> Option<T> {
+        let track = self.tracks.iter().find(|t| t.name == name)?;
+        let (from, to) = track.values.downcast_ref::<(T, T)>()?;
+        let phase = track.timing.phase(self.time);
+        Some(match phase {
+            p if p <= 0.0 => from.clone(),
+            p if p >= 1.0 => to.clone(),
+            p => from.interpolate(to, track.timing.easing.apply(p)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::Point;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_tracks_share_clock() {
+        let mut timeline = Timeline::new();
+        timeline.add(
+            "x",
+            Tween::new(0.0, 100.0).with_duration(100.0).with_easing(Easing::Linear),
+        );
+        timeline.add(
+            "pos",
+            Tween::new(Point::new(0.0, 0.0), Point::new(10.0, 20.0))
+                .with_delay(50.0)
+                .with_duration(100.0)
+                .with_easing(Easing::Linear),
+        );
+        assert_eq!(timeline.duration(), 150.0);
+
+        // Paused timelines do not advance
+        assert!(!timeline.tick(10.0));
+        assert_eq!(timeline.time(), 0.0);
+
+        timeline.play();
+        timeline.tick(75.0);
+        assert_eq!(timeline.value::<f64>("x"), Some(75.0));
+        assert_eq!(timeline.value::<Point>("pos"), Some(Point::new(2.5, 5.0)));
+        // Wrong type or name
+        assert_eq!(timeline.value::<Point>("x"), None);
+        assert_eq!(timeline.progress("missing"), None);
+
+        timeline.pause();
+        timeline.tick(100.0);
+        assert_eq!(timeline.time(), 75.0);
+    }
+
+    #[test]
+    fn test_repeat_and_yoyo() {
+        let mut timeline = Timeline::new();
+        timeline.add(
+            "pulse",
+            Tween::new(0.0, 1.0)
+                .with_duration(100.0)
+                .with_easing(Easing::Linear)
+                .with_repeat(1)
+                .with_yoyo(true),
+        );
+        timeline.add(
+            "spin",
+            Tween::new(0.0, 360.0).with_duration(100.0).with_easing(Easing::Linear).with_repeat(2),
+        );
+        assert_eq!(timeline.duration(), 300.0);
+
+        timeline.seek(125.0);
+        assert_eq!(timeline.value::<f64>("pulse"), Some(0.75));
+        assert_eq!(timeline.value::<f64>("spin"), Some(90.0));
+
+        // Yoyo with an even run count ends back at `from`
+        timeline.seek(300.0);
+        assert_eq!(timeline.value::<f64>("pulse"), Some(0.0));
+        assert_eq!(timeline.value::<f64>("spin"), Some(360.0));
+
+        let mut forever = Timeline::new();
+        forever.add("blink", Tween::new(0.0, 1.0).with_repeat_forever());
+        assert_eq!(forever.duration(), f64::INFINITY);
+        forever.play();
+        assert!(forever.tick(1e6));
+        assert!(!forever.is_finished());
+    }
+
+    #[test]
+    fn test_callbacks_and_append() {
+        let tracks = Arc::new(AtomicUsize::new(0));
+        let done = Arc::new(AtomicUsize::new(0));
+
+        let mut timeline = Timeline::new();
+        timeline.add("a", Tween::new(0.0, 1.0).with_duration(100.0));
+        timeline.append("b", Tween::new(0.0, 1.0).with_duration(100.0).with_delay(20.0));
+        assert_eq!(timeline.duration(), 220.0);
+
+        let counter = tracks.clone();
+        assert!(timeline.on_track_complete("a", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        assert!(!timeline.on_track_complete("missing", || {}));
+        let counter = done.clone();
+        timeline.on_complete(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        timeline.play();
+        timeline.tick(110.0);
+        assert_eq!(tracks.load(Ordering::SeqCst), 1);
+        assert_eq!(done.load(Ordering::SeqCst), 0);
+        assert_eq!(timeline.value::<f64>("b"), Some(0.0));
+
+        assert!(!timeline.tick(1000.0));
+        assert_eq!(timeline.time(), 220.0);
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+        assert!(timeline.is_finished());
+
+        // Replaying fires the callbacks again
+        timeline.play();
+        assert_eq!(timeline.time(), 0.0);
+        timeline.tick(500.0);
+        assert_eq!(tracks.load(Ordering::SeqCst), 2);
+        assert_eq!(done.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,444 @@
+//! Timed transitions between values
+//!
+//! A [`Transition`] interpolates any [`Interpolatable`] value from a start
+//! to an end state over a duration, after an optional delay, with an
+//! [`Easing`] curve. A [`TransitionManager`] keeps keyed transitions (one per
+//! bar, point or path) and advances them all from a single timestamp, so
+//! widgets no longer need their own animator.
+//!
+//! Times are plain numbers in any consistent unit, typically milliseconds.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::animation::{Easing, Transition, TransitionManager};
+//!
+//! let mut bars = TransitionManager::new();
+//! // Staggered reveal: bar i grows from 0 starting at i * 50ms
+//! bars.insert_staggered(
+//!     [("a", 40.0), ("b", 80.0), ("c", 60.0)]
+//!         .map(|(key, h)| (key, Transition::new(0.0, h).with_duration(300.0))),
+//!     50.0,
+//! );
+//!
+//! bars.tick(0.0);
+//! assert_eq!(bars.value(&"b"), Some(0.0));
+//! bars.tick(400.0);
+//! assert_eq!(bars.value(&"c"), Some(60.0));
+//! assert!(!bars.is_active());
+//!
+//! // Data update: morph from the current height to the new one
+//! bars.transition_to("a", 10.0, 300.0, Easing::Linear);
+//! bars.tick(550.0);
+//! assert_eq!(bars.value(&"a"), Some(25.0));
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::ease::Easing;
+use crate::color::Rgba;
+use crate::interaction::ZoomTransform;
+use crate::shape::{interpolate_path, ArcDatum, Path, PieSlice, Point};
+
+/// A value that can be blended between two states
+pub trait Interpolatable: Clone {
+    /// Value at `t` between `self` (t = 0) and `to` (t = 1)
+    ///
+    /// `t` may leave `[0, 1]` for overshooting easings.
+    fn interpolate(&self, to: &Self, t: f64) -> Self;
+}
+
+impl Interpolatable for f64 {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolatable for Point {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        Point::new(self.x.interpolate(&to.x, t), self.y.interpolate(&to.y, t))
+    }
+}
+
+impl Interpolatable for Rgba {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        self.lerp(to, t as f32)
+    }
+}
+
+impl Interpolatable for ZoomTransform {
+    /// Interpolates the scale geometrically, so zooming by a constant
+    /// factor feels uniform, and the translation linearly
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        let k = if self.k > 0.0 && to.k > 0.0 {
+            self.k * (to.k / self.k).powf(t)
+        } else {
+            self.k.interpolate(&to.k, t)
+        };
+        ZoomTransform::new(k, self.x.interpolate(&to.x, t), self.y.interpolate(&to.y, t))
+    }
+}
+
+impl Interpolatable for Path {
+    /// Morphs between paths with [`interpolate_path`], which resamples
+    /// paths with different segment counts
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        interpolate_path(self, to, t)
+    }
+}
+
+impl Interpolatable for ArcDatum {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        ArcDatum::interpolate(self, to, t)
+    }
+}
+
+impl<D: Clone> Interpolatable for PieSlice<D> {
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        PieSlice::interpolate(self, to, t)
+    }
+}
+
+impl<T: Interpolatable> Interpolatable for Vec<T> {
+    /// Interpolates element-wise; elements only present in `to` appear as-is
+    fn interpolate(&self, to: &Self, t: f64) -> Self {
+        to.iter()
+            .enumerate()
+            .map(|(i, b)| match self.get(i) {
+                Some(a) => a.interpolate(b, t),
+                None => b.clone(),
+            })
+            .collect()
+    }
+}
+
+/// A timed, eased interpolation between two values
+#[derive(Clone, Debug)]
+pub struct Transition<T> {
+    /// Start value
+    from: T,
+    /// End value
+    to: T,
+    /// Duration of the interpolation
+    duration: f64,
+    /// Wait before the interpolation starts
+    delay: f64,
+    /// Easing curve
+    easing: Easing,
+    /// Time the transition was started (None = not started yet)
+    start_time: Option<f64>,
+}
+
+impl<T: Interpolatable> Transition<T> {
+    /// Create a transition with d3's defaults: 250 duration, no delay, cubic easing
+    pub fn new(from: T, to: T) -> Self {
+        Self {
+            from,
+            to,
+            duration: 250.0,
+            delay: 0.0,
+            easing: Easing::default(),
+            start_time: None,
+        }
+    }
+
+    /// Set the duration
+    pub fn with_duration(mut self, duration: f64) -> Self {
+        self.duration = duration.max(0.0);
+        self
+    }
+
+    /// Set the delay before the interpolation starts
+    pub fn with_delay(mut self, delay: f64) -> Self {
+        self.delay = delay.max(0.0);
+        self
+    }
+
+    /// Set the easing curve
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Start the transition at `time`
+    pub fn start(&mut self, time: f64) {
+        self.start_time = Some(time);
+    }
+
+    /// Check if the transition has been started
+    pub fn is_started(&self) -> bool {
+        self.start_time.is_some()
+    }
+
+    /// Get the start value
+    pub fn from(&self) -> &T {
+        &self.from
+    }
+
+    /// Get the end value
+    pub fn to(&self) -> &T {
+        &self.to
+    }
+
+    /// Get the duration
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// Get the delay
+    pub fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    /// Get the easing curve
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Linear progress in `[0, 1]` at `time` (0 before the start or delay)
+    pub fn linear_progress(&self, time: f64) -> f64 {
+        let Some(start) = self.start_time else {
+            return 0.0;
+        };
+        let elapsed = time - start - self.delay;
+        if self.duration <= 0.0 {
+            if elapsed >= 0.0 { 1.0 } else { 0.0 }
+        } else {
+            (elapsed / self.duration).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Eased progress at `time`
+    pub fn progress(&self, time: f64) -> f64 {
+        self.easing.apply(self.linear_progress(time))
+    }
+
+    /// Interpolated value at `time`
+    pub fn value_at(&self, time: f64) -> T {
+        let t = self.linear_progress(time);
+        if t >= 1.0 {
+            self.to.clone()
+        } else {
+            self.from.interpolate(&self.to, self.easing.apply(t))
+        }
+    }
+
+    /// Check if the transition has reached its end value at `time`
+    pub fn is_finished(&self, time: f64) -> bool {
+        self.start_time.is_some() && self.linear_progress(time) >= 1.0
+    }
+
+    /// Redirect to a new end value, starting from the value at `time`
+    ///
+    /// Used when data updates mid-animation, so elements continue smoothly
+    /// from where they are instead of jumping.
+    pub fn retarget(&mut self, time: f64, to: T) {
+        self.from = self.value_at(time);
+        self.to = to;
+        self.delay = 0.0;
+        self.start_time = Some(time);
+    }
+}
+
+/// Keyed transitions advanced together by timestamp
+#[derive(Clone, Debug)]
+pub struct TransitionManager<K, T> {
+    /// Transitions by key
+    transitions: HashMap<K, Transition<T>>,
+    /// Time of the last tick
+    now: f64,
+}
+
+impl<K: Eq + Hash + Clone, T: Interpolatable> Default for TransitionManager<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, T: Interpolatable> TransitionManager<K, T> {
+    /// Create an empty manager
+    pub fn new() -> Self {
+        Self {
+            transitions: HashMap::new(),
+            now: 0.0,
+        }
+    }
+
+    /// Add or replace a transition
+    ///
+    /// Transitions that were not started begin at the time of the last tick.
+    pub fn insert(&mut self, key: K, mut transition: Transition<T>) {
+        if !transition.is_started() {
+            transition.start(self.now);
+        }
+        self.transitions.insert(key, transition);
+    }
+
+    /// Add transitions with delays increasing by `step` in iteration order
+    ///
+    /// The step is added to each transition's own delay.
+    pub fn insert_staggered(
+        &mut self,
+        transitions: impl IntoIterator<Item = (K, Transition<T>)>,
+        step: f64,
+    ) {
+        for (i, (key, transition)) in transitions.into_iter().enumerate() {
+            let delay = transition.delay() + step * i as f64;
+            self.insert(key, transition.with_delay(delay));
+        }
+    }
+
+    /// Animate `key` to a new value from wherever it currently is
+    ///
+    /// A key without a transition jumps straight to `to`.
+    pub fn transition_to(&mut self, key: K, to: T, duration: f64, easing: Easing) {
+        let now = self.now;
+        match self.transitions.get_mut(&key) {
+            Some(transition) => {
+                transition.retarget(now, to);
+                transition.duration = duration.max(0.0);
+                transition.easing = easing;
+            }
+            None => {
+                let transition = Transition::new(to.clone(), to)
+                    .with_duration(0.0)
+                    .with_easing(easing);
+                self.insert(key, transition);
+            }
+        }
+    }
+
+    /// Advance to `time`; returns true while any transition is still running
+    pub fn tick(&mut self, time: f64) -> bool {
+        self.now = time;
+        self.is_active()
+    }
+
+    /// Time of the last tick
+    pub fn now(&self) -> f64 {
+        self.now
+    }
+
+    /// Check if any transition is still running
+    pub fn is_active(&self) -> bool {
+        self.transitions.values().any(|t| !t.is_finished(self.now))
+    }
+
+    /// Current value for a key
+    pub fn value(&self, key: &K) -> Option<T> {
+        self.transitions.get(key).map(|t| t.value_at(self.now))
+    }
+
+    /// Current values of all keys
+    pub fn values(&self) -> HashMap<K, T> {
+        self.transitions
+            .iter()
+            .map(|(k, t)| (k.clone(), t.value_at(self.now)))
+            .collect()
+    }
+
+    /// Get the transition for a key
+    pub fn get(&self, key: &K) -> Option<&Transition<T>> {
+        self.transitions.get(key)
+    }
+
+    /// Remove a key, returning its transition
+    pub fn remove(&mut self, key: &K) -> Option<Transition<T>> {
+        self.transitions.remove(key)
+    }
+
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Check if there are no keys
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Remove all transitions
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_timing() {
+        let mut t = Transition::new(0.0, 100.0)
+            .with_duration(200.0)
+            .with_delay(100.0)
+            .with_easing(Easing::Linear);
+        assert_eq!(t.value_at(500.0), 0.0); // not started
+        t.start(1000.0);
+        assert_eq!(t.value_at(1050.0), 0.0);
+        assert_eq!(t.value_at(1200.0), 50.0);
+        assert_eq!(t.value_at(1300.0), 100.0);
+        assert!(!t.is_finished(1299.0));
+        assert!(t.is_finished(1300.0));
+
+        let mut eased = Transition::new(0.0, 1.0).with_duration(100.0);
+        eased.start(0.0);
+        assert!((eased.value_at(25.0) - 0.0625).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_retarget_continues_from_current_value() {
+        let mut t = Transition::new(0.0, 100.0).with_duration(100.0).with_easing(Easing::Linear);
+        t.start(0.0);
+        t.retarget(50.0, 0.0);
+        assert_eq!(t.value_at(50.0), 50.0);
+        assert_eq!(t.value_at(100.0), 25.0);
+        assert_eq!(t.value_at(150.0), 0.0);
+    }
+
+    #[test]
+    fn test_interpolatable_impls() {
+        let p = Point::new(0.0, 10.0).interpolate(&Point::new(10.0, 20.0), 0.5);
+        assert_eq!(p, Point::new(5.0, 15.0));
+
+        let c = Rgba::new(0.0, 0.0, 0.0, 1.0).interpolate(&Rgba::new(1.0, 0.5, 0.0, 1.0), 0.5);
+        assert!((c.r - 0.5).abs() < 1e-6 && (c.g - 0.25).abs() < 1e-6);
+
+        let z = ZoomTransform::new(1.0, 0.0, 0.0).interpolate(&ZoomTransform::new(4.0, 100.0, -50.0), 0.5);
+        assert!((z.k - 2.0).abs() < 1e-12);
+        assert_eq!((z.x, z.y), (50.0, -25.0));
+
+        let v = vec![0.0, 10.0].interpolate(&vec![10.0, 20.0, 30.0], 0.5);
+        assert_eq!(v, vec![5.0, 15.0, 30.0]);
+
+        let mut a = Path::new();
+        a.move_to(0.0, 0.0).line_to(10.0, 0.0);
+        let mut b = Path::new();
+        b.move_to(0.0, 10.0).line_to(10.0, 10.0);
+        let mid = a.interpolate(&b, 0.5);
+        let end = mid.iter().last().and_then(|s| s.end_point()).unwrap();
+        assert!((end.y - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_manager_stagger_and_activity() {
+        let mut manager = TransitionManager::new();
+        manager.insert_staggered(
+            (0..3).map(|i| (i, Transition::new(0.0, 1.0).with_duration(100.0).with_easing(Easing::Linear))),
+            50.0,
+        );
+        assert_eq!(manager.len(), 3);
+        assert!(manager.tick(100.0));
+        assert_eq!(manager.value(&0), Some(1.0));
+        assert_eq!(manager.value(&1), Some(0.5));
+        assert_eq!(manager.value(&2), Some(0.0));
+        assert!(!manager.tick(200.0));
+        assert_eq!(manager.values().len(), 3);
+
+        // Unknown key jumps to its value
+        manager.transition_to(7, 3.0, 100.0, Easing::Linear);
+        assert_eq!(manager.value(&7), Some(3.0));
+        assert!(!manager.is_active());
+    }
+}
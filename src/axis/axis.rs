@@ -2,9 +2,15 @@
 //!
 //! Provides axis configuration and layout computation for chart axes.
 
-use crate::scale::{Scale, Tick, TickOptions, DiscreteScale, BandScale, PointScale};
+use std::sync::Arc;
+
+use crate::scale::{Scale, Tick, TickOptions, DiscreteScale, BandScale, GroupedBandScale, PointScale};
 use super::format::NumberFormat;
-use super::grid::GridConfig;
+use super::grid::{GridConfig, GridGeometry};
+use super::text::{
+    ApproxTextMeasurer, TextDirection, TextMeasurer, WritingMode,
+    truncate_label, truncate_label_vertical,
+};
 
 /// Axis orientation
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -63,6 +69,21 @@ pub enum TextAnchor {
     End,
 }
 
+impl TextAnchor {
+    /// Convert a physical anchor to the logical anchor for a text direction
+    ///
+    /// Layout anchors are physical (`Start` = left edge). Text engines that
+    /// interpret anchors logically need `Start` and `End` swapped for
+    /// right-to-left text.
+    pub fn for_direction(self, direction: TextDirection) -> TextAnchor {
+        match (self, direction) {
+            (TextAnchor::Start, TextDirection::Rtl) => TextAnchor::End,
+            (TextAnchor::End, TextDirection::Rtl) => TextAnchor::Start,
+            (anchor, _) => anchor,
+        }
+    }
+}
+
 /// Vertical alignment for labels
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum LabelAlign {
@@ -183,6 +204,18 @@ pub struct AxisConfig {
     pub band_offset: f64,
     /// Enhanced grid configuration
     pub grid_config: GridConfig,
+    /// Font size for labels
+    pub font_size: f64,
+    /// Base direction of label text
+    pub text_direction: TextDirection,
+    /// Label writing mode (vertical upright for CJK y-axis labels)
+    pub writing_mode: WritingMode,
+    /// Maximum label extent along the text flow; longer labels are truncated
+    pub max_label_width: Option<f64>,
+    /// Text measurer (None = [`ApproxTextMeasurer`])
+    pub text_measurer: Option<Arc<dyn TextMeasurer>>,
+    /// Axis title (e.g. from [`Dataset::axis_title`](crate::data::Dataset::axis_title))
+    pub title: Option<String>,
 }
 
 impl Default for AxisConfig {
@@ -203,6 +236,12 @@ impl Default for AxisConfig {
             text_anchor: None,
             band_offset: 0.0,
             grid_config: GridConfig::default(),
+            font_size: 12.0,
+            text_direction: TextDirection::Ltr,
+            writing_mode: WritingMode::Horizontal,
+            max_label_width: None,
+            text_measurer: None,
+            title: None,
         }
     }
 }
@@ -305,6 +344,12 @@ impl AxisConfig {
         self
     }
 
+    /// Aim for one tick every `pixels` instead of a fixed count
+    pub fn with_tick_spacing(mut self, pixels: f64) -> Self {
+        self.tick_options = self.tick_options.with_pixel_spacing(pixels);
+        self
+    }
+
     /// Set label rotation
     pub fn with_label_rotation(mut self, rotation: LabelRotation) -> Self {
         self.label_rotation = rotation;
@@ -329,6 +374,42 @@ impl AxisConfig {
         self
     }
 
+    /// Set the label font size
+    pub fn with_font_size(mut self, size: f64) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set the base direction of label text
+    pub fn with_text_direction(mut self, direction: TextDirection) -> Self {
+        self.text_direction = direction;
+        self
+    }
+
+    /// Stack label glyphs vertically (for CJK labels on y-axes)
+    pub fn with_vertical_text(mut self) -> Self {
+        self.writing_mode = WritingMode::VerticalUpright;
+        self
+    }
+
+    /// Truncate labels longer than a maximum extent, with an ellipsis
+    pub fn with_max_label_width(mut self, width: f64) -> Self {
+        self.max_label_width = Some(width);
+        self
+    }
+
+    /// Set the axis title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the text measurer used for label sizes and truncation
+    pub fn with_text_measurer(mut self, measurer: Arc<dyn TextMeasurer>) -> Self {
+        self.text_measurer = Some(measurer);
+        self
+    }
+
     /// Set band offset (for centering ticks on bands)
     pub fn with_band_offset(mut self, offset: f64) -> Self {
         self.band_offset = offset;
@@ -384,6 +465,15 @@ pub struct AxisTick {
     pub text_anchor: TextAnchor,
     /// Whether this is a minor tick
     pub is_minor: bool,
+    /// Measured label size (width, height)
+    pub label_size: (f64, f64),
+    /// Resolved direction of the label text
+    ///
+    /// `text_anchor` is physical; use [`TextAnchor::for_direction`] with text
+    /// engines that interpret anchors logically.
+    pub text_direction: TextDirection,
+    /// Label writing mode
+    pub writing_mode: WritingMode,
 }
 
 /// Computed axis layout ready for rendering
@@ -409,6 +499,49 @@ pub struct AxisLayout {
     pub label_align: LabelAlign,
     /// Grid configuration
     pub grid_config: GridConfig,
+    /// Axis title
+    pub title: Option<String>,
+}
+
+impl AxisLayout {
+    /// Compute grid lines and bands from the major ticks
+    ///
+    /// `cross` is the pixel extent the grid spans perpendicular to the axis.
+    pub fn grid_geometry(&self, cross: (f64, f64)) -> GridGeometry {
+        let ticks: Vec<(f64, f64)> = self
+            .ticks
+            .iter()
+            .filter(|t| !t.is_minor)
+            .map(|t| (t.tick.value, t.position))
+            .collect();
+        self.grid_config.compute(&ticks, self.orientation, cross)
+    }
+}
+
+/// Label for a group of bands in the second row of a grouped axis
+#[derive(Clone, Debug)]
+pub struct AxisGroupLabel {
+    /// Group name
+    pub label: String,
+    /// Pixel extent of the group along the axis
+    pub extent: (f64, f64),
+    /// Position of the group center along the axis
+    pub position: f64,
+    /// Position for the label
+    pub label_position: (f64, f64),
+    /// Measured label size (width, height)
+    pub label_size: (f64, f64),
+}
+
+/// Axis layout for a [`GroupedBandScale`]: item labels plus a group row
+#[derive(Clone, Debug)]
+pub struct GroupedAxisLayout {
+    /// Layout of the inner category ticks and labels (first row)
+    pub items: AxisLayout,
+    /// Group labels (second row)
+    pub groups: Vec<AxisGroupLabel>,
+    /// Separator lines between groups, as (start, end) points spanning both rows
+    pub separators: Vec<((f64, f64), (f64, f64))>,
 }
 
 /// Axis instance that computes layout from scale
@@ -479,15 +612,26 @@ impl Axis {
     }
 
     /// Update axis from a scale
+    ///
+    /// With a pixel tick spacing the count is derived from the scale's range
+    /// length, so calling this again after a resize keeps the density.
     pub fn set_scale<S: Scale>(&mut self, scale: &S) {
-        self.ticks = scale.ticks(&self.config.tick_options);
-        self.range = scale.range();
+        let options = self.config.tick_options.clone();
+        self.set_scale_with_options(scale, &options);
     }
 
     /// Update axis from a scale with custom tick options
     pub fn set_scale_with_options<S: Scale>(&mut self, scale: &S, options: &TickOptions) {
-        self.ticks = scale.ticks(options);
-        self.range = scale.range();
+        let range = scale.range();
+        self.ticks = scale.ticks(&options.for_length(range.1 - range.0));
+        self.range = range;
+    }
+
+    /// Tick count the configured options give for the current range
+    pub fn tick_count_for_range(&self) -> usize {
+        self.config
+            .tick_options
+            .count_for_length(self.range.1 - self.range.0)
     }
 
     /// Update axis from a band scale
@@ -499,6 +643,16 @@ impl Axis {
         self.config.band_offset = self.bandwidth / 2.0;
     }
 
+    /// Update axis from a grouped band scale
+    ///
+    /// Ticks sit at the band centers already, so no band offset is applied.
+    pub fn set_grouped_band_scale(&mut self, scale: &GroupedBandScale) {
+        self.ticks = scale.ticks(&self.config.tick_options);
+        self.range = scale.range();
+        self.bandwidth = scale.bandwidth();
+        self.config.band_offset = 0.0;
+    }
+
     /// Update axis from a point scale
     pub fn set_point_scale(&mut self, scale: &PointScale) {
         self.ticks = scale.ticks(&self.config.tick_options);
@@ -535,12 +689,24 @@ impl Axis {
         };
 
         // Compute tick layouts
-        let ticks: Vec<AxisTick> = self
+        let mut ticks: Vec<AxisTick> = self
             .ticks
             .iter()
             .map(|tick| self.compute_tick_layout(tick, axis_position, false))
             .collect();
 
+        // Unit formats may share one unit across all ticks
+        if let NumberFormat::Unit(unit) = &self.config.format {
+            let values: Vec<f64> = self.ticks.iter().map(|t| t.value).collect();
+            for (tick, label) in ticks.iter_mut().zip(unit.format_all(&values)) {
+                tick.label = label;
+            }
+        }
+
+        for tick in &mut ticks {
+            self.apply_text_metrics(tick);
+        }
+
         AxisLayout {
             orientation,
             range,
@@ -552,6 +718,91 @@ impl Axis {
             text_anchor: self.config.effective_text_anchor(),
             label_align: self.config.effective_label_align(),
             grid_config: self.config.grid_config.clone(),
+            title: self.config.title.clone(),
+        }
+    }
+
+    /// Compute a two-row layout for a grouped band scale at a given position
+    ///
+    /// The first row is the regular layout of the inner category ticks (set
+    /// with [`set_grouped_band_scale`](Self::set_grouped_band_scale)). Group
+    /// labels are placed `row_gap` beyond the widest item label, centered on
+    /// each group, and separators run from the axis line through both rows
+    /// halfway between adjacent groups.
+    pub fn compute_grouped_layout(
+        &self,
+        scale: &GroupedBandScale,
+        axis_position: f64,
+        row_gap: f64,
+    ) -> GroupedAxisLayout {
+        let items = self.compute_layout(axis_position);
+        let orientation = self.config.orientation;
+        let horizontal = matches!(orientation, AxisOrientation::Bottom | AxisOrientation::Top);
+
+        // Distance from the axis line to the outer edge of the item labels
+        let label_extent = items
+            .ticks
+            .iter()
+            .map(|t| if horizontal { t.label_size.1 } else { t.label_size.0 })
+            .fold(0.0, f64::max);
+        let first_row = self.config.tick_size_inner
+            + self.config.tick_padding
+            + self.config.label_offset
+            + label_extent;
+        let second_row = first_row + row_gap;
+
+        let measurer: &dyn TextMeasurer = match &self.config.text_measurer {
+            Some(m) => m.as_ref(),
+            None => &ApproxTextMeasurer::DEFAULT,
+        };
+        let sign = match orientation {
+            AxisOrientation::Bottom | AxisOrientation::Right => 1.0,
+            AxisOrientation::Top | AxisOrientation::Left => -1.0,
+        };
+        let point = |along: f64, offset: f64| {
+            let across = axis_position + sign * offset;
+            if horizontal {
+                (along, across)
+            } else {
+                (across, along)
+            }
+        };
+
+        let groups = scale
+            .groups()
+            .iter()
+            .enumerate()
+            .filter_map(|(g, group)| {
+                let extent = scale.group_extent(g)?;
+                let position = (extent.0 + extent.1) / 2.0;
+                Some(AxisGroupLabel {
+                    label: group.name.clone(),
+                    extent,
+                    position,
+                    label_position: point(position, second_row),
+                    label_size: measurer.measure_label(
+                        &group.name,
+                        self.config.font_size,
+                        self.config.writing_mode,
+                    ),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let group_extent = groups
+            .iter()
+            .map(|g| if horizontal { g.label_size.1 } else { g.label_size.0 })
+            .fold(0.0, f64::max);
+        let separators = scale
+            .separators()
+            .into_iter()
+            .map(|pos| (point(pos, 0.0), point(pos, second_row + group_extent)))
+            .collect();
+
+        GroupedAxisLayout {
+            items,
+            groups,
+            separators,
         }
     }
 
@@ -635,7 +886,29 @@ impl Axis {
             label_rotation: self.config.label_rotation.angle,
             text_anchor: self.config.effective_text_anchor(),
             is_minor,
+            label_size: (0.0, 0.0),
+            text_direction: TextDirection::Ltr,
+            writing_mode: self.config.writing_mode,
+        }
+    }
+
+    /// Resolve direction, truncate and measure a tick label
+    fn apply_text_metrics(&self, tick: &mut AxisTick) {
+        let measurer: &dyn TextMeasurer = match &self.config.text_measurer {
+            Some(m) => m.as_ref(),
+            None => &ApproxTextMeasurer::DEFAULT,
+        };
+        let font_size = self.config.font_size;
+        let mode = self.config.writing_mode;
+
+        if let Some(max) = self.config.max_label_width {
+            tick.label = match mode {
+                WritingMode::Horizontal => truncate_label(&tick.label, max, font_size, measurer),
+                WritingMode::VerticalUpright => truncate_label_vertical(&tick.label, max, font_size),
+            };
         }
+        tick.text_direction = self.config.text_direction.resolve(&tick.label);
+        tick.label_size = measurer.measure_label(&tick.label, font_size, mode);
     }
 }
 
@@ -644,6 +917,67 @@ mod tests {
     use super::*;
     use crate::scale::{LinearScale, ScaleExt};
 
+    #[test]
+    fn test_grouped_axis_layout() {
+        let scale = GroupedBandScale::new()
+            .group("Q1", ["Jan", "Feb", "Mar"])
+            .group("Q2", ["Apr", "May", "Jun"])
+            .range(0.0, 700.0)
+            .group_padding(1.0);
+        let mut axis = Axis::with_config(AxisConfig::bottom());
+        axis.set_grouped_band_scale(&scale);
+
+        let layout = axis.compute_grouped_layout(&scale, 300.0, 4.0);
+        assert_eq!(layout.items.ticks.len(), 6);
+        assert_eq!(layout.items.ticks[3].label, "Apr");
+        assert_eq!(layout.items.ticks[3].position, 450.0);
+
+        assert_eq!(layout.groups.len(), 2);
+        assert_eq!(layout.groups[1].label, "Q2");
+        assert_eq!(layout.groups[1].extent, (400.0, 700.0));
+        let (gx, gy) = layout.groups[1].label_position;
+        assert_eq!(gx, 550.0);
+        // Second row sits below the item labels
+        assert!(gy > layout.items.ticks[3].label_position.1 + 4.0);
+
+        assert_eq!(layout.separators.len(), 1);
+        let (start, end) = layout.separators[0];
+        assert_eq!(start, (350.0, 300.0));
+        assert_eq!(end.0, 350.0);
+        assert!(end.1 > gy);
+    }
+
+    #[test]
+    fn test_axis_tick_spacing_follows_range() {
+        let config = AxisConfig::bottom().with_tick_spacing(100.0);
+        let mut axis = Axis::with_config(config);
+
+        let narrow = LinearScale::new().with_domain(0.0, 100.0).with_range(0.0, 200.0);
+        axis.set_scale(&narrow);
+        assert_eq!(axis.tick_count_for_range(), 2);
+        let narrow_ticks = axis.ticks().len();
+
+        // Resizing re-derives the count from the new range length
+        let wide = LinearScale::new().with_domain(0.0, 100.0).with_range(0.0, 1000.0);
+        axis.set_scale(&wide);
+        assert_eq!(axis.tick_count_for_range(), 10);
+        assert!(axis.ticks().len() > narrow_ticks);
+        assert_eq!(axis.ticks().len(), 11);
+
+        // Inverted (vertical) ranges use the absolute length
+        let vertical = LinearScale::new().with_domain(0.0, 100.0).with_range(1000.0, 0.0);
+        axis.set_scale(&vertical);
+        assert_eq!(axis.tick_count_for_range(), 10);
+    }
+
+    #[test]
+    fn test_axis_title_from_dataset() {
+        let dataset = crate::data::Dataset::new("Revenue").with_unit("USD");
+        let config = AxisConfig::left().with_title(dataset.axis_title());
+        let axis = Axis::with_config(config);
+        assert_eq!(axis.compute_layout(0.0).title.as_deref(), Some("Revenue (USD)"));
+    }
+
     #[test]
     fn test_axis_orientation() {
         assert!(AxisOrientation::Bottom.is_horizontal());
@@ -754,6 +1088,79 @@ mod tests {
         assert!(has_percent);
     }
 
+    #[test]
+    fn test_axis_shared_unit_format() {
+        use crate::axis::UnitFormat;
+
+        let mut axis = Axis::with_config(
+            AxisConfig::left().with_format(NumberFormat::unit(UnitFormat::bytes().shared())),
+        );
+        axis.set_range((0.0, 100.0));
+        axis.set_ticks(vec![
+            Tick::new(0.0, "").with_position(100.0),
+            Tick::new(524_288.0, "").with_position(50.0),
+            Tick::new(1_048_576.0, "").with_position(0.0),
+        ]);
+
+        let layout = axis.compute_layout(0.0);
+        let labels: Vec<&str> = layout.ticks.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(labels, vec!["0.0 MiB", "0.5 MiB", "1.0 MiB"]);
+    }
+
+    #[test]
+    fn test_axis_label_truncation_and_size() {
+        let mut axis = Axis::with_config(
+            AxisConfig::bottom().with_font_size(10.0).with_max_label_width(30.0),
+        );
+        axis.set_range((0.0, 100.0));
+        axis.set_ticks(vec![
+            Tick::new(0.0, "abcdefgh").with_position(0.0),
+            Tick::new(1.0, "ab").with_position(100.0),
+        ]);
+
+        let layout = axis.compute_layout(0.0);
+        assert_eq!(layout.ticks[0].label, "abcd…");
+        assert_eq!(layout.ticks[1].label, "ab");
+        assert!((layout.ticks[1].label_size.0 - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_axis_rtl_labels() {
+        let mut axis = Axis::with_config(
+            AxisConfig::left().with_text_direction(TextDirection::Auto),
+        );
+        axis.set_range((0.0, 100.0));
+        axis.set_ticks(vec![
+            Tick::new(0.0, "שלום").with_position(0.0),
+            Tick::new(1.0, "Hello").with_position(100.0),
+        ]);
+
+        let layout = axis.compute_layout(0.0);
+        assert_eq!(layout.ticks[0].text_direction, TextDirection::Rtl);
+        assert_eq!(layout.ticks[1].text_direction, TextDirection::Ltr);
+        // Left axis labels end at the tick; logically that is the start for RTL
+        assert_eq!(layout.ticks[0].text_anchor, TextAnchor::End);
+        assert_eq!(
+            layout.ticks[0].text_anchor.for_direction(TextDirection::Rtl),
+            TextAnchor::Start
+        );
+    }
+
+    #[test]
+    fn test_axis_vertical_cjk_labels() {
+        let mut axis = Axis::with_config(
+            AxisConfig::left().with_font_size(12.0).with_vertical_text().with_max_label_width(36.0),
+        );
+        axis.set_range((0.0, 100.0));
+        axis.set_ticks(vec![Tick::new(0.0, "東京都庁舎").with_position(50.0)]);
+
+        let layout = axis.compute_layout(0.0);
+        let tick = &layout.ticks[0];
+        assert_eq!(tick.writing_mode, WritingMode::VerticalUpright);
+        assert_eq!(tick.label, "東京…");
+        assert_eq!(tick.label_size, (12.0, 36.0));
+    }
+
     #[test]
     fn test_set_ticks_directly() {
         let mut axis = Axis::new();
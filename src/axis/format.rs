@@ -5,6 +5,9 @@
 use std::sync::Arc;
 use chrono::{DateTime, Utc, Datelike, Timelike};
 
+use super::unit::UnitFormat;
+use crate::scale::{TimeLocale, TimeZoneRule};
+
 /// Format specifier for numeric axis labels
 #[derive(Clone)]
 pub enum NumberFormat {
@@ -25,6 +28,8 @@ pub enum NumberFormat {
         /// Number of decimal places
         decimals: usize,
     },
+    /// Unit-aware format (bytes, Hz, °C, currency, ...)
+    Unit(UnitFormat),
     /// Custom formatter function
     Custom(Arc<dyn Fn(f64) -> String + Send + Sync>),
 }
@@ -40,6 +45,7 @@ impl std::fmt::Debug for NumberFormat {
             Self::Currency { prefix, decimals } => {
                 write!(f, "Currency {{ prefix: {:?}, decimals: {} }}", prefix, decimals)
             }
+            Self::Unit(u) => write!(f, "Unit({:?})", u),
             Self::Custom(_) => write!(f, "Custom(<fn>)"),
         }
     }
@@ -63,10 +69,22 @@ impl NumberFormat {
             Self::Currency { prefix, decimals } => {
                 format!("{}{:.*}", prefix, *decimals, value)
             }
+            Self::Unit(u) => u.format(value),
             Self::Custom(f) => f(value),
         }
     }
 
+    /// Format a set of values (e.g. all ticks of an axis) together
+    ///
+    /// Unit formats in shared mode pick one unit for the whole set; other
+    /// formats format each value independently.
+    pub fn format_all(&self, values: &[f64]) -> Vec<String> {
+        match self {
+            Self::Unit(u) => u.format_all(values),
+            _ => values.iter().map(|&v| self.format(v)).collect(),
+        }
+    }
+
     /// Create a fixed decimal places format
     pub fn fixed(decimals: usize) -> Self {
         Self::Fixed(decimals)
@@ -85,6 +103,27 @@ impl NumberFormat {
         }
     }
 
+    /// Create a byte-size format with binary prefixes (KiB, MiB, GiB, ...)
+    ///
+    /// ```
+    /// use makepad_d3::axis::NumberFormat;
+    ///
+    /// assert_eq!(NumberFormat::bytes().format(3.0 * 1_048_576.0), "3 MiB");
+    /// ```
+    pub fn bytes() -> Self {
+        Self::Unit(UnitFormat::bytes())
+    }
+
+    /// Create a byte-size format with decimal prefixes (kB, MB, GB, ...)
+    pub fn bytes_decimal() -> Self {
+        Self::Unit(UnitFormat::bytes_decimal())
+    }
+
+    /// Create a unit-aware format
+    pub fn unit(format: UnitFormat) -> Self {
+        Self::Unit(format)
+    }
+
     /// Create a custom formatter
     pub fn custom<F>(f: F) -> Self
     where
@@ -402,6 +441,10 @@ pub struct MultiScaleTimeFormat {
     pub month: String,
     /// Format for year intervals
     pub year: String,
+    /// Locale for month and weekday names (chrono's English names if unset)
+    pub locale: Option<TimeLocale>,
+    /// Time zone labels are shown in
+    pub time_zone: TimeZoneRule,
 }
 
 impl Default for MultiScaleTimeFormat {
@@ -415,6 +458,8 @@ impl Default for MultiScaleTimeFormat {
             week: "%b %d".to_string(),
             month: "%b %Y".to_string(),
             year: "%Y".to_string(),
+            locale: None,
+            time_zone: TimeZoneRule::Utc,
         }
     }
 }
@@ -428,7 +473,11 @@ impl MultiScaleTimeFormat {
     /// Format a datetime based on the interval duration (in milliseconds)
     pub fn format(&self, dt: DateTime<Utc>, interval_ms: f64) -> String {
         let format_str = self.get_format_for_interval(interval_ms);
-        dt.format(&format_str).to_string()
+        let local = self.time_zone.to_local(dt);
+        match &self.locale {
+            Some(locale) => locale.format(local, format_str),
+            None => local.format(format_str).to_string(),
+        }
     }
 
     /// Get the format string for a given interval
@@ -507,6 +556,18 @@ impl MultiScaleTimeFormat {
         self.year = fmt.into();
         self
     }
+
+    /// Set the locale for month and weekday names
+    pub fn with_locale(mut self, locale: TimeLocale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Set the time zone labels are shown in
+    pub fn with_time_zone(mut self, zone: TimeZoneRule) -> Self {
+        self.time_zone = zone;
+        self
+    }
 }
 
 /// Relative time format (e.g., "2 hours ago", "in 3 days")
@@ -698,6 +759,19 @@ mod tests {
         assert_eq!(fmt.month, "%B %Y");
     }
 
+    #[test]
+    fn test_multi_scale_time_format_locale() {
+        // 2024-01-15 14:30:45 UTC
+        let dt = DateTime::from_timestamp(1705329045, 0).unwrap();
+        let fmt = MultiScaleTimeFormat::new()
+            .with_locale(TimeLocale::fr_fr())
+            .with_month("%B %Y")
+            .with_time_zone(TimeZoneRule::us_eastern());
+        assert_eq!(fmt.format(dt, 30.0 * 24.0 * 3600.0 * 1000.0), "janvier 2024");
+        assert_eq!(fmt.format(dt, 3600.0 * 1000.0), "09:30");
+        assert_eq!(fmt.format(dt, 86400.0 * 1000.0), "janv. 15");
+    }
+
     #[test]
     fn test_relative_time_format() {
         let now = DateTime::from_timestamp(1705329045, 0).unwrap();
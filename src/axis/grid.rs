@@ -1,7 +1,10 @@
 //! Grid line configuration and styling
 //!
 //! This module provides configuration for grid lines that extend from axis tick marks
-//! across the chart area.
+//! across the chart area, plus minor lines between ticks and alternating
+//! band shading (zebra stripes).
+
+use super::axis::AxisOrientation;
 
 /// Grid line style
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -30,6 +33,18 @@ impl GridLineStyle {
     }
 }
 
+/// Alternating background shading
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GridBands {
+    /// No shading
+    #[default]
+    None,
+    /// Shade every other interval between major ticks
+    BetweenTicks,
+    /// Shade every other category cell (ticks at category centers)
+    Categories,
+}
+
 /// Configuration for grid lines
 #[derive(Clone, Debug)]
 pub struct GridConfig {
@@ -57,6 +72,14 @@ pub struct GridConfig {
     pub minor_opacity: f64,
     /// Minor grid line width multiplier
     pub minor_width: f64,
+    /// Number of minor lines between adjacent major ticks
+    pub minor_count: usize,
+    /// Alternating band shading mode
+    pub bands: GridBands,
+    /// Band fill color (RGBA)
+    pub band_color: [f64; 4],
+    /// Shade odd intervals instead of even ones
+    pub band_odd: bool,
 }
 
 impl Default for GridConfig {
@@ -74,6 +97,10 @@ impl Default for GridConfig {
             show_minor: false,
             minor_opacity: 0.5,
             minor_width: 0.5,
+            minor_count: 4,
+            bands: GridBands::None,
+            band_color: [0.0, 0.0, 0.0, 0.04],
+            band_odd: false,
         }
     }
 }
@@ -174,6 +201,30 @@ impl GridConfig {
         self
     }
 
+    /// Set the number of minor lines between adjacent major ticks
+    pub fn with_minor_count(mut self, count: usize) -> Self {
+        self.minor_count = count;
+        self
+    }
+
+    /// Set the band shading mode
+    pub fn with_bands(mut self, bands: GridBands) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    /// Set band fill color with alpha (RGBA)
+    pub fn with_band_color(mut self, r: f64, g: f64, b: f64, a: f64) -> Self {
+        self.band_color = [r, g, b, a];
+        self
+    }
+
+    /// Shade odd intervals instead of even ones
+    pub fn with_band_odd(mut self, odd: bool) -> Self {
+        self.band_odd = odd;
+        self
+    }
+
     /// Check if grid is enabled
     pub fn is_enabled(&self) -> bool {
         self.enabled
@@ -259,6 +310,148 @@ pub struct GridLine {
     pub is_minor: bool,
 }
 
+/// A shaded background band
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridBand {
+    /// Top-left corner (x, y)
+    pub min: (f64, f64),
+    /// Bottom-right corner (x, y)
+    pub max: (f64, f64),
+    /// Fill color (RGBA)
+    pub color: [f64; 4],
+    /// Interval or category index the band covers
+    pub index: usize,
+}
+
+/// Grid lines and bands for one axis, ready for rendering
+///
+/// Draw `bands` first, then `lines`.
+#[derive(Clone, Debug, Default)]
+pub struct GridGeometry {
+    /// Major and minor grid lines
+    pub lines: Vec<GridLine>,
+    /// Shaded bands
+    pub bands: Vec<GridBand>,
+}
+
+impl GridConfig {
+    /// Positions of minor lines between adjacent major ticks
+    ///
+    /// `ticks` are (value, pixel position) pairs in axis order. Returns
+    /// evenly spaced (value, position) pairs, excluding the majors.
+    pub fn minor_positions(&self, ticks: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let n = self.minor_count;
+        let mut minors = Vec::with_capacity(ticks.len().saturating_sub(1) * n);
+        for pair in ticks.windows(2) {
+            let ((v0, p0), (v1, p1)) = (pair[0], pair[1]);
+            for k in 1..=n {
+                let t = k as f64 / (n + 1) as f64;
+                minors.push((v0 + (v1 - v0) * t, p0 + (p1 - p0) * t));
+            }
+        }
+        minors
+    }
+
+    /// Compute grid lines and bands for an axis
+    ///
+    /// `ticks` are the major (value, pixel position) pairs along the axis.
+    /// `cross` is the pixel extent the grid spans perpendicular to the axis
+    /// (e.g. the plot's top and bottom for a bottom axis). Nothing is
+    /// produced when the grid is disabled.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::axis::{AxisOrientation, GridBands, GridConfig};
+    ///
+    /// let grid = GridConfig::new()
+    ///     .enabled()
+    ///     .with_minor_grid()
+    ///     .with_minor_count(1)
+    ///     .with_bands(GridBands::BetweenTicks);
+    ///
+    /// let ticks = [(0.0, 0.0), (50.0, 100.0), (100.0, 200.0)];
+    /// let geometry = grid.compute(&ticks, AxisOrientation::Left, (0.0, 400.0));
+    ///
+    /// assert_eq!(geometry.lines.len(), 5); // 3 major + 2 minor
+    /// assert_eq!(geometry.bands.len(), 1); // first interval shaded
+    /// ```
+    pub fn compute(
+        &self,
+        ticks: &[(f64, f64)],
+        orientation: AxisOrientation,
+        cross: (f64, f64),
+    ) -> GridGeometry {
+        let mut geometry = GridGeometry::default();
+        if !self.enabled {
+            return geometry;
+        }
+
+        let vertical = matches!(orientation, AxisOrientation::Bottom | AxisOrientation::Top);
+        let point = |along: f64, across: f64| if vertical { (along, across) } else { (across, along) };
+
+        // Bands first so the lines draw on top
+        let intervals: Vec<(f64, f64)> = match self.bands {
+            GridBands::None => Vec::new(),
+            GridBands::BetweenTicks => ticks.windows(2).map(|w| (w[0].1, w[1].1)).collect(),
+            GridBands::Categories => category_cells(ticks),
+        };
+        let phase = usize::from(self.band_odd);
+        for (index, (a, b)) in intervals.into_iter().enumerate() {
+            if index % 2 != phase {
+                continue;
+            }
+            let (x0, y0) = point(a.min(b), cross.0.min(cross.1));
+            let (x1, y1) = point(a.max(b), cross.0.max(cross.1));
+            geometry.bands.push(GridBand {
+                min: (x0.min(x1), y0.min(y1)),
+                max: (x0.max(x1), y0.max(y1)),
+                color: self.band_color,
+                index,
+            });
+        }
+
+        for &(value, pos) in ticks {
+            geometry.lines.push(GridLine {
+                start: point(pos, cross.0),
+                end: point(pos, cross.1),
+                params: self.get_line_style(value, false),
+                value,
+                is_minor: false,
+            });
+        }
+        if self.show_minor {
+            for (value, pos) in self.minor_positions(ticks) {
+                geometry.lines.push(GridLine {
+                    start: point(pos, cross.0),
+                    end: point(pos, cross.1),
+                    params: self.get_line_style(value, true),
+                    value,
+                    is_minor: true,
+                });
+            }
+        }
+
+        geometry
+    }
+}
+
+/// Cells around category centers: boundaries at midpoints, ends extended by
+/// half a step
+fn category_cells(ticks: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let positions: Vec<f64> = ticks.iter().map(|t| t.1).collect();
+    if positions.len() < 2 {
+        return Vec::new();
+    }
+    let last = positions.len() - 1;
+    let mut bounds = Vec::with_capacity(positions.len() + 1);
+    bounds.push(positions[0] - (positions[1] - positions[0]) / 2.0);
+    for w in positions.windows(2) {
+        bounds.push((w[0] + w[1]) / 2.0);
+    }
+    bounds.push(positions[last] + (positions[last] - positions[last - 1]) / 2.0);
+    bounds.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
 /// Presets for common grid configurations
 impl GridConfig {
     /// Light gray dashed grid
@@ -405,6 +598,65 @@ mod tests {
         assert!(pro.zero_line);
     }
 
+    #[test]
+    fn test_minor_positions() {
+        let config = GridConfig::new().with_minor_count(3);
+        let minors = config.minor_positions(&[(0.0, 0.0), (100.0, 400.0)]);
+        assert_eq!(minors, vec![(25.0, 100.0), (50.0, 200.0), (75.0, 300.0)]);
+    }
+
+    #[test]
+    fn test_compute_lines_orientation() {
+        let config = GridConfig::new().enabled();
+        let ticks = [(0.0, 10.0), (1.0, 20.0)];
+
+        let vertical = config.compute(&ticks, AxisOrientation::Bottom, (0.0, 300.0));
+        assert_eq!(vertical.lines[0].start, (10.0, 0.0));
+        assert_eq!(vertical.lines[0].end, (10.0, 300.0));
+        assert!(vertical.bands.is_empty());
+
+        let horizontal = config.compute(&ticks, AxisOrientation::Right, (0.0, 300.0));
+        assert_eq!(horizontal.lines[1].start, (0.0, 20.0));
+
+        let disabled = GridConfig::new().compute(&ticks, AxisOrientation::Bottom, (0.0, 1.0));
+        assert!(disabled.lines.is_empty());
+    }
+
+    #[test]
+    fn test_compute_minor_styles() {
+        let config = GridConfig::new().enabled().with_minor_grid().with_minor_count(1);
+        let geometry = config.compute(&[(0.0, 0.0), (10.0, 100.0)], AxisOrientation::Left, (0.0, 50.0));
+        let minor: Vec<&GridLine> = geometry.lines.iter().filter(|l| l.is_minor).collect();
+        assert_eq!(minor.len(), 1);
+        assert_eq!(minor[0].value, 5.0);
+        assert_eq!(minor[0].params.width, config.effective_minor_width());
+    }
+
+    #[test]
+    fn test_bands_between_ticks() {
+        let ticks = [(0.0, 0.0), (1.0, 100.0), (2.0, 200.0), (3.0, 300.0)];
+        let config = GridConfig::new().enabled().with_bands(GridBands::BetweenTicks);
+        let bands = config.compute(&ticks, AxisOrientation::Left, (0.0, 50.0)).bands;
+        assert_eq!(bands.len(), 2);
+        assert_eq!((bands[0].min, bands[0].max), ((0.0, 0.0), (50.0, 100.0)));
+        assert_eq!(bands[1].index, 2);
+
+        let odd = config.with_band_odd(true).compute(&ticks, AxisOrientation::Left, (0.0, 50.0));
+        assert_eq!(odd.bands.len(), 1);
+        assert_eq!(odd.bands[0].index, 1);
+    }
+
+    #[test]
+    fn test_bands_categories() {
+        // Category centers 50px apart
+        let ticks = [(0.0, 25.0), (1.0, 75.0), (2.0, 125.0)];
+        let config = GridConfig::new().enabled().with_bands(GridBands::Categories);
+        let bands = config.compute(&ticks, AxisOrientation::Bottom, (200.0, 0.0)).bands;
+        assert_eq!(bands.len(), 2);
+        assert_eq!((bands[0].min, bands[0].max), ((0.0, 0.0), (50.0, 200.0)));
+        assert_eq!((bands[1].min, bands[1].max), ((100.0, 0.0), (150.0, 200.0)));
+    }
+
     #[test]
     fn test_grid_line_params() {
         let config = GridConfig::new()
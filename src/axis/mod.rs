@@ -7,10 +7,14 @@
 //!
 //! - **Orientation**: Bottom, Top, Left, Right axis placement
 //! - **Label Rotation**: Support for diagonal and vertical labels on crowded axes
-//! - **Grid Lines**: Configurable grid with styles (solid, dashed, dotted)
+//! - **Grid Lines**: Configurable grid with styles (solid, dashed, dotted), minor lines and zebra bands
 //! - **Minor Ticks**: Sub-division ticks between major ticks
 //! - **Time Formatting**: Multi-scale time formatting for time-series charts
+//! - **Unit Formatting**: Bytes, bit rates, frequency, mass, temperature and currency
+//! - **Text Metrics**: Pluggable text measurement, RTL labels, vertical CJK labels, truncation
+//! - **Number Parsing**: Parse formatted strings (`"1.2k"`, `"45%"`, `"$1.5M"`) back to values
 //! - **Discrete Scale Support**: Integration with BandScale and PointScale
+//! - **Grouped Labels**: Two label rows and group separators for GroupedBandScale
 //!
 //! # Example
 //! ```
@@ -52,6 +56,9 @@
 
 mod axis;
 mod format;
+mod unit;
+mod parse;
+mod text;
 mod tick;
 mod grid;
 
@@ -59,6 +66,7 @@ mod grid;
 pub use axis::{
     Axis, AxisConfig, AxisLayout, AxisOrientation, AxisTick,
     TextAnchor, LabelAlign, LabelRotation,
+    AxisGroupLabel, GroupedAxisLayout,
 };
 
 // Number and time formatting
@@ -68,6 +76,24 @@ pub use format::{
     timestamp_from_ms, timestamp_to_ms, format_relative,
 };
 
+// Unit-aware formatting
+pub use unit::{
+    Unit, UnitFormat, UnitMode, TemperatureScale,
+};
+
+// Text metrics
+pub use text::{
+    TextMeasurer, ApproxTextMeasurer, TextDirection, WritingMode,
+    graphemes, truncate_label, truncate_label_vertical, truncate_end, truncate_middle,
+    wrap_text, wrap_text_clamped, ELLIPSIS,
+};
+pub(crate) use text::break_opportunities;
+
+// Number parsing
+pub use parse::{
+    parse_number, NumberLocale, NumberParseError, NumberParseErrorKind,
+};
+
 // Enhanced tick configuration
 pub use tick::{
     TickConfig, TickFilter, TickResult, MinorTick,
@@ -77,4 +103,5 @@ pub use tick::{
 // Grid configuration
 pub use grid::{
     GridConfig, GridLineStyle, GridLineParams, GridLine,
+    GridBands, GridBand, GridGeometry,
 };
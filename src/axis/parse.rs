@@ -0,0 +1,537 @@
+//! Number parsing utilities
+//!
+//! The inverse of the number formatters: turns strings such as `"1.2k"`,
+//! `"3,400"`, `"45%"` or `"$1.5M"` back into `f64` values. Errors carry the
+//! character position of the problem so that data import can report exactly
+//! where a cell failed to parse.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::axis::{parse_number, NumberLocale};
+//!
+//! let en = NumberLocale::en_us();
+//! assert_eq!(parse_number("3,400", &en).unwrap(), 3400.0);
+//! assert_eq!(parse_number("45%", &en).unwrap(), 0.45);
+//! assert_eq!(parse_number("$1.5M", &en).unwrap(), 1_500_000.0);
+//!
+//! let de = NumberLocale::de_de();
+//! assert_eq!(parse_number("1.234,5 €", &de).unwrap(), 1234.5);
+//!
+//! let err = parse_number("12x4", &en).unwrap_err();
+//! assert_eq!(err.position, 2);
+//! ```
+
+use crate::error::D3Error;
+
+/// Separators and symbols used when parsing numbers
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberLocale {
+    /// Decimal separator
+    pub decimal: char,
+    /// Grouping (thousands) separator
+    pub group: char,
+    /// Recognized currency symbols (prefix or suffix)
+    pub currency: Vec<String>,
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self::en_us()
+    }
+}
+
+impl NumberLocale {
+    /// English (US): `1,234.5`
+    pub fn en_us() -> Self {
+        Self {
+            decimal: '.',
+            group: ',',
+            currency: default_currency_symbols(),
+        }
+    }
+
+    /// German: `1.234,5`
+    pub fn de_de() -> Self {
+        Self {
+            decimal: ',',
+            group: '.',
+            currency: default_currency_symbols(),
+        }
+    }
+
+    /// French: `1 234,5`
+    pub fn fr_fr() -> Self {
+        Self {
+            decimal: ',',
+            group: ' ',
+            currency: default_currency_symbols(),
+        }
+    }
+
+    /// Add a recognized currency symbol
+    pub fn with_currency(mut self, symbol: impl Into<String>) -> Self {
+        self.currency.push(symbol.into());
+        // Longest symbols first so "US$" wins over "$"
+        self.currency.sort_by_key(|s| std::cmp::Reverse(s.chars().count()));
+        self
+    }
+
+    /// Format a value with this locale's separators
+    ///
+    /// With `decimals` the value is rounded to that many places, otherwise
+    /// the shortest exact representation is used. `grouping` inserts the
+    /// group separator every three integer digits. Round-trips through
+    /// [`parse_number`].
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::axis::NumberLocale;
+    ///
+    /// assert_eq!(NumberLocale::de_de().format(-1234.5, Some(2), true), "-1.234,50");
+    /// assert_eq!(NumberLocale::en_us().format(0.125, None, false), "0.125");
+    /// ```
+    pub fn format(&self, value: f64, decimals: Option<usize>, grouping: bool) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let plain = match decimals {
+            Some(d) => format!("{:.*}", d, value),
+            None => value.to_string(),
+        };
+        let (sign, digits) = match plain.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", plain.as_str()),
+        };
+        let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let mut out = String::from(sign);
+        for (i, c) in int.chars().enumerate() {
+            if grouping && i > 0 && (int.len() - i) % 3 == 0 {
+                out.push(self.group);
+            }
+            out.push(c);
+        }
+        if !frac.is_empty() {
+            out.push(self.decimal);
+            out.push_str(frac);
+        }
+        out
+    }
+
+    fn is_group(&self, c: char) -> bool {
+        c == self.group || (self.group == ' ' && (c == '\u{a0}' || c == '\u{202f}'))
+    }
+}
+
+fn default_currency_symbols() -> Vec<String> {
+    ["$", "€", "£", "¥", "₹"].iter().map(|s| s.to_string()).collect()
+}
+
+/// Kind of number parse failure
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NumberParseErrorKind {
+    /// The input is empty or only whitespace
+    Empty,
+    /// No digits where a number was expected
+    MissingDigits,
+    /// A character that cannot appear at this position
+    InvalidCharacter(char),
+    /// A grouping separator in the wrong place (e.g. `"1,23"`)
+    MisplacedGroupSeparator,
+    /// More than one decimal separator
+    MultipleDecimalSeparators,
+    /// More than one sign, percent, suffix or currency symbol
+    Duplicate(char),
+}
+
+/// Number parse failure with the character position of the problem
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberParseError {
+    /// The original input
+    pub input: String,
+    /// Character offset of the problem within the input
+    pub position: usize,
+    /// What went wrong
+    pub kind: NumberParseErrorKind,
+}
+
+impl std::fmt::Display for NumberParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = match &self.kind {
+            NumberParseErrorKind::Empty => "empty input".to_string(),
+            NumberParseErrorKind::MissingDigits => "expected digits".to_string(),
+            NumberParseErrorKind::InvalidCharacter(c) => format!("invalid character {:?}", c),
+            NumberParseErrorKind::MisplacedGroupSeparator => {
+                "misplaced grouping separator".to_string()
+            }
+            NumberParseErrorKind::MultipleDecimalSeparators => {
+                "multiple decimal separators".to_string()
+            }
+            NumberParseErrorKind::Duplicate(c) => format!("duplicate {:?}", c),
+        };
+        write!(f, "{} at position {} in {:?}", what, self.position, self.input)
+    }
+}
+
+impl std::error::Error for NumberParseError {}
+
+impl From<NumberParseError> for D3Error {
+    fn from(err: NumberParseError) -> Self {
+        D3Error::parse_error(err.to_string())
+    }
+}
+
+/// Parse a formatted number back into a value
+///
+/// Accepts an optional sign, a currency symbol before or after the number,
+/// grouping separators (groups of three digits), a decimal separator, an
+/// exponent, and an SI suffix (`p n μ u m k K M G B T`) or `%`.
+pub fn parse_number(input: &str, locale: &NumberLocale) -> Result<f64, NumberParseError> {
+    Parser::new(input, locale).parse()
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+    locale: &'a NumberLocale,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str, locale: &'a NumberLocale) -> Self {
+        Self {
+            input,
+            chars: input.chars().collect(),
+            pos: 0,
+            locale,
+        }
+    }
+
+    fn error(&self, position: usize, kind: NumberParseErrorKind) -> NumberParseError {
+        NumberParseError {
+            input: self.input.to_string(),
+            position,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consume a currency symbol at the current position, if any
+    fn eat_currency(&mut self) -> bool {
+        for symbol in &self.locale.currency {
+            let len = symbol.chars().count();
+            if self.pos + len <= self.chars.len()
+                && self.chars[self.pos..self.pos + len].iter().copied().eq(symbol.chars())
+            {
+                self.pos += len;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Consume a sign, returning -1.0, 1.0, or None
+    fn eat_sign(&mut self) -> Option<f64> {
+        match self.peek() {
+            Some('-') | Some('\u{2212}') => {
+                self.pos += 1;
+                Some(-1.0)
+            }
+            Some('+') => {
+                self.pos += 1;
+                Some(1.0)
+            }
+            _ => None,
+        }
+    }
+
+    fn parse(mut self) -> Result<f64, NumberParseError> {
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Err(self.error(0, NumberParseErrorKind::Empty));
+        }
+
+        // Prefix: sign and currency in either order ("-$5", "$-5")
+        let mut sign = self.eat_sign();
+        let mut currency = self.eat_currency();
+        if currency {
+            self.skip_whitespace();
+            if let Some(s) = self.eat_sign() {
+                if sign.is_some() {
+                    return Err(self.error(self.pos - 1, NumberParseErrorKind::Duplicate(self.chars[self.pos - 1])));
+                }
+                sign = Some(s);
+            }
+        }
+
+        let mantissa = self.parse_mantissa()?;
+        let exponent = self.parse_exponent()?;
+        let mut value = mantissa * 10f64.powi(exponent);
+
+        // Suffixes: SI prefix or percent, then an optional trailing currency
+        let mut scaled = false;
+        loop {
+            let start = self.pos;
+            self.skip_whitespace();
+            let Some(c) = self.peek() else { break };
+
+            if let Some(mult) = suffix_multiplier(c) {
+                if scaled {
+                    return Err(self.error(self.pos, NumberParseErrorKind::Duplicate(c)));
+                }
+                value *= mult;
+                scaled = true;
+                self.pos += 1;
+            } else if self.eat_currency() {
+                if currency {
+                    return Err(self.error(start, NumberParseErrorKind::Duplicate(c)));
+                }
+                currency = true;
+            } else {
+                let kind = if c == self.locale.decimal {
+                    NumberParseErrorKind::MultipleDecimalSeparators
+                } else if self.locale.is_group(c) {
+                    NumberParseErrorKind::MisplacedGroupSeparator
+                } else {
+                    NumberParseErrorKind::InvalidCharacter(c)
+                };
+                return Err(self.error(self.pos, kind));
+            }
+        }
+
+        Ok(sign.unwrap_or(1.0) * value)
+    }
+
+    /// Digits with grouping and decimal separators
+    fn parse_mantissa(&mut self) -> Result<f64, NumberParseError> {
+        let start = self.pos;
+        let mut normalized = String::new();
+        let mut group_len: Option<usize> = None; // digits since last group separator
+        let mut int_digits = 0;
+        let mut frac_digits = 0;
+        let mut in_fraction = false;
+
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() {
+                normalized.push(c);
+                if in_fraction {
+                    frac_digits += 1;
+                } else {
+                    int_digits += 1;
+                    if let Some(n) = group_len.as_mut() {
+                        *n += 1;
+                        if *n > 3 {
+                            return Err(self.error(self.pos, NumberParseErrorKind::MisplacedGroupSeparator));
+                        }
+                    }
+                }
+                self.pos += 1;
+            } else if c == self.locale.decimal {
+                if in_fraction {
+                    return Err(self.error(self.pos, NumberParseErrorKind::MultipleDecimalSeparators));
+                }
+                if group_len.is_some_and(|n| n != 3) {
+                    return Err(self.error(self.pos, NumberParseErrorKind::MisplacedGroupSeparator));
+                }
+                in_fraction = true;
+                normalized.push('.');
+                self.pos += 1;
+            } else if self.locale.is_group(c) && !in_fraction {
+                // A separator must be followed by a digit, otherwise it is not part of the number
+                let next_is_digit = self
+                    .chars
+                    .get(self.pos + 1)
+                    .is_some_and(|d| d.is_ascii_digit());
+                if !next_is_digit && c.is_whitespace() {
+                    break;
+                }
+                let valid_prev = match group_len {
+                    None => (1..=3).contains(&int_digits),
+                    Some(n) => n == 3,
+                };
+                if !valid_prev || !next_is_digit {
+                    return Err(self.error(self.pos, NumberParseErrorKind::MisplacedGroupSeparator));
+                }
+                group_len = Some(0);
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        if group_len.is_some_and(|n| n != 3) {
+            return Err(self.error(self.pos.saturating_sub(1), NumberParseErrorKind::MisplacedGroupSeparator));
+        }
+        if int_digits + frac_digits == 0 {
+            return Err(self.error(start, match self.peek() {
+                Some(c) if !c.is_ascii_digit() && suffix_multiplier(c).is_none() => {
+                    NumberParseErrorKind::InvalidCharacter(c)
+                }
+                _ => NumberParseErrorKind::MissingDigits,
+            }));
+        }
+
+        normalized
+            .parse::<f64>()
+            .map_err(|_| self.error(start, NumberParseErrorKind::MissingDigits))
+    }
+
+    /// Optional exponent (`e3`, `E-6`)
+    fn parse_exponent(&mut self) -> Result<i32, NumberParseError> {
+        if !matches!(self.peek(), Some('e') | Some('E')) {
+            return Ok(0);
+        }
+        let start = self.pos;
+        self.pos += 1;
+        let sign = self.eat_sign().unwrap_or(1.0) as i32;
+        let digits_start = self.pos;
+        let mut exp: i32 = 0;
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            exp = exp.saturating_mul(10).saturating_add(d as i32);
+            self.pos += 1;
+        }
+        if self.pos == digits_start {
+            return Err(self.error(start, NumberParseErrorKind::MissingDigits));
+        }
+        Ok(sign * exp)
+    }
+}
+
+/// Multiplier for an SI or percent suffix
+fn suffix_multiplier(c: char) -> Option<f64> {
+    match c {
+        '%' => Some(0.01),
+        'p' => Some(1e-12),
+        'n' => Some(1e-9),
+        'μ' | 'µ' | 'u' => Some(1e-6),
+        'm' => Some(1e-3),
+        'k' | 'K' => Some(1e3),
+        'M' => Some(1e6),
+        'G' | 'B' => Some(1e9),
+        'T' => Some(1e12),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::{format_si, NumberFormat, UnitFormat};
+
+    fn en(s: &str) -> Result<f64, NumberParseError> {
+        parse_number(s, &NumberLocale::en_us())
+    }
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_plain_numbers() {
+        assert_eq!(en("42").unwrap(), 42.0);
+        assert_eq!(en("-3.5").unwrap(), -3.5);
+        assert_eq!(en("+7").unwrap(), 7.0);
+        assert_eq!(en(".5").unwrap(), 0.5);
+        assert_eq!(en("  12  ").unwrap(), 12.0);
+        assert_eq!(en("1.5e3").unwrap(), 1500.0);
+        assert_eq!(en("\u{2212}2").unwrap(), -2.0);
+    }
+
+    #[test]
+    fn test_grouping() {
+        assert_eq!(en("3,400").unwrap(), 3400.0);
+        assert_eq!(en("1,234,567.89").unwrap(), 1_234_567.89);
+        assert_eq!(parse_number("1 234,5", &NumberLocale::fr_fr()).unwrap(), 1234.5);
+        assert_eq!(parse_number("1.234.567", &NumberLocale::de_de()).unwrap(), 1_234_567.0);
+    }
+
+    #[test]
+    fn test_locale_format_round_trips() {
+        let fr = NumberLocale::fr_fr();
+        assert_eq!(fr.format(1234567.25, None, true), "1 234 567,25");
+        assert_eq!(NumberLocale::en_us().format(999.0, Some(0), true), "999");
+        for locale in [NumberLocale::en_us(), NumberLocale::de_de(), fr] {
+            let text = locale.format(-98765.4321, Some(3), true);
+            assert_eq!(parse_number(&text, &locale).unwrap(), -98765.432);
+        }
+    }
+
+    #[test]
+    fn test_suffixes() {
+        assert!(approx(en("1.2K").unwrap(), 1200.0));
+        assert!(approx(en("1.2k").unwrap(), 1200.0));
+        assert!(approx(en("3M").unwrap(), 3e6));
+        assert!(approx(en("2.5G").unwrap(), 2.5e9));
+        assert!(approx(en("5m").unwrap(), 0.005));
+        assert!(approx(en("45%").unwrap(), 0.45));
+        assert!(approx(en("12 μ").unwrap(), 12e-6));
+    }
+
+    #[test]
+    fn test_currency() {
+        assert!(approx(en("$1.5M").unwrap(), 1.5e6));
+        assert!(approx(en("-$2,000").unwrap(), -2000.0));
+        assert!(approx(en("$-2,000").unwrap(), -2000.0));
+        assert!(approx(parse_number("1.234,50 €", &NumberLocale::de_de()).unwrap(), 1234.5));
+        let chf = NumberLocale::en_us().with_currency("CHF");
+        assert!(approx(parse_number("CHF 99.90", &chf).unwrap(), 99.9));
+    }
+
+    #[test]
+    fn test_round_trip_with_formatters() {
+        for v in [1230.0, 0.0042, 5.5e6, -12.5] {
+            assert!(approx(en(&format_si(v)).unwrap(), v), "{}", format_si(v));
+        }
+        let percent = NumberFormat::Percent.format(0.25);
+        assert!(approx(en(&percent).unwrap(), 0.25));
+        let currency = NumberFormat::currency("$", 2).format(19.99);
+        assert!(approx(en(&currency).unwrap(), 19.99));
+        let unit = UnitFormat::currency("$").format(3_400_000.0);
+        assert!(approx(en(&unit).unwrap(), 3.4e6));
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let err = en("").unwrap_err();
+        assert_eq!(err.kind, NumberParseErrorKind::Empty);
+
+        let err = en("12x4").unwrap_err();
+        assert_eq!(err.position, 2);
+        assert_eq!(err.kind, NumberParseErrorKind::InvalidCharacter('x'));
+
+        let err = en("1,23").unwrap_err();
+        assert_eq!(err.kind, NumberParseErrorKind::MisplacedGroupSeparator);
+
+        let err = en("1.2.3").unwrap_err();
+        assert_eq!(err.position, 3);
+        assert_eq!(err.kind, NumberParseErrorKind::MultipleDecimalSeparators);
+
+        let err = en("abc").unwrap_err();
+        assert_eq!(err.position, 0);
+
+        let err = en("5kk").unwrap_err();
+        assert_eq!(err.position, 2);
+        assert_eq!(err.kind, NumberParseErrorKind::Duplicate('k'));
+
+        let err = en("1e").unwrap_err();
+        assert_eq!(err.position, 1);
+    }
+
+    #[test]
+    fn test_error_display_and_conversion() {
+        let err = en("12x4").unwrap_err();
+        assert_eq!(err.to_string(), "invalid character 'x' at position 2 in \"12x4\"");
+        let d3: D3Error = err.into();
+        assert!(matches!(d3, D3Error::ParseError { .. }));
+    }
+}
@@ -0,0 +1,554 @@
+//! Text metrics for label layout
+//!
+//! Separates text measurement from layout so that axes and legends can handle
+//! right-to-left scripts, wide CJK glyphs, and combining characters instead of
+//! assuming fixed-width Latin text.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::axis::{ApproxTextMeasurer, TextDirection, TextMeasurer, truncate_label};
+//!
+//! let measurer = ApproxTextMeasurer::default();
+//!
+//! // CJK glyphs are measured as full-width
+//! assert!(measurer.measure("東京", 12.0) > measurer.measure("ab", 12.0));
+//!
+//! // Truncation never splits a grapheme cluster
+//! let label = truncate_label("Revenue by region", 50.0, 12.0, &measurer);
+//! assert!(label.ends_with('…'));
+//!
+//! assert_eq!(TextDirection::detect("שלום"), TextDirection::Rtl);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Ellipsis appended to truncated labels
+pub const ELLIPSIS: &str = "…";
+
+/// Base direction of label text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextDirection {
+    /// Left-to-right
+    #[default]
+    Ltr,
+    /// Right-to-left
+    Rtl,
+    /// Detect from the first strong character of each label
+    Auto,
+}
+
+impl TextDirection {
+    /// Detect the direction from the first strong directional character
+    ///
+    /// Returns [`TextDirection::Ltr`] if no strong character is found.
+    pub fn detect(text: &str) -> TextDirection {
+        for c in text.chars() {
+            if is_rtl_char(c) {
+                return TextDirection::Rtl;
+            }
+            if c.is_alphabetic() {
+                return TextDirection::Ltr;
+            }
+        }
+        TextDirection::Ltr
+    }
+
+    /// Resolve `Auto` for a specific label
+    pub fn resolve(self, text: &str) -> TextDirection {
+        match self {
+            TextDirection::Auto => TextDirection::detect(text),
+            other => other,
+        }
+    }
+
+    /// Check if this direction is right-to-left
+    pub fn is_rtl(self) -> bool {
+        self == TextDirection::Rtl
+    }
+}
+
+/// How label glyphs are laid out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WritingMode {
+    /// Glyphs flow horizontally
+    #[default]
+    Horizontal,
+    /// Upright glyphs stacked top to bottom (vertical CJK labels)
+    VerticalUpright,
+}
+
+/// Measures text for layout
+///
+/// Implement this to use real font metrics from the renderer; the default
+/// [`ApproxTextMeasurer`] estimates widths from character classes.
+pub trait TextMeasurer: std::fmt::Debug + Send + Sync {
+    /// Advance width of a single grapheme cluster
+    fn grapheme_width(&self, grapheme: &str, font_size: f64) -> f64;
+
+    /// Width of a string laid out horizontally
+    fn measure(&self, text: &str, font_size: f64) -> f64 {
+        graphemes(text)
+            .into_iter()
+            .map(|g| self.grapheme_width(g, font_size))
+            .sum()
+    }
+
+    /// Height of a line of text
+    fn line_height(&self, font_size: f64) -> f64 {
+        font_size * 1.2
+    }
+
+    /// Size (width, height) of a label in the given writing mode
+    fn measure_label(&self, text: &str, font_size: f64, mode: WritingMode) -> (f64, f64) {
+        match mode {
+            WritingMode::Horizontal => (self.measure(text, font_size), self.line_height(font_size)),
+            WritingMode::VerticalUpright => {
+                let clusters = graphemes(text);
+                let width = clusters
+                    .iter()
+                    .map(|g| self.grapheme_width(g, font_size))
+                    .fold(0.0, f64::max);
+                (width, clusters.len() as f64 * font_size)
+            }
+        }
+    }
+}
+
+/// Width estimate based on character classes
+///
+/// Narrow characters use `char_width` em; CJK, fullwidth forms and emoji
+/// use `wide_width` em.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ApproxTextMeasurer {
+    /// Width of a narrow character in em
+    pub char_width: f64,
+    /// Width of a wide character in em
+    pub wide_width: f64,
+}
+
+impl ApproxTextMeasurer {
+    /// Default widths: 0.6 em narrow, 1 em wide
+    pub const DEFAULT: Self = Self {
+        char_width: 0.6,
+        wide_width: 1.0,
+    };
+}
+
+impl Default for ApproxTextMeasurer {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl TextMeasurer for ApproxTextMeasurer {
+    fn grapheme_width(&self, grapheme: &str, font_size: f64) -> f64 {
+        match grapheme.chars().next() {
+            Some(c) if is_wide_char(c) => self.wide_width * font_size,
+            Some(_) => self.char_width * font_size,
+            None => 0.0,
+        }
+    }
+}
+
+/// Split text into grapheme clusters
+///
+/// Handles combining marks, variation selectors, emoji modifiers, ZWJ
+/// sequences and regional-indicator flag pairs. This is a lightweight
+/// approximation of the Unicode segmentation rules.
+pub fn graphemes(text: &str) -> Vec<&str> {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+    let mut regional_run = 0;
+
+    for (i, c) in text.char_indices() {
+        let joins = match prev {
+            None => false,
+            Some(p) => {
+                is_extending_char(c)
+                    || p == '\u{200D}'
+                    || (is_regional_indicator(c) && is_regional_indicator(p) && regional_run % 2 == 1)
+            }
+        };
+        if !joins && i > start {
+            clusters.push(&text[start..i]);
+            start = i;
+        }
+        regional_run = if is_regional_indicator(c) { regional_run + 1 } else { 0 };
+        prev = Some(c);
+    }
+    if start < text.len() {
+        clusters.push(&text[start..]);
+    }
+    clusters
+}
+
+/// Truncate a label to a maximum width, appending an ellipsis
+///
+/// Same as [`truncate_end`].
+pub fn truncate_label(text: &str, max_width: f64, font_size: f64, measurer: &dyn TextMeasurer) -> String {
+    truncate_end(text, max_width, font_size, measurer)
+}
+
+/// Truncate text at the end to a maximum width, appending an ellipsis
+///
+/// Cuts only between grapheme clusters and trims whitespace before the
+/// ellipsis. Returns the text unchanged if it already fits, and an empty
+/// string if not even the ellipsis fits.
+pub fn truncate_end(text: &str, max_width: f64, font_size: f64, measurer: &dyn TextMeasurer) -> String {
+    if measurer.measure(text, font_size) <= max_width {
+        return text.to_string();
+    }
+    ellipsize(text, max_width, font_size, measurer)
+}
+
+/// Truncate text in the middle to a maximum width, keeping both ends
+///
+/// Suits names whose endings matter, such as file paths or IDs that share
+/// a long prefix.
+///
+/// # Example
+/// ```
+/// use makepad_d3::axis::{truncate_middle, ApproxTextMeasurer};
+///
+/// let m = ApproxTextMeasurer::default();
+/// assert_eq!(truncate_middle("sensor-eu-west-0042", 54.0, 10.0, &m), "sens…0042");
+/// ```
+pub fn truncate_middle(text: &str, max_width: f64, font_size: f64, measurer: &dyn TextMeasurer) -> String {
+    if measurer.measure(text, font_size) <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width - measurer.measure(ELLIPSIS, font_size);
+    if budget < 0.0 {
+        return String::new();
+    }
+
+    let clusters = graphemes(text);
+    let (head, head_width) = fit_prefix(clusters.iter().copied(), budget / 2.0, font_size, measurer);
+    let (tail, _) = fit_prefix(
+        clusters[head..].iter().rev().copied(),
+        budget - head_width,
+        font_size,
+        measurer,
+    );
+    let head: String = clusters[..head].concat();
+    let tail: String = clusters[clusters.len() - tail..].concat();
+    format!("{}{}{}", head.trim_end(), ELLIPSIS, tail.trim_start())
+}
+
+/// Break text into lines no wider than `max_width`
+///
+/// Breaks at whitespace and between wide (CJK) characters; explicit
+/// newlines start a new line. Words wider than a whole line are split
+/// between grapheme clusters.
+///
+/// # Example
+/// ```
+/// use makepad_d3::axis::{wrap_text, ApproxTextMeasurer};
+///
+/// let m = ApproxTextMeasurer::default();
+/// // 6 px per character at 10 px: ten characters per line
+/// assert_eq!(wrap_text("The quick brown fox", 60.0, 10.0, &m), vec!["The quick", "brown fox"]);
+/// ```
+pub fn wrap_text(text: &str, max_width: f64, font_size: f64, measurer: &dyn TextMeasurer) -> Vec<String> {
+    let space = measurer.measure(" ", font_size);
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut width = 0.0;
+        for (word, gap) in break_opportunities(paragraph) {
+            let word_width = measurer.measure(word, font_size);
+            let sep = if gap && !line.is_empty() { space } else { 0.0 };
+            if !line.is_empty() && width + sep + word_width > max_width {
+                lines.push(std::mem::take(&mut line));
+                width = 0.0;
+            } else if sep > 0.0 {
+                line.push(' ');
+                width += sep;
+            }
+
+            if word_width <= max_width {
+                line.push_str(word);
+                width += word_width;
+                continue;
+            }
+            // Too long for any line: split between clusters
+            for g in graphemes(word) {
+                let w = measurer.grapheme_width(g, font_size);
+                if !line.is_empty() && width + w > max_width {
+                    lines.push(std::mem::take(&mut line));
+                    width = 0.0;
+                }
+                line.push_str(g);
+                width += w;
+            }
+        }
+        // Blank paragraphs keep their empty line
+        if !line.is_empty() || paragraph.trim().is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+/// Wrap text into at most `max_lines` lines, ellipsizing the last one
+///
+/// # Example
+/// ```
+/// use makepad_d3::axis::{wrap_text_clamped, ApproxTextMeasurer};
+///
+/// let m = ApproxTextMeasurer::default();
+/// let lines = wrap_text_clamped("The quick brown fox jumps", 60.0, 2, 10.0, &m);
+/// assert_eq!(lines, vec!["The quick", "brown fox…"]);
+/// ```
+pub fn wrap_text_clamped(
+    text: &str,
+    max_width: f64,
+    max_lines: usize,
+    font_size: f64,
+    measurer: &dyn TextMeasurer,
+) -> Vec<String> {
+    let mut lines = wrap_text(text, max_width, font_size, measurer);
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            *last = ellipsize(last, max_width, font_size, measurer);
+        }
+    }
+    lines
+}
+
+/// Cut text to fit `max_width` including a trailing ellipsis
+fn ellipsize(text: &str, max_width: f64, font_size: f64, measurer: &dyn TextMeasurer) -> String {
+    let budget = max_width - measurer.measure(ELLIPSIS, font_size);
+    if budget < 0.0 {
+        return String::new();
+    }
+    let clusters = graphemes(text);
+    let (count, _) = fit_prefix(clusters.iter().copied(), budget, font_size, measurer);
+    format!("{}{}", clusters[..count].concat().trim_end(), ELLIPSIS)
+}
+
+/// Number and total width of the leading clusters that fit in `budget`
+fn fit_prefix<'a>(
+    clusters: impl Iterator<Item = &'a str>,
+    budget: f64,
+    font_size: f64,
+    measurer: &dyn TextMeasurer,
+) -> (usize, f64) {
+    let mut width = 0.0;
+    let mut count = 0;
+    for g in clusters {
+        let w = measurer.grapheme_width(g, font_size);
+        if width + w > budget {
+            break;
+        }
+        width += w;
+        count += 1;
+    }
+    (count, width)
+}
+
+/// Words of a paragraph, each flagged with whether whitespace precedes it
+///
+/// Wide characters are words of their own, so CJK text can break between
+/// any two characters.
+pub(crate) fn break_opportunities(paragraph: &str) -> Vec<(&str, bool)> {
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut gap = false;
+    let mut offset = 0;
+
+    for g in graphemes(paragraph) {
+        let first = g.chars().next().unwrap_or(' ');
+        if first.is_whitespace() || is_wide_char(first) {
+            if let Some(s) = start.take() {
+                words.push((&paragraph[s..offset], gap));
+                gap = false;
+            }
+            if first.is_whitespace() {
+                gap = true;
+            } else {
+                words.push((g, gap));
+                gap = false;
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+        offset += g.len();
+    }
+    if let Some(s) = start {
+        words.push((&paragraph[s..], gap));
+    }
+    words
+}
+
+/// Truncate a vertically stacked label to a maximum height
+pub fn truncate_label_vertical(text: &str, max_height: f64, font_size: f64) -> String {
+    let clusters = graphemes(text);
+    let max_count = if font_size > 0.0 { (max_height / font_size).floor() as usize } else { 0 };
+    if clusters.len() <= max_count {
+        return text.to_string();
+    }
+    if max_count == 0 {
+        return String::new();
+    }
+    let kept: String = clusters[..max_count - 1].concat();
+    format!("{}{}", kept, ELLIPSIS)
+}
+
+fn is_rtl_char(c: char) -> bool {
+    matches!(c as u32,
+        0x0590..=0x05FF   // Hebrew
+        | 0x0600..=0x06FF // Arabic
+        | 0x0700..=0x074F // Syriac
+        | 0x0750..=0x077F // Arabic Supplement
+        | 0x0780..=0x07BF // Thaana
+        | 0x08A0..=0x08FF // Arabic Extended-A
+        | 0xFB1D..=0xFDFF // Hebrew and Arabic presentation forms
+        | 0xFE70..=0xFEFF // Arabic presentation forms B
+    )
+}
+
+fn is_wide_char(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x115F   // Hangul Jamo
+        | 0x2E80..=0x303E // CJK radicals, punctuation
+        | 0x3041..=0x33FF // Kana, CJK symbols
+        | 0x3400..=0x4DBF // CJK Extension A
+        | 0x4E00..=0x9FFF // CJK Unified Ideographs
+        | 0xA960..=0xA97F // Hangul Jamo Extended-A
+        | 0xAC00..=0xD7A3 // Hangul syllables
+        | 0xF900..=0xFAFF // CJK compatibility ideographs
+        | 0xFE30..=0xFE4F // CJK compatibility forms
+        | 0xFF00..=0xFF60 // Fullwidth forms
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F // Emoji
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD // CJK Extensions B+
+    )
+}
+
+fn is_extending_char(c: char) -> bool {
+    matches!(c as u32,
+        0x0300..=0x036F     // Combining diacritical marks
+        | 0x0483..=0x0489
+        | 0x0591..=0x05BD   // Hebrew points
+        | 0x05BF | 0x05C1 | 0x05C2 | 0x05C4 | 0x05C5 | 0x05C7
+        | 0x0610..=0x061A   // Arabic marks
+        | 0x064B..=0x065F
+        | 0x0670
+        | 0x06D6..=0x06DC
+        | 0x06DF..=0x06E4
+        | 0x0900..=0x0903   // Devanagari signs
+        | 0x093A..=0x094F
+        | 0x0951..=0x0957
+        | 0x0962..=0x0963
+        | 0x0E31 | 0x0E34..=0x0E3A | 0x0E47..=0x0E4E // Thai
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200D            // Zero-width joiner
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F   // Variation selectors
+        | 0xFE20..=0xFE2F
+        | 0x1F3FB..=0x1F3FF // Emoji skin tone modifiers
+        | 0xE0020..=0xE007F // Tags
+        | 0xE0100..=0xE01EF
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c as u32, 0x1F1E6..=0x1F1FF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphemes_combining_and_emoji() {
+        assert_eq!(graphemes("abc"), vec!["a", "b", "c"]);
+        assert_eq!(graphemes("e\u{301}x"), vec!["e\u{301}", "x"]);
+        // Family emoji joined with ZWJ
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+        assert_eq!(graphemes(family), vec![family]);
+        // Two flags
+        let flags = "\u{1F1EF}\u{1F1F5}\u{1F1FA}\u{1F1F8}";
+        assert_eq!(graphemes(flags).len(), 2);
+        // Thumbs up with skin tone
+        assert_eq!(graphemes("\u{1F44D}\u{1F3FD}").len(), 1);
+    }
+
+    #[test]
+    fn test_direction_detection() {
+        assert_eq!(TextDirection::detect("Hello"), TextDirection::Ltr);
+        assert_eq!(TextDirection::detect("مرحبا"), TextDirection::Rtl);
+        assert_eq!(TextDirection::detect("123 שלום"), TextDirection::Rtl);
+        assert_eq!(TextDirection::detect("123"), TextDirection::Ltr);
+        assert_eq!(TextDirection::Auto.resolve("עברית"), TextDirection::Rtl);
+        assert_eq!(TextDirection::Rtl.resolve("abc"), TextDirection::Rtl);
+    }
+
+    #[test]
+    fn test_approx_widths() {
+        let m = ApproxTextMeasurer::default();
+        assert!((m.measure("abcd", 10.0) - 24.0).abs() < 1e-9);
+        assert!((m.measure("東京", 10.0) - 20.0).abs() < 1e-9);
+        // Combining marks do not add width
+        assert!((m.measure("e\u{301}", 10.0) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_vertical_measure() {
+        let m = ApproxTextMeasurer::default();
+        let (w, h) = m.measure_label("東京都", 12.0, WritingMode::VerticalUpright);
+        assert_eq!(w, 12.0);
+        assert_eq!(h, 36.0);
+    }
+
+    #[test]
+    fn test_truncate_label() {
+        let m = ApproxTextMeasurer::default();
+        assert_eq!(truncate_label("short", 100.0, 10.0, &m), "short");
+        // 30px budget: ellipsis (6px) + 4 narrow chars
+        assert_eq!(truncate_label("abcdefgh", 30.0, 10.0, &m), "abcd…");
+        // Whitespace before the ellipsis is trimmed
+        assert_eq!(truncate_label("ab cdefgh", 30.0, 10.0, &m), "ab c…");
+        // Never splits a combining sequence
+        let t = truncate_label("e\u{301}e\u{301}e\u{301}e\u{301}", 20.0, 10.0, &m);
+        assert_eq!(t, "e\u{301}e\u{301}…");
+        assert_eq!(truncate_label("abc", 3.0, 10.0, &m), "");
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        let m = ApproxTextMeasurer::default();
+        assert_eq!(truncate_middle("abcdefghij", 60.0, 10.0, &m), "abcdefghij");
+        // 42px: ellipsis + 18px either side
+        assert_eq!(truncate_middle("abcdefghij", 42.0, 10.0, &m), "abc…hij");
+        assert_eq!(truncate_middle("ab  cdefgh  ij", 42.0, 10.0, &m), "ab…ij");
+        assert_eq!(truncate_middle("abcdefghij", 5.0, 10.0, &m), "");
+        assert_eq!(truncate_end("abcdefgh", 30.0, 10.0, &m), "abcd…");
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        let m = ApproxTextMeasurer::default();
+        // Over-long words are split between clusters
+        assert_eq!(wrap_text("Supercalifragilistic", 60.0, 10.0, &m), vec!["Supercalif", "ragilistic"]);
+        assert_eq!(wrap_text("go Supercalifragilistic", 60.0, 10.0, &m), vec!["go", "Supercalif", "ragilistic"]);
+        // CJK breaks between characters
+        assert_eq!(wrap_text("東京都庁舎", 25.0, 10.0, &m), vec!["東京", "都庁", "舎"]);
+        // Explicit newlines and blank lines survive; runs of spaces collapse
+        assert_eq!(wrap_text("a\n\nb   c", 60.0, 10.0, &m), vec!["a", "", "b c"]);
+        assert!(wrap_text_clamped("a b c", 6.0, 0, 10.0, &m).is_empty());
+    }
+
+    #[test]
+    fn test_truncate_vertical() {
+        assert_eq!(truncate_label_vertical("東京都庁", 48.0, 12.0), "東京都庁");
+        assert_eq!(truncate_label_vertical("東京都庁舎", 36.0, 12.0), "東京…");
+    }
+}
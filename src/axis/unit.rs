@@ -0,0 +1,497 @@
+//! Unit-aware value formatting
+//!
+//! Formats values with a physical or monetary unit, picking a readable
+//! magnitude (e.g. `1.5 MiB` instead of `1572864 B`). Axis labels can either
+//! share one unit across all ticks or choose a unit per tick.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::axis::{UnitFormat, UnitMode};
+//!
+//! let bytes = UnitFormat::bytes();
+//! assert_eq!(bytes.format(1536.0), "1.5 KiB");
+//!
+//! // One unit for the whole axis
+//! let shared = UnitFormat::bytes().with_mode(UnitMode::Shared);
+//! let labels = shared.format_all(&[0.0, 524_288.0, 1_048_576.0, 1_572_864.0]);
+//! assert_eq!(labels, vec!["0.0 MiB", "0.5 MiB", "1.0 MiB", "1.5 MiB"]);
+//!
+//! // Currency with an exchange hook (values in USD, shown in EUR)
+//! let eur = UnitFormat::currency("€").with_conversion(|usd| usd * 0.5);
+//! assert_eq!(eur.format(3_000_000.0), "€1.5M");
+//! ```
+
+use std::sync::Arc;
+
+/// Temperature scale
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TemperatureScale {
+    /// Degrees Celsius
+    Celsius,
+    /// Degrees Fahrenheit
+    Fahrenheit,
+    /// Kelvin
+    Kelvin,
+}
+
+impl TemperatureScale {
+    fn to_celsius(self, value: f64) -> f64 {
+        match self {
+            Self::Celsius => value,
+            Self::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+            Self::Kelvin => value - 273.15,
+        }
+    }
+
+    fn celsius_to(self, value: f64) -> f64 {
+        match self {
+            Self::Celsius => value,
+            Self::Fahrenheit => value * 9.0 / 5.0 + 32.0,
+            Self::Kelvin => value + 273.15,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+            Self::Kelvin => " K",
+        }
+    }
+}
+
+/// A unit family with its magnitude steps
+#[derive(Clone, Debug, PartialEq)]
+pub enum Unit {
+    /// Bytes with binary prefixes (B, KiB, MiB, ...)
+    Bytes,
+    /// Bytes with decimal prefixes (B, kB, MB, ...)
+    BytesDecimal,
+    /// Data rate in bits per second (bit/s, kbit/s, Mbit/s, ...)
+    BitsPerSecond,
+    /// Frequency (Hz, kHz, MHz, ...)
+    Hertz,
+    /// Mass, with values given in grams (mg, g, kg, t)
+    Grams,
+    /// Temperature conversion between scales (no magnitude steps)
+    Temperature {
+        /// Scale of the input values
+        input: TemperatureScale,
+        /// Scale used for display
+        output: TemperatureScale,
+    },
+    /// Currency with the given symbol prefix and K/M/B/T suffixes
+    Currency(String),
+}
+
+const BYTE_STEPS: &[(f64, &str)] = &[
+    (1.0, "B"),
+    (1024.0, "KiB"),
+    (1_048_576.0, "MiB"),
+    (1_073_741_824.0, "GiB"),
+    (1_099_511_627_776.0, "TiB"),
+    (1_125_899_906_842_624.0, "PiB"),
+];
+
+const BYTE_DECIMAL_STEPS: &[(f64, &str)] = &[
+    (1.0, "B"),
+    (1e3, "kB"),
+    (1e6, "MB"),
+    (1e9, "GB"),
+    (1e12, "TB"),
+    (1e15, "PB"),
+];
+
+const BIT_RATE_STEPS: &[(f64, &str)] = &[
+    (1.0, "bit/s"),
+    (1e3, "kbit/s"),
+    (1e6, "Mbit/s"),
+    (1e9, "Gbit/s"),
+    (1e12, "Tbit/s"),
+];
+
+const HERTZ_STEPS: &[(f64, &str)] = &[
+    (1.0, "Hz"),
+    (1e3, "kHz"),
+    (1e6, "MHz"),
+    (1e9, "GHz"),
+    (1e12, "THz"),
+];
+
+const GRAM_STEPS: &[(f64, &str)] = &[
+    (1e-3, "mg"),
+    (1.0, "g"),
+    (1e3, "kg"),
+    (1e6, "t"),
+];
+
+const CURRENCY_STEPS: &[(f64, &str)] = &[
+    (1.0, ""),
+    (1e3, "K"),
+    (1e6, "M"),
+    (1e9, "B"),
+    (1e12, "T"),
+];
+
+impl Unit {
+    /// Magnitude steps as (factor, suffix), ascending
+    fn steps(&self) -> &'static [(f64, &'static str)] {
+        match self {
+            Unit::Bytes => BYTE_STEPS,
+            Unit::BytesDecimal => BYTE_DECIMAL_STEPS,
+            Unit::BitsPerSecond => BIT_RATE_STEPS,
+            Unit::Hertz => HERTZ_STEPS,
+            Unit::Grams => GRAM_STEPS,
+            Unit::Temperature { .. } => &[(1.0, "")],
+            Unit::Currency(_) => CURRENCY_STEPS,
+        }
+    }
+
+    /// Pick the step for a magnitude: the largest factor not exceeding it
+    fn step_for(&self, magnitude: f64) -> (f64, &'static str) {
+        let steps = self.steps();
+        let magnitude = magnitude.abs();
+        if magnitude == 0.0 || !magnitude.is_finite() {
+            return steps
+                .iter()
+                .copied()
+                .find(|(f, _)| *f == 1.0)
+                .unwrap_or(steps[0]);
+        }
+        steps
+            .iter()
+            .rev()
+            .copied()
+            .find(|(f, _)| magnitude >= *f)
+            .unwrap_or(steps[0])
+    }
+
+    fn apply(&self, suffix: &str, number: &str) -> String {
+        match self {
+            Unit::Currency(symbol) => {
+                let (sign, digits) = match number.strip_prefix('-') {
+                    Some(rest) => ("-", rest),
+                    None => ("", number),
+                };
+                format!("{}{}{}{}", sign, symbol, digits, suffix)
+            }
+            Unit::Temperature { output, .. } => format!("{}{}", number, output.symbol()),
+            _ => format!("{} {}", number, suffix),
+        }
+    }
+}
+
+/// How a unit is chosen across a set of values (e.g. axis ticks)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnitMode {
+    /// Each value picks its own unit
+    #[default]
+    PerValue,
+    /// All values share the unit chosen for the largest magnitude
+    Shared,
+}
+
+/// Unit-aware number formatter
+#[derive(Clone)]
+pub struct UnitFormat {
+    /// Unit family
+    pub unit: Unit,
+    /// Per-value or shared unit selection
+    pub mode: UnitMode,
+    /// Fixed decimal places (None = automatic)
+    pub decimals: Option<usize>,
+    conversion: Option<Arc<dyn Fn(f64) -> f64 + Send + Sync>>,
+}
+
+impl std::fmt::Debug for UnitFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnitFormat")
+            .field("unit", &self.unit)
+            .field("mode", &self.mode)
+            .field("decimals", &self.decimals)
+            .field("conversion", &self.conversion.as_ref().map(|_| "<fn>"))
+            .finish()
+    }
+}
+
+impl UnitFormat {
+    /// Create a formatter for a unit
+    pub fn new(unit: Unit) -> Self {
+        Self {
+            unit,
+            mode: UnitMode::PerValue,
+            decimals: None,
+            conversion: None,
+        }
+    }
+
+    /// Bytes with binary prefixes (KiB, MiB, ...)
+    pub fn bytes() -> Self {
+        Self::new(Unit::Bytes)
+    }
+
+    /// Bytes with decimal prefixes (kB, MB, ...)
+    pub fn bytes_decimal() -> Self {
+        Self::new(Unit::BytesDecimal)
+    }
+
+    /// Bits per second
+    pub fn bits_per_second() -> Self {
+        Self::new(Unit::BitsPerSecond)
+    }
+
+    /// Frequency in hertz
+    pub fn hertz() -> Self {
+        Self::new(Unit::Hertz)
+    }
+
+    /// Mass with values in grams
+    pub fn grams() -> Self {
+        Self::new(Unit::Grams)
+    }
+
+    /// Temperature, converting between scales
+    pub fn temperature(input: TemperatureScale, output: TemperatureScale) -> Self {
+        Self::new(Unit::Temperature { input, output })
+    }
+
+    /// Celsius values shown in Celsius
+    pub fn celsius() -> Self {
+        Self::temperature(TemperatureScale::Celsius, TemperatureScale::Celsius)
+    }
+
+    /// Celsius values shown in Fahrenheit
+    pub fn fahrenheit() -> Self {
+        Self::temperature(TemperatureScale::Celsius, TemperatureScale::Fahrenheit)
+    }
+
+    /// Currency with a symbol prefix (e.g. "$", "€")
+    pub fn currency(symbol: impl Into<String>) -> Self {
+        Self::new(Unit::Currency(symbol.into()))
+    }
+
+    /// Set the unit selection mode
+    pub fn with_mode(mut self, mode: UnitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Use one unit for all values
+    pub fn shared(self) -> Self {
+        self.with_mode(UnitMode::Shared)
+    }
+
+    /// Set fixed decimal places
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Convert raw values before formatting (e.g. a currency exchange rate)
+    pub fn with_conversion<F>(mut self, f: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        self.conversion = Some(Arc::new(f));
+        self
+    }
+
+    /// Convert raw values by a constant rate
+    pub fn with_rate(self, rate: f64) -> Self {
+        self.with_conversion(move |v| v * rate)
+    }
+
+    /// Apply the conversion hook and any temperature conversion
+    fn convert(&self, value: f64) -> f64 {
+        let value = match &self.conversion {
+            Some(f) => f(value),
+            None => value,
+        };
+        match self.unit {
+            Unit::Temperature { input, output } => output.celsius_to(input.to_celsius(value)),
+            _ => value,
+        }
+    }
+
+    /// Format a single value, choosing its unit from its own magnitude
+    pub fn format(&self, value: f64) -> String {
+        let converted = self.convert(value);
+        if !converted.is_finite() {
+            return converted.to_string();
+        }
+        let (factor, suffix) = self.unit.step_for(converted);
+        let scaled = converted / factor;
+        let decimals = self.decimals.unwrap_or_else(|| auto_decimals(scaled));
+        let number = trim_number(&format!("{:.1$}", scaled, decimals), self.decimals.is_none());
+        self.unit.apply(suffix, &number)
+    }
+
+    /// Format a set of values according to the unit mode
+    ///
+    /// In [`UnitMode::Shared`] mode the unit is chosen from the largest
+    /// magnitude and all values use the same number of decimals.
+    pub fn format_all(&self, values: &[f64]) -> Vec<String> {
+        match self.mode {
+            UnitMode::PerValue => values.iter().map(|&v| self.format(v)).collect(),
+            UnitMode::Shared => {
+                let converted: Vec<f64> = values.iter().map(|&v| self.convert(v)).collect();
+                let (factor, suffix) = self.unit.step_for(max_magnitude(&converted));
+                let scaled: Vec<f64> = converted.iter().map(|v| v / factor).collect();
+                let decimals = self.decimals.unwrap_or_else(|| shared_decimals(&scaled));
+
+                converted
+                    .iter()
+                    .zip(&scaled)
+                    .map(|(&v, &s)| {
+                        if !v.is_finite() {
+                            return v.to_string();
+                        }
+                        let number = trim_number(&format!("{:.1$}", s, decimals), false);
+                        self.unit.apply(suffix, &number)
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The unit suffix shared by a set of values (for axis titles)
+    pub fn shared_suffix(&self, values: &[f64]) -> String {
+        let converted: Vec<f64> = values.iter().map(|&v| self.convert(v)).collect();
+        let (_, suffix) = self.unit.step_for(max_magnitude(&converted));
+        match &self.unit {
+            Unit::Temperature { output, .. } => output.symbol().trim().to_string(),
+            Unit::Currency(symbol) => format!("{}{}", symbol, suffix),
+            _ => suffix.to_string(),
+        }
+    }
+}
+
+fn max_magnitude(values: &[f64]) -> f64 {
+    values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold(0.0_f64, |m, v| m.max(v.abs()))
+}
+
+/// Decimal places for a single scaled value
+fn auto_decimals(scaled: f64) -> usize {
+    let abs = scaled.abs();
+    if abs >= 100.0 || abs == 0.0 {
+        0
+    } else if abs >= 10.0 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Fewest decimals (up to 3) that represent every scaled value exactly
+fn shared_decimals(scaled: &[f64]) -> usize {
+    (0..=3)
+        .find(|&d| {
+            let factor = 10f64.powi(d as i32);
+            scaled
+                .iter()
+                .filter(|v| v.is_finite())
+                .all(|v| ((v * factor).round() - v * factor).abs() < 1e-6 * factor.max(1.0))
+        })
+        .unwrap_or(3)
+}
+
+/// Trim trailing zeros after the decimal point (when requested) and "-0"
+fn trim_number(s: &str, trim_zeros: bool) -> String {
+    let s = if trim_zeros && s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    };
+    if s.trim_start_matches('-').chars().all(|c| c == '0' || c == '.') {
+        s.trim_start_matches('-').to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_per_value() {
+        let fmt = UnitFormat::bytes();
+        assert_eq!(fmt.format(0.0), "0 B");
+        assert_eq!(fmt.format(512.0), "512 B");
+        assert_eq!(fmt.format(1024.0), "1 KiB");
+        assert_eq!(fmt.format(1_572_864.0), "1.5 MiB");
+        assert_eq!(fmt.format(5.0 * 1_073_741_824.0), "5 GiB");
+    }
+
+    #[test]
+    fn test_decimal_units() {
+        assert_eq!(UnitFormat::bytes_decimal().format(2_500_000.0), "2.5 MB");
+        assert_eq!(UnitFormat::bits_per_second().format(100e6), "100 Mbit/s");
+        assert_eq!(UnitFormat::hertz().format(2.4e9), "2.4 GHz");
+    }
+
+    #[test]
+    fn test_grams() {
+        let fmt = UnitFormat::grams();
+        assert_eq!(fmt.format(0.25), "250 mg");
+        assert_eq!(fmt.format(12.0), "12 g");
+        assert_eq!(fmt.format(1500.0), "1.5 kg");
+        assert_eq!(fmt.format(2e6), "2 t");
+    }
+
+    #[test]
+    fn test_temperature() {
+        assert_eq!(UnitFormat::celsius().format(21.5), "21.5°C");
+        assert_eq!(UnitFormat::fahrenheit().format(100.0), "212°F");
+        assert_eq!(UnitFormat::fahrenheit().format(0.0), "32°F");
+        let kelvin = UnitFormat::temperature(TemperatureScale::Celsius, TemperatureScale::Kelvin);
+        assert_eq!(kelvin.format(0.0), "273 K");
+    }
+
+    #[test]
+    fn test_currency() {
+        let usd = UnitFormat::currency("$");
+        assert_eq!(usd.format(950.0), "$950");
+        assert_eq!(usd.format(1500.0), "$1.5K");
+        assert_eq!(usd.format(-2_000_000.0), "-$2M");
+
+        let eur = UnitFormat::currency("€").with_rate(0.9);
+        assert_eq!(eur.format(1000.0), "€900");
+    }
+
+    #[test]
+    fn test_shared_mode_consistent_unit_and_decimals() {
+        let fmt = UnitFormat::bytes().shared();
+        let labels = fmt.format_all(&[0.0, 256.0 * 1024.0, 512.0 * 1024.0, 768.0 * 1024.0, 1_048_576.0]);
+        assert_eq!(labels, vec!["0.00 MiB", "0.25 MiB", "0.50 MiB", "0.75 MiB", "1.00 MiB"]);
+
+        let fmt = UnitFormat::hertz().shared();
+        let labels = fmt.format_all(&[0.0, 5e5, 1e6, 1.5e6]);
+        assert_eq!(labels, vec!["0.0 MHz", "0.5 MHz", "1.0 MHz", "1.5 MHz"]);
+        assert_eq!(fmt.shared_suffix(&[0.0, 1.5e6]), "MHz");
+    }
+
+    #[test]
+    fn test_per_value_mode_mixes_units() {
+        let fmt = UnitFormat::bytes();
+        let labels = fmt.format_all(&[512.0, 2048.0]);
+        assert_eq!(labels, vec!["512 B", "2 KiB"]);
+    }
+
+    #[test]
+    fn test_fixed_decimals() {
+        let fmt = UnitFormat::hertz().with_decimals(2);
+        assert_eq!(fmt.format(1e6), "1.00 MHz");
+    }
+
+    #[test]
+    fn test_non_finite() {
+        assert_eq!(UnitFormat::bytes().format(f64::INFINITY), "inf");
+        let labels = UnitFormat::bytes().shared().format_all(&[f64::NAN, 1024.0]);
+        assert_eq!(labels[1], "1 KiB");
+    }
+}
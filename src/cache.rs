@@ -0,0 +1,438 @@
+//! Content-keyed cache for expensive derived artifacts
+//!
+//! Tessellated meshes, tick layouts and projected geometry only need to be
+//! rebuilt when their inputs change. [`KeyBuilder`] hashes those inputs (a
+//! data version, configuration values, sizes) into a [`CacheKey`], and
+//! [`ArtifactCache`] keeps the most recently used artifacts under that key so
+//! interactive resizing and zooming can reuse work across frames.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::cache::{ArtifactCache, KeyBuilder};
+//!
+//! let mut cache: ArtifactCache<Vec<f64>> = ArtifactCache::new(16);
+//!
+//! let key = KeyBuilder::new("ticks")
+//!     .version(3)          // data version
+//!     .f64(0.0).f64(100.0) // domain
+//!     .f64(480.0)          // axis length
+//!     .finish();
+//!
+//! let ticks = cache.get_or_insert_with(key, || vec![0.0, 25.0, 50.0, 75.0, 100.0]);
+//! assert_eq!(ticks.len(), 5);
+//!
+//! // Same inputs next frame: no recomputation
+//! cache.get_or_insert_with(key, || unreachable!());
+//! assert_eq!(cache.stats().hits, 1);
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+
+use crate::data::{DataPoint, Dataset};
+use crate::error::D3Result;
+
+/// Hash of every input an artifact depends on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CacheKey(pub u64);
+
+impl CacheKey {
+    /// Key for a single hashable value
+    pub fn of<T: Hash + ?Sized>(value: &T) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// Incremental builder for a [`CacheKey`]
+///
+/// Floats are hashed by bit pattern, so `0.0` and `-0.0` give different keys
+/// and every NaN payload is distinct; that only costs a spurious miss.
+#[derive(Debug)]
+pub struct KeyBuilder {
+    hasher: DefaultHasher,
+}
+
+impl KeyBuilder {
+    /// Start a key in a namespace (e.g. "ticks", "mesh")
+    ///
+    /// The namespace keeps artifacts of different kinds from colliding when
+    /// they share a cache.
+    pub fn new(namespace: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        namespace.hash(&mut hasher);
+        Self { hasher }
+    }
+
+    /// Add a data or config version counter
+    pub fn version(self, version: u64) -> Self {
+        self.u64(version)
+    }
+
+    /// Add an integer
+    pub fn u64(mut self, value: u64) -> Self {
+        value.hash(&mut self.hasher);
+        self
+    }
+
+    /// Add a float
+    pub fn f64(mut self, value: f64) -> Self {
+        value.to_bits().hash(&mut self.hasher);
+        self
+    }
+
+    /// Add a slice of floats
+    pub fn f64s(mut self, values: &[f64]) -> Self {
+        values.len().hash(&mut self.hasher);
+        for v in values {
+            v.to_bits().hash(&mut self.hasher);
+        }
+        self
+    }
+
+    /// Add a string
+    pub fn str(mut self, value: &str) -> Self {
+        value.hash(&mut self.hasher);
+        self
+    }
+
+    /// Add any hashable value
+    pub fn hash<T: Hash + ?Sized>(mut self, value: &T) -> Self {
+        value.hash(&mut self.hasher);
+        self
+    }
+
+    /// Add the numeric content of data points
+    ///
+    /// Use when the data has no version counter; labels, ids and metadata
+    /// are not included.
+    pub fn points(mut self, points: &[DataPoint]) -> Self {
+        points.len().hash(&mut self.hasher);
+        for p in points {
+            p.x.map(f64::to_bits).hash(&mut self.hasher);
+            p.y.to_bits().hash(&mut self.hasher);
+            p.y_min.map(f64::to_bits).hash(&mut self.hasher);
+            p.r.map(f64::to_bits).hash(&mut self.hasher);
+            p.size.map(f64::to_bits).hash(&mut self.hasher);
+            p.color_value.map(f64::to_bits).hash(&mut self.hasher);
+            p.category.hash(&mut self.hasher);
+        }
+        self
+    }
+
+    /// Add a dataset's label, visibility and point content
+    pub fn dataset(self, dataset: &Dataset) -> Self {
+        self.str(&dataset.label)
+            .hash(&dataset.hidden)
+            .points(&dataset.data)
+    }
+
+    /// Finish the key
+    pub fn finish(self) -> CacheKey {
+        CacheKey(self.hasher.finish())
+    }
+}
+
+/// Hit, miss and eviction counters
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups that found an artifact
+    pub hits: u64,
+    /// Lookups that had to compute one
+    pub misses: u64,
+    /// Artifacts dropped to stay within capacity
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups that hit (0 when there were none)
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// Least-recently-used cache of derived artifacts
+///
+/// Holds at most `capacity` entries; inserting beyond that evicts the entry
+/// that was used longest ago.
+#[derive(Debug)]
+pub struct ArtifactCache<V> {
+    capacity: usize,
+    entries: HashMap<CacheKey, (V, u64)>,
+    /// Recency order: last-use tick to key
+    order: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+impl<V> ArtifactCache<V> {
+    /// Create a cache holding at most `capacity` artifacts (minimum 1)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Maximum number of artifacts
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Change the capacity, evicting least recently used entries if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict_to(self.capacity);
+    }
+
+    /// Number of cached artifacts
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Check for an artifact without changing recency or stats
+    pub fn contains(&self, key: CacheKey) -> bool {
+        self.entries.contains_key(&key)
+    }
+
+    /// Look up an artifact without changing recency or stats
+    pub fn peek(&self, key: CacheKey) -> Option<&V> {
+        self.entries.get(&key).map(|(v, _)| v)
+    }
+
+    /// Look up an artifact, marking it most recently used
+    pub fn get(&mut self, key: CacheKey) -> Option<&V> {
+        if self.touch(key) {
+            self.stats.hits += 1;
+            self.peek(key)
+        } else {
+            self.stats.misses += 1;
+            None
+        }
+    }
+
+    /// Store an artifact, returning the one it replaced under the same key
+    pub fn insert(&mut self, key: CacheKey, value: V) -> Option<V> {
+        let tick = self.next_tick();
+        let previous = self.entries.insert(key, (value, tick)).map(|(old, old_tick)| {
+            self.order.remove(&old_tick);
+            old
+        });
+        self.order.insert(tick, key);
+        self.evict_to(self.capacity);
+        previous
+    }
+
+    /// Get an artifact, computing and storing it on a miss
+    pub fn get_or_insert_with(&mut self, key: CacheKey, compute: impl FnOnce() -> V) -> &V {
+        if self.touch(key) {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+            self.insert(key, compute());
+        }
+        self.peek(key).expect("entry present after insert")
+    }
+
+    /// Like [`get_or_insert_with`](Self::get_or_insert_with) for fallible work
+    ///
+    /// Errors are returned and nothing is cached.
+    pub fn try_get_or_insert_with(
+        &mut self,
+        key: CacheKey,
+        compute: impl FnOnce() -> D3Result<V>,
+    ) -> D3Result<&V> {
+        if self.touch(key) {
+            self.stats.hits += 1;
+        } else {
+            self.stats.misses += 1;
+            let value = compute()?;
+            self.insert(key, value);
+        }
+        Ok(self.peek(key).expect("entry present after insert"))
+    }
+
+    /// Remove an artifact
+    pub fn remove(&mut self, key: CacheKey) -> Option<V> {
+        self.entries.remove(&key).map(|(v, tick)| {
+            self.order.remove(&tick);
+            v
+        })
+    }
+
+    /// Remove every artifact (stats are kept)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Hit, miss and eviction counters
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Reset the counters
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Mark an entry most recently used; false if absent
+    fn touch(&mut self, key: CacheKey) -> bool {
+        let tick = self.next_tick();
+        match self.entries.get_mut(&key) {
+            Some((_, last)) => {
+                self.order.remove(last);
+                *last = tick;
+                self.order.insert(tick, key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.entries.len() > capacity {
+            let Some((_, key)) = self.order.pop_first() else { break };
+            self.entries.remove(&key);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+impl<V> Default for ArtifactCache<V> {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::D3Error;
+
+    fn key(n: u64) -> CacheKey {
+        KeyBuilder::new("test").u64(n).finish()
+    }
+
+    #[test]
+    fn test_key_builder_deterministic() {
+        let a = KeyBuilder::new("mesh").version(1).f64(2.5).str("x").finish();
+        let b = KeyBuilder::new("mesh").version(1).f64(2.5).str("x").finish();
+        let c = KeyBuilder::new("mesh").version(2).f64(2.5).str("x").finish();
+        let d = KeyBuilder::new("ticks").version(1).f64(2.5).str("x").finish();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(a, d);
+    }
+
+    #[test]
+    fn test_key_builder_slices_are_length_prefixed() {
+        let a = KeyBuilder::new("k").f64s(&[1.0, 2.0]).f64s(&[3.0]).finish();
+        let b = KeyBuilder::new("k").f64s(&[1.0]).f64s(&[2.0, 3.0]).finish();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_dataset_content_key() {
+        let ds = Dataset::new("A").with_data(vec![1.0, 2.0]);
+        let same = Dataset::new("A").with_data(vec![1.0, 2.0]);
+        let changed = Dataset::new("A").with_data(vec![1.0, 2.5]);
+        let k = |d: &Dataset| KeyBuilder::new("line").dataset(d).finish();
+        assert_eq!(k(&ds), k(&same));
+        assert_ne!(k(&ds), k(&changed));
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = ArtifactCache::new(2);
+        cache.insert(key(1), "one");
+        cache.insert(key(2), "two");
+        assert_eq!(cache.get(key(1)), Some(&"one"));
+
+        // key(2) is now least recently used
+        cache.insert(key(3), "three");
+        assert!(cache.contains(key(1)));
+        assert!(!cache.contains(key(2)));
+        assert!(cache.contains(key(3)));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_insert_replaces() {
+        let mut cache = ArtifactCache::new(2);
+        assert_eq!(cache.insert(key(1), 1), None);
+        assert_eq!(cache.insert(key(1), 2), Some(1));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.peek(key(1)), Some(&2));
+    }
+
+    #[test]
+    fn test_get_or_insert_stats() {
+        let mut cache = ArtifactCache::new(4);
+        let mut computed = 0;
+        for _ in 0..3 {
+            cache.get_or_insert_with(key(7), || {
+                computed += 1;
+                42
+            });
+        }
+        assert_eq!(computed, 1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert!((stats.hit_rate() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_try_get_or_insert_does_not_cache_errors() {
+        let mut cache: ArtifactCache<u32> = ArtifactCache::new(4);
+        let err = cache.try_get_or_insert_with(key(1), || Err(D3Error::invalid_data("bad")));
+        assert!(err.is_err());
+        assert!(cache.is_empty());
+        assert_eq!(*cache.try_get_or_insert_with(key(1), || Ok(5)).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_set_capacity_shrinks() {
+        let mut cache = ArtifactCache::new(4);
+        for n in 0..4 {
+            cache.insert(key(n), n);
+        }
+        cache.set_capacity(2);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(key(2)) && cache.contains(key(3)));
+    }
+
+    #[test]
+    fn test_remove_and_clear() {
+        let mut cache = ArtifactCache::new(4);
+        cache.insert(key(1), 1);
+        cache.insert(key(2), 2);
+        assert_eq!(cache.remove(key(1)), Some(1));
+        assert_eq!(cache.remove(key(1)), None);
+        cache.clear();
+        assert!(cache.is_empty());
+        // Recency bookkeeping stays consistent after clear
+        cache.insert(key(3), 3);
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -299,6 +299,15 @@ pub fn meets_wcag_aaa(foreground: &Rgba, background: &Rgba) -> bool {
     contrast_ratio(foreground, background) >= 7.0
 }
 
+/// Pick black or white text, whichever contrasts more with the background
+pub fn readable_text_color(background: &Rgba) -> Rgba {
+    if contrast_ratio(&Rgba::BLACK, background) >= contrast_ratio(&Rgba::WHITE, background) {
+        Rgba::BLACK
+    } else {
+        Rgba::WHITE
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -428,6 +437,12 @@ mod tests {
         assert!(gray_on_white_ratio < 4.5);
     }
 
+    #[test]
+    fn test_readable_text_color() {
+        assert_eq!(readable_text_color(&Rgba::from_hex(0x1F2937)), Rgba::WHITE);
+        assert_eq!(readable_text_color(&Rgba::from_hex(0xFDE68A)), Rgba::BLACK);
+    }
+
     #[test]
     fn test_brightness() {
         let gray = Rgba::rgb(0.5, 0.5, 0.5);
@@ -102,7 +102,7 @@ pub use blend::{
     BlendMode, blend, blend_with_opacity, composite_over,
     mix, mix_weighted, mix_lab,
     tint, shade, tone, brightness, contrast, invert, grayscale, sepia,
-    luminance, contrast_ratio, meets_wcag_aa, meets_wcag_aaa,
+    luminance, contrast_ratio, meets_wcag_aa, meets_wcag_aaa, readable_text_color,
 };
 
 /// Interpolate between two colors
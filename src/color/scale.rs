@@ -3,6 +3,8 @@
 //! Provides sequential, diverging, and categorical color scales for
 //! mapping data values to colors.
 
+use std::collections::HashMap;
+
 use super::types::Rgba;
 
 /// Trait for color scales that map values to colors
@@ -370,17 +372,52 @@ impl ColorScale for DivergingScale {
 /// let color0 = scale.color(0.0);  // First category
 /// let color1 = scale.color(0.1);  // Second category (if 10 categories)
 /// ```
+///
+/// # Ordinal Mapping
+///
+/// Like d3's `scaleOrdinal`, colors can be keyed by category. Keys are
+/// assigned colors in the order they are first seen (or in the order of an
+/// explicit domain), so the same data always gets the same colors.
+///
+/// ```
+/// use makepad_d3::color::{CategoricalScale, Rgba};
+///
+/// let mut scale = CategoricalScale::category10().with_domain(["Asia", "Europe"]);
+/// assert_eq!(scale.color_for("Europe"), scale.get(1));
+///
+/// // Implicit domain growth
+/// assert_eq!(scale.color_for("Africa"), scale.get(2));
+/// assert_eq!(scale.domain(), ["Asia", "Europe", "Africa"]);
+///
+/// // With an unknown color the domain is fixed
+/// let mut fixed = CategoricalScale::category10()
+///     .with_domain(["Asia"])
+///     .with_unknown(Rgba::from_hex(0xCCCCCC));
+/// assert_eq!(fixed.color_for("Oceania"), Rgba::from_hex(0xCCCCCC));
+/// assert_eq!(fixed.domain().len(), 1);
+/// ```
 #[derive(Clone, Debug)]
 pub struct CategoricalScale {
     /// Category colors
     colors: Vec<Rgba>,
+    /// Keys in color order
+    domain: Vec<String>,
+    /// Key to domain position
+    positions: HashMap<String, usize>,
+    /// Color for keys outside the domain (None = grow the domain)
+    unknown: Option<Rgba>,
 }
 
 impl CategoricalScale {
     /// Create a new categorical scale
     pub fn new(colors: Vec<Rgba>) -> Self {
         assert!(!colors.is_empty(), "Categorical scale requires at least one color");
-        Self { colors }
+        Self {
+            colors,
+            domain: Vec::new(),
+            positions: HashMap::new(),
+            unknown: None,
+        }
     }
 
     /// Create from hex colors
@@ -408,6 +445,93 @@ impl CategoricalScale {
         &self.colors
     }
 
+    /// Set the key domain; duplicate keys keep their first position
+    pub fn with_domain<I, K>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: ToString,
+    {
+        self.set_domain(keys);
+        self
+    }
+
+    /// Set the color returned for keys outside the domain
+    ///
+    /// Without one, unseen keys are appended to the domain.
+    pub fn with_unknown(mut self, color: Rgba) -> Self {
+        self.unknown = Some(color);
+        self
+    }
+
+    /// Replace the key domain; duplicate keys keep their first position
+    pub fn set_domain<I, K>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+        K: ToString,
+    {
+        self.domain.clear();
+        self.positions.clear();
+        for key in keys {
+            self.insert(key.to_string());
+        }
+    }
+
+    /// Set or clear the unknown color
+    pub fn set_unknown(&mut self, color: Option<Rgba>) {
+        self.unknown = color;
+    }
+
+    /// Keys in color order
+    pub fn domain(&self) -> &[String] {
+        &self.domain
+    }
+
+    /// Color for keys outside the domain, if the domain is fixed
+    pub fn unknown(&self) -> Option<Rgba> {
+        self.unknown
+    }
+
+    /// Position of a key in the domain
+    pub fn index_of(&self, key: impl ToString) -> Option<usize> {
+        self.positions.get(&key.to_string()).copied()
+    }
+
+    /// Color for a key, adding unseen keys to the domain unless an unknown
+    /// color is set
+    pub fn color_for(&mut self, key: impl ToString) -> Rgba {
+        let key = key.to_string();
+        if let Some(&index) = self.positions.get(&key) {
+            return self.get(index);
+        }
+        match self.unknown {
+            Some(color) => color,
+            None => {
+                let index = self.insert(key);
+                self.get(index)
+            }
+        }
+    }
+
+    /// Color for a key without growing the domain
+    ///
+    /// Unseen keys get the unknown color, or None if no unknown color is set.
+    pub fn lookup(&self, key: impl ToString) -> Option<Rgba> {
+        match self.index_of(key) {
+            Some(index) => Some(self.get(index)),
+            None => self.unknown,
+        }
+    }
+
+    fn insert(&mut self, key: String) -> usize {
+        if let Some(&index) = self.positions.get(&key) {
+            return index;
+        }
+        let index = self.domain.len();
+        self.positions.insert(key.clone(), index);
+        self.domain.push(key);
+        index
+    }
+
     // ==================== D3 Categorical Scales ====================
 
     /// D3 Category10 - 10 distinct colors
@@ -598,6 +722,32 @@ mod tests {
         assert_eq!(scale.get(1).to_hex(), scale.get(11).to_hex());
     }
 
+    #[test]
+    fn test_categorical_ordinal_domain() {
+        let mut scale = CategoricalScale::from_hex(&[0xFF0000, 0x00FF00]).with_domain(["a", "b", "a"]);
+        assert_eq!(scale.domain(), ["a", "b"]);
+        assert_eq!(scale.lookup("c"), None);
+        // Third key wraps around the palette
+        assert_eq!(scale.color_for("c"), Rgba::from_hex(0xFF0000));
+        assert_eq!(scale.index_of("c"), Some(2));
+        // Numeric keys work too
+        assert_eq!(scale.color_for(2024), Rgba::from_hex(0x00FF00));
+        assert_eq!(scale.lookup(2024), Some(Rgba::from_hex(0x00FF00)));
+    }
+
+    #[test]
+    fn test_categorical_unknown() {
+        let gray = Rgba::from_hex(0x999999);
+        let mut scale = CategoricalScale::category10().with_domain(["x"]).with_unknown(gray);
+        assert_eq!(scale.color_for("y"), gray);
+        assert_eq!(scale.lookup("y"), Some(gray));
+        assert_eq!(scale.domain().len(), 1);
+
+        // Clearing the unknown color restores implicit growth
+        scale.set_unknown(None);
+        assert_eq!(scale.color_for("y"), scale.get(1));
+    }
+
     #[test]
     fn test_sequential_clamping() {
         let scale = SequentialScale::viridis();
@@ -18,6 +18,7 @@
 //!     .with_arrow(true);
 //! ```
 
+use crate::axis::{wrap_text, ApproxTextMeasurer, TextMeasurer};
 use crate::color::Rgba;
 use serde::{Deserialize, Serialize};
 
@@ -304,6 +305,9 @@ pub struct Annotation {
     pub visible: bool,
     /// Whether the annotation is interactive
     pub interactive: bool,
+    /// Wrap the text body to this width (None = single line)
+    #[serde(default)]
+    pub max_text_width: Option<f64>,
 }
 
 impl Default for Annotation {
@@ -327,6 +331,7 @@ impl Default for Annotation {
             rotation: 0.0,
             visible: true,
             interactive: false,
+            max_text_width: None,
         }
     }
 }
@@ -517,6 +522,34 @@ impl Annotation {
         self
     }
 
+    /// Wrap the text body to a maximum width (excluding padding)
+    pub fn with_max_text_width(mut self, width: f64) -> Self {
+        self.max_text_width = Some(width);
+        self
+    }
+
+    /// Lines of the text body, wrapped to `max_text_width` if set
+    pub fn text_lines(&self, measurer: &dyn TextMeasurer) -> Vec<String> {
+        match self.max_text_width {
+            Some(width) => wrap_text(&self.text, width, self.style.font_size, measurer),
+            None => vec![self.text.clone()],
+        }
+    }
+
+    /// Size of the text label including padding
+    fn label_size(&self) -> (f64, f64) {
+        let font_size = self.style.font_size;
+        let (w, h) = if self.max_text_width.is_some() {
+            let measurer = ApproxTextMeasurer::default();
+            let lines = self.text_lines(&measurer);
+            let widest = lines.iter().map(|l| measurer.measure(l, font_size)).fold(0.0, f64::max);
+            (widest, lines.len() as f64 * measurer.line_height(font_size))
+        } else {
+            (self.text.len() as f64 * font_size * 0.6, font_size)
+        };
+        (w + self.style.padding * 2.0, h + self.style.padding * 2.0)
+    }
+
     /// Get the primary position
     pub fn position(&self) -> (f64, f64) {
         (self.x, self.y)
@@ -545,19 +578,14 @@ impl Annotation {
                 (self.x - d / 2.0, self.y - d / 2.0, d, d)
             }
             AnnotationType::Text | AnnotationType::Badge => {
-                // Estimate text bounds
-                let w = self.text.len() as f64 * self.style.font_size * 0.6
-                    + self.style.padding * 2.0;
-                let h = self.style.font_size + self.style.padding * 2.0;
+                let (w, h) = self.label_size();
                 (self.x - w / 2.0, self.y - h / 2.0, w, h)
             }
             AnnotationType::Callout => {
                 // Return bounds of the label portion
                 let label_x = self.x2.unwrap_or(self.x);
                 let label_y = self.y2.unwrap_or(self.y);
-                let w = self.text.len() as f64 * self.style.font_size * 0.6
-                    + self.style.padding * 2.0;
-                let h = self.style.font_size + self.style.padding * 2.0;
+                let (w, h) = self.label_size();
                 (label_x - w / 2.0, label_y - h / 2.0, w, h)
             }
             AnnotationType::Line | AnnotationType::Arrow => {
@@ -778,6 +806,20 @@ mod tests {
         assert_eq!(ann.rotation, 45.0);
     }
 
+    #[test]
+    fn test_annotation_wrapped_text() {
+        let ann = Annotation::text(100.0, 100.0, "Record high after the storm")
+            .with_style(AnnotationStyle::default().font_size(10.0).padding(0.0))
+            .with_max_text_width(90.0);
+        let lines = ann.text_lines(&ApproxTextMeasurer::default());
+        assert_eq!(lines, vec!["Record high", "after the storm"]);
+
+        // 15 characters wide, two 12px lines tall
+        let (_, _, w, h) = ann.bounds();
+        assert!((w - 90.0).abs() < 1e-9);
+        assert!((h - 24.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_annotation_contains() {
         let ann = Annotation::rectangle(10.0, 10.0, 50.0, 30.0);
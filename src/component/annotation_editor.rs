@@ -0,0 +1,802 @@
+//! Interactive editing of chart annotations
+//!
+//! [`AnnotationEditor`] is a pointer-driven state machine on top of an
+//! [`AnnotationLayer`]: it hit-tests annotations and their drag handles,
+//! moves and resizes them, creates new ones with a drawing tool, and snaps
+//! edited points to data points or tick positions. Each finished gesture
+//! produces an [`AnnotationEdit`] that an authoring UI can record for undo
+//! or persist.
+//!
+//! All coordinates are in pixels, like the annotations themselves.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::component::{
+//!     Annotation, AnnotationEdit, AnnotationEditor, AnnotationLayer, AnnotationSnap,
+//! };
+//!
+//! let mut layer = AnnotationLayer::new("notes");
+//! layer.add(
+//!     Annotation::callout(100.0, 100.0, 160.0, 40.0, "Peak")
+//!         .with_id("peak")
+//!         .with_interactive(true),
+//! );
+//!
+//! // Drag the callout's target onto the nearby data point at (203, 150)
+//! let mut editor = AnnotationEditor::new()
+//!     .with_snap(AnnotationSnap::new(8.0).with_points(vec![(203.0, 150.0)]));
+//! editor.handle_start(&mut layer, 100.0, 100.0);
+//! editor.handle_move(&mut layer, 198.0, 147.0);
+//!
+//! match editor.handle_end(&mut layer) {
+//!     Some(AnnotationEdit::Resized { id, after, .. }) => {
+//!         assert_eq!(id, "peak");
+//!         assert_eq!((after.x, after.y), (203.0, 150.0));
+//!     }
+//!     other => panic!("unexpected edit {:?}", other),
+//! }
+//! ```
+
+use super::annotation::{Annotation, AnnotationLayer, AnnotationType};
+
+/// Draggable part of an annotation
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditHandle {
+    /// The annotation body (moves it; for callouts, moves the label)
+    Body,
+    /// Primary point of a line, arrow, or callout target
+    Start,
+    /// Secondary point of a line or arrow
+    End,
+    /// Rectangle top-left corner
+    TopLeft,
+    /// Rectangle top edge
+    Top,
+    /// Rectangle top-right corner
+    TopRight,
+    /// Rectangle right edge
+    Right,
+    /// Rectangle bottom-right corner
+    BottomRight,
+    /// Rectangle bottom edge
+    Bottom,
+    /// Rectangle bottom-left corner
+    BottomLeft,
+    /// Rectangle left edge
+    Left,
+    /// Circle radius (right-hand edge of the circle)
+    Radius,
+}
+
+impl EditHandle {
+    /// Check if the handle moves the whole annotation rather than reshaping it
+    pub fn is_move(&self) -> bool {
+        matches!(self, EditHandle::Body)
+    }
+}
+
+/// Editable geometry of an annotation
+///
+/// Captured before and after a gesture so edits can be undone by applying
+/// the `before` geometry back to the annotation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnnotationGeometry {
+    /// Primary X position
+    pub x: f64,
+    /// Primary Y position
+    pub y: f64,
+    /// Secondary X position
+    pub x2: Option<f64>,
+    /// Secondary Y position
+    pub y2: Option<f64>,
+    /// Width (rectangles) or diameter (circles)
+    pub width: Option<f64>,
+    /// Height (rectangles)
+    pub height: Option<f64>,
+}
+
+impl AnnotationGeometry {
+    /// Capture the geometry of an annotation
+    pub fn of(annotation: &Annotation) -> Self {
+        Self {
+            x: annotation.x,
+            y: annotation.y,
+            x2: annotation.x2,
+            y2: annotation.y2,
+            width: annotation.width,
+            height: annotation.height,
+        }
+    }
+
+    /// Write this geometry into an annotation
+    pub fn apply_to(&self, annotation: &mut Annotation) {
+        annotation.x = self.x;
+        annotation.y = self.y;
+        annotation.x2 = self.x2;
+        annotation.y2 = self.y2;
+        annotation.width = self.width;
+        annotation.height = self.height;
+    }
+}
+
+/// Structured result of a finished edit gesture
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnnotationEdit {
+    /// A new annotation was drawn with the create tool
+    Created {
+        /// The annotation as added to the layer
+        annotation: Annotation,
+    },
+    /// An annotation was dragged by its body
+    Moved {
+        /// Annotation ID
+        id: String,
+        /// Geometry before the drag
+        before: AnnotationGeometry,
+        /// Geometry after the drag
+        after: AnnotationGeometry,
+    },
+    /// An endpoint, edge, corner, or radius handle was dragged
+    Resized {
+        /// Annotation ID
+        id: String,
+        /// Handle that was dragged
+        handle: EditHandle,
+        /// Geometry before the drag
+        before: AnnotationGeometry,
+        /// Geometry after the drag
+        after: AnnotationGeometry,
+    },
+    /// An annotation was removed
+    Deleted {
+        /// The removed annotation
+        annotation: Annotation,
+    },
+}
+
+impl AnnotationEdit {
+    /// ID of the annotation the edit applies to
+    pub fn id(&self) -> &str {
+        match self {
+            AnnotationEdit::Created { annotation } | AnnotationEdit::Deleted { annotation } => {
+                &annotation.id
+            }
+            AnnotationEdit::Moved { id, .. } | AnnotationEdit::Resized { id, .. } => id,
+        }
+    }
+}
+
+/// Snap targets for edited points
+///
+/// A point within `tolerance` of a data point snaps to it on both axes;
+/// otherwise each axis snaps independently to the nearest tick in range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnnotationSnap {
+    /// Data point positions
+    pub points: Vec<(f64, f64)>,
+    /// X tick positions
+    pub x_ticks: Vec<f64>,
+    /// Y tick positions
+    pub y_ticks: Vec<f64>,
+    /// Maximum snapping distance in pixels
+    pub tolerance: f64,
+}
+
+impl Default for AnnotationSnap {
+    fn default() -> Self {
+        Self::new(8.0)
+    }
+}
+
+impl AnnotationSnap {
+    /// Create with no targets
+    pub fn new(tolerance: f64) -> Self {
+        Self {
+            points: Vec::new(),
+            x_ticks: Vec::new(),
+            y_ticks: Vec::new(),
+            tolerance,
+        }
+    }
+
+    /// Set data point targets
+    pub fn with_points(mut self, points: Vec<(f64, f64)>) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Set X tick targets
+    pub fn with_x_ticks(mut self, ticks: Vec<f64>) -> Self {
+        self.x_ticks = ticks;
+        self
+    }
+
+    /// Set Y tick targets
+    pub fn with_y_ticks(mut self, ticks: Vec<f64>) -> Self {
+        self.y_ticks = ticks;
+        self
+    }
+
+    /// Check if there is nothing to snap to
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.x_ticks.is_empty() && self.y_ticks.is_empty()
+    }
+
+    /// Snap a point to the nearest target within tolerance
+    pub fn snap(&self, x: f64, y: f64) -> (f64, f64) {
+        let nearest_point = self
+            .points
+            .iter()
+            .map(|&(px, py)| ((px - x).hypot(py - y), (px, py)))
+            .filter(|(d, _)| *d <= self.tolerance)
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some((_, point)) = nearest_point {
+            return point;
+        }
+        (
+            nearest_tick(&self.x_ticks, x, self.tolerance).unwrap_or(x),
+            nearest_tick(&self.y_ticks, y, self.tolerance).unwrap_or(y),
+        )
+    }
+}
+
+fn nearest_tick(ticks: &[f64], value: f64, tolerance: f64) -> Option<f64> {
+    ticks
+        .iter()
+        .copied()
+        .filter(|t| (t - value).abs() <= tolerance)
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+}
+
+/// In-progress drag
+#[derive(Clone, Debug)]
+struct EditDrag {
+    id: String,
+    handle: EditHandle,
+    start: (f64, f64),
+    before: AnnotationGeometry,
+    creating: bool,
+}
+
+/// Pointer-driven annotation editor
+///
+/// Only visible, interactive annotations can be edited. Handles of the
+/// selected annotation take precedence over bodies, and later annotations
+/// (drawn on top) win over earlier ones.
+#[derive(Clone, Debug)]
+pub struct AnnotationEditor {
+    /// Handle hit radius in pixels
+    handle_size: f64,
+    /// Minimum rectangle side / circle diameter
+    min_size: f64,
+    /// Snap targets
+    snap: AnnotationSnap,
+    /// Annotation type drawn when pressing on empty space
+    create_tool: Option<AnnotationType>,
+    /// Currently selected annotation ID
+    selected: Option<String>,
+    /// Active drag
+    drag: Option<EditDrag>,
+    /// Counter for generated IDs
+    next_id: usize,
+}
+
+impl Default for AnnotationEditor {
+    fn default() -> Self {
+        Self {
+            handle_size: 6.0,
+            min_size: 4.0,
+            snap: AnnotationSnap::default(),
+            create_tool: None,
+            selected: None,
+            drag: None,
+            next_id: 1,
+        }
+    }
+}
+
+impl AnnotationEditor {
+    /// Create an editor with no snap targets and no create tool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set handle hit radius
+    pub fn with_handle_size(mut self, size: f64) -> Self {
+        self.handle_size = size.max(0.0);
+        self
+    }
+
+    /// Set minimum rectangle side / circle diameter
+    pub fn with_min_size(mut self, size: f64) -> Self {
+        self.min_size = size.max(0.0);
+        self
+    }
+
+    /// Set snap targets
+    pub fn with_snap(mut self, snap: AnnotationSnap) -> Self {
+        self.snap = snap;
+        self
+    }
+
+    /// Set the create tool
+    pub fn with_create_tool(mut self, tool: AnnotationType) -> Self {
+        self.create_tool = Some(tool);
+        self
+    }
+
+    /// Replace snap targets (e.g. after the data or scales change)
+    pub fn set_snap(&mut self, snap: AnnotationSnap) {
+        self.snap = snap;
+    }
+
+    /// Set or clear the create tool
+    pub fn set_create_tool(&mut self, tool: Option<AnnotationType>) {
+        self.create_tool = tool;
+    }
+
+    /// Get the create tool
+    pub fn create_tool(&self) -> Option<AnnotationType> {
+        self.create_tool
+    }
+
+    /// Get the selected annotation ID
+    pub fn selected(&self) -> Option<&str> {
+        self.selected.as_deref()
+    }
+
+    /// Select an annotation by ID, or clear the selection
+    pub fn select(&mut self, id: Option<&str>) {
+        self.selected = id.map(str::to_string);
+    }
+
+    /// Check if a drag is in progress
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Get the handle being dragged
+    pub fn active_handle(&self) -> Option<EditHandle> {
+        self.drag.as_ref().map(|d| d.handle)
+    }
+
+    /// Get the drag handles of an annotation with their positions
+    pub fn handles(&self, annotation: &Annotation) -> Vec<(EditHandle, f64, f64)> {
+        match annotation.annotation_type {
+            AnnotationType::Rectangle => {
+                let (x, y, w, h) = annotation.bounds();
+                let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+                vec![
+                    (EditHandle::TopLeft, x, y),
+                    (EditHandle::Top, cx, y),
+                    (EditHandle::TopRight, x + w, y),
+                    (EditHandle::Right, x + w, cy),
+                    (EditHandle::BottomRight, x + w, y + h),
+                    (EditHandle::Bottom, cx, y + h),
+                    (EditHandle::BottomLeft, x, y + h),
+                    (EditHandle::Left, x, cy),
+                ]
+            }
+            AnnotationType::Circle => {
+                let r = annotation.width.unwrap_or(0.0) / 2.0;
+                vec![(EditHandle::Radius, annotation.x + r, annotation.y)]
+            }
+            AnnotationType::Line | AnnotationType::Arrow => {
+                let (x2, y2) = annotation.secondary_position().unwrap_or(annotation.position());
+                vec![
+                    (EditHandle::Start, annotation.x, annotation.y),
+                    (EditHandle::End, x2, y2),
+                ]
+            }
+            AnnotationType::Callout => vec![(EditHandle::Start, annotation.x, annotation.y)],
+            AnnotationType::Text | AnnotationType::Badge => Vec::new(),
+        }
+    }
+
+    /// Find the annotation and handle under a point
+    pub fn hit_test(&self, layer: &AnnotationLayer, x: f64, y: f64) -> Option<(String, EditHandle)> {
+        if !layer.visible {
+            return None;
+        }
+        let editable = |a: &&Annotation| a.visible && a.interactive;
+
+        if let Some(selected) = self.selected.as_deref().and_then(|id| layer.find(id)).filter(editable) {
+            if let Some(handle) = self.handle_at(selected, x, y) {
+                return Some((selected.id.clone(), handle));
+            }
+        }
+
+        for annotation in layer.annotations.iter().rev().filter(editable) {
+            if let Some(handle) = self.handle_at(annotation, x, y) {
+                return Some((annotation.id.clone(), handle));
+            }
+            if self.body_contains(annotation, x, y) {
+                return Some((annotation.id.clone(), EditHandle::Body));
+            }
+        }
+        None
+    }
+
+    /// Handle the start of a drag (pointer down); returns whether anything was grabbed
+    ///
+    /// Pressing on an annotation selects it and starts a move or resize.
+    /// Pressing on empty space starts drawing with the create tool, or
+    /// clears the selection if there is none.
+    pub fn handle_start(&mut self, layer: &mut AnnotationLayer, x: f64, y: f64) -> bool {
+        self.drag = None;
+        if let Some((id, handle)) = self.hit_test(layer, x, y) {
+            let before = layer.find(&id).map(AnnotationGeometry::of);
+            if let Some(before) = before {
+                self.selected = Some(id.clone());
+                self.drag = Some(EditDrag {
+                    id,
+                    handle,
+                    start: (x, y),
+                    before,
+                    creating: false,
+                });
+                return true;
+            }
+        }
+
+        let Some(tool) = self.create_tool else {
+            self.selected = None;
+            return false;
+        };
+        let (sx, sy) = self.snap.snap(x, y);
+        let id = self.generate_id(layer);
+        let (annotation, handle) = template(tool, sx, sy);
+        let annotation = annotation.with_id(id.clone()).with_interactive(true);
+        let before = AnnotationGeometry::of(&annotation);
+        layer.add(annotation);
+        self.selected = Some(id.clone());
+        self.drag = Some(EditDrag {
+            id,
+            handle,
+            start: (sx, sy),
+            before,
+            creating: true,
+        });
+        true
+    }
+
+    /// Handle pointer movement during a drag; returns whether the annotation changed
+    pub fn handle_move(&mut self, layer: &mut AnnotationLayer, x: f64, y: f64) -> bool {
+        let Some(drag) = &self.drag else {
+            return false;
+        };
+        let Some(annotation) = layer.find_mut(&drag.id) else {
+            return false;
+        };
+        let current = AnnotationGeometry::of(annotation);
+        let next = self.drag_geometry(annotation, drag, x, y);
+        if next == current {
+            return false;
+        }
+        next.apply_to(annotation);
+        true
+    }
+
+    /// Handle the end of a drag (pointer up); returns the resulting edit, if any
+    ///
+    /// Drawn rectangles and circles smaller than the minimum size are
+    /// discarded, so a plain click with a shape tool creates nothing.
+    pub fn handle_end(&mut self, layer: &mut AnnotationLayer) -> Option<AnnotationEdit> {
+        let drag = self.drag.take()?;
+        let annotation = layer.find(&drag.id)?;
+
+        if drag.creating {
+            let too_small = match annotation.annotation_type {
+                AnnotationType::Rectangle => {
+                    let (_, _, w, h) = annotation.bounds();
+                    w < self.min_size || h < self.min_size
+                }
+                AnnotationType::Circle => annotation.width.unwrap_or(0.0) < self.min_size,
+                _ => false,
+            };
+            if too_small {
+                layer.remove(&drag.id);
+                self.selected = None;
+                return None;
+            }
+            return Some(AnnotationEdit::Created { annotation: annotation.clone() });
+        }
+
+        let after = AnnotationGeometry::of(annotation);
+        if after == drag.before {
+            return None;
+        }
+        Some(if drag.handle.is_move() {
+            AnnotationEdit::Moved { id: drag.id, before: drag.before, after }
+        } else {
+            AnnotationEdit::Resized {
+                id: drag.id,
+                handle: drag.handle,
+                before: drag.before,
+                after,
+            }
+        })
+    }
+
+    /// Abort the current drag, restoring the original geometry
+    ///
+    /// An annotation being drawn is removed.
+    pub fn cancel(&mut self, layer: &mut AnnotationLayer) {
+        let Some(drag) = self.drag.take() else {
+            return;
+        };
+        if drag.creating {
+            layer.remove(&drag.id);
+            self.selected = None;
+        } else if let Some(annotation) = layer.find_mut(&drag.id) {
+            drag.before.apply_to(annotation);
+        }
+    }
+
+    /// Remove the selected annotation
+    pub fn delete_selected(&mut self, layer: &mut AnnotationLayer) -> Option<AnnotationEdit> {
+        if self.drag.is_some() {
+            return None;
+        }
+        let id = self.selected.take()?;
+        layer
+            .remove(&id)
+            .map(|annotation| AnnotationEdit::Deleted { annotation })
+    }
+
+    /// Handle under a point, if within the hit radius
+    fn handle_at(&self, annotation: &Annotation, x: f64, y: f64) -> Option<EditHandle> {
+        self.handles(annotation)
+            .into_iter()
+            .map(|(handle, hx, hy)| (handle, (hx - x).hypot(hy - y)))
+            .filter(|(_, d)| *d <= self.handle_size)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Whether a point is on the annotation body
+    fn body_contains(&self, annotation: &Annotation, x: f64, y: f64) -> bool {
+        match annotation.annotation_type {
+            AnnotationType::Line | AnnotationType::Arrow => {
+                let end = annotation.secondary_position().unwrap_or(annotation.position());
+                segment_distance(annotation.position(), end, (x, y)) <= self.handle_size / 2.0
+            }
+            AnnotationType::Circle => {
+                let r = annotation.width.unwrap_or(0.0) / 2.0;
+                (x - annotation.x).hypot(y - annotation.y) <= r
+            }
+            _ => annotation.contains(x, y),
+        }
+    }
+
+    /// Geometry for the pointer at (x, y) during a drag
+    fn drag_geometry(&self, annotation: &Annotation, drag: &EditDrag, x: f64, y: f64) -> AnnotationGeometry {
+        let before = drag.before;
+        let mut next = before;
+        let is_callout = annotation.annotation_type == AnnotationType::Callout;
+
+        match drag.handle {
+            EditHandle::Body => {
+                // Snap the dragged reference point: the label for callouts,
+                // the primary point otherwise
+                let (rx, ry) = match (is_callout, before.x2, before.y2) {
+                    (true, Some(x2), Some(y2)) => (x2, y2),
+                    _ => (before.x, before.y),
+                };
+                let (tx, ty) = self.snap.snap(rx + x - drag.start.0, ry + y - drag.start.1);
+                let (dx, dy) = (tx - rx, ty - ry);
+                if !is_callout {
+                    next.x += dx;
+                    next.y += dy;
+                }
+                next.x2 = before.x2.map(|v| v + dx);
+                next.y2 = before.y2.map(|v| v + dy);
+            }
+            EditHandle::Start => {
+                let (sx, sy) = self.snap.snap(x, y);
+                next.x = sx;
+                next.y = sy;
+            }
+            EditHandle::End => {
+                let (sx, sy) = self.snap.snap(x, y);
+                next.x2 = Some(sx);
+                next.y2 = Some(sy);
+            }
+            EditHandle::Radius => {
+                let d = (x - before.x).hypot(y - before.y) * 2.0;
+                // A circle being drawn may stay below the minimum so that a
+                // plain click is discarded on release
+                next.width = Some(if drag.creating { d } else { d.max(self.min_size) });
+            }
+            handle => {
+                let (sx, sy) = self.snap.snap(x, y);
+                let (mut x0, mut y0) = (before.x, before.y);
+                let mut x1 = x0 + before.width.unwrap_or(0.0);
+                let mut y1 = y0 + before.height.unwrap_or(0.0);
+                let min = self.min_size;
+                // While drawing, the box starts empty and may grow either way
+                if drag.creating {
+                    x0 = drag.start.0.min(sx);
+                    x1 = drag.start.0.max(sx);
+                    y0 = drag.start.1.min(sy);
+                    y1 = drag.start.1.max(sy);
+                } else {
+                    if matches!(handle, EditHandle::TopLeft | EditHandle::Left | EditHandle::BottomLeft) {
+                        x0 = sx.min(x1 - min);
+                    }
+                    if matches!(handle, EditHandle::TopRight | EditHandle::Right | EditHandle::BottomRight) {
+                        x1 = sx.max(x0 + min);
+                    }
+                    if matches!(handle, EditHandle::TopLeft | EditHandle::Top | EditHandle::TopRight) {
+                        y0 = sy.min(y1 - min);
+                    }
+                    if matches!(handle, EditHandle::BottomLeft | EditHandle::Bottom | EditHandle::BottomRight) {
+                        y1 = sy.max(y0 + min);
+                    }
+                }
+                next.x = x0;
+                next.y = y0;
+                next.width = Some(x1 - x0);
+                next.height = Some(y1 - y0);
+            }
+        }
+        next
+    }
+
+    /// Next `annotation-N` ID not already in the layer
+    fn generate_id(&mut self, layer: &AnnotationLayer) -> String {
+        loop {
+            let id = format!("annotation-{}", self.next_id);
+            self.next_id += 1;
+            if layer.find(&id).is_none() {
+                return id;
+            }
+        }
+    }
+}
+
+/// Starting annotation and drag handle for a create tool
+fn template(tool: AnnotationType, x: f64, y: f64) -> (Annotation, EditHandle) {
+    match tool {
+        AnnotationType::Rectangle => (Annotation::rectangle(x, y, 0.0, 0.0), EditHandle::BottomRight),
+        AnnotationType::Circle => (Annotation::circle(x, y, 0.0), EditHandle::Radius),
+        AnnotationType::Line => (Annotation::line(x, y, x, y), EditHandle::End),
+        AnnotationType::Arrow => (Annotation::arrow(x, y, x, y), EditHandle::End),
+        // The press marks the target; dragging places the label
+        AnnotationType::Callout => (Annotation::callout(x, y, x, y, ""), EditHandle::Body),
+        AnnotationType::Text | AnnotationType::Badge => (
+            Annotation {
+                annotation_type: tool,
+                x,
+                y,
+                ..Default::default()
+            },
+            EditHandle::Body,
+        ),
+    }
+}
+
+/// Distance from point `p` to the segment `a`-`b`
+fn segment_distance(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len_sq = dx * dx + dy * dy;
+    let t = if len_sq > 0.0 {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (p.0 - (a.0 + t * dx)).hypot(p.1 - (a.1 + t * dy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer() -> AnnotationLayer {
+        let mut layer = AnnotationLayer::new("test");
+        layer.add(Annotation::rectangle(100.0, 100.0, 50.0, 40.0).with_id("rect").with_interactive(true));
+        layer.add(Annotation::line(0.0, 0.0, 60.0, 0.0).with_id("line").with_interactive(true));
+        layer.add(Annotation::text(300.0, 300.0, "static").with_id("static"));
+        layer
+    }
+
+    #[test]
+    fn test_hit_test_handles_and_body() {
+        let editor = AnnotationEditor::new();
+        let layer = layer();
+        assert_eq!(editor.hit_test(&layer, 150.0, 140.0), Some(("rect".into(), EditHandle::BottomRight)));
+        assert_eq!(editor.hit_test(&layer, 125.0, 101.0), Some(("rect".into(), EditHandle::Top)));
+        assert_eq!(editor.hit_test(&layer, 120.0, 120.0), Some(("rect".into(), EditHandle::Body)));
+        assert_eq!(editor.hit_test(&layer, 30.0, 2.0), Some(("line".into(), EditHandle::Body)));
+        assert_eq!(editor.hit_test(&layer, 59.0, 1.0), Some(("line".into(), EditHandle::End)));
+        // Not interactive
+        assert_eq!(editor.hit_test(&layer, 300.0, 300.0), None);
+    }
+
+    #[test]
+    fn test_move_with_tick_snap() {
+        let mut layer = layer();
+        let mut editor = AnnotationEditor::new()
+            .with_snap(AnnotationSnap::new(5.0).with_x_ticks(vec![120.0]).with_y_ticks(vec![200.0]));
+
+        assert!(editor.handle_start(&mut layer, 110.0, 110.0));
+        assert_eq!(editor.selected(), Some("rect"));
+        assert!(editor.handle_move(&mut layer, 128.0, 150.0));
+
+        let rect = layer.find("rect").unwrap();
+        // x snaps 118 -> 120; y 150 has no tick within range
+        assert_eq!(rect.position(), (120.0, 140.0));
+
+        match editor.handle_end(&mut layer).unwrap() {
+            AnnotationEdit::Moved { id, before, after } => {
+                assert_eq!(id, "rect");
+                assert_eq!((before.x, before.y), (100.0, 100.0));
+                assert_eq!((after.x, after.y), (120.0, 140.0));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(!editor.is_dragging());
+    }
+
+    #[test]
+    fn test_resize_respects_min_size() {
+        let mut layer = layer();
+        let mut editor = AnnotationEditor::new().with_min_size(10.0);
+
+        editor.handle_start(&mut layer, 100.0, 100.0);
+        assert_eq!(editor.active_handle(), Some(EditHandle::TopLeft));
+        editor.handle_move(&mut layer, 80.0, 200.0);
+        assert_eq!(layer.find("rect").unwrap().bounds(), (80.0, 130.0, 70.0, 10.0));
+
+        let edit = editor.handle_end(&mut layer).unwrap();
+        assert!(matches!(edit, AnnotationEdit::Resized { handle: EditHandle::TopLeft, .. }));
+    }
+
+    #[test]
+    fn test_create_and_cancel() {
+        let mut layer = AnnotationLayer::new("test");
+        let mut editor = AnnotationEditor::new().with_create_tool(AnnotationType::Rectangle);
+
+        assert!(editor.handle_start(&mut layer, 50.0, 50.0));
+        editor.handle_move(&mut layer, 20.0, 90.0);
+        match editor.handle_end(&mut layer).unwrap() {
+            AnnotationEdit::Created { annotation } => {
+                assert_eq!(annotation.id, "annotation-1");
+                assert_eq!(annotation.bounds(), (20.0, 50.0, 30.0, 40.0));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // A click without a drag creates nothing
+        editor.handle_start(&mut layer, 300.0, 300.0);
+        assert_eq!(editor.handle_end(&mut layer), None);
+        assert_eq!(layer.len(), 1);
+
+        // Cancel restores the original geometry
+        editor.set_create_tool(None);
+        editor.handle_start(&mut layer, 30.0, 60.0);
+        editor.handle_move(&mut layer, 200.0, 200.0);
+        editor.cancel(&mut layer);
+        assert_eq!(layer.find("annotation-1").unwrap().position(), (20.0, 50.0));
+    }
+
+    #[test]
+    fn test_delete_and_no_op_drag() {
+        let mut layer = layer();
+        let mut editor = AnnotationEditor::new();
+
+        editor.handle_start(&mut layer, 30.0, 0.0);
+        assert_eq!(editor.handle_end(&mut layer), None);
+        assert_eq!(editor.selected(), Some("line"));
+
+        let edit = editor.delete_selected(&mut layer).unwrap();
+        assert_eq!(edit.id(), "line");
+        assert!(layer.find("line").is_none());
+
+        // Pressing empty space clears the selection
+        editor.select(Some("rect"));
+        assert!(!editor.handle_start(&mut layer, 500.0, 500.0));
+        assert_eq!(editor.selected(), None);
+    }
+}
@@ -0,0 +1,404 @@
+//! Data-driven annotation rules
+//!
+//! An [`AnnotationRule`] describes which points deserve an annotation
+//! ("the global maximum", "the last point", "every point above 90") and how
+//! to label them. [`AnnotationRules`] evaluates a set of rules against a
+//! series, keeps the resulting annotations anchored in data space, and only
+//! re-evaluates when the data changes. [`AnnotationRules::project`] maps them
+//! through the chart's scales into a pixel-space [`AnnotationLayer`] for
+//! rendering and editing.
+//!
+//! Labels are templates: `{value}` is replaced by the formatted y value,
+//! `{x}` by the formatted x value and `{label}` by the point label.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::component::{AnnotationRule, AnnotationRules};
+//! use makepad_d3::data::DataPoint;
+//! use makepad_d3::scale::{LinearScale, ScaleExt};
+//!
+//! let mut rules = AnnotationRules::new()
+//!     .with_rule(AnnotationRule::max().with_label("Peak {value}"))
+//!     .with_rule(AnnotationRule::last())
+//!     .with_rule(AnnotationRule::above(50.0));
+//!
+//! let data: Vec<DataPoint> = [10.0, 80.0, 30.0, 60.0].into_iter().map(DataPoint::from_y).collect();
+//! assert!(rules.update(&data));
+//! assert!(!rules.update(&data)); // unchanged data is not re-evaluated
+//!
+//! // Max, last, and the two points above 50
+//! assert_eq!(rules.annotations().len(), 4);
+//! assert_eq!(rules.annotations()[0].text, "Peak 80.0");
+//!
+//! let x = LinearScale::new().with_domain(0.0, 3.0).with_range(0.0, 300.0);
+//! let y = LinearScale::new().with_domain(0.0, 100.0).with_range(200.0, 0.0);
+//! let layer = rules.project(&x, &y);
+//! assert_eq!(layer.find("max").map(|a| (a.x, a.y)), Some((100.0, 40.0)));
+//! ```
+
+use super::annotation::{Annotation, AnnotationLayer, AnnotationStyle, AnnotationType};
+use crate::axis::NumberFormat;
+use crate::cache::{CacheKey, KeyBuilder};
+use crate::data::DataPoint;
+use crate::scale::Scale;
+
+/// Which points a rule annotates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnnotationRuleKind {
+    /// The point with the largest y (first one on ties)
+    Max,
+    /// The point with the smallest y (first one on ties)
+    Min,
+    /// The last point
+    Last,
+    /// Every point with y strictly above the threshold
+    Above(f64),
+    /// Every point with y strictly below the threshold
+    Below(f64),
+}
+
+impl AnnotationRuleKind {
+    /// Check if the rule can match more than one point
+    pub fn is_flag(&self) -> bool {
+        matches!(self, AnnotationRuleKind::Above(_) | AnnotationRuleKind::Below(_))
+    }
+
+    fn default_id(&self) -> &'static str {
+        match self {
+            AnnotationRuleKind::Max => "max",
+            AnnotationRuleKind::Min => "min",
+            AnnotationRuleKind::Last => "last",
+            AnnotationRuleKind::Above(_) => "above",
+            AnnotationRuleKind::Below(_) => "below",
+        }
+    }
+}
+
+/// How a matched point is drawn
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RuleMark {
+    /// Callout whose label sits at a pixel offset from the point
+    Callout {
+        /// Horizontal label offset in pixels
+        dx: f64,
+        /// Vertical label offset in pixels
+        dy: f64,
+    },
+    /// Circle of a pixel radius around the point, carrying the label text
+    Circle {
+        /// Circle radius in pixels
+        radius: f64,
+    },
+}
+
+/// A declarative rule producing annotations from data
+#[derive(Clone, Debug)]
+pub struct AnnotationRule {
+    /// Which points to annotate
+    pub kind: AnnotationRuleKind,
+    /// Annotation id; flags append `:` and the point key or index
+    pub id: String,
+    /// Label template
+    pub label: String,
+    /// Format for `{value}` and `{x}`
+    pub format: NumberFormat,
+    /// How matched points are drawn
+    pub mark: RuleMark,
+    /// Annotation styling
+    pub style: AnnotationStyle,
+}
+
+impl AnnotationRule {
+    /// Create a rule; single-point rules default to a callout, flags to circles
+    pub fn new(kind: AnnotationRuleKind) -> Self {
+        let mark = match kind {
+            AnnotationRuleKind::Max => RuleMark::Callout { dx: 0.0, dy: -24.0 },
+            AnnotationRuleKind::Min => RuleMark::Callout { dx: 0.0, dy: 24.0 },
+            AnnotationRuleKind::Last => RuleMark::Callout { dx: 32.0, dy: 0.0 },
+            AnnotationRuleKind::Above(_) | AnnotationRuleKind::Below(_) => RuleMark::Circle { radius: 5.0 },
+        };
+        Self {
+            kind,
+            id: kind.default_id().to_string(),
+            label: "{value}".to_string(),
+            format: NumberFormat::Auto,
+            mark,
+            style: AnnotationStyle::default(),
+        }
+    }
+
+    /// Annotate the global maximum
+    pub fn max() -> Self {
+        Self::new(AnnotationRuleKind::Max)
+    }
+
+    /// Annotate the global minimum
+    pub fn min() -> Self {
+        Self::new(AnnotationRuleKind::Min)
+    }
+
+    /// Annotate the last point with its value
+    pub fn last() -> Self {
+        Self::new(AnnotationRuleKind::Last)
+    }
+
+    /// Flag every point above a threshold
+    pub fn above(threshold: f64) -> Self {
+        Self::new(AnnotationRuleKind::Above(threshold))
+    }
+
+    /// Flag every point below a threshold
+    pub fn below(threshold: f64) -> Self {
+        Self::new(AnnotationRuleKind::Below(threshold))
+    }
+
+    /// Set the annotation id (prefix for flags)
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Set the label template
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the value format
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Draw matches as callouts with the label offset by `(dx, dy)` pixels
+    pub fn with_callout(mut self, dx: f64, dy: f64) -> Self {
+        self.mark = RuleMark::Callout { dx, dy };
+        self
+    }
+
+    /// Draw matches as circles of `radius` pixels
+    pub fn with_circle(mut self, radius: f64) -> Self {
+        self.mark = RuleMark::Circle { radius };
+        self
+    }
+
+    /// Set the annotation style
+    pub fn with_style(mut self, style: AnnotationStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Indices of the points this rule matches
+    ///
+    /// Points with a non-finite y never match.
+    pub fn matches(&self, data: &[DataPoint]) -> Vec<usize> {
+        let valid = || data.iter().enumerate().filter(|(_, p)| p.y.is_finite());
+        let extreme = |better: fn(f64, f64) -> bool| {
+            valid()
+                .fold(None, |best: Option<(usize, f64)>, (i, p)| match best {
+                    Some((_, y)) if !better(p.y, y) => best,
+                    _ => Some((i, p.y)),
+                })
+                .map(|(i, _)| i)
+        };
+        match self.kind {
+            AnnotationRuleKind::Max => extreme(|a, b| a > b).into_iter().collect(),
+            AnnotationRuleKind::Min => extreme(|a, b| a < b).into_iter().collect(),
+            AnnotationRuleKind::Last => valid().next_back().map(|(i, _)| i).into_iter().collect(),
+            AnnotationRuleKind::Above(t) => valid().filter(|(_, p)| p.y > t).map(|(i, _)| i).collect(),
+            AnnotationRuleKind::Below(t) => valid().filter(|(_, p)| p.y < t).map(|(i, _)| i).collect(),
+        }
+    }
+
+    /// Data-space annotations for the matched points
+    ///
+    /// Callout labels sit on the point itself; the pixel offset is applied
+    /// by [`AnnotationRules::project`].
+    pub fn evaluate(&self, data: &[DataPoint]) -> Vec<Annotation> {
+        self.matches(data)
+            .into_iter()
+            .map(|i| {
+                let point = &data[i];
+                let x = point.x_or(i);
+                let text = self
+                    .label
+                    .replace("{value}", &self.format.format(point.y))
+                    .replace("{x}", &self.format.format(x))
+                    .replace("{label}", point.label.as_deref().unwrap_or(""));
+                let id = if self.kind.is_flag() {
+                    format!("{}:{}", self.id, point.key().map_or_else(|| i.to_string(), str::to_string))
+                } else {
+                    self.id.clone()
+                };
+                let annotation = match self.mark {
+                    RuleMark::Callout { .. } => Annotation::callout(x, point.y, x, point.y, text),
+                    RuleMark::Circle { radius } => Annotation {
+                        text,
+                        ..Annotation::circle(x, point.y, radius)
+                    },
+                };
+                annotation.with_id(id).with_style(self.style.clone())
+            })
+            .collect()
+    }
+}
+
+/// A set of rules with their current annotations
+///
+/// Call [`update`](Self::update) whenever the data may have changed; the
+/// rules are re-evaluated only if the points differ from the last update.
+#[derive(Clone, Debug, Default)]
+pub struct AnnotationRules {
+    rules: Vec<AnnotationRule>,
+    /// Data-space annotations with their pixel label offsets
+    annotations: Vec<Annotation>,
+    offsets: Vec<(f64, f64)>,
+    key: Option<CacheKey>,
+}
+
+impl AnnotationRules {
+    /// Create an empty rule set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a rule
+    pub fn with_rule(mut self, rule: AnnotationRule) -> Self {
+        self.push(rule);
+        self
+    }
+
+    /// Add a rule; annotations refresh on the next update
+    pub fn push(&mut self, rule: AnnotationRule) {
+        self.rules.push(rule);
+        self.key = None;
+    }
+
+    /// Remove all rules whose id is `id`
+    pub fn remove(&mut self, id: &str) {
+        self.rules.retain(|r| r.id != id);
+        self.key = None;
+    }
+
+    /// Get the rules
+    pub fn rules(&self) -> &[AnnotationRule] {
+        &self.rules
+    }
+
+    /// Re-evaluate the rules if `data` changed; returns true if it did
+    pub fn update(&mut self, data: &[DataPoint]) -> bool {
+        let labels: Vec<(&Option<String>, &Option<String>)> = data.iter().map(|p| (&p.label, &p.id)).collect();
+        let key = KeyBuilder::new("annotation-rules").points(data).hash(&labels).finish();
+        if self.key == Some(key) {
+            return false;
+        }
+        self.key = Some(key);
+        self.annotations.clear();
+        self.offsets.clear();
+        for rule in &self.rules {
+            let offset = match rule.mark {
+                RuleMark::Callout { dx, dy } => (dx, dy),
+                RuleMark::Circle { .. } => (0.0, 0.0),
+            };
+            let annotations = rule.evaluate(data);
+            self.offsets.extend(std::iter::repeat(offset).take(annotations.len()));
+            self.annotations.extend(annotations);
+        }
+        true
+    }
+
+    /// Current annotations in data space
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Map the annotations to pixels into a layer named "rules"
+    pub fn project(&self, x: &dyn Scale, y: &dyn Scale) -> AnnotationLayer {
+        let mut layer = AnnotationLayer::new("rules");
+        for (annotation, &(dx, dy)) in self.annotations.iter().zip(&self.offsets) {
+            let mut projected = annotation.clone();
+            projected.x = x.scale(annotation.x);
+            projected.y = y.scale(annotation.y);
+            if annotation.annotation_type == AnnotationType::Callout {
+                projected.x2 = Some(projected.x + dx);
+                projected.y2 = Some(projected.y + dy);
+            }
+            layer.add(projected);
+        }
+        layer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::{LinearScale, ScaleExt};
+
+    fn series(values: &[f64]) -> Vec<DataPoint> {
+        values.iter().copied().map(DataPoint::from_y).collect()
+    }
+
+    #[test]
+    fn test_rule_matches() {
+        let data = series(&[3.0, f64::NAN, 9.0, 1.0, 9.0, 4.0, f64::NAN]);
+        assert_eq!(AnnotationRule::max().matches(&data), vec![2]);
+        assert_eq!(AnnotationRule::min().matches(&data), vec![3]);
+        assert_eq!(AnnotationRule::last().matches(&data), vec![5]);
+        assert_eq!(AnnotationRule::above(3.0).matches(&data), vec![2, 4, 5]);
+        assert_eq!(AnnotationRule::below(3.0).matches(&data), vec![3]);
+        assert!(AnnotationRule::max().matches(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_rule_evaluate_labels_and_ids() {
+        let mut data = series(&[5.0, 120.0, 7.5]);
+        data[1].label = Some("Feb".to_string());
+        data[2].id = Some("mar".to_string());
+
+        let max = AnnotationRule::max().with_label("{label}: {value}").evaluate(&data);
+        assert_eq!(max[0].text, "Feb: 120");
+        assert_eq!(max[0].annotation_type, AnnotationType::Callout);
+        assert_eq!((max[0].x, max[0].y, max[0].x2), (1.0, 120.0, Some(1.0)));
+
+        let flags = AnnotationRule::above(6.0)
+            .with_format(NumberFormat::fixed(0))
+            .with_label("x={x}")
+            .evaluate(&data);
+        assert_eq!(flags.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), vec!["above:Feb", "above:mar"]);
+        assert_eq!(flags[1].text, "x=2");
+        assert_eq!(flags[1].annotation_type, AnnotationType::Circle);
+        assert_eq!(flags[1].width, Some(10.0));
+    }
+
+    #[test]
+    fn test_rules_update_and_project() {
+        let mut rules = AnnotationRules::new()
+            .with_rule(AnnotationRule::last().with_callout(10.0, -5.0))
+            .with_rule(AnnotationRule::below(0.0).with_circle(3.0));
+
+        let mut data = vec![DataPoint::new(0.0, 2.0), DataPoint::new(1.0, -1.0)];
+        assert!(rules.update(&data));
+        assert!(!rules.update(&data));
+        assert_eq!(rules.annotations().len(), 2);
+
+        let x = LinearScale::new().with_domain(0.0, 2.0).with_range(0.0, 100.0);
+        let y = LinearScale::new().with_domain(-2.0, 2.0).with_range(100.0, 0.0);
+        let layer = rules.project(&x, &y);
+        let last = layer.find("last").unwrap();
+        assert_eq!((last.x, last.y, last.x2, last.y2), (50.0, 75.0, Some(60.0), Some(70.0)));
+        let flag = layer.find("below:1").unwrap();
+        assert_eq!((flag.x, flag.y, flag.x2), (50.0, 75.0, None));
+
+        // New data moves the annotations
+        data.push(DataPoint::new(2.0, 1.0));
+        assert!(rules.update(&data));
+        assert_eq!(rules.project(&x, &y).find("last").map(|a| a.x), Some(100.0));
+
+        // Changing the rules forces re-evaluation of the same data
+        rules.remove("below");
+        assert_eq!(rules.rules().len(), 1);
+        assert!(rules.update(&data));
+        assert_eq!(rules.annotations().len(), 1);
+    }
+}
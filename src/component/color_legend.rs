@@ -0,0 +1,864 @@
+//! Continuous color legend component
+//!
+//! Renders a sampled color ramp for continuous color scales (choropleths,
+//! heatmaps) with tick labels and an optional histogram strip that shows how
+//! the underlying data is distributed along the ramp.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::component::ColorLegend;
+//! use makepad_d3::color::SequentialScale;
+//!
+//! let values = vec![1.0, 2.0, 2.5, 3.0, 8.0, 9.5];
+//!
+//! let mut legend = ColorLegend::new(0.0, 10.0)
+//!     .with_color_scale(&SequentialScale::viridis(), 16)
+//!     .with_size(200.0, 12.0)
+//!     .with_histogram(5, 30.0);
+//! legend.set_values(&values);
+//!
+//! let layout = legend.compute_layout(20.0, 20.0);
+//! assert_eq!(layout.stops.len(), 16);
+//! assert_eq!(layout.bars.len(), 5);
+//! assert_eq!(layout.bars[1].count, 3); // 2.0, 2.5 and 3.0
+//! ```
+//!
+//! # Linked Example
+//!
+//! A [`ColorLegendLink`] derives the ramp, extent and tick labels from the
+//! chart's own color scale, so the two cannot drift apart:
+//!
+//! ```
+//! use makepad_d3::axis::NumberFormat;
+//! use makepad_d3::color::DivergingScale;
+//! use makepad_d3::component::{ColorLegend, ColorLegendLink, DomainColorScale};
+//!
+//! let mut heat = DomainColorScale::new(DivergingScale::red_blue(), -4.0, 10.0).with_pivot(0.0);
+//! let mut link = ColorLegendLink::new(ColorLegend::new(0.0, 1.0), NumberFormat::Fixed(1));
+//! assert!(link.sync(&heat));
+//! assert_eq!(link.legend().domain(), (-4.0, 10.0));
+//! assert!(link.legend().ticks().iter().any(|t| t.label == "0.0"));
+//!
+//! // Nothing changed: no work
+//! assert!(!link.sync(&heat));
+//!
+//! heat.set_domain(-2.0, 2.0);
+//! assert!(link.sync(&heat));
+//! assert_eq!(link.legend().domain(), (-2.0, 2.0));
+//! ```
+
+use crate::axis::NumberFormat;
+use crate::color::{ColorScale, Rgba};
+use crate::scale::{format_number, nice_step, DivergingScale, SequentialScale, Tick};
+
+use super::legend::LegendOrientation;
+
+/// A color stop along the legend ramp
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GradientStop {
+    /// Position along the ramp (0.0 = domain start, 1.0 = domain end)
+    pub offset: f64,
+    /// Color at this position
+    pub color: Rgba,
+}
+
+impl GradientStop {
+    /// Create a new gradient stop
+    pub fn new(offset: f64, color: Rgba) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// Histogram strip configuration and bin counts
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramStrip {
+    /// Number of equal-width bins across the domain
+    pub bins: usize,
+    /// Maximum bar extent in pixels (perpendicular to the ramp)
+    pub size: f64,
+    /// Gap between the strip and the ramp
+    pub gap: f64,
+    /// Gap between adjacent bars in pixels
+    pub bar_padding: f64,
+    /// Counts per bin, populated by [`ColorLegend::set_values`]
+    counts: Vec<usize>,
+}
+
+impl HistogramStrip {
+    /// Create a histogram strip with the given bin count and size
+    pub fn new(bins: usize, size: f64) -> Self {
+        let bins = bins.max(1);
+        Self {
+            bins,
+            size,
+            gap: 2.0,
+            bar_padding: 1.0,
+            counts: vec![0; bins],
+        }
+    }
+
+    /// Counts per bin
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Largest bin count
+    pub fn max_count(&self) -> usize {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// A laid-out histogram bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramBar {
+    /// Left edge
+    pub x: f64,
+    /// Top edge
+    pub y: f64,
+    /// Bar width
+    pub width: f64,
+    /// Bar height
+    pub height: f64,
+    /// Number of values in the bin
+    pub count: usize,
+    /// Domain range covered by the bin
+    pub range: (f64, f64),
+    /// Ramp color at the bin center
+    pub color: Rgba,
+}
+
+/// Computed geometry for rendering a [`ColorLegend`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorLegendLayout {
+    /// Ramp rectangle (x, y, width, height)
+    pub ramp: (f64, f64, f64, f64),
+    /// Gradient stops, offsets relative to the ramp (start to end)
+    pub stops: Vec<GradientStop>,
+    /// Ticks with absolute positions along the ramp axis
+    pub ticks: Vec<Tick>,
+    /// Histogram bars (empty when no histogram is configured)
+    pub bars: Vec<HistogramBar>,
+    /// Title anchor position, if a title is set
+    pub title_position: Option<(f64, f64)>,
+    /// Total width
+    pub width: f64,
+    /// Total height
+    pub height: f64,
+}
+
+/// Continuous color legend
+///
+/// Samples a [`ColorScale`] into gradient stops and maps a numeric domain
+/// onto the ramp. Horizontal legends run left to right with ticks below the
+/// ramp and the histogram above it. Vertical legends run bottom to top with
+/// ticks to the right and the histogram to the left.
+#[derive(Clone, Debug)]
+pub struct ColorLegend {
+    /// Optional title shown above the legend
+    pub title: Option<String>,
+    /// Ramp orientation
+    pub orientation: LegendOrientation,
+    /// Ramp length in pixels
+    pub length: f64,
+    /// Ramp thickness in pixels
+    pub thickness: f64,
+    /// Approximate number of ticks
+    pub tick_count: usize,
+    /// Tick mark length
+    pub tick_size: f64,
+    /// Font size used to reserve space for labels and title
+    pub font_size: f64,
+    /// Tick label format (None = automatic)
+    pub format: Option<NumberFormat>,
+    /// Value always shown as a tick, such as a diverging midpoint
+    pub pivot: Option<f64>,
+    domain: (f64, f64),
+    stops: Vec<GradientStop>,
+    histogram: Option<HistogramStrip>,
+}
+
+impl ColorLegend {
+    /// Create a legend for a numeric domain with a grayscale ramp
+    pub fn new(min: f64, max: f64) -> Self {
+        Self {
+            title: None,
+            orientation: LegendOrientation::Horizontal,
+            length: 240.0,
+            thickness: 10.0,
+            tick_count: 5,
+            tick_size: 4.0,
+            font_size: 11.0,
+            format: None,
+            pivot: None,
+            domain: (min, max),
+            stops: vec![
+                GradientStop::new(0.0, Rgba::WHITE),
+                GradientStop::new(1.0, Rgba::BLACK),
+            ],
+            histogram: None,
+        }
+    }
+
+    /// Sample a color scale into `samples` evenly spaced stops
+    pub fn with_color_scale(mut self, scale: &dyn ColorScale, samples: usize) -> Self {
+        let samples = samples.max(2);
+        self.stops = (0..samples)
+            .map(|i| {
+                let t = i as f64 / (samples - 1) as f64;
+                GradientStop::new(t, scale.color(t))
+            })
+            .collect();
+        self
+    }
+
+    /// Set explicit gradient stops (offsets in 0..1)
+    pub fn with_stops(mut self, mut stops: Vec<GradientStop>) -> Self {
+        if !stops.is_empty() {
+            stops.sort_by(|a, b| a.offset.partial_cmp(&b.offset).unwrap_or(std::cmp::Ordering::Equal));
+            self.stops = stops;
+        }
+        self
+    }
+
+    /// Set the title
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the orientation
+    pub fn with_orientation(mut self, orientation: LegendOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set ramp length and thickness
+    pub fn with_size(mut self, length: f64, thickness: f64) -> Self {
+        self.length = length;
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set the approximate tick count
+    pub fn with_tick_count(mut self, count: usize) -> Self {
+        self.tick_count = count;
+        self
+    }
+
+    /// Set the tick label format
+    pub fn with_format(mut self, format: NumberFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Always show a tick at `value` (e.g. the midpoint of a diverging scale)
+    pub fn with_pivot(mut self, value: f64) -> Self {
+        self.pivot = Some(value);
+        self
+    }
+
+    /// Resample the ramp from a domain-aware color source
+    ///
+    /// Unlike [`with_color_scale`](Self::with_color_scale), stops are taken
+    /// at evenly spaced domain values, so non-linear and pivoted mappings
+    /// show on the ramp exactly as they do in the chart.
+    pub fn with_color_source(mut self, source: &dyn LegendColorSource, samples: usize) -> Self {
+        let (min, max) = source.legend_domain();
+        let samples = samples.max(2);
+        self.stops = (0..samples)
+            .map(|i| {
+                let t = i as f64 / (samples - 1) as f64;
+                GradientStop::new(t, source.legend_color(min + (max - min) * t))
+            })
+            .collect();
+        // Pin the pivot color at its exact position on the ramp
+        if let Some(pivot) = source.legend_pivot() {
+            let offset = if max == min { 0.5 } else { (pivot - min) / (max - min) };
+            if offset > 0.0 && offset < 1.0 && !self.stops.iter().any(|s| (s.offset - offset).abs() < 1e-9) {
+                let at = self.stops.partition_point(|s| s.offset < offset);
+                self.stops.insert(at, GradientStop::new(offset, source.legend_color(pivot)));
+            }
+        }
+        self
+    }
+
+    /// Enable the histogram strip with `bins` bins and a maximum bar size
+    pub fn with_histogram(mut self, bins: usize, size: f64) -> Self {
+        self.histogram = Some(HistogramStrip::new(bins, size));
+        self
+    }
+
+    /// Get the domain
+    pub fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    /// Set the domain (existing histogram counts are cleared)
+    pub fn set_domain(&mut self, min: f64, max: f64) {
+        self.domain = (min, max);
+        if let Some(hist) = &mut self.histogram {
+            hist.counts = vec![0; hist.bins];
+        }
+    }
+
+    /// Get the gradient stops
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    /// Get the histogram strip, if enabled
+    pub fn histogram(&self) -> Option<&HistogramStrip> {
+        self.histogram.as_ref()
+    }
+
+    /// Bin raw data values into the histogram strip
+    ///
+    /// Non-finite values are ignored; values outside the domain are counted
+    /// in the first or last bin, matching how the color scale clamps them.
+    /// Does nothing if no histogram is configured.
+    pub fn set_values(&mut self, values: &[f64]) {
+        let (min, max) = self.domain;
+        let hist = match &mut self.histogram {
+            Some(hist) => hist,
+            None => return,
+        };
+
+        let mut counts = vec![0usize; hist.bins];
+        let span = max - min;
+        for &v in values {
+            if !v.is_finite() {
+                continue;
+            }
+            let t = if span.abs() < f64::EPSILON { 0.0 } else { (v - min) / span };
+            let bin = ((t * hist.bins as f64).floor().max(0.0) as usize).min(hist.bins - 1);
+            counts[bin] += 1;
+        }
+        hist.counts = counts;
+    }
+
+    /// Normalized ramp position (0..1) of a domain value, clamped
+    pub fn normalize(&self, value: f64) -> f64 {
+        let (min, max) = self.domain;
+        let span = max - min;
+        if span.abs() < f64::EPSILON {
+            0.5
+        } else {
+            ((value - min) / span).clamp(0.0, 1.0)
+        }
+    }
+
+    /// Color of a domain value according to the sampled stops
+    pub fn color_at(&self, value: f64) -> Rgba {
+        let t = self.normalize(value);
+        let first = self.stops[0];
+        if t <= first.offset {
+            return first.color;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if t <= b.offset {
+                let span = b.offset - a.offset;
+                let local = if span > 0.0 { (t - a.offset) / span } else { 1.0 };
+                return a.color.lerp(&b.color, local as f32);
+            }
+        }
+        self.stops[self.stops.len() - 1].color
+    }
+
+    /// Generate ticks with positions relative to the ramp start
+    pub fn ticks(&self) -> Vec<Tick> {
+        let (min, max) = self.domain;
+        let (lo, hi) = if min <= max { (min, max) } else { (max, min) };
+        let step = nice_step(hi - lo, self.tick_count.max(1));
+        if !step.is_finite() || step <= 0.0 {
+            return Vec::new();
+        }
+
+        let epsilon = step * 1e-6;
+        let mut values = Vec::new();
+        let mut value = (lo / step).ceil() * step;
+        while value <= hi + epsilon {
+            // Avoid "-0" labels from ceil on negative bounds
+            values.push(if value == 0.0 { 0.0 } else { value });
+            value += step;
+        }
+        if let Some(pivot) = self.pivot.filter(|p| *p >= lo - epsilon && *p <= hi + epsilon) {
+            if !values.iter().any(|v| (v - pivot).abs() <= epsilon) {
+                let at = values.partition_point(|v| *v < pivot);
+                values.insert(at, pivot);
+            }
+        }
+
+        values
+            .into_iter()
+            .map(|value| {
+                let offset = self.normalize(value) * self.length;
+                let position = match self.orientation {
+                    LegendOrientation::Horizontal => offset,
+                    LegendOrientation::Vertical => self.length - offset,
+                };
+                let label = match &self.format {
+                    Some(format) => format.format(value),
+                    None => format_number(value),
+                };
+                Tick::new(value, label).with_position(position)
+            })
+            .collect()
+    }
+
+    fn histogram_extent(&self) -> f64 {
+        self.histogram.as_ref().map_or(0.0, |h| h.size + h.gap)
+    }
+
+    fn title_extent(&self) -> f64 {
+        if self.title.is_some() {
+            self.font_size * 1.5
+        } else {
+            0.0
+        }
+    }
+
+    /// Compute the legend layout at the given origin (top-left corner)
+    pub fn compute_layout(&self, origin_x: f64, origin_y: f64) -> ColorLegendLayout {
+        let title_h = self.title_extent();
+        let hist_extent = self.histogram_extent();
+        let label_extent = self.tick_size + self.font_size * 1.2;
+        let title_position = self.title.as_ref().map(|_| (origin_x, origin_y));
+        let content_y = origin_y + title_h;
+
+        let (ramp, width, height) = match self.orientation {
+            LegendOrientation::Horizontal => {
+                let ramp = (origin_x, content_y + hist_extent, self.length, self.thickness);
+                (ramp, self.length, title_h + hist_extent + self.thickness + label_extent)
+            }
+            LegendOrientation::Vertical => {
+                let ramp = (origin_x + hist_extent, content_y, self.thickness, self.length);
+                let label_width = self.font_size * 4.0;
+                (ramp, hist_extent + self.thickness + self.tick_size + label_width, title_h + self.length)
+            }
+        };
+
+        let axis_start = match self.orientation {
+            LegendOrientation::Horizontal => ramp.0,
+            LegendOrientation::Vertical => ramp.1,
+        };
+        let ticks = self
+            .ticks()
+            .into_iter()
+            .map(|t| {
+                let position = axis_start + t.position;
+                t.with_position(position)
+            })
+            .collect();
+
+        ColorLegendLayout {
+            ramp,
+            stops: self.stops.clone(),
+            ticks,
+            bars: self.layout_bars(ramp),
+            title_position,
+            width,
+            height,
+        }
+    }
+
+    fn layout_bars(&self, ramp: (f64, f64, f64, f64)) -> Vec<HistogramBar> {
+        let hist = match &self.histogram {
+            Some(hist) => hist,
+            None => return Vec::new(),
+        };
+        let max_count = hist.max_count();
+        let (min, max) = self.domain;
+        let bin_len = self.length / hist.bins as f64;
+        let bin_span = (max - min) / hist.bins as f64;
+        let pad = hist.bar_padding.min(bin_len * 0.5);
+
+        hist.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let extent = if max_count > 0 {
+                    hist.size * count as f64 / max_count as f64
+                } else {
+                    0.0
+                };
+                let range = (min + bin_span * i as f64, min + bin_span * (i + 1) as f64);
+                let color = self.color_at((range.0 + range.1) / 2.0);
+                let along = i as f64 * bin_len + pad / 2.0;
+                let thickness = bin_len - pad;
+
+                let (x, y, width, height) = match self.orientation {
+                    LegendOrientation::Horizontal => {
+                        let base = ramp.1 - hist.gap;
+                        (ramp.0 + along, base - extent, thickness, extent)
+                    }
+                    LegendOrientation::Vertical => {
+                        let base = ramp.0 - hist.gap;
+                        let top = ramp.1 + self.length - along - thickness;
+                        (base - extent, top, extent, thickness)
+                    }
+                };
+
+                HistogramBar { x, y, width, height, count, range, color }
+            })
+            .collect()
+    }
+}
+
+/// A chart color scale a [`ColorLegend`] can follow
+pub trait LegendColorSource {
+    /// Domain endpoints as (start, end)
+    fn legend_domain(&self) -> (f64, f64);
+
+    /// Color the chart uses for a domain value
+    fn legend_color(&self, value: f64) -> Rgba;
+
+    /// Value that should always be labelled, such as a diverging midpoint
+    fn legend_pivot(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl LegendColorSource for SequentialScale<Rgba> {
+    fn legend_domain(&self) -> (f64, f64) {
+        self.get_domain()
+    }
+
+    fn legend_color(&self, value: f64) -> Rgba {
+        self.interpolate(value)
+    }
+}
+
+impl LegendColorSource for SequentialScale<[f64; 3]> {
+    fn legend_domain(&self) -> (f64, f64) {
+        self.get_domain()
+    }
+
+    fn legend_color(&self, value: f64) -> Rgba {
+        let [r, g, b] = self.interpolate(value);
+        Rgba::rgb(r as f32, g as f32, b as f32)
+    }
+}
+
+impl LegendColorSource for DivergingScale<Rgba> {
+    fn legend_domain(&self) -> (f64, f64) {
+        let (low, _, high) = self.get_domain();
+        (low, high)
+    }
+
+    fn legend_color(&self, value: f64) -> Rgba {
+        self.interpolate(value)
+    }
+
+    fn legend_pivot(&self) -> Option<f64> {
+        Some(self.pivot())
+    }
+}
+
+impl LegendColorSource for DivergingScale<[f64; 3]> {
+    fn legend_domain(&self) -> (f64, f64) {
+        let (low, _, high) = self.get_domain();
+        (low, high)
+    }
+
+    fn legend_color(&self, value: f64) -> Rgba {
+        let [r, g, b] = self.interpolate(value);
+        Rgba::rgb(r as f32, g as f32, b as f32)
+    }
+
+    fn legend_pivot(&self) -> Option<f64> {
+        Some(self.pivot())
+    }
+}
+
+/// A [`ColorScale`] placed on a data domain
+///
+/// Color scales such as [`DivergingScale`](crate::color::DivergingScale)
+/// take a position in `0..1`; this maps data values onto it. With a pivot
+/// the two halves are scaled separately so the pivot lands on the scale's
+/// midpoint, as in d3's `scaleDiverging`.
+#[derive(Clone, Debug)]
+pub struct DomainColorScale<S> {
+    /// Underlying color scale
+    pub scale: S,
+    domain: (f64, f64),
+    pivot: Option<f64>,
+}
+
+impl<S: ColorScale> DomainColorScale<S> {
+    /// Map `min..max` onto `scale`
+    pub fn new(scale: S, min: f64, max: f64) -> Self {
+        Self { scale, domain: (min, max), pivot: None }
+    }
+
+    /// Split the domain at `pivot`, which maps to the scale midpoint
+    pub fn with_pivot(mut self, pivot: f64) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+
+    /// Set the domain
+    pub fn set_domain(&mut self, min: f64, max: f64) {
+        self.domain = (min, max);
+    }
+
+    /// Set or clear the pivot
+    pub fn set_pivot(&mut self, pivot: Option<f64>) {
+        self.pivot = pivot;
+    }
+
+    /// Get the domain
+    pub fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    /// Position of a domain value on the color scale, clamped to 0..1
+    pub fn normalize(&self, value: f64) -> f64 {
+        let (min, max) = self.domain;
+        let lerp = |a: f64, b: f64| if (b - a).abs() < f64::EPSILON { 0.5 } else { (value - a) / (b - a) };
+        let t = match self.pivot {
+            Some(mid) if (value - mid) * (max - min) < 0.0 => 0.5 * lerp(min, mid),
+            Some(mid) => 0.5 + 0.5 * lerp(mid, max),
+            None => lerp(min, max),
+        };
+        t.clamp(0.0, 1.0)
+    }
+
+    /// Color of a domain value
+    pub fn color(&self, value: f64) -> Rgba {
+        self.scale.color(self.normalize(value))
+    }
+}
+
+impl<S: ColorScale> LegendColorSource for DomainColorScale<S> {
+    fn legend_domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    fn legend_color(&self, value: f64) -> Rgba {
+        self.color(value)
+    }
+
+    fn legend_pivot(&self) -> Option<f64> {
+        self.pivot
+    }
+}
+
+/// Keeps a [`ColorLegend`] in step with a chart's color scale
+///
+/// Call [`sync`](Self::sync) with the chart's scale before laying out the
+/// legend (typically every frame). When the scale's domain or pivot has
+/// changed, the legend's extent, ramp, pivot tick and label format are
+/// derived from it again; otherwise nothing is recomputed.
+#[derive(Clone, Debug)]
+pub struct ColorLegendLink {
+    legend: ColorLegend,
+    format: NumberFormat,
+    samples: usize,
+    synced: Option<((f64, f64), Option<f64>)>,
+}
+
+impl ColorLegendLink {
+    /// Link `legend`, labelling ticks with `format`
+    pub fn new(legend: ColorLegend, format: NumberFormat) -> Self {
+        Self { legend, format, samples: 16, synced: None }
+    }
+
+    /// Set the number of ramp stops sampled from the scale
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(2);
+        self.synced = None;
+        self
+    }
+
+    /// Set the tick label format
+    pub fn set_format(&mut self, format: NumberFormat) {
+        self.format = format;
+        self.synced = None;
+    }
+
+    /// Force the next [`sync`](Self::sync) to rebuild, e.g. after the
+    /// scale's colors changed without a domain change
+    pub fn invalidate(&mut self) {
+        self.synced = None;
+    }
+
+    /// Update the legend from `source`; returns whether anything changed
+    ///
+    /// Histogram counts are cleared on a domain change, as with
+    /// [`ColorLegend::set_domain`].
+    pub fn sync(&mut self, source: &dyn LegendColorSource) -> bool {
+        let state = (source.legend_domain(), source.legend_pivot());
+        if self.synced == Some(state) {
+            return false;
+        }
+        let ((min, max), pivot) = state;
+        if self.legend.domain() != (min, max) {
+            self.legend.set_domain(min, max);
+        }
+        let mut legend = std::mem::replace(&mut self.legend, ColorLegend::new(min, max));
+        legend = legend.with_color_source(source, self.samples);
+        legend.pivot = pivot;
+        legend.format = Some(self.format.clone());
+        self.legend = legend;
+        self.synced = Some(state);
+        true
+    }
+
+    /// The linked legend
+    pub fn legend(&self) -> &ColorLegend {
+        &self.legend
+    }
+
+    /// Mutable access for layout options and histogram values
+    pub fn legend_mut(&mut self) -> &mut ColorLegend {
+        &mut self.legend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::{DivergingScale, SequentialScale};
+
+    #[test]
+    fn test_sampled_stops() {
+        let legend = ColorLegend::new(0.0, 1.0).with_color_scale(&SequentialScale::viridis(), 8);
+        assert_eq!(legend.stops().len(), 8);
+        assert_eq!(legend.stops()[0].offset, 0.0);
+        assert_eq!(legend.stops()[7].offset, 1.0);
+        assert_eq!(legend.stops()[0].color, SequentialScale::viridis().color(0.0));
+    }
+
+    #[test]
+    fn test_domain_color_scale_pivot() {
+        let scale = DomainColorScale::new(DivergingScale::red_blue(), -2.0, 8.0).with_pivot(0.0);
+        assert_eq!(scale.normalize(-2.0), 0.0);
+        assert_eq!(scale.normalize(-1.0), 0.25);
+        assert_eq!(scale.normalize(0.0), 0.5);
+        assert_eq!(scale.normalize(4.0), 0.75);
+        assert_eq!(scale.color(0.0), DivergingScale::red_blue().color(0.5));
+    }
+
+    #[test]
+    fn test_link_follows_sequential_scale() {
+        let mut scale = crate::scale::SequentialScale::new(crate::scale::interpolators::grayscale())
+            .domain(0.0, 50.0);
+        let mut link = ColorLegendLink::new(ColorLegend::new(0.0, 1.0).with_histogram(2, 10.0), NumberFormat::Percent)
+            .with_samples(3);
+        assert!(link.sync(&scale));
+        assert_eq!(link.legend().stops().len(), 3);
+        assert_eq!(link.legend().stops()[2].color, Rgba::rgb(1.0, 1.0, 1.0));
+        assert!(link.legend().format.is_some());
+
+        link.legend_mut().set_values(&[1.0, 2.0]);
+        assert!(!link.sync(&scale));
+        assert_eq!(link.legend().histogram().unwrap().counts(), &[2, 0]);
+
+        scale.set_domain(0.0, 1.0);
+        assert!(link.sync(&scale));
+        let ticks = link.legend().ticks();
+        assert_eq!(ticks.last().unwrap().label, NumberFormat::Percent.format(1.0));
+        // Domain change clears stale counts
+        assert_eq!(link.legend().histogram().unwrap().counts(), &[0, 0]);
+    }
+
+    #[test]
+    fn test_link_follows_diverging_scale() {
+        let scale = crate::scale::DivergingScale::new(|t| Rgba::rgb(t as f32, 0.0, 0.0))
+            .domain(-1.0, 0.25, 9.0)
+            .symlog(1.0);
+        let mut link = ColorLegendLink::new(ColorLegend::new(0.0, 1.0), NumberFormat::Auto).with_samples(5);
+        assert!(link.sync(&scale));
+        assert_eq!(link.legend().pivot, Some(0.25));
+        assert_eq!(link.legend().color_at(0.25), scale.interpolate(0.25));
+        assert!(link.legend().ticks().iter().any(|t| t.value == 0.25));
+    }
+
+    #[test]
+    fn test_pivot_tick_inserted() {
+        let legend = ColorLegend::new(-3.0, 7.0).with_tick_count(2).with_pivot(1.5);
+        let values: Vec<f64> = legend.ticks().iter().map(|t| t.value).collect();
+        assert!(values.contains(&1.5));
+        assert!(values.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_color_at_interpolates_and_clamps() {
+        let legend = ColorLegend::new(0.0, 100.0);
+        let mid = legend.color_at(50.0);
+        assert!((mid.r - 0.5).abs() < 0.01);
+        assert_eq!(legend.color_at(-10.0), Rgba::WHITE);
+        assert_eq!(legend.color_at(1000.0), Rgba::BLACK);
+    }
+
+    #[test]
+    fn test_histogram_counts() {
+        let mut legend = ColorLegend::new(0.0, 10.0).with_histogram(5, 20.0);
+        legend.set_values(&[0.0, 1.9, 2.0, 5.5, 10.0, 25.0, -3.0, f64::NAN]);
+
+        let hist = legend.histogram().unwrap();
+        assert_eq!(hist.counts(), &[3, 1, 1, 0, 2]);
+        assert_eq!(hist.max_count(), 3);
+    }
+
+    #[test]
+    fn test_set_values_without_histogram_is_noop() {
+        let mut legend = ColorLegend::new(0.0, 10.0);
+        legend.set_values(&[1.0, 2.0]);
+        assert!(legend.histogram().is_none());
+    }
+
+    #[test]
+    fn test_horizontal_layout() {
+        let mut legend = ColorLegend::new(0.0, 100.0)
+            .with_size(200.0, 10.0)
+            .with_histogram(4, 40.0);
+        legend.set_values(&[10.0, 10.0, 60.0]);
+
+        let layout = legend.compute_layout(0.0, 0.0);
+        let hist = legend.histogram().unwrap();
+        assert_eq!(layout.ramp, (0.0, 40.0 + hist.gap, 200.0, 10.0));
+
+        // Tallest bar reaches the full strip size and sits on the ramp
+        let tallest = &layout.bars[0];
+        assert!((tallest.height - 40.0).abs() < 1e-9);
+        assert!((tallest.y + tallest.height - (layout.ramp.1 - hist.gap)).abs() < 1e-9);
+        assert!((layout.bars[2].height - 20.0).abs() < 1e-9);
+        assert_eq!(layout.bars[1].height, 0.0);
+        assert_eq!(layout.bars[0].range, (0.0, 25.0));
+
+        // Ticks span the ramp
+        assert_eq!(layout.ticks.first().unwrap().position, 0.0);
+        assert_eq!(layout.ticks.last().unwrap().position, 200.0);
+    }
+
+    #[test]
+    fn test_vertical_layout() {
+        let mut legend = ColorLegend::new(0.0, 10.0)
+            .with_orientation(LegendOrientation::Vertical)
+            .with_size(100.0, 12.0)
+            .with_histogram(2, 30.0)
+            .with_title("Density");
+        legend.set_values(&[1.0, 8.0, 9.0]);
+
+        let layout = legend.compute_layout(0.0, 0.0);
+        assert!(layout.title_position.is_some());
+        let (rx, ry, rw, rh) = layout.ramp;
+        assert_eq!(rw, 12.0);
+        assert_eq!(rh, 100.0);
+
+        // Low values at the bottom, high values at the top
+        let low = layout.ticks.iter().find(|t| t.value == 0.0).unwrap();
+        let high = layout.ticks.iter().find(|t| t.value == 10.0).unwrap();
+        assert_eq!(low.position, ry + 100.0);
+        assert_eq!(high.position, ry);
+
+        // High bin is the tallest and grows leftward from the ramp
+        let upper = &layout.bars[1];
+        assert!((upper.width - 30.0).abs() < 1e-9);
+        assert!(upper.y < layout.bars[0].y);
+        assert!(upper.x + upper.width <= rx);
+    }
+}
@@ -20,8 +20,41 @@
 //! // Get line positions for rendering
 //! let (v_line, h_line) = crosshair.get_lines();
 //! ```
+//!
+//! # Multi-series Ruler
+//!
+//! With snap points for every series, [`Crosshair::ruler_at`] intersects a
+//! vertical ruler with each visible series and lays out one value badge per
+//! series, stacked so badges never overlap:
+//!
+//! ```
+//! use makepad_d3::component::{Crosshair, RulerSeries, SnapPoint};
+//! use makepad_d3::color::Rgba;
+//!
+//! let mut crosshair = Crosshair::new().bounds(0.0, 0.0, 400.0, 200.0);
+//! let point = |series_index, point_index, x: f64, y: f64, value| SnapPoint {
+//!     x, y, x_value: x, y_value: value, series_index, point_index,
+//! };
+//! crosshair.set_snap_points(vec![
+//!     point(0, 0, 0.0, 100.0, 50.0),
+//!     point(0, 1, 200.0, 60.0, 70.0),
+//!     point(1, 0, 0.0, 104.0, 48.0),
+//!     point(1, 1, 200.0, 64.0, 68.0),
+//! ]);
+//!
+//! let series = vec![
+//!     RulerSeries::new("Bid", Rgba::from_hex(0x4285F4)),
+//!     RulerSeries::new("Ask", Rgba::from_hex(0xEA4335)),
+//! ];
+//! let ruler = crosshair.ruler_at(100.0, &series);
+//!
+//! assert_eq!(ruler.badges.len(), 2);
+//! assert_eq!(ruler.badges[0].text, "60.00");
+//! // The two series are 4px apart, so the second badge is pushed down
+//! assert!(ruler.badges[1].y >= ruler.badges[0].y + ruler.badges[0].height);
+//! ```
 
-use crate::color::Rgba;
+use crate::color::{readable_text_color, Rgba};
 use serde::{Deserialize, Serialize};
 
 /// Crosshair display mode
@@ -295,6 +328,119 @@ pub struct SnapPoint {
     pub point_index: usize,
 }
 
+/// Series shown on the multi-series ruler
+///
+/// The series' samples are the crosshair snap points whose `series_index`
+/// equals the position of this entry in the slice passed to the ruler.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RulerSeries {
+    /// Series label
+    pub label: String,
+    /// Series color (badge background)
+    pub color: Rgba,
+    /// Whether the series is shown
+    pub visible: bool,
+}
+
+impl RulerSeries {
+    /// Create a visible ruler series
+    pub fn new(label: impl Into<String>, color: Rgba) -> Self {
+        Self {
+            label: label.into(),
+            color,
+            visible: true,
+        }
+    }
+
+    /// Set visibility
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+}
+
+/// Badge layout configuration for the multi-series ruler
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RulerConfig {
+    /// Badge font size
+    pub font_size: f64,
+    /// Horizontal padding inside badges
+    pub padding: f64,
+    /// Badge height
+    pub badge_height: f64,
+    /// Minimum vertical gap between stacked badges
+    pub gap: f64,
+    /// Horizontal distance between the ruler and the badges
+    pub tick_length: f64,
+    /// Decimal places in badge values
+    pub decimals: usize,
+}
+
+impl Default for RulerConfig {
+    fn default() -> Self {
+        Self {
+            font_size: 11.0,
+            padding: 4.0,
+            badge_height: 18.0,
+            gap: 2.0,
+            tick_length: 6.0,
+            decimals: 2,
+        }
+    }
+}
+
+/// A value badge on the multi-series ruler
+#[derive(Clone, Debug, PartialEq)]
+pub struct RulerBadge {
+    /// Index of the series in the ruler series slice
+    pub series_index: usize,
+    /// Series label
+    pub label: String,
+    /// Series value at the ruler position
+    pub value: f64,
+    /// Formatted value
+    pub text: String,
+    /// Intersection of the ruler with the series (x, y)
+    pub point: (f64, f64),
+    /// Badge left edge
+    pub x: f64,
+    /// Badge top edge
+    pub y: f64,
+    /// Badge width
+    pub width: f64,
+    /// Badge height
+    pub height: f64,
+    /// Badge background color
+    pub color: Rgba,
+    /// Badge text color (black or white, whichever contrasts more)
+    pub text_color: Rgba,
+    /// Connector tick from the intersection to the badge edge
+    pub connector: ((f64, f64), (f64, f64)),
+}
+
+impl RulerBadge {
+    /// Vertical center of the badge
+    pub fn center_y(&self) -> f64 {
+        self.y + self.height / 2.0
+    }
+
+    /// Whether stacking moved the badge away from its intersection
+    pub fn is_displaced(&self) -> bool {
+        (self.center_y() - self.point.1).abs() > 1e-9
+    }
+}
+
+/// Vertical ruler with per-series value badges
+#[derive(Clone, Debug)]
+pub struct CrosshairRuler {
+    /// Ruler X position
+    pub x: f64,
+    /// The vertical ruler line
+    pub line: CrosshairLine,
+    /// Badges ordered top to bottom
+    pub badges: Vec<RulerBadge>,
+}
+
 /// Crosshair component for cursor tracking
 #[derive(Clone, Debug)]
 pub struct Crosshair {
@@ -320,6 +466,8 @@ pub struct Crosshair {
     pub snap_threshold: f64,
     /// Current snapped point (if any)
     pub snapped_point: Option<SnapPoint>,
+    /// Multi-series ruler badge layout
+    pub ruler: RulerConfig,
     /// Available snap points
     snap_points: Vec<SnapPoint>,
 }
@@ -338,6 +486,7 @@ impl Default for Crosshair {
             snap_to_data: false,
             snap_threshold: 20.0,
             snapped_point: None,
+            ruler: RulerConfig::default(),
             snap_points: Vec::new(),
         }
     }
@@ -398,6 +547,12 @@ impl Crosshair {
         self
     }
 
+    /// Set multi-series ruler badge layout
+    pub fn ruler_config(mut self, config: RulerConfig) -> Self {
+        self.ruler = config;
+        self
+    }
+
     /// Set chart bounds
     pub fn bounds(mut self, x: f64, y: f64, width: f64, height: f64) -> Self {
         self.bounds = (x, y, width, height);
@@ -546,6 +701,112 @@ impl Crosshair {
         })
     }
 
+    /// Get the multi-series ruler at the effective cursor X, if active
+    pub fn ruler(&self, series: &[RulerSeries]) -> Option<CrosshairRuler> {
+        if !self.active {
+            return None;
+        }
+        Some(self.ruler_at(self.effective_position().0, series))
+    }
+
+    /// Compute the multi-series ruler at a given X
+    ///
+    /// Each visible series is intersected by linear interpolation between its
+    /// snap points; series that don't span `x` get no badge. Badges sit beside
+    /// the ruler (flipping to the left near the right edge), centered on their
+    /// intersection where possible and pushed apart vertically, within the
+    /// chart bounds, where they would overlap.
+    pub fn ruler_at(&self, x: f64, series: &[RulerSeries]) -> CrosshairRuler {
+        let (bx, by, bw, bh) = self.bounds;
+        let config = &self.ruler;
+        let h = config.badge_height;
+
+        let mut badges: Vec<RulerBadge> = series
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.visible)
+            .filter_map(|(i, s)| {
+                let (y, value) = self.series_intersection(i, x)?;
+                let text = format!("{:.*}", config.decimals, value);
+                let width = text.len() as f64 * config.font_size * 0.6 + config.padding * 2.0;
+                Some(RulerBadge {
+                    series_index: i,
+                    label: s.label.clone(),
+                    value,
+                    text,
+                    point: (x, y),
+                    x: 0.0,
+                    y: y - h / 2.0,
+                    width,
+                    height: h,
+                    color: s.color,
+                    text_color: readable_text_color(&s.color),
+                    connector: ((x, y), (x, y)),
+                })
+            })
+            .collect();
+        badges.sort_by(|a, b| a.point.1.total_cmp(&b.point.1));
+
+        // Push overlapping badges down, then back up from the bottom edge,
+        // then down again from the top if they don't all fit
+        let step = h + config.gap;
+        let (top, bottom) = (by, by + bh - h);
+        for i in 0..badges.len() {
+            let min = if i == 0 { top } else { badges[i - 1].y + step };
+            badges[i].y = badges[i].y.max(min);
+        }
+        for i in (0..badges.len()).rev() {
+            let max = if i + 1 == badges.len() { bottom } else { badges[i + 1].y - step };
+            badges[i].y = badges[i].y.min(max);
+        }
+        for i in 0..badges.len() {
+            let min = if i == 0 { top } else { badges[i - 1].y + step };
+            badges[i].y = badges[i].y.max(min);
+        }
+
+        let widest = badges.iter().map(|b| b.width).fold(0.0, f64::max);
+        let right_side = x + config.tick_length + widest <= bx + bw;
+        for badge in &mut badges {
+            let (edge, left) = if right_side {
+                let edge = x + config.tick_length;
+                (edge, edge)
+            } else {
+                let edge = x - config.tick_length;
+                (edge, edge - badge.width)
+            };
+            badge.x = left;
+            badge.connector = (badge.point, (edge, badge.center_y()));
+        }
+
+        CrosshairRuler {
+            x,
+            line: CrosshairLine::vertical(x, by, by + bh),
+            badges,
+        }
+    }
+
+    /// Screen Y and value of a series at X, interpolated between snap points
+    fn series_intersection(&self, series_index: usize, x: f64) -> Option<(f64, f64)> {
+        let mut points: Vec<&SnapPoint> = self
+            .snap_points
+            .iter()
+            .filter(|p| p.series_index == series_index)
+            .collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        let i = points.iter().position(|p| p.x >= x)?;
+        let p1 = points[i];
+        if p1.x == x {
+            return Some((p1.y, p1.y_value));
+        }
+        let p0 = points.get(i.checked_sub(1)?)?;
+        let t = (x - p0.x) / (p1.x - p0.x);
+        Some((
+            p0.y + (p1.y - p0.y) * t,
+            p0.y_value + (p1.y_value - p0.y_value) * t,
+        ))
+    }
+
     /// Set X axis label format
     pub fn set_x_format(&mut self, format: impl Into<String>) {
         self.labels.x_format = Some(format.into());
@@ -813,6 +1074,71 @@ mod tests {
         assert!(crosshair.labels.visible);
     }
 
+    fn ruler_crosshair() -> Crosshair {
+        let mut crosshair = Crosshair::new().bounds(0.0, 0.0, 200.0, 100.0);
+        let series = [(0, 10.0, 50.0), (1, 12.0, 48.0), (2, 95.0, 1.0)];
+        for (s, y, value) in series {
+            for (i, x) in [0.0, 100.0].into_iter().enumerate() {
+                crosshair.add_snap_point(SnapPoint {
+                    x,
+                    y,
+                    x_value: x,
+                    y_value: value + i as f64,
+                    series_index: s,
+                    point_index: i,
+                });
+            }
+        }
+        crosshair
+    }
+
+    #[test]
+    fn test_ruler_intersections_and_stacking() {
+        let crosshair = ruler_crosshair();
+        let series = vec![
+            RulerSeries::new("A", Rgba::BLACK),
+            RulerSeries::new("B", Rgba::WHITE),
+            RulerSeries::new("C", Rgba::RED),
+        ];
+        let ruler = crosshair.ruler_at(50.0, &series);
+
+        assert_eq!(ruler.badges.len(), 3);
+        let a = &ruler.badges[0];
+        assert_eq!(a.series_index, 0);
+        assert!((a.value - 50.5).abs() < 1e-9);
+        assert_eq!(a.text, "50.50");
+        assert_eq!(a.text_color, Rgba::WHITE);
+        // A is centered on its intersection; B is pushed below it
+        assert_eq!(a.y, 1.0);
+        assert!(!a.is_displaced());
+        assert_eq!(ruler.badges[1].y, 21.0);
+        assert!(ruler.badges[1].is_displaced());
+        // C is clamped to the bottom edge
+        assert_eq!(ruler.badges[2].y, 82.0);
+        // Badges sit to the right of the ruler, connected by a tick
+        assert_eq!(a.x, 56.0);
+        assert_eq!(a.connector, ((50.0, 10.0), (56.0, 10.0)));
+    }
+
+    #[test]
+    fn test_ruler_hidden_series_and_flip() {
+        let mut crosshair = ruler_crosshair();
+        crosshair.set_bounds((0.0, 0.0, 120.0, 100.0));
+        let series = vec![
+            RulerSeries::new("A", Rgba::BLACK),
+            RulerSeries::new("B", Rgba::BLACK).with_visible(false),
+        ];
+        let ruler = crosshair.ruler_at(100.0, &series);
+        assert_eq!(ruler.badges.len(), 1);
+        // Near the right edge the badges flip to the left
+        let badge = &ruler.badges[0];
+        assert_eq!(badge.x + badge.width, 94.0);
+
+        // Outside the series' extent there is no intersection
+        assert!(crosshair.ruler_at(150.0, &series).badges.is_empty());
+        assert!(crosshair.ruler(&series).is_none());
+    }
+
     #[test]
     fn test_crosshair_labels() {
         let mut crosshair = Crosshair::new()
@@ -19,7 +19,11 @@
 //! assert!(legend.is_visible(0));
 //! ```
 
-use crate::color::Rgba;
+use std::sync::Arc;
+
+use crate::axis::{ApproxTextMeasurer, TextDirection, TextMeasurer, truncate_label};
+use crate::color::{CategoricalScale, Rgba};
+use crate::data::{Dataset, Marker, MarkerAnchor, MarkerLayout};
 use serde::{Deserialize, Serialize};
 
 /// Shape of the legend symbol
@@ -88,6 +92,9 @@ pub struct LegendItem {
     pub value: Option<String>,
     /// Optional description/tooltip
     pub description: Option<String>,
+    /// Image or glyph drawn instead of the color swatch
+    #[serde(default)]
+    pub marker: Option<Marker>,
 }
 
 impl LegendItem {
@@ -100,6 +107,7 @@ impl LegendItem {
             visible: true,
             value: None,
             description: None,
+            marker: None,
         }
     }
 
@@ -121,6 +129,22 @@ impl LegendItem {
         self
     }
 
+    /// Show an image or glyph (e.g. a logo or emoji) instead of the swatch
+    pub fn with_marker(mut self, marker: Marker) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Create an item for a dataset, taking its label, visibility,
+    /// description and point marker from the dataset
+    pub fn from_dataset(dataset: &Dataset, color: Rgba) -> Self {
+        let mut item = Self::new(dataset.label.clone(), color);
+        item.visible = !dataset.hidden;
+        item.description = dataset.meta.description.clone();
+        item.marker = dataset.point_marker.clone();
+        item
+    }
+
     /// Set a description/tooltip
     pub fn with_description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
@@ -142,6 +166,7 @@ impl Default for LegendItem {
             visible: true,
             value: None,
             description: None,
+            marker: None,
         }
     }
 }
@@ -174,6 +199,12 @@ pub struct LegendStyle {
     pub corner_radius: f64,
     /// Opacity for disabled items
     pub disabled_opacity: f32,
+    /// Base direction of label text (RTL mirrors the item flow)
+    #[serde(default)]
+    pub text_direction: TextDirection,
+    /// Maximum label width; longer labels are truncated with an ellipsis
+    #[serde(default)]
+    pub max_label_width: Option<f64>,
 }
 
 impl Default for LegendStyle {
@@ -190,10 +221,98 @@ impl Default for LegendStyle {
             padding: 8.0,
             corner_radius: 4.0,
             disabled_opacity: 0.4,
+            text_direction: TextDirection::Ltr,
+            max_label_width: None,
         }
     }
 }
 
+/// Distance in pixels a press must move before it becomes a drag
+pub const LEGEND_DRAG_THRESHOLD: f64 = 4.0;
+
+/// In-progress drag of a legend item
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegendDrag {
+    /// Index of the dragged item
+    pub from: usize,
+    /// Index the item would move to if dropped now
+    pub target: usize,
+    /// Pointer position where the drag started
+    pub start: (f64, f64),
+    /// Current pointer position
+    pub current: (f64, f64),
+    /// Whether the pointer moved past [`LEGEND_DRAG_THRESHOLD`]
+    pub active: bool,
+}
+
+impl LegendDrag {
+    /// Pointer offset from the drag start
+    pub fn offset(&self) -> (f64, f64) {
+        (self.current.0 - self.start.0, self.current.1 - self.start.1)
+    }
+}
+
+/// Series order change emitted when a legend item is moved
+///
+/// `order[i]` is the previous index of the series now at position `i`.
+/// Apply it to datasets (and therefore stack order, which follows dataset
+/// order with [`StackOrder::None`](crate::shape::StackOrder)) with
+/// [`apply`](Self::apply).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SeriesOrderChange {
+    /// Previous index of the moved series
+    pub from: usize,
+    /// New index of the moved series
+    pub to: usize,
+    /// Previous index of each series in the new order
+    pub order: Vec<usize>,
+}
+
+impl SeriesOrderChange {
+    /// Create the change for moving one item among `len`
+    pub fn new(len: usize, from: usize, to: usize) -> Self {
+        let mut order: Vec<usize> = (0..len).collect();
+        let moved = order.remove(from);
+        order.insert(to, moved);
+        Self { from, to, order }
+    }
+
+    /// Reorder a parallel collection (e.g. `ChartData::datasets`)
+    ///
+    /// Collections of a different length are left untouched; returns
+    /// whether the change was applied.
+    pub fn apply<T>(&self, items: &mut Vec<T>) -> bool {
+        if items.len() != self.order.len() || self.from >= items.len() || self.to >= items.len() {
+            return false;
+        }
+        let moved = items.remove(self.from);
+        items.insert(self.to, moved);
+        true
+    }
+}
+
+/// Visibility change emitted by legend clicks
+///
+/// Chart widgets apply these the same way, e.g. with
+/// [`Legend::apply_visibility`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegendEvent {
+    /// One series was shown or hidden
+    Toggled {
+        /// Item index
+        index: usize,
+        /// Visibility after the toggle
+        visible: bool,
+    },
+    /// One series was isolated: it is shown and every other series hidden
+    Isolated {
+        /// Item index
+        index: usize,
+    },
+    /// Every series was shown again after an isolation
+    Restored,
+}
+
 /// Interactive legend component
 ///
 /// Manages a collection of legend items with support for toggling,
@@ -214,6 +333,10 @@ pub struct Legend {
     pub title: Option<String>,
     /// Maximum items per row/column (0 = unlimited)
     pub max_items_per_line: usize,
+    /// Text measurer (None = [`ApproxTextMeasurer`])
+    pub text_measurer: Option<Arc<dyn TextMeasurer>>,
+    /// Item drag in progress
+    pub drag: Option<LegendDrag>,
 }
 
 impl Legend {
@@ -250,6 +373,20 @@ impl Legend {
         }
     }
 
+    /// Create from the key domain of an ordinal color scale, in domain order
+    pub fn from_categorical(scale: &CategoricalScale) -> Self {
+        let items = scale
+            .domain()
+            .iter()
+            .enumerate()
+            .map(|(i, key)| LegendItem::new(key.clone(), scale.get(i)))
+            .collect();
+        Self {
+            items,
+            ..Default::default()
+        }
+    }
+
     /// Set the orientation
     pub fn orientation(mut self, orientation: LegendOrientation) -> Self {
         self.orientation = orientation;
@@ -280,6 +417,12 @@ impl Legend {
         self
     }
 
+    /// Set the text measurer used for label widths
+    pub fn text_measurer(mut self, measurer: Arc<dyn TextMeasurer>) -> Self {
+        self.text_measurer = Some(measurer);
+        self
+    }
+
     /// Set maximum items per row/column
     pub fn max_items_per_line(mut self, max: usize) -> Self {
         self.max_items_per_line = max;
@@ -396,6 +539,60 @@ impl Legend {
         }
     }
 
+    /// Handle a click on an item: toggle that series
+    ///
+    /// Returns None if the legend is not interactive or the index is out
+    /// of range.
+    pub fn click(&mut self, index: usize) -> Option<LegendEvent> {
+        if !self.interactive || index >= self.items.len() {
+            return None;
+        }
+        self.toggle(index);
+        Some(LegendEvent::Toggled {
+            index,
+            visible: self.items[index].visible,
+        })
+    }
+
+    /// Handle a double-click on an item: isolate it, or restore all
+    ///
+    /// Double-clicking the only visible series shows every series again;
+    /// double-clicking any other series hides all the rest. The decision
+    /// looks at current visibility only, so platforms that deliver two
+    /// clicks before the double-click (whose toggles cancel out) behave
+    /// the same as those that do not.
+    pub fn double_click(&mut self, index: usize) -> Option<LegendEvent> {
+        if !self.interactive || index >= self.items.len() {
+            return None;
+        }
+        if self.isolated() == Some(index) {
+            self.show_all();
+            Some(LegendEvent::Restored)
+        } else {
+            for (i, item) in self.items.iter_mut().enumerate() {
+                item.visible = i == index;
+            }
+            Some(LegendEvent::Isolated { index })
+        }
+    }
+
+    /// Index of the isolated series: the only visible one among several
+    pub fn isolated(&self) -> Option<usize> {
+        match self.visible_indices().as_slice() {
+            &[index] if self.items.len() > 1 => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Copy item visibility onto parallel datasets (`hidden` flags)
+    ///
+    /// Datasets past the last item are left untouched.
+    pub fn apply_visibility(&self, datasets: &mut [Dataset]) {
+        for (dataset, item) in datasets.iter_mut().zip(&self.items) {
+            dataset.hidden = !item.visible;
+        }
+    }
+
     /// Calculate layout dimensions
     ///
     /// Returns (width, height) based on current items and style.
@@ -456,11 +653,44 @@ impl Legend {
         }
     }
 
-    /// Estimate average label width (simplified)
+    /// Check if items flow right-to-left
+    pub fn is_rtl(&self) -> bool {
+        self.style.text_direction.is_rtl()
+    }
+
+    /// Label of an item as displayed, truncated to the maximum label width
+    pub fn display_label(&self, index: usize) -> Option<String> {
+        let item = self.items.get(index)?;
+        Some(match self.style.max_label_width {
+            Some(max) => truncate_label(&item.label, max, self.style.font_size, self.measurer()),
+            None => item.label.clone(),
+        })
+    }
+
+    fn measurer(&self) -> &dyn TextMeasurer {
+        match &self.text_measurer {
+            Some(m) => m.as_ref(),
+            None => &ApproxTextMeasurer::DEFAULT,
+        }
+    }
+
+    /// Estimate average label width
     fn estimate_label_width(&self) -> f64 {
-        let avg_chars = self.items.iter().map(|i| i.label.len()).sum::<usize>() as f64
-            / self.items.len().max(1) as f64;
-        avg_chars * self.style.font_size * 0.6 // Rough estimate
+        let measurer = self.measurer();
+        let total: f64 = (0..self.items.len())
+            .filter_map(|i| self.display_label(i))
+            .map(|label| measurer.measure(&label, self.style.font_size))
+            .sum();
+        total / self.items.len().max(1) as f64
+    }
+
+    /// Mirror a column index for right-to-left layouts
+    fn visual_column(&self, col: usize, cols: usize) -> usize {
+        if self.is_rtl() {
+            cols - 1 - col
+        } else {
+            col
+        }
     }
 
     /// Get item at position (for hit testing)
@@ -501,6 +731,7 @@ impl Legend {
         if col >= cols || row >= rows {
             return None;
         }
+        let col = self.visual_column(col, cols);
 
         // Calculate item index based on orientation
         let index = match self.orientation {
@@ -515,9 +746,112 @@ impl Legend {
         }
     }
 
+    /// Move an item to a new index, returning the order change
+    ///
+    /// Returns None if either index is out of range or nothing moves.
+    pub fn move_item(&mut self, from: usize, to: usize) -> Option<SeriesOrderChange> {
+        if from >= self.items.len() || to >= self.items.len() || from == to {
+            return None;
+        }
+        let change = SeriesOrderChange::new(self.items.len(), from, to);
+        change.apply(&mut self.items);
+        Some(change)
+    }
+
+    /// Start dragging the item under the pointer
+    ///
+    /// Returns false if no item is there. The drag only becomes active once
+    /// the pointer moves past [`LEGEND_DRAG_THRESHOLD`], so a plain click can
+    /// still toggle the item.
+    pub fn begin_drag(&mut self, x: f64, y: f64, origin_x: f64, origin_y: f64) -> bool {
+        match self.item_at_position(x, y, origin_x, origin_y) {
+            Some(index) => {
+                self.drag = Some(LegendDrag {
+                    from: index,
+                    target: index,
+                    start: (x, y),
+                    current: (x, y),
+                    active: false,
+                });
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Track the pointer during a drag; returns the current drop index
+    ///
+    /// The drop index is the slot whose center is nearest the pointer.
+    pub fn drag_to(&mut self, x: f64, y: f64, origin_x: f64, origin_y: f64) -> Option<usize> {
+        let centers = self.slot_centers(origin_x, origin_y);
+        let drag = self.drag.as_mut()?;
+        drag.current = (x, y);
+        let (dx, dy) = drag.offset();
+        if !drag.active && dx.hypot(dy) < LEGEND_DRAG_THRESHOLD {
+            return Some(drag.target);
+        }
+        drag.active = true;
+        drag.target = centers
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let da = (a.0 - x).hypot(a.1 - y);
+                let db = (b.0 - x).hypot(b.1 - y);
+                da.total_cmp(&db)
+            })
+            .map_or(drag.from, |(i, _)| i);
+        Some(drag.target)
+    }
+
+    /// Finish a drag, reordering items if it moved
+    ///
+    /// Returns the order change for chart builders to apply to datasets.
+    pub fn end_drag(&mut self) -> Option<SeriesOrderChange> {
+        let drag = self.drag.take()?;
+        if !drag.active {
+            return None;
+        }
+        self.move_item(drag.from, drag.target)
+    }
+
+    /// Abandon a drag without reordering
+    pub fn cancel_drag(&mut self) {
+        self.drag = None;
+    }
+
+    /// Check if an item is being dragged past the threshold
+    pub fn is_dragging(&self) -> bool {
+        self.drag.as_ref().is_some_and(|d| d.active)
+    }
+
+    /// Item order as it would be after dropping now, for live previews
+    ///
+    /// Entry `i` is the index of the item to draw in slot `i`.
+    pub fn drag_preview_order(&self) -> Vec<usize> {
+        match &self.drag {
+            Some(drag) if drag.active => {
+                SeriesOrderChange::new(self.items.len(), drag.from, drag.target).order
+            }
+            _ => (0..self.items.len()).collect(),
+        }
+    }
+
+    /// Centers of each item slot
+    fn slot_centers(&self, origin_x: f64, origin_y: f64) -> Vec<(f64, f64)> {
+        let style = &self.style;
+        let item_width = style.symbol_size + style.label_spacing + self.estimate_label_width();
+        let item_height = style.symbol_size.max(style.font_size);
+        self.get_item_positions(origin_x, origin_y)
+            .into_iter()
+            .map(|(x, y, _)| (x + item_width / 2.0, y + item_height / 2.0))
+            .collect()
+    }
+
     /// Get item positions for rendering
     ///
-    /// Returns a vector of (x, y, item) for each legend item.
+    /// Returns a vector of (x, y, item) for each legend item. Right-to-left
+    /// legends mirror the column order; draw their symbols after the label,
+    /// at the right edge of the item.
     pub fn get_item_positions(&self, origin_x: f64, origin_y: f64) -> Vec<(f64, f64, &LegendItem)> {
         if self.items.is_empty() {
             return Vec::new();
@@ -546,6 +880,7 @@ impl Legend {
                     LegendOrientation::Vertical => (i % rows, i / rows),
                 };
 
+                let col = self.visual_column(col, cols);
                 let x = content_x + col as f64 * (item_width + style.item_spacing);
                 let y = content_y + row as f64 * (item_height + style.item_spacing);
 
@@ -553,6 +888,34 @@ impl Legend {
             })
             .collect()
     }
+
+    /// Layouts of the image and glyph markers, as `(item index, layout)`
+    ///
+    /// Each marker is fitted into the item's symbol slot (`symbol_size`
+    /// square, vertically centered on the row) and centered there, whatever
+    /// its own anchor; items without a marker are skipped.
+    pub fn marker_layouts(&self, origin_x: f64, origin_y: f64) -> Vec<(usize, MarkerLayout)> {
+        let style = &self.style;
+        let item_width = style.symbol_size + style.label_spacing + self.estimate_label_width();
+        let item_height = style.symbol_size.max(style.font_size);
+        let half = style.symbol_size / 2.0;
+
+        self.get_item_positions(origin_x, origin_y)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, (x, y, item))| {
+                let marker = item.marker.as_ref()?;
+                let cx = if self.is_rtl() {
+                    x + item_width - half
+                } else {
+                    x + half
+                };
+                let cy = y + item_height / 2.0;
+                let centered = marker.clone().with_anchor(MarkerAnchor::Center);
+                Some((i, centered.layout_sized(cx, cy, style.symbol_size)))
+            })
+            .collect()
+    }
 }
 
 /// Builder for creating legends from data
@@ -657,6 +1020,54 @@ impl Default for LegendBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_legend_style_deserializes_without_text_fields() {
+        let json = r#"{"symbol_size":10.0,"item_spacing":20.0,"label_spacing":6.0,"font_size":12.0,
+            "border_width":1.0,"padding":8.0,"corner_radius":4.0,"disabled_opacity":0.4}"#;
+        let style: LegendStyle = serde_json::from_str(json).unwrap();
+        assert_eq!(style.symbol_size, 10.0);
+        assert_eq!(style.text_direction, TextDirection::Ltr);
+        assert_eq!(style.max_label_width, None);
+    }
+
+    #[test]
+    fn test_legend_item_from_dataset() {
+        let mut dataset = Dataset::new("Revenue").with_description("Monthly revenue");
+        dataset.hidden = true;
+        let item = LegendItem::from_dataset(&dataset, Rgba::from_hex(0x4285F4));
+        assert_eq!(item.label, "Revenue");
+        assert!(!item.visible);
+        assert_eq!(item.description.as_deref(), Some("Monthly revenue"));
+    }
+
+    #[test]
+    fn test_legend_click_and_isolate() {
+        let mut legend = Legend::new()
+            .add_item("A", Rgba::RED)
+            .add_item("B", Rgba::GREEN)
+            .add_item("C", Rgba::BLUE);
+        assert_eq!(legend.click(0), None);
+
+        let mut legend = legend.interactive(true);
+        assert_eq!(legend.click(0), Some(LegendEvent::Toggled { index: 0, visible: false }));
+        assert_eq!(legend.double_click(1), Some(LegendEvent::Isolated { index: 1 }));
+        assert_eq!(legend.visible_indices(), vec![1]);
+        assert_eq!(legend.isolated(), Some(1));
+
+        // Two clicks ahead of the double-click cancel out
+        legend.click(1);
+        legend.click(1);
+        assert_eq!(legend.double_click(1), Some(LegendEvent::Restored));
+        assert_eq!(legend.visible_count(), 3);
+        assert_eq!(legend.isolated(), None);
+        assert_eq!(legend.double_click(9), None);
+
+        legend.double_click(2);
+        let mut datasets = vec![Dataset::new("A"), Dataset::new("B"), Dataset::new("C")];
+        legend.apply_visibility(&mut datasets);
+        assert_eq!(datasets.iter().map(|d| d.hidden).collect::<Vec<_>>(), vec![true, true, false]);
+    }
+
     #[test]
     fn test_legend_item_new() {
         let item = LegendItem::new("Series A", Rgba::from_hex(0x4285F4));
@@ -705,6 +1116,17 @@ mod tests {
         assert_eq!(legend.items[2].label, "C");
     }
 
+    #[test]
+    fn test_legend_from_categorical() {
+        let mut scale = CategoricalScale::category10();
+        let series = scale.color_for("Europe");
+        scale.color_for("Asia");
+        let legend = Legend::from_categorical(&scale);
+        assert_eq!(legend.len(), 2);
+        assert_eq!(legend.items[0].label, "Europe");
+        assert_eq!(legend.items[0].color, series);
+    }
+
     #[test]
     fn test_legend_from_pairs() {
         let pairs = vec![
@@ -804,6 +1226,65 @@ mod tests {
         assert!(height > 0.0);
     }
 
+    #[test]
+    fn test_legend_label_width_uses_graphemes() {
+        let latin = Legend::new().add_item("abcd", Rgba::RED);
+        let cjk = Legend::new().add_item("東京", Rgba::RED);
+        // Two wide glyphs (2em) vs four narrow ones (2.4em), not byte counts
+        assert!(cjk.calculate_size().0 < latin.calculate_size().0);
+    }
+
+    #[test]
+    fn test_legend_truncated_labels() {
+        let mut legend = Legend::new().add_item("A very long series name", Rgba::RED);
+        legend.style.font_size = 10.0;
+        legend.style.max_label_width = Some(30.0);
+        assert_eq!(legend.display_label(0), Some("A ve…".to_string()));
+        assert_eq!(legend.display_label(1), None);
+    }
+
+    #[test]
+    fn test_legend_rtl_mirrors_columns() {
+        let mut legend = Legend::new()
+            .orientation(LegendOrientation::Horizontal)
+            .add_item("א", Rgba::RED)
+            .add_item("ב", Rgba::GREEN);
+        let ltr = legend.get_item_positions(0.0, 0.0);
+        let (ltr_first, ltr_second) = (ltr[0].0, ltr[1].0);
+
+        legend.style.text_direction = TextDirection::Rtl;
+        let rtl = legend.get_item_positions(0.0, 0.0);
+        assert_eq!(rtl[0].0, ltr_second);
+        assert_eq!(rtl[1].0, ltr_first);
+
+        let (x, y) = (rtl[0].0 + 1.0, rtl[0].1 + 1.0);
+        assert_eq!(legend.item_at_position(x, y, 0.0, 0.0), Some(0));
+    }
+
+    #[test]
+    fn test_legend_marker_layouts() {
+        let logo = Marker::image(3, 2.0).with_size(40.0).with_anchor(MarkerAnchor::Bottom);
+        let dataset = Dataset::new("Acme").with_point_marker(logo);
+        let mut legend = Legend::new()
+            .orientation(LegendOrientation::Vertical)
+            .add_item("Plain", Rgba::RED);
+        legend.items.push(LegendItem::from_dataset(&dataset, Rgba::BLUE));
+        assert!(legend.items[1].marker.is_some());
+
+        let layouts = legend.marker_layouts(0.0, 0.0);
+        assert_eq!(layouts.len(), 1);
+        let (index, layout) = &layouts[0];
+        assert_eq!(*index, 1);
+        // Fitted into the 12 px symbol slot and centered in it
+        assert_eq!((layout.width, layout.height), (12.0, 6.0));
+        let (x, y, _) = legend.get_item_positions(0.0, 0.0)[1];
+        assert_eq!(layout.center(), (x + 6.0, y + 6.0));
+
+        legend.style.text_direction = TextDirection::Rtl;
+        let rtl = legend.marker_layouts(0.0, 0.0);
+        assert!(rtl[0].1.center().0 > layout.center().0);
+    }
+
     #[test]
     fn test_legend_builder() {
         let legend = LegendBuilder::new()
@@ -839,6 +1320,79 @@ mod tests {
         assert!(y >= 0.0);
     }
 
+    fn abc_vertical() -> Legend {
+        Legend::new()
+            .orientation(LegendOrientation::Vertical)
+            .add_item("A", Rgba::RED)
+            .add_item("B", Rgba::GREEN)
+            .add_item("C", Rgba::BLUE)
+    }
+
+    fn labels(legend: &Legend) -> Vec<&str> {
+        legend.items.iter().map(|i| i.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_move_item() {
+        let mut legend = abc_vertical();
+        let change = legend.move_item(0, 2).unwrap();
+        assert_eq!(labels(&legend), vec!["B", "C", "A"]);
+        assert_eq!(change.order, vec![1, 2, 0]);
+        assert!(legend.move_item(1, 1).is_none());
+        assert!(legend.move_item(0, 5).is_none());
+    }
+
+    #[test]
+    fn test_series_order_change_apply() {
+        let change = SeriesOrderChange::new(3, 2, 0);
+        let mut datasets = vec!["a", "b", "c"];
+        assert!(change.apply(&mut datasets));
+        assert_eq!(datasets, vec!["c", "a", "b"]);
+        assert!(!change.apply(&mut vec![1, 2]));
+    }
+
+    #[test]
+    fn test_drag_reorder() {
+        let mut legend = abc_vertical();
+        let positions: Vec<(f64, f64)> =
+            legend.get_item_positions(0.0, 0.0).iter().map(|&(x, y, _)| (x + 2.0, y + 2.0)).collect();
+
+        assert!(legend.begin_drag(positions[0].0, positions[0].1, 0.0, 0.0));
+        assert!(!legend.is_dragging());
+        let target = legend.drag_to(positions[2].0, positions[2].1 + 1.0, 0.0, 0.0);
+        assert_eq!(target, Some(2));
+        assert!(legend.is_dragging());
+        assert_eq!(legend.drag_preview_order(), vec![1, 2, 0]);
+
+        let change = legend.end_drag().unwrap();
+        assert_eq!((change.from, change.to), (0, 2));
+        assert_eq!(labels(&legend), vec!["B", "C", "A"]);
+        assert!(legend.drag.is_none());
+    }
+
+    #[test]
+    fn test_drag_below_threshold_is_click() {
+        let mut legend = abc_vertical();
+        let (x, y, _) = legend.get_item_positions(0.0, 0.0)[1];
+        assert!(legend.begin_drag(x + 1.0, y + 1.0, 0.0, 0.0));
+        legend.drag_to(x + 2.0, y + 2.0, 0.0, 0.0);
+        assert!(legend.end_drag().is_none());
+        assert_eq!(labels(&legend), vec!["A", "B", "C"]);
+
+        assert!(!legend.begin_drag(-50.0, -50.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_cancel_drag() {
+        let mut legend = abc_vertical();
+        let (x, y, _) = legend.get_item_positions(0.0, 0.0)[0];
+        legend.begin_drag(x + 1.0, y + 1.0, 0.0, 0.0);
+        legend.drag_to(x + 1.0, y + 200.0, 0.0, 0.0);
+        legend.cancel_drag();
+        assert!(legend.end_drag().is_none());
+        assert_eq!(labels(&legend), vec!["A", "B", "C"]);
+    }
+
     #[test]
     fn test_legend_style_default() {
         let style = LegendStyle::default();
@@ -6,10 +6,17 @@
 //! # Components
 //!
 //! - [`Legend`]: Interactive legend for displaying series colors and labels
+//! - [`ColorLegend`]: Continuous color ramp with optional histogram strip
+//! - [`ColorLegendLink`]: Keeps a [`ColorLegend`] in step with the chart's color scale
 //! - [`TooltipWidget`]: Configurable tooltip for data point information
 //! - [`Crosshair`]: Cursor tracking with guide lines
 //! - [`Annotation`]: Labels, callouts, and markers for chart annotations
+//! - [`AnnotationEditor`]: Pointer-driven create/move/resize of annotations
+//! - [`AnnotationRules`]: Annotations generated from data (max, min, last, thresholds)
 //! - [`ReferenceLine`]: Horizontal/vertical lines for thresholds and targets
+//! - [`Sparkline`]: Word-sized line/area/bar/win-loss charts for cells and tooltips
+//! - [`ScaleBar`]: Map scale bar with a nice ground distance, segments and labels
+//! - [`NorthArrow`]: Map north arrow turned to the projection's local north
 //!
 //! # Example
 //!
@@ -106,15 +113,27 @@
 //! ```
 
 mod legend;
+mod color_legend;
 mod tooltip;
 mod crosshair;
 mod annotation;
+mod annotation_editor;
+mod annotation_rules;
 mod reference_line;
+mod sparkline;
+mod scale_bar;
+mod north_arrow;
 
 // Legend exports
 pub use legend::{
     Legend, LegendItem, LegendSymbol, LegendOrientation, LegendPosition,
-    LegendStyle, LegendBuilder,
+    LegendStyle, LegendBuilder, LegendDrag, LegendEvent, SeriesOrderChange, LEGEND_DRAG_THRESHOLD,
+};
+
+// Continuous color legend exports
+pub use color_legend::{
+    ColorLegend, ColorLegendLayout, GradientStop, HistogramStrip, HistogramBar,
+    ColorLegendLink, DomainColorScale, LegendColorSource,
 };
 
 // Tooltip exports
@@ -127,7 +146,7 @@ pub use tooltip::{
 pub use crosshair::{
     Crosshair, CrosshairMode, CrosshairLineStyle, CrosshairStyle,
     CrosshairLine, CrosshairLabelConfig, LabelPosition, SnapPoint,
-    CrosshairBuilder,
+    CrosshairBuilder, CrosshairRuler, RulerBadge, RulerConfig, RulerSeries,
 };
 
 // Annotation exports
@@ -136,6 +155,14 @@ pub use annotation::{
     TextAlign, VerticalAlign, ConnectorStyle, ArrowStyle,
 };
 
+// Annotation editor exports
+pub use annotation_editor::{
+    AnnotationEditor, AnnotationEdit, AnnotationGeometry, AnnotationSnap, EditHandle,
+};
+
+// Data-driven annotation rule exports
+pub use annotation_rules::{AnnotationRule, AnnotationRuleKind, AnnotationRules, RuleMark};
+
 // Reference line exports
 pub use reference_line::{
     ReferenceLine, ReferenceLineOrientation, ReferenceLineStyle,
@@ -143,6 +170,15 @@ pub use reference_line::{
     LineDash, LabelAnchor,
 };
 
+// Sparkline exports
+pub use sparkline::{
+    SparkBar, SparkMarker, SparkMarkerKind, Sparkline, SparklineLayout, SparklineMode,
+};
+
+// Map scale bar and north arrow exports
+pub use scale_bar::{ScaleBar, ScaleBarLayout, ScaleBarSegment, ScaleBarTick, ScaleBarUnits};
+pub use north_arrow::{NorthArrow, NorthArrowLayout};
+
 #[cfg(test)]
 mod tests {
     use super::*;
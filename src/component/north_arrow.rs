@@ -0,0 +1,167 @@
+//! North arrow component
+//!
+//! Finds which way north points at a spot on the map by projecting a step
+//! along the local meridian, so rotated, conic and azimuthal projections
+//! (where north varies across the map) get a correctly turned arrow.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::component::NorthArrow;
+//! use makepad_d3::geo::{ConicEqualAreaProjection, MercatorProjection, Projection, ProjectionBuilder};
+//!
+//! // North is straight up everywhere on Mercator
+//! let mercator = MercatorProjection::new().scale(100.0).translate(400.0, 300.0);
+//! let arrow = NorthArrow::new().compute_layout(&mercator, 700.0, 100.0).unwrap();
+//! assert!(arrow.angle.abs() < 1e-9);
+//!
+//! // Meridians converge on a conic map, so north leans toward the central meridian
+//! let conic = ConicEqualAreaProjection::new().parallels(29.5, 45.5).rotate(96.0, 0.0, 0.0);
+//! let (x, y) = conic.project(-70.0, 42.0);
+//! let arrow = NorthArrow::new().compute_layout(&conic, x, y).unwrap();
+//! assert!(arrow.angle < 0.0);
+//! ```
+
+use crate::geo::Projection;
+use crate::shape::{Path, Point};
+
+/// Meridian step in degrees used to find the local north direction
+const NORTH_STEP: f64 = 0.01;
+
+/// Computed geometry for rendering a [`NorthArrow`]
+#[derive(Clone, Debug)]
+pub struct NorthArrowLayout {
+    /// Arrow center x
+    pub x: f64,
+    /// Arrow center y
+    pub y: f64,
+    /// Direction of north in radians, clockwise from screen up
+    pub angle: f64,
+    /// Arrow tip (points north)
+    pub tip: Point,
+    /// Closed arrowhead outline: tip, right barb, notch, left barb
+    pub path: Path,
+    /// Anchor for the "N" label, just beyond the tip
+    pub label: Point,
+}
+
+/// North arrow geometry generator
+#[derive(Clone, Debug)]
+pub struct NorthArrow {
+    /// Arrow length from tail to tip in pixels
+    pub size: f64,
+    /// Arrow width at the barbs as a fraction of the length
+    pub width_ratio: f64,
+    /// Gap between the tip and the label anchor
+    pub label_gap: f64,
+}
+
+impl Default for NorthArrow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NorthArrow {
+    /// Create a 24 px arrow
+    pub fn new() -> Self {
+        Self {
+            size: 24.0,
+            width_ratio: 0.6,
+            label_gap: 4.0,
+        }
+    }
+
+    /// Set the arrow length
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size.max(0.0);
+        self
+    }
+
+    /// Set the barb width as a fraction of the length
+    pub fn with_width_ratio(mut self, ratio: f64) -> Self {
+        self.width_ratio = ratio.max(0.0);
+        self
+    }
+
+    /// Direction of north at screen (x, y) in radians, clockwise from up
+    ///
+    /// Returns None where the projection shows no location (e.g. off the
+    /// globe) or north is undefined (at a pole).
+    pub fn north_angle(projection: &dyn Projection, x: f64, y: f64) -> Option<f64> {
+        let (lon, lat) = projection.invert(x, y);
+        if !lon.is_finite() || !lat.is_finite() || lat.abs() >= 90.0 - NORTH_STEP {
+            return None;
+        }
+        // Central difference along the meridian
+        let (x0, y0) = projection.project(lon, lat - NORTH_STEP);
+        let (x1, y1) = projection.project(lon, lat + NORTH_STEP);
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        if !dx.is_finite() || !dy.is_finite() || (dx == 0.0 && dy == 0.0) {
+            return None;
+        }
+        // Screen y grows downward
+        Some(dx.atan2(-dy))
+    }
+
+    /// Compute the arrow centered at screen (x, y)
+    pub fn compute_layout(&self, projection: &dyn Projection, x: f64, y: f64) -> Option<NorthArrowLayout> {
+        let angle = Self::north_angle(projection, x, y)?;
+        let (sin, cos) = angle.sin_cos();
+        // Local (right, up) offsets rotated onto the screen
+        let at = |right: f64, up: f64| Point::new(x + right * cos + up * sin, y + right * sin - up * cos);
+
+        let half = self.size / 2.0;
+        let barb = self.size * self.width_ratio / 2.0;
+        let tip = at(0.0, half);
+        let mut path = Path::new();
+        path.move_to(tip.x, tip.y);
+        for p in [at(barb, -half), at(0.0, -half / 2.0), at(-barb, -half)] {
+            path.line_to(p.x, p.y);
+        }
+        path.close();
+
+        Some(NorthArrowLayout {
+            x,
+            y,
+            angle,
+            tip,
+            path,
+            label: at(0.0, half + self.label_gap),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::{ConicEqualAreaProjection, OrthographicProjection, ProjectionBuilder};
+
+    #[test]
+    fn test_north_angle_on_conic() {
+        let conic = ConicEqualAreaProjection::new()
+            .parallels(29.5, 45.5)
+            .rotate(96.0, 0.0, 0.0)
+            .scale(1000.0);
+        let (cx, cy) = conic.project(-96.0, 40.0);
+        let (ex, ey) = conic.project(-70.0, 42.0);
+        let (wx, wy) = conic.project(-120.0, 42.0);
+
+        assert!(NorthArrow::north_angle(&conic, cx, cy).unwrap().abs() < 1e-6);
+        let east = NorthArrow::north_angle(&conic, ex, ey).unwrap();
+        let west = NorthArrow::north_angle(&conic, wx, wy).unwrap();
+        assert!(east < -0.1 && west > 0.1);
+    }
+
+    #[test]
+    fn test_arrow_geometry() {
+        let globe = OrthographicProjection::new().scale(100.0).translate(200.0, 200.0);
+        let arrow = NorthArrow::new().with_size(20.0).compute_layout(&globe, 200.0, 200.0).unwrap();
+        assert!(arrow.angle.abs() < 1e-9);
+        assert!((arrow.tip.x - 200.0).abs() < 1e-9 && (arrow.tip.y - 190.0).abs() < 1e-9);
+        assert!((arrow.label.y - 186.0).abs() < 1e-9);
+
+        // Off the globe there is nothing to point at
+        assert!(NorthArrow::new().compute_layout(&globe, 350.0, 200.0).is_none());
+    }
+}
@@ -0,0 +1,288 @@
+//! Map scale bar component
+//!
+//! Measures the ground distance spanned by the bar's maximum width at its
+//! position on the map, picks the largest nice distance (1, 2 or 5 × 10ⁿ)
+//! that fits, and lays out alternating segments with tick labels. Because
+//! it inverts the projection at the bar itself, the bar stays correct for
+//! any projection, center and zoom.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::component::{ScaleBar, ScaleBarUnits};
+//! use makepad_d3::geo::{MercatorProjection, ProjectionBuilder};
+//!
+//! let projection = MercatorProjection::new().scale(100.0).translate(400.0, 300.0);
+//! let bar = ScaleBar::new().with_units(ScaleBarUnits::Metric).with_max_width(100.0);
+//!
+//! // 100 px at the equator is about 6371 km, so the bar shows 5000 km
+//! let layout = bar.compute_layout(&projection, 400.0, 300.0).unwrap();
+//! assert_eq!(layout.distance, 5000.0);
+//! assert_eq!(layout.unit, "km");
+//! assert_eq!(layout.ticks.last().unwrap().label, "5000 km");
+//! assert!(layout.width < 100.0);
+//! ```
+
+use crate::geo::Projection;
+use crate::scale::format_number;
+
+/// Mean Earth radius in meters (IUGG), used for ground distances
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Unit system for scale bar labels
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScaleBarUnits {
+    /// Kilometers, or meters for bars under 1 km
+    #[default]
+    Metric,
+    /// Miles, or feet for bars under 1 mile
+    Imperial,
+}
+
+impl ScaleBarUnits {
+    /// Unit label and size in meters for a bar spanning up to `meters`
+    fn unit_for(&self, meters: f64) -> (&'static str, f64) {
+        match self {
+            ScaleBarUnits::Metric if meters >= 1000.0 => ("km", 1000.0),
+            ScaleBarUnits::Metric => ("m", 1.0),
+            ScaleBarUnits::Imperial if meters >= 1609.344 => ("mi", 1609.344),
+            ScaleBarUnits::Imperial => ("ft", 0.3048),
+        }
+    }
+}
+
+/// One alternating block of the bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleBarSegment {
+    /// Left edge
+    pub x: f64,
+    /// Segment width
+    pub width: f64,
+    /// Whether the segment is drawn filled (segments alternate)
+    pub filled: bool,
+}
+
+/// A labeled tick along the bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleBarTick {
+    /// Screen x
+    pub x: f64,
+    /// Distance from the bar start in the bar's unit
+    pub distance: f64,
+    /// Label text; the last tick carries the unit
+    pub label: String,
+}
+
+/// Computed geometry for rendering a [`ScaleBar`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleBarLayout {
+    /// Left edge
+    pub x: f64,
+    /// Top edge
+    pub y: f64,
+    /// Bar width in pixels
+    pub width: f64,
+    /// Bar height in pixels
+    pub height: f64,
+    /// Distance the bar represents, in `unit`
+    pub distance: f64,
+    /// Unit label ("km", "m", "mi" or "ft")
+    pub unit: &'static str,
+    /// Distance the bar represents in meters
+    pub meters: f64,
+    /// Alternating segments from left to right
+    pub segments: Vec<ScaleBarSegment>,
+    /// Ticks at segment boundaries, including both ends
+    pub ticks: Vec<ScaleBarTick>,
+}
+
+/// Scale bar geometry generator
+#[derive(Clone, Debug)]
+pub struct ScaleBar {
+    /// Unit system for labels
+    pub units: ScaleBarUnits,
+    /// Widest the bar may be, in pixels
+    pub max_width: f64,
+    /// Bar height in pixels
+    pub height: f64,
+    /// Fixed segment count; None picks one from the distance's leading digit
+    pub segments: Option<usize>,
+}
+
+impl Default for ScaleBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScaleBar {
+    /// Create a metric scale bar up to 120 px wide
+    pub fn new() -> Self {
+        Self {
+            units: ScaleBarUnits::Metric,
+            max_width: 120.0,
+            height: 4.0,
+            segments: None,
+        }
+    }
+
+    /// Set the unit system
+    pub fn with_units(mut self, units: ScaleBarUnits) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Set the widest the bar may be
+    pub fn with_max_width(mut self, width: f64) -> Self {
+        self.max_width = width.max(1.0);
+        self
+    }
+
+    /// Set the bar height
+    pub fn with_height(mut self, height: f64) -> Self {
+        self.height = height.max(0.0);
+        self
+    }
+
+    /// Use a fixed number of segments
+    pub fn with_segments(mut self, segments: usize) -> Self {
+        self.segments = Some(segments.max(1));
+        self
+    }
+
+    /// Compute the bar with its left end at screen (x, y)
+    ///
+    /// Returns None if the projection cannot invert the bar's span (e.g.
+    /// it lies off the globe of an orthographic projection).
+    pub fn compute_layout(&self, projection: &dyn Projection, x: f64, y: f64) -> Option<ScaleBarLayout> {
+        let start = projection.invert(x, y);
+        let end = projection.invert(x + self.max_width, y);
+        if ![start.0, start.1, end.0, end.1].iter().all(|v| v.is_finite()) {
+            return None;
+        }
+        let max_meters = ground_distance(start, end);
+        if !max_meters.is_finite() || max_meters <= 0.0 {
+            return None;
+        }
+
+        let (unit, unit_meters) = self.units.unit_for(max_meters);
+        let distance = nice_distance(max_meters / unit_meters);
+        let meters = distance * unit_meters;
+        let width = self.max_width * meters / max_meters;
+
+        let count = self.segments.unwrap_or_else(|| auto_segments(distance));
+        let segment_width = width / count as f64;
+        let segments = (0..count)
+            .map(|i| ScaleBarSegment {
+                x: x + i as f64 * segment_width,
+                width: segment_width,
+                filled: i % 2 == 0,
+            })
+            .collect();
+        let ticks = (0..=count)
+            .map(|i| {
+                let value = distance * i as f64 / count as f64;
+                let label = if i == count {
+                    format!("{} {}", format_number(value), unit)
+                } else {
+                    format_number(value)
+                };
+                ScaleBarTick { x: x + i as f64 * segment_width, distance: value, label }
+            })
+            .collect();
+
+        Some(ScaleBarLayout {
+            x,
+            y,
+            width,
+            height: self.height,
+            distance,
+            unit,
+            meters,
+            segments,
+            ticks,
+        })
+    }
+}
+
+/// Largest 1, 2 or 5 × 10ⁿ not exceeding `max`
+fn nice_distance(max: f64) -> f64 {
+    let magnitude = 10f64.powf(max.log10().floor());
+    [5.0, 2.0, 1.0]
+        .into_iter()
+        .map(|m| m * magnitude)
+        .find(|&d| d <= max * (1.0 + 1e-9))
+        .unwrap_or(magnitude)
+}
+
+/// Segment count giving round tick values for a nice distance
+fn auto_segments(distance: f64) -> usize {
+    let leading = distance / 10f64.powf(distance.log10().floor());
+    if (leading - 2.0).abs() < 1e-9 {
+        4
+    } else {
+        5
+    }
+}
+
+/// Great-circle distance in meters between two (lon, lat) locations
+fn ground_distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lon1, lat1) = (a.0.to_radians(), a.1.to_radians());
+    let (lon2, lat2) = (b.0.to_radians(), b.1.to_radians());
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    2.0 * h.sqrt().min(1.0).asin() * EARTH_RADIUS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::{MercatorProjection, OrthographicProjection, ProjectionBuilder};
+
+    #[test]
+    fn test_nice_distance_and_segments() {
+        assert_eq!(nice_distance(6371.0), 5000.0);
+        assert_eq!(nice_distance(3185.0), 2000.0);
+        assert_eq!(nice_distance(1.7), 1.0);
+        assert_eq!(nice_distance(200.0), 200.0);
+        assert_eq!(auto_segments(2000.0), 4);
+        assert_eq!(auto_segments(500.0), 5);
+    }
+
+    #[test]
+    fn test_scale_bar_follows_latitude() {
+        let projection = MercatorProjection::new().scale(100.0).translate(400.0, 300.0);
+        let bar = ScaleBar::new().with_max_width(100.0);
+
+        let equator = bar.compute_layout(&projection, 400.0, 300.0).unwrap();
+        assert!((equator.width - 5000.0 / 6371.0088 * 100.0).abs() < 0.01);
+        assert_eq!(equator.segments.len(), 5);
+        assert!(equator.segments[0].filled && !equator.segments[1].filled);
+        assert_eq!(equator.ticks[1].label, "1000");
+
+        // Mercator doubles distances at 60°, so the same pixels cover half the ground
+        let (_, y60) = projection.project(0.0, 60.0);
+        let north = bar.compute_layout(&projection, 400.0, y60).unwrap();
+        assert_eq!(north.distance, 2000.0);
+        assert_eq!(north.ticks.len(), 5);
+        assert_eq!(north.ticks[2].label, "1000");
+    }
+
+    #[test]
+    fn test_scale_bar_units_and_off_globe() {
+        // Zoomed in far enough for a sub-kilometer bar
+        let projection = MercatorProjection::new().scale(1_000_000.0);
+        let metric = ScaleBar::new().compute_layout(&projection, 0.0, 0.0).unwrap();
+        assert_eq!((metric.unit, metric.distance), ("m", 500.0));
+        let imperial = ScaleBar::new()
+            .with_units(ScaleBarUnits::Imperial)
+            .with_segments(2)
+            .compute_layout(&projection, 0.0, 0.0)
+            .unwrap();
+        assert_eq!((imperial.unit, imperial.distance), ("ft", 2000.0));
+        assert_eq!(imperial.ticks.last().unwrap().label, "2000 ft");
+
+        let globe = OrthographicProjection::new().scale(100.0);
+        assert!(ScaleBar::new().compute_layout(&globe, 150.0, 0.0).is_none());
+    }
+}
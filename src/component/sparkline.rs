@@ -0,0 +1,396 @@
+//! Sparkline component
+//!
+//! Word-sized charts for table cells and tooltips: a raw series is fitted
+//! into a small rectangle as a line, filled area, bars, or win/loss ticks,
+//! with no axes. An optional normal-range band and min/max/last point
+//! markers give the series context at a glance.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::component::{Sparkline, SparklineMode, SparkMarkerKind};
+//!
+//! let values = [3.0, 5.0, 2.0, 8.0, 6.0, 7.0];
+//! let spark = Sparkline::new()
+//!     .with_mode(SparklineMode::Area)
+//!     .with_band(4.0, 6.0)
+//!     .with_markers(true, true);
+//!
+//! let layout = spark.compute_layout(&values, (0.0, 0.0, 60.0, 16.0));
+//! assert!(layout.area.is_some());
+//! assert!(layout.band.is_some());
+//!
+//! let max = layout.markers.iter().find(|m| m.kind == SparkMarkerKind::Max).unwrap();
+//! assert_eq!(max.index, 3);
+//! ```
+
+use crate::shape::{Path, Point};
+
+/// How a sparkline draws its series
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SparklineMode {
+    /// Polyline through the values
+    #[default]
+    Line,
+    /// Polyline filled down to the baseline
+    Area,
+    /// One bar per value from the baseline
+    Bars,
+    /// Fixed-height up/down ticks for positive/negative values
+    WinLoss,
+}
+
+/// Kind of highlighted point
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SparkMarkerKind {
+    /// Lowest value
+    Min,
+    /// Highest value
+    Max,
+    /// Last finite value
+    Last,
+}
+
+/// A highlighted point
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparkMarker {
+    /// What the marker highlights
+    pub kind: SparkMarkerKind,
+    /// Index in the series
+    pub index: usize,
+    /// Series value
+    pub value: f64,
+    /// Screen x
+    pub x: f64,
+    /// Screen y
+    pub y: f64,
+}
+
+/// A laid-out sparkline bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparkBar {
+    /// Index in the series
+    pub index: usize,
+    /// Series value
+    pub value: f64,
+    /// Left edge
+    pub x: f64,
+    /// Top edge
+    pub y: f64,
+    /// Bar width
+    pub width: f64,
+    /// Bar height
+    pub height: f64,
+    /// Whether the value is above the baseline (a win in win/loss mode)
+    pub positive: bool,
+}
+
+/// Computed geometry for rendering a [`Sparkline`]
+#[derive(Clone, Debug, Default)]
+pub struct SparklineLayout {
+    /// Polyline (line and area modes); gaps at non-finite values
+    pub line: Option<Path>,
+    /// Closed fill down to the baseline (area mode)
+    pub area: Option<Path>,
+    /// Bars (bar and win/loss modes)
+    pub bars: Vec<SparkBar>,
+    /// Normal-range band rectangle (x, y, width, height)
+    pub band: Option<(f64, f64, f64, f64)>,
+    /// Highlighted points
+    pub markers: Vec<SparkMarker>,
+    /// Screen y of the baseline
+    pub baseline: f64,
+    /// Value domain mapped to the rectangle height
+    pub domain: (f64, f64),
+}
+
+/// Sparkline geometry generator
+#[derive(Clone, Debug)]
+pub struct Sparkline {
+    /// Drawing mode
+    pub mode: SparklineMode,
+    /// Inset from the rectangle edges, so markers and strokes aren't clipped
+    pub padding: f64,
+    /// Gap between bars as a fraction of the bar slot (0-1)
+    pub bar_gap: f64,
+    /// Marker dot radius for rendering
+    pub marker_radius: f64,
+    domain: Option<(f64, f64)>,
+    band: Option<(f64, f64)>,
+    min_max_markers: bool,
+    last_marker: bool,
+}
+
+impl Default for Sparkline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sparkline {
+    /// Create a line sparkline with no band or markers
+    pub fn new() -> Self {
+        Self {
+            mode: SparklineMode::Line,
+            padding: 2.0,
+            bar_gap: 0.2,
+            marker_radius: 1.5,
+            domain: None,
+            band: None,
+            min_max_markers: false,
+            last_marker: false,
+        }
+    }
+
+    /// Set the drawing mode
+    pub fn with_mode(mut self, mode: SparklineMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the inset from the rectangle edges
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = padding.max(0.0);
+        self
+    }
+
+    /// Set the gap between bars as a fraction of the bar slot
+    pub fn with_bar_gap(mut self, gap: f64) -> Self {
+        self.bar_gap = gap.clamp(0.0, 0.9);
+        self
+    }
+
+    /// Fix the value domain, e.g. to compare sparklines in one column
+    pub fn with_domain(mut self, min: f64, max: f64) -> Self {
+        self.domain = Some((min.min(max), min.max(max)));
+        self
+    }
+
+    /// Shade a normal range of values behind the series
+    pub fn with_band(mut self, low: f64, high: f64) -> Self {
+        self.band = Some((low.min(high), low.max(high)));
+        self
+    }
+
+    /// Highlight the min/max points and/or the last point
+    pub fn with_markers(mut self, min_max: bool, last: bool) -> Self {
+        self.min_max_markers = min_max;
+        self.last_marker = last;
+        self
+    }
+
+    /// Value domain for a series: the configured one, or the data extent
+    /// widened to include the band (and zero for bars)
+    pub fn domain_for(&self, values: &[f64]) -> (f64, f64) {
+        if let Some(domain) = self.domain {
+            return domain;
+        }
+        let mut lo = f64::INFINITY;
+        let mut hi = f64::NEG_INFINITY;
+        for &v in values.iter().filter(|v| v.is_finite()) {
+            lo = lo.min(v);
+            hi = hi.max(v);
+        }
+        if let Some((low, high)) = self.band {
+            lo = lo.min(low);
+            hi = hi.max(high);
+        }
+        if self.mode == SparklineMode::Bars {
+            lo = lo.min(0.0);
+            hi = hi.max(0.0);
+        }
+        if !lo.is_finite() {
+            return (0.0, 1.0);
+        }
+        if lo == hi {
+            return (lo - 1.0, hi + 1.0);
+        }
+        (lo, hi)
+    }
+
+    /// Compute geometry for a series inside a rectangle (x, y, width, height)
+    pub fn compute_layout(&self, values: &[f64], rect: (f64, f64, f64, f64)) -> SparklineLayout {
+        let (rx, ry, rw, rh) = rect;
+        let pad = self.padding.min(rw / 2.0).min(rh / 2.0);
+        let (left, top, width, height) = (rx + pad, ry + pad, rw - 2.0 * pad, rh - 2.0 * pad);
+        let domain = self.domain_for(values);
+        let y_of = |v: f64| {
+            let t = (v - domain.0) / (domain.1 - domain.0);
+            top + height * (1.0 - t.clamp(0.0, 1.0))
+        };
+        let n = values.len();
+
+        // Points sit on slot centers for bars, and span the full width for lines
+        let bar_like = matches!(self.mode, SparklineMode::Bars | SparklineMode::WinLoss);
+        let x_of = |i: usize| {
+            if bar_like {
+                left + width * (i as f64 + 0.5) / n as f64
+            } else if n > 1 {
+                left + width * i as f64 / (n - 1) as f64
+            } else {
+                left + width / 2.0
+            }
+        };
+
+        let baseline = match self.mode {
+            SparklineMode::WinLoss => top + height / 2.0,
+            _ => y_of(0.0_f64.clamp(domain.0, domain.1)),
+        };
+
+        let mut layout = SparklineLayout {
+            baseline,
+            domain,
+            band: self.band.map(|(low, high)| (left, y_of(high), width, y_of(low) - y_of(high))),
+            ..SparklineLayout::default()
+        };
+        if n == 0 {
+            return layout;
+        }
+
+        match self.mode {
+            SparklineMode::Line | SparklineMode::Area => {
+                let mut line = Path::new();
+                let mut area = Path::new();
+                let mut run: Vec<Point> = Vec::new();
+                let mut flush = |run: &mut Vec<Point>| {
+                    if let (Some(first), Some(last)) = (run.first().copied(), run.last().copied()) {
+                        line.move_to(first.x, first.y);
+                        area.move_to(first.x, baseline);
+                        for p in run.iter() {
+                            if p != &first {
+                                line.line_to(p.x, p.y);
+                            }
+                            area.line_to(p.x, p.y);
+                        }
+                        area.line_to(last.x, baseline).close();
+                    }
+                    run.clear();
+                };
+                for (i, &v) in values.iter().enumerate() {
+                    if v.is_finite() {
+                        run.push(Point::new(x_of(i), y_of(v)));
+                    } else {
+                        flush(&mut run);
+                    }
+                }
+                flush(&mut run);
+                if self.mode == SparklineMode::Area {
+                    layout.area = Some(area);
+                }
+                layout.line = Some(line);
+            }
+            SparklineMode::Bars | SparklineMode::WinLoss => {
+                let slot = width / n as f64;
+                let bar_width = slot * (1.0 - self.bar_gap);
+                for (i, &v) in values.iter().enumerate() {
+                    if !v.is_finite() || (self.mode == SparklineMode::WinLoss && v == 0.0) {
+                        continue;
+                    }
+                    let positive = v >= 0.0;
+                    let end = match self.mode {
+                        SparklineMode::WinLoss if positive => top,
+                        SparklineMode::WinLoss => top + height,
+                        _ => y_of(v),
+                    };
+                    layout.bars.push(SparkBar {
+                        index: i,
+                        value: v,
+                        x: x_of(i) - bar_width / 2.0,
+                        y: end.min(baseline),
+                        width: bar_width,
+                        height: (end - baseline).abs(),
+                        positive,
+                    });
+                }
+            }
+        }
+
+        let marker = |kind, index: usize| SparkMarker {
+            kind,
+            index,
+            value: values[index],
+            x: x_of(index),
+            y: if self.mode == SparklineMode::WinLoss { baseline } else { y_of(values[index]) },
+        };
+        let finite = || values.iter().enumerate().filter(|(_, v)| v.is_finite());
+        if self.min_max_markers {
+            if let Some((i, _)) = finite().min_by(|a, b| a.1.total_cmp(b.1)) {
+                layout.markers.push(marker(SparkMarkerKind::Min, i));
+            }
+            // Ties go to the first maximum
+            if let Some((i, _)) = finite().rev().max_by(|a, b| a.1.total_cmp(b.1)) {
+                layout.markers.push(marker(SparkMarkerKind::Max, i));
+            }
+        }
+        if self.last_marker {
+            if let Some((i, _)) = finite().next_back() {
+                layout.markers.push(marker(SparkMarkerKind::Last, i));
+            }
+        }
+        layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::PathSegment;
+
+    const RECT: (f64, f64, f64, f64) = (10.0, 20.0, 104.0, 24.0);
+
+    #[test]
+    fn test_line_fits_padded_rect_with_gaps() {
+        let spark = Sparkline::new();
+        let layout = spark.compute_layout(&[0.0, 10.0, f64::NAN, 5.0, 0.0], RECT);
+        let line = layout.line.unwrap();
+        let moves = line.iter().filter(|s| matches!(s, PathSegment::MoveTo(_))).count();
+        assert_eq!(moves, 2);
+
+        let points: Vec<Point> = line.iter().filter_map(PathSegment::end_point).collect();
+        assert_eq!(points[0], Point::new(12.0, 42.0));
+        assert_eq!(points[1], Point::new(37.0, 22.0));
+        assert_eq!(points.last().unwrap().x, 112.0);
+        assert!(layout.area.is_none() && layout.bars.is_empty());
+    }
+
+    #[test]
+    fn test_band_and_markers() {
+        let spark = Sparkline::new().with_band(-5.0, 2.0).with_markers(true, true);
+        let layout = spark.compute_layout(&[1.0, 4.0, 4.0, -1.0, f64::NAN], RECT);
+
+        assert_eq!(layout.domain, (-5.0, 4.0));
+        let (_, y, _, h) = layout.band.unwrap();
+        assert!((y - (22.0 + 20.0 * 2.0 / 9.0)).abs() < 1e-9);
+        assert!((y + h - 42.0).abs() < 1e-9);
+
+        let kinds: Vec<(SparkMarkerKind, usize)> = layout.markers.iter().map(|m| (m.kind, m.index)).collect();
+        assert_eq!(
+            kinds,
+            vec![(SparkMarkerKind::Min, 3), (SparkMarkerKind::Max, 1), (SparkMarkerKind::Last, 3)]
+        );
+    }
+
+    #[test]
+    fn test_bars_from_zero_baseline() {
+        let spark = Sparkline::new().with_mode(SparklineMode::Bars).with_bar_gap(0.0);
+        let layout = spark.compute_layout(&[2.0, -2.0], RECT);
+
+        assert_eq!(layout.domain, (-2.0, 2.0));
+        assert_eq!(layout.baseline, 32.0);
+        let (up, down) = (&layout.bars[0], &layout.bars[1]);
+        assert_eq!((up.x, up.y, up.width, up.height, up.positive), (12.0, 22.0, 50.0, 10.0, true));
+        assert_eq!((down.y, down.height, down.positive), (32.0, 10.0, false));
+    }
+
+    #[test]
+    fn test_win_loss_ticks_ignore_magnitude() {
+        let spark = Sparkline::new().with_mode(SparklineMode::WinLoss);
+        let layout = spark.compute_layout(&[3.0, 0.0, -100.0, 0.5], RECT);
+
+        assert_eq!(layout.bars.len(), 3);
+        assert!(layout.bars.iter().all(|b| b.height == 10.0));
+        assert_eq!(layout.bars.iter().filter(|b| b.positive).count(), 2);
+        assert_eq!(layout.bars[1].index, 2);
+        assert_eq!(layout.bars[1].y, layout.baseline);
+    }
+}
@@ -21,7 +21,9 @@
 //! tooltip.show_at(100.0, 200.0, TooltipContent::new("January"));
 //! ```
 
+use crate::axis::UnitFormat;
 use crate::color::Rgba;
+use crate::data::Dataset;
 use crate::interaction::{TooltipContent, TooltipPosition, TooltipState};
 use serde::{Deserialize, Serialize};
 
@@ -569,6 +571,32 @@ impl DataTooltipBuilder {
         self.add(label, format!("{}{:.2}", symbol, value))
     }
 
+    /// Add a value formatted with a unit (bytes, Hz, °C, ...)
+    pub fn add_unit(self, label: impl Into<String>, value: f64, format: &UnitFormat) -> Self {
+        self.add(label, format.format(value))
+    }
+
+    /// Add a value of a dataset, formatted with the dataset's unit and
+    /// precision; the dataset's source becomes the footer unless one is set
+    pub fn add_series(mut self, dataset: &Dataset, value: f64) -> Self {
+        if self.footer.is_none() {
+            if let Some(source) = &dataset.meta.source {
+                self.footer = Some(format!("Source: {}", source));
+            }
+        }
+        let formatted = dataset.format_value(value);
+        self.add(dataset.label.clone(), formatted)
+    }
+
+    /// Add entry `index` of a dataset, shown as "No data" when it is missing
+    /// rather than as a number
+    pub fn add_series_at(self, dataset: &Dataset, index: usize) -> Self {
+        match dataset.value(index) {
+            Some(value) => self.add_series(dataset, value),
+            None => self.add(dataset.label.clone(), "No data"),
+        }
+    }
+
     /// Build the tooltip content
     pub fn build(self) -> TooltipContent {
         let mut content = TooltipContent::new(self.title);
@@ -597,6 +625,33 @@ impl DataTooltipBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_data_tooltip_add_series() {
+        let dataset = Dataset::new("Temperature")
+            .with_unit("°C")
+            .with_precision(1)
+            .with_source("NOAA");
+        let builder = DataTooltipBuilder::new("Jan").add_series(&dataset, 21.34);
+        assert_eq!(builder.items[0].0, "Temperature");
+        assert_eq!(builder.items[0].1, "21.3 °C");
+        assert_eq!(builder.footer.as_deref(), Some("Source: NOAA"));
+
+        let builder = DataTooltipBuilder::new("Jan")
+            .footer("Preliminary")
+            .add_series(&dataset, 1.0);
+        assert_eq!(builder.footer.as_deref(), Some("Preliminary"));
+    }
+
+    #[test]
+    fn test_data_tooltip_add_series_at_missing() {
+        let dataset = Dataset::new("Rain").with_optional_data(vec![Some(0.0), None]);
+        let builder = DataTooltipBuilder::new("Mon")
+            .add_series_at(&dataset, 0)
+            .add_series_at(&dataset, 1);
+        assert_eq!(builder.items[0].1, "0");
+        assert_eq!(builder.items[1].1, "No data");
+    }
+
     #[test]
     fn test_tooltip_config_default() {
         let config = TooltipConfig::default();
@@ -717,6 +772,15 @@ mod tests {
         assert_eq!(content.footer, Some("All values in USD".to_string()));
     }
 
+    #[test]
+    fn test_data_tooltip_builder_unit() {
+        let content = DataTooltipBuilder::new("Disk")
+            .add_unit("Used", 1_572_864.0, &UnitFormat::bytes())
+            .build();
+
+        assert_eq!(content.items[0].value, "1.5 MiB");
+    }
+
     #[test]
     fn test_tooltip_anchor_positions() {
         let config = TooltipConfig::default().anchor(TooltipAnchor::BottomRight);
@@ -0,0 +1,505 @@
+//! Marching-squares iso-bands over a value grid (`d3.contours`)
+
+use crate::error::{D3Error, D3Result};
+use crate::geo::{Feature, Geometry, Position};
+use crate::scale::nice_step;
+use crate::shape::Path;
+use std::collections::HashMap;
+
+/// How contour thresholds are chosen
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Thresholds {
+    /// Sturges' rule on the number of values, rounded to nice steps
+    #[default]
+    Sturges,
+    /// Roughly this many nice thresholds spanning the data
+    Count(usize),
+    /// Exactly these thresholds
+    Values(Vec<f64>),
+}
+
+impl Thresholds {
+    /// Resolve to concrete thresholds for the given values
+    ///
+    /// Counts produce nice multiples of a step within the finite extent; a
+    /// threshold at or above the maximum would be empty and is dropped.
+    pub fn resolve(&self, values: &[f64]) -> Vec<f64> {
+        let count = match self {
+            Thresholds::Values(values) => return values.clone(),
+            Thresholds::Count(count) => *count,
+            Thresholds::Sturges => (values.len().max(1) as f64).log2().ceil() as usize + 1,
+        };
+        let Some((min, max)) = crate::scale::extent(values.iter().copied()) else {
+            return Vec::new();
+        };
+        if count == 0 || min == max {
+            return vec![min];
+        }
+
+        let step = nice_step(max - min, count);
+        let mut thresholds: Vec<f64> = Vec::new();
+        let mut i = (min / step).floor();
+        while i * step < max {
+            thresholds.push(i * step);
+            i += 1.0;
+        }
+        while thresholds.len() > 1 && thresholds[1] <= min {
+            thresholds.remove(0);
+        }
+        thresholds
+    }
+}
+
+/// Iso-band for one threshold: the area where the values are `>= value`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contour {
+    /// Threshold value
+    pub value: f64,
+    /// MultiPolygon coordinates: polygons of rings, exterior first then holes
+    pub coordinates: Vec<Vec<Vec<Position>>>,
+}
+
+impl Contour {
+    /// Check if the band covers no area
+    pub fn is_empty(&self) -> bool {
+        self.coordinates.is_empty()
+    }
+
+    /// GeoJSON MultiPolygon geometry of the band
+    pub fn geometry(&self) -> Geometry {
+        Geometry::MultiPolygon {
+            coordinates: self.coordinates.clone(),
+        }
+    }
+
+    /// GeoJSON feature with the threshold in the `value` property
+    pub fn to_feature(&self) -> Feature {
+        Feature::new(self.geometry()).with_property("value", self.value)
+    }
+
+    /// Map every coordinate, e.g. from grid cells to screen pixels
+    pub fn transform(mut self, f: impl Fn(f64, f64) -> (f64, f64)) -> Self {
+        for ring in self.coordinates.iter_mut().flatten() {
+            for p in ring.iter_mut() {
+                let (x, y) = f(p[0], p[1]);
+                *p = [x, y];
+            }
+        }
+        self
+    }
+
+    /// Outline as a path, one closed subpath per ring (fill with even-odd)
+    pub fn to_path(&self) -> Path {
+        let mut path = Path::new();
+        for ring in self.coordinates.iter().flatten() {
+            let Some((first, rest)) = ring.split_first() else {
+                continue;
+            };
+            path.move_to(first[0], first[1]);
+            // Rings repeat their first point at the end
+            for p in &rest[..rest.len().saturating_sub(1)] {
+                path.line_to(p[0], p[1]);
+            }
+            path.close();
+        }
+        path
+    }
+
+    /// Area of the band in coordinate units (holes subtracted)
+    pub fn area(&self) -> f64 {
+        self.coordinates
+            .iter()
+            .flatten()
+            .map(|ring| ring_area(ring) / 2.0)
+            .sum()
+    }
+}
+
+/// Contour generator over a `width` x `height` grid of values
+///
+/// Values are row-major; value `i` covers the unit cell
+/// `[x, x + 1] x [y, y + 1]` with `x = i % width`, `y = i / width`, so
+/// coordinates span `[0, width] x [0, height]`. NaN values count as below
+/// every threshold.
+///
+/// # Example
+/// ```
+/// use makepad_d3::contour::{ContourGenerator, Thresholds};
+///
+/// #[rustfmt::skip]
+/// let values = [
+///     0.0, 0.0, 0.0,
+///     0.0, 5.0, 0.0,
+///     0.0, 0.0, 0.0,
+/// ];
+/// let contours = ContourGenerator::new()
+///     .size(3, 3)
+///     .thresholds(Thresholds::Values(vec![1.0, 10.0]))
+///     .contours(&values)
+///     .unwrap();
+///
+/// assert_eq!(contours[0].coordinates.len(), 1); // a diamond around the peak
+/// assert!(contours[1].is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContourGenerator {
+    /// Grid columns
+    width: usize,
+    /// Grid rows
+    height: usize,
+    /// Threshold rule
+    thresholds: Thresholds,
+    /// Interpolate ring vertices between cell values
+    smooth: bool,
+}
+
+impl Default for ContourGenerator {
+    fn default() -> Self {
+        Self {
+            width: 1,
+            height: 1,
+            thresholds: Thresholds::default(),
+            smooth: true,
+        }
+    }
+}
+
+/// Ring segments per marching-squares case, in half-cell units
+const CASES: [&[[[f64; 2]; 2]]; 16] = [
+    &[],
+    &[[[1.0, 1.5], [0.5, 1.0]]],
+    &[[[1.5, 1.0], [1.0, 1.5]]],
+    &[[[1.5, 1.0], [0.5, 1.0]]],
+    &[[[1.0, 0.5], [1.5, 1.0]]],
+    &[[[1.0, 1.5], [0.5, 1.0]], [[1.0, 0.5], [1.5, 1.0]]],
+    &[[[1.0, 0.5], [1.0, 1.5]]],
+    &[[[1.0, 0.5], [0.5, 1.0]]],
+    &[[[0.5, 1.0], [1.0, 0.5]]],
+    &[[[1.0, 1.5], [1.0, 0.5]]],
+    &[[[0.5, 1.0], [1.0, 0.5]], [[1.5, 1.0], [1.0, 1.5]]],
+    &[[[1.5, 1.0], [1.0, 0.5]]],
+    &[[[0.5, 1.0], [1.5, 1.0]]],
+    &[[[1.0, 1.5], [1.5, 1.0]]],
+    &[[[0.5, 1.0], [1.0, 1.5]]],
+    &[],
+];
+
+impl ContourGenerator {
+    /// Create a generator for a 1 x 1 grid with Sturges thresholds
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the grid size
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self
+    }
+
+    /// Set the threshold rule
+    pub fn thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Enable or disable linear interpolation of ring vertices (default on)
+    pub fn smooth(mut self, smooth: bool) -> Self {
+        self.smooth = smooth;
+        self
+    }
+
+    /// Get the grid size
+    pub fn get_size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// Compute one contour per threshold
+    pub fn contours(&self, values: &[f64]) -> D3Result<Vec<Contour>> {
+        self.check(values)?;
+        Ok(self
+            .thresholds
+            .resolve(values)
+            .into_iter()
+            .map(|value| self.band(values, value))
+            .collect())
+    }
+
+    /// Compute the contour for a single threshold
+    pub fn contour(&self, values: &[f64], value: f64) -> D3Result<Contour> {
+        self.check(values)?;
+        Ok(self.band(values, value))
+    }
+
+    fn check(&self, values: &[f64]) -> D3Result<()> {
+        if values.len() != self.width * self.height {
+            return Err(D3Error::InvalidData {
+                message: format!(
+                    "contour grid is {}x{} but got {} values",
+                    self.width,
+                    self.height,
+                    values.len()
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn band(&self, values: &[f64], value: f64) -> Contour {
+        let mut polygons: Vec<Vec<Vec<Position>>> = Vec::new();
+        let mut holes = Vec::new();
+        for mut ring in self.isorings(values, value) {
+            if self.smooth {
+                self.smooth_ring(&mut ring, values, value);
+            }
+            if ring_area(&ring) > 0.0 {
+                polygons.push(vec![ring]);
+            } else {
+                holes.push(ring);
+            }
+        }
+        for hole in holes {
+            if let Some(polygon) = polygons.iter_mut().find(|p| ring_contains(&p[0], &hole[0])) {
+                polygon.push(hole);
+            }
+        }
+        Contour {
+            value,
+            coordinates: polygons,
+        }
+    }
+
+    /// Closed rings traced through the grid padded with a below-threshold border
+    fn isorings(&self, values: &[f64], value: f64) -> Vec<Vec<Position>> {
+        let (dx, dy) = (self.width as isize, self.height as isize);
+        let above = |x: isize, y: isize| -> usize {
+            if x < 0 || y < 0 || x >= dx || y >= dy {
+                0
+            } else {
+                (values[(y * dx + x) as usize] >= value) as usize
+            }
+        };
+
+        let mut stitcher = Stitcher::new(self.width);
+        for y in -1..dy {
+            for x in -1..dx {
+                let case = above(x, y + 1)
+                    | above(x + 1, y + 1) << 1
+                    | above(x + 1, y) << 2
+                    | above(x, y) << 3;
+                for line in CASES[case] {
+                    let start = [line[0][0] + x as f64, line[0][1] + y as f64];
+                    let end = [line[1][0] + x as f64, line[1][1] + y as f64];
+                    stitcher.stitch(start, end);
+                }
+            }
+        }
+        stitcher.rings
+    }
+
+    /// Move edge midpoints to the linearly interpolated threshold crossing
+    fn smooth_ring(&self, ring: &mut [Position], values: &[f64], value: f64) {
+        let (dx, dy) = (self.width, self.height);
+        let valid = |i: usize| {
+            let v = values[i];
+            if v.is_nan() {
+                f64::NEG_INFINITY
+            } else {
+                v
+            }
+        };
+        for point in ring.iter_mut() {
+            let (x, y) = (point[0], point[1]);
+            let (xt, yt) = (x as usize, y as usize);
+            if x > 0.0 && x < dx as f64 && xt as f64 == x {
+                let v1 = valid(yt * dx + xt);
+                point[0] = smooth1(x, valid(yt * dx + xt - 1), v1, value);
+            }
+            if y > 0.0 && y < dy as f64 && yt as f64 == y {
+                let v1 = valid(yt * dx + xt);
+                point[1] = smooth1(y, valid((yt - 1) * dx + xt), v1, value);
+            }
+        }
+    }
+}
+
+fn smooth1(x: f64, v0: f64, v1: f64, value: f64) -> f64 {
+    let a = value - v0;
+    let b = v1 - v0;
+    let d = if a.is_finite() || b.is_finite() {
+        a / b
+    } else {
+        a.signum() / b.signum()
+    };
+    if d.is_nan() {
+        x
+    } else {
+        x + d - 0.5
+    }
+}
+
+/// Twice the signed area; positive for exterior rings in y-down grid order
+fn ring_area(ring: &[Position]) -> f64 {
+    let n = ring.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let mut area = ring[n - 1][1] * ring[0][0] - ring[n - 1][0] * ring[0][1];
+    for i in 1..n {
+        area += ring[i - 1][1] * ring[i][0] - ring[i - 1][0] * ring[i][1];
+    }
+    area
+}
+
+/// Even-odd point in ring test
+fn ring_contains(ring: &[Position], point: &Position) -> bool {
+    let (x, y) = (point[0], point[1]);
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for i in 0..ring.len() {
+        let (xi, yi) = (ring[i][0], ring[i][1]);
+        let (xj, yj) = (ring[j][0], ring[j][1]);
+        if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Open ring fragment awaiting more segments
+struct Fragment {
+    start: usize,
+    end: usize,
+    ring: Vec<Position>,
+}
+
+/// Joins marching-squares segments into closed rings by shared endpoints
+struct Stitcher {
+    width: usize,
+    fragments: Vec<Option<Fragment>>,
+    by_start: HashMap<usize, usize>,
+    by_end: HashMap<usize, usize>,
+    rings: Vec<Vec<Position>>,
+}
+
+impl Stitcher {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            fragments: Vec::new(),
+            by_start: HashMap::new(),
+            by_end: HashMap::new(),
+            rings: Vec::new(),
+        }
+    }
+
+    /// Unique key of a half-cell grid point (coordinates are >= -0.5)
+    fn index(&self, p: Position) -> usize {
+        let x = ((p[0] + 0.5) * 2.0) as usize;
+        let y = ((p[1] + 0.5) * 2.0) as usize;
+        x + y * (self.width * 2 + 3)
+    }
+
+    fn stitch(&mut self, start: Position, end: Position) {
+        let start_index = self.index(start);
+        let end_index = self.index(end);
+
+        if let Some(&f) = self.by_end.get(&start_index) {
+            self.by_end.remove(&start_index);
+            if let Some(&g) = self.by_start.get(&end_index) {
+                self.by_start.remove(&end_index);
+                if f == g {
+                    let mut fragment = self.fragments[f].take().unwrap();
+                    fragment.ring.push(end);
+                    self.rings.push(fragment.ring);
+                } else {
+                    let tail = self.fragments[g].take().unwrap();
+                    let head = self.fragments[f].as_mut().unwrap();
+                    head.ring.extend(tail.ring);
+                    head.end = tail.end;
+                    self.by_end.insert(tail.end, f);
+                }
+            } else {
+                let fragment = self.fragments[f].as_mut().unwrap();
+                fragment.ring.push(end);
+                fragment.end = end_index;
+                self.by_end.insert(end_index, f);
+            }
+        } else if let Some(&f) = self.by_start.get(&end_index) {
+            self.by_start.remove(&end_index);
+            let fragment = self.fragments[f].as_mut().unwrap();
+            fragment.ring.insert(0, start);
+            fragment.start = start_index;
+            self.by_start.insert(start_index, f);
+        } else {
+            let id = self.fragments.len();
+            self.fragments.push(Some(Fragment {
+                start: start_index,
+                end: end_index,
+                ring: vec![start, end],
+            }));
+            self.by_start.insert(start_index, id);
+            self.by_end.insert(end_index, id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_peak_unsmoothed() {
+        let values = [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+        let contour = ContourGenerator::new()
+            .size(3, 3)
+            .smooth(false)
+            .contour(&values, 0.5)
+            .unwrap();
+        assert_eq!(contour.coordinates.len(), 1);
+        let ring = &contour.coordinates[0][0];
+        // Closed diamond halfway between the center cell and its neighbours
+        assert_eq!(ring.len(), 5);
+        assert_eq!(ring.first(), ring.last());
+        for p in &ring[..4] {
+            let d = (p[0] - 1.5).abs() + (p[1] - 1.5).abs();
+            assert!((d - 0.5).abs() < 1e-9, "{:?}", p);
+        }
+        assert!((contour.area() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ring_with_hole() {
+        // A ring of high values around a low center
+        #[rustfmt::skip]
+        let values = [
+            0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 1.0, 1.0, 0.0,
+            0.0, 1.0, 0.0, 1.0, 0.0,
+            0.0, 1.0, 1.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 0.0,
+        ];
+        let contour = ContourGenerator::new().size(5, 5).contour(&values, 0.5).unwrap();
+        assert_eq!(contour.coordinates.len(), 1);
+        assert_eq!(contour.coordinates[0].len(), 2);
+        assert!(contour.area() > 0.0);
+
+        let path = contour.to_path();
+        assert_eq!(path.into_segments().iter().filter(|s| matches!(s, crate::shape::PathSegment::ClosePath)).count(), 2);
+    }
+
+    #[test]
+    fn test_smoothing_and_thresholds() {
+        // Linear ramp along x: the 2.5 threshold crosses at x = 3
+        let values: Vec<f64> = (0..4).flat_map(|_| (0..5).map(|x| x as f64)).collect();
+        let contour = ContourGenerator::new().size(5, 4).contour(&values, 2.5).unwrap();
+        let ring = &contour.coordinates[0][0];
+        let min_x = ring.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+        assert!((min_x - 3.0).abs() < 1e-9);
+
+        let thresholds = Thresholds::Count(4).resolve(&values);
+        assert_eq!(thresholds, vec![0.0, 1.0, 2.0, 3.0]);
+        assert!(ContourGenerator::new().size(2, 2).contours(&values).is_err());
+    }
+}
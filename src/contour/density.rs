@@ -0,0 +1,289 @@
+//! Kernel density contours from scattered points (`d3.contourDensity`)
+
+use super::contours::{Contour, ContourGenerator, Thresholds};
+
+/// Gaussian kernel density estimator producing density contours
+///
+/// Points are splatted onto a grid of `cell_size` pixel cells, blurred with
+/// a Gaussian of standard deviation `bandwidth`, and contoured. The grid is
+/// padded by three bandwidths so bands near the edges close; contour
+/// coordinates are in the same pixel space as the input points. Contour
+/// values are densities in weight per square pixel.
+///
+/// # Example
+/// ```
+/// use makepad_d3::contour::{ContourDensity, Thresholds};
+///
+/// let points: Vec<(f64, f64)> = (0..50)
+///     .map(|i| (100.0 + (i % 7) as f64, 80.0 + (i % 5) as f64))
+///     .collect();
+///
+/// let contours = ContourDensity::new()
+///     .size(200.0, 160.0)
+///     .bandwidth(10.0)
+///     .thresholds(Thresholds::Count(5))
+///     .contours(&points);
+///
+/// assert!(!contours.is_empty());
+/// assert!(contours.iter().all(|c| c.value > 0.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContourDensity {
+    /// Width of the estimation area in pixels
+    width: f64,
+    /// Height of the estimation area in pixels
+    height: f64,
+    /// Grid cell size in pixels
+    cell_size: f64,
+    /// Kernel standard deviation in pixels
+    bandwidth: f64,
+    /// Threshold rule
+    thresholds: Thresholds,
+}
+
+impl Default for ContourDensity {
+    fn default() -> Self {
+        Self {
+            width: 960.0,
+            height: 500.0,
+            cell_size: 4.0,
+            bandwidth: 20.4939015319192,
+            thresholds: Thresholds::Count(20),
+        }
+    }
+}
+
+/// Density values on the padded estimation grid
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityGrid {
+    /// Row-major densities in weight per square pixel
+    pub values: Vec<f64>,
+    /// Grid columns
+    pub width: usize,
+    /// Grid rows
+    pub height: usize,
+    /// Cell size in pixels
+    pub cell_size: f64,
+    /// Padding in pixels added on every side of the estimation area
+    pub padding: f64,
+}
+
+impl DensityGrid {
+    /// Pixel position of grid coordinates
+    pub fn to_pixels(&self, x: f64, y: f64) -> (f64, f64) {
+        (x * self.cell_size - self.padding, y * self.cell_size - self.padding)
+    }
+
+    /// Largest density on the grid
+    pub fn max(&self) -> f64 {
+        self.values.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+impl ContourDensity {
+    /// Create an estimator for a 960 x 500 area, as in d3
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the estimation area size in pixels
+    pub fn size(mut self, width: f64, height: f64) -> Self {
+        self.width = width.max(0.0);
+        self.height = height.max(0.0);
+        self
+    }
+
+    /// Set the grid cell size in pixels (default 4)
+    ///
+    /// Rounded down to a power of two like d3; smaller cells are finer and
+    /// slower.
+    pub fn cell_size(mut self, size: f64) -> Self {
+        self.cell_size = 2f64.powi(size.max(1.0).log2().floor() as i32);
+        self
+    }
+
+    /// Set the kernel standard deviation in pixels (default ~20.5)
+    pub fn bandwidth(mut self, bandwidth: f64) -> Self {
+        self.bandwidth = bandwidth.max(0.0);
+        self
+    }
+
+    /// Set the threshold rule (default 20 nice thresholds)
+    pub fn thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Get the cell size
+    pub fn get_cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    /// Get the bandwidth
+    pub fn get_bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Estimate the density grid for unit-weight points
+    pub fn grid(&self, points: &[(f64, f64)]) -> DensityGrid {
+        self.grid_weighted(points.iter().map(|&(x, y)| (x, y, 1.0)))
+    }
+
+    /// Estimate the density grid for `(x, y, weight)` points
+    pub fn grid_weighted(&self, points: impl IntoIterator<Item = (f64, f64, f64)>) -> DensityGrid {
+        let padding = self.bandwidth * 3.0;
+        let cell = self.cell_size;
+        let n = ((self.width + padding * 2.0) / cell).ceil().max(1.0) as usize;
+        let m = ((self.height + padding * 2.0) / cell).ceil().max(1.0) as usize;
+        let mut values = vec![0.0; n * m];
+
+        for (x, y, w) in points {
+            let xi = (x + padding) / cell;
+            let yi = (y + padding) / cell;
+            if !(w.is_finite() && w != 0.0 && xi >= 0.0 && xi < n as f64 && yi >= 0.0 && yi < m as f64) {
+                continue;
+            }
+            // Bilinear splat onto the four nearest cell centers
+            let (xs, ys) = (xi - 0.5, yi - 0.5);
+            let (x0, y0) = (xs.floor(), ys.floor());
+            let (xt, yt) = (xs - x0, ys - y0);
+            let mut add = |cx: isize, cy: isize, share: f64| {
+                if cx >= 0 && cy >= 0 && (cx as usize) < n && (cy as usize) < m {
+                    values[cy as usize * n + cx as usize] += share * w;
+                }
+            };
+            let (x0, y0) = (x0 as isize, y0 as isize);
+            add(x0, y0, (1.0 - xt) * (1.0 - yt));
+            add(x0 + 1, y0, xt * (1.0 - yt));
+            add(x0 + 1, y0 + 1, xt * yt);
+            add(x0, y0 + 1, (1.0 - xt) * yt);
+        }
+
+        gaussian_blur(&mut values, n, m, self.bandwidth / cell);
+        let area = cell * cell;
+        for v in &mut values {
+            *v /= area;
+        }
+
+        DensityGrid {
+            values,
+            width: n,
+            height: m,
+            cell_size: cell,
+            padding,
+        }
+    }
+
+    /// Density contours for unit-weight points
+    pub fn contours(&self, points: &[(f64, f64)]) -> Vec<Contour> {
+        self.contours_for(&self.grid(points))
+    }
+
+    /// Density contours for `(x, y, weight)` points
+    pub fn contours_weighted(&self, points: impl IntoIterator<Item = (f64, f64, f64)>) -> Vec<Contour> {
+        self.contours_for(&self.grid_weighted(points))
+    }
+
+    fn contours_for(&self, grid: &DensityGrid) -> Vec<Contour> {
+        // Zero density everywhere outside the data would be a band covering
+        // the whole area; d3 starts the thresholds above zero
+        let thresholds: Vec<f64> = match &self.thresholds {
+            Thresholds::Values(values) => values.clone(),
+            rule => rule
+                .resolve(&grid.values)
+                .into_iter()
+                .filter(|&t| t > 0.0)
+                .collect(),
+        };
+        let generator = ContourGenerator::new()
+            .size(grid.width, grid.height)
+            .thresholds(Thresholds::Values(thresholds));
+        generator
+            .contours(&grid.values)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|c| c.transform(|x, y| grid.to_pixels(x, y)))
+            .collect()
+    }
+}
+
+/// Separable Gaussian blur truncated at three standard deviations
+fn gaussian_blur(values: &mut [f64], width: usize, height: usize, sigma: f64) {
+    if sigma <= 0.0 {
+        return;
+    }
+    let radius = (sigma * 3.0).ceil() as isize;
+    let mut kernel: Vec<f64> = (-radius..=radius)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f64 = kernel.iter().sum();
+    for k in &mut kernel {
+        *k /= total;
+    }
+
+    let mut scratch = vec![0.0; values.len()];
+    let pass = |src: &[f64], dst: &mut [f64], horizontal: bool| {
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = 0.0;
+                for (k, weight) in kernel.iter().enumerate() {
+                    let offset = k as isize - radius;
+                    let (sx, sy) = if horizontal {
+                        (x as isize + offset, y as isize)
+                    } else {
+                        (x as isize, y as isize + offset)
+                    };
+                    if sx >= 0 && sy >= 0 && (sx as usize) < width && (sy as usize) < height {
+                        sum += src[sy as usize * width + sx as usize] * weight;
+                    }
+                }
+                dst[y * width + x] = sum;
+            }
+        }
+    };
+    pass(values, &mut scratch, true);
+    pass(&scratch, values, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density_preserves_mass() {
+        let estimator = ContourDensity::new().size(100.0, 100.0).bandwidth(5.0);
+        let grid = estimator.grid(&[(50.0, 50.0), (52.0, 48.0), (-500.0, 0.0)]);
+        // The point outside the padded area is ignored
+        let mass: f64 = grid.values.iter().sum::<f64>() * grid.cell_size * grid.cell_size;
+        assert!((mass - 2.0).abs() < 1e-9);
+        assert_eq!(grid.padding, 15.0);
+        assert_eq!(grid.to_pixels(0.0, 0.0), (-15.0, -15.0));
+    }
+
+    #[test]
+    fn test_density_contours_surround_cluster() {
+        let points: Vec<(f64, f64)> = (0..20).map(|i| (60.0 + (i % 4) as f64, 40.0 + (i / 4) as f64)).collect();
+        let contours = ContourDensity::new()
+            .size(120.0, 80.0)
+            .bandwidth(6.0)
+            .cell_size(3.0)
+            .contours(&points);
+        assert!(!contours.is_empty());
+
+        let densest = contours.iter().rev().find(|c| !c.is_empty()).unwrap();
+        let ring = &densest.coordinates[0][0];
+        let cx = ring.iter().map(|p| p[0]).sum::<f64>() / ring.len() as f64;
+        let cy = ring.iter().map(|p| p[1]).sum::<f64>() / ring.len() as f64;
+        assert!((cx - 61.5).abs() < 4.0 && (cy - 42.0).abs() < 4.0, "{} {}", cx, cy);
+    }
+
+    #[test]
+    fn test_weights_and_cell_size() {
+        assert_eq!(ContourDensity::new().cell_size(3.0).get_cell_size(), 2.0);
+        let estimator = ContourDensity::new().size(40.0, 40.0).bandwidth(4.0);
+        let light = estimator.grid_weighted([(20.0, 20.0, 1.0)]).max();
+        let heavy = estimator.grid_weighted([(20.0, 20.0, 3.0)]).max();
+        assert!((heavy - 3.0 * light).abs() < 1e-12);
+        assert!(estimator.contours(&[]).is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+//! Contour lines and density estimation
+//!
+//! Port of [d3-contour](https://github.com/d3/d3-contour):
+//!
+//! - [`ContourGenerator`]: Marching-squares iso-bands over a grid of values
+//!   (elevation, heat maps, function plots)
+//! - [`ContourDensity`]: Gaussian kernel density contours from scattered points
+//!
+//! Each [`Contour`] is a MultiPolygon covering the area at or above its
+//! threshold. Use [`Contour::to_path`] for shape rendering or
+//! [`Contour::geometry`] / [`Contour::to_feature`] with
+//! [`GeoPath`](crate::geo::GeoPath).
+//!
+//! # Example
+//! ```
+//! use makepad_d3::contour::{ContourGenerator, Thresholds};
+//!
+//! // z = x + y on a 10 x 10 grid
+//! let values: Vec<f64> = (0..100).map(|i| (i % 10 + i / 10) as f64).collect();
+//!
+//! let contours = ContourGenerator::new()
+//!     .size(10, 10)
+//!     .thresholds(Thresholds::Count(5))
+//!     .contours(&values)
+//!     .unwrap();
+//!
+//! // Scale grid cells to a 400 x 400 plot
+//! let path = contours[1].clone().transform(|x, y| (x * 40.0, y * 40.0)).to_path();
+//! assert!(!path.is_empty());
+//! ```
+
+mod contours;
+mod density;
+
+pub use contours::{Contour, ContourGenerator, Thresholds};
+pub use density::{ContourDensity, DensityGrid};
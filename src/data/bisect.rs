@@ -0,0 +1,185 @@
+//! Binary search over sorted data
+//!
+//! Equivalents of d3's `bisectLeft`, `bisectRight`, `bisectCenter` and
+//! `bisector`. Combined with a scale's `invert`, they find the data point
+//! nearest to the pointer for hover tooltips and crosshairs.
+//!
+//! Values (or accessor keys) must be sorted ascending. NaN keys compare as
+//! neither less nor greater, so they should be filtered out beforehand.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::{nearest_point, DataPoint};
+//! use makepad_d3::scale::{LinearScale, Scale, ScaleExt};
+//!
+//! let data: Vec<_> = (0..10).map(|i| DataPoint::new(i as f64 * 10.0, i as f64)).collect();
+//! let x = LinearScale::new().with_domain(0.0, 90.0).with_range(0.0, 900.0);
+//!
+//! // Pointer at pixel 432 is x = 43.2, nearest to the point at x = 40
+//! assert_eq!(nearest_point(&data, x.invert(432.0)), Some(4));
+//! ```
+
+use super::DataPoint;
+
+/// First index in `0..len` where `is_right(index)` holds
+///
+/// `is_right` must be false for a prefix of the indices and true for the rest.
+fn partition(len: usize, is_right: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if is_right(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+/// Index of the closer of the keys at `i - 1` and `i` (the insertion point)
+fn center(len: usize, i: usize, x: f64, key: impl Fn(usize) -> f64) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    if i == 0 {
+        return Some(0);
+    }
+    if i == len {
+        return Some(len - 1);
+    }
+    // Ties go to the left neighbour, as in d3
+    if x - key(i - 1) > key(i) - x {
+        Some(i)
+    } else {
+        Some(i - 1)
+    }
+}
+
+/// Insertion point for `x` before any equal values
+pub fn bisect_left(values: &[f64], x: f64) -> usize {
+    partition(values.len(), |i| values[i] >= x)
+}
+
+/// Insertion point for `x` after any equal values
+pub fn bisect_right(values: &[f64], x: f64) -> usize {
+    partition(values.len(), |i| values[i] > x)
+}
+
+/// Index of the value closest to `x`, or None for an empty slice
+pub fn bisect_center(values: &[f64], x: f64) -> Option<usize> {
+    center(values.len(), bisect_left(values, x), x, |i| values[i])
+}
+
+/// Binary search over items sorted by an accessor key
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::data::Bisector;
+///
+/// let sales = [("Jan", 1.0), ("Feb", 2.0), ("Mar", 3.0)];
+/// let by_month = Bisector::new(|d: &(&str, f64)| d.1);
+///
+/// assert_eq!(by_month.left(&sales, 2.0), 1);
+/// assert_eq!(by_month.right(&sales, 2.0), 2);
+/// assert_eq!(by_month.center(&sales, 2.6), Some(2));
+/// ```
+pub struct Bisector<F> {
+    key: F,
+}
+
+impl<F> Bisector<F> {
+    /// Create a bisector from an accessor returning each item's sort key
+    pub fn new<T>(key: F) -> Self
+    where
+        F: Fn(&T) -> f64,
+    {
+        Self { key }
+    }
+
+    /// Insertion point for `x` before any items with an equal key
+    pub fn left<T>(&self, items: &[T], x: f64) -> usize
+    where
+        F: Fn(&T) -> f64,
+    {
+        partition(items.len(), |i| (self.key)(&items[i]) >= x)
+    }
+
+    /// Insertion point for `x` after any items with an equal key
+    pub fn right<T>(&self, items: &[T], x: f64) -> usize
+    where
+        F: Fn(&T) -> f64,
+    {
+        partition(items.len(), |i| (self.key)(&items[i]) > x)
+    }
+
+    /// Index of the item whose key is closest to `x`
+    pub fn center<T>(&self, items: &[T], x: f64) -> Option<usize>
+    where
+        F: Fn(&T) -> f64,
+    {
+        center(items.len(), self.left(items, x), x, |i| (self.key)(&items[i]))
+    }
+}
+
+/// Create a [`Bisector`] from a key accessor (d3's `bisector`)
+pub fn bisector<T, F>(key: F) -> Bisector<F>
+where
+    F: Fn(&T) -> f64,
+{
+    Bisector::new(key)
+}
+
+/// Index of the point whose x is closest to `x`
+///
+/// Points without an x use their index (see [`DataPoint::x_or`]), so this
+/// works for both x-y series and index-based series. Points must be sorted
+/// by x.
+pub fn nearest_point(data: &[DataPoint], x: f64) -> Option<usize> {
+    let key = |i: usize| data[i].x_or(i);
+    let i = partition(data.len(), |i| key(i) >= x);
+    center(data.len(), i, x, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bisect_left_right() {
+        let values = [1.0, 2.0, 2.0, 2.0, 5.0];
+        assert_eq!(bisect_left(&values, 2.0), 1);
+        assert_eq!(bisect_right(&values, 2.0), 4);
+        assert_eq!(bisect_left(&values, 0.0), 0);
+        assert_eq!(bisect_right(&values, 9.0), 5);
+        assert_eq!(bisect_left(&[], 1.0), 0);
+    }
+
+    #[test]
+    fn test_bisect_center() {
+        let values = [0.0, 10.0, 20.0];
+        assert_eq!(bisect_center(&values, -5.0), Some(0));
+        assert_eq!(bisect_center(&values, 4.0), Some(0));
+        assert_eq!(bisect_center(&values, 5.0), Some(0));
+        assert_eq!(bisect_center(&values, 6.0), Some(1));
+        assert_eq!(bisect_center(&values, 99.0), Some(2));
+        assert_eq!(bisect_center(&[], 1.0), None);
+    }
+
+    #[test]
+    fn test_bisector_and_nearest_point() {
+        let data = vec![DataPoint::new(1.0, 5.0), DataPoint::new(3.0, 6.0), DataPoint::new(8.0, 7.0)];
+        let by_x = bisector(|p: &DataPoint| p.x.unwrap_or(f64::NAN));
+        assert_eq!(by_x.left(&data, 3.0), 1);
+        assert_eq!(by_x.right(&data, 3.0), 2);
+        assert_eq!(by_x.center(&data, 6.0), Some(2));
+        assert_eq!(nearest_point(&data, 6.0), Some(2));
+
+        // Index-based series
+        let indexed: Vec<_> = [4.0, 2.0, 9.0].iter().map(|&y| DataPoint::from_y(y)).collect();
+        assert_eq!(nearest_point(&indexed, 1.4), Some(1));
+        assert_eq!(nearest_point(&[], 1.4), None);
+    }
+}
@@ -88,6 +88,61 @@ impl ChartData {
         if found { Some((min, max)) } else { None }
     }
 
+    /// Get the y-axis ids used by datasets, in order of first appearance
+    ///
+    /// Hidden datasets are included so axes don't disappear when a series
+    /// is toggled off.
+    pub fn y_axis_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = Vec::new();
+        for dataset in &self.datasets {
+            let id = dataset.y_axis_id();
+            if !ids.iter().any(|existing| existing == id) {
+                ids.push(id.to_string());
+            }
+        }
+        ids
+    }
+
+    /// Get the datasets bound to a y-axis
+    pub fn datasets_for_axis<'a>(&'a self, axis_id: &'a str) -> impl Iterator<Item = &'a Dataset> + 'a {
+        self.datasets.iter().filter(move |d| d.y_axis_id() == axis_id)
+    }
+
+    /// Get Y extent across visible datasets bound to a y-axis
+    pub fn y_extent_for_axis(&self, axis_id: &str) -> Option<(f64, f64)> {
+        let mut min = f64::MAX;
+        let mut max = f64::MIN;
+        let mut found = false;
+
+        for dataset in self.datasets_for_axis(axis_id) {
+            if dataset.hidden {
+                continue;
+            }
+            if let Some((d_min, d_max)) = dataset.y_extent() {
+                min = min.min(d_min);
+                max = max.max(d_max);
+                found = true;
+            }
+        }
+
+        if found { Some((min, max)) } else { None }
+    }
+
+    /// Get Y extents per y-axis, in the order returned by [`y_axis_ids`](Self::y_axis_ids)
+    ///
+    /// Axes whose datasets are all hidden or empty are omitted.
+    pub fn y_extents_by_axis(&self) -> Vec<(String, (f64, f64))> {
+        self.y_axis_ids()
+            .into_iter()
+            .filter_map(|id| self.y_extent_for_axis(&id).map(|extent| (id, extent)))
+            .collect()
+    }
+
+    /// Check if datasets are spread over more than one y-axis
+    pub fn has_multiple_y_axes(&self) -> bool {
+        self.y_axis_ids().len() > 1
+    }
+
     /// Get total Y value (for pie charts)
     pub fn total(&self) -> f64 {
         self.datasets
@@ -145,6 +200,25 @@ impl ChartData {
         Ok(())
     }
 
+    /// Plain-text summary of the visible series for screen readers
+    pub fn summary(&self) -> String {
+        let visible: Vec<&Dataset> = self.datasets.iter().filter(|d| !d.hidden).collect();
+        let mut text = format!(
+            "Chart with {} series{}.",
+            visible.len(),
+            if self.labels.is_empty() {
+                String::new()
+            } else {
+                format!(" over {} categories", self.labels.len())
+            }
+        );
+        for dataset in visible {
+            text.push(' ');
+            text.push_str(&dataset.summary());
+        }
+        text
+    }
+
     /// Get maximum number of data points across all datasets
     pub fn max_points(&self) -> usize {
         self.datasets.iter().map(|d| d.len()).max().unwrap_or(0)
@@ -159,6 +233,21 @@ impl ChartData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::data::dataset::DEFAULT_Y_AXIS;
+
+    #[test]
+    fn test_chart_data_summary() {
+        let mut hidden = Dataset::new("Hidden").with_data(vec![1.0]);
+        hidden.hidden = true;
+        let data = ChartData::new()
+            .with_labels(vec!["Q1", "Q2"])
+            .add_dataset(Dataset::new("Sales").with_data(vec![5.0, 7.0]).with_precision(0))
+            .add_dataset(hidden);
+        assert_eq!(
+            data.summary(),
+            "Chart with 1 series over 2 categories. Sales: 2 values from 5 to 7, last 7."
+        );
+    }
 
     #[test]
     fn test_chart_data_new() {
@@ -258,6 +347,36 @@ mod tests {
         assert_eq!(parsed.datasets.len(), original.datasets.len());
     }
 
+    #[test]
+    fn test_y_extents_by_axis() {
+        let data = ChartData::new()
+            .add_dataset(Dataset::new("Revenue").with_data(vec![1000.0, 5000.0]).with_y_axis("left"))
+            .add_dataset(Dataset::new("Conversion").with_data(vec![0.02, 0.05]).with_y_axis("right"))
+            .add_dataset(Dataset::new("Cost").with_data(vec![500.0, 7000.0]).with_y_axis("left"));
+
+        assert_eq!(data.y_axis_ids(), vec!["left", "right"]);
+        assert!(data.has_multiple_y_axes());
+        assert_eq!(data.y_extent_for_axis("left"), Some((500.0, 7000.0)));
+        assert_eq!(data.y_extent_for_axis("right"), Some((0.02, 0.05)));
+        assert_eq!(data.y_extent_for_axis("missing"), None);
+
+        let extents = data.y_extents_by_axis();
+        assert_eq!(extents.len(), 2);
+        assert_eq!(extents[1].0, "right");
+    }
+
+    #[test]
+    fn test_y_axis_default_and_hidden() {
+        let data = ChartData::new()
+            .add_dataset(Dataset::new("A").with_data(vec![1.0, 2.0]))
+            .add_dataset(Dataset::new("B").with_data(vec![10.0]).with_y_axis("right").with_hidden(true));
+
+        assert_eq!(data.y_axis_ids(), vec![DEFAULT_Y_AXIS, "right"]);
+        assert_eq!(data.datasets_for_axis("right").count(), 1);
+        assert_eq!(data.y_extent_for_axis("right"), None);
+        assert_eq!(data.y_extents_by_axis().len(), 1);
+    }
+
     #[test]
     fn test_visible_count() {
         let data = ChartData::new()
@@ -0,0 +1,601 @@
+//! Crossfilter-style linked filtering over a record table
+//!
+//! A [`Crossfilter`] holds a table of records and a set of dimensions, each a
+//! numeric or categorical accessor over a record. Every dimension can carry
+//! one filter (a range or a set of categories); a record is in the filtered
+//! set when it passes all of them.
+//!
+//! Grouping follows the dc.js convention: a dimension's groups are computed
+//! with every filter except its own, so the chart that owns a brush still
+//! shows the full distribution it is brushing over while all other charts
+//! narrow to the selection. Groups keep empty keys with a count of zero, so
+//! bars do not disappear while filtering.
+//!
+//! Filters can be set directly or from a [`BrushSelection`] (through the
+//! chart's scale), a [`Legend`] or a [`CategoryFilter`]. Each change bumps
+//! [`Crossfilter::version`] and queues a [`CrossfilterChange`], so every
+//! linked chart can tell when to redraw.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::{Crossfilter, Dimension, GroupKey};
+//!
+//! struct Sale { region: &'static str, price: f64, units: f64 }
+//!
+//! let mut sales = Crossfilter::new(vec![
+//!     Sale { region: "North", price: 12.0, units: 3.0 },
+//!     Sale { region: "South", price: 25.0, units: 1.0 },
+//!     Sale { region: "North", price: 31.0, units: 2.0 },
+//!     Sale { region: "East", price: 8.0, units: 5.0 },
+//! ]);
+//! let region = sales.add_dimension(Dimension::category("region", |s: &Sale| s.region.to_string())).unwrap();
+//! let price = sales.add_dimension(Dimension::numeric("price", |s: &Sale| s.price).with_bin(10.0)).unwrap();
+//!
+//! // Brushing prices 10..30 narrows the region chart...
+//! sales.filter_range(price, 10.0, 30.0);
+//! let units = sales.group(region, |s| s.units);
+//! assert_eq!(units[1].key, GroupKey::Category("North".into()));
+//! assert_eq!((units[1].count, units[1].sum), (1, 3.0));
+//! assert_eq!(units[0].count, 0); // East is out of the price range
+//!
+//! // ...while the price histogram ignores its own filter
+//! assert_eq!(sales.group(price, |_| 1.0).len(), 4);
+//! assert_eq!(sales.filtered_len(), 2);
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::component::Legend;
+use crate::error::{D3Error, D3Result};
+use crate::interaction::{BrushSelection, CategoryFilter};
+use crate::scale::Scale;
+
+/// Most dimensions a [`Crossfilter`] can hold (one bit each per record)
+pub const MAX_DIMENSIONS: usize = 64;
+
+enum Accessor<R> {
+    Numeric(Box<dyn Fn(&R) -> f64 + Send + Sync>),
+    Category(Box<dyn Fn(&R) -> String + Send + Sync>),
+}
+
+/// A numeric or categorical view of a record
+pub struct Dimension<R> {
+    name: String,
+    accessor: Accessor<R>,
+    bin: Option<f64>,
+}
+
+impl<R> Dimension<R> {
+    /// Dimension over a numeric field
+    pub fn numeric(name: impl Into<String>, accessor: impl Fn(&R) -> f64 + Send + Sync + 'static) -> Self {
+        Self { name: name.into(), accessor: Accessor::Numeric(Box::new(accessor)), bin: None }
+    }
+
+    /// Dimension over a categorical field
+    pub fn category(name: impl Into<String>, accessor: impl Fn(&R) -> String + Send + Sync + 'static) -> Self {
+        Self { name: name.into(), accessor: Accessor::Category(Box::new(accessor)), bin: None }
+    }
+
+    /// Group numeric values into bins of `width` (keyed by the bin start)
+    ///
+    /// Filters still apply to the raw values. Ignored for categories and
+    /// for non-positive widths.
+    pub fn with_bin(mut self, width: f64) -> Self {
+        self.bin = (width > 0.0 && width.is_finite()).then_some(width);
+        self
+    }
+
+    /// Get the dimension name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn value(&self, record: &R) -> Value {
+        match &self.accessor {
+            Accessor::Numeric(f) => Value::Number(f(record)),
+            Accessor::Category(f) => Value::Category(f(record)),
+        }
+    }
+}
+
+/// Handle to a dimension of a [`Crossfilter`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DimensionId(usize);
+
+impl DimensionId {
+    /// Position of the dimension in insertion order
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Filter applied to one dimension
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum DimensionFilter {
+    /// Every record passes
+    #[default]
+    All,
+    /// Numeric values within `[lo, hi]`; NaN never passes
+    Range(f64, f64),
+    /// Categories in the set (numeric values never pass)
+    Categories(HashSet<String>),
+}
+
+impl DimensionFilter {
+    fn passes(&self, value: &Value) -> bool {
+        match (self, value) {
+            (DimensionFilter::All, _) => true,
+            (DimensionFilter::Range(lo, hi), Value::Number(v)) => *lo <= *v && *v <= *hi,
+            (DimensionFilter::Categories(set), Value::Category(c)) => set.contains(c),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Number(f64),
+    Category(String),
+}
+
+/// Key of an aggregated group
+#[derive(Clone, Debug, PartialEq)]
+pub enum GroupKey {
+    /// Numeric value, or bin start for binned dimensions
+    Number(f64),
+    /// Category
+    Category(String),
+}
+
+impl GroupKey {
+    fn order(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (GroupKey::Number(a), GroupKey::Number(b)) => a.total_cmp(b),
+            (GroupKey::Category(a), GroupKey::Category(b)) => a.cmp(b),
+            (GroupKey::Number(_), GroupKey::Category(_)) => Ordering::Less,
+            (GroupKey::Category(_), GroupKey::Number(_)) => Ordering::Greater,
+        }
+    }
+}
+
+/// Aggregate of the records sharing a key
+#[derive(Clone, Debug, PartialEq)]
+pub struct Group {
+    /// Group key
+    pub key: GroupKey,
+    /// Number of records passing the other dimensions' filters
+    pub count: usize,
+    /// Sum of the finite reduced values of those records
+    pub sum: f64,
+}
+
+impl Group {
+    /// Mean of the reduced values (NaN for an empty group)
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+}
+
+/// A change recorded by [`Crossfilter`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CrossfilterChange {
+    /// A dimension's filter changed
+    Filtered(DimensionId),
+    /// Records were appended
+    Added {
+        /// Number of new records
+        count: usize,
+    },
+    /// All records were removed
+    Cleared,
+}
+
+/// Linked filtering and grouping over a table of records
+pub struct Crossfilter<R> {
+    records: Vec<R>,
+    dimensions: Vec<Dimension<R>>,
+    filters: Vec<DimensionFilter>,
+    /// Per dimension, the value of every record
+    values: Vec<Vec<Value>>,
+    /// Per record, one bit for each dimension whose filter it fails
+    fails: Vec<u64>,
+    version: u64,
+    changes: VecDeque<CrossfilterChange>,
+}
+
+impl<R> Crossfilter<R> {
+    /// Create a crossfilter over `records`
+    pub fn new(records: Vec<R>) -> Self {
+        Self {
+            fails: vec![0; records.len()],
+            records,
+            dimensions: Vec::new(),
+            filters: Vec::new(),
+            values: Vec::new(),
+            version: 0,
+            changes: VecDeque::new(),
+        }
+    }
+
+    /// Add a dimension; fails once [`MAX_DIMENSIONS`] exist
+    pub fn add_dimension(&mut self, dimension: Dimension<R>) -> D3Result<DimensionId> {
+        if self.dimensions.len() >= MAX_DIMENSIONS {
+            return Err(D3Error::config_error(format!(
+                "Crossfilter supports at most {} dimensions",
+                MAX_DIMENSIONS
+            )));
+        }
+        self.values.push(self.records.iter().map(|r| dimension.value(r)).collect());
+        self.dimensions.push(dimension);
+        self.filters.push(DimensionFilter::All);
+        Ok(DimensionId(self.dimensions.len() - 1))
+    }
+
+    /// Find a dimension by name
+    pub fn dimension(&self, name: &str) -> Option<DimensionId> {
+        self.dimensions.iter().position(|d| d.name == name).map(DimensionId)
+    }
+
+    /// Get all records
+    pub fn records(&self) -> &[R] {
+        &self.records
+    }
+
+    /// Number of records
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Check if there are no records
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Append records, applying the current filters to them
+    pub fn add(&mut self, records: impl IntoIterator<Item = R>) {
+        let start = self.records.len();
+        self.records.extend(records);
+        for (dimension, values) in self.dimensions.iter().zip(&mut self.values) {
+            values.extend(self.records[start..].iter().map(|r| dimension.value(r)));
+        }
+        for i in start..self.records.len() {
+            let mut fails = 0u64;
+            for (d, (filter, values)) in self.filters.iter().zip(&self.values).enumerate() {
+                if !filter.passes(&values[i]) {
+                    fails |= 1 << d;
+                }
+            }
+            self.fails.push(fails);
+        }
+        let count = self.records.len() - start;
+        if count > 0 {
+            self.notify(CrossfilterChange::Added { count });
+        }
+    }
+
+    /// Remove every record, keeping dimensions and filters
+    pub fn clear(&mut self) {
+        self.records.clear();
+        self.fails.clear();
+        self.values.iter_mut().for_each(Vec::clear);
+        self.notify(CrossfilterChange::Cleared);
+    }
+
+    /// Get a dimension's filter
+    pub fn filter(&self, dimension: DimensionId) -> &DimensionFilter {
+        &self.filters[dimension.0]
+    }
+
+    /// Check if a dimension has a filter other than [`DimensionFilter::All`]
+    pub fn is_filtered(&self, dimension: DimensionId) -> bool {
+        self.filters[dimension.0] != DimensionFilter::All
+    }
+
+    /// Set a dimension's filter; returns false if it was unchanged
+    pub fn set_filter(&mut self, dimension: DimensionId, filter: DimensionFilter) -> bool {
+        let d = dimension.0;
+        if self.filters[d] == filter {
+            return false;
+        }
+        let bit = 1u64 << d;
+        for (fails, value) in self.fails.iter_mut().zip(&self.values[d]) {
+            if filter.passes(value) {
+                *fails &= !bit;
+            } else {
+                *fails |= bit;
+            }
+        }
+        self.filters[d] = filter;
+        self.notify(CrossfilterChange::Filtered(dimension));
+        true
+    }
+
+    /// Keep numeric values within `[lo, hi]` (bounds in either order)
+    pub fn filter_range(&mut self, dimension: DimensionId, lo: f64, hi: f64) -> bool {
+        self.set_filter(dimension, DimensionFilter::Range(lo.min(hi), lo.max(hi)))
+    }
+
+    /// Keep a domain range, or clear the filter for None
+    ///
+    /// Fits [`DomainSelection`](crate::interaction::DomainSelection) axes.
+    pub fn filter_domain(&mut self, dimension: DimensionId, range: Option<(f64, f64)>) -> bool {
+        match range {
+            Some((lo, hi)) => self.filter_range(dimension, lo, hi),
+            None => self.filter_all(dimension),
+        }
+    }
+
+    /// Keep the categories in `categories`
+    pub fn filter_categories<I, S>(&mut self, dimension: DimensionId, categories: I) -> bool
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let set = categories.into_iter().map(Into::into).collect();
+        self.set_filter(dimension, DimensionFilter::Categories(set))
+    }
+
+    /// Clear a dimension's filter
+    pub fn filter_all(&mut self, dimension: DimensionId) -> bool {
+        self.set_filter(dimension, DimensionFilter::All)
+    }
+
+    /// Clear every filter
+    pub fn clear_filters(&mut self) {
+        for d in 0..self.dimensions.len() {
+            self.filter_all(DimensionId(d));
+        }
+    }
+
+    /// Filter by the x extent of a pixel brush, or clear for None
+    ///
+    /// The extent is inverted through the chart's x scale.
+    pub fn filter_brush_x(&mut self, dimension: DimensionId, selection: Option<BrushSelection>, scale: &dyn Scale) -> bool {
+        let range = selection.map(|s| (scale.invert(s.x0), scale.invert(s.x1)));
+        self.filter_domain(dimension, range)
+    }
+
+    /// Filter by the y extent of a pixel brush, or clear for None
+    pub fn filter_brush_y(&mut self, dimension: DimensionId, selection: Option<BrushSelection>, scale: &dyn Scale) -> bool {
+        let range = selection.map(|s| (scale.invert(s.y0), scale.invert(s.y1)));
+        self.filter_domain(dimension, range)
+    }
+
+    /// Keep the categories of visible legend items
+    ///
+    /// With every item visible the filter is cleared, so categories missing
+    /// from the legend are not dropped.
+    pub fn filter_legend(&mut self, dimension: DimensionId, legend: &Legend) -> bool {
+        if legend.items.iter().all(|item| item.visible) {
+            return self.filter_all(dimension);
+        }
+        let visible = legend.items.iter().filter(|item| item.visible).map(|item| item.label.clone());
+        self.filter_categories(dimension, visible)
+    }
+
+    /// Keep the categories selected in a [`CategoryFilter`]
+    ///
+    /// With every category selected the filter is cleared.
+    pub fn filter_category_filter(&mut self, dimension: DimensionId, filter: &CategoryFilter) -> bool {
+        if filter.is_all_selected() {
+            return self.filter_all(dimension);
+        }
+        self.filter_categories(dimension, filter.selected())
+    }
+
+    /// Check if a record passes every filter
+    pub fn is_filtered_in(&self, index: usize) -> bool {
+        self.fails.get(index) == Some(&0)
+    }
+
+    /// Indices of the records passing every filter
+    pub fn filtered_indices(&self) -> Vec<usize> {
+        self.fails.iter().enumerate().filter(|(_, &f)| f == 0).map(|(i, _)| i).collect()
+    }
+
+    /// Records passing every filter
+    pub fn filtered(&self) -> Vec<&R> {
+        self.records.iter().zip(&self.fails).filter(|(_, &f)| f == 0).map(|(r, _)| r).collect()
+    }
+
+    /// Number of records passing every filter
+    pub fn filtered_len(&self) -> usize {
+        self.fails.iter().filter(|&&f| f == 0).count()
+    }
+
+    /// Count of the records passing every filter and the sum of their finite `value`s
+    pub fn total(&self, value: impl Fn(&R) -> f64) -> (usize, f64) {
+        self.filtered()
+            .into_iter()
+            .map(value)
+            .fold((0, 0.0), |(count, sum), v| (count + 1, if v.is_finite() { sum + v } else { sum }))
+    }
+
+    /// Group a dimension's records by key, reducing `value`
+    ///
+    /// Only records passing every *other* dimension's filter are counted.
+    /// Every key present in the data gets a group, sorted ascending;
+    /// records with a NaN numeric value are not grouped.
+    pub fn group(&self, dimension: DimensionId, value: impl Fn(&R) -> f64) -> Vec<Group> {
+        let others = !(1u64 << dimension.0);
+        let bin = self.dimensions[dimension.0].bin;
+        let mut groups: Vec<Group> = Vec::new();
+        let mut numbers: HashMap<u64, usize> = HashMap::new();
+        let mut categories: HashMap<&str, usize> = HashMap::new();
+
+        for (i, record_value) in self.values[dimension.0].iter().enumerate() {
+            let slot = match record_value {
+                Value::Number(v) if v.is_nan() => continue,
+                Value::Number(v) => {
+                    let key = bin.map_or(*v, |w| (v / w).floor() * w);
+                    *numbers.entry(key.to_bits()).or_insert_with(|| {
+                        groups.push(Group { key: GroupKey::Number(key), count: 0, sum: 0.0 });
+                        groups.len() - 1
+                    })
+                }
+                Value::Category(c) => *categories.entry(c.as_str()).or_insert_with(|| {
+                    groups.push(Group { key: GroupKey::Category(c.clone()), count: 0, sum: 0.0 });
+                    groups.len() - 1
+                }),
+            };
+            if self.fails[i] & others == 0 {
+                let group = &mut groups[slot];
+                group.count += 1;
+                let v = value(&self.records[i]);
+                if v.is_finite() {
+                    group.sum += v;
+                }
+            }
+        }
+        groups.sort_by(|a, b| a.key.order(&b.key));
+        groups
+    }
+
+    /// Change counter; linked charts redraw when it differs from the last seen
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Get next queued change
+    pub fn poll_change(&mut self) -> Option<CrossfilterChange> {
+        self.changes.pop_front()
+    }
+
+    /// Drain all queued changes
+    pub fn drain_changes(&mut self) -> Vec<CrossfilterChange> {
+        self.changes.drain(..).collect()
+    }
+
+    fn notify(&mut self, change: CrossfilterChange) {
+        self.version += 1;
+        self.changes.push_back(change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataPoint;
+    use crate::scale::{LinearScale, ScaleExt};
+
+    fn points() -> Crossfilter<DataPoint> {
+        Crossfilter::new(vec![
+            DataPoint::new(1.0, 10.0).with_category("a"),
+            DataPoint::new(2.0, 20.0).with_category("b"),
+            DataPoint::new(3.0, f64::NAN).with_category("a"),
+            DataPoint::new(f64::NAN, 40.0).with_category("c"),
+        ])
+    }
+
+    fn dims(cf: &mut Crossfilter<DataPoint>) -> (DimensionId, DimensionId) {
+        let x = cf.add_dimension(Dimension::numeric("x", |p: &DataPoint| p.x_or(0))).unwrap();
+        let cat = cf
+            .add_dimension(Dimension::category("category", |p: &DataPoint| p.category.clone().unwrap_or_default()))
+            .unwrap();
+        (x, cat)
+    }
+
+    #[test]
+    fn test_crossfilter_groups_ignore_own_filter() {
+        let mut cf = points();
+        let (x, cat) = dims(&mut cf);
+        assert_eq!(cf.dimension("category"), Some(cat));
+
+        cf.filter_range(x, 2.5, 0.5);
+        assert_eq!(cf.filtered_indices(), vec![0, 1]);
+
+        let by_cat = cf.group(cat, |p| p.y);
+        let summary: Vec<_> = by_cat.iter().map(|g| (g.key.clone(), g.count, g.sum)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (GroupKey::Category("a".into()), 1, 10.0),
+                (GroupKey::Category("b".into()), 1, 20.0),
+                (GroupKey::Category("c".into()), 0, 0.0),
+            ]
+        );
+
+        // x groups see every record with a number (NaN x is not grouped)
+        cf.filter_categories(cat, ["a"]);
+        let by_x = cf.group(x, |p| p.y);
+        assert_eq!(by_x.iter().map(|g| g.count).collect::<Vec<_>>(), vec![1, 0, 1]);
+        assert_eq!((by_x[2].sum, by_x[2].mean()), (0.0, 0.0));
+        assert!(by_x[1].mean().is_nan());
+        assert_eq!(cf.filtered_len(), 1);
+
+        assert_eq!(cf.total(|p| p.y), (1, 10.0));
+
+        cf.clear_filters();
+        assert_eq!(cf.filtered_len(), 4);
+    }
+
+    #[test]
+    fn test_crossfilter_changes_and_add() {
+        let mut cf = points();
+        let (x, cat) = dims(&mut cf);
+        assert!(cf.filter_range(x, 0.0, 2.0));
+        assert!(!cf.filter_range(x, 2.0, 0.0));
+        assert_eq!(cf.version(), 1);
+
+        cf.add(vec![DataPoint::new(1.5, 5.0).with_category("d"), DataPoint::new(9.0, 1.0)]);
+        assert!(cf.is_filtered_in(4));
+        assert!(!cf.is_filtered_in(5));
+        assert_eq!(cf.group(cat, |_| 1.0).len(), 5);
+
+        assert_eq!(
+            cf.drain_changes(),
+            vec![CrossfilterChange::Filtered(x), CrossfilterChange::Added { count: 2 }]
+        );
+        cf.clear();
+        assert!(cf.is_empty());
+        assert_eq!(cf.poll_change(), Some(CrossfilterChange::Cleared));
+    }
+
+    #[test]
+    fn test_crossfilter_from_interactions() {
+        let mut cf = points();
+        let (x, cat) = dims(&mut cf);
+
+        let scale = LinearScale::new().with_domain(0.0, 4.0).with_range(0.0, 400.0);
+        cf.filter_brush_x(x, Some(BrushSelection::new(250.0, 0.0, 50.0, 100.0)), &scale);
+        assert_eq!(cf.filter(x), &DimensionFilter::Range(0.5, 2.5));
+        cf.filter_brush_x(x, None, &scale);
+        assert!(!cf.is_filtered(x));
+
+        let mut legend = Legend::new().add_item("a", crate::color::Rgba::RED).add_item("b", crate::color::Rgba::BLUE);
+        cf.filter_legend(cat, &legend);
+        assert!(!cf.is_filtered(cat));
+        legend.toggle(0);
+        cf.filter_legend(cat, &legend);
+        assert_eq!(cf.filtered_indices(), vec![1]);
+
+        let mut categories = CategoryFilter::new(["a", "b", "c"]);
+        categories.toggle("b");
+        cf.filter_category_filter(cat, &categories);
+        assert_eq!(cf.filtered_indices(), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_crossfilter_bins_and_limit() {
+        let mut cf = Crossfilter::new((0..10).map(|i| i as f64).collect::<Vec<f64>>());
+        let value = cf.add_dimension(Dimension::numeric("v", |v: &f64| *v).with_bin(4.0)).unwrap();
+        let keys: Vec<_> = cf.group(value, |v| *v).into_iter().map(|g| (g.key, g.count)).collect();
+        assert_eq!(
+            keys,
+            vec![(GroupKey::Number(0.0), 4), (GroupKey::Number(4.0), 4), (GroupKey::Number(8.0), 2)]
+        );
+
+        for i in 1..MAX_DIMENSIONS {
+            cf.add_dimension(Dimension::numeric(format!("d{}", i), |v: &f64| *v)).unwrap();
+        }
+        assert!(cf.add_dimension(Dimension::numeric("extra", |v: &f64| *v)).is_err());
+        cf.filter_range(DimensionId(MAX_DIMENSIONS - 1), 0.0, 2.0);
+        assert_eq!(cf.filtered_len(), 3);
+    }
+}
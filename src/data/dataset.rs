@@ -1,6 +1,8 @@
 //! Dataset representation
 
-use super::DataPoint;
+use super::{DataPoint, FillStrategy, Marker, MarkerLayout, ValidityMask};
+use crate::axis::NumberFormat;
+use crate::shape::curve::CurveKind;
 use serde::{Deserialize, Serialize};
 
 /// Point marker styles for scatter/line charts
@@ -62,6 +64,120 @@ impl Color {
     pub const TRANSPARENT: Color = Color { r: 0.0, g: 0.0, b: 0.0, a: 0.0 };
 }
 
+/// Identifier of the y-axis used by datasets without an explicit binding
+pub const DEFAULT_Y_AXIS: &str = "y";
+
+/// Descriptive metadata for a series
+///
+/// Declared once on the [`Dataset`], it feeds axis titles, tooltip values,
+/// legend descriptions and the text summary read by screen readers.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeriesMeta {
+    /// Unit of the values (e.g. "USD", "°C", "req/s")
+    pub unit: Option<String>,
+    /// Longer description of what the series measures
+    pub description: Option<String>,
+    /// Where the data comes from (name or URL)
+    pub source: Option<String>,
+    /// Decimal places to show for values
+    pub precision: Option<usize>,
+}
+
+impl SeriesMeta {
+    /// Number format implied by the precision hint
+    pub fn number_format(&self) -> NumberFormat {
+        match self.precision {
+            Some(decimals) => NumberFormat::Fixed(decimals),
+            None => NumberFormat::Auto,
+        }
+    }
+
+    /// Format a value with the precision hint and unit, e.g. "12.5 kg"
+    pub fn format_value(&self, value: f64) -> String {
+        let number = self.number_format().format(value);
+        match self.unit.as_deref() {
+            Some(unit) if !unit.is_empty() => format!("{} {}", number, unit),
+            _ => number,
+        }
+    }
+}
+
+/// Per-series drawing style
+///
+/// Lets multi-series charts mix smooth, stepped and dashed series
+/// declaratively. Unset fields fall back to the older flat options on
+/// [`Dataset`] (`tension`, `border_width`, `point_style`).
+///
+/// # Example
+/// ```
+/// use makepad_d3::data::{Dataset, PointStyle, SeriesStyle};
+/// use makepad_d3::shape::curve::CurveKind;
+///
+/// let forecast = Dataset::new("Forecast").with_style(
+///     SeriesStyle::new()
+///         .with_curve(CurveKind::Monotone)
+///         .with_dash(vec![6.0, 4.0])
+///         .with_marker(PointStyle::None),
+/// );
+/// assert_eq!(forecast.curve(), CurveKind::Monotone);
+/// assert_eq!(forecast.stroke_width(), 2.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeriesStyle {
+    /// Curve connecting the points (None = derived from `tension`)
+    pub curve: Option<CurveKind>,
+    /// Line width in pixels (None = `border_width`)
+    pub stroke_width: Option<f64>,
+    /// Dash pattern as alternating dash/gap lengths (empty = solid)
+    pub dash: Vec<f64>,
+    /// Point marker symbol (None = `point_style`)
+    pub marker: Option<PointStyle>,
+    /// Opacity of the area fill, 0-1 (None = opaque)
+    pub fill_opacity: Option<f64>,
+}
+
+impl SeriesStyle {
+    /// Create a style that defers everything to the dataset defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the curve
+    pub fn with_curve(mut self, curve: CurveKind) -> Self {
+        self.curve = Some(curve);
+        self
+    }
+
+    /// Set the line width
+    pub fn with_stroke_width(mut self, width: f64) -> Self {
+        self.stroke_width = Some(width.max(0.0));
+        self
+    }
+
+    /// Set the dash pattern
+    pub fn with_dash(mut self, pattern: impl Into<Vec<f64>>) -> Self {
+        self.dash = pattern.into();
+        self
+    }
+
+    /// Set the point marker symbol
+    pub fn with_marker(mut self, marker: PointStyle) -> Self {
+        self.marker = Some(marker);
+        self
+    }
+
+    /// Set the fill opacity
+    pub fn with_fill_opacity(mut self, opacity: f64) -> Self {
+        self.fill_opacity = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Whether the line is drawn dashed
+    pub fn is_dashed(&self) -> bool {
+        self.dash.iter().any(|&d| d > 0.0)
+    }
+}
+
 /// A dataset containing multiple data points with styling
 ///
 /// # Example
@@ -107,12 +223,32 @@ pub struct Dataset {
     /// Point style
     pub point_style: PointStyle,
 
+    /// Image or glyph drawn instead of the point style
+    #[serde(default)]
+    pub point_marker: Option<Marker>,
+
     // Bar chart options
     /// Bar width as fraction of category width (0-1)
     pub bar_percent: f64,
 
     /// Bar border radius
     pub bar_radius: f64,
+
+    // Axis binding
+    /// Y-axis this dataset is plotted against (None = default axis)
+    pub y_axis: Option<String>,
+
+    /// Unit, description, source and precision hints
+    #[serde(default)]
+    pub meta: SeriesMeta,
+
+    /// Explicitly missing entries (None = only non-finite values are missing)
+    #[serde(default)]
+    pub mask: Option<ValidityMask>,
+
+    /// Curve, stroke, dash, marker and fill overrides
+    #[serde(default)]
+    pub style: SeriesStyle,
 }
 
 impl Dataset {
@@ -147,6 +283,80 @@ impl Dataset {
         self
     }
 
+    /// Set data from optional y values, masking the `None` entries
+    ///
+    /// Missing entries hold `NaN` so code that ignores the mask still
+    /// skips them.
+    pub fn with_optional_data(mut self, data: impl IntoIterator<Item = Option<f64>>) -> Self {
+        let (points, flags): (Vec<DataPoint>, Vec<bool>) = data
+            .into_iter()
+            .map(|y| (DataPoint::from_y(y.unwrap_or(f64::NAN)), y.is_some()))
+            .unzip();
+        self.data = points;
+        self.mask = Some(ValidityMask::from_flags(flags));
+        self
+    }
+
+    /// Set the validity mask
+    pub fn with_mask(mut self, mask: ValidityMask) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Mark entry `index` as missing
+    pub fn set_missing(&mut self, index: usize) {
+        let len = self.data.len();
+        self.mask.get_or_insert_with(|| ValidityMask::all_valid(len)).set(index, false);
+    }
+
+    /// Mark entry `index` as present
+    pub fn set_present(&mut self, index: usize) {
+        if let Some(mask) = &mut self.mask {
+            mask.set(index, true);
+        }
+    }
+
+    /// Whether entry `index` exists, is not masked out and is finite
+    pub fn is_valid(&self, index: usize) -> bool {
+        self.data.get(index).is_some_and(|p| p.is_valid())
+            && self.mask.as_ref().map_or(true, |m| m.get(index))
+    }
+
+    /// Number of entries that are masked out or non-finite
+    pub fn count_missing(&self) -> usize {
+        (0..self.data.len()).filter(|&i| !self.is_valid(i)).count()
+    }
+
+    /// Y value of entry `index`, or None if it is missing
+    pub fn value(&self, index: usize) -> Option<f64> {
+        self.is_valid(index).then(|| self.data[index].y)
+    }
+
+    /// Y values with missing entries as None
+    pub fn values(&self) -> Vec<Option<f64>> {
+        (0..self.data.len()).map(|i| self.value(i)).collect()
+    }
+
+    /// Present entries with their indices
+    pub fn valid_points(&self) -> impl Iterator<Item = (usize, &DataPoint)> + '_ {
+        self.data.iter().enumerate().filter(move |(i, _)| self.is_valid(*i))
+    }
+
+    /// Copy with missing y values replaced according to `strategy`
+    ///
+    /// Filled entries become present; entries the strategy cannot fill
+    /// (such as leading gaps with [`FillStrategy::Previous`]) stay missing.
+    pub fn fill_missing(&self, strategy: FillStrategy) -> Self {
+        let xs: Vec<f64> = self.data.iter().enumerate().map(|(i, p)| p.x_or(i)).collect();
+        let filled = strategy.fill(&self.values(), Some(&xs));
+        let mut out = self.clone();
+        for (i, value) in filled.into_iter().enumerate() {
+            out.data[i].y = value.unwrap_or(f64::NAN);
+        }
+        out.mask = Some(ValidityMask::from_flags((0..out.data.len()).map(|i| out.data[i].y.is_finite())));
+        out
+    }
+
     /// Set background color
     pub fn with_color(mut self, color: Color) -> Self {
         self.background_color = Some(color);
@@ -195,6 +405,60 @@ impl Dataset {
         self
     }
 
+    /// Draw points as an image or glyph marker instead of the point style
+    pub fn with_point_marker(mut self, marker: Marker) -> Self {
+        self.point_marker = Some(marker);
+        self
+    }
+
+    /// Layout of the point marker anchored at a point's screen position
+    pub fn marker_layout(&self, x: f64, y: f64) -> Option<MarkerLayout> {
+        self.point_marker.as_ref().map(|marker| marker.layout(x, y))
+    }
+
+    /// Set the curve, stroke, dash, marker and fill style
+    pub fn with_style(mut self, style: SeriesStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the curve connecting the points
+    pub fn with_curve(mut self, curve: CurveKind) -> Self {
+        self.style.curve = Some(curve);
+        self
+    }
+
+    /// Set the line dash pattern
+    pub fn with_dash(mut self, pattern: impl Into<Vec<f64>>) -> Self {
+        self.style.dash = pattern.into();
+        self
+    }
+
+    /// Curve to draw with: the style's curve, else one matching `tension`
+    pub fn curve(&self) -> CurveKind {
+        self.style.curve.unwrap_or_else(|| CurveKind::from_tension(self.tension))
+    }
+
+    /// Line width to draw with
+    pub fn stroke_width(&self) -> f64 {
+        self.style.stroke_width.unwrap_or(self.border_width)
+    }
+
+    /// Dash pattern to draw with (empty = solid)
+    pub fn dash(&self) -> &[f64] {
+        &self.style.dash
+    }
+
+    /// Point marker symbol to draw with
+    pub fn marker_style(&self) -> PointStyle {
+        self.style.marker.unwrap_or(self.point_style)
+    }
+
+    /// Opacity of the area fill, or None if the area is not filled
+    pub fn fill_opacity(&self) -> Option<f64> {
+        self.fill.then(|| self.style.fill_opacity.unwrap_or(1.0))
+    }
+
     /// Set hidden state
     pub fn with_hidden(mut self, hidden: bool) -> Self {
         self.hidden = hidden;
@@ -207,6 +471,95 @@ impl Dataset {
         self
     }
 
+    /// Bind this dataset to a y-axis (e.g. "left", "right")
+    pub fn with_y_axis(mut self, axis_id: impl Into<String>) -> Self {
+        self.y_axis = Some(axis_id.into());
+        self
+    }
+
+    /// Get the y-axis id this dataset is bound to
+    pub fn y_axis_id(&self) -> &str {
+        self.y_axis.as_deref().unwrap_or(DEFAULT_Y_AXIS)
+    }
+
+    /// Set the unit of the values
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.meta.unit = Some(unit.into());
+        self
+    }
+
+    /// Set a longer description of the series
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.meta.description = Some(description.into());
+        self
+    }
+
+    /// Set the data source (name or URL)
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.meta.source = Some(source.into());
+        self
+    }
+
+    /// Set the number of decimal places to show for values
+    pub fn with_precision(mut self, decimals: usize) -> Self {
+        self.meta.precision = Some(decimals);
+        self
+    }
+
+    /// Format a value of this series for display, e.g. "12.50 USD"
+    pub fn format_value(&self, value: f64) -> String {
+        self.meta.format_value(value)
+    }
+
+    /// Axis title for this series: the label with its unit, e.g. "Revenue (USD)"
+    pub fn axis_title(&self) -> String {
+        match self.meta.unit.as_deref() {
+            Some(unit) if !unit.is_empty() => format!("{} ({})", self.label, unit),
+            _ => self.label.clone(),
+        }
+    }
+
+    /// Plain-text summary for screen readers
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::data::Dataset;
+    ///
+    /// let ds = Dataset::new("Revenue")
+    ///     .with_data(vec![10.0, 40.0, 25.0])
+    ///     .with_unit("USD")
+    ///     .with_precision(0)
+    ///     .with_source("Finance DB");
+    /// assert_eq!(
+    ///     ds.summary(),
+    ///     "Revenue: 3 values from 10 USD to 40 USD, last 25 USD. Source: Finance DB."
+    /// );
+    /// ```
+    pub fn summary(&self) -> String {
+        let finite: Vec<f64> = self.values().into_iter().flatten().collect();
+        let mut text = match (self.y_extent(), finite.last()) {
+            (Some((min, max)), Some(&last)) => format!(
+                "{}: {} value{} from {} to {}, last {}.",
+                self.label,
+                finite.len(),
+                if finite.len() == 1 { "" } else { "s" },
+                self.format_value(min),
+                self.format_value(max),
+                self.format_value(last)
+            ),
+            _ => format!("{}: no data.", self.label),
+        };
+        if let Some(description) = &self.meta.description {
+            text.push(' ');
+            text.push_str(description.trim_end_matches('.'));
+            text.push('.');
+        }
+        if let Some(source) = &self.meta.source {
+            text.push_str(&format!(" Source: {}.", source));
+        }
+        text
+    }
+
     /// Get number of data points
     pub fn len(&self) -> usize {
         self.data.len()
@@ -226,10 +579,7 @@ impl Dataset {
         let mut min = f64::MAX;
         let mut max = f64::MIN;
 
-        for point in &self.data {
-            if !point.y.is_finite() {
-                continue;
-            }
+        for (_, point) in self.valid_points() {
             if let Some(y_min) = point.y_min {
                 if y_min.is_finite() {
                     min = min.min(y_min);
@@ -255,11 +605,8 @@ impl Dataset {
         let mut min = f64::MAX;
         let mut max = f64::MIN;
 
-        for (i, point) in self.data.iter().enumerate() {
+        for (i, point) in self.valid_points() {
             let x = point.x_or(i);
-            if !x.is_finite() {
-                continue;
-            }
             min = min.min(x);
             max = max.max(x);
         }
@@ -270,12 +617,99 @@ impl Dataset {
             Some((min, max))
         }
     }
+
+    /// Get size value extent (min, max), for sizing a bubble scale
+    pub fn size_extent(&self) -> Option<(f64, f64)> {
+        self.value_extent(|p| p.size)
+    }
+
+    /// Get color value extent (min, max), for a sequential color scale
+    pub fn color_extent(&self) -> Option<(f64, f64)> {
+        self.value_extent(|p| p.color_value)
+    }
+
+    /// Distinct point categories in first-seen order
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = Vec::new();
+        for point in &self.data {
+            if let Some(category) = point.category.as_deref() {
+                if !categories.contains(&category) {
+                    categories.push(category);
+                }
+            }
+        }
+        categories
+    }
+
+    fn value_extent(&self, value: impl Fn(&DataPoint) -> Option<f64>) -> Option<(f64, f64)> {
+        self.valid_points()
+            .filter_map(|(_, point)| value(point))
+            .filter(|v| v.is_finite())
+            .fold(None, |extent, v| match extent {
+                Some((min, max)) => Some((v.min(min), v.max(max))),
+                None => Some((v, v)),
+            })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dimension_extents() {
+        let dataset = Dataset::new("Countries").with_points(vec![
+            DataPoint::new(1.0, 2.0).with_size(30.0).with_category("EU"),
+            DataPoint::new(2.0, 3.0).with_size(10.0).with_color_value(0.5).with_category("AS"),
+            DataPoint::new(3.0, 1.0).with_size(f64::NAN).with_category("EU"),
+            DataPoint::new(f64::NAN, 1.0).with_size(99.0),
+        ]);
+        assert_eq!(dataset.size_extent(), Some((10.0, 30.0)));
+        assert_eq!(dataset.color_extent(), Some((0.5, 0.5)));
+        assert_eq!(dataset.categories(), vec!["EU", "AS"]);
+        assert_eq!(Dataset::new("Empty").size_extent(), None);
+    }
+
+    #[test]
+    fn test_point_marker() {
+        use crate::data::MarkerAnchor;
+
+        let plain = Dataset::new("Plain");
+        assert!(plain.marker_layout(0.0, 0.0).is_none());
+
+        let emoji = Dataset::new("Rockets").with_point_marker(Marker::glyph("🚀").with_size(16.0));
+        let layout = emoji.marker_layout(100.0, 50.0).unwrap();
+        assert_eq!((layout.x, layout.y, layout.font_size), (92.0, 42.0, 16.0));
+
+        let pin = emoji.with_point_marker(Marker::image(1, 1.0).with_anchor(MarkerAnchor::Bottom));
+        assert_eq!(pin.marker_layout(0.0, 0.0).unwrap().y, -12.0);
+    }
+
+    #[test]
+    fn test_dataset_meta_formatting() {
+        let ds = Dataset::new("Revenue").with_unit("USD").with_precision(2);
+        assert_eq!(ds.format_value(12.5), "12.50 USD");
+        assert_eq!(ds.axis_title(), "Revenue (USD)");
+
+        let plain = Dataset::new("Count");
+        assert_eq!(plain.axis_title(), "Count");
+        assert_eq!(plain.meta, SeriesMeta::default());
+    }
+
+    #[test]
+    fn test_dataset_summary() {
+        let ds = Dataset::new("Temp")
+            .with_data(vec![3.0])
+            .with_precision(0)
+            .with_description("Daily high.")
+            .with_source("https://example.com/weather");
+        assert_eq!(
+            ds.summary(),
+            "Temp: 1 value from 3 to 3, last 3. Daily high. Source: https://example.com/weather."
+        );
+        assert_eq!(Dataset::new("Empty").summary(), "Empty: no data.");
+    }
+
     #[test]
     fn test_dataset_new() {
         let ds = Dataset::new("Test");
@@ -357,6 +791,56 @@ mod tests {
         assert!((ds.point_radius - 5.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_dataset_y_axis() {
+        let ds = Dataset::new("Revenue");
+        assert_eq!(ds.y_axis_id(), DEFAULT_Y_AXIS);
+
+        let ds = ds.with_y_axis("right");
+        assert_eq!(ds.y_axis_id(), "right");
+    }
+
+    #[test]
+    fn test_dataset_missing_values() {
+        let mut ds = Dataset::new("Sales").with_data(vec![0.0, 100.0, 5.0]).with_precision(0);
+        assert_eq!(ds.count_missing(), 0);
+        ds.set_missing(1);
+        assert_eq!(ds.values(), vec![Some(0.0), None, Some(5.0)]);
+        assert_eq!(ds.y_extent(), Some((0.0, 5.0)));
+        assert_eq!(ds.summary(), "Sales: 2 values from 0 to 5, last 5.");
+        ds.set_present(1);
+        assert_eq!(ds.y_extent(), Some((0.0, 100.0)));
+
+        let filled = Dataset::new("Gaps")
+            .with_optional_data(vec![None, Some(2.0), None])
+            .fill_missing(FillStrategy::Previous);
+        assert_eq!(filled.values(), vec![None, Some(2.0), Some(2.0)]);
+        assert_eq!(filled.x_extent(), Some((1.0, 2.0)));
+    }
+
+    #[test]
+    fn test_series_style_fallbacks() {
+        let plain = Dataset::new("Plain").with_tension(0.4).with_point_style(PointStyle::Square);
+        assert_eq!(plain.curve(), CurveKind::Cardinal { tension: 0.6 });
+        assert_eq!(plain.stroke_width(), 2.0);
+        assert_eq!(plain.marker_style(), PointStyle::Square);
+        assert!(plain.dash().is_empty());
+        assert_eq!(plain.fill_opacity(), None);
+        assert_eq!(Dataset::new("Straight").curve(), CurveKind::Linear);
+
+        let styled = plain
+            .with_style(SeriesStyle::new().with_stroke_width(1.5).with_fill_opacity(0.25).with_marker(PointStyle::None))
+            .with_curve(CurveKind::StepAfter)
+            .with_dash(vec![4.0, 2.0])
+            .with_fill(true);
+        assert_eq!(styled.curve(), CurveKind::StepAfter);
+        assert_eq!(styled.stroke_width(), 1.5);
+        assert_eq!(styled.marker_style(), PointStyle::None);
+        assert_eq!(styled.dash(), &[4.0, 2.0]);
+        assert!(styled.style.is_dashed());
+        assert_eq!(styled.fill_opacity(), Some(0.25));
+    }
+
     #[test]
     fn test_dataset_empty_extent() {
         let ds = Dataset::new("Empty");
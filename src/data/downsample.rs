@@ -0,0 +1,125 @@
+//! Shape-preserving downsampling for line charts
+//!
+//! [`lttb`] implements Largest-Triangle-Three-Buckets (Steinarsson, 2013):
+//! the series is split into equal buckets and each bucket keeps the point
+//! forming the largest triangle with the previously kept point and the
+//! average of the next bucket. Peaks and troughs survive, which plain
+//! every-nth sampling loses, so a million-point series can be reduced to
+//! roughly one point per pixel before scaling and rendering.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::{lttb, DataPoint};
+//!
+//! let data: Vec<DataPoint> = (0..1_000)
+//!     .map(|i| DataPoint::new(i as f64, if i == 500 { 100.0 } else { 0.0 }))
+//!     .collect();
+//!
+//! let reduced = lttb(&data, 20);
+//! assert_eq!(reduced.len(), 20);
+//! // The spike is kept
+//! assert!(reduced.iter().any(|p| p.y == 100.0));
+//! ```
+
+use super::DataPoint;
+
+/// Reduce `data` to at most `threshold` points with Largest-Triangle-Three-Buckets
+///
+/// X values come from `x` (or the index when `x` is None) and must be
+/// sorted. The first and last points are always kept. Points with a
+/// non-finite value are dropped first. A `threshold` of 0 or at least the
+/// number of points returns the valid points unchanged.
+pub fn lttb(data: &[DataPoint], threshold: usize) -> Vec<DataPoint> {
+    let points: Vec<(f64, &DataPoint)> = data
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (p.x_or(i), p))
+        .filter(|(x, p)| x.is_finite() && p.y.is_finite())
+        .collect();
+    let n = points.len();
+    if threshold == 0 || threshold >= n {
+        return points.into_iter().map(|(_, p)| p.clone()).collect();
+    }
+    if threshold < 3 {
+        return [points[0], points[n - 1]][..threshold]
+            .iter()
+            .map(|(_, p)| (*p).clone())
+            .collect();
+    }
+
+    // Interior points split into threshold - 2 buckets
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+    let bucket_start = |b: usize| ((b as f64 * every) as usize + 1).min(n - 1);
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(points[0].1.clone());
+    let mut a = 0;
+    for b in 0..threshold - 2 {
+        // Average of the next bucket (the last point for the final bucket)
+        let next_start = bucket_start(b + 1);
+        let next_end = bucket_start(b + 2).max(next_start + 1).min(n);
+        let next = &points[next_start..next_end];
+        let count = next.len() as f64;
+        let avg_x = next.iter().map(|(x, _)| x).sum::<f64>() / count;
+        let avg_y = next.iter().map(|(_, p)| p.y).sum::<f64>() / count;
+
+        let (ax, ay) = (points[a].0, points[a].1.y);
+        let mut best = (bucket_start(b), f64::NEG_INFINITY);
+        for (i, &(x, p)) in points.iter().enumerate().take(next_start).skip(bucket_start(b)) {
+            // Twice the triangle area; the factor does not change the maximum
+            let area = ((ax - avg_x) * (p.y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > best.1 {
+                best = (i, area);
+            }
+        }
+        a = best.0;
+        sampled.push(points[a].1.clone());
+    }
+    sampled.push(points[n - 1].1.clone());
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lttb_keeps_extremes() {
+        let data: Vec<DataPoint> = (0..101)
+            .map(|i| {
+                let x = i as f64;
+                DataPoint::new(x, (x / 10.0).sin() * 10.0)
+            })
+            .collect();
+        let reduced = lttb(&data, 12);
+        assert_eq!(reduced.len(), 12);
+        assert_eq!(reduced[0], data[0]);
+        assert_eq!(reduced[11], data[100]);
+        // Strictly increasing x: one point per bucket, in order
+        assert!(reduced.windows(2).all(|w| w[0].x < w[1].x));
+
+        // Peak and trough of the sine survive
+        let max = reduced.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max);
+        let min = reduced.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+        assert!(max > 9.9 && min < -9.9);
+    }
+
+    #[test]
+    fn test_lttb_small_thresholds() {
+        let data: Vec<DataPoint> = [1.0, f64::NAN, 3.0, 2.0, 5.0]
+            .into_iter()
+            .map(DataPoint::from_y)
+            .collect();
+        assert_eq!(lttb(&data, 0).len(), 4);
+        assert_eq!(lttb(&data, 4).len(), 4);
+        let ends = lttb(&data, 2);
+        assert_eq!((ends[0].y, ends[1].y), (1.0, 5.0));
+        assert_eq!(lttb(&data, 1).len(), 1);
+        assert!(lttb(&[], 10).is_empty());
+
+        // Three points: the middle bucket keeps the biggest deviation
+        let mid = lttb(&data, 3);
+        assert_eq!(mid.iter().map(|p| p.y).collect::<Vec<_>>(), vec![1.0, 2.0, 5.0]);
+    }
+}
@@ -0,0 +1,586 @@
+//! Delimiter-separated values (CSV/TSV) parsing and formatting
+//!
+//! A port of the parts of d3-dsv that charts need: [`from_csv_str`] and
+//! [`from_tsv_str`] read text with a header row into a [`DsvTable`],
+//! inferring numbers, booleans and ISO 8601 dates per cell like
+//! `d3.autoType`. Tables convert to [`ChartData`] or [`Dataset`]s, and
+//! format back to text for export.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::{from_csv_str, DsvValue};
+//!
+//! let csv = "year,coal,\"gas, natural\"\n2019,120.5,80\n2020,98,\n";
+//! let table = from_csv_str(csv).unwrap();
+//!
+//! assert_eq!(table.columns, vec!["year", "coal", "gas, natural"]);
+//! assert_eq!(table.get(0, "coal"), Some(&DsvValue::Number(120.5)));
+//! assert_eq!(table.get(1, "gas, natural"), Some(&DsvValue::Empty));
+//!
+//! let chart = table.to_chart_data("year").unwrap();
+//! assert_eq!(chart.labels, vec!["2019", "2020"]);
+//! assert_eq!(chart.datasets.len(), 2);
+//!
+//! // Round-trip back to text
+//! assert_eq!(from_csv_str(&table.to_csv_string()).unwrap(), table);
+//! ```
+
+use std::fmt;
+
+use super::{ChartData, Dataset};
+use crate::axis::{parse_number, NumberLocale};
+use crate::error::{D3Error, D3Result};
+
+/// A typed cell value
+#[derive(Clone, Debug, PartialEq)]
+pub enum DsvValue {
+    /// Empty cell
+    Empty,
+    /// Number
+    Number(f64),
+    /// `true` or `false`
+    Bool(bool),
+    /// ISO 8601 date, as milliseconds since the Unix epoch (UTC)
+    Date(f64),
+    /// Anything else
+    Text(String),
+}
+
+impl DsvValue {
+    /// Infer the type of a raw cell, like `d3.autoType`
+    ///
+    /// With a locale, formatted numbers such as `"3,400"` or `"45%"` are
+    /// recognized too.
+    pub fn infer(raw: &str, locale: Option<&NumberLocale>) -> Self {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return DsvValue::Empty;
+        }
+        match trimmed {
+            "true" => return DsvValue::Bool(true),
+            "false" => return DsvValue::Bool(false),
+            "NaN" => return DsvValue::Number(f64::NAN),
+            _ => {}
+        }
+        if let Ok(v) = trimmed.parse::<f64>() {
+            if v.is_finite() {
+                return DsvValue::Number(v);
+            }
+        }
+        if let Some(ms) = parse_iso_date(trimmed) {
+            return DsvValue::Date(ms);
+        }
+        if let Some(locale) = locale {
+            if let Ok(v) = parse_number(trimmed, locale) {
+                return DsvValue::Number(v);
+            }
+        }
+        DsvValue::Text(raw.to_string())
+    }
+
+    /// Numeric value of numbers and dates
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            DsvValue::Number(v) | DsvValue::Date(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Text of a text cell
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DsvValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Check if the cell is empty
+    pub fn is_empty(&self) -> bool {
+        matches!(self, DsvValue::Empty)
+    }
+}
+
+impl fmt::Display for DsvValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DsvValue::Empty => Ok(()),
+            DsvValue::Number(v) => write!(f, "{}", v),
+            DsvValue::Bool(b) => write!(f, "{}", b),
+            DsvValue::Date(ms) => f.write_str(&format_iso_date(*ms)),
+            DsvValue::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+/// A parsed table with a header row
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DsvTable {
+    /// Column names from the header row
+    pub columns: Vec<String>,
+    /// Rows, each with one value per column
+    pub rows: Vec<Vec<DsvValue>>,
+}
+
+impl DsvTable {
+    /// Create an empty table with the given columns
+    pub fn new<S: Into<String>>(columns: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Build a table from chart data: a label column, then one column per dataset
+    pub fn from_chart_data(data: &ChartData, label_column: &str) -> Self {
+        let mut table = Self::new(
+            std::iter::once(label_column.to_string()).chain(data.datasets.iter().map(|d| d.label.clone())),
+        );
+        for i in 0..data.max_points().max(data.labels.len()) {
+            let mut row = vec![data.labels.get(i).map_or(DsvValue::Empty, |l| DsvValue::Text(l.clone()))];
+            row.extend(data.datasets.iter().map(|d| match d.data.get(i) {
+                Some(p) if p.y.is_finite() => DsvValue::Number(p.y),
+                _ => DsvValue::Empty,
+            }));
+            table.rows.push(row);
+        }
+        table
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if the table has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Index of a column by name
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == name)
+    }
+
+    /// Get a cell by row index and column name
+    pub fn get(&self, row: usize, column: &str) -> Option<&DsvValue> {
+        let col = self.column_index(column)?;
+        self.rows.get(row)?.get(col)
+    }
+
+    /// Values of one column
+    pub fn column(&self, name: &str) -> Option<Vec<&DsvValue>> {
+        let col = self.column_index(name)?;
+        Some(self.rows.iter().map(|row| &row[col]).collect())
+    }
+
+    /// Check if every non-empty cell of a column is a number
+    pub fn is_numeric(&self, name: &str) -> bool {
+        self.column(name).is_some_and(|values| {
+            values.iter().any(|v| !v.is_empty())
+                && values.iter().all(|v| v.is_empty() || matches!(v, DsvValue::Number(_)))
+        })
+    }
+
+    /// Convert to chart data: labels from one column, a dataset per numeric column
+    ///
+    /// Empty cells become NaN, which charts skip. Non-numeric columns are
+    /// left out.
+    pub fn to_chart_data(&self, label_column: &str) -> D3Result<ChartData> {
+        let label_col = self
+            .column_index(label_column)
+            .ok_or_else(|| D3Error::invalid_data(format!("no column named {:?}", label_column)))?;
+        let labels: Vec<String> = self.rows.iter().map(|row| row[label_col].to_string()).collect();
+
+        let datasets = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|&(i, name)| i != label_col && self.is_numeric(name))
+            .map(|(i, name)| {
+                Dataset::new(name.clone())
+                    .with_data(self.rows.iter().map(|row| row[i].as_f64().unwrap_or(f64::NAN)))
+            })
+            .collect();
+
+        Ok(ChartData::new().with_labels(labels).with_datasets(datasets))
+    }
+
+    /// Convert two columns to an (x, y) dataset named after the y column
+    ///
+    /// Dates are used as millisecond timestamps. Rows where either value is
+    /// not numeric are skipped.
+    pub fn to_dataset(&self, x_column: &str, y_column: &str) -> D3Result<Dataset> {
+        let missing = |name: &str| D3Error::invalid_data(format!("no column named {:?}", name));
+        let x = self.column_index(x_column).ok_or_else(|| missing(x_column))?;
+        let y = self.column_index(y_column).ok_or_else(|| missing(y_column))?;
+        let points = self
+            .rows
+            .iter()
+            .filter_map(|row| Some((row[x].as_f64()?, row[y].as_f64()?)));
+        Ok(Dataset::new(y_column).with_xy_data(points))
+    }
+
+    /// Format as text with the given delimiter
+    ///
+    /// Fields containing the delimiter, quotes or line breaks are quoted.
+    pub fn format(&self, delimiter: char) -> String {
+        let mut out = String::new();
+        let mut write_row = |fields: &mut dyn Iterator<Item = String>| {
+            for (i, field) in fields.enumerate() {
+                if i > 0 {
+                    out.push(delimiter);
+                }
+                if field.contains([delimiter, '"', '\n', '\r']) {
+                    out.push('"');
+                    out.push_str(&field.replace('"', "\"\""));
+                    out.push('"');
+                } else {
+                    out.push_str(&field);
+                }
+            }
+            out.push('\n');
+        };
+        write_row(&mut self.columns.iter().cloned());
+        for row in &self.rows {
+            write_row(&mut row.iter().map(ToString::to_string));
+        }
+        out
+    }
+
+    /// Format as CSV
+    pub fn to_csv_string(&self) -> String {
+        self.format(',')
+    }
+
+    /// Format as TSV
+    pub fn to_tsv_string(&self) -> String {
+        self.format('\t')
+    }
+}
+
+/// Configurable DSV reader
+///
+/// # Example
+/// ```
+/// use makepad_d3::axis::NumberLocale;
+/// use makepad_d3::data::{DsvReader, DsvValue};
+///
+/// let table = DsvReader::new(';')
+///     .with_locale(NumberLocale::de_de())
+///     .parse("land;anteil\nDE;12,5%\n")
+///     .unwrap();
+/// assert_eq!(table.get(0, "anteil"), Some(&DsvValue::Number(0.125)));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DsvReader {
+    /// Field delimiter
+    delimiter: char,
+    /// Infer cell types (otherwise every non-empty cell is text)
+    infer_types: bool,
+    /// Locale for formatted numbers
+    locale: Option<NumberLocale>,
+}
+
+impl DsvReader {
+    /// Create a reader for the given delimiter
+    pub fn new(delimiter: char) -> Self {
+        Self {
+            delimiter,
+            infer_types: true,
+            locale: None,
+        }
+    }
+
+    /// Create a comma-separated reader
+    pub fn csv() -> Self {
+        Self::new(',')
+    }
+
+    /// Create a tab-separated reader
+    pub fn tsv() -> Self {
+        Self::new('\t')
+    }
+
+    /// Enable or disable type inference
+    pub fn with_type_inference(mut self, infer: bool) -> Self {
+        self.infer_types = infer;
+        self
+    }
+
+    /// Also recognize numbers formatted for a locale (`"1,234.5"`, `"45%"`)
+    pub fn with_locale(mut self, locale: NumberLocale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Parse text with a header row
+    ///
+    /// Short rows are padded with empty cells and extra fields are dropped.
+    /// Returns a parse error for an unterminated quoted field.
+    pub fn parse(&self, text: &str) -> D3Result<DsvTable> {
+        let mut records = self.parse_rows(text)?.into_iter();
+        let columns = match records.next() {
+            Some(header) => header,
+            None => return Ok(DsvTable::default()),
+        };
+        let rows = records
+            .map(|record| {
+                (0..columns.len())
+                    .map(|i| match record.get(i) {
+                        Some(raw) if self.infer_types => DsvValue::infer(raw, self.locale.as_ref()),
+                        Some(raw) if !raw.is_empty() => DsvValue::Text(raw.clone()),
+                        _ => DsvValue::Empty,
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(DsvTable { columns, rows })
+    }
+
+    /// Split text into records of raw fields, like `d3.dsvFormat().parseRows`
+    pub fn parse_rows(&self, text: &str) -> D3Result<Vec<Vec<String>>> {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut chars = text.chars().peekable();
+        let mut line = 1;
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if field.is_empty() => {
+                    let start = line;
+                    loop {
+                        match chars.next() {
+                            Some('"') if chars.peek() == Some(&'"') => {
+                                chars.next();
+                                field.push('"');
+                            }
+                            Some('"') => break,
+                            Some(c) => {
+                                if c == '\n' {
+                                    line += 1;
+                                }
+                                field.push(c);
+                            }
+                            None => {
+                                return Err(D3Error::parse_error(format!(
+                                    "unterminated quoted field starting on line {}",
+                                    start
+                                )))
+                            }
+                        }
+                    }
+                }
+                c if c == self.delimiter => record.push(std::mem::take(&mut field)),
+                '\r' | '\n' => {
+                    if c == '\r' && chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    line += 1;
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                c => field.push(c),
+            }
+        }
+        if !field.is_empty() || !record.is_empty() {
+            record.push(field);
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+/// Parse comma-separated text with a header row
+pub fn from_csv_str(text: &str) -> D3Result<DsvTable> {
+    DsvReader::csv().parse(text)
+}
+
+/// Parse tab-separated text with a header row
+pub fn from_tsv_str(text: &str) -> D3Result<DsvTable> {
+    DsvReader::tsv().parse(text)
+}
+
+/// Parse `YYYY-MM-DD[THH:MM[:SS[.sss]]][Z|±HH:MM]` into epoch milliseconds
+///
+/// Times without an offset are taken as UTC.
+fn parse_iso_date(s: &str) -> Option<f64> {
+    let b = s.as_bytes();
+    let num = |range: std::ops::Range<usize>| -> Option<i64> {
+        let part = s.get(range)?;
+        part.bytes().all(|c| c.is_ascii_digit()).then(|| part.parse().ok())?
+    };
+    if b.len() < 10 || b[4] != b'-' || b[7] != b'-' {
+        return None;
+    }
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut ms = days_from_civil(year, month, day) as f64 * 86_400_000.0;
+    if b.len() == 10 {
+        return Some(ms);
+    }
+
+    if !matches!(b[10], b'T' | b' ') || b.len() < 16 || b[13] != b':' {
+        return None;
+    }
+    let (hour, minute) = (num(11..13)?, num(14..16)?);
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+    ms += (hour * 3_600_000 + minute * 60_000) as f64;
+    let mut pos = 16;
+    if b.get(pos) == Some(&b':') {
+        let second = num(17..19)?;
+        if second > 59 {
+            return None;
+        }
+        ms += (second * 1000) as f64;
+        pos = 19;
+        if b.get(pos) == Some(&b'.') {
+            let end = s[pos + 1..].find(|c: char| !c.is_ascii_digit()).map_or(b.len(), |i| pos + 1 + i);
+            let fraction: f64 = s.get(pos..end)?.parse().ok()?;
+            ms += (fraction * 1000.0).round();
+            pos = end;
+        }
+    }
+    match s.get(pos..)? {
+        "" | "Z" => Some(ms),
+        offset if offset.len() == 6 && offset.as_bytes()[3] == b':' => {
+            let sign = match offset.as_bytes()[0] {
+                b'+' => 1.0,
+                b'-' => -1.0,
+                _ => return None,
+            };
+            let (oh, om) = (num(pos + 1..pos + 3)?, num(pos + 4..pos + 6)?);
+            Some(ms - sign * (oh * 3_600_000 + om * 60_000) as f64)
+        }
+        _ => None,
+    }
+}
+
+/// Format epoch milliseconds as ISO 8601, like d3-dsv's date formatting
+fn format_iso_date(ms: f64) -> String {
+    let days = (ms / 86_400_000.0).floor() as i64;
+    let rem = (ms - days as f64 * 86_400_000.0).round() as i64;
+    let (year, month, day) = civil_from_days(days);
+    if rem == 0 {
+        return format!("{:04}-{:02}-{:02}", year, month, day);
+    }
+    let (h, m, s, milli) = (rem / 3_600_000, rem / 60_000 % 60, rem / 1000 % 60, rem % 1000);
+    if milli != 0 {
+        format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z", year, month, day, h, m, s, milli)
+    } else if s != 0 {
+        format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, h, m, s)
+    } else {
+        format!("{:04}-{:02}-{:02}T{:02}:{:02}Z", year, month, day, h, m)
+    }
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quoting_and_line_endings() {
+        let text = "name,note\r\n\"Smith, J\",\"said \"\"hi\"\"\nthen left\"\r\nDoe,\n";
+        let rows = DsvReader::csv().parse_rows(text).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1], vec!["Smith, J", "said \"hi\"\nthen left"]);
+        assert_eq!(rows[2], vec!["Doe", ""]);
+
+        let err = DsvReader::csv().parse_rows("a\n\"open").unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_type_inference() {
+        let infer = |s| DsvValue::infer(s, None);
+        assert_eq!(infer(""), DsvValue::Empty);
+        assert_eq!(infer(" 42 "), DsvValue::Number(42.0));
+        assert_eq!(infer("-1.5e3"), DsvValue::Number(-1500.0));
+        assert_eq!(infer("true"), DsvValue::Bool(true));
+        assert_eq!(infer("2020-03-01"), DsvValue::Date(1_583_020_800_000.0));
+        assert_eq!(infer("2020-03-01T12:30Z"), DsvValue::Date(1_583_065_800_000.0));
+        assert_eq!(infer("2020-03-01T14:30:00+02:00"), DsvValue::Date(1_583_065_800_000.0));
+        assert_eq!(infer("2020-02-30"), DsvValue::Text("2020-02-30".into()));
+        assert_eq!(infer("3,400"), DsvValue::Text("3,400".into()));
+        assert_eq!(DsvValue::infer("3,400", Some(&NumberLocale::en_us())), DsvValue::Number(3400.0));
+        assert!(matches!(infer("NaN"), DsvValue::Number(v) if v.is_nan()));
+
+        let raw = DsvReader::tsv().with_type_inference(false).parse("a\tb\n1\t\n").unwrap();
+        assert_eq!(raw.rows[0], vec![DsvValue::Text("1".into()), DsvValue::Empty]);
+    }
+
+    #[test]
+    fn test_dates_round_trip() {
+        for s in ["1969-12-31", "2024-02-29", "2024-02-29T23:59Z", "2024-02-29T23:59:07Z", "2000-01-01T00:00:00.250Z"] {
+            let ms = parse_iso_date(s).unwrap();
+            assert_eq!(format_iso_date(ms), s);
+        }
+    }
+
+    #[test]
+    fn test_tsv_to_dataset_and_ragged_rows() {
+        let table = from_tsv_str("date\tvalue\textra\n2024-01-01\t5\n2024-01-02\tx\t1\t2\n2024-01-03\t7\t\n").unwrap();
+        assert_eq!(table.len(), 3);
+        assert!(table.rows.iter().all(|r| r.len() == 3));
+        assert!(!table.is_numeric("value"));
+
+        let dataset = table.to_dataset("date", "value").unwrap();
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.data[1].x, Some(1_704_240_000_000.0));
+        assert!(table.to_dataset("date", "missing").is_err());
+    }
+
+    #[test]
+    fn test_chart_data_export() {
+        let data = ChartData::new()
+            .with_labels(vec!["Q1", "Q2"])
+            .add_dataset(Dataset::new("Sales, EU").with_data(vec![1.5, 2.0]))
+            .add_dataset(Dataset::new("US").with_data(vec![3.0]));
+        let table = DsvTable::from_chart_data(&data, "quarter");
+
+        assert_eq!(table.to_csv_string(), "quarter,\"Sales, EU\",US\nQ1,1.5,3\nQ2,2,\n");
+        let back = from_csv_str(&table.to_csv_string()).unwrap().to_chart_data("quarter").unwrap();
+        assert_eq!(back.labels, data.labels);
+        assert_eq!(back.datasets[0].data[1].y, 2.0);
+        assert!(back.datasets[1].data[1].y.is_nan());
+        assert!(from_csv_str("").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! Export of chart data and computed layout for "download data" features
+//!
+//! [`DataExport`] turns [`ChartData`], after an optional [`DataPipeline`],
+//! into CSV or JSON records. [`LayoutReport`] collects what the chart
+//! computed from it (axis ticks, stacked extents and totals) as JSON.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::axis::NumberLocale;
+//! use makepad_d3::data::{ChartData, DataExport, DataPipeline, Dataset};
+//!
+//! let data = ChartData::new()
+//!     .with_labels(vec!["Q1", "Q2"])
+//!     .add_dataset(Dataset::new("Revenue").with_data(vec![1250.5, 980.0]));
+//!
+//! let csv = DataExport::new()
+//!     .with_label_column("quarter")
+//!     .with_locale(NumberLocale::de_de())
+//!     .with_grouping(true)
+//!     .with_decimals(2)
+//!     .with_delimiter(';')
+//!     .to_csv(&data);
+//! assert_eq!(csv, "quarter;Revenue\nQ1;1.250,50\nQ2;980,00\n");
+//!
+//! let json = DataExport::new().with_pipeline(DataPipeline::new().scale_y(2.0)).to_json(&data);
+//! assert!(json.starts_with(r#"[{"label":"Q1","Revenue":2501.0}"#));
+//! ```
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+
+use super::dsv::{DsvTable, DsvValue};
+use super::{ChartData, DataPipeline, Dataset};
+use crate::axis::{AxisLayout, NumberLocale};
+use crate::shape::StackedSeries;
+
+/// Exporter for the data behind a chart
+///
+/// Rows follow point indices: the label column holds the chart's category
+/// label, or else the point's own label or x value, and each dataset adds
+/// a value column. Hidden datasets are left out unless asked for.
+#[derive(Debug)]
+pub struct DataExport {
+    /// Header of the label column
+    label_column: String,
+    /// Whether hidden datasets are exported
+    include_hidden: bool,
+    /// Transforms applied to every dataset before export
+    pipeline: Option<DataPipeline>,
+    /// Separators for CSV numbers; None writes plain `1234.5`
+    locale: Option<NumberLocale>,
+    /// Decimal places for CSV numbers; None uses each dataset's precision hint
+    decimals: Option<usize>,
+    /// Whether CSV numbers get group separators
+    grouping: bool,
+    /// CSV field delimiter
+    delimiter: char,
+}
+
+impl Default for DataExport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataExport {
+    /// Create an exporter writing plain comma-separated numbers
+    pub fn new() -> Self {
+        Self {
+            label_column: "label".to_string(),
+            include_hidden: false,
+            pipeline: None,
+            locale: None,
+            decimals: None,
+            grouping: false,
+            delimiter: ',',
+        }
+    }
+
+    /// Set the header of the label column
+    pub fn with_label_column(mut self, name: impl Into<String>) -> Self {
+        self.label_column = name.into();
+        self
+    }
+
+    /// Export hidden datasets too
+    pub fn with_hidden(mut self, include: bool) -> Self {
+        self.include_hidden = include;
+        self
+    }
+
+    /// Apply a pipeline to every dataset before export
+    pub fn with_pipeline(mut self, pipeline: DataPipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+
+    /// Write CSV numbers with a locale's decimal and group separators
+    pub fn with_locale(mut self, locale: NumberLocale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Round CSV numbers to a fixed number of decimal places
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Insert group separators in CSV numbers
+    pub fn with_grouping(mut self, grouping: bool) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Set the CSV field delimiter
+    ///
+    /// Use `;` with locales whose decimal separator is a comma.
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// The exported datasets, filtered and transformed
+    pub fn datasets(&self, data: &ChartData) -> Vec<Dataset> {
+        data.datasets
+            .iter()
+            .filter(|d| self.include_hidden || !d.hidden)
+            .map(|d| match &self.pipeline {
+                Some(pipeline) => {
+                    let mut d = d.clone();
+                    d.data = pipeline.apply(&d.data);
+                    d
+                }
+                None => d.clone(),
+            })
+            .collect()
+    }
+
+    /// Table of the exported data with numbers formatted for CSV
+    pub fn table(&self, data: &ChartData) -> DsvTable {
+        let datasets = self.datasets(data);
+        let mut table = DsvTable::new(
+            std::iter::once(self.label_column.clone()).chain(datasets.iter().map(|d| d.label.clone())),
+        );
+        for i in 0..row_count(data, &datasets) {
+            let mut row = vec![match row_label(data, &datasets, i) {
+                Some(label) => DsvValue::Text(label),
+                None => DsvValue::Empty,
+            }];
+            row.extend(datasets.iter().map(|d| match value_at(d, i) {
+                Some(v) => DsvValue::Text(self.format_value(v, d)),
+                None => DsvValue::Empty,
+            }));
+            table.rows.push(row);
+        }
+        table
+    }
+
+    /// Export as CSV text with a header row
+    ///
+    /// Missing and masked values are left empty.
+    pub fn to_csv(&self, data: &ChartData) -> String {
+        self.table(data).format(self.delimiter)
+    }
+
+    /// Export as a JSON array of records, one object per row
+    ///
+    /// Values are plain JSON numbers (locale and rounding options apply to
+    /// CSV only); missing values are `null`.
+    pub fn to_json(&self, data: &ChartData) -> String {
+        let datasets = self.datasets(data);
+        let records: Vec<Record> = (0..row_count(data, &datasets))
+            .map(|i| {
+                let label = row_label(data, &datasets, i).map_or(Value::Null, Value::String);
+                let mut fields = vec![(self.label_column.as_str(), label)];
+                for d in &datasets {
+                    fields.push((d.label.as_str(), value_at(d, i).map_or(Value::Null, Value::from)));
+                }
+                Record(fields)
+            })
+            .collect();
+        serde_json::to_string(&records).unwrap_or_default()
+    }
+
+    fn format_value(&self, value: f64, dataset: &Dataset) -> String {
+        let decimals = self.decimals.or(dataset.meta.precision);
+        match &self.locale {
+            Some(locale) => locale.format(value, decimals, self.grouping),
+            None => NumberLocale::en_us().format(value, decimals, self.grouping),
+        }
+    }
+}
+
+/// JSON object that keeps its fields in column order
+struct Record<'a>(Vec<(&'a str, Value)>);
+
+impl Serialize for Record<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+fn row_count(data: &ChartData, datasets: &[Dataset]) -> usize {
+    datasets.iter().map(|d| d.len()).max().unwrap_or(0).max(data.labels.len())
+}
+
+/// Category label, else the first point label or x value found in the row
+fn row_label(data: &ChartData, datasets: &[Dataset], index: usize) -> Option<String> {
+    if let Some(label) = data.labels.get(index) {
+        return Some(label.clone());
+    }
+    let point = datasets.iter().find_map(|d| d.data.get(index))?;
+    point.label.clone().or_else(|| point.x.map(|x| x.to_string()))
+}
+
+fn value_at(dataset: &Dataset, index: usize) -> Option<f64> {
+    let point = dataset.data.get(index)?;
+    (dataset.is_valid(index) && point.y.is_finite()).then_some(point.y)
+}
+
+/// One tick in a [`LayoutReport`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TickReport {
+    /// Domain value
+    pub value: f64,
+    /// Label as drawn
+    pub label: String,
+    /// Position along the axis in pixels
+    pub position: f64,
+}
+
+/// Ticks of one axis in a [`LayoutReport`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AxisReport {
+    /// Axis name
+    pub name: String,
+    /// Ticks in axis order
+    pub ticks: Vec<TickReport>,
+}
+
+/// One stacked series in a [`LayoutReport`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StackReport {
+    /// Series key
+    pub key: String,
+    /// (y0, y1) per point
+    pub extents: Vec<(f64, f64)>,
+}
+
+/// Machine-readable summary of a computed chart layout
+///
+/// # Example
+/// ```
+/// use makepad_d3::data::{ChartData, Dataset, LayoutReport};
+/// use makepad_d3::shape::StackGenerator;
+///
+/// let data = ChartData::new()
+///     .add_dataset(Dataset::new("a").with_data(vec![1.0, 2.0]))
+///     .add_dataset(Dataset::new("b").with_data(vec![3.0, 4.0]));
+/// let report = LayoutReport::new().with_stack(&StackGenerator::new().compute(&data));
+///
+/// assert_eq!(report.totals, vec![4.0, 6.0]);
+/// assert!(report.to_json().contains(r#""totals":[4.0,6.0]"#));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LayoutReport {
+    /// Axis ticks
+    pub axes: Vec<AxisReport>,
+    /// Stacked series extents
+    pub stacks: Vec<StackReport>,
+    /// Per-point totals of the stacked values
+    pub totals: Vec<f64>,
+}
+
+impl LayoutReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an axis's computed ticks
+    pub fn with_axis(mut self, name: impl Into<String>, layout: &AxisLayout) -> Self {
+        self.axes.push(AxisReport {
+            name: name.into(),
+            ticks: layout
+                .ticks
+                .iter()
+                .map(|t| TickReport {
+                    value: t.tick.value,
+                    label: t.label.clone(),
+                    position: t.position,
+                })
+                .collect(),
+        });
+        self
+    }
+
+    /// Add stacked series, replacing any added before, and their totals
+    ///
+    /// Totals sum the segment heights, so they hold for any stack offset.
+    pub fn with_stack(mut self, series: &[StackedSeries]) -> Self {
+        let n = series.iter().map(|s| s.points.len()).max().unwrap_or(0);
+        self.totals = (0..n)
+            .map(|i| series.iter().filter_map(|s| s.points.get(i)).map(|p| p.height()).sum())
+            .collect();
+        self.stacks = series
+            .iter()
+            .map(|s| StackReport {
+                key: s.key.clone(),
+                extents: s.points.iter().map(|p| (p.y0, p.y1)).collect(),
+            })
+            .collect();
+        self
+    }
+
+    /// Serialize as compact JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Serialize as indented JSON
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ChartData {
+        let mut sparse = Dataset::new("Cost").with_data(vec![10.0, 20.0, 30.0]);
+        sparse.set_missing(1);
+        ChartData::new()
+            .with_labels(vec!["Jan", "Feb", "Mar"])
+            .add_dataset(Dataset::new("Sales, EU").with_data(vec![1234.5, 2000.0, 0.25]))
+            .add_dataset(sparse)
+            .add_dataset(Dataset::new("Draft").with_data(vec![1.0, 2.0, 3.0]).with_hidden(true))
+    }
+
+    #[test]
+    fn test_csv_quotes_and_skips() {
+        let csv = DataExport::new().to_csv(&sample());
+        assert_eq!(csv, "label,\"Sales, EU\",Cost\nJan,1234.5,10\nFeb,2000,\nMar,0.25,30\n");
+
+        let all = DataExport::new().with_hidden(true).to_csv(&sample());
+        assert!(all.starts_with("label,\"Sales, EU\",Cost,Draft\n"));
+    }
+
+    #[test]
+    fn test_csv_locale_grouping_quotes_decimal_commas() {
+        let csv = DataExport::new()
+            .with_locale(NumberLocale::de_de())
+            .with_grouping(true)
+            .with_decimals(1)
+            .to_csv(&sample());
+        assert_eq!(csv.lines().nth(1), Some("Jan,\"1.234,5\",\"10,0\""));
+    }
+
+    #[test]
+    fn test_json_records_after_pipeline() {
+        let json = DataExport::new().with_pipeline(DataPipeline::new().scale_y(2.0)).to_json(&sample());
+        let records: Vec<Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["Sales, EU"], 2469.0);
+        assert_eq!(records[1]["Cost"], Value::Null);
+        assert!(records[0].get("Draft").is_none());
+    }
+
+    #[test]
+    fn test_row_labels_fall_back_to_x() {
+        let data = ChartData::new().add_dataset(Dataset::new("y").with_xy_data(vec![(0.5, 1.0), (1.5, 2.0)]));
+        assert_eq!(DataExport::new().to_csv(&data), "label,y\n0.5,1\n1.5,2\n");
+    }
+
+    #[test]
+    fn test_layout_report_totals_ignore_offset() {
+        use crate::shape::{StackGenerator, StackOffset};
+
+        let data = ChartData::new()
+            .add_dataset(Dataset::new("a").with_data(vec![1.0, 2.0]))
+            .add_dataset(Dataset::new("b").with_data(vec![3.0, 4.0]));
+        let series = StackGenerator::new().offset(StackOffset::Silhouette).compute(&data);
+        let report = LayoutReport::new().with_stack(&series);
+        assert_eq!(report.totals, vec![4.0, 6.0]);
+        assert_eq!(report.stacks[0].extents[0], (-2.0, -1.0));
+
+        let parsed: LayoutReport = serde_json::from_str(&report.to_json_pretty()).unwrap();
+        assert_eq!(parsed, report);
+    }
+}
@@ -0,0 +1,153 @@
+//! Keyed data join (enter / update / exit)
+//!
+//! Matches elements of an old and a new collection by key, like D3's
+//! `selection.data(data, key)`. Animations use the result to tween updated
+//! elements and fade entering and exiting ones.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::DataPoint;
+
+/// Result of joining old and new collections by key
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DataJoin {
+    /// Indices into the new collection with no matching old element
+    pub enter: Vec<usize>,
+    /// Matched (old index, new index) pairs, in new order
+    pub update: Vec<(usize, usize)>,
+    /// Indices into the old collection with no matching new element
+    pub exit: Vec<usize>,
+}
+
+impl DataJoin {
+    /// Join two key sequences
+    ///
+    /// Each key matches at most once; repeated keys pair up in order of
+    /// appearance and any surplus enters or exits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use makepad_d3::data::DataJoin;
+    ///
+    /// let join = DataJoin::keys(&["a", "b", "c"], &["b", "c", "d"]);
+    /// assert_eq!(join.exit, vec![0]);
+    /// assert_eq!(join.update, vec![(1, 0), (2, 1)]);
+    /// assert_eq!(join.enter, vec![2]);
+    /// ```
+    pub fn keys<K: Eq + Hash>(old: &[K], new: &[K]) -> Self {
+        Self::by(old, new, |k| k)
+    }
+
+    /// Join two collections using a key function
+    pub fn by<'a, T, K, F>(old: &'a [T], new: &'a [T], key: F) -> Self
+    where
+        K: Eq + Hash,
+        F: Fn(&'a T) -> K,
+    {
+        let mut pending: HashMap<K, Vec<usize>> = HashMap::new();
+        for (i, item) in old.iter().enumerate().rev() {
+            pending.entry(key(item)).or_default().push(i);
+        }
+
+        let mut join = Self::default();
+        let mut matched = vec![false; old.len()];
+        for (j, item) in new.iter().enumerate() {
+            match pending.get_mut(&key(item)).and_then(Vec::pop) {
+                Some(i) => {
+                    matched[i] = true;
+                    join.update.push((i, j));
+                }
+                None => join.enter.push(j),
+            }
+        }
+        join.exit = (0..old.len()).filter(|&i| !matched[i]).collect();
+        join
+    }
+
+    /// Join data points by id, then label, falling back to position for
+    /// points with neither
+    pub fn points(old: &[DataPoint], new: &[DataPoint]) -> Self {
+        let keyed = |points: &[DataPoint]| -> Vec<String> {
+            points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| match (&p.id, &p.label) {
+                    (Some(id), _) => format!("d:{}", id),
+                    (None, Some(label)) => format!("l:{}", label),
+                    (None, None) => format!("i:{}", i),
+                })
+                .collect()
+        };
+        Self::keys(&keyed(old), &keyed(new))
+    }
+
+    /// Check if nothing entered or exited
+    pub fn is_stable(&self) -> bool {
+        self.enter.is_empty() && self.exit.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_keys() {
+        let join = DataJoin::keys(&[1, 2, 3], &[3, 4, 1]);
+        assert_eq!(join.update, vec![(2, 0), (0, 2)]);
+        assert_eq!(join.enter, vec![1]);
+        assert_eq!(join.exit, vec![1]);
+        assert!(!join.is_stable());
+    }
+
+    #[test]
+    fn test_join_duplicate_keys() {
+        let join = DataJoin::keys(&["a", "a", "b"], &["a", "b", "b"]);
+        assert_eq!(join.update, vec![(0, 0), (2, 1)]);
+        assert_eq!(join.enter, vec![2]);
+        assert_eq!(join.exit, vec![1]);
+    }
+
+    #[test]
+    fn test_join_points_by_label() {
+        let old = vec![
+            DataPoint::new(0.0, 1.0).with_label("US"),
+            DataPoint::new(1.0, 2.0).with_label("FR"),
+        ];
+        let new = vec![
+            DataPoint::new(1.0, 3.0).with_label("FR"),
+            DataPoint::new(2.0, 4.0).with_label("JP"),
+        ];
+        let join = DataJoin::points(&old, &new);
+        assert_eq!(join.update, vec![(1, 0)]);
+        assert_eq!(join.enter, vec![1]);
+        assert_eq!(join.exit, vec![0]);
+    }
+
+    #[test]
+    fn test_join_points_by_id() {
+        // Ids win over labels, so renamed points still update in place
+        let old = vec![
+            DataPoint::from_y(1.0).with_id("a").with_label("Old"),
+            DataPoint::from_y(2.0).with_label("B"),
+        ];
+        let new = vec![
+            DataPoint::from_y(3.0).with_label("B"),
+            DataPoint::from_y(4.0).with_id("a").with_label("New"),
+        ];
+        let join = DataJoin::points(&old, &new);
+        assert_eq!(join.update, vec![(1, 0), (0, 1)]);
+        assert!(join.is_stable());
+    }
+
+    #[test]
+    fn test_join_points_unlabeled_by_index() {
+        let old = vec![DataPoint::from_y(1.0), DataPoint::from_y(2.0)];
+        let new = vec![DataPoint::from_y(5.0)];
+        let join = DataJoin::points(&old, &new);
+        assert_eq!(join.update, vec![(0, 0)]);
+        assert_eq!(join.exit, vec![1]);
+    }
+}
@@ -0,0 +1,218 @@
+//! Image and glyph markers
+//!
+//! Besides the geometric [`PointStyle`](super::PointStyle) shapes, series
+//! can be marked with a picture (a company logo) or a text glyph (an emoji).
+//! The crate only references images through an opaque [`ImageHandle`] and
+//! computes where and how large to draw the marker; loading and drawing the
+//! texture or glyph is left to the renderer.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::data::{Marker, MarkerAnchor, MarkerContent};
+//!
+//! // A 2:1 logo fitted into a 20 px box, pinned by its bottom center
+//! let logo = Marker::image(7, 2.0).with_size(20.0).with_anchor(MarkerAnchor::Bottom);
+//! let layout = logo.layout(100.0, 50.0);
+//! assert_eq!((layout.x, layout.y, layout.width, layout.height), (90.0, 40.0, 20.0, 10.0));
+//!
+//! let emoji = Marker::glyph("🚀").with_size(16.0);
+//! assert!(matches!(emoji.layout(0.0, 0.0).content, MarkerContent::Glyph(_)));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Opaque reference to an image owned by the renderer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ImageHandle(pub u64);
+
+/// What a marker shows
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MarkerContent {
+    /// An image with its width / height aspect ratio
+    Image {
+        /// Renderer image handle
+        handle: ImageHandle,
+        /// Width divided by height
+        aspect_ratio: f64,
+    },
+    /// A text glyph or short string (e.g. an emoji)
+    Glyph(String),
+}
+
+/// Point of the marker box placed on the anchor position
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarkerAnchor {
+    /// Box center (scatter markers)
+    #[default]
+    Center,
+    /// Bottom center (map pins, markers standing on a bar)
+    Bottom,
+    /// Top center
+    Top,
+    /// Left center
+    Left,
+    /// Right center
+    Right,
+    /// Top-left corner
+    TopLeft,
+}
+
+impl MarkerAnchor {
+    /// Anchor point as fractions of the box width and height
+    pub fn fraction(&self) -> (f64, f64) {
+        match self {
+            MarkerAnchor::Center => (0.5, 0.5),
+            MarkerAnchor::Bottom => (0.5, 1.0),
+            MarkerAnchor::Top => (0.5, 0.0),
+            MarkerAnchor::Left => (0.0, 0.5),
+            MarkerAnchor::Right => (1.0, 0.5),
+            MarkerAnchor::TopLeft => (0.0, 0.0),
+        }
+    }
+}
+
+/// An image or glyph marker with size and anchor
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    /// Image or glyph
+    pub content: MarkerContent,
+    /// Size of the square box the marker is fitted into, in pixels
+    pub size: f64,
+    /// Point of the box placed on the marker position
+    pub anchor: MarkerAnchor,
+}
+
+/// Computed placement of a marker, ready for the renderer
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarkerLayout {
+    /// Left edge of the drawn box
+    pub x: f64,
+    /// Top edge of the drawn box
+    pub y: f64,
+    /// Box width
+    pub width: f64,
+    /// Box height
+    pub height: f64,
+    /// Font size for glyph markers (the box size for images)
+    pub font_size: f64,
+    /// Image or glyph to draw in the box
+    pub content: MarkerContent,
+}
+
+impl MarkerLayout {
+    /// Center of the drawn box
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Check if a point falls inside the drawn box (for hit testing)
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x <= self.x + self.width && y >= self.y && y <= self.y + self.height
+    }
+}
+
+impl Marker {
+    /// Image marker with the image's width / height aspect ratio
+    pub fn image(handle: u64, aspect_ratio: f64) -> Self {
+        let aspect_ratio = if aspect_ratio.is_finite() && aspect_ratio > 0.0 {
+            aspect_ratio
+        } else {
+            1.0
+        };
+        Self {
+            content: MarkerContent::Image {
+                handle: ImageHandle(handle),
+                aspect_ratio,
+            },
+            size: 12.0,
+            anchor: MarkerAnchor::Center,
+        }
+    }
+
+    /// Glyph marker (e.g. an emoji)
+    pub fn glyph(text: impl Into<String>) -> Self {
+        Self {
+            content: MarkerContent::Glyph(text.into()),
+            size: 12.0,
+            anchor: MarkerAnchor::Center,
+        }
+    }
+
+    /// Set the size of the box the marker is fitted into
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = size.max(0.0);
+        self
+    }
+
+    /// Set the anchor point
+    pub fn with_anchor(mut self, anchor: MarkerAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Drawn width and height: images keep their aspect ratio inside the box
+    pub fn dimensions(&self) -> (f64, f64) {
+        match &self.content {
+            MarkerContent::Image { aspect_ratio, .. } if *aspect_ratio >= 1.0 => {
+                (self.size, self.size / aspect_ratio)
+            }
+            MarkerContent::Image { aspect_ratio, .. } => (self.size * aspect_ratio, self.size),
+            MarkerContent::Glyph(_) => (self.size, self.size),
+        }
+    }
+
+    /// Place the marker with its anchor at `(x, y)`
+    pub fn layout(&self, x: f64, y: f64) -> MarkerLayout {
+        self.layout_sized(x, y, self.size)
+    }
+
+    /// Place the marker fitted into a box of `size` instead of its own size
+    pub fn layout_sized(&self, x: f64, y: f64, size: f64) -> MarkerLayout {
+        let sized = Marker {
+            size: size.max(0.0),
+            ..self.clone()
+        };
+        let (width, height) = sized.dimensions();
+        let (fx, fy) = self.anchor.fraction();
+        MarkerLayout {
+            x: x - width * fx,
+            y: y - height * fy,
+            width,
+            height,
+            font_size: sized.size,
+            content: self.content.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_keeps_aspect_ratio() {
+        let tall = Marker::image(1, 0.5).with_size(20.0);
+        assert_eq!(tall.dimensions(), (10.0, 20.0));
+        let wide = Marker::image(1, 4.0).with_size(20.0);
+        assert_eq!(wide.dimensions(), (20.0, 5.0));
+        // Invalid aspect ratios fall back to square
+        assert_eq!(Marker::image(1, f64::NAN).with_size(8.0).dimensions(), (8.0, 8.0));
+    }
+
+    #[test]
+    fn test_layout_anchors() {
+        let glyph = Marker::glyph("★").with_size(10.0);
+        let centered = glyph.layout(50.0, 50.0);
+        assert_eq!((centered.x, centered.y), (45.0, 45.0));
+        assert_eq!(centered.center(), (50.0, 50.0));
+        assert_eq!(centered.font_size, 10.0);
+        assert!(centered.contains(54.0, 46.0));
+        assert!(!centered.contains(56.0, 50.0));
+
+        let pinned = glyph.clone().with_anchor(MarkerAnchor::Bottom).layout(50.0, 50.0);
+        assert_eq!((pinned.x, pinned.y), (45.0, 40.0));
+
+        let resized = glyph.with_anchor(MarkerAnchor::TopLeft).layout_sized(0.0, 0.0, 24.0);
+        assert_eq!((resized.x, resized.y, resized.width, resized.font_size), (0.0, 0.0, 24.0, 24.0));
+    }
+}
@@ -0,0 +1,226 @@
+//! Validity masks for missing values
+//!
+//! A [`ValidityMask`] records which entries of a series hold a value, so a
+//! missing sample is never confused with a real `0.0`. [`FillStrategy`]
+//! describes how to replace missing samples when a gap-free series is needed.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::data::{Dataset, FillStrategy};
+//!
+//! let ds = Dataset::new("Sensor").with_optional_data(vec![Some(1.0), None, Some(0.0), Some(5.0)]);
+//! assert!(!ds.is_valid(1));
+//! assert!(ds.is_valid(2)); // a real zero
+//! assert_eq!(ds.count_missing(), 1);
+//!
+//! let filled = ds.fill_missing(FillStrategy::Linear);
+//! assert_eq!(filled.data[1].y, 0.5);
+//! assert_eq!(filled.count_missing(), 0);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Bitset marking which entries of a series are present
+///
+/// Indices past the end of the mask count as valid, so a mask only needs to
+/// cover the data it was built for.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidityMask {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl ValidityMask {
+    /// Mask of `len` entries, all valid
+    pub fn all_valid(len: usize) -> Self {
+        let mut bits = vec![u64::MAX; len.div_ceil(64)];
+        if len % 64 != 0 {
+            if let Some(last) = bits.last_mut() {
+                *last = (1u64 << (len % 64)) - 1;
+            }
+        }
+        Self { bits, len }
+    }
+
+    /// Mask from one flag per entry (`true` = present)
+    pub fn from_flags(flags: impl IntoIterator<Item = bool>) -> Self {
+        let mut mask = Self::default();
+        for flag in flags {
+            mask.push(flag);
+        }
+        mask
+    }
+
+    /// Number of entries covered
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the mask covers no entries
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append an entry
+    pub fn push(&mut self, valid: bool) {
+        if self.len % 64 == 0 {
+            self.bits.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, valid);
+    }
+
+    /// Whether entry `index` is present
+    pub fn get(&self, index: usize) -> bool {
+        if index >= self.len {
+            return true;
+        }
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Mark entry `index`, growing the mask with valid entries if needed
+    pub fn set(&mut self, index: usize, valid: bool) {
+        while self.len <= index {
+            self.push(true);
+        }
+        let bit = 1u64 << (index % 64);
+        if valid {
+            self.bits[index / 64] |= bit;
+        } else {
+            self.bits[index / 64] &= !bit;
+        }
+    }
+
+    /// Number of missing entries
+    pub fn count_missing(&self) -> usize {
+        self.len - self.bits.iter().map(|b| b.count_ones() as usize).sum::<usize>()
+    }
+
+    /// Indices of missing entries
+    pub fn missing_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |&i| !self.get(i))
+    }
+}
+
+/// How to replace missing values
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillStrategy {
+    /// Use zero
+    Zero,
+    /// Use a constant
+    Value(f64),
+    /// Carry the last present value forward (leading gaps stay missing)
+    Previous,
+    /// Carry the next present value backward (trailing gaps stay missing)
+    Next,
+    /// Interpolate linearly between neighbours (edge gaps stay missing)
+    Linear,
+    /// Use the mean of the present values
+    Mean,
+}
+
+impl FillStrategy {
+    /// Fill the gaps of `values`, leaving entries it cannot fill as `None`
+    ///
+    /// `Linear` interpolates by position; pass `xs` to interpolate by x.
+    pub fn fill(&self, values: &[Option<f64>], xs: Option<&[f64]>) -> Vec<Option<f64>> {
+        match *self {
+            FillStrategy::Zero => values.iter().map(|v| Some(v.unwrap_or(0.0))).collect(),
+            FillStrategy::Value(c) => values.iter().map(|v| Some(v.unwrap_or(c))).collect(),
+            FillStrategy::Mean => {
+                let present: Vec<f64> = values.iter().flatten().copied().collect();
+                if present.is_empty() {
+                    return values.to_vec();
+                }
+                let mean = present.iter().sum::<f64>() / present.len() as f64;
+                values.iter().map(|v| Some(v.unwrap_or(mean))).collect()
+            }
+            FillStrategy::Previous => {
+                let mut last = None;
+                values
+                    .iter()
+                    .map(|v| {
+                        if v.is_some() {
+                            last = *v;
+                        }
+                        last
+                    })
+                    .collect()
+            }
+            FillStrategy::Next => {
+                let mut next = None;
+                let mut out: Vec<Option<f64>> = values
+                    .iter()
+                    .rev()
+                    .map(|v| {
+                        if v.is_some() {
+                            next = *v;
+                        }
+                        next
+                    })
+                    .collect();
+                out.reverse();
+                out
+            }
+            FillStrategy::Linear => {
+                let x = |i: usize| xs.and_then(|xs| xs.get(i).copied()).unwrap_or(i as f64);
+                let mut out = values.to_vec();
+                let mut prev: Option<usize> = None;
+                for i in 0..values.len() {
+                    if values[i].is_none() {
+                        continue;
+                    }
+                    if let Some(p) = prev {
+                        let (x0, y0, x1, y1) = (x(p), values[p].unwrap(), x(i), values[i].unwrap());
+                        for (j, slot) in out.iter_mut().enumerate().take(i).skip(p + 1) {
+                            let t = if x1 == x0 { 0.0 } else { (x(j) - x0) / (x1 - x0) };
+                            *slot = Some(y0 + (y1 - y0) * t);
+                        }
+                    }
+                    prev = Some(i);
+                }
+                out
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_bits() {
+        let mut mask = ValidityMask::all_valid(70);
+        assert_eq!(mask.count_missing(), 0);
+        mask.set(3, false);
+        mask.set(65, false);
+        assert!(!mask.get(3) && !mask.get(65) && mask.get(64));
+        assert_eq!(mask.missing_indices().collect::<Vec<_>>(), vec![3, 65]);
+        // Past the end counts as valid
+        assert!(mask.get(500));
+        mask.set(100, false);
+        assert_eq!((mask.len(), mask.count_missing()), (101, 3));
+    }
+
+    #[test]
+    fn test_mask_from_flags() {
+        let mask = ValidityMask::from_flags([true, false, true]);
+        assert_eq!(mask.len(), 3);
+        assert_eq!(mask.count_missing(), 1);
+        assert!(ValidityMask::default().is_empty());
+    }
+
+    #[test]
+    fn test_fill_strategies() {
+        let v = [None, Some(1.0), None, None, Some(4.0), None];
+        assert_eq!(FillStrategy::Zero.fill(&v, None)[0], Some(0.0));
+        assert_eq!(FillStrategy::Previous.fill(&v, None), vec![None, Some(1.0), Some(1.0), Some(1.0), Some(4.0), Some(4.0)]);
+        assert_eq!(FillStrategy::Next.fill(&v, None), vec![Some(1.0), Some(1.0), Some(4.0), Some(4.0), Some(4.0), None]);
+        assert_eq!(FillStrategy::Linear.fill(&v, None), vec![None, Some(1.0), Some(2.0), Some(3.0), Some(4.0), None]);
+        assert_eq!(FillStrategy::Mean.fill(&v, None)[5], Some(2.5));
+
+        let xs = [0.0, 0.0, 1.0, 3.0, 4.0, 5.0];
+        assert_eq!(FillStrategy::Linear.fill(&v, Some(&xs))[3], Some(3.25));
+    }
+}
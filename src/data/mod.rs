@@ -5,6 +5,19 @@
 //! - Dynamic data sources ([`DataSource`], [`BufferedDataSource`], [`StreamingDataSource`])
 //! - Observable datasets with change tracking ([`ObservableDataset`])
 //! - Data transformation pipelines ([`DataPipeline`])
+//! - Calendar aggregation and seasonal decomposition ([`CalendarMatrix`], [`SeasonalDecomposition`])
+//! - Time-aware rolling statistics for irregular samples ([`time_weighted_average`], [`rate_per_second`])
+//! - Index-based rolling windows ([`rolling_min`], [`rolling_std_dev`], [`exponential_moving_average`])
+//! - Shape-preserving downsampling of long series ([`lttb`])
+//! - Keyed enter/update/exit joins ([`DataJoin`])
+//! - Min/max envelope previews for context strips ([`PreviewSeries`])
+//! - CSV/TSV parsing and export ([`from_csv_str`], [`DsvTable`])
+//! - "Download data" exports and layout reports ([`DataExport`], [`LayoutReport`])
+//! - Image and glyph point markers ([`Marker`])
+//! - Explicit missing-value masks and gap filling ([`ValidityMask`], [`FillStrategy`])
+//! - Binary search for the point nearest an inverted coordinate ([`bisect_left`], [`Bisector`], [`nearest_point`])
+//! - Crossfilter-style linked filtering and grouped aggregates ([`Crossfilter`])
+//! - HTTP polling and WebSocket sources behind the `net` feature (`net::HttpPollingSource`, `net::WebSocketSource`)
 //!
 //! # Static Data Example
 //!
@@ -52,11 +65,31 @@ mod observable;
 mod streaming;
 mod polling;
 mod pipeline;
+mod seasonal;
+mod rolling;
+mod window;
+mod downsample;
+mod join;
+mod preview;
+mod dsv;
+mod export;
+mod marker;
+mod mask;
+mod bisect;
+mod crossfilter;
+#[cfg(feature = "net")]
+pub mod net;
 
 // Core data structures
 pub use point::DataPoint;
-pub use dataset::{Dataset, PointStyle, Color};
+pub use dataset::{Dataset, PointStyle, Color, SeriesMeta, SeriesStyle, DEFAULT_Y_AXIS};
 pub use chart_data::ChartData;
+pub use marker::{Marker, MarkerAnchor, MarkerContent, MarkerLayout, ImageHandle};
+pub use mask::{ValidityMask, FillStrategy};
+pub use bisect::{bisect_left, bisect_right, bisect_center, bisector, Bisector, nearest_point};
+pub use crossfilter::{
+    Crossfilter, CrossfilterChange, Dimension, DimensionFilter, DimensionId, Group, GroupKey, MAX_DIMENSIONS,
+};
 
 // Data source traits and types
 pub use source::{
@@ -97,3 +130,30 @@ pub use pipeline::{
     Transform,
     Aggregation,
 };
+
+// Calendar aggregation and seasonal decomposition
+pub use seasonal::{
+    CalendarUnit,
+    CalendarMatrix,
+    SeasonalDecomposition,
+    aggregate_by_calendar,
+};
+
+// Time-aware rolling statistics
+pub use rolling::{exponential_decay, rate_per_second, time_weighted_average};
+
+// Index-based rolling windows
+pub use window::{exponential_moving_average, rolling_max, rolling_min, rolling_std_dev};
+
+// Largest-Triangle-Three-Buckets downsampling
+pub use downsample::lttb;
+
+// Keyed enter/update/exit join
+pub use join::DataJoin;
+
+// Downsampled preview series
+pub use preview::{PreviewSeries, EnvelopeBucket};
+
+// Delimiter-separated values
+pub use dsv::{DsvReader, DsvTable, DsvValue, from_csv_str, from_tsv_str};
+pub use export::{AxisReport, DataExport, LayoutReport, StackReport, TickReport};
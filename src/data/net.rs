@@ -0,0 +1,620 @@
+//! Network data source adapters (requires the `net` feature)
+//!
+//! [`HttpPollingSource`] fetches a URL on an interval and [`WebSocketSource`]
+//! listens to a WebSocket feed. Both do their I/O on a background thread and
+//! forward parsed points as [`StreamMessage`]s into an internal
+//! [`StreamingDataSource`], so the render loop only ever calls the
+//! non-blocking [`DataSource::poll`] and no async runtime is needed.
+//!
+//! Response bodies and socket messages are turned into points by a parser;
+//! [`parse_json_points`] is the default and custom formats can be plugged in
+//! with `with_parser`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use makepad_d3::data::net::{HttpPollingSource, WebSocketSource};
+//! use makepad_d3::data::{DataSource, DataSourceEvent};
+//!
+//! let mut prices = HttpPollingSource::new("https://example.com/prices.json", 5_000);
+//! prices.connect();
+//!
+//! let mut ticks = WebSocketSource::new("wss://example.com/ticks")
+//!     .with_subscribe(r#"{"subscribe":"BTC"}"#)
+//!     .with_parser(|text| {
+//!         let value: f64 = text.trim().parse().map_err(|e| format!("{}", e))?;
+//!         Ok(vec![makepad_d3::data::DataPoint::from_y(value)])
+//!     });
+//! ticks.connect();
+//!
+//! // In render loop
+//! while let DataSourceEvent::Append(points) = ticks.poll() {
+//!     // Update chart with new points
+//! }
+//! ```
+
+use super::{
+    DataPoint, DataSource, DataSourceConfig, DataSourceEvent, DataSourceState, PollingConfig,
+    PollingStrategy, StreamMessage, StreamingDataSource,
+};
+use serde::Deserialize;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::Message;
+
+/// Longest a worker blocks before checking for disconnect
+const WAKE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Parser from a response body or socket message to data points
+pub type PointParser = Arc<dyn Fn(&str) -> Result<Vec<DataPoint>, String> + Send + Sync>;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonPoints {
+    Points(Vec<DataPoint>),
+    Point(DataPoint),
+    Values(Vec<f64>),
+}
+
+/// Parse a JSON array of points, a single point, or an array of y values
+///
+/// ```
+/// use makepad_d3::data::net::parse_json_points;
+///
+/// let points = parse_json_points(r#"[{"x": 1.0, "y": 2.0}, {"x": 2.0, "y": 3.5}]"#).unwrap();
+/// assert_eq!(points[1].y, 3.5);
+///
+/// assert_eq!(parse_json_points(r#"{"x": 3.0, "y": 1.0}"#).unwrap().len(), 1);
+/// assert_eq!(parse_json_points("[4, 5, 6]").unwrap()[2].y, 6.0);
+/// assert!(parse_json_points("not json").is_err());
+/// ```
+pub fn parse_json_points(text: &str) -> Result<Vec<DataPoint>, String> {
+    match serde_json::from_str(text) {
+        Ok(JsonPoints::Points(points)) => Ok(points),
+        Ok(JsonPoints::Point(point)) => Ok(vec![point]),
+        Ok(JsonPoints::Values(values)) => Ok(values.into_iter().map(DataPoint::from_y).collect()),
+        Err(e) => Err(format!("Invalid point JSON: {}", e)),
+    }
+}
+
+/// How parsed points are applied to the buffer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// Each response is the full series
+    #[default]
+    Replace,
+    /// Each response holds new points to append
+    Append,
+}
+
+impl UpdateMode {
+    fn message(self, points: Vec<DataPoint>) -> StreamMessage {
+        match self {
+            UpdateMode::Replace => StreamMessage::Replace(points),
+            UpdateMode::Append => StreamMessage::Points(points),
+        }
+    }
+}
+
+/// Flags shared with a background worker
+#[derive(Default)]
+struct WorkerFlags {
+    stop: AtomicBool,
+    paused: AtomicBool,
+    finished: AtomicBool,
+}
+
+impl WorkerFlags {
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    fn paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Sleep for `ms`, waking early on stop; returns false if stopped
+    fn sleep(&self, ms: u64) -> bool {
+        let mut remaining = Duration::from_millis(ms);
+        while !self.stopped() && !remaining.is_zero() {
+            let step = remaining.min(WAKE_INTERVAL);
+            thread::sleep(step);
+            remaining -= step;
+        }
+        !self.stopped()
+    }
+}
+
+/// Streaming buffer fed by at most one background worker
+struct NetLink {
+    source: StreamingDataSource,
+    sender: Sender<StreamMessage>,
+    worker: Option<Arc<WorkerFlags>>,
+}
+
+impl NetLink {
+    fn new(config: DataSourceConfig) -> Self {
+        let (source, sender) = StreamingDataSource::with_config(config);
+        Self { source, sender, worker: None }
+    }
+
+    fn is_running(&self) -> bool {
+        self.worker.as_ref().is_some_and(|flags| !flags.finished.load(Ordering::Relaxed))
+    }
+
+    /// Start a worker unless one is running
+    fn spawn<F>(&mut self, run: F)
+    where
+        F: FnOnce(Sender<StreamMessage>, Arc<WorkerFlags>) + Send + 'static,
+    {
+        if self.is_running() {
+            return;
+        }
+        let flags = Arc::new(WorkerFlags::default());
+        let (sender, worker_flags) = (self.sender.clone(), Arc::clone(&flags));
+        thread::spawn(move || {
+            run(sender, Arc::clone(&worker_flags));
+            worker_flags.finished.store(true, Ordering::Relaxed);
+        });
+        self.worker = Some(flags);
+        // Connected is reported by the worker once the remote answers
+        self.source.set_state(DataSourceState::Connecting);
+    }
+
+    /// Signal the worker to exit without waiting for it
+    fn stop(&mut self) {
+        if let Some(flags) = self.worker.take() {
+            flags.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        if let Some(flags) = &self.worker {
+            flags.paused.store(paused, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Drop for NetLink {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Data source that fetches a URL over HTTP on an interval
+///
+/// Each successful response is parsed and either replaces the series or is
+/// appended to it (see [`UpdateMode`]). Failures are reported as
+/// [`DataSourceEvent::Error`]; with [`PollingStrategy::ExponentialBackoff`]
+/// the interval grows after each failure, and after
+/// [`PollingConfig::max_retries`] consecutive failures polling stops until
+/// [`connect`](DataSource::connect) is called again.
+pub struct HttpPollingSource {
+    url: String,
+    polling: PollingConfig,
+    mode: UpdateMode,
+    timeout_ms: u64,
+    parser: PointParser,
+    link: NetLink,
+}
+
+impl HttpPollingSource {
+    /// Create a source polling `url` every `interval_ms`
+    pub fn new(url: impl Into<String>, interval_ms: u64) -> Self {
+        Self {
+            url: url.into(),
+            polling: PollingConfig::default().with_interval(interval_ms),
+            mode: UpdateMode::Replace,
+            timeout_ms: 10_000,
+            parser: Arc::new(parse_json_points),
+            link: NetLink::new(DataSourceConfig::default()),
+        }
+    }
+
+    /// Set the data source configuration (point limits)
+    pub fn with_config(mut self, config: DataSourceConfig) -> Self {
+        self.link = NetLink::new(config);
+        self
+    }
+
+    /// Set the polling configuration (interval, backoff, retries)
+    pub fn with_polling(mut self, polling: PollingConfig) -> Self {
+        self.polling = polling;
+        self
+    }
+
+    /// Set whether responses replace or extend the data
+    pub fn with_mode(mut self, mode: UpdateMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the request timeout in milliseconds
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Set the response body parser
+    pub fn with_parser<F>(mut self, parser: F) -> Self
+    where
+        F: Fn(&str) -> Result<Vec<DataPoint>, String> + Send + Sync + 'static,
+    {
+        self.parser = Arc::new(parser);
+        self
+    }
+
+    /// Get the polled URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get retained data
+    pub fn data(&self) -> &[DataPoint] {
+        self.link.source.data()
+    }
+
+    /// Check if the background worker is running
+    pub fn is_running(&self) -> bool {
+        self.link.is_running()
+    }
+
+    fn start(&mut self) {
+        let (url, polling, mode, parser) = (self.url.clone(), self.polling.clone(), self.mode, Arc::clone(&self.parser));
+        let timeout = Duration::from_millis(self.timeout_ms);
+        self.link.spawn(move |sender, flags| {
+            let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+            let fetch = || -> Result<Vec<DataPoint>, String> {
+                let body = agent
+                    .get(&url)
+                    .call()
+                    .map_err(|e| e.to_string())?
+                    .into_string()
+                    .map_err(|e| e.to_string())?;
+                parser(&body)
+            };
+
+            let (mut interval, mut errors, mut connected) = (polling.interval_ms, 0, false);
+            while !flags.stopped() {
+                if !flags.paused() {
+                    let message = match fetch() {
+                        Ok(points) => {
+                            if !connected && sender.send(StreamMessage::Connected).is_err() {
+                                return;
+                            }
+                            connected = true;
+                            errors = 0;
+                            interval = polling.interval_ms;
+                            mode.message(points)
+                        }
+                        Err(error) => {
+                            connected = false;
+                            errors += 1;
+                            if polling.strategy == PollingStrategy::ExponentialBackoff {
+                                let next = (interval as f64 * polling.backoff_multiplier) as u64;
+                                interval = next.min(polling.max_interval_ms);
+                            }
+                            if errors >= polling.max_retries {
+                                let _ = sender.send(StreamMessage::Error(format!("Max retries exceeded: {}", error)));
+                                return;
+                            }
+                            StreamMessage::Error(error)
+                        }
+                    };
+                    if sender.send(message).is_err() {
+                        return;
+                    }
+                }
+                if !flags.sleep(interval) {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+impl DataSource for HttpPollingSource {
+    fn poll(&mut self) -> DataSourceEvent {
+        self.link.source.poll()
+    }
+
+    fn state(&self) -> DataSourceState {
+        self.link.source.state()
+    }
+
+    fn connect(&mut self) {
+        self.start();
+    }
+
+    fn disconnect(&mut self) {
+        self.link.stop();
+        self.link.source.disconnect();
+    }
+
+    fn pause(&mut self) {
+        self.link.set_paused(true);
+        self.link.source.pause();
+    }
+
+    fn resume(&mut self) {
+        self.link.set_paused(false);
+        self.link.source.resume();
+    }
+
+    fn snapshot(&self) -> Vec<DataPoint> {
+        self.link.source.snapshot()
+    }
+
+    fn config(&self) -> &DataSourceConfig {
+        self.link.source.config()
+    }
+}
+
+/// Data source that listens to a WebSocket feed
+///
+/// Every text (or UTF-8 binary) message is parsed and appended by default.
+/// Parse failures are reported as errors without closing the socket. When
+/// the connection drops, the source reconnects after
+/// [`DataSourceConfig::reconnect_delay_ms`] if
+/// [`DataSourceConfig::auto_reconnect`] is set. Messages arriving while
+/// paused are discarded.
+pub struct WebSocketSource {
+    url: String,
+    subscribe: Vec<String>,
+    mode: UpdateMode,
+    parser: PointParser,
+    link: NetLink,
+}
+
+impl WebSocketSource {
+    /// Create a source for a `ws://` or `wss://` URL
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            subscribe: Vec::new(),
+            mode: UpdateMode::Append,
+            parser: Arc::new(parse_json_points),
+            link: NetLink::new(DataSourceConfig::realtime()),
+        }
+    }
+
+    /// Set the data source configuration (point limits, reconnect)
+    pub fn with_config(mut self, config: DataSourceConfig) -> Self {
+        self.link = NetLink::new(config);
+        self
+    }
+
+    /// Add a text message sent after every (re)connect, e.g. a subscription
+    pub fn with_subscribe(mut self, message: impl Into<String>) -> Self {
+        self.subscribe.push(message.into());
+        self
+    }
+
+    /// Set whether messages replace or extend the data
+    pub fn with_mode(mut self, mode: UpdateMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set the message parser
+    pub fn with_parser<F>(mut self, parser: F) -> Self
+    where
+        F: Fn(&str) -> Result<Vec<DataPoint>, String> + Send + Sync + 'static,
+    {
+        self.parser = Arc::new(parser);
+        self
+    }
+
+    /// Get the socket URL
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Get retained data
+    pub fn data(&self) -> &[DataPoint] {
+        self.link.source.data()
+    }
+
+    /// Check if the background worker is running
+    pub fn is_running(&self) -> bool {
+        self.link.is_running()
+    }
+
+    fn start(&mut self) {
+        let (url, subscribe, mode, parser) = (self.url.clone(), self.subscribe.clone(), self.mode, Arc::clone(&self.parser));
+        let config = self.link.source.config().clone();
+        self.link.spawn(move |sender, flags| {
+            while !flags.stopped() {
+                let open = match tungstenite::connect(url.as_str()) {
+                    Ok((mut socket, _)) => {
+                        // Bounded reads so disconnect is noticed on a quiet feed
+                        let tcp: Option<&TcpStream> = match socket.get_ref() {
+                            MaybeTlsStream::Plain(stream) => Some(stream),
+                            MaybeTlsStream::Rustls(stream) => Some(stream.get_ref()),
+                            _ => None,
+                        };
+                        if let Some(tcp) = tcp {
+                            let _ = tcp.set_read_timeout(Some(WAKE_INTERVAL));
+                        }
+                        let mut result = sender.send(StreamMessage::Connected).map_err(|_| ());
+                        for message in &subscribe {
+                            if let Err(e) = socket.send(Message::Text(message.clone())) {
+                                result = result.and(sender.send(StreamMessage::Error(e.to_string())).map_err(|_| ()));
+                            }
+                        }
+
+                        while result.is_ok() && !flags.stopped() {
+                            let text = match socket.read() {
+                                Ok(Message::Text(text)) => text,
+                                Ok(Message::Binary(bytes)) => String::from_utf8_lossy(&bytes).into_owned(),
+                                Ok(Message::Close(_)) => break,
+                                Ok(_) => continue,
+                                Err(tungstenite::Error::Io(e))
+                                    if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                                {
+                                    continue
+                                }
+                                Err(tungstenite::Error::ConnectionClosed) => break,
+                                Err(e) => {
+                                    result = sender.send(StreamMessage::Error(e.to_string())).map_err(|_| ());
+                                    break;
+                                }
+                            };
+                            if flags.paused() {
+                                continue;
+                            }
+                            let message = match parser(&text) {
+                                Ok(points) => mode.message(points),
+                                Err(error) => StreamMessage::Error(error),
+                            };
+                            result = sender.send(message).map_err(|_| ());
+                        }
+                        if flags.stopped() {
+                            let _ = socket.close(None);
+                            let _ = socket.flush();
+                        }
+                        result
+                    }
+                    Err(e) => sender.send(StreamMessage::Error(e.to_string())).map_err(|_| ()),
+                };
+                if open.is_err() || flags.stopped() {
+                    return;
+                }
+                if !config.auto_reconnect {
+                    let _ = sender.send(StreamMessage::Disconnected);
+                    return;
+                }
+                if !flags.sleep(config.reconnect_delay_ms) {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+impl DataSource for WebSocketSource {
+    fn poll(&mut self) -> DataSourceEvent {
+        self.link.source.poll()
+    }
+
+    fn state(&self) -> DataSourceState {
+        self.link.source.state()
+    }
+
+    fn connect(&mut self) {
+        self.start();
+    }
+
+    fn disconnect(&mut self) {
+        self.link.stop();
+        self.link.source.disconnect();
+    }
+
+    fn pause(&mut self) {
+        self.link.set_paused(true);
+        self.link.source.pause();
+    }
+
+    fn resume(&mut self) {
+        self.link.set_paused(false);
+        self.link.source.resume();
+    }
+
+    fn snapshot(&self) -> Vec<DataPoint> {
+        self.link.source.snapshot()
+    }
+
+    fn config(&self) -> &DataSourceConfig {
+        self.link.source.config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    /// Poll until an event matches or two seconds pass
+    fn wait_for(source: &mut dyn DataSource, mut matches: impl FnMut(&DataSourceEvent) -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while Instant::now() < deadline {
+            let event = source.poll();
+            if matches(&event) {
+                return true;
+            }
+            if matches!(event, DataSourceEvent::None) {
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_http_polling_source() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/data", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for (i, stream) in listener.incoming().take(2).enumerate() {
+                let mut stream = stream.unwrap();
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let body = if i == 0 { "[1, 2, 3]" } else { "oops" };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let polling = PollingConfig::default().with_interval(10);
+        let mut source = HttpPollingSource::new(url, 10).with_polling(polling);
+        source.connect();
+        assert!(source.is_running());
+        assert_eq!(source.state(), DataSourceState::Connecting);
+
+        assert!(wait_for(&mut source, |e| matches!(e, DataSourceEvent::Connected)));
+        assert!(wait_for(&mut source, |e| matches!(e, DataSourceEvent::Replace(p) if p.len() == 3)));
+        assert_eq!(source.data()[2].y, 3.0);
+        assert!(wait_for(&mut source, |e| matches!(e, DataSourceEvent::Error(_))));
+
+        source.disconnect();
+        assert!(!source.is_running());
+        assert_eq!(source.state(), DataSourceState::Disconnected);
+    }
+
+    #[test]
+    fn test_websocket_source() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let subscribe = socket.read().unwrap();
+            assert_eq!(subscribe.into_text().unwrap(), "hello");
+            socket.send(Message::Text(r#"{"x": 1.0, "y": 10.0}"#.to_string())).unwrap();
+            socket.send(Message::Text("[20, 30]".to_string())).unwrap();
+            socket.close(None).unwrap();
+            while socket.read().is_ok() {}
+        });
+
+        let config = DataSourceConfig::realtime().with_auto_reconnect(false);
+        let mut source = WebSocketSource::new(url).with_config(config).with_subscribe("hello");
+        source.connect();
+
+        assert!(wait_for(&mut source, |e| matches!(e, DataSourceEvent::Connected)));
+        assert!(wait_for(&mut source, |e| matches!(e, DataSourceEvent::Append(p) if p.len() == 2)));
+        assert_eq!(source.data().iter().map(|p| p.y).collect::<Vec<_>>(), vec![10.0, 20.0, 30.0]);
+        assert!(wait_for(&mut source, |e| matches!(e, DataSourceEvent::Disconnected)));
+        assert_eq!(source.state(), DataSourceState::Disconnected);
+    }
+}
@@ -20,7 +20,7 @@
 //! }
 //! ```
 
-use super::{DataPoint, Dataset, Color, PointStyle};
+use super::{DataPoint, Dataset, Color, PointStyle, SeriesStyle};
 use std::collections::VecDeque;
 
 /// Types of changes to the dataset
@@ -287,6 +287,12 @@ impl ObservableDataset {
         self.record_change(DataChange::StyleChange);
     }
 
+    /// Set the curve, stroke, dash, marker and fill style
+    pub fn set_style(&mut self, style: SeriesStyle) {
+        self.inner.style = style;
+        self.record_change(DataChange::StyleChange);
+    }
+
     /// Set fill enabled
     pub fn set_fill(&mut self, fill: bool) {
         self.inner.fill = fill;
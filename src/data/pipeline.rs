@@ -24,7 +24,11 @@
 //! assert_eq!(result[0].y, 100.0); // 50 * 2
 //! ```
 
-use super::DataPoint;
+use super::{DataPoint, Dataset};
+use super::downsample::lttb;
+use super::rolling::{exponential_decay, rate_per_second, time_weighted_average};
+use super::seasonal::{aggregate_by_calendar, CalendarUnit, SeasonalDecomposition};
+use super::window::{exponential_moving_average, rolling_max, rolling_min, rolling_std_dev};
 
 /// Transform operation types
 pub enum Transform {
@@ -60,6 +64,26 @@ pub enum Transform {
     Reverse,
     /// Deduplicate consecutive equal Y values
     Dedupe,
+    /// Aggregate timestamped points into one point per calendar bucket
+    CalendarAggregate(CalendarUnit, Aggregation),
+    /// Remove the seasonal component with the given period
+    Deseasonalize(usize),
+    /// Time-weighted average over a trailing window in milliseconds
+    TimeWeightedAverage(f64),
+    /// Exponential smoothing with the given half-life in milliseconds
+    ExponentialDecay(f64),
+    /// Rate of change per second between consecutive samples
+    RatePerSecond,
+    /// Exponential moving average with the given smoothing factor
+    ExponentialMovingAverage(f64),
+    /// Minimum over a trailing window of N points
+    RollingMin(usize),
+    /// Maximum over a trailing window of N points
+    RollingMax(usize),
+    /// Standard deviation over a trailing window of N points
+    RollingStdDev(usize),
+    /// Largest-Triangle-Three-Buckets downsampling to at most N points
+    Lttb(usize),
 }
 
 impl std::fmt::Debug for Transform {
@@ -81,6 +105,18 @@ impl std::fmt::Debug for Transform {
             Transform::SortByY => write!(f, "SortByY"),
             Transform::Reverse => write!(f, "Reverse"),
             Transform::Dedupe => write!(f, "Dedupe"),
+            Transform::CalendarAggregate(unit, aggregation) => {
+                write!(f, "CalendarAggregate({:?}, {:?})", unit, aggregation)
+            }
+            Transform::Deseasonalize(p) => write!(f, "Deseasonalize({})", p),
+            Transform::TimeWeightedAverage(w) => write!(f, "TimeWeightedAverage({})", w),
+            Transform::ExponentialDecay(h) => write!(f, "ExponentialDecay({})", h),
+            Transform::RatePerSecond => write!(f, "RatePerSecond"),
+            Transform::ExponentialMovingAverage(a) => write!(f, "ExponentialMovingAverage({})", a),
+            Transform::RollingMin(n) => write!(f, "RollingMin({})", n),
+            Transform::RollingMax(n) => write!(f, "RollingMax({})", n),
+            Transform::RollingStdDev(n) => write!(f, "RollingStdDev({})", n),
+            Transform::Lttb(n) => write!(f, "Lttb({})", n),
         }
     }
 }
@@ -201,6 +237,74 @@ impl DataPipeline {
         self
     }
 
+    /// Aggregate timestamped points (x in ms) by calendar unit
+    pub fn aggregate_calendar(mut self, unit: CalendarUnit, aggregation: Aggregation) -> Self {
+        self.transforms.push(Transform::CalendarAggregate(unit, aggregation));
+        self
+    }
+
+    /// Remove the seasonal component with the given period
+    ///
+    /// Series too short to decompose pass through unchanged.
+    pub fn deseasonalize(mut self, period: usize) -> Self {
+        self.transforms.push(Transform::Deseasonalize(period));
+        self
+    }
+
+    /// Smooth with a time-weighted average over a trailing window
+    ///
+    /// Uses the timestamps in `x` (milliseconds), so irregular sampling
+    /// does not skew the result.
+    pub fn time_weighted_average(mut self, window_ms: f64) -> Self {
+        self.transforms.push(Transform::TimeWeightedAverage(window_ms));
+        self
+    }
+
+    /// Smooth exponentially, halving the weight of history every `half_life_ms`
+    pub fn exponential_decay(mut self, half_life_ms: f64) -> Self {
+        self.transforms.push(Transform::ExponentialDecay(half_life_ms));
+        self
+    }
+
+    /// Replace values with their rate of change per second
+    pub fn rate_per_second(mut self) -> Self {
+        self.transforms.push(Transform::RatePerSecond);
+        self
+    }
+
+    /// Smooth with an exponential moving average (`alpha` in (0, 1])
+    pub fn ema(mut self, alpha: f64) -> Self {
+        self.transforms.push(Transform::ExponentialMovingAverage(alpha));
+        self
+    }
+
+    /// Replace values with the minimum of the last `window` points
+    pub fn rolling_min(mut self, window: usize) -> Self {
+        self.transforms.push(Transform::RollingMin(window));
+        self
+    }
+
+    /// Replace values with the maximum of the last `window` points
+    pub fn rolling_max(mut self, window: usize) -> Self {
+        self.transforms.push(Transform::RollingMax(window));
+        self
+    }
+
+    /// Replace values with the standard deviation of the last `window` points
+    pub fn rolling_std_dev(mut self, window: usize) -> Self {
+        self.transforms.push(Transform::RollingStdDev(window));
+        self
+    }
+
+    /// Downsample to at most `threshold` points, keeping the line's shape
+    ///
+    /// Put this last, after smoothing, with a threshold around the plot
+    /// width in pixels.
+    pub fn lttb(mut self, threshold: usize) -> Self {
+        self.transforms.push(Transform::Lttb(threshold));
+        self
+    }
+
     /// Apply all transforms to data
     pub fn apply(&self, data: &[DataPoint]) -> Vec<DataPoint> {
         let mut result: Vec<DataPoint> = data.to_vec();
@@ -298,6 +402,22 @@ impl DataPipeline {
             Transform::Dedupe => {
                 Self::apply_dedupe(data)
             }
+            Transform::CalendarAggregate(unit, aggregation) => {
+                aggregate_by_calendar(data, *unit, *aggregation)
+            }
+            Transform::Deseasonalize(period) => {
+                SeasonalDecomposition::decompose(data, *period)
+                    .map(|parts| parts.adjusted())
+                    .unwrap_or_else(|_| data.to_vec())
+            }
+            Transform::TimeWeightedAverage(window) => time_weighted_average(data, *window),
+            Transform::ExponentialDecay(half_life) => exponential_decay(data, *half_life),
+            Transform::RatePerSecond => rate_per_second(data),
+            Transform::ExponentialMovingAverage(alpha) => exponential_moving_average(data, *alpha),
+            Transform::RollingMin(window) => rolling_min(data, *window),
+            Transform::RollingMax(window) => rolling_max(data, *window),
+            Transform::RollingStdDev(window) => rolling_std_dev(data, *window),
+            Transform::Lttb(threshold) => lttb(data, *threshold),
         }
     }
 
@@ -414,6 +534,15 @@ pub enum Aggregation {
 }
 
 impl Aggregation {
+    /// Apply aggregation to the present entries of a dataset
+    ///
+    /// Masked-out and non-finite entries are skipped, so `Count` counts
+    /// only real values and `Mean` is not pulled toward zero.
+    pub fn apply_dataset(&self, dataset: &Dataset) -> Option<f64> {
+        let present: Vec<DataPoint> = dataset.valid_points().map(|(_, p)| p.clone()).collect();
+        self.apply(&present)
+    }
+
     /// Apply aggregation to data
     pub fn apply(&self, data: &[DataPoint]) -> Option<f64> {
         if data.is_empty() {
@@ -453,6 +582,49 @@ impl Aggregation {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_aggregation_skips_missing() {
+        let ds = Dataset::new("t").with_optional_data(vec![Some(2.0), None, Some(0.0), Some(4.0)]);
+        assert_eq!(Aggregation::Count.apply_dataset(&ds), Some(3.0));
+        assert_eq!(Aggregation::Mean.apply_dataset(&ds), Some(2.0));
+        assert_eq!(Aggregation::Min.apply_dataset(&ds), Some(0.0));
+        assert_eq!(Aggregation::Last.apply_dataset(&Dataset::new("e").with_optional_data(vec![None])), None);
+    }
+
+    #[test]
+    fn test_pipeline_time_aware_transforms() {
+        let data = vec![
+            DataPoint::new(0.0, 0.0),
+            DataPoint::new(1_000.0, 10.0),
+            DataPoint::new(3_000.0, 30.0),
+        ];
+        let rate = DataPipeline::new().rate_per_second().apply(&data);
+        assert_eq!(rate.len(), 2);
+        assert!(rate.iter().all(|p| (p.y - 10.0).abs() < 1e-9));
+
+        let smoothed = DataPipeline::new()
+            .time_weighted_average(2_000.0)
+            .apply(&data);
+        assert!((smoothed[2].y - 10.0).abs() < 1e-9);
+
+        let decayed = DataPipeline::new().exponential_decay(1_000.0).apply(&data);
+        assert!((decayed[1].y - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pipeline_windowed_and_downsampled() {
+        let data: Vec<DataPoint> = (0..10_000).map(|i| DataPoint::new(i as f64, (i % 7) as f64)).collect();
+        let result = DataPipeline::new().rolling_max(7).lttb(500).apply(&data);
+        assert_eq!(result.len(), 500);
+        assert!(result[1..].iter().all(|p| p.y == 6.0));
+
+        let spread = DataPipeline::new().rolling_min(2).rolling_std_dev(3).apply(&sample_data());
+        assert!((spread[4].y - (200.0f64 / 3.0).sqrt()).abs() < 1e-9);
+
+        let smoothed = DataPipeline::new().ema(0.5).apply(&sample_data());
+        assert_eq!(smoothed[1].y, 15.0);
+    }
+
     fn sample_data() -> Vec<DataPoint> {
         vec![
             DataPoint::from_y(10.0),
@@ -634,6 +806,37 @@ mod tests {
         assert_eq!(result[1].y, 100.0);
     }
 
+    #[test]
+    fn test_calendar_aggregate() {
+        // 2024-01-01T00:00:00Z plus whole hours
+        let base = 1_704_067_200_000.0;
+        let data = vec![
+            DataPoint::new(base + 3_600_000.0, 4.0),
+            DataPoint::new(base + 86_400_000.0 + 3_600_000.0, 6.0),
+        ];
+        let pipeline = DataPipeline::new()
+            .aggregate_calendar(CalendarUnit::HourOfDay, Aggregation::Mean);
+        let result = pipeline.apply(&data);
+
+        assert_eq!(result.len(), 24);
+        assert_eq!(result[1].y, 5.0);
+    }
+
+    #[test]
+    fn test_deseasonalize() {
+        let data: Vec<DataPoint> = (0..12)
+            .map(|i| DataPoint::from_y(if i % 3 == 0 { 8.0 } else { 5.0 }))
+            .collect();
+        let result = DataPipeline::new().deseasonalize(3).apply(&data);
+
+        assert_eq!(result.len(), 12);
+        assert!(result.iter().all(|p| (p.y - 6.0).abs() < 1e-9));
+
+        // Too short to decompose: passthrough
+        let short = DataPipeline::new().deseasonalize(10).apply(&data);
+        assert_eq!(short, data);
+    }
+
     #[test]
     fn test_aggregation_sum() {
         let result = Aggregation::Sum.apply(&sample_data());
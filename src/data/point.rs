@@ -16,6 +16,14 @@ use serde::{Deserialize, Serialize};
 ///
 /// // Bubble chart point with radius
 /// let p3 = DataPoint::bubble(1.0, 2.0, 10.0);
+///
+/// // Multi-dimensional point: every channel travels with its record
+/// let p4 = DataPoint::new(1.0, 2.0)
+///     .with_size(350.0)
+///     .with_color_value(0.8)
+///     .with_category("Europe")
+///     .with_id("fr");
+/// assert_eq!(p4.key(), Some("fr"));
 /// ```
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct DataPoint {
@@ -36,6 +44,18 @@ pub struct DataPoint {
 
     /// Additional metadata (for tooltips)
     pub meta: Option<String>,
+
+    /// Value mapped through a size scale (e.g. population for bubble area)
+    pub size: Option<f64>,
+
+    /// Value mapped through a color scale
+    pub color_value: Option<f64>,
+
+    /// Category key for grouping, coloring and filtering
+    pub category: Option<String>,
+
+    /// Stable identity across updates (joins, selection, filtering)
+    pub id: Option<String>,
 }
 
 impl DataPoint {
@@ -84,6 +104,35 @@ impl DataPoint {
         self
     }
 
+    /// Builder: set size value
+    pub fn with_size(mut self, size: f64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Builder: set color value
+    pub fn with_color_value(mut self, value: f64) -> Self {
+        self.color_value = Some(value);
+        self
+    }
+
+    /// Builder: set category
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Builder: set id
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Identity of the point: its id, or its label if it has no id
+    pub fn key(&self) -> Option<&str> {
+        self.id.as_deref().or(self.label.as_deref())
+    }
+
     /// Get effective X value (or index if None)
     pub fn x_or(&self, index: usize) -> f64 {
         self.x.unwrap_or(index as f64)
@@ -188,6 +237,33 @@ mod tests {
         assert!(!DataPoint::from_y(f64::INFINITY).is_valid());
     }
 
+    #[test]
+    fn test_dimensions() {
+        let point = DataPoint::bubble(1.0, 2.0, 3.0)
+            .with_size(40.0)
+            .with_color_value(-0.5)
+            .with_category("a")
+            .with_label("Alpha");
+        assert_eq!(point.size, Some(40.0));
+        assert_eq!(point.color_value, Some(-0.5));
+        assert_eq!(point.category.as_deref(), Some("a"));
+        assert_eq!(point.key(), Some("Alpha"));
+        assert_eq!(point.with_id("1").key(), Some("1"));
+        assert_eq!(DataPoint::from_y(1.0).key(), None);
+    }
+
+    #[test]
+    fn test_serde_missing_dimensions() {
+        // Points serialized before the extra dimensions existed still load
+        let json = r#"{"x":1.0,"y":2.0,"y_min":null,"r":null,"label":"a","meta":null}"#;
+        let parsed: DataPoint = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed, DataPoint::new(1.0, 2.0).with_label("a"));
+
+        let original = DataPoint::new(1.0, 2.0).with_size(3.0).with_category("c").with_id("p");
+        let json = serde_json::to_string(&original).unwrap();
+        assert_eq!(serde_json::from_str::<DataPoint>(&json).unwrap(), original);
+    }
+
     #[test]
     fn test_serde_roundtrip() {
         let original = DataPoint::new(1.0, 2.0).with_label("test");
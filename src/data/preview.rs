@@ -0,0 +1,307 @@
+//! Min/max envelope preview of large series
+//!
+//! A [`PreviewSeries`] keeps a bounded summary of an arbitrarily long,
+//! append-only series for minimap and focus+context strips: points are
+//! grouped into consecutive buckets and only each bucket's minimum and
+//! maximum are kept, so spikes survive downsampling. When the buckets fill
+//! up, neighbours are merged pairwise and the bucket size doubles, keeping
+//! appends O(1) amortized no matter how much data streams in.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::PreviewSeries;
+//!
+//! let mut preview = PreviewSeries::new(200);
+//! for i in 0..100_000 {
+//!     let y = if i == 54_321 { 1000.0 } else { (i as f64 * 0.01).sin() };
+//!     preview.push(i as f64, y);
+//! }
+//!
+//! let points = preview.points();
+//! assert!(points.len() <= 200);
+//! // The single spike is still visible
+//! assert!(points.iter().any(|p| p.y == 1000.0));
+//! ```
+
+use super::DataPoint;
+
+/// Summary of a run of consecutive points
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnvelopeBucket {
+    /// X of the first point
+    pub x0: f64,
+    /// X of the last point
+    pub x1: f64,
+    /// Point with the lowest y, as (x, y)
+    pub min: (f64, f64),
+    /// Point with the highest y, as (x, y)
+    pub max: (f64, f64),
+    /// Number of points summarized
+    pub count: usize,
+}
+
+impl EnvelopeBucket {
+    fn new(x: f64, y: f64) -> Self {
+        Self {
+            x0: x,
+            x1: x,
+            min: (x, y),
+            max: (x, y),
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, x: f64, y: f64) {
+        self.x1 = x;
+        if y < self.min.1 {
+            self.min = (x, y);
+        }
+        if y > self.max.1 {
+            self.max = (x, y);
+        }
+        self.count += 1;
+    }
+
+    fn merge(&mut self, next: &EnvelopeBucket) {
+        self.x1 = next.x1;
+        if next.min.1 < self.min.1 {
+            self.min = next.min;
+        }
+        if next.max.1 > self.max.1 {
+            self.max = next.max;
+        }
+        self.count += next.count;
+    }
+
+    /// Middle of the bucket's x range
+    pub fn x_mid(&self) -> f64 {
+        (self.x0 + self.x1) / 2.0
+    }
+}
+
+/// Fixed-size min/max envelope of a growing series
+#[derive(Clone, Debug)]
+pub struct PreviewSeries {
+    /// Maximum number of buckets (half the point budget)
+    capacity: usize,
+    /// Points per full bucket
+    bucket_size: usize,
+    /// Buckets in x order
+    buckets: Vec<EnvelopeBucket>,
+    /// Points pushed so far (including skipped non-finite ones)
+    len: usize,
+}
+
+impl PreviewSeries {
+    /// Create a preview producing at most `max_points` points (minimum 2)
+    pub fn new(max_points: usize) -> Self {
+        let capacity = (max_points / 2).max(1);
+        Self {
+            capacity,
+            bucket_size: 1,
+            buckets: Vec::with_capacity(capacity),
+            len: 0,
+        }
+    }
+
+    /// Create a preview of existing points
+    ///
+    /// Points without an x value use their index.
+    pub fn from_points(max_points: usize, points: &[DataPoint]) -> Self {
+        let mut preview = Self::new(max_points);
+        preview.extend(points);
+        preview
+    }
+
+    /// Append a point; x values are expected to be non-decreasing
+    ///
+    /// Points with a non-finite y are counted but not summarized.
+    pub fn push(&mut self, x: f64, y: f64) {
+        self.len += 1;
+        if !y.is_finite() {
+            return;
+        }
+        if let Some(last) = self.buckets.last_mut() {
+            if last.count < self.bucket_size {
+                last.add(x, y);
+                return;
+            }
+        }
+        if self.buckets.len() == self.capacity {
+            self.compact();
+            if let Some(last) = self.buckets.last_mut() {
+                if last.count < self.bucket_size {
+                    last.add(x, y);
+                    return;
+                }
+            }
+        }
+        self.buckets.push(EnvelopeBucket::new(x, y));
+    }
+
+    /// Append data points
+    ///
+    /// Points without an x value use their index in the whole series.
+    pub fn extend<'a>(&mut self, points: impl IntoIterator<Item = &'a DataPoint>) {
+        for point in points {
+            let x = point.x.unwrap_or(self.len as f64);
+            self.push(x, point.y);
+        }
+    }
+
+    /// Merge neighbouring buckets pairwise and double the bucket size
+    fn compact(&mut self) {
+        self.bucket_size *= 2;
+        let mut merged = Vec::with_capacity(self.capacity);
+        for pair in self.buckets.chunks(2) {
+            let mut bucket = pair[0];
+            if let Some(next) = pair.get(1) {
+                bucket.merge(next);
+            }
+            merged.push(bucket);
+        }
+        self.buckets = merged;
+    }
+
+    /// Number of points pushed
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if no points were pushed
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Maximum number of points returned by [`points`](Self::points)
+    pub fn max_points(&self) -> usize {
+        self.capacity * 2
+    }
+
+    /// Number of source points per full bucket
+    pub fn bucket_size(&self) -> usize {
+        self.bucket_size
+    }
+
+    /// Get the buckets
+    pub fn buckets(&self) -> &[EnvelopeBucket] {
+        &self.buckets
+    }
+
+    /// Preview polyline: each bucket's min and max, in x order
+    pub fn points(&self) -> Vec<DataPoint> {
+        let mut points = Vec::with_capacity(self.buckets.len() * 2);
+        for bucket in &self.buckets {
+            let (first, second) = if bucket.min.0 <= bucket.max.0 {
+                (bucket.min, bucket.max)
+            } else {
+                (bucket.max, bucket.min)
+            };
+            points.push(DataPoint::new(first.0, first.1));
+            if bucket.min != bucket.max {
+                points.push(DataPoint::new(second.0, second.1));
+            }
+        }
+        points
+    }
+
+    /// Preview band: one point per bucket at its x midpoint, with `y` the
+    /// maximum and `y_min` the minimum
+    pub fn envelope(&self) -> Vec<DataPoint> {
+        self.buckets
+            .iter()
+            .map(|b| DataPoint {
+                x: Some(b.x_mid()),
+                ..DataPoint::range(b.min.1, b.max.1)
+            })
+            .collect()
+    }
+
+    /// Overall (min, max) of y, if any points were summarized
+    pub fn y_extent(&self) -> Option<(f64, f64)> {
+        let first = self.buckets.first()?;
+        Some(self.buckets.iter().fold((first.min.1, first.max.1), |(lo, hi), b| {
+            (lo.min(b.min.1), hi.max(b.max.1))
+        }))
+    }
+
+    /// Overall (first, last) x, if any points were summarized
+    pub fn x_extent(&self) -> Option<(f64, f64)> {
+        Some((self.buckets.first()?.x0, self.buckets.last()?.x1))
+    }
+
+    /// Remove all points, keeping the point budget
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.bucket_size = 1;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_series_kept_exactly() {
+        let mut preview = PreviewSeries::new(10);
+        for (i, y) in [3.0, 1.0, 4.0].into_iter().enumerate() {
+            preview.push(i as f64, y);
+        }
+        let ys: Vec<f64> = preview.points().iter().map(|p| p.y).collect();
+        assert_eq!(ys, vec![3.0, 1.0, 4.0]);
+        assert_eq!(preview.bucket_size(), 1);
+    }
+
+    #[test]
+    fn test_compaction_bounds_size_and_keeps_extremes() {
+        let mut preview = PreviewSeries::new(8);
+        for i in 0..1000 {
+            let y = match i {
+                137 => -50.0,
+                862 => 75.0,
+                _ => (i % 7) as f64,
+            };
+            preview.push(i as f64, y);
+        }
+        assert_eq!(preview.len(), 1000);
+        assert!(preview.buckets().len() <= 4);
+        assert!(preview.points().len() <= preview.max_points());
+        assert_eq!(preview.y_extent(), Some((-50.0, 75.0)));
+        assert_eq!(preview.x_extent(), Some((0.0, 999.0)));
+        assert_eq!(preview.buckets().iter().map(|b| b.count).sum::<usize>(), 1000);
+
+        let points = preview.points();
+        assert!(points.windows(2).all(|w| w[0].x <= w[1].x));
+    }
+
+    #[test]
+    fn test_incremental_matches_batch() {
+        let data: Vec<DataPoint> = (0..333).map(|i| DataPoint::from_y(((i * 37) % 101) as f64)).collect();
+        let batch = PreviewSeries::from_points(20, &data);
+
+        let mut streamed = PreviewSeries::new(20);
+        for chunk in data.chunks(17) {
+            streamed.extend(chunk);
+        }
+        assert_eq!(streamed.buckets(), batch.buckets());
+
+        let envelope = batch.envelope();
+        assert_eq!(envelope.len(), batch.buckets().len());
+        assert!(envelope.iter().all(|p| p.y_min.unwrap() <= p.y));
+    }
+
+    #[test]
+    fn test_non_finite_skipped() {
+        let mut preview = PreviewSeries::new(4);
+        preview.push(0.0, f64::NAN);
+        preview.push(1.0, 2.0);
+        assert_eq!(preview.len(), 2);
+        assert_eq!(preview.points().len(), 1);
+
+        preview.clear();
+        assert!(preview.is_empty());
+        assert_eq!(preview.y_extent(), None);
+    }
+}
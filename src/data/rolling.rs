@@ -0,0 +1,185 @@
+//! Time-aware rolling statistics for irregularly sampled series
+//!
+//! Monitoring data rarely arrives at a fixed interval, so index-based windows
+//! (like [`DataPipeline::moving_average`](super::DataPipeline::moving_average))
+//! over- or under-weight bursts and gaps. The functions here weight every
+//! sample by the time it was in effect instead.
+//!
+//! Points carry their timestamp in `x` as milliseconds since the Unix epoch
+//! and must be sorted by `x`. Points without a timestamp or with a
+//! non-finite value are skipped.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::{rate_per_second, time_weighted_average, DataPoint};
+//!
+//! // 10 for 9 seconds, then 100 for 1 second
+//! let data = vec![
+//!     DataPoint::new(0.0, 10.0),
+//!     DataPoint::new(9_000.0, 100.0),
+//!     DataPoint::new(10_000.0, 100.0),
+//! ];
+//!
+//! let avg = time_weighted_average(&data, 10_000.0);
+//! assert!((avg[2].y - 19.0).abs() < 1e-9);
+//!
+//! let rate = rate_per_second(&data);
+//! assert!((rate[0].y - 10.0).abs() < 1e-9); // +90 over 9 s
+//! ```
+
+use super::DataPoint;
+
+/// Timestamped samples usable for time-based statistics
+fn samples(data: &[DataPoint]) -> Vec<(f64, &DataPoint)> {
+    data.iter()
+        .filter_map(|p| p.x.filter(|x| x.is_finite()).map(|x| (x, p)))
+        .filter(|(_, p)| p.y.is_finite())
+        .collect()
+}
+
+fn with_y(point: &DataPoint, y: f64) -> DataPoint {
+    let mut point = point.clone();
+    point.y = y;
+    point
+}
+
+/// Time-weighted average over a trailing window of `window_ms`
+///
+/// Each sample holds its value until the next sample (step interpolation),
+/// and the average at a point covers `[t - window_ms, t]`. The sample in
+/// effect at the window start contributes for the part that overlaps.
+pub fn time_weighted_average(data: &[DataPoint], window_ms: f64) -> Vec<DataPoint> {
+    let samples = samples(data);
+    let window = window_ms.max(0.0);
+    let mut left = 0;
+
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, &(t, point))| {
+            let start = t - window;
+            while left < i && samples[left + 1].0 <= start {
+                left += 1;
+            }
+
+            let mut weighted = 0.0;
+            let mut total = 0.0;
+            for j in left..i {
+                let (t0, p) = samples[j];
+                let t1 = samples[j + 1].0;
+                let span = t1 - t0.max(start);
+                if span > 0.0 {
+                    weighted += p.y * span;
+                    total += span;
+                }
+            }
+
+            with_y(point, if total > 0.0 { weighted / total } else { point.y })
+        })
+        .collect()
+}
+
+/// Exponentially weighted moving average with decay driven by elapsed time
+///
+/// The previous average loses half its weight every `half_life_ms`, so a
+/// long gap lets a new sample dominate while a burst of samples moves the
+/// average only a little.
+pub fn exponential_decay(data: &[DataPoint], half_life_ms: f64) -> Vec<DataPoint> {
+    let samples = samples(data);
+    let mut result = Vec::with_capacity(samples.len());
+    let mut state: Option<(f64, f64)> = None;
+
+    for (t, point) in samples {
+        let value = match state {
+            Some((prev_t, prev)) if half_life_ms > 0.0 => {
+                let dt = (t - prev_t).max(0.0);
+                let alpha = 1.0 - (-dt / half_life_ms).exp2();
+                prev + alpha * (point.y - prev)
+            }
+            _ => point.y,
+        };
+        state = Some((t, value));
+        result.push(with_y(point, value));
+    }
+
+    result
+}
+
+/// Rate of change per second between consecutive samples
+///
+/// Each output point sits at the later sample's timestamp; pairs with no
+/// elapsed time are skipped, so the result has at most `n - 1` points.
+pub fn rate_per_second(data: &[DataPoint]) -> Vec<DataPoint> {
+    samples(data)
+        .windows(2)
+        .filter_map(|pair| {
+            let (t0, a) = pair[0];
+            let (t1, b) = pair[1];
+            let seconds = (t1 - t0) / 1000.0;
+            (seconds > 0.0).then(|| with_y(b, (b.y - a.y) / seconds))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_weighted_average_irregular() {
+        let data = vec![
+            DataPoint::new(0.0, 0.0),
+            DataPoint::new(1_000.0, 10.0),
+            DataPoint::new(1_500.0, 20.0),
+            DataPoint::new(4_000.0, 0.0),
+        ];
+        let avg = time_weighted_average(&data, 2_000.0);
+        assert_eq!(avg.len(), 4);
+        assert_eq!(avg[0].y, 0.0);
+        // Window [-1000, 1000]: only value 0 was in effect
+        assert_eq!(avg[1].y, 0.0);
+        // Window [-500, 1500]: 0 for 1000ms, 10 for 500ms
+        assert!((avg[2].y - 10.0 / 3.0).abs() < 1e-9);
+        // Window [2000, 4000]: 20 held for the full window
+        assert!((avg[3].y - 20.0).abs() < 1e-9);
+        assert_eq!(avg[3].x, Some(4_000.0));
+    }
+
+    #[test]
+    fn test_exponential_decay_by_elapsed_time() {
+        let data = vec![
+            DataPoint::new(0.0, 0.0),
+            DataPoint::new(1_000.0, 100.0),
+            DataPoint::new(1_000.0, 100.0),
+            DataPoint::new(11_000.0, 0.0),
+        ];
+        let smoothed = exponential_decay(&data, 1_000.0);
+        // One half-life: half way to the new value
+        assert!((smoothed[1].y - 50.0).abs() < 1e-9);
+        // No elapsed time: unchanged
+        assert!((smoothed[2].y - 50.0).abs() < 1e-9);
+        // Ten half-lives: almost entirely the new value
+        assert!(smoothed[3].y < 0.1);
+
+        let raw = exponential_decay(&data, 0.0);
+        assert_eq!(raw[1].y, 100.0);
+    }
+
+    #[test]
+    fn test_rate_per_second_skips_invalid() {
+        let data = vec![
+            DataPoint::new(0.0, 5.0),
+            DataPoint::from_y(1_000.0), // no timestamp
+            DataPoint::new(500.0, f64::NAN),
+            DataPoint::new(2_000.0, 9.0),
+            DataPoint::new(2_000.0, 12.0),
+            DataPoint::new(2_250.0, 8.0),
+        ];
+        let rate = rate_per_second(&data);
+        assert_eq!(rate.len(), 2);
+        assert!((rate[0].y - 2.0).abs() < 1e-9);
+        assert_eq!(rate[0].x, Some(2_000.0));
+        assert!((rate[1].y + 16.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,476 @@
+//! Calendar aggregation and seasonal decomposition for time series
+//!
+//! Time series points are expected to carry their timestamp in `x` as
+//! milliseconds since the Unix epoch (the same convention used by
+//! [`TimeScale`](crate::scale::TimeScale)). All calendar math is done in UTC.
+//!
+//! # Calendar Heatmap Example
+//!
+//! ```
+//! use makepad_d3::data::{Aggregation, CalendarMatrix, CalendarUnit, DataPoint};
+//!
+//! // Two readings on Monday 2024-01-01 at 09:00 and 10:00 UTC
+//! let data = vec![
+//!     DataPoint::new(1_704_099_600_000.0, 5.0),
+//!     DataPoint::new(1_704_103_200_000.0, 7.0),
+//! ];
+//!
+//! // Rows = day of week, columns = hour of day
+//! let matrix = CalendarMatrix::from_points(
+//!     &data,
+//!     CalendarUnit::DayOfWeek,
+//!     CalendarUnit::HourOfDay,
+//!     Aggregation::Sum,
+//! );
+//!
+//! assert_eq!(matrix.get(0, 9), Some(5.0));
+//! assert_eq!(matrix.get(0, 10), Some(7.0));
+//! assert_eq!(matrix.get(1, 9), None);
+//! ```
+//!
+//! # Decomposition Example
+//!
+//! ```
+//! use makepad_d3::data::{DataPoint, SeasonalDecomposition};
+//!
+//! let data: Vec<DataPoint> = (0..28)
+//!     .map(|i| DataPoint::new(i as f64, i as f64 + [0.0, 3.0, 0.0, -3.0][i % 4]))
+//!     .collect();
+//!
+//! let parts = SeasonalDecomposition::decompose(&data, 4).unwrap();
+//! assert_eq!(parts.trend.len(), data.len());
+//! assert!((parts.seasonal[1].y - 3.0).abs() < 0.5);
+//! ```
+
+use chrono::{DateTime, Datelike, Timelike, Utc};
+
+use super::{Aggregation, DataPoint};
+use crate::error::{D3Error, D3Result};
+
+const WEEKDAY_LABELS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTH_LABELS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Calendar unit used to bucket timestamps
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CalendarUnit {
+    /// Hour of the day (0-23)
+    HourOfDay,
+    /// Day of the week, Monday first (0-6)
+    DayOfWeek,
+    /// Day of the month (0-30 for the 1st to the 31st)
+    DayOfMonth,
+    /// Month of the year (0-11)
+    Month,
+}
+
+impl CalendarUnit {
+    /// Number of buckets for this unit
+    pub fn bucket_count(&self) -> usize {
+        match self {
+            CalendarUnit::HourOfDay => 24,
+            CalendarUnit::DayOfWeek => 7,
+            CalendarUnit::DayOfMonth => 31,
+            CalendarUnit::Month => 12,
+        }
+    }
+
+    /// Bucket index for a timestamp in milliseconds, or None if it is not representable
+    pub fn bucket(&self, timestamp_ms: f64) -> Option<usize> {
+        if !timestamp_ms.is_finite() {
+            return None;
+        }
+        let dt = DateTime::<Utc>::from_timestamp_millis(timestamp_ms.floor() as i64)?;
+        Some(self.bucket_of(&dt))
+    }
+
+    /// Bucket index for a UTC datetime
+    pub fn bucket_of(&self, dt: &DateTime<Utc>) -> usize {
+        match self {
+            CalendarUnit::HourOfDay => dt.hour() as usize,
+            CalendarUnit::DayOfWeek => dt.weekday().num_days_from_monday() as usize,
+            CalendarUnit::DayOfMonth => dt.day0() as usize,
+            CalendarUnit::Month => dt.month0() as usize,
+        }
+    }
+
+    /// Display label for a bucket index
+    pub fn label(&self, index: usize) -> String {
+        match self {
+            CalendarUnit::HourOfDay => format!("{:02}", index),
+            CalendarUnit::DayOfWeek => WEEKDAY_LABELS.get(index).unwrap_or(&"").to_string(),
+            CalendarUnit::DayOfMonth => (index + 1).to_string(),
+            CalendarUnit::Month => MONTH_LABELS.get(index).unwrap_or(&"").to_string(),
+        }
+    }
+
+    /// Labels for all buckets in order
+    pub fn labels(&self) -> Vec<String> {
+        (0..self.bucket_count()).map(|i| self.label(i)).collect()
+    }
+}
+
+/// Aggregate time series points into one point per calendar bucket
+///
+/// The result has exactly [`CalendarUnit::bucket_count`] points with `x` set
+/// to the bucket index and `label` set to the bucket name. Buckets without
+/// data get a NaN `y` so the output stays aligned with the unit's labels.
+pub fn aggregate_by_calendar(
+    data: &[DataPoint],
+    unit: CalendarUnit,
+    aggregation: Aggregation,
+) -> Vec<DataPoint> {
+    let mut buckets: Vec<Vec<DataPoint>> = vec![Vec::new(); unit.bucket_count()];
+    for point in data {
+        if let Some(b) = point.x.and_then(|x| unit.bucket(x)) {
+            buckets[b].push(point.clone());
+        }
+    }
+
+    buckets
+        .iter()
+        .enumerate()
+        .map(|(i, bucket)| {
+            let y = aggregation.apply(bucket).unwrap_or(f64::NAN);
+            DataPoint::new(i as f64, y).with_label(unit.label(i))
+        })
+        .collect()
+}
+
+/// A two-dimensional calendar aggregation, suitable for heatmaps
+///
+/// Rows and columns are each indexed by a [`CalendarUnit`] bucket, e.g.
+/// day-of-week × hour-of-day for a "punch card" heatmap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarMatrix {
+    /// Unit used for rows
+    pub row_unit: CalendarUnit,
+    /// Unit used for columns
+    pub col_unit: CalendarUnit,
+    /// Aggregated values, `values[row][col]`; None where a cell has no data
+    pub values: Vec<Vec<Option<f64>>>,
+    /// Number of points that fell into each cell
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl CalendarMatrix {
+    /// Aggregate time series points into a calendar matrix
+    pub fn from_points(
+        data: &[DataPoint],
+        row_unit: CalendarUnit,
+        col_unit: CalendarUnit,
+        aggregation: Aggregation,
+    ) -> Self {
+        let rows = row_unit.bucket_count();
+        let cols = col_unit.bucket_count();
+        let mut cells: Vec<Vec<Vec<DataPoint>>> = vec![vec![Vec::new(); cols]; rows];
+
+        for point in data {
+            let dt = match point
+                .x
+                .filter(|x| x.is_finite())
+                .and_then(|x| DateTime::<Utc>::from_timestamp_millis(x.floor() as i64))
+            {
+                Some(dt) => dt,
+                None => continue,
+            };
+            cells[row_unit.bucket_of(&dt)][col_unit.bucket_of(&dt)].push(point.clone());
+        }
+
+        let values = cells
+            .iter()
+            .map(|row| row.iter().map(|cell| aggregation.apply(cell)).collect())
+            .collect();
+        let counts = cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.len()).collect())
+            .collect();
+
+        Self { row_unit, col_unit, values, counts }
+    }
+
+    /// Number of rows
+    pub fn rows(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Number of columns
+    pub fn cols(&self) -> usize {
+        self.values.first().map_or(0, |r| r.len())
+    }
+
+    /// Get the aggregated value of a cell
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        self.values.get(row).and_then(|r| r.get(col)).copied().flatten()
+    }
+
+    /// Row labels
+    pub fn row_labels(&self) -> Vec<String> {
+        self.row_unit.labels()
+    }
+
+    /// Column labels
+    pub fn col_labels(&self) -> Vec<String> {
+        self.col_unit.labels()
+    }
+
+    /// Min and max over populated cells, for building a color scale domain
+    pub fn extent(&self) -> Option<(f64, f64)> {
+        let mut iter = self.values.iter().flatten().flatten().copied();
+        let first = iter.next()?;
+        Some(iter.fold((first, first), |(lo, hi), v| (lo.min(v), hi.max(v))))
+    }
+}
+
+/// Additive seasonal-trend decomposition of a regularly sampled series
+///
+/// The input is split into `trend + seasonal + residual`. The trend is a
+/// centered moving average over one period, the seasonal component is the
+/// mean detrended value at each phase of the period (centered on zero), and
+/// the residual is whatever is left. All three series keep the input's `x`
+/// values so they can be plotted in aligned facets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeasonalDecomposition {
+    /// Season length in samples
+    pub period: usize,
+    /// Smoothed long-term component
+    pub trend: Vec<DataPoint>,
+    /// Repeating component with period `period`
+    pub seasonal: Vec<DataPoint>,
+    /// Remainder after removing trend and seasonality
+    pub residual: Vec<DataPoint>,
+}
+
+impl SeasonalDecomposition {
+    /// Decompose a series with the given period
+    ///
+    /// Returns an error if the period is shorter than 2 or the series does
+    /// not cover at least two full periods.
+    pub fn decompose(data: &[DataPoint], period: usize) -> D3Result<Self> {
+        if period < 2 {
+            return Err(D3Error::invalid_data("seasonal period must be at least 2"));
+        }
+        if data.len() < period * 2 {
+            return Err(D3Error::invalid_data(format!(
+                "seasonal decomposition needs at least {} points, got {}",
+                period * 2,
+                data.len()
+            )));
+        }
+        if data.iter().any(|p| !p.y.is_finite()) {
+            return Err(D3Error::invalid_data("seasonal decomposition requires finite values"));
+        }
+
+        let values: Vec<f64> = data.iter().map(|p| p.y).collect();
+        let n = values.len();
+        let half = period / 2;
+
+        // Seasonal indices come from positions where a full-period window fits,
+        // so the shrinking edge windows cannot leak seasonality into them
+        let initial_trend = centered_moving_average(&values, period);
+        let mut phase_sums = vec![0.0; period];
+        let mut phase_counts = vec![0usize; period];
+        for i in half..n - half {
+            phase_sums[i % period] += values[i] - initial_trend[i];
+            phase_counts[i % period] += 1;
+        }
+        let mut indices: Vec<f64> = phase_sums
+            .iter()
+            .zip(&phase_counts)
+            .map(|(s, &c)| if c > 0 { s / c as f64 } else { 0.0 })
+            .collect();
+        let mean_index = indices.iter().sum::<f64>() / period as f64;
+        for idx in &mut indices {
+            *idx -= mean_index;
+        }
+
+        let seasonal_values: Vec<f64> = (0..n).map(|i| indices[i % period]).collect();
+
+        // Re-estimate the trend on the deseasonalized series; over full
+        // windows this matches the initial trend, and at the edges it avoids
+        // picking up a partial season
+        let deseasonalized: Vec<f64> = values
+            .iter()
+            .zip(&seasonal_values)
+            .map(|(v, s)| v - s)
+            .collect();
+        let trend = centered_moving_average(&deseasonalized, period);
+
+        let aligned = |i: usize, y: f64| {
+            let mut point = data[i].clone();
+            point.x = Some(data[i].x_or(i));
+            point.y = y;
+            point.y_min = None;
+            point
+        };
+        Ok(Self {
+            period,
+            trend: trend.iter().enumerate().map(|(i, &t)| aligned(i, t)).collect(),
+            seasonal: seasonal_values.iter().enumerate().map(|(i, &s)| aligned(i, s)).collect(),
+            residual: values
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| aligned(i, v - trend[i] - seasonal_values[i]))
+                .collect(),
+        })
+    }
+
+    /// Seasonally adjusted series (input minus the seasonal component)
+    pub fn adjusted(&self) -> Vec<DataPoint> {
+        self.trend
+            .iter()
+            .zip(&self.residual)
+            .map(|(t, r)| {
+                let mut point = t.clone();
+                point.y = t.y + r.y;
+                point
+            })
+            .collect()
+    }
+}
+
+/// Centered moving average over `window` samples
+///
+/// Even windows use the classical 2×m average so the result stays centered.
+/// Near the edges the window shrinks symmetrically to the available samples.
+fn centered_moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    let n = values.len();
+    let half = window / 2;
+
+    (0..n)
+        .map(|i| {
+            let reach = half.min(i).min(n - 1 - i);
+            let lo = i - reach;
+            let hi = i + reach;
+            if window % 2 == 0 && reach == half {
+                // 2×m MA: endpoints get half weight
+                let inner: f64 = values[lo + 1..hi].iter().sum();
+                (inner + 0.5 * (values[lo] + values[hi])) / window as f64
+            } else {
+                values[lo..=hi].iter().sum::<f64>() / (hi - lo + 1) as f64
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01T00:00:00Z, a Monday
+    const MONDAY: f64 = 1_704_067_200_000.0;
+    const HOUR: f64 = 3_600_000.0;
+    const DAY: f64 = 24.0 * HOUR;
+
+    #[test]
+    fn test_calendar_unit_buckets() {
+        assert_eq!(CalendarUnit::HourOfDay.bucket(MONDAY + 5.0 * HOUR), Some(5));
+        assert_eq!(CalendarUnit::DayOfWeek.bucket(MONDAY + 2.0 * DAY), Some(2));
+        assert_eq!(CalendarUnit::DayOfMonth.bucket(MONDAY + 9.0 * DAY), Some(9));
+        assert_eq!(CalendarUnit::Month.bucket(MONDAY + 40.0 * DAY), Some(1));
+        assert_eq!(CalendarUnit::Month.bucket(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_calendar_unit_labels() {
+        assert_eq!(CalendarUnit::DayOfWeek.label(6), "Sun");
+        assert_eq!(CalendarUnit::Month.label(0), "Jan");
+        assert_eq!(CalendarUnit::HourOfDay.label(7), "07");
+        assert_eq!(CalendarUnit::DayOfMonth.labels().len(), 31);
+    }
+
+    #[test]
+    fn test_aggregate_by_calendar() {
+        let data = vec![
+            DataPoint::new(MONDAY, 10.0),
+            DataPoint::new(MONDAY + 7.0 * DAY, 20.0),
+            DataPoint::new(MONDAY + DAY, 5.0),
+            DataPoint::from_y(99.0), // no timestamp, ignored
+        ];
+
+        let result = aggregate_by_calendar(&data, CalendarUnit::DayOfWeek, Aggregation::Mean);
+        assert_eq!(result.len(), 7);
+        assert_eq!(result[0].y, 15.0);
+        assert_eq!(result[1].y, 5.0);
+        assert!(result[2].y.is_nan());
+        assert_eq!(result[0].label.as_deref(), Some("Mon"));
+    }
+
+    #[test]
+    fn test_calendar_matrix() {
+        let data = vec![
+            DataPoint::new(MONDAY + 9.0 * HOUR, 1.0),
+            DataPoint::new(MONDAY + 7.0 * DAY + 9.0 * HOUR, 3.0),
+            DataPoint::new(MONDAY + DAY + 14.0 * HOUR, 4.0),
+        ];
+
+        let matrix = CalendarMatrix::from_points(
+            &data,
+            CalendarUnit::DayOfWeek,
+            CalendarUnit::HourOfDay,
+            Aggregation::Sum,
+        );
+
+        assert_eq!(matrix.rows(), 7);
+        assert_eq!(matrix.cols(), 24);
+        assert_eq!(matrix.get(0, 9), Some(4.0));
+        assert_eq!(matrix.counts[0][9], 2);
+        assert_eq!(matrix.get(1, 14), Some(4.0));
+        assert_eq!(matrix.get(3, 3), None);
+        assert_eq!(matrix.extent(), Some((4.0, 4.0)));
+    }
+
+    #[test]
+    fn test_decompose_recovers_components() {
+        let pattern = [2.0, -1.0, 0.5, -1.5];
+        let data: Vec<DataPoint> = (0..40)
+            .map(|i| DataPoint::new(i as f64, 0.5 * i as f64 + pattern[i % 4]))
+            .collect();
+
+        let parts = SeasonalDecomposition::decompose(&data, 4).unwrap();
+        assert_eq!(parts.trend.len(), 40);
+        assert_eq!(parts.seasonal.len(), 40);
+        assert_eq!(parts.residual.len(), 40);
+
+        // Interior trend is exact for a linear signal
+        assert!((parts.trend[20].y - 10.0).abs() < 1e-9);
+        for (i, expected) in pattern.iter().enumerate() {
+            assert!((parts.seasonal[i].y - expected).abs() < 0.2);
+        }
+
+        // Components always sum back to the input
+        for (i, p) in data.iter().enumerate() {
+            let sum = parts.trend[i].y + parts.seasonal[i].y + parts.residual[i].y;
+            assert!((sum - p.y).abs() < 1e-9);
+            assert_eq!(parts.trend[i].x, p.x);
+        }
+    }
+
+    #[test]
+    fn test_decompose_seasonal_centered() {
+        let data: Vec<DataPoint> = (0..21).map(|i| DataPoint::from_y((i % 7) as f64)).collect();
+        let parts = SeasonalDecomposition::decompose(&data, 7).unwrap();
+        let total: f64 = parts.seasonal[..7].iter().map(|p| p.y).sum();
+        assert!(total.abs() < 1e-9);
+        assert_eq!(parts.seasonal[3].x, Some(3.0));
+    }
+
+    #[test]
+    fn test_decompose_errors() {
+        let data: Vec<DataPoint> = (0..5).map(|i| DataPoint::from_y(i as f64)).collect();
+        assert!(SeasonalDecomposition::decompose(&data, 1).is_err());
+        assert!(SeasonalDecomposition::decompose(&data, 3).is_err());
+    }
+
+    #[test]
+    fn test_adjusted_removes_seasonality() {
+        let data: Vec<DataPoint> = (0..24)
+            .map(|i| DataPoint::from_y(10.0 + if i % 2 == 0 { 1.0 } else { -1.0 }))
+            .collect();
+        let parts = SeasonalDecomposition::decompose(&data, 2).unwrap();
+        for p in parts.adjusted() {
+            assert!((p.y - 10.0).abs() < 1e-9);
+        }
+    }
+}
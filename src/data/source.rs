@@ -76,6 +76,9 @@ pub struct DataSourceConfig {
     pub batch_updates: bool,
     /// Batch interval in milliseconds
     pub batch_interval_ms: u64,
+    /// Span of `x` to retain, counted back from the newest point, for
+    /// timestamped streams (0 = unlimited)
+    pub time_window_ms: f64,
 }
 
 impl Default for DataSourceConfig {
@@ -87,6 +90,7 @@ impl Default for DataSourceConfig {
             buffer_size: 100,
             batch_updates: false,
             batch_interval_ms: 16, // ~60fps
+            time_window_ms: 0.0,
         }
     }
 }
@@ -118,6 +122,12 @@ impl DataSourceConfig {
         self
     }
 
+    /// Retain only points whose `x` is within `window_ms` of the newest point
+    pub fn with_time_window_ms(mut self, window_ms: f64) -> Self {
+        self.time_window_ms = if window_ms.is_finite() { window_ms.max(0.0) } else { 0.0 };
+        self
+    }
+
     /// Set auto-reconnect
     pub fn with_auto_reconnect(mut self, auto: bool) -> Self {
         self.auto_reconnect = auto;
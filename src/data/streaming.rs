@@ -19,6 +19,33 @@
 //! // Poll for data in render loop
 //! source.poll();
 //! ```
+//!
+//! # Bounded Buffer Example
+//!
+//! Live charts keep a fixed span of time: points are evicted by count
+//! ([`DataSourceConfig::max_points`]) and by age relative to the newest
+//! timestamp ([`DataSourceConfig::time_window_ms`]), and
+//! [`StreamingDataSource::window_domain`] gives the domain to scroll to.
+//!
+//! ```
+//! use makepad_d3::data::{DataPoint, DataSourceConfig, StreamMessage, StreamingDataSource};
+//! use makepad_d3::scale::{Scale, TimeScale};
+//!
+//! let config = DataSourceConfig::realtime().with_max_points(0).with_time_window_ms(60_000.0);
+//! let (mut source, tx) = StreamingDataSource::with_config(config);
+//! for second in 0..90 {
+//!     tx.send(StreamMessage::Point(DataPoint::new(second as f64 * 1000.0, 1.0))).unwrap();
+//! }
+//! source.process_messages();
+//!
+//! // Only the last minute is kept
+//! assert_eq!(source.len(), 61);
+//! assert_eq!(source.retained_extent(), Some((29_000.0, 89_000.0)));
+//!
+//! let (start, end) = source.window_domain().unwrap();
+//! let mut x = TimeScale::new();
+//! x.set_domain(start, end);
+//! ```
 
 use super::{DataPoint, DataSource, DataSourceConfig, DataSourceEvent, DataSourceState};
 use std::collections::VecDeque;
@@ -47,9 +74,15 @@ pub enum StreamMessage {
 /// Streaming data source using channels
 ///
 /// Receives data through a channel and buffers it for chart consumption.
+/// The buffer is bounded by the config's `max_points` and `time_window_ms`;
+/// evicted points are reported as [`DataSourceEvent::Remove`] at index 0
+/// after the append that displaced them. Eviction by time assumes points
+/// arrive in `x` order.
 pub struct StreamingDataSource {
-    /// Data buffer
+    /// Data buffer; entries before `start` are evicted
     data: Vec<DataPoint>,
+    /// Number of evicted entries not yet compacted out of `data`
+    start: usize,
     /// Channel receiver
     receiver: Option<Receiver<StreamMessage>>,
     /// Pending events
@@ -68,6 +101,7 @@ impl StreamingDataSource {
         let (tx, rx) = channel();
         let source = Self {
             data: Vec::new(),
+            start: 0,
             receiver: Some(rx),
             events: VecDeque::new(),
             state: DataSourceState::Connected,
@@ -82,6 +116,7 @@ impl StreamingDataSource {
         let (tx, rx) = channel();
         let source = Self {
             data: Vec::new(),
+            start: 0,
             receiver: Some(rx),
             events: VecDeque::new(),
             state: DataSourceState::Connected,
@@ -95,6 +130,7 @@ impl StreamingDataSource {
     pub fn from_receiver(receiver: Receiver<StreamMessage>) -> Self {
         Self {
             data: Vec::new(),
+            start: 0,
             receiver: Some(receiver),
             events: VecDeque::new(),
             state: DataSourceState::Connected,
@@ -142,21 +178,20 @@ impl StreamingDataSource {
         match message {
             StreamMessage::Point(point) => {
                 self.data.push(point.clone());
-                self.trim_to_max();
                 self.events.push_back(DataSourceEvent::Append(vec![point]));
+                self.evict();
             }
             StreamMessage::Points(points) => {
                 self.data.extend(points.clone());
-                self.trim_to_max();
                 self.events.push_back(DataSourceEvent::Append(points));
+                self.evict();
             }
             StreamMessage::Replace(points) => {
-                self.data = points.clone();
-                self.trim_to_max();
-                self.events.push_back(DataSourceEvent::Replace(points));
+                self.set_data(points);
+                self.events.push_back(DataSourceEvent::Replace(self.data().to_vec()));
             }
             StreamMessage::Clear => {
-                self.data.clear();
+                self.set_data(Vec::new());
                 self.events.push_back(DataSourceEvent::Replace(vec![]));
             }
             StreamMessage::Connected => {
@@ -174,10 +209,47 @@ impl StreamingDataSource {
         }
     }
 
-    fn trim_to_max(&mut self) {
-        if self.config.max_points > 0 && self.data.len() > self.config.max_points {
-            let excess = self.data.len() - self.config.max_points;
-            self.data.drain(0..excess);
+    /// Replace the buffer, keeping only what the limits allow
+    fn set_data(&mut self, data: Vec<DataPoint>) {
+        self.data = data;
+        self.start = 0;
+        self.drop_expired();
+        self.compact();
+    }
+
+    /// Evict points over the limits and queue a remove event for them
+    fn evict(&mut self) {
+        let evicted = self.drop_expired();
+        if evicted > 0 {
+            self.events.push_back(DataSourceEvent::Remove { index: 0, count: evicted });
+        }
+        self.compact();
+    }
+
+    /// Advance `start` past points over the count or time limit
+    fn drop_expired(&mut self) -> usize {
+        let before = self.start;
+        if self.config.max_points > 0 && self.len() > self.config.max_points {
+            self.start += self.len() - self.config.max_points;
+        }
+        let window = self.config.time_window_ms;
+        if let Some(newest) = self.data.last().and_then(|p| p.x).filter(|_| window > 0.0) {
+            let cutoff = newest - window;
+            while self.data.get(self.start).is_some_and(|p| p.x.is_some_and(|x| x < cutoff)) {
+                self.start += 1;
+            }
+        }
+        self.start - before
+    }
+
+    /// Drop evicted entries once they make up half the buffer
+    ///
+    /// Keeps eviction amortized O(1) per point while [`data`](Self::data)
+    /// stays a contiguous slice.
+    fn compact(&mut self) {
+        if self.start > 0 && self.start * 2 >= self.data.len() {
+            self.data.drain(..self.start);
+            self.start = 0;
         }
     }
 
@@ -186,19 +258,48 @@ impl StreamingDataSource {
         self.message_count
     }
 
+    /// Set the connection state without queuing an event
+    #[cfg(feature = "net")]
+    pub(crate) fn set_state(&mut self, state: DataSourceState) {
+        self.state = state;
+    }
+
     /// Get data reference
     pub fn data(&self) -> &[DataPoint] {
-        &self.data
+        &self.data[self.start..]
     }
 
     /// Get data length
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.data.len() - self.start
     }
 
     /// Check if empty
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.len() == 0
+    }
+
+    /// X of the oldest and newest retained points
+    ///
+    /// None if either has no `x`.
+    pub fn retained_extent(&self) -> Option<(f64, f64)> {
+        let data = self.data();
+        Some((data.first()?.x?, data.last()?.x?))
+    }
+
+    /// Domain for a chart that scrolls with the stream
+    ///
+    /// With a time window this is the full window ending at the newest
+    /// point, so the chart scrolls at a steady span even before the window
+    /// fills; otherwise it is the [`retained_extent`](Self::retained_extent).
+    pub fn window_domain(&self) -> Option<(f64, f64)> {
+        let window = self.config.time_window_ms;
+        if window > 0.0 {
+            let newest = self.data().last()?.x?;
+            Some((newest - window, newest))
+        } else {
+            self.retained_extent()
+        }
     }
 }
 
@@ -240,7 +341,7 @@ impl DataSource for StreamingDataSource {
     }
 
     fn snapshot(&self) -> Vec<DataPoint> {
-        self.data.clone()
+        self.data().to_vec()
     }
 
     fn config(&self) -> &DataSourceConfig {
@@ -317,6 +418,12 @@ impl StreamingSourceBuilder {
         self
     }
 
+    /// Set the retained time window in milliseconds of `x`
+    pub fn time_window_ms(mut self, window_ms: f64) -> Self {
+        self.config = self.config.with_time_window_ms(window_ms);
+        self
+    }
+
     /// Set initial data
     pub fn initial_data(mut self, data: Vec<DataPoint>) -> Self {
         self.initial_data = data;
@@ -326,7 +433,7 @@ impl StreamingSourceBuilder {
     /// Build the streaming source
     pub fn build(self) -> (StreamingDataSource, Sender<StreamMessage>) {
         let (mut source, tx) = StreamingDataSource::with_config(self.config);
-        source.data = self.initial_data;
+        source.set_data(self.initial_data);
         (source, tx)
     }
 }
@@ -399,6 +506,53 @@ mod tests {
         assert_eq!(source.data()[2].y, 4.0);
     }
 
+    #[test]
+    fn test_streaming_source_time_window() {
+        let config = DataSourceConfig::historical().with_time_window_ms(1_000.0);
+        let (mut source, tx) = StreamingDataSource::with_config(config);
+        assert_eq!(source.window_domain(), None);
+
+        let points: Vec<DataPoint> = (0..5).map(|i| DataPoint::new(i as f64 * 400.0, i as f64)).collect();
+        tx.send(StreamMessage::Points(points)).unwrap();
+        tx.send(StreamMessage::Point(DataPoint::new(2_000.0, 5.0))).unwrap();
+        source.process_messages();
+
+        // Newest x is 2000, so points before 1000 are gone
+        assert_eq!(source.data().iter().map(|p| p.y).collect::<Vec<_>>(), vec![3.0, 4.0, 5.0]);
+        assert_eq!(source.retained_extent(), Some((1_200.0, 2_000.0)));
+        assert_eq!(source.window_domain(), Some((1_000.0, 2_000.0)));
+
+        assert!(matches!(source.poll(), DataSourceEvent::Append(p) if p.len() == 5));
+        assert!(matches!(source.poll(), DataSourceEvent::Remove { index: 0, count: 2 }));
+        assert!(matches!(source.poll(), DataSourceEvent::Append(_)));
+        assert!(matches!(source.poll(), DataSourceEvent::Remove { index: 0, count: 1 }));
+        assert_eq!(source.snapshot().len(), 3);
+    }
+
+    #[test]
+    fn test_streaming_source_ring_buffer() {
+        let (mut source, tx) = StreamingSourceBuilder::new()
+            .max_points(10)
+            .time_window_ms(50.0)
+            .initial_data((0..20).map(|i| DataPoint::new(i as f64, 0.0)).collect())
+            .build();
+        assert_eq!(source.len(), 10);
+
+        for i in 20..1_000 {
+            tx.send(StreamMessage::Point(DataPoint::new(i as f64, i as f64))).unwrap();
+        }
+        source.process_messages();
+        assert_eq!(source.len(), 10);
+        assert_eq!(source.data()[0].y, 990.0);
+        // Evicted entries are compacted away rather than piling up
+        assert!(source.data.len() <= 20);
+
+        tx.send(StreamMessage::Replace(vec![DataPoint::new(0.0, 0.0), DataPoint::new(100.0, 1.0)])).unwrap();
+        source.process_messages();
+        assert_eq!(source.data().len(), 1);
+        assert_eq!(source.window_domain(), Some((50.0, 100.0)));
+    }
+
     #[test]
     fn test_streaming_source_replace() {
         let (mut source, tx) = StreamingDataSource::new();
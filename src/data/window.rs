@@ -0,0 +1,175 @@
+//! Index-based rolling window statistics
+//!
+//! Each output point is the input point with `y` replaced by a statistic of
+//! the trailing window of `window` points ending at it (shorter at the
+//! start). All functions run in O(n) regardless of the window size, so they
+//! are cheap on long streaming series.
+//!
+//! Points with a non-finite `y` are left out of every window; if a window
+//! holds no finite values its output is NaN. For irregularly sampled series
+//! prefer the time-weighted functions such as
+//! [`time_weighted_average`](super::time_weighted_average).
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::data::{rolling_max, rolling_std_dev, DataPoint};
+//!
+//! let data: Vec<DataPoint> = [1.0, 5.0, 2.0, 2.0, 2.0].into_iter().map(DataPoint::from_y).collect();
+//!
+//! let max = rolling_max(&data, 3);
+//! assert_eq!(max.iter().map(|p| p.y).collect::<Vec<_>>(), vec![1.0, 5.0, 5.0, 5.0, 2.0]);
+//!
+//! let std_dev = rolling_std_dev(&data, 3);
+//! assert_eq!(std_dev[4].y, 0.0);
+//! ```
+
+use std::collections::VecDeque;
+
+use super::DataPoint;
+
+fn with_y(point: &DataPoint, y: f64) -> DataPoint {
+    let mut point = point.clone();
+    point.y = y;
+    point
+}
+
+/// Trailing-window extreme using a monotonic deque of candidate indices
+fn rolling_extreme(data: &[DataPoint], window: usize, replaces: impl Fn(f64, f64) -> bool) -> Vec<DataPoint> {
+    if window == 0 {
+        return data.to_vec();
+    }
+    let mut candidates: VecDeque<usize> = VecDeque::new();
+    data.iter()
+        .enumerate()
+        .map(|(i, point)| {
+            if point.y.is_finite() {
+                // Drop candidates the new value beats; they can never be the extreme again
+                while candidates.back().is_some_and(|&j| replaces(point.y, data[j].y)) {
+                    candidates.pop_back();
+                }
+                candidates.push_back(i);
+            }
+            while candidates.front().is_some_and(|&j| j + window <= i) {
+                candidates.pop_front();
+            }
+            with_y(point, candidates.front().map_or(f64::NAN, |&j| data[j].y))
+        })
+        .collect()
+}
+
+/// Minimum over a trailing window of `window` points
+pub fn rolling_min(data: &[DataPoint], window: usize) -> Vec<DataPoint> {
+    rolling_extreme(data, window, |new, old| new <= old)
+}
+
+/// Maximum over a trailing window of `window` points
+pub fn rolling_max(data: &[DataPoint], window: usize) -> Vec<DataPoint> {
+    rolling_extreme(data, window, |new, old| new >= old)
+}
+
+/// Population standard deviation over a trailing window of `window` points
+///
+/// Sums are kept relative to the first finite value, which keeps the
+/// running variance accurate for large values with small spread.
+pub fn rolling_std_dev(data: &[DataPoint], window: usize) -> Vec<DataPoint> {
+    if window == 0 {
+        return data.to_vec();
+    }
+    let shift = data.iter().map(|p| p.y).find(|y| y.is_finite()).unwrap_or(0.0);
+    let (mut sum, mut sum_sq, mut count) = (0.0, 0.0, 0usize);
+    data.iter()
+        .enumerate()
+        .map(|(i, point)| {
+            if point.y.is_finite() {
+                let d = point.y - shift;
+                sum += d;
+                sum_sq += d * d;
+                count += 1;
+            }
+            if let Some(old) = i.checked_sub(window).map(|j| data[j].y).filter(|y| y.is_finite()) {
+                let d = old - shift;
+                sum -= d;
+                sum_sq -= d * d;
+                count -= 1;
+            }
+            let y = if count == 0 {
+                f64::NAN
+            } else {
+                let mean = sum / count as f64;
+                (sum_sq / count as f64 - mean * mean).max(0.0).sqrt()
+            };
+            with_y(point, y)
+        })
+        .collect()
+}
+
+/// Exponential moving average with smoothing factor `alpha` in (0, 1]
+///
+/// Each output is `alpha * y + (1 - alpha) * previous`, starting from the
+/// first finite value. A window-like span `n` corresponds to
+/// `alpha = 2 / (n + 1)`. Non-finite values pass through unchanged and do
+/// not move the average.
+pub fn exponential_moving_average(data: &[DataPoint], alpha: f64) -> Vec<DataPoint> {
+    let alpha = if alpha.is_finite() { alpha.clamp(f64::EPSILON, 1.0) } else { 1.0 };
+    let mut average: Option<f64> = None;
+    data.iter()
+        .map(|point| {
+            if !point.y.is_finite() {
+                return point.clone();
+            }
+            let y = average.map_or(point.y, |avg| avg + alpha * (point.y - avg));
+            average = Some(y);
+            with_y(point, y)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(values: &[f64]) -> Vec<DataPoint> {
+        values.iter().copied().map(DataPoint::from_y).collect()
+    }
+
+    fn ys(data: &[DataPoint]) -> Vec<f64> {
+        data.iter().map(|p| p.y).collect()
+    }
+
+    #[test]
+    fn test_rolling_min_max_skip_invalid() {
+        let data = points(&[3.0, f64::NAN, 1.0, 4.0, 4.0, 9.0, 2.0]);
+        assert_eq!(ys(&rolling_min(&data, 2)), vec![3.0, 3.0, 1.0, 1.0, 4.0, 4.0, 2.0]);
+        assert_eq!(ys(&rolling_max(&data, 3)), vec![3.0, 3.0, 3.0, 4.0, 4.0, 9.0, 9.0]);
+
+        let gap = rolling_max(&points(&[f64::NAN, f64::NAN, 1.0]), 2);
+        assert!(gap[1].y.is_nan());
+        assert_eq!(gap[2].y, 1.0);
+        assert_eq!(ys(&rolling_min(&data[..1], 0)), vec![3.0]);
+    }
+
+    #[test]
+    fn test_rolling_std_dev() {
+        let data = points(&[1e9 + 2.0, 1e9 + 4.0, 1e9 + 4.0, 1e9 + 4.0, 1e9 + 5.0, 1e9 + 5.0, 1e9 + 7.0, 1e9 + 9.0]);
+        let std_dev = rolling_std_dev(&data, 8);
+        assert_eq!(std_dev[0].y, 0.0);
+        assert!((std_dev[7].y - 2.0).abs() < 1e-6);
+
+        let windowed = rolling_std_dev(&points(&[0.0, 10.0, 2.0, f64::NAN, 4.0]), 2);
+        assert_eq!(ys(&windowed)[..3], [0.0, 5.0, 4.0]);
+        assert_eq!(windowed[3].y, 0.0);
+        assert_eq!(windowed[4].y, 0.0);
+    }
+
+    #[test]
+    fn test_exponential_moving_average() {
+        let data = points(&[10.0, 20.0, f64::NAN, 20.0]);
+        let ema = exponential_moving_average(&data, 0.5);
+        assert_eq!(ema[0].y, 10.0);
+        assert_eq!(ema[1].y, 15.0);
+        assert!(ema[2].y.is_nan());
+        assert_eq!(ema[3].y, 17.5);
+        assert_eq!(ys(&exponential_moving_average(&data[..2], 1.0)), vec![10.0, 20.0]);
+    }
+}
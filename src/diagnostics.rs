@@ -0,0 +1,412 @@
+//! Chart configuration diagnostics
+//!
+//! Inspects configured chart data, scales and palettes and reports
+//! actionable warnings for tooling and debug overlays. Nothing here fails:
+//! every finding is returned as a [`D3Error::Warning`] so callers can log,
+//! display or ignore it.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::data::{ChartData, Dataset};
+//! use makepad_d3::diagnostics::{ChartWarning, Diagnostics};
+//! use makepad_d3::error::D3Error;
+//! use makepad_d3::scale::{LinearScale, ScaleExt};
+//!
+//! let data = ChartData::new()
+//!     .with_labels(vec!["A", "B", "C"])
+//!     .add_dataset(Dataset::new("Sales").with_data(vec![10.0, 250.0, f64::NAN]));
+//!
+//! let y = LinearScale::new().with_domain(0.0, 100.0);
+//!
+//! let warnings = Diagnostics::new(&data).with_y_scale(&y).run();
+//! assert!(warnings.iter().all(D3Error::is_warning));
+//! assert!(warnings.iter().any(|w| matches!(
+//!     w.as_warning(),
+//!     Some(ChartWarning::NonFiniteValues { count: 1, .. })
+//! )));
+//! ```
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::data::{ChartData, Dataset, DEFAULT_Y_AXIS};
+use crate::error::D3Error;
+use crate::scale::Scale;
+
+/// Scale types whose domain is a continuous value interval
+const CONTINUOUS_SCALE_TYPES: &[&str] = &["linear", "log", "pow", "symlog", "time", "sequential"];
+
+/// A configuration problem found by [`Diagnostics`]
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ChartWarning {
+    /// The scale domain does not cover the data extent, so points are clipped
+    #[error("{axis} domain [{domain_min}, {domain_max}] does not include data extent [{data_min}, {data_max}]")]
+    DomainExcludesData {
+        /// Axis description
+        axis: String,
+        /// Lower domain bound
+        domain_min: f64,
+        /// Upper domain bound
+        domain_max: f64,
+        /// Smallest data value
+        data_min: f64,
+        /// Largest data value
+        data_max: f64,
+    },
+
+    /// A log scale is fed zero or negative values, which cannot be plotted
+    #[error("{axis} uses a log scale but {count} value(s) are zero or negative")]
+    NonPositiveLogData {
+        /// Axis description
+        axis: String,
+        /// Number of zero or negative values
+        count: usize,
+    },
+
+    /// More categories than palette colors, so colors repeat
+    #[error("{categories} categories but the palette only has {palette} colors")]
+    TooManyCategories {
+        /// Number of categories
+        categories: usize,
+        /// Number of palette colors
+        palette: usize,
+    },
+
+    /// A series has a different number of points than expected
+    #[error("series '{series}' has {len} points, expected {expected}")]
+    SeriesLengthMismatch {
+        /// Series label
+        series: String,
+        /// Actual number of points
+        len: usize,
+        /// Expected number of points
+        expected: usize,
+    },
+
+    /// A series contains NaN or infinite values, which are skipped
+    #[error("series '{series}' has {count} NaN or infinite value(s)")]
+    NonFiniteValues {
+        /// Series label
+        series: String,
+        /// Number of points with a non-finite value
+        count: usize,
+    },
+}
+
+impl From<ChartWarning> for D3Error {
+    fn from(warning: ChartWarning) -> Self {
+        D3Error::warning(warning)
+    }
+}
+
+/// Diagnostics pass over a configured chart
+///
+/// Attach whichever scales and palette the chart uses; checks that need a
+/// missing piece are skipped.
+pub struct Diagnostics<'a> {
+    data: &'a ChartData,
+    x_scale: Option<&'a dyn Scale>,
+    y_scales: HashMap<String, &'a dyn Scale>,
+    palette_size: Option<usize>,
+}
+
+impl<'a> Diagnostics<'a> {
+    /// Create a diagnostics pass over chart data
+    pub fn new(data: &'a ChartData) -> Self {
+        Self {
+            data,
+            x_scale: None,
+            y_scales: HashMap::new(),
+            palette_size: None,
+        }
+    }
+
+    /// Check against the x scale
+    pub fn with_x_scale(mut self, scale: &'a dyn Scale) -> Self {
+        self.x_scale = Some(scale);
+        self
+    }
+
+    /// Check against the default y-axis scale
+    pub fn with_y_scale(self, scale: &'a dyn Scale) -> Self {
+        self.with_y_axis_scale(DEFAULT_Y_AXIS, scale)
+    }
+
+    /// Check against the scale of a named y-axis
+    pub fn with_y_axis_scale(mut self, axis_id: impl Into<String>, scale: &'a dyn Scale) -> Self {
+        self.y_scales.insert(axis_id.into(), scale);
+        self
+    }
+
+    /// Check category count against a palette of this many colors
+    pub fn with_palette_size(mut self, colors: usize) -> Self {
+        self.palette_size = Some(colors);
+        self
+    }
+
+    /// Run every applicable check
+    ///
+    /// Returns only [`D3Error::Warning`] values, in a stable order: x-axis,
+    /// y-axes (in dataset order), palette, then per-series checks.
+    pub fn run(&self) -> Vec<D3Error> {
+        self.warnings().into_iter().map(D3Error::from).collect()
+    }
+
+    /// Run every applicable check, returning the bare warnings
+    pub fn warnings(&self) -> Vec<ChartWarning> {
+        let mut out = Vec::new();
+        self.check_x_scale(&mut out);
+        self.check_y_scales(&mut out);
+        self.check_palette(&mut out);
+        self.check_series_lengths(&mut out);
+        self.check_non_finite(&mut out);
+        out
+    }
+
+    fn check_x_scale(&self, out: &mut Vec<ChartWarning>) {
+        let Some(scale) = self.x_scale else { return };
+        let values = self.visible().flat_map(|ds| {
+            ds.data.iter().enumerate().map(|(i, p)| p.x_or(i))
+        });
+        check_scale("x", scale, values, out);
+    }
+
+    fn check_y_scales(&self, out: &mut Vec<ChartWarning>) {
+        for axis_id in self.data.y_axis_ids() {
+            let Some(&scale) = self.y_scales.get(&axis_id) else { continue };
+            let values = self
+                .visible()
+                .filter(|ds| ds.y_axis_id() == axis_id)
+                .flat_map(|ds| {
+                    ds.data.iter().flat_map(|p| std::iter::once(p.y).chain(p.y_min))
+                });
+            check_scale(&format!("y-axis '{}'", axis_id), scale, values, out);
+        }
+    }
+
+    fn check_palette(&self, out: &mut Vec<ChartWarning>) {
+        let Some(palette) = self.palette_size else { return };
+        let categories = if self.data.datasets.len() > 1 {
+            self.data.datasets.len()
+        } else {
+            self.data.labels.len()
+        };
+        if palette > 0 && categories > palette {
+            out.push(ChartWarning::TooManyCategories { categories, palette });
+        }
+    }
+
+    fn check_series_lengths(&self, out: &mut Vec<ChartWarning>) {
+        // Series with explicit x values may legitimately differ in length
+        let indexed: Vec<&Dataset> = self
+            .visible()
+            .filter(|ds| ds.data.iter().all(|p| p.x.is_none()))
+            .collect();
+        let expected = if !self.data.labels.is_empty() {
+            self.data.labels.len()
+        } else {
+            match indexed.first() {
+                Some(first) => first.data.len(),
+                None => return,
+            }
+        };
+        for ds in indexed {
+            if ds.data.len() != expected {
+                out.push(ChartWarning::SeriesLengthMismatch {
+                    series: ds.label.clone(),
+                    len: ds.data.len(),
+                    expected,
+                });
+            }
+        }
+    }
+
+    fn check_non_finite(&self, out: &mut Vec<ChartWarning>) {
+        for ds in &self.data.datasets {
+            let count = ds
+                .data
+                .iter()
+                .filter(|p| {
+                    !p.y.is_finite()
+                        || p.x.is_some_and(|x| !x.is_finite())
+                        || p.y_min.is_some_and(|v| !v.is_finite())
+                })
+                .count();
+            if count > 0 {
+                out.push(ChartWarning::NonFiniteValues {
+                    series: ds.label.clone(),
+                    count,
+                });
+            }
+        }
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &'a Dataset> {
+        self.data.datasets.iter().filter(|ds| !ds.hidden)
+    }
+}
+
+/// Domain-coverage and log-positivity checks for one continuous scale
+fn check_scale(
+    axis: &str,
+    scale: &dyn Scale,
+    values: impl Iterator<Item = f64>,
+    out: &mut Vec<ChartWarning>,
+) {
+    if !CONTINUOUS_SCALE_TYPES.contains(&scale.scale_type()) {
+        return;
+    }
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut non_positive = 0;
+    for v in values.filter(|v| v.is_finite()) {
+        min = min.min(v);
+        max = max.max(v);
+        if v <= 0.0 {
+            non_positive += 1;
+        }
+    }
+    if min > max {
+        return;
+    }
+
+    if scale.scale_type() == "log" && non_positive > 0 {
+        out.push(ChartWarning::NonPositiveLogData {
+            axis: axis.to_string(),
+            count: non_positive,
+        });
+    }
+
+    let (d0, d1) = scale.domain();
+    let (lo, hi) = (d0.min(d1), d0.max(d1));
+    let tolerance = (hi - lo).abs() * 1e-9;
+    if min < lo - tolerance || max > hi + tolerance {
+        out.push(ChartWarning::DomainExcludesData {
+            axis: axis.to_string(),
+            domain_min: lo,
+            domain_max: hi,
+            data_min: min,
+            data_max: max,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::DataPoint;
+    use crate::scale::{CategoryScale, LinearScale, LogScale, ScaleExt};
+
+    fn sales() -> ChartData {
+        ChartData::new()
+            .with_labels(vec!["Q1", "Q2", "Q3"])
+            .add_dataset(Dataset::new("North").with_data(vec![10.0, 20.0, 30.0]))
+            .add_dataset(Dataset::new("South").with_data(vec![5.0, 15.0, 25.0]))
+    }
+
+    #[test]
+    fn test_clean_chart_has_no_warnings() {
+        let data = sales();
+        let y = LinearScale::new().with_domain(0.0, 30.0);
+        let warnings = Diagnostics::new(&data)
+            .with_y_scale(&y)
+            .with_palette_size(10)
+            .run();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_domain_excludes_data() {
+        let data = sales();
+        let y = LinearScale::new().with_domain(10.0, 20.0);
+        let warnings = Diagnostics::new(&data).with_y_scale(&y).warnings();
+        assert_eq!(
+            warnings,
+            vec![ChartWarning::DomainExcludesData {
+                axis: "y-axis 'y'".to_string(),
+                domain_min: 10.0,
+                domain_max: 20.0,
+                data_min: 5.0,
+                data_max: 30.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_log_scale_non_positive() {
+        let data = ChartData::new()
+            .add_dataset(Dataset::new("Latency").with_data(vec![0.0, -1.0, 10.0, 100.0]));
+        let y = LogScale::new().with_domain(1.0, 1000.0);
+        let warnings = Diagnostics::new(&data).with_y_scale(&y).warnings();
+        assert!(warnings.contains(&ChartWarning::NonPositiveLogData {
+            axis: "y-axis 'y'".to_string(),
+            count: 2,
+        }));
+    }
+
+    #[test]
+    fn test_discrete_scale_skips_domain_check() {
+        let data = sales();
+        let x = CategoryScale::new().with_labels(vec!["Q1"]);
+        assert!(Diagnostics::new(&data).with_x_scale(&x).warnings().is_empty());
+    }
+
+    #[test]
+    fn test_too_many_categories() {
+        let data = ChartData::new()
+            .with_labels(vec!["a", "b", "c", "d"])
+            .add_dataset(Dataset::new("Share").with_data(vec![1.0, 2.0, 3.0, 4.0]));
+        let warnings = Diagnostics::new(&data).with_palette_size(3).warnings();
+        assert_eq!(
+            warnings,
+            vec![ChartWarning::TooManyCategories { categories: 4, palette: 3 }]
+        );
+    }
+
+    #[test]
+    fn test_series_length_mismatch() {
+        let data = sales().add_dataset(Dataset::new("West").with_data(vec![1.0, 2.0]));
+        let warnings = Diagnostics::new(&data).warnings();
+        assert_eq!(
+            warnings,
+            vec![ChartWarning::SeriesLengthMismatch {
+                series: "West".to_string(),
+                len: 2,
+                expected: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_explicit_x_series_skip_length_check() {
+        let data = sales().add_dataset(
+            Dataset::new("Samples").with_points(vec![DataPoint::new(0.5, 1.0)]),
+        );
+        assert!(Diagnostics::new(&data).warnings().is_empty());
+    }
+
+    #[test]
+    fn test_non_finite_counts() {
+        let data = ChartData::new().add_dataset(
+            Dataset::new("Sensor").with_data(vec![1.0, f64::NAN, f64::INFINITY, 2.0]),
+        );
+        let warnings = Diagnostics::new(&data).warnings();
+        assert_eq!(
+            warnings,
+            vec![ChartWarning::NonFiniteValues {
+                series: "Sensor".to_string(),
+                count: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_run_wraps_as_d3_warning() {
+        let data = sales().add_dataset(Dataset::new("West").with_data(vec![1.0]));
+        let errors = Diagnostics::new(&data).run();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_warning());
+        assert!(errors[0].to_string().contains("West"));
+    }
+}
@@ -4,6 +4,8 @@
 
 use thiserror::Error;
 
+use crate::diagnostics::ChartWarning;
+
 /// Errors that can occur in makepad-d3
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum D3Error {
@@ -30,6 +32,54 @@ pub enum D3Error {
     /// Configuration error
     #[error("Configuration error: {message}")]
     ConfigError { message: String },
+
+    /// Domain has no extent (min equals max) or no values
+    #[error("Empty domain: {message}")]
+    EmptyDomain {
+        /// What was empty
+        message: String,
+    },
+
+    /// A NaN or infinite value where a finite one is required
+    #[error("Non-finite value in {context}: {value}")]
+    NonFiniteValue {
+        /// Where the value was found
+        context: String,
+        /// The offending value
+        value: f64,
+    },
+
+    /// A parent/child relation loops back on itself
+    #[error("Cycle detected at node '{node}'")]
+    CycleDetected {
+        /// A node on the cycle
+        node: String,
+    },
+
+    /// A transform cannot be inverted
+    #[error("Singular matrix: {message}")]
+    SingularMatrix {
+        /// Which transform failed
+        message: String,
+    },
+
+    /// GeoJSON input is malformed; line and column are 1-based, 0 if unknown
+    #[error("Invalid GeoJSON at line {line}, column {column}: {message}")]
+    InvalidGeoJson {
+        /// Parser message
+        message: String,
+        /// Line of the error
+        line: usize,
+        /// Column of the error
+        column: usize,
+    },
+
+    /// Non-fatal configuration problem reported by diagnostics
+    #[error("Warning: {warning}")]
+    Warning {
+        /// Warning details
+        warning: ChartWarning,
+    },
 }
 
 /// Result type alias for makepad-d3
@@ -65,6 +115,49 @@ impl D3Error {
     pub fn config_error(msg: impl Into<String>) -> Self {
         Self::ConfigError { message: msg.into() }
     }
+
+    /// Create an empty domain error
+    pub fn empty_domain(msg: impl Into<String>) -> Self {
+        Self::EmptyDomain { message: msg.into() }
+    }
+
+    /// Create a non-finite value error
+    pub fn non_finite(context: impl Into<String>, value: f64) -> Self {
+        Self::NonFiniteValue { context: context.into(), value }
+    }
+
+    /// Create a cycle detected error
+    pub fn cycle_detected(node: impl Into<String>) -> Self {
+        Self::CycleDetected { node: node.into() }
+    }
+
+    /// Create a singular matrix error
+    pub fn singular_matrix(msg: impl Into<String>) -> Self {
+        Self::SingularMatrix { message: msg.into() }
+    }
+
+    /// Create an invalid GeoJSON error
+    pub fn invalid_geojson(msg: impl Into<String>, line: usize, column: usize) -> Self {
+        Self::InvalidGeoJson { message: msg.into(), line, column }
+    }
+
+    /// Create a diagnostics warning
+    pub fn warning(warning: ChartWarning) -> Self {
+        Self::Warning { warning }
+    }
+
+    /// Check if this is a non-fatal warning
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::Warning { .. })
+    }
+
+    /// Get the warning details, if this is a warning
+    pub fn as_warning(&self) -> Option<&ChartWarning> {
+        match self {
+            Self::Warning { warning } => Some(warning),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +197,24 @@ mod tests {
         let err2 = err1.clone();
         assert_eq!(err1, err2);
     }
+
+    #[test]
+    fn test_structured_variants() {
+        assert_eq!(
+            D3Error::non_finite("log domain", f64::INFINITY).to_string(),
+            "Non-finite value in log domain: inf"
+        );
+        assert_eq!(D3Error::cycle_detected("a").to_string(), "Cycle detected at node 'a'");
+        let err = D3Error::invalid_geojson("expected value", 3, 14);
+        assert_eq!(err.to_string(), "Invalid GeoJSON at line 3, column 14: expected value");
+    }
+
+    #[test]
+    fn test_warning() {
+        let err = D3Error::warning(ChartWarning::TooManyCategories { categories: 12, palette: 10 });
+        assert!(err.is_warning());
+        assert!(err.to_string().starts_with("Warning: 12 categories"));
+        assert!(!D3Error::config_error("x").is_warning());
+        assert!(D3Error::config_error("x").as_warning().is_none());
+    }
 }
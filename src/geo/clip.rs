@@ -0,0 +1,464 @@
+//! Spherical clipping
+//!
+//! Cuts geometry on the sphere before it is projected, so [`GeoPath`] emits
+//! well-formed lines and closed rings:
+//!
+//! - Antimeridian cutting splits lines and rings where they cross the
+//!   meridian opposite a projection's central meridian, and closes rings
+//!   that enclose a pole along the map edge (like `d3.geoClipAntimeridian`).
+//! - Circle clipping keeps what lies within a small circle around the view
+//!   center and follows the circle's edge where rings leave it (like
+//!   `d3.geoClipCircle`), which gives orthographic globes a clean horizon.
+//!
+//! Projections choose their clipping through [`Projection::clip`].
+//!
+//! [`GeoPath`]: super::GeoPath
+//! [`Projection::clip`]: super::Projection::clip
+
+use super::geojson::Position;
+
+const EPSILON: f64 = 1e-9;
+
+/// Angular step, in degrees, for points interpolated along a clip circle
+const CIRCLE_STEP: f64 = 2.0;
+
+/// Spherical clipping applied by [`GeoPath`](super::GeoPath) before projecting
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GeoClip {
+    /// No clipping; points are filtered by [`Projection::is_visible`](super::Projection::is_visible)
+    None,
+    /// Cut along the meridian opposite `central_lon` (degrees)
+    Antimeridian {
+        /// Central meridian of the projection
+        central_lon: f64,
+    },
+    /// Keep what lies within `radius` degrees of `center`
+    Circle {
+        /// Center of the visible cap [longitude, latitude]
+        center: Position,
+        /// Angular radius in degrees
+        radius: f64,
+    },
+}
+
+impl GeoClip {
+    /// Clip a line string into the pieces that should be drawn
+    pub fn clip_line(&self, line: &[Position]) -> Vec<Vec<Position>> {
+        match *self {
+            GeoClip::None => vec![line.to_vec()],
+            GeoClip::Antimeridian { central_lon } => antimeridian_line(line, central_lon),
+            GeoClip::Circle { center, radius } => Cap::new(center, radius).clip_line(line),
+        }
+    }
+
+    /// Clip a polygon ring into closed rings that should be drawn
+    pub fn clip_ring(&self, ring: &[Position]) -> Vec<Vec<Position>> {
+        match *self {
+            GeoClip::None => vec![ring.to_vec()],
+            GeoClip::Antimeridian { central_lon } => antimeridian_ring(ring, central_lon),
+            GeoClip::Circle { center, radius } => Cap::new(center, radius).clip_ring(ring),
+        }
+    }
+}
+
+/// Wrap degrees into [-180, 180)
+fn wrap180(degrees: f64) -> f64 {
+    (degrees + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Longitudes relative to `central_lon`, unwrapped so consecutive points
+/// never jump by more than 180°
+fn unwrap(points: &[Position], central_lon: f64) -> Vec<Position> {
+    let mut unwrapped: Vec<Position> = Vec::with_capacity(points.len());
+    for p in points {
+        let lon = wrap180(p[0] - central_lon);
+        let lon = match unwrapped.last() {
+            Some(prev) => prev[0] + wrap180(lon - prev[0]),
+            None => lon,
+        };
+        unwrapped.push([lon, p[1]]);
+    }
+    unwrapped
+}
+
+/// Index of the 360° strip containing an unwrapped longitude
+fn strip(lon: f64) -> i64 {
+    ((lon + 180.0) / 360.0).floor() as i64
+}
+
+/// Latitude where the great circle through `a` and `b` crosses the
+/// meridian at longitude `boundary` (unwrapped degrees)
+fn crossing_lat(a: Position, b: Position, boundary: f64) -> f64 {
+    let (lambda0, phi0) = (a[0].to_radians(), a[1].to_radians());
+    let (lambda1, phi1) = (b[0].to_radians(), b[1].to_radians());
+    let (cos_phi0, cos_phi1) = (phi0.cos(), phi1.cos());
+    let sin_lambda01 = (lambda0 - lambda1).sin();
+    if sin_lambda01.abs() > EPSILON && cos_phi0 > EPSILON && cos_phi1 > EPSILON {
+        ((phi0.sin() * cos_phi1 * lambda1.sin() - phi1.sin() * cos_phi0 * lambda0.sin())
+            / (cos_phi0 * cos_phi1 * sin_lambda01))
+            .atan()
+            .to_degrees()
+    } else if (b[0] - a[0]).abs() > EPSILON {
+        a[1] + (b[1] - a[1]) * (boundary - a[0]) / (b[0] - a[0])
+    } else {
+        (a[1] + b[1]) / 2.0
+    }
+}
+
+fn antimeridian_line(line: &[Position], central_lon: f64) -> Vec<Vec<Position>> {
+    let points = unwrap(line, central_lon);
+    let Some(first) = points.first() else {
+        return Vec::new();
+    };
+    if points.iter().all(|p| p[0].abs() <= 180.0) {
+        return vec![line.to_vec()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut k = strip(first[0]);
+    let mut piece = vec![*first];
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        // Points exactly on the cut stay with the piece they end
+        let on_edge = ((b[0] - 360.0 * k as f64).abs() - 180.0).abs() < EPSILON;
+        let next = if on_edge { k } else { strip(b[0]) };
+        if next != k {
+            let boundary = 180.0 + 360.0 * k.min(next) as f64;
+            let lat = crossing_lat(a, b, boundary);
+            piece.push([boundary, lat]);
+            pieces.push(shift(piece, k, central_lon));
+            piece = vec![[boundary, lat]];
+            k = next;
+        }
+        piece.push(b);
+    }
+    pieces.push(shift(piece, k, central_lon));
+    pieces.retain(|p| p.len() >= 2);
+    pieces
+}
+
+/// Move unwrapped points from strip `k` back to absolute longitudes
+fn shift(points: Vec<Position>, k: i64, central_lon: f64) -> Vec<Position> {
+    let offset = central_lon - 360.0 * k as f64;
+    points.into_iter().map(|p| [p[0] + offset, p[1]]).collect()
+}
+
+fn antimeridian_ring(ring: &[Position], central_lon: f64) -> Vec<Vec<Position>> {
+    let mut open = ring;
+    if open.len() > 1 && open.first() == open.last() {
+        open = &open[..open.len() - 1];
+    }
+    let mut points = unwrap(open, central_lon);
+    let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+
+    // A ring that winds around the pole doesn't close in unwrapped space;
+    // close it along the map edge through the pole it encircles
+    let end = last[0] + wrap180(first[0] - last[0]);
+    let winding = ((end - first[0]) / 360.0).round();
+    if winding != 0.0 {
+        let mean_lat = points.iter().map(|p| p[1]).sum::<f64>() / points.len() as f64;
+        let pole = if mean_lat < 0.0 { -90.0 } else { 90.0 };
+        points.push([end, first[1]]);
+        points.push([end, pole]);
+        points.push([first[0], pole]);
+    } else if points.iter().all(|p| p[0].abs() <= 180.0) {
+        return vec![ring.to_vec()];
+    }
+
+    let lo = points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+    let hi = points.iter().map(|p| p[0]).fold(f64::NEG_INFINITY, f64::max);
+    let mut rings = Vec::new();
+    for k in strip(lo)..=((hi - 180.0) / 360.0).ceil() as i64 {
+        let offset = 360.0 * k as f64;
+        let shifted: Vec<Position> = points.iter().map(|p| [p[0] - offset, p[1]]).collect();
+        let west = clip_half_plane(&shifted, -180.0, true);
+        let clipped = clip_half_plane(&west, 180.0, false);
+        if clipped.len() >= 3 {
+            let mut ring: Vec<Position> = clipped.into_iter().map(|p| [p[0] + central_lon, p[1]]).collect();
+            ring.push(ring[0]);
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+/// Sutherland-Hodgman clip of a closed ring against `lon >= boundary`
+/// (`keep_east`) or `lon <= boundary`
+fn clip_half_plane(points: &[Position], boundary: f64, keep_east: bool) -> Vec<Position> {
+    let inside = |p: &Position| if keep_east { p[0] >= boundary } else { p[0] <= boundary };
+    let mut out = Vec::with_capacity(points.len());
+    for (i, &b) in points.iter().enumerate() {
+        let a = points[(i + points.len() - 1) % points.len()];
+        match (inside(&a), inside(&b)) {
+            (true, true) => out.push(b),
+            (true, false) => out.push([boundary, crossing_lat(a, b, boundary)]),
+            (false, true) => {
+                out.push([boundary, crossing_lat(a, b, boundary)]);
+                out.push(b);
+            }
+            (false, false) => {}
+        }
+    }
+    out
+}
+
+type Vec3 = [f64; 3];
+
+fn to_vec3(p: Position) -> Vec3 {
+    let (lambda, phi) = (p[0].to_radians(), p[1].to_radians());
+    [phi.cos() * lambda.cos(), phi.cos() * lambda.sin(), phi.sin()]
+}
+
+fn to_position(v: Vec3) -> Position {
+    let r = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    [v[1].atan2(v[0]).to_degrees(), (v[2] / r).clamp(-1.0, 1.0).asin().to_degrees()]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn normalize(v: Vec3) -> Vec3 {
+    let r = dot(v, v).sqrt();
+    [v[0] / r, v[1] / r, v[2] / r]
+}
+
+/// Spherical cap used for circle clipping
+struct Cap {
+    center: Vec3,
+    /// Tangent basis at the center, for measuring azimuths
+    u: Vec3,
+    v: Vec3,
+    cos_radius: f64,
+    sin_radius: f64,
+}
+
+impl Cap {
+    fn new(center: Position, radius: f64) -> Self {
+        let c = to_vec3(center);
+        let axis = if c[2].abs() < 0.9 { [0.0, 0.0, 1.0] } else { [1.0, 0.0, 0.0] };
+        let u = normalize(cross(axis, c));
+        let v = cross(c, u);
+        let radius = radius.to_radians();
+        Self {
+            center: c,
+            u,
+            v,
+            cos_radius: radius.cos(),
+            sin_radius: radius.sin(),
+        }
+    }
+
+    fn contains(&self, p: Vec3) -> bool {
+        dot(self.center, p) >= self.cos_radius
+    }
+
+    fn azimuth(&self, p: Vec3) -> f64 {
+        dot(p, self.v).atan2(dot(p, self.u))
+    }
+
+    /// Point on the clip circle at an azimuth
+    fn edge(&self, azimuth: f64) -> Position {
+        let (s, c) = azimuth.sin_cos();
+        to_position(std::array::from_fn(|i| {
+            self.center[i] * self.cos_radius + (self.u[i] * c + self.v[i] * s) * self.sin_radius
+        }))
+    }
+
+    /// Where the great-circle arc from `inside` to `outside` leaves the cap
+    fn crossing(&self, inside: Vec3, outside: Vec3) -> Vec3 {
+        let (mut lo, mut hi) = (0.0, 1.0);
+        let at = |t: f64| normalize(std::array::from_fn(|i| inside[i] * (1.0 - t) + outside[i] * t));
+        for _ in 0..48 {
+            let mid = (lo + hi) / 2.0;
+            if self.contains(at(mid)) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        at(lo)
+    }
+
+    /// Points along the circle edge sweeping `sweep` radians from `start`,
+    /// excluding both ends
+    fn push_arc(&self, start: f64, sweep: f64, out: &mut Vec<Position>) {
+        let steps = (sweep.abs() / CIRCLE_STEP.to_radians()).ceil() as usize;
+        for s in 1..steps {
+            out.push(self.edge(start + sweep * s as f64 / steps as f64));
+        }
+    }
+
+    fn clip_line(&self, line: &[Position]) -> Vec<Vec<Position>> {
+        let points: Vec<Vec3> = line.iter().map(|&p| to_vec3(p)).collect();
+        let mut pieces = Vec::new();
+        let mut piece: Vec<Position> = Vec::new();
+        for (i, &p) in points.iter().enumerate() {
+            let inside = self.contains(p);
+            let prev_inside = i > 0 && self.contains(points[i - 1]);
+            if i > 0 && inside != prev_inside {
+                let edge = if inside {
+                    self.crossing(p, points[i - 1])
+                } else {
+                    self.crossing(points[i - 1], p)
+                };
+                piece.push(to_position(edge));
+                if !inside {
+                    pieces.push(std::mem::take(&mut piece));
+                }
+            }
+            if inside {
+                piece.push(line[i]);
+            }
+        }
+        pieces.push(piece);
+        pieces.retain(|p| p.len() >= 2);
+        pieces
+    }
+
+    fn clip_ring(&self, ring: &[Position]) -> Vec<Vec<Position>> {
+        let mut open = ring;
+        if open.len() > 1 && open.first() == open.last() {
+            open = &open[..open.len() - 1];
+        }
+        let points: Vec<Vec3> = open.iter().map(|&p| to_vec3(p)).collect();
+        let inside: Vec<bool> = points.iter().map(|&p| self.contains(p)).collect();
+        let n = points.len();
+
+        let Some(start) = inside.iter().position(|&i| i) else {
+            // Entirely outside: the ring either encircles the cap or misses it
+            let winding: f64 = (0..n)
+                .map(|i| wrap_pi(self.azimuth(points[(i + 1) % n]) - self.azimuth(points[i])))
+                .sum();
+            if n < 3 || winding.abs() < std::f64::consts::PI {
+                return Vec::new();
+            }
+            let mut circle = vec![self.edge(0.0)];
+            self.push_arc(0.0, winding.signum() * std::f64::consts::TAU, &mut circle);
+            circle.push(circle[0]);
+            return vec![circle];
+        };
+        if inside.iter().all(|&i| i) {
+            return vec![ring.to_vec()];
+        }
+
+        let mut out = Vec::with_capacity(n);
+        let (mut exit_azimuth, mut sweep) = (0.0, 0.0);
+        for k in 0..n {
+            let i = (start + k) % n;
+            let j = (i + 1) % n;
+            if inside[i] {
+                out.push(open[i]);
+            }
+            match (inside[i], inside[j]) {
+                (true, false) => {
+                    let exit = self.crossing(points[i], points[j]);
+                    out.push(to_position(exit));
+                    exit_azimuth = self.azimuth(exit);
+                    sweep = wrap_pi(self.azimuth(points[j]) - exit_azimuth);
+                }
+                (false, false) => {
+                    sweep += wrap_pi(self.azimuth(points[j]) - self.azimuth(points[i]));
+                }
+                (false, true) => {
+                    let entry = self.crossing(points[j], points[i]);
+                    sweep += wrap_pi(self.azimuth(entry) - self.azimuth(points[i]));
+                    self.push_arc(exit_azimuth, sweep, &mut out);
+                    out.push(to_position(entry));
+                }
+                (true, true) => {}
+            }
+        }
+        out.push(out[0]);
+        vec![out]
+    }
+}
+
+/// Wrap radians into [-π, π)
+fn wrap_pi(radians: f64) -> f64 {
+    use std::f64::consts::{PI, TAU};
+    (radians + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_cut_at_antimeridian() {
+        let clip = GeoClip::Antimeridian { central_lon: 0.0 };
+        let pieces = clip.clip_line(&[[170.0, 10.0], [-170.0, 10.0], [-160.0, 12.0]]);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0][1][0], 180.0);
+        assert_eq!(pieces[1][0][0], -180.0);
+        assert!((pieces[0][1][1] - pieces[1][0][1]).abs() < 1e-9);
+        assert!(pieces[0][1][1] > 10.0);
+
+        // Relative to a shifted central meridian the same line doesn't cross
+        let shifted = GeoClip::Antimeridian { central_lon: 180.0 };
+        assert_eq!(shifted.clip_line(&[[170.0, 10.0], [-170.0, 10.0]]).len(), 1);
+    }
+
+    #[test]
+    fn test_ring_split_into_closed_rings() {
+        let clip = GeoClip::Antimeridian { central_lon: 0.0 };
+        let ring = [[170.0, -10.0], [-170.0, -10.0], [-170.0, 10.0], [170.0, 10.0], [170.0, -10.0]];
+        let rings = clip.clip_ring(&ring);
+        assert_eq!(rings.len(), 2);
+        for r in &rings {
+            assert_eq!(r.first(), r.last());
+            let east = r.iter().all(|p| p[0] >= 170.0);
+            let west = r.iter().all(|p| p[0] <= -170.0);
+            assert!(east || west);
+        }
+
+        // Rings away from the cut are returned untouched
+        let plain = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 0.0]];
+        assert_eq!(clip.clip_ring(&plain), vec![plain.to_vec()]);
+    }
+
+    #[test]
+    fn test_polar_ring_closed_through_pole() {
+        let clip = GeoClip::Antimeridian { central_lon: 0.0 };
+        let ring: Vec<Position> = (0..=12).map(|i| [-180.0 + 30.0 * i as f64, -70.0]).collect();
+        let rings = clip.clip_ring(&ring);
+        assert_eq!(rings.len(), 1);
+        assert!(rings[0].iter().any(|p| p[1] == -90.0));
+        assert!(rings[0].iter().all(|p| (-180.0..=180.0).contains(&p[0])));
+    }
+
+    #[test]
+    fn test_circle_clip_follows_horizon() {
+        let clip = GeoClip::Circle { center: [0.0, 0.0], radius: 90.0 };
+
+        let line = clip.clip_line(&[[0.0, 0.0], [60.0, 0.0], [120.0, 0.0]]);
+        assert_eq!(line.len(), 1);
+        assert!((line[0].last().unwrap()[0] - 90.0).abs() < 1e-6);
+
+        // A band straddling the horizon is closed along the circle edge
+        let ring = [[60.0, -10.0], [120.0, -10.0], [120.0, 10.0], [60.0, 10.0], [60.0, -10.0]];
+        let rings = clip.clip_ring(&ring);
+        assert_eq!(rings.len(), 1);
+        let r = &rings[0];
+        assert_eq!(r.first(), r.last());
+        let cap = Cap::new([0.0, 0.0], 90.0 + 1e-6);
+        assert!(r.iter().all(|&p| cap.contains(to_vec3(p))));
+        assert!(r.iter().filter(|p| (p[0] - 90.0).abs() < 1e-6).count() >= 2);
+
+        // Wholly hidden rings vanish; rings encircling the cap fill it
+        let hidden = [[170.0, -5.0], [175.0, -5.0], [175.0, 5.0], [170.0, -5.0]];
+        assert!(clip.clip_ring(&hidden).is_empty());
+        let small = GeoClip::Circle { center: [0.0, 0.0], radius: 10.0 };
+        let square = [[-20.0, -20.0], [20.0, -20.0], [20.0, 20.0], [-20.0, 20.0], [-20.0, -20.0]];
+        let filled = small.clip_ring(&square);
+        assert_eq!(filled.len(), 1);
+        assert!(filled[0].len() > 100);
+        assert!(filled[0].iter().all(|p| (p[0].hypot(p[1]) - 10.0).abs() < 0.1));
+    }
+}
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::error::{D3Error, D3Result};
+
 /// A position is an array of numbers (longitude, latitude, [altitude])
 pub type Position = [f64; 2];
 
@@ -362,31 +364,45 @@ pub enum GeoJson {
 
 impl GeoJson {
     /// Parse GeoJSON from a string
-    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
-        // Try parsing as each type in order
-        if let Ok(fc) = serde_json::from_str::<FeatureCollectionJson>(json) {
-            if fc.r#type == "FeatureCollection" {
-                return Ok(GeoJson::FeatureCollection(FeatureCollection {
+    ///
+    /// The top-level `type` member selects the object kind, so errors point
+    /// at the actual problem inside it rather than at a failed fallback.
+    /// Errors are [`D3Error::InvalidGeoJson`] with the parser's line and column.
+    pub fn parse(json: &str) -> D3Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(geojson_error)?;
+        let kind = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| D3Error::invalid_geojson("missing string \"type\" member", 0, 0))?;
+
+        match kind {
+            "FeatureCollection" => {
+                let fc: FeatureCollectionJson = serde_json::from_str(json).map_err(geojson_error)?;
+                Ok(GeoJson::FeatureCollection(FeatureCollection {
                     features: fc.features,
                     bbox: fc.bbox,
-                }));
+                }))
             }
-        }
-
-        if let Ok(f) = serde_json::from_str::<FeatureJson>(json) {
-            if f.r#type == "Feature" {
-                return Ok(GeoJson::Feature(Box::new(Feature {
+            "Feature" => {
+                let f: FeatureJson = serde_json::from_str(json).map_err(geojson_error)?;
+                Ok(GeoJson::Feature(Box::new(Feature {
                     geometry: f.geometry,
                     properties: f.properties,
                     id: f.id,
                     bbox: f.bbox,
-                })));
+                })))
+            }
+            "Point" | "MultiPoint" | "LineString" | "MultiLineString" | "Polygon"
+            | "MultiPolygon" | "GeometryCollection" => {
+                let geom: Geometry = serde_json::from_str(json).map_err(geojson_error)?;
+                Ok(GeoJson::Geometry(geom))
             }
+            other => Err(D3Error::invalid_geojson(
+                format!("unknown GeoJSON type \"{}\"", other),
+                0,
+                0,
+            )),
         }
-
-        // Try as geometry
-        let geom: Geometry = serde_json::from_str(json)?;
-        Ok(GeoJson::Geometry(geom))
     }
 
     /// Serialize to JSON string
@@ -494,6 +510,8 @@ impl GeoJson {
 // Helper structs for JSON serialization
 #[derive(Serialize, Deserialize)]
 struct FeatureJson {
+    // Written for serialization; parse dispatches on the raw "type" member
+    #[allow(dead_code)]
     r#type: String,
     geometry: Option<Geometry>,
     #[serde(default)]
@@ -506,12 +524,23 @@ struct FeatureJson {
 
 #[derive(Serialize, Deserialize)]
 struct FeatureCollectionJson {
+    // Written for serialization; parse dispatches on the raw "type" member
+    #[allow(dead_code)]
     r#type: String,
     features: Vec<Feature>,
     #[serde(skip_serializing_if = "Option::is_none")]
     bbox: Option<BoundingBox>,
 }
 
+/// Convert a serde_json error, keeping its position as separate fields
+fn geojson_error(err: serde_json::Error) -> D3Error {
+    let (line, column) = (err.line(), err.column());
+    let message = err.to_string();
+    let suffix = format!(" at line {} column {}", line, column);
+    let message = message.strip_suffix(&suffix).unwrap_or(&message).to_string();
+    D3Error::invalid_geojson(message, line, column)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -662,6 +691,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_geojson_parse_errors() {
+        let err = GeoJson::parse("{\n  \"type\": \"Point\",\n  \"coordinates\": [1.0,\n}").unwrap_err();
+        match err {
+            D3Error::InvalidGeoJson { line, .. } => assert_eq!(line, 4),
+            other => panic!("Expected InvalidGeoJson, got {:?}", other),
+        }
+
+        let err = GeoJson::parse(r#"{"type": "Circle", "radius": 3}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown GeoJSON type \"Circle\""));
+
+        let err = GeoJson::parse(r#"{"coordinates": [1.0, 2.0]}"#).unwrap_err();
+        assert!(matches!(err, D3Error::InvalidGeoJson { .. }));
+    }
+
     #[test]
     fn test_geojson_serialize() {
         let point = Geometry::point(100.0, 0.0);
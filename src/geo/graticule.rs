@@ -0,0 +1,292 @@
+//! Graticule generator
+//!
+//! Produces the meridians and parallels of a longitude/latitude grid as
+//! GeoJSON geometry, for drawing map backgrounds with [`GeoPath`]. Follows
+//! `d3.geoGraticule`: major lines span the major extent, minor lines span the
+//! minor extent and skip positions already covered by a major line.
+//!
+//! [`GeoPath`]: super::GeoPath
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::geo::{EquirectangularProjection, GeoPath, Graticule};
+//!
+//! let projection = EquirectangularProjection::new();
+//! let graticule = Graticule::new().step(15.0, 15.0);
+//!
+//! let grid = GeoPath::new(&projection).generate(&graticule.to_geojson());
+//! let outline = GeoPath::new(&projection).generate(&graticule.outline_geojson());
+//! assert!(!grid.is_empty());
+//! assert!(!outline.is_empty());
+//! ```
+
+use super::geojson::{BoundingBox, GeoJson, Geometry, Position};
+
+const EPSILON: f64 = 1e-6;
+
+/// Longitude/latitude grid generator, like `d3.geoGraticule`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Graticule {
+    /// Extent of major lines [west, south, east, north]
+    extent_major: BoundingBox,
+    /// Extent of minor lines [west, south, east, north]
+    extent_minor: BoundingBox,
+    /// Major step [longitude, latitude] in degrees
+    step_major: [f64; 2],
+    /// Minor step [longitude, latitude] in degrees
+    step_minor: [f64; 2],
+    /// Sampling interval along each line in degrees
+    precision: f64,
+}
+
+impl Default for Graticule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Graticule {
+    /// Create the default graticule: minor lines every 10° up to ±80°
+    /// latitude, and major meridians every 90° reaching the poles
+    pub fn new() -> Self {
+        Self {
+            extent_major: [-180.0, -90.0 + EPSILON, 180.0, 90.0 - EPSILON],
+            extent_minor: [-180.0, -80.0 - EPSILON, 180.0, 80.0 + EPSILON],
+            step_major: [90.0, 360.0],
+            step_minor: [10.0, 10.0],
+            precision: 2.5,
+        }
+    }
+
+    /// Set both the major and minor extent
+    pub fn extent(self, extent: BoundingBox) -> Self {
+        self.extent_major(extent).extent_minor(extent)
+    }
+
+    /// Set the extent of major lines [west, south, east, north]
+    pub fn extent_major(mut self, extent: BoundingBox) -> Self {
+        self.extent_major = normalize_extent(extent);
+        self
+    }
+
+    /// Set the extent of minor lines [west, south, east, north]
+    pub fn extent_minor(mut self, extent: BoundingBox) -> Self {
+        self.extent_minor = normalize_extent(extent);
+        self
+    }
+
+    /// Set both the major and minor step in degrees
+    pub fn step(self, lon: f64, lat: f64) -> Self {
+        self.step_major(lon, lat).step_minor(lon, lat)
+    }
+
+    /// Set the major step [longitude, latitude] in degrees
+    pub fn step_major(mut self, lon: f64, lat: f64) -> Self {
+        self.step_major = [positive_step(lon), positive_step(lat)];
+        self
+    }
+
+    /// Set the minor step [longitude, latitude] in degrees
+    pub fn step_minor(mut self, lon: f64, lat: f64) -> Self {
+        self.step_minor = [positive_step(lon), positive_step(lat)];
+        self
+    }
+
+    /// Set the sampling interval along each line in degrees
+    ///
+    /// Lines are sampled so that curved projections render them smoothly.
+    pub fn precision(mut self, degrees: f64) -> Self {
+        self.precision = positive_step(degrees);
+        self
+    }
+
+    /// Get the major extent
+    pub fn get_extent_major(&self) -> BoundingBox {
+        self.extent_major
+    }
+
+    /// Get the minor extent
+    pub fn get_extent_minor(&self) -> BoundingBox {
+        self.extent_minor
+    }
+
+    /// Get the sampling precision
+    pub fn get_precision(&self) -> f64 {
+        self.precision
+    }
+
+    /// Grid lines as coordinate arrays: major meridians, major parallels,
+    /// then minor meridians and parallels
+    pub fn line_coordinates(&self) -> Vec<Vec<Position>> {
+        let [big_x0, big_y0, big_x1, big_y1] = self.extent_major;
+        let [x0, y0, x1, y1] = self.extent_minor;
+        let [big_dx, big_dy] = self.step_major;
+        let [dx, dy] = self.step_minor;
+        let p = self.precision;
+
+        let mut lines = Vec::new();
+        for x in grid_range(big_x0, big_x1, big_dx) {
+            lines.push(meridian(x, big_y0, big_y1, p));
+        }
+        for y in grid_range(big_y0, big_y1, big_dy) {
+            lines.push(parallel(y, big_x0, big_x1, p));
+        }
+        for x in grid_range(x0, x1, dx).filter(|x| (x % big_dx).abs() > EPSILON) {
+            lines.push(meridian(x, y0, y1, p));
+        }
+        for y in grid_range(y0, y1, dy).filter(|y| (y % big_dy).abs() > EPSILON) {
+            lines.push(parallel(y, x0, x1, p));
+        }
+        lines
+    }
+
+    /// Grid lines as separate LineString geometries
+    pub fn lines(&self) -> Vec<Geometry> {
+        self.line_coordinates()
+            .into_iter()
+            .map(Geometry::line_string)
+            .collect()
+    }
+
+    /// All grid lines as a single MultiLineString geometry
+    pub fn geometry(&self) -> Geometry {
+        Geometry::MultiLineString {
+            coordinates: self.line_coordinates(),
+        }
+    }
+
+    /// All grid lines as GeoJSON, ready for [`GeoPath`](super::GeoPath)
+    pub fn to_geojson(&self) -> GeoJson {
+        GeoJson::Geometry(self.geometry())
+    }
+
+    /// Polygon bounding the major extent, e.g. for a map's sphere background
+    pub fn outline(&self) -> Geometry {
+        let [x0, y0, x1, y1] = self.extent_major;
+        let p = self.precision;
+
+        let mut ring = meridian(x0, y0, y1, p);
+        ring.extend(parallel(y1, x0, x1, p).into_iter().skip(1));
+        ring.extend(meridian(x1, y0, y1, p).into_iter().rev().skip(1));
+        ring.extend(parallel(y0, x0, x1, p).into_iter().rev().skip(1));
+        Geometry::simple_polygon(ring)
+    }
+
+    /// The outline as GeoJSON
+    pub fn outline_geojson(&self) -> GeoJson {
+        GeoJson::Geometry(self.outline())
+    }
+}
+
+fn normalize_extent(extent: BoundingBox) -> BoundingBox {
+    let [a, b, c, d] = extent;
+    [a.min(c), b.min(d), a.max(c), b.max(d)]
+}
+
+fn positive_step(step: f64) -> f64 {
+    if step.is_finite() && step > 0.0 {
+        step
+    } else {
+        1.0
+    }
+}
+
+/// Multiples of `step` in `[start, stop)`
+fn grid_range(start: f64, stop: f64, step: f64) -> impl Iterator<Item = f64> {
+    let first = (start / step).ceil();
+    let count = ((stop - first * step) / step).ceil().max(0.0) as usize;
+    (0..count).map(move |i| (first + i as f64) * step)
+}
+
+/// Samples from `start` to `stop` every `step`, always ending exactly at `stop`
+fn samples(start: f64, stop: f64, step: f64) -> impl Iterator<Item = f64> {
+    let count = ((stop - EPSILON - start) / step).ceil().max(0.0) as usize;
+    (0..count)
+        .map(move |i| start + i as f64 * step)
+        .chain(std::iter::once(stop))
+}
+
+fn meridian(x: f64, y0: f64, y1: f64, precision: f64) -> Vec<Position> {
+    samples(y0, y1, precision).map(|y| [x, y]).collect()
+}
+
+fn parallel(y: f64, x0: f64, x1: f64, precision: f64) -> Vec<Position> {
+    samples(x0, x1, precision).map(|x| [x, y]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::{EquirectangularProjection, GeoPath, GeoPathSegment};
+
+    #[test]
+    fn test_default_lines_match_d3() {
+        let lines = Graticule::new().line_coordinates();
+        // 4 major meridians, the equator as the only major parallel,
+        // then 32 minor meridians and 16 minor parallels
+        assert_eq!(lines.len(), 4 + 1 + 32 + 16);
+
+        let first = &lines[0];
+        assert_eq!(first[0], [-180.0, -90.0 + EPSILON]);
+        assert_eq!(*first.last().unwrap(), [-180.0, 90.0 - EPSILON]);
+        assert_eq!(first.len(), 73);
+
+        assert_eq!(lines[4][0], [-180.0, 0.0]);
+
+        // Minor meridians stop at the minor extent
+        let minor = &lines[5];
+        assert_eq!(minor[0], [-170.0, -80.0 - EPSILON]);
+        assert_eq!(minor.last().unwrap()[1], 80.0 + EPSILON);
+        assert!(lines.iter().all(|l| l.len() >= 2));
+    }
+
+    #[test]
+    fn test_custom_extent_and_steps() {
+        let graticule = Graticule::new()
+            .extent([20.0, 40.0, -10.0, 10.0])
+            .step_major(10.0, 10.0)
+            .step_minor(5.0, 5.0)
+            .precision(1.0);
+        assert_eq!(graticule.get_extent_major(), [-10.0, 10.0, 20.0, 40.0]);
+
+        let lines = graticule.line_coordinates();
+        // Major: x = -10, 0, 10; y = 10, 20, 30. Minor: x = -5, 5, 15; y = 15, 25, 35
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[3][0], [-10.0, 10.0]);
+        assert_eq!(lines[3].len(), 31);
+        assert_eq!(lines[6][0], [-5.0, 10.0]);
+
+        match graticule.geometry() {
+            Geometry::MultiLineString { coordinates } => assert_eq!(coordinates, lines),
+            other => panic!("unexpected geometry {:?}", other),
+        }
+        assert_eq!(graticule.lines().len(), 12);
+    }
+
+    #[test]
+    fn test_outline_is_closed_ring() {
+        let graticule = Graticule::new().extent([-10.0, -10.0, 10.0, 10.0]).precision(5.0);
+        let ring = match graticule.outline() {
+            Geometry::Polygon { coordinates } => coordinates[0].clone(),
+            other => panic!("unexpected geometry {:?}", other),
+        };
+        assert_eq!(ring[0], [-10.0, -10.0]);
+        assert_eq!(ring.first(), ring.last());
+        // 4 sides of 4 intervals each, plus the closing point
+        assert_eq!(ring.len(), 17);
+        assert!(ring.contains(&[10.0, 10.0]));
+    }
+
+    #[test]
+    fn test_renders_with_geo_path() {
+        let projection = EquirectangularProjection::new();
+        let graticule = Graticule::new();
+        let segments = GeoPath::new(&projection).generate(&graticule.to_geojson());
+        let moves = segments
+            .iter()
+            .filter(|s| matches!(s, GeoPathSegment::MoveTo(..)))
+            .count();
+        assert_eq!(moves, graticule.line_coordinates().len());
+    }
+}
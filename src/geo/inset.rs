@@ -0,0 +1,310 @@
+//! Inset and locator map layout
+//!
+//! An [`InsetLayout`] holds secondary projections, each fitted to its own
+//! screen rectangle: detached regions such as Alaska and Hawaii next to a
+//! US choropleth, or a small overview map beside a zoomed-in view. For an
+//! overview inset, [`InsetLayout::locator`] outlines the main view's extent
+//! inside the inset.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::geo::{
+//!     EquirectangularProjection, GeoJson, Geometry, InsetCorner, InsetLayout,
+//!     MercatorProjection, ProjectionBuilder,
+//! };
+//!
+//! let world = GeoJson::Geometry(Geometry::simple_polygon(vec![
+//!     [-180.0, -60.0], [180.0, -60.0], [180.0, 80.0], [-180.0, 80.0], [-180.0, -60.0],
+//! ]));
+//! let view = [[0.0, 0.0], [800.0, 600.0]];
+//! let overview = InsetLayout::corner_rect(view, 200.0, 100.0, InsetCorner::BottomRight, 10.0);
+//! let insets = InsetLayout::new().with_inset("overview", overview, EquirectangularProjection::new(), &world);
+//!
+//! // Main map zoomed in on the Gulf of Guinea
+//! let main = MercatorProjection::new().scale(2000.0).center(5.0, 0.0).translate(400.0, 300.0);
+//! let locator = insets.locator("overview", &main, view).unwrap();
+//! let [[x0, y0], [x1, y1]] = locator.bounds;
+//! assert!(x0 > 590.0 && x1 < 790.0 && y0 > 490.0 && y1 < 590.0);
+//! ```
+
+use super::geojson::GeoJson;
+use super::projection::{Projection, ProjectionBuilder};
+
+/// Points sampled along each edge of the main view for the locator outline
+const LOCATOR_SAMPLES: usize = 16;
+
+/// Corner of a container rectangle for placing an inset
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InsetCorner {
+    /// Top-left corner
+    TopLeft,
+    /// Top-right corner
+    TopRight,
+    /// Bottom-left corner
+    #[default]
+    BottomLeft,
+    /// Bottom-right corner
+    BottomRight,
+}
+
+/// A secondary projection drawn inside its own screen rectangle
+pub struct MapInset {
+    /// Identifier used to look the inset up
+    pub name: String,
+    /// Screen rectangle `[[x0, y0], [x1, y1]]`
+    pub extent: [[f64; 2]; 2],
+    /// Projection fitted to the rectangle
+    pub projection: Box<dyn Projection>,
+}
+
+impl MapInset {
+    /// Whether a screen point lies inside the inset rectangle
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        let [[x0, y0], [x1, y1]] = self.extent;
+        x >= x0.min(x1) && x <= x0.max(x1) && y >= y0.min(y1) && y <= y0.max(y1)
+    }
+
+    /// Project a location, or None if it falls outside the inset rectangle
+    pub fn project(&self, lon: f64, lat: f64) -> Option<(f64, f64)> {
+        let (x, y) = self.projection.project(lon, lat);
+        self.contains(x, y).then_some((x, y))
+    }
+
+    /// Invert a screen point inside the inset rectangle
+    pub fn invert(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        if !self.contains(x, y) {
+            return None;
+        }
+        let (lon, lat) = self.projection.invert(x, y);
+        (lon.is_finite() && lat.is_finite()).then_some((lon, lat))
+    }
+}
+
+/// Outline of the main view's extent drawn inside an inset
+#[derive(Clone, Debug, PartialEq)]
+pub struct Locator {
+    /// Closed outline in inset screen coordinates (first point repeated last)
+    pub outline: Vec<(f64, f64)>,
+    /// Bounding box of the outline `[[x0, y0], [x1, y1]]`
+    pub bounds: [[f64; 2]; 2],
+}
+
+/// Secondary projections fitted to sub-rectangles of a map
+#[derive(Default)]
+pub struct InsetLayout {
+    insets: Vec<MapInset>,
+}
+
+impl InsetLayout {
+    /// Create an empty layout
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rectangle of `width` x `height` in a corner of `container`, `margin` from its edges
+    pub fn corner_rect(
+        container: [[f64; 2]; 2],
+        width: f64,
+        height: f64,
+        corner: InsetCorner,
+        margin: f64,
+    ) -> [[f64; 2]; 2] {
+        let [[x0, y0], [x1, y1]] = container;
+        let left = match corner {
+            InsetCorner::TopLeft | InsetCorner::BottomLeft => x0 + margin,
+            InsetCorner::TopRight | InsetCorner::BottomRight => x1 - margin - width,
+        };
+        let top = match corner {
+            InsetCorner::TopLeft | InsetCorner::TopRight => y0 + margin,
+            InsetCorner::BottomLeft | InsetCorner::BottomRight => y1 - margin - height,
+        };
+        [[left, top], [left + width, top + height]]
+    }
+
+    /// Add an inset whose projection is fitted so `object` fills `extent`
+    pub fn with_inset<P>(
+        mut self,
+        name: impl Into<String>,
+        extent: [[f64; 2]; 2],
+        projection: P,
+        object: &GeoJson,
+    ) -> Self
+    where
+        P: Projection + ProjectionBuilder + 'static,
+    {
+        self.add_inset(name, extent, projection, object);
+        self
+    }
+
+    /// Add an inset whose projection is fitted so `object` fills `extent`
+    ///
+    /// An inset with the same name is replaced.
+    pub fn add_inset<P>(
+        &mut self,
+        name: impl Into<String>,
+        extent: [[f64; 2]; 2],
+        projection: P,
+        object: &GeoJson,
+    ) where
+        P: Projection + ProjectionBuilder + 'static,
+    {
+        let projection = projection.fit_extent(extent, object);
+        self.push(MapInset {
+            name: name.into(),
+            extent,
+            projection: Box::new(projection),
+        });
+    }
+
+    /// Add an inset with an already configured projection
+    ///
+    /// An inset with the same name is replaced.
+    pub fn push(&mut self, inset: MapInset) {
+        self.insets.retain(|existing| existing.name != inset.name);
+        self.insets.push(inset);
+    }
+
+    /// Remove an inset by name
+    pub fn remove(&mut self, name: &str) -> Option<MapInset> {
+        let index = self.insets.iter().position(|inset| inset.name == name)?;
+        Some(self.insets.remove(index))
+    }
+
+    /// All insets in insertion order
+    pub fn insets(&self) -> &[MapInset] {
+        &self.insets
+    }
+
+    /// Inset by name
+    pub fn get(&self, name: &str) -> Option<&MapInset> {
+        self.insets.iter().find(|inset| inset.name == name)
+    }
+
+    /// Number of insets
+    pub fn len(&self) -> usize {
+        self.insets.len()
+    }
+
+    /// Whether the layout has no insets
+    pub fn is_empty(&self) -> bool {
+        self.insets.is_empty()
+    }
+
+    /// Topmost inset containing a screen point (later insets draw on top)
+    pub fn inset_at(&self, x: f64, y: f64) -> Option<&MapInset> {
+        self.insets.iter().rev().find(|inset| inset.contains(x, y))
+    }
+
+    /// Outline of the main view `view` (screen rectangle of `main`) inside an inset
+    ///
+    /// The view's edges are inverted through the main projection and
+    /// projected into the inset, so the outline bends where the two
+    /// projections disagree. Edge points the main projection cannot invert
+    /// (e.g. off an orthographic globe) are skipped. Returns None for an
+    /// unknown inset or if no edge point can be placed.
+    pub fn locator(&self, name: &str, main: &dyn Projection, view: [[f64; 2]; 2]) -> Option<Locator> {
+        let inset = self.get(name)?;
+        let [[x0, y0], [x1, y1]] = view;
+        let corners = [(x0, y0), (x1, y0), (x1, y1), (x0, y1), (x0, y0)];
+
+        let mut outline = Vec::with_capacity(4 * LOCATOR_SAMPLES + 1);
+        for edge in corners.windows(2) {
+            let ((ax, ay), (bx, by)) = (edge[0], edge[1]);
+            for i in 0..LOCATOR_SAMPLES {
+                let t = i as f64 / LOCATOR_SAMPLES as f64;
+                let (lon, lat) = main.invert(ax + (bx - ax) * t, ay + (by - ay) * t);
+                if !lon.is_finite() || !lat.is_finite() {
+                    continue;
+                }
+                let (x, y) = inset.projection.project(lon, lat);
+                if x.is_finite() && y.is_finite() {
+                    outline.push((x, y));
+                }
+            }
+        }
+        let first = *outline.first()?;
+        outline.push(first);
+
+        let bounds = outline.iter().fold(
+            [[f64::INFINITY, f64::INFINITY], [f64::NEG_INFINITY, f64::NEG_INFINITY]],
+            |[[bx0, by0], [bx1, by1]], &(x, y)| [[bx0.min(x), by0.min(y)], [bx1.max(x), by1.max(y)]],
+        );
+        Some(Locator { outline, bounds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::{ConicEqualAreaProjection, Geometry, MercatorProjection};
+
+    fn region(lon0: f64, lat0: f64, lon1: f64, lat1: f64) -> GeoJson {
+        GeoJson::Geometry(Geometry::simple_polygon(vec![
+            [lon0, lat0], [lon1, lat0], [lon1, lat1], [lon0, lat1], [lon0, lat0],
+        ]))
+    }
+
+    #[test]
+    fn test_corner_rects() {
+        let container = [[0.0, 0.0], [960.0, 600.0]];
+        assert_eq!(
+            InsetLayout::corner_rect(container, 200.0, 150.0, InsetCorner::BottomLeft, 10.0),
+            [[10.0, 440.0], [210.0, 590.0]]
+        );
+        assert_eq!(
+            InsetLayout::corner_rect(container, 200.0, 150.0, InsetCorner::TopRight, 0.0),
+            [[760.0, 0.0], [960.0, 150.0]]
+        );
+    }
+
+    #[test]
+    fn test_fitted_insets_and_hit_testing() {
+        let alaska_rect = [[10.0, 440.0], [210.0, 590.0]];
+        let hawaii_rect = [[220.0, 500.0], [320.0, 590.0]];
+        let mut layout = InsetLayout::new()
+            .with_inset(
+                "alaska",
+                alaska_rect,
+                ConicEqualAreaProjection::new().parallels(55.0, 65.0).rotate(154.0, 0.0, 0.0),
+                &region(-170.0, 52.0, -130.0, 71.0),
+            )
+            .with_inset("hawaii", hawaii_rect, MercatorProjection::new(), &region(-160.5, 18.9, -154.8, 22.3));
+        assert_eq!(layout.len(), 2);
+
+        let anchorage = layout.get("alaska").unwrap().project(-149.9, 61.2).unwrap();
+        assert_eq!(layout.inset_at(anchorage.0, anchorage.1).unwrap().name, "alaska");
+        let (lon, lat) = layout.get("alaska").unwrap().invert(anchorage.0, anchorage.1).unwrap();
+        assert!((lon + 149.9).abs() < 1e-6 && (lat - 61.2).abs() < 1e-6);
+
+        // Honolulu is outside the Alaska inset but inside Hawaii's
+        assert!(layout.get("alaska").unwrap().project(-157.8, 21.3).is_none());
+        assert!(layout.get("hawaii").unwrap().project(-157.8, 21.3).is_some());
+        assert!(layout.inset_at(500.0, 100.0).is_none());
+
+        assert!(layout.remove("hawaii").is_some());
+        assert!(layout.locator("hawaii", &MercatorProjection::new(), [[0.0, 0.0], [1.0, 1.0]]).is_none());
+    }
+
+    #[test]
+    fn test_locator_follows_main_view() {
+        let world = region(-180.0, -80.0, 180.0, 80.0);
+        let layout =
+            InsetLayout::new().with_inset("overview", [[0.0, 0.0], [360.0, 360.0]], MercatorProjection::new(), &world);
+        let inset = layout.get("overview").unwrap();
+
+        let view = [[0.0, 0.0], [800.0, 600.0]];
+        let main = MercatorProjection::new().scale(10_000.0).center(10.0, 0.0).translate(400.0, 300.0);
+        let locator = layout.locator("overview", &main, view).unwrap();
+        assert_eq!(locator.outline.first(), locator.outline.last());
+        assert_eq!(locator.outline.len(), 4 * LOCATOR_SAMPLES + 1);
+
+        // The main view's center lies inside the locator bounds
+        let (lon, lat) = main.invert(400.0, 300.0);
+        let (x, y) = inset.projection.project(lon, lat);
+        let [[x0, y0], [x1, y1]] = locator.bounds;
+        assert!(x0 < x && x < x1 && y0 < y && y < y1);
+        // Zoomed in, so the locator is much smaller than the inset
+        assert!(x1 - x0 < 20.0 && y1 - y0 < 20.0);
+    }
+}
@@ -0,0 +1,293 @@
+//! Filled map meshes with per-feature ranges
+//!
+//! [`GeoMesh`] triangulates the polygons of a feature collection into one
+//! indexed triangle list, remembering which vertex and index ranges belong to
+//! each feature. A hover highlight can then recolor or redraw one feature by
+//! its range instead of re-tessellating the map.
+//!
+//! Features are tessellated independently, so with the `parallel` feature
+//! enabled they are triangulated concurrently on the rayon thread pool and
+//! then assembled in order; the result is identical either way.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::geo::{
+//!     EquirectangularProjection, Feature, GeoMesh, GeoPath, Geometry, ProjectionBuilder,
+//! };
+//!
+//! let square = |lon: f64| Feature {
+//!     geometry: Some(Geometry::Polygon {
+//!         coordinates: vec![vec![
+//!             [lon, 0.0], [lon + 10.0, 0.0], [lon + 10.0, 10.0], [lon, 10.0], [lon, 0.0],
+//!         ]],
+//!     }),
+//!     properties: None,
+//!     id: None,
+//!     bbox: None,
+//! };
+//!
+//! let projection = EquirectangularProjection::new().scale(10.0).translate(0.0, 0.0);
+//! let mesh = GeoMesh::from_features(&GeoPath::new(&projection), &[square(0.0), square(20.0)]);
+//!
+//! assert_eq!(mesh.feature_count(), 2);
+//! assert_eq!(mesh.range(1).unwrap().triangle_count(), 2);
+//! ```
+
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::geojson::{Feature, Geometry};
+use super::path::{GeoPath, GeoPathSegment};
+use super::projection::Projection;
+use crate::color::Rgba;
+use crate::render::{RenderTarget, Tessellator, TriangleMesh};
+
+/// Vertex and index ranges of one feature within a [`GeoMesh`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeatureRange {
+    /// Index of the feature in the input slice
+    pub feature: usize,
+    /// Range into [`GeoMesh::vertices`]
+    pub vertices: Range<u32>,
+    /// Range into [`GeoMesh::indices`]
+    pub indices: Range<u32>,
+}
+
+impl FeatureRange {
+    /// Number of triangles
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Check if the feature produced no triangles
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// Indexed triangle mesh of a feature collection
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeoMesh {
+    /// Projected vertex positions
+    pub vertices: Vec<[f32; 2]>,
+    /// Vertex indices, three per triangle
+    pub indices: Vec<u32>,
+    /// One range per input feature, in input order
+    pub ranges: Vec<FeatureRange>,
+}
+
+impl GeoMesh {
+    /// Tessellate the polygonal geometry of each feature
+    ///
+    /// Polygon rings after the first are cut out as holes. Points and lines
+    /// have no area and produce empty ranges, as do features without geometry.
+    pub fn from_features<P: Projection>(path: &GeoPath<'_, P>, features: &[Feature]) -> Self {
+        let tessellate = |feature: &Feature| -> TriangleMesh {
+            let mut segments = Vec::new();
+            if let Some(geometry) = &feature.geometry {
+                polygon_segments(path, geometry, &mut segments);
+            }
+            fill_segments(&segments)
+        };
+
+        #[cfg(feature = "parallel")]
+        let meshes: Vec<TriangleMesh> = features.par_iter().map(tessellate).collect();
+        #[cfg(not(feature = "parallel"))]
+        let meshes: Vec<TriangleMesh> = features.iter().map(tessellate).collect();
+
+        Self::assemble(meshes)
+    }
+
+    /// Concatenate per-feature meshes, offsetting their indices
+    fn assemble(meshes: Vec<TriangleMesh>) -> Self {
+        let mut mesh = Self {
+            vertices: Vec::with_capacity(meshes.iter().map(|m| m.vertices.len()).sum()),
+            indices: Vec::with_capacity(meshes.iter().map(|m| m.indices.len()).sum()),
+            ranges: Vec::with_capacity(meshes.len()),
+        };
+        for (feature, part) in meshes.into_iter().enumerate() {
+            let v0 = mesh.vertices.len() as u32;
+            let i0 = mesh.indices.len() as u32;
+            mesh.vertices.extend(part.vertices);
+            mesh.indices.extend(part.indices.into_iter().map(|i| i + v0));
+            mesh.ranges.push(FeatureRange {
+                feature,
+                vertices: v0..mesh.vertices.len() as u32,
+                indices: i0..mesh.indices.len() as u32,
+            });
+        }
+        mesh
+    }
+
+    /// Number of features
+    pub fn feature_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Total number of triangles
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Get the ranges of a feature
+    pub fn range(&self, feature: usize) -> Option<&FeatureRange> {
+        self.ranges.get(feature)
+    }
+
+    /// Indices of one feature's triangles, for drawing it on its own
+    pub fn feature_indices(&self, feature: usize) -> &[u32] {
+        match self.ranges.get(feature) {
+            Some(range) => &self.indices[range.indices.start as usize..range.indices.end as usize],
+            None => &[],
+        }
+    }
+
+    /// Per-vertex colors, one color per feature
+    pub fn vertex_colors(&self, color: impl Fn(usize) -> Rgba) -> Vec<Rgba> {
+        let mut colors = Vec::with_capacity(self.vertices.len());
+        for range in &self.ranges {
+            let c = color(range.feature);
+            colors.extend(std::iter::repeat(c).take(range.vertices.len()));
+        }
+        colors
+    }
+
+    /// Recolor one feature in a per-vertex color buffer
+    pub fn restyle(&self, colors: &mut [Rgba], feature: usize, color: Rgba) {
+        if let Some(range) = self.ranges.get(feature) {
+            colors[range.vertices.start as usize..range.vertices.end as usize].fill(color);
+        }
+    }
+
+    /// Find the feature whose triangles contain a point (e.g. for hover)
+    pub fn feature_at(&self, x: f64, y: f64) -> Option<usize> {
+        let (x, y) = (x as f32, y as f32);
+        self.ranges.iter().find_map(|range| {
+            let indices = &self.indices[range.indices.start as usize..range.indices.end as usize];
+            indices
+                .chunks_exact(3)
+                .any(|t| {
+                    let [a, b, c] = [0, 1, 2].map(|k| self.vertices[t[k] as usize]);
+                    in_triangle([x, y], a, b, c)
+                })
+                .then_some(range.feature)
+        })
+    }
+}
+
+/// Path segments of the areal parts of a geometry
+fn polygon_segments<P: Projection>(path: &GeoPath<'_, P>, geometry: &Geometry, segments: &mut Vec<GeoPathSegment>) {
+    match geometry {
+        Geometry::Polygon { coordinates } => path.polygon_to_segments(coordinates, segments),
+        Geometry::MultiPolygon { coordinates } => {
+            for polygon in coordinates {
+                path.polygon_to_segments(polygon, segments);
+            }
+        }
+        Geometry::GeometryCollection { geometries } => {
+            for geometry in geometries {
+                polygon_segments(path, geometry, segments);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fill projected segments with the shared tessellator
+fn fill_segments(segments: &[GeoPathSegment]) -> TriangleMesh {
+    let mut tess = Tessellator::new();
+    tess.begin_path();
+    for segment in segments {
+        match *segment {
+            GeoPathSegment::MoveTo(x, y) => tess.move_to(x, y),
+            GeoPathSegment::LineTo(x, y) => tess.line_to(x, y),
+            GeoPathSegment::ClosePath => tess.close(),
+        }
+    }
+    tess.fill(Rgba::default());
+    tess.into_meshes().pop().unwrap_or_default()
+}
+
+fn in_triangle(p: [f32; 2], a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> bool {
+    let side = |o: [f32; 2], u: [f32; 2]| (u[0] - o[0]) * (p[1] - o[1]) - (u[1] - o[1]) * (p[0] - o[0]);
+    let (d1, d2, d3) = (side(a, b), side(b, c), side(c, a));
+    let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_neg && has_pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::{EquirectangularProjection, ProjectionBuilder};
+
+    fn feature(geometry: Option<Geometry>) -> Feature {
+        Feature {
+            geometry,
+            properties: None,
+            id: None,
+            bbox: None,
+        }
+    }
+
+    fn ring(x0: f64, y0: f64, size: f64) -> Vec<[f64; 2]> {
+        vec![[x0, y0], [x0 + size, y0], [x0 + size, y0 + size], [x0, y0 + size], [x0, y0]]
+    }
+
+    fn mesh(features: &[Feature]) -> GeoMesh {
+        let projection = EquirectangularProjection::new().scale(1.0).translate(0.0, 0.0);
+        GeoMesh::from_features(&GeoPath::new(&projection), features)
+    }
+
+    #[test]
+    fn test_ranges_follow_feature_order() {
+        let features = vec![
+            feature(Some(Geometry::Polygon { coordinates: vec![ring(0.0, 0.0, 10.0)] })),
+            feature(None),
+            feature(Some(Geometry::MultiPolygon {
+                coordinates: vec![vec![ring(20.0, 0.0, 5.0)], vec![ring(30.0, 0.0, 5.0)]],
+            })),
+        ];
+        let mesh = mesh(&features);
+
+        assert_eq!(mesh.feature_count(), 3);
+        assert_eq!(mesh.range(0).unwrap().triangle_count(), 2);
+        assert!(mesh.range(1).unwrap().is_empty());
+        assert_eq!(mesh.range(2).unwrap().triangle_count(), 4);
+        assert_eq!(mesh.triangle_count(), 6);
+
+        // Indices of each feature point into its own vertex range
+        for range in &mesh.ranges {
+            assert!(mesh.feature_indices(range.feature).iter().all(|i| range.vertices.contains(i)));
+        }
+    }
+
+    #[test]
+    fn test_hit_test_and_restyle() {
+        let features = vec![
+            feature(Some(Geometry::Polygon {
+                coordinates: vec![ring(0.0, 0.0, 10.0), ring(3.0, 3.0, 4.0)],
+            })),
+            feature(Some(Geometry::Polygon { coordinates: vec![ring(20.0, 0.0, 10.0)] })),
+        ];
+        let mesh = mesh(&features);
+        let projection = EquirectangularProjection::new().scale(1.0).translate(0.0, 0.0);
+        let at = |lon, lat| {
+            let (x, y) = projection.project(lon, lat);
+            mesh.feature_at(x, y)
+        };
+
+        assert_eq!(at(1.0, 1.0), Some(0));
+        assert_eq!(at(5.0, 5.0), None); // inside the hole
+        assert_eq!(at(25.0, 5.0), Some(1));
+
+        let mut colors = mesh.vertex_colors(|_| Rgba::BLACK);
+        assert_eq!(colors.len(), mesh.vertices.len());
+        mesh.restyle(&mut colors, 1, Rgba::RED);
+        let red = colors.iter().filter(|c| **c == Rgba::RED).count();
+        assert_eq!(red, mesh.range(1).unwrap().vertices.len());
+    }
+}
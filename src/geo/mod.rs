@@ -9,6 +9,10 @@
 //! - [`EquirectangularProjection`]: Simple plate carrée projection
 //! - [`OrthographicProjection`]: Azimuthal projection (globe view)
 //! - [`AlbersProjection`]: Equal-area conic projection (US maps)
+//! - [`ConicConformalProjection`]: Lambert conformal conic projection
+//! - [`ConicEqualAreaProjection`]: Equal-area conic with D3-style rotate/center
+//! - [`NaturalEarth1Projection`]: Pseudocylindrical compromise for world maps
+//! - [`AlbersUsaProjection`]: Composite US projection with Alaska/Hawaii insets
 //!
 //! # GeoJSON Support
 //!
@@ -19,6 +23,27 @@
 //! # Path Generation
 //!
 //! - [`GeoPath`]: Generate SVG-like paths from geographic data
+//! - [`IndexedGeoPath`]: Path segments with per-feature ranges for hit-testing
+//! - [`GeoMesh`]: Triangulated fills with per-feature ranges (parallel with the
+//!   `parallel` feature)
+//! - [`Graticule`]: Longitude/latitude grid lines and sphere outline
+//! - [`GeoClip`]: Antimeridian cutting and clip-angle (small circle) clipping,
+//!   chosen per projection via [`Projection::clip`]
+//!
+//! # Insets
+//!
+//! - [`InsetLayout`]: Secondary projections fitted to sub-rectangles, with a
+//!   [`Locator`] outlining the main view inside an overview inset
+//!
+//! # Tiles
+//!
+//! - [`TileCoord`], [`lonlat_to_meters`], [`lonlat_to_pixel`], [`ground_resolution`]:
+//!   Web Mercator (EPSG:3857) meters, tile pixels and meters per pixel
+//! - [`tile_projection`]: Mercator projection aligned with basemap tile pixels
+//!
+//! # Verification
+//!
+//! - [`verify_roundtrip`]: Checks project→invert round trips across a projection's domain
 //!
 //! # Example
 //!
@@ -40,10 +65,17 @@
 mod projection;
 mod geojson;
 mod path;
+mod mesh;
+mod graticule;
+mod clip;
+mod verify;
+mod tile;
+mod inset;
 
 pub use projection::{
     Projection, ProjectionBuilder,
     MercatorProjection, EquirectangularProjection, OrthographicProjection, AlbersProjection,
+    ConicConformalProjection, ConicEqualAreaProjection, NaturalEarth1Projection, AlbersUsaProjection,
 };
 
 pub use geojson::{
@@ -51,4 +83,14 @@ pub use geojson::{
     Position, BoundingBox, Properties,
 };
 
-pub use path::{GeoPath, GeoPathSegment};
+pub use path::{FeaturePath, GeoPath, GeoPathSegment, IndexedGeoPath};
+pub use mesh::{GeoMesh, FeatureRange};
+pub use graticule::Graticule;
+pub use clip::GeoClip;
+pub use inset::{InsetCorner, InsetLayout, Locator, MapInset};
+pub use verify::{roundtrip_error, verify_roundtrip, verify_roundtrip_points, RoundTripReport};
+pub use tile::{
+    TileCoord, EARTH_RADIUS, TILE_SIZE, MAX_LATITUDE,
+    world_size, lonlat_to_meters, meters_to_lonlat, meters_to_pixel, pixel_to_meters,
+    lonlat_to_pixel, pixel_to_lonlat, lonlat_to_tile, ground_resolution, tile_projection,
+};
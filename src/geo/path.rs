@@ -2,7 +2,10 @@
 //!
 //! Generates SVG-like path segments from geographic data.
 
-use super::geojson::{Feature, FeatureCollection, GeoJson, Geometry, Position};
+use std::ops::Range;
+
+use super::clip::GeoClip;
+use super::geojson::{Feature, FeatureCollection, GeoJson, Geometry, Position, Properties};
 use super::projection::Projection;
 
 /// A segment of a geographic path
@@ -27,6 +30,92 @@ impl GeoPathSegment {
     }
 }
 
+/// Segments of one feature within an [`IndexedGeoPath`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeaturePath {
+    /// Index of the feature in its collection (0 for a lone feature or geometry)
+    pub index: usize,
+    /// Feature id, if any
+    pub id: Option<serde_json::Value>,
+    /// Feature properties, if any
+    pub properties: Option<Properties>,
+    /// Range into [`IndexedGeoPath::segments`]
+    pub range: Range<usize>,
+    /// Screen bounds as [[x0, y0], [x1, y1]], or `None` if nothing is visible
+    pub bounds: Option<[[f64; 2]; 2]>,
+}
+
+impl FeaturePath {
+    /// Check if the feature produced no segments
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+}
+
+/// Path segments with per-feature ranges, from [`GeoPath::generate_indexed`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IndexedGeoPath {
+    /// All segments, in feature order (same as [`GeoPath::generate`])
+    pub segments: Vec<GeoPathSegment>,
+    /// One entry per feature, in input order
+    pub features: Vec<FeaturePath>,
+}
+
+impl IndexedGeoPath {
+    /// Segments of one feature
+    pub fn feature_segments(&self, index: usize) -> &[GeoPathSegment] {
+        match self.features.get(index) {
+            Some(feature) => &self.segments[feature.range.clone()],
+            None => &[],
+        }
+    }
+
+    /// Find a feature by id
+    pub fn find_by_id(&self, id: &serde_json::Value) -> Option<&FeaturePath> {
+        self.features.iter().find(|f| f.id.as_ref() == Some(id))
+    }
+
+    /// Find the topmost feature whose closed rings contain a screen point
+    ///
+    /// Uses the even-odd rule, so polygon holes are excluded. Lines enclose
+    /// no area and are never hit.
+    pub fn feature_at(&self, x: f64, y: f64) -> Option<&FeaturePath> {
+        self.features.iter().rev().find(|feature| {
+            let inside_bounds = feature
+                .bounds
+                .is_some_and(|[[x0, y0], [x1, y1]]| x >= x0 && x <= x1 && y >= y0 && y <= y1);
+            inside_bounds && contains_point(&self.segments[feature.range.clone()], x, y)
+        })
+    }
+}
+
+/// Even-odd test of a point against the closed subpaths of a segment list
+fn contains_point(segments: &[GeoPathSegment], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    let mut ring: Vec<(f64, f64)> = Vec::new();
+    for segment in segments {
+        match *segment {
+            GeoPathSegment::MoveTo(px, py) => {
+                ring.clear();
+                ring.push((px, py));
+            }
+            GeoPathSegment::LineTo(px, py) => ring.push((px, py)),
+            GeoPathSegment::ClosePath => {
+                let mut j = ring.len().wrapping_sub(1);
+                for i in 0..ring.len() {
+                    let ((xi, yi), (xj, yj)) = (ring[i], ring[j]);
+                    if (yi > y) != (yj > y) && x < (xj - xi) * (y - yi) / (yj - yi) + xi {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                ring.clear();
+            }
+        }
+    }
+    inside
+}
+
 /// Geographic path generator
 ///
 /// Converts geographic coordinates to screen coordinates using a projection
@@ -102,6 +191,69 @@ impl<'a, P: Projection> GeoPath<'a, P> {
         segments
     }
 
+    /// Generate path segments from GeoJSON, keeping track of which
+    /// segments belong to which feature
+    ///
+    /// The segments are the same as [`generate`](Self::generate) returns;
+    /// each feature also carries its id, properties and screen bounds so
+    /// hit-testing and selective styling can map geometry back to it.
+    pub fn generate_indexed(&self, geojson: &GeoJson) -> IndexedGeoPath {
+        let mut indexed = IndexedGeoPath::default();
+        match geojson {
+            GeoJson::Geometry(geometry) => {
+                self.push_indexed(&mut indexed, 0, Some(geometry), None, None);
+            }
+            GeoJson::Feature(feature) => {
+                self.push_indexed(&mut indexed, 0, feature.geometry.as_ref(), feature.id.as_ref(), feature.properties.as_ref());
+            }
+            GeoJson::FeatureCollection(collection) => {
+                for (index, feature) in collection.features.iter().enumerate() {
+                    self.push_indexed(
+                        &mut indexed,
+                        index,
+                        feature.geometry.as_ref(),
+                        feature.id.as_ref(),
+                        feature.properties.as_ref(),
+                    );
+                }
+            }
+        }
+        indexed
+    }
+
+    /// Append one feature's segments and its range
+    fn push_indexed(
+        &self,
+        indexed: &mut IndexedGeoPath,
+        index: usize,
+        geometry: Option<&Geometry>,
+        id: Option<&serde_json::Value>,
+        properties: Option<&Properties>,
+    ) {
+        let start = indexed.segments.len();
+        if let Some(geometry) = geometry {
+            self.geometry_to_segments(geometry, &mut indexed.segments);
+        }
+        let range = start..indexed.segments.len();
+
+        let mut bounds: Option<[[f64; 2]; 2]> = None;
+        for segment in &indexed.segments[range.clone()] {
+            if let GeoPathSegment::MoveTo(x, y) | GeoPathSegment::LineTo(x, y) = *segment {
+                let b = bounds.get_or_insert([[x, y], [x, y]]);
+                b[0] = [b[0][0].min(x), b[0][1].min(y)];
+                b[1] = [b[1][0].max(x), b[1][1].max(y)];
+            }
+        }
+
+        indexed.features.push(FeaturePath {
+            index,
+            id: id.cloned(),
+            properties: properties.cloned(),
+            range,
+            bounds,
+        });
+    }
+
     /// Generate SVG path string from GeoJSON
     pub fn to_svg(&self, geojson: &GeoJson) -> String {
         let segments = self.generate(geojson);
@@ -176,6 +328,14 @@ impl<'a, P: Projection> GeoPath<'a, P> {
 
     /// Generate path segments for a line string
     fn line_to_segments(&self, coordinates: &[Position], segments: &mut Vec<GeoPathSegment>) {
+        let clip = self.projection.clip();
+        if clip != GeoClip::None {
+            for line in clip.clip_line(coordinates) {
+                self.project_points(&line, segments);
+            }
+            return;
+        }
+
         let mut started = false;
 
         for coord in coordinates {
@@ -200,7 +360,7 @@ impl<'a, P: Projection> GeoPath<'a, P> {
     }
 
     /// Generate path segments for a polygon
-    fn polygon_to_segments(
+    pub(super) fn polygon_to_segments(
         &self,
         rings: &[Vec<Position>],
         segments: &mut Vec<GeoPathSegment>,
@@ -210,12 +370,35 @@ impl<'a, P: Projection> GeoPath<'a, P> {
         }
     }
 
+    /// Project already-clipped points as one subpath
+    fn project_points(&self, coordinates: &[Position], segments: &mut Vec<GeoPathSegment>) {
+        for (i, coord) in coordinates.iter().enumerate() {
+            let (x, y) = self.projection.project(coord[0], coord[1]);
+            segments.push(if i == 0 {
+                GeoPathSegment::MoveTo(x, y)
+            } else {
+                GeoPathSegment::LineTo(x, y)
+            });
+        }
+    }
+
     /// Generate path segments for a ring (closed line string)
     fn ring_to_segments(&self, coordinates: &[Position], segments: &mut Vec<GeoPathSegment>) {
         if coordinates.is_empty() {
             return;
         }
 
+        let clip = self.projection.clip();
+        if clip != GeoClip::None {
+            for ring in clip.clip_ring(coordinates) {
+                if ring.len() >= 2 {
+                    self.project_points(&ring, segments);
+                    segments.push(GeoPathSegment::ClosePath);
+                }
+            }
+            return;
+        }
+
         let mut started = false;
         let mut visible_count = 0;
 
@@ -847,6 +1030,59 @@ mod tests {
         assert!(segments.is_empty());
     }
 
+    #[test]
+    fn test_generate_indexed_ranges_and_hit_test() {
+        let projection = EquirectangularProjection::new()
+            .scale(1.0)
+            .translate(0.0, 0.0);
+        let path = GeoPath::new(&projection);
+
+        let square = |lon: f64, name: &str| Feature {
+            geometry: Some(Geometry::Polygon {
+                coordinates: vec![
+                    vec![[lon, 0.0], [lon + 10.0, 0.0], [lon + 10.0, 10.0], [lon, 10.0], [lon, 0.0]],
+                    vec![[lon + 4.0, 4.0], [lon + 6.0, 4.0], [lon + 6.0, 6.0], [lon + 4.0, 6.0], [lon + 4.0, 4.0]],
+                ],
+            }),
+            properties: Some(Properties::from([("name".to_string(), serde_json::Value::String(name.to_string()))])),
+            id: Some(serde_json::Value::String(name.to_string())),
+            bbox: None,
+        };
+        let empty = Feature {
+            geometry: None,
+            properties: None,
+            id: None,
+            bbox: None,
+        };
+        let geojson = GeoJson::FeatureCollection(FeatureCollection {
+            features: vec![square(0.0, "A"), empty, square(20.0, "B")],
+            bbox: None,
+        });
+
+        let indexed = path.generate_indexed(&geojson);
+        assert_eq!(indexed.segments, path.generate(&geojson));
+        assert_eq!(indexed.features.len(), 3);
+        assert!(indexed.features[1].is_empty());
+        assert_eq!(indexed.features[1].bounds, None);
+        assert_eq!(indexed.features[2].range.end, indexed.segments.len());
+        assert_eq!(indexed.feature_segments(0).len(), indexed.features[0].range.len());
+
+        let b = indexed.find_by_id(&serde_json::Value::String("B".to_string())).unwrap();
+        assert_eq!(b.index, 2);
+        let (x0, y0) = projection.project(20.0, 0.0);
+        let (x1, y1) = projection.project(30.0, 10.0);
+        assert_eq!(b.bounds, Some([[x0.min(x1), y0.min(y1)], [x0.max(x1), y0.max(y1)]]));
+
+        let at = |lon, lat| {
+            let (x, y) = projection.project(lon, lat);
+            indexed.feature_at(x, y).map(|f| f.index)
+        };
+        assert_eq!(at(1.0, 1.0), Some(0));
+        assert_eq!(at(5.0, 5.0), None); // inside the hole
+        assert_eq!(at(25.0, 1.0), Some(2));
+        assert_eq!(at(15.0, 5.0), None);
+    }
+
     #[test]
     fn test_geo_path_polygon_with_hole() {
         let projection = EquirectangularProjection::new()
@@ -879,4 +1115,57 @@ mod tests {
         // Area should be positive but less than full square
         assert!(area > 0.0);
     }
+
+    #[test]
+    fn test_polygon_across_antimeridian_is_cut() {
+        let projection = MercatorProjection::new().scale(100.0);
+        let path = GeoPath::new(&projection);
+        let geometry = Geometry::polygon(vec![vec![
+            [170.0, -10.0],
+            [-170.0, -10.0],
+            [-170.0, 10.0],
+            [170.0, 10.0],
+            [170.0, -10.0],
+        ]]);
+        let segments = path.generate(&GeoJson::Geometry(geometry));
+
+        let closes = segments.iter().filter(|s| **s == GeoPathSegment::ClosePath).count();
+        assert_eq!(closes, 2);
+        // No edge spans the map: every point stays near one of the two edges
+        let limit = 100.0 * std::f64::consts::PI + 1e-6;
+        for segment in &segments {
+            if let GeoPathSegment::MoveTo(x, _) | GeoPathSegment::LineTo(x, _) = *segment {
+                assert!(x.abs() <= limit && x.abs() > 250.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_orthographic_ring_closed_along_horizon() {
+        use crate::geo::projection::OrthographicProjection;
+
+        let projection = OrthographicProjection::new().scale(100.0);
+        let path = GeoPath::new(&projection);
+        let geometry = Geometry::polygon(vec![vec![
+            [60.0, -10.0],
+            [120.0, -10.0],
+            [120.0, 10.0],
+            [60.0, 10.0],
+            [60.0, -10.0],
+        ]]);
+        let segments = path.generate(&GeoJson::Geometry(geometry));
+
+        assert_eq!(segments.last(), Some(&GeoPathSegment::ClosePath));
+        let points: Vec<(f64, f64)> = segments
+            .iter()
+            .filter_map(|s| match *s {
+                GeoPathSegment::MoveTo(x, y) | GeoPathSegment::LineTo(x, y) => Some((x, y)),
+                _ => None,
+            })
+            .collect();
+        // The hidden half is replaced by points on the globe's rim
+        let on_rim = points.iter().filter(|(x, y)| (x.hypot(*y) - 100.0).abs() < 1e-3).count();
+        assert!(on_rim >= 2);
+        assert!(points.iter().all(|(x, y)| x.hypot(*y) <= 100.0 + 1e-6));
+    }
 }
@@ -4,6 +4,10 @@
 
 use std::f64::consts::PI;
 
+use super::clip::GeoClip;
+use super::geojson::GeoJson;
+use super::path::GeoPath;
+
 /// Trait for geographic projections
 ///
 /// Projects spherical coordinates (longitude, latitude in degrees) to
@@ -42,6 +46,11 @@ pub trait Projection: Send + Sync {
     fn clip_extent(&self) -> Option<((f64, f64), (f64, f64))> {
         None
     }
+
+    /// Spherical clipping applied to lines and polygons before projecting
+    fn clip(&self) -> GeoClip {
+        GeoClip::None
+    }
 }
 
 /// Builder trait for projections
@@ -63,6 +72,67 @@ pub trait ProjectionBuilder: Sized {
 
     /// Set the precision for adaptive resampling
     fn precision(self, precision: f64) -> Self;
+
+    /// Set scale and translation so the object fills the extent
+    /// `[[x0, y0], [x1, y1]]`, centered, like `projection.fitExtent`
+    ///
+    /// Center and rotation are kept. If the object has no projectable
+    /// coordinates the projection is left at scale 150 with no translation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use makepad_d3::geo::{GeoJson, Geometry, MercatorProjection, Projection, ProjectionBuilder};
+    ///
+    /// let country = GeoJson::Geometry(Geometry::simple_polygon(vec![
+    ///     [0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0],
+    /// ]));
+    /// let projection = MercatorProjection::new().fit_extent([[20.0, 20.0], [780.0, 580.0]], &country);
+    ///
+    /// let (_, y) = projection.project(5.0, 10.0);
+    /// assert!((y - 20.0).abs() < 1e-6);
+    /// ```
+    fn fit_extent(self, extent: [[f64; 2]; 2], object: &GeoJson) -> Self
+    where
+        Self: Projection,
+    {
+        let probe = self.scale(150.0).translate(0.0, 0.0);
+        let Some([[bx0, by0], [bx1, by1]]) = projected_bounds(&probe, object) else {
+            return probe;
+        };
+
+        let [[x0, y0], [x1, y1]] = extent;
+        let (w, h) = (x1 - x0, y1 - y0);
+        let k = [w / (bx1 - bx0), h / (by1 - by0)]
+            .into_iter()
+            .filter(|k| k.is_finite())
+            .fold(f64::INFINITY, f64::min);
+        let k = if k.is_finite() { k } else { 1.0 };
+
+        let tx = x0 + (w - k * (bx1 + bx0)) / 2.0;
+        let ty = y0 + (h - k * (by1 + by0)) / 2.0;
+        probe.scale(150.0 * k).translate(tx, ty)
+    }
+
+    /// Fit the object to `[[0, 0], [width, height]]`, like `projection.fitSize`
+    fn fit_size(self, width: f64, height: f64, object: &GeoJson) -> Self
+    where
+        Self: Projection,
+    {
+        self.fit_extent([[0.0, 0.0], [width, height]], object)
+    }
+}
+
+/// Projected bounding box of every geometry in a GeoJSON object
+fn projected_bounds<P: Projection>(projection: &P, object: &GeoJson) -> Option<[[f64; 2]; 2]> {
+    let path = GeoPath::new(projection);
+    object
+        .geometries()
+        .into_iter()
+        .filter_map(|geometry| path.bounds(geometry))
+        .reduce(|[[ax0, ay0], [ax1, ay1]], [[bx0, by0], [bx1, by1]]| {
+            [[ax0.min(bx0), ay0.min(by0)], [ax1.max(bx1), ay1.max(by1)]]
+        })
 }
 
 /// Mercator projection (conformal cylindrical)
@@ -192,6 +262,10 @@ impl Projection for MercatorProjection {
     fn projection_type(&self) -> &'static str {
         "mercator"
     }
+
+    fn clip(&self) -> GeoClip {
+        GeoClip::Antimeridian { central_lon: self.center_lon }
+    }
 }
 
 /// Equirectangular projection (plate carrée)
@@ -301,6 +375,10 @@ impl Projection for EquirectangularProjection {
     fn projection_type(&self) -> &'static str {
         "equirectangular"
     }
+
+    fn clip(&self) -> GeoClip {
+        GeoClip::Antimeridian { central_lon: self.center_lon }
+    }
 }
 
 /// Orthographic projection (azimuthal)
@@ -458,11 +536,18 @@ impl Projection for OrthographicProjection {
 
         let lon = (px * sin_c).atan2(rho * cos_c);
 
-        // Reverse rotation (simplified - full inverse rotation would be more complex)
-        let final_lon = lon.to_degrees() - self.rotate_lambda;
-        let final_lat = lat.to_degrees();
+        // Undo the phi rotation around the Y axis, then the lambda rotation
+        let (cos_gamma, sin_gamma) = (self.rotate_phi.to_radians().cos(), self.rotate_phi.to_radians().sin());
+        let x2 = lat.cos() * lon.cos();
+        let y2 = lat.cos() * lon.sin();
+        let z2 = lat.sin();
+        let x = x2 * cos_gamma - z2 * sin_gamma;
+        let z = x2 * sin_gamma + z2 * cos_gamma;
+
+        let final_lon = y2.atan2(x).to_degrees() - self.rotate_lambda;
+        let final_lat = z.clamp(-1.0, 1.0).asin().to_degrees();
 
-        (final_lon, final_lat)
+        ((final_lon + 540.0).rem_euclid(360.0) - 180.0, final_lat)
     }
 
     fn projection_type(&self) -> &'static str {
@@ -474,6 +559,17 @@ impl Projection for OrthographicProjection {
         let cos_c = phi.cos() * lambda.cos();
         cos_c >= self.clip_angle.to_radians().cos()
     }
+
+    fn clip(&self) -> GeoClip {
+        if self.clip_angle >= 180.0 {
+            return GeoClip::None;
+        }
+        // The geographic point that rotate_point maps to the view center
+        GeoClip::Circle {
+            center: [-self.rotate_lambda, self.rotate_phi],
+            radius: self.clip_angle,
+        }
+    }
 }
 
 /// Albers equal-area conic projection
@@ -642,11 +738,674 @@ impl Projection for AlbersProjection {
     fn projection_type(&self) -> &'static str {
         "albers"
     }
+
+    fn clip(&self) -> GeoClip {
+        GeoClip::Antimeridian { central_lon: self.center_lon }
+    }
+}
+
+/// Wrap radians into [-π, π]
+fn wrap_lambda(lambda: f64) -> f64 {
+    if lambda.abs() <= PI {
+        lambda
+    } else {
+        lambda - (lambda / (2.0 * PI)).round() * 2.0 * PI
+    }
+}
+
+/// Raw conic conformal projection (radians in, unit plane out, y up)
+#[derive(Clone, Copy, Debug)]
+enum ConformalRaw {
+    /// Parallels symmetric about the equator degenerate to Mercator
+    Mercator,
+    Conic { n: f64, f: f64 },
+}
+
+impl ConformalRaw {
+    fn new(phi0: f64, phi1: f64) -> Self {
+        let tany = |y: f64| ((PI / 2.0 + y) / 2.0).tan();
+        let cy0 = phi0.cos();
+        let n = if phi0 == phi1 {
+            phi0.sin()
+        } else {
+            (cy0 / phi1.cos()).ln() / (tany(phi1) / tany(phi0)).ln()
+        };
+        if !n.is_finite() || n.abs() < 1e-9 {
+            return ConformalRaw::Mercator;
+        }
+        ConformalRaw::Conic { n, f: cy0 * tany(phi0).powf(n) / n }
+    }
+
+    fn project(&self, lambda: f64, phi: f64) -> (f64, f64) {
+        match *self {
+            ConformalRaw::Mercator => (lambda, (PI / 4.0 + phi / 2.0).tan().ln()),
+            ConformalRaw::Conic { n, f } => {
+                let limit = PI / 2.0 - 1e-6;
+                let phi = if f > 0.0 { phi.max(-limit) } else { phi.min(limit) };
+                let r = f / ((PI / 2.0 + phi) / 2.0).tan().powf(n);
+                (r * (n * lambda).sin(), f - r * (n * lambda).cos())
+            }
+        }
+    }
+
+    fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+        match *self {
+            ConformalRaw::Mercator => (x, 2.0 * y.exp().atan() - PI / 2.0),
+            ConformalRaw::Conic { n, f } => {
+                let fy = f - y;
+                let r = n.signum() * (x * x + fy * fy).sqrt();
+                let mut l = x.atan2(fy.abs()) * fy.signum();
+                if fy * n < 0.0 {
+                    l -= PI * x.signum() * fy.signum();
+                }
+                (l / n, 2.0 * (f / r).powf(1.0 / n).atan() - PI / 2.0)
+            }
+        }
+    }
+}
+
+/// Raw conic equal-area projection (radians in, unit plane out, y up)
+#[derive(Clone, Copy, Debug)]
+enum EqualAreaRaw {
+    /// Parallels symmetric about the equator degenerate to cylindrical equal-area
+    Cylindrical { cos_phi0: f64 },
+    Conic { n: f64, c: f64, r0: f64 },
+}
+
+impl EqualAreaRaw {
+    fn new(phi0: f64, phi1: f64) -> Self {
+        let sy0 = phi0.sin();
+        let n = (sy0 + phi1.sin()) / 2.0;
+        if n.abs() < 1e-9 {
+            return EqualAreaRaw::Cylindrical { cos_phi0: phi0.cos() };
+        }
+        let c = 1.0 + sy0 * (2.0 * n - sy0);
+        EqualAreaRaw::Conic { n, c, r0: c.sqrt() / n }
+    }
+
+    fn project(&self, lambda: f64, phi: f64) -> (f64, f64) {
+        match *self {
+            EqualAreaRaw::Cylindrical { cos_phi0 } => (lambda * cos_phi0, phi.sin() / cos_phi0),
+            EqualAreaRaw::Conic { n, c, r0 } => {
+                let r = (c - 2.0 * n * phi.sin()).max(0.0).sqrt() / n;
+                (r * (lambda * n).sin(), r0 - r * (lambda * n).cos())
+            }
+        }
+    }
+
+    fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+        match *self {
+            EqualAreaRaw::Cylindrical { cos_phi0 } => (x / cos_phi0, (y * cos_phi0).clamp(-1.0, 1.0).asin()),
+            EqualAreaRaw::Conic { n, c, r0 } => {
+                let r0y = r0 - y;
+                let mut l = x.atan2(r0y.abs()) * r0y.signum();
+                if r0y * n < 0.0 {
+                    l -= PI * x.signum() * r0y.signum();
+                }
+                let sin_phi = (c - (x * x + r0y * r0y) * n * n) / (2.0 * n);
+                (l / n, sin_phi.clamp(-1.0, 1.0).asin())
+            }
+        }
+    }
+}
+
+/// Shared placement for d3-style projections: rotation about the polar
+/// axis, a center point in rotated coordinates, then scale and translate
+/// with y pointing down
+#[derive(Clone, Copy, Debug)]
+struct Placement {
+    scale: f64,
+    translate_x: f64,
+    translate_y: f64,
+    /// Rotation λ in degrees; the central meridian is at `-rotate_lambda`
+    rotate_lambda: f64,
+    /// Center [longitude, latitude] in rotated coordinates, in degrees
+    center: (f64, f64),
+}
+
+impl Placement {
+    fn new() -> Self {
+        Self {
+            scale: 1.0,
+            translate_x: 0.0,
+            translate_y: 0.0,
+            rotate_lambda: 0.0,
+            center: (0.0, 0.0),
+        }
+    }
+
+    fn project(&self, raw: impl Fn(f64, f64) -> (f64, f64), lon: f64, lat: f64) -> (f64, f64) {
+        let (x, y) = raw(wrap_lambda((lon + self.rotate_lambda).to_radians()), lat.to_radians());
+        let (cx, cy) = raw(self.center.0.to_radians(), self.center.1.to_radians());
+        (
+            self.translate_x + (x - cx) * self.scale,
+            self.translate_y - (y - cy) * self.scale,
+        )
+    }
+
+    fn invert(
+        &self,
+        raw: impl Fn(f64, f64) -> (f64, f64),
+        inverse: impl Fn(f64, f64) -> (f64, f64),
+        x: f64,
+        y: f64,
+    ) -> (f64, f64) {
+        let (cx, cy) = raw(self.center.0.to_radians(), self.center.1.to_radians());
+        let (lambda, phi) = inverse(
+            (x - self.translate_x) / self.scale + cx,
+            -(y - self.translate_y) / self.scale + cy,
+        );
+        (
+            wrap_lambda(lambda - self.rotate_lambda.to_radians()).to_degrees(),
+            phi.to_degrees(),
+        )
+    }
+
+    fn clip(&self) -> GeoClip {
+        GeoClip::Antimeridian { central_lon: -self.rotate_lambda }
+    }
+}
+
+/// Lambert conformal conic projection
+///
+/// Preserves angles; common for aeronautical charts and mid-latitude
+/// regions with a large east-west extent. Equivalent to
+/// `d3.geoConicConformal`, with standard parallels at 30° by default.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::geo::{ConicConformalProjection, Projection, ProjectionBuilder};
+///
+/// let projection = ConicConformalProjection::new()
+///     .parallels(33.0, 45.0)
+///     .rotate(96.0, 0.0, 0.0)
+///     .center(0.0, 39.0)
+///     .scale(1000.0)
+///     .translate(480.0, 250.0);
+///
+/// let (x, y) = projection.project(-96.0, 39.0);
+/// assert!((x - 480.0).abs() < 1e-6 && (y - 250.0).abs() < 1e-6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConicConformalProjection {
+    placement: Placement,
+    /// Standard parallels in degrees
+    parallels: (f64, f64),
+    raw: ConformalRaw,
+}
+
+impl Default for ConicConformalProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConicConformalProjection {
+    /// Create a conic conformal projection with standard parallels at 30°
+    pub fn new() -> Self {
+        Self {
+            placement: Placement::new(),
+            parallels: (30.0, 30.0),
+            raw: ConformalRaw::new(30f64.to_radians(), 30f64.to_radians()),
+        }
+    }
+
+    /// Set the standard parallels in degrees
+    pub fn parallels(mut self, p1: f64, p2: f64) -> Self {
+        self.parallels = (p1, p2);
+        self.raw = ConformalRaw::new(p1.to_radians(), p2.to_radians());
+        self
+    }
+
+    /// Get the standard parallels in degrees
+    pub fn get_parallels(&self) -> (f64, f64) {
+        self.parallels
+    }
+}
+
+impl ProjectionBuilder for ConicConformalProjection {
+    fn scale(mut self, scale: f64) -> Self {
+        self.placement.scale = scale;
+        self
+    }
+
+    fn center(mut self, lon: f64, lat: f64) -> Self {
+        self.placement.center = (lon, lat);
+        self
+    }
+
+    fn translate(mut self, x: f64, y: f64) -> Self {
+        self.placement.translate_x = x;
+        self.placement.translate_y = y;
+        self
+    }
+
+    fn rotate(mut self, lambda: f64, _phi: f64, _gamma: f64) -> Self {
+        // Only rotation about the polar axis keeps the cone upright
+        self.placement.rotate_lambda = lambda;
+        self
+    }
+
+    fn clip_angle(self, _angle: f64) -> Self {
+        self
+    }
+
+    fn precision(self, _precision: f64) -> Self {
+        self
+    }
+}
+
+impl Projection for ConicConformalProjection {
+    fn project(&self, lon: f64, lat: f64) -> (f64, f64) {
+        self.placement.project(|l, p| self.raw.project(l, p), lon, lat)
+    }
+
+    fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+        self.placement.invert(|l, p| self.raw.project(l, p), |x, y| self.raw.invert(x, y), x, y)
+    }
+
+    fn projection_type(&self) -> &'static str {
+        "conic-conformal"
+    }
+
+    fn clip(&self) -> GeoClip {
+        self.placement.clip()
+    }
+}
+
+/// Albers-type conic equal-area projection with configurable parallels
+///
+/// Equivalent to `d3.geoConicEqualArea`, with standard parallels at 0° and
+/// 33⅓° and center latitude 33.6442° by default. Unlike
+/// [`AlbersProjection`], it separates the rotation (central meridian) from
+/// the center point, as D3 does.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::geo::{ConicEqualAreaProjection, Projection, ProjectionBuilder};
+///
+/// let projection = ConicEqualAreaProjection::new()
+///     .parallels(55.0, 65.0)
+///     .rotate(154.0, 0.0, 0.0)
+///     .center(-2.0, 58.5)
+///     .scale(400.0);
+///
+/// let (x, y) = projection.project(-150.0, 61.0);
+/// let (lon, lat) = projection.invert(x, y);
+/// assert!((lon + 150.0).abs() < 1e-6 && (lat - 61.0).abs() < 1e-6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConicEqualAreaProjection {
+    placement: Placement,
+    /// Standard parallels in degrees
+    parallels: (f64, f64),
+    raw: EqualAreaRaw,
+}
+
+impl Default for ConicEqualAreaProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConicEqualAreaProjection {
+    /// Create a conic equal-area projection with D3's default parallels
+    pub fn new() -> Self {
+        let mut projection = Self {
+            placement: Placement::new(),
+            parallels: (0.0, 0.0),
+            raw: EqualAreaRaw::new(0.0, 0.0),
+        }
+        .parallels(0.0, 100.0 / 3.0);
+        projection.placement.center = (0.0, 33.6442);
+        projection
+    }
+
+    /// Set the standard parallels in degrees
+    pub fn parallels(mut self, p1: f64, p2: f64) -> Self {
+        self.parallels = (p1, p2);
+        self.raw = EqualAreaRaw::new(p1.to_radians(), p2.to_radians());
+        self
+    }
+
+    /// Get the standard parallels in degrees
+    pub fn get_parallels(&self) -> (f64, f64) {
+        self.parallels
+    }
+
+    /// Get the scale factor
+    pub fn get_scale(&self) -> f64 {
+        self.placement.scale
+    }
+
+    /// Get the translation
+    pub fn get_translate(&self) -> (f64, f64) {
+        (self.placement.translate_x, self.placement.translate_y)
+    }
+}
+
+impl ProjectionBuilder for ConicEqualAreaProjection {
+    fn scale(mut self, scale: f64) -> Self {
+        self.placement.scale = scale;
+        self
+    }
+
+    fn center(mut self, lon: f64, lat: f64) -> Self {
+        self.placement.center = (lon, lat);
+        self
+    }
+
+    fn translate(mut self, x: f64, y: f64) -> Self {
+        self.placement.translate_x = x;
+        self.placement.translate_y = y;
+        self
+    }
+
+    fn rotate(mut self, lambda: f64, _phi: f64, _gamma: f64) -> Self {
+        // Only rotation about the polar axis keeps the cone upright
+        self.placement.rotate_lambda = lambda;
+        self
+    }
+
+    fn clip_angle(self, _angle: f64) -> Self {
+        self
+    }
+
+    fn precision(self, _precision: f64) -> Self {
+        self
+    }
+}
+
+impl Projection for ConicEqualAreaProjection {
+    fn project(&self, lon: f64, lat: f64) -> (f64, f64) {
+        self.placement.project(|l, p| self.raw.project(l, p), lon, lat)
+    }
+
+    fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+        self.placement.invert(|l, p| self.raw.project(l, p), |x, y| self.raw.invert(x, y), x, y)
+    }
+
+    fn projection_type(&self) -> &'static str {
+        "conic-equal-area"
+    }
+
+    fn clip(&self) -> GeoClip {
+        self.placement.clip()
+    }
+}
+
+/// Natural Earth projection
+///
+/// A pseudocylindrical compromise projection for world maps, equivalent to
+/// `d3.geoNaturalEarth1`.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::geo::{NaturalEarth1Projection, Projection, ProjectionBuilder};
+///
+/// let projection = NaturalEarth1Projection::new()
+///     .scale(175.0)
+///     .translate(480.0, 250.0);
+///
+/// let (x, y) = projection.project(0.0, 0.0);
+/// assert!((x - 480.0).abs() < 1e-9 && (y - 250.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct NaturalEarth1Projection {
+    placement: Placement,
+}
+
+impl Default for NaturalEarth1Projection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NaturalEarth1Projection {
+    /// Create a Natural Earth projection
+    pub fn new() -> Self {
+        Self {
+            placement: Placement::new(),
+        }
+    }
+
+    fn raw(lambda: f64, phi: f64) -> (f64, f64) {
+        let phi2 = phi * phi;
+        let phi4 = phi2 * phi2;
+        (
+            lambda * (0.8707 - 0.131979 * phi2 + phi4 * (-0.013791 + phi4 * (0.003971 * phi2 - 0.001529 * phi4))),
+            phi * (1.007226 + phi2 * (0.015085 + phi4 * (-0.044475 + 0.028874 * phi2 - 0.005916 * phi4))),
+        )
+    }
+
+    fn raw_invert(x: f64, y: f64) -> (f64, f64) {
+        // Newton's method on the latitude polynomial
+        let mut phi = y;
+        for _ in 0..25 {
+            let phi2 = phi * phi;
+            let phi4 = phi2 * phi2;
+            let delta = (phi * (1.007226 + phi2 * (0.015085 + phi4 * (-0.044475 + 0.028874 * phi2 - 0.005916 * phi4)))
+                - y)
+                / (1.007226
+                    + phi2 * (0.015085 * 3.0 + phi4 * (-0.044475 * 7.0 + 0.028874 * 9.0 * phi2 - 0.005916 * 11.0 * phi4)));
+            phi -= delta;
+            if delta.abs() < 1e-12 {
+                break;
+            }
+        }
+        let phi2 = phi * phi;
+        (
+            x / (0.8707 + phi2 * (-0.131979 + phi2 * (-0.013791 + phi2 * phi2 * phi2 * (0.003971 - 0.001529 * phi2)))),
+            phi,
+        )
+    }
+}
+
+impl ProjectionBuilder for NaturalEarth1Projection {
+    fn scale(mut self, scale: f64) -> Self {
+        self.placement.scale = scale;
+        self
+    }
+
+    fn center(mut self, lon: f64, lat: f64) -> Self {
+        self.placement.center = (lon, lat);
+        self
+    }
+
+    fn translate(mut self, x: f64, y: f64) -> Self {
+        self.placement.translate_x = x;
+        self.placement.translate_y = y;
+        self
+    }
+
+    fn rotate(mut self, lambda: f64, _phi: f64, _gamma: f64) -> Self {
+        self.placement.rotate_lambda = lambda;
+        self
+    }
+
+    fn clip_angle(self, _angle: f64) -> Self {
+        self
+    }
+
+    fn precision(self, _precision: f64) -> Self {
+        self
+    }
+}
+
+impl Projection for NaturalEarth1Projection {
+    fn project(&self, lon: f64, lat: f64) -> (f64, f64) {
+        self.placement.project(Self::raw, lon, lat)
+    }
+
+    fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+        self.placement.invert(Self::raw, Self::raw_invert, x, y)
+    }
+
+    fn projection_type(&self) -> &'static str {
+        "natural-earth1"
+    }
+
+    fn clip(&self) -> GeoClip {
+        self.placement.clip()
+    }
+}
+
+/// Composite Albers projection for the United States
+///
+/// The lower 48 states use a conic equal-area projection, with Alaska and
+/// Hawaii drawn as insets in the lower left, like `d3.geoAlbersUsa`. Points
+/// outside all three regions are not visible and invert to NaN.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::geo::{AlbersUsaProjection, Projection, ProjectionBuilder};
+///
+/// let projection = AlbersUsaProjection::new()
+///     .scale(1070.0)
+///     .translate(480.0, 250.0);
+///
+/// // Anchorage lands in the Alaska inset, left of and below the mainland
+/// let (x, y) = projection.project(-149.9, 61.2);
+/// assert!(x < 480.0 - 0.214 * 1070.0 && y > 250.0 + 0.12 * 1070.0);
+///
+/// let (lon, lat) = projection.invert(x, y);
+/// assert!((lon + 149.9).abs() < 1e-6 && (lat - 61.2).abs() < 1e-6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AlbersUsaProjection {
+    lower48: ConicEqualAreaProjection,
+    alaska: ConicEqualAreaProjection,
+    hawaii: ConicEqualAreaProjection,
+    scale: f64,
+    translate: (f64, f64),
+}
+
+impl Default for AlbersUsaProjection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlbersUsaProjection {
+    /// Create the composite projection
+    pub fn new() -> Self {
+        Self {
+            lower48: ConicEqualAreaProjection::new()
+                .parallels(29.5, 45.5)
+                .rotate(96.0, 0.0, 0.0)
+                .center(-0.6, 38.7),
+            alaska: ConicEqualAreaProjection::new()
+                .parallels(55.0, 65.0)
+                .rotate(154.0, 0.0, 0.0)
+                .center(-2.0, 58.5),
+            hawaii: ConicEqualAreaProjection::new()
+                .parallels(8.0, 18.0)
+                .rotate(157.0, 0.0, 0.0)
+                .center(-3.0, 19.9),
+            scale: 1.0,
+            translate: (0.0, 0.0),
+        }
+        .place()
+    }
+
+    /// Rescale and reposition the insets relative to the mainland
+    fn place(mut self) -> Self {
+        let k = self.scale;
+        let (x, y) = self.translate;
+        self.lower48 = self.lower48.scale(k).translate(x, y);
+        self.alaska = self.alaska.scale(k * 0.35).translate(x - 0.307 * k, y + 0.201 * k);
+        self.hawaii = self.hawaii.scale(k).translate(x - 0.205 * k, y + 0.212 * k);
+        self
+    }
+
+    /// Screen rectangles of the lower 48, Alaska and Hawaii, in that order
+    ///
+    /// Useful for drawing inset borders.
+    pub fn inset_extents(&self) -> [((f64, f64), (f64, f64)); 3] {
+        let k = self.scale;
+        let (x, y) = self.translate;
+        [
+            ((x - 0.455 * k, y - 0.238 * k), (x + 0.455 * k, y + 0.238 * k)),
+            ((x - 0.425 * k, y + 0.120 * k), (x - 0.214 * k, y + 0.234 * k)),
+            ((x - 0.214 * k, y + 0.166 * k), (x - 0.115 * k, y + 0.234 * k)),
+        ]
+    }
+
+    /// The part projection and screen point for a location, if inside any extent
+    fn locate(&self, lon: f64, lat: f64) -> Option<(f64, f64)> {
+        let parts: [&ConicEqualAreaProjection; 3] = [&self.lower48, &self.alaska, &self.hawaii];
+        parts.iter().zip(self.inset_extents()).find_map(|(part, ((x0, y0), (x1, y1)))| {
+            let (x, y) = part.project(lon, lat);
+            (x >= x0 && x <= x1 && y >= y0 && y <= y1).then_some((x, y))
+        })
+    }
+}
+
+impl ProjectionBuilder for AlbersUsaProjection {
+    fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self.place()
+    }
+
+    fn center(self, _lon: f64, _lat: f64) -> Self {
+        // The composite has a fixed layout
+        self
+    }
+
+    fn translate(mut self, x: f64, y: f64) -> Self {
+        self.translate = (x, y);
+        self.place()
+    }
+
+    fn rotate(self, _lambda: f64, _phi: f64, _gamma: f64) -> Self {
+        self
+    }
+
+    fn clip_angle(self, _angle: f64) -> Self {
+        self
+    }
+
+    fn precision(self, _precision: f64) -> Self {
+        self
+    }
+}
+
+impl Projection for AlbersUsaProjection {
+    fn project(&self, lon: f64, lat: f64) -> (f64, f64) {
+        self.locate(lon, lat).unwrap_or((f64::NAN, f64::NAN))
+    }
+
+    fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+        let k = self.scale;
+        let (tx, ty) = self.translate;
+        let (px, py) = ((x - tx) / k, (y - ty) / k);
+        let part = if (0.120..0.234).contains(&py) && (-0.425..-0.214).contains(&px) {
+            &self.alaska
+        } else if (0.166..0.234).contains(&py) && (-0.214..-0.115).contains(&px) {
+            &self.hawaii
+        } else {
+            &self.lower48
+        };
+        part.invert(x, y)
+    }
+
+    fn projection_type(&self) -> &'static str {
+        "albers-usa"
+    }
+
+    fn is_visible(&self, lon: f64, lat: f64) -> bool {
+        self.locate(lon, lat).is_some()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::geo::{FeatureCollection, Geometry};
 
     #[test]
     fn test_mercator_new() {
@@ -772,4 +1531,151 @@ mod tests {
         assert!((lon - original.0).abs() < 0.1);
         assert!((lat - original.1).abs() < 0.1);
     }
+
+    #[test]
+    fn test_clip_matches_projection() {
+        let mercator = MercatorProjection::new().center(30.0, 0.0);
+        assert_eq!(mercator.clip(), GeoClip::Antimeridian { central_lon: 30.0 });
+
+        let globe = OrthographicProjection::new().rotate(40.0, 20.0, 0.0).clip_angle(60.0);
+        let clip = globe.clip();
+        let GeoClip::Circle { center, radius } = clip else {
+            panic!("expected circle clip, got {:?}", clip);
+        };
+        assert_eq!(radius, 60.0);
+        // The clip circle agrees with point visibility
+        let hidden = |lon: f64, lat: f64| clip.clip_line(&[[lon, lat], [lon, lat]]).is_empty();
+        assert!(globe.is_visible(center[0], center[1]));
+        for lon in (-175..180).step_by(20) {
+            for lat in (-75..=85).step_by(20) {
+                let (lon, lat) = (lon as f64, lat as f64);
+                assert_eq!(globe.is_visible(lon, lat), !hidden(lon, lat), "at {lon}, {lat}");
+            }
+        }
+        assert_eq!(OrthographicProjection::new().clip_angle(180.0).clip(), GeoClip::None);
+    }
+
+    #[test]
+    fn test_conic_projections_roundtrip() {
+        let conformal = ConicConformalProjection::new()
+            .parallels(33.0, 45.0)
+            .rotate(96.0, 0.0, 0.0)
+            .scale(500.0)
+            .translate(300.0, 200.0);
+        let equal_area = ConicEqualAreaProjection::new().rotate(-10.0, 0.0, 0.0).scale(300.0);
+        let natural = NaturalEarth1Projection::new().scale(150.0);
+
+        for &(lon, lat) in &[(-120.0, 35.0), (-75.0, 45.0), (10.0, -20.0), (150.0, 70.0)] {
+            let projections: [&dyn Projection; 3] = [&conformal, &equal_area, &natural];
+            for projection in projections {
+                let (x, y) = projection.project(lon, lat);
+                let (lon2, lat2) = projection.invert(x, y);
+                assert!(
+                    (lon2 - lon).abs() < 1e-6 && (lat2 - lat).abs() < 1e-6,
+                    "{} at {}, {}",
+                    projection.projection_type(),
+                    lon,
+                    lat
+                );
+            }
+        }
+        assert_eq!(natural.clip(), GeoClip::Antimeridian { central_lon: 0.0 });
+        assert_eq!(conformal.clip(), GeoClip::Antimeridian { central_lon: -96.0 });
+    }
+
+    #[test]
+    fn test_conic_special_cases() {
+        // Parallels symmetric about the equator degenerate to Mercator
+        let conformal = ConicConformalProjection::new().parallels(-10.0, 10.0).scale(100.0);
+        let mercator = MercatorProjection::new().scale(100.0);
+        let (a, b) = (conformal.project(40.0, 50.0), mercator.project(40.0, 50.0));
+        assert!((a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9);
+
+        // Equal-area: a 1° cell keeps its area relative to the sphere
+        let projection = ConicEqualAreaProjection::new().parallels(20.0, 50.0).scale(1000.0);
+        let cell_ratio = |lat: f64| {
+            let (x0, y0) = projection.project(0.0, lat);
+            let (x1, y1) = projection.project(1.0, lat + 1.0);
+            let planar = ((x1 - x0) * (y1 - y0)).abs();
+            let spherical = 1f64.to_radians() * ((lat + 1.0).to_radians().sin() - lat.to_radians().sin());
+            planar / spherical
+        };
+        assert!((cell_ratio(10.0) / cell_ratio(60.0) - 1.0).abs() < 0.02);
+
+        let natural = NaturalEarth1Projection::new().scale(100.0);
+        assert!((natural.project(180.0, 0.0).0 - 100.0 * PI * 0.8707).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_albers_usa_insets() {
+        let projection = AlbersUsaProjection::new().scale(1000.0).translate(500.0, 300.0);
+        let [lower48, alaska, hawaii] = projection.inset_extents();
+        let inside = |p: (f64, f64), e: ((f64, f64), (f64, f64))| {
+            p.0 >= e.0 .0 && p.0 <= e.1 .0 && p.1 >= e.0 .1 && p.1 <= e.1 .1
+        };
+
+        let san_francisco = projection.project(-122.42, 37.77);
+        assert!(inside(san_francisco, lower48));
+        let (x, y) = projection.project(-96.6, 38.7);
+        assert!((x - 500.0).abs() < 1e-9 && (y - 300.0).abs() < 1e-9);
+
+        assert!(inside(projection.project(-149.9, 61.2), alaska));
+        let honolulu = projection.project(-157.86, 21.31);
+        assert!(inside(honolulu, hawaii));
+        let (lon, lat) = projection.invert(honolulu.0, honolulu.1);
+        assert!((lon + 157.86).abs() < 1e-6 && (lat - 21.31).abs() < 1e-6);
+
+        assert!(!projection.is_visible(2.35, 48.86));
+        assert!(projection.project(2.35, 48.86).0.is_nan());
+    }
+
+    fn square(lon0: f64, lat0: f64, size: f64) -> GeoJson {
+        let (lon1, lat1) = (lon0 + size, lat0 + size);
+        GeoJson::Geometry(Geometry::simple_polygon(vec![
+            [lon0, lat0], [lon1, lat0], [lon1, lat1], [lon0, lat1], [lon0, lat0],
+        ]))
+    }
+
+    #[test]
+    fn test_fit_extent_fills_and_centers() {
+        let object = square(-20.0, -10.0, 20.0);
+        let extent = [[10.0, 20.0], [410.0, 220.0]];
+        let proj = EquirectangularProjection::new().fit_extent(extent, &object);
+
+        // Height is the limiting dimension: the square spans 200px
+        let (x0, y0) = proj.project(-20.0, 10.0);
+        let (x1, y1) = proj.project(0.0, -10.0);
+        assert!((y0 - 20.0).abs() < 1e-9);
+        assert!((y1 - 220.0).abs() < 1e-9);
+        assert!((x1 - x0 - 200.0).abs() < 1e-9);
+        assert!(((x0 + x1) / 2.0 - 210.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_size_with_rotation() {
+        let object = square(100.0, 30.0, 10.0);
+        let proj = OrthographicProjection::new()
+            .rotate(-105.0, -35.0, 0.0)
+            .fit_size(300.0, 300.0, &object);
+
+        let bounds = projected_bounds(&proj, &object).unwrap();
+        let [[x0, y0], [x1, y1]] = bounds;
+        assert!(x0 >= -1e-9 && y0 >= -1e-9 && x1 <= 300.0 + 1e-9 && y1 <= 300.0 + 1e-9);
+        assert!((x1 - x0 - 300.0).abs() < 1e-9 || (y1 - y0 - 300.0).abs() < 1e-9);
+        assert!(((x0 + x1) / 2.0 - 150.0).abs() < 1e-9);
+        assert!(((y0 + y1) / 2.0 - 150.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_empty_and_point() {
+        let empty = GeoJson::FeatureCollection(FeatureCollection::new());
+        let proj = MercatorProjection::new().fit_size(100.0, 100.0, &empty);
+        assert!((proj.project(0.0, 0.0).0).abs() < 1e-9);
+
+        // A single point has no extent: it is centered at the probe scale
+        let point = GeoJson::Geometry(Geometry::point(10.0, 10.0));
+        let proj = MercatorProjection::new().fit_size(100.0, 100.0, &point);
+        let (x, y) = proj.project(10.0, 10.0);
+        assert!((x - 50.0).abs() < 1e-9 && (y - 50.0).abs() < 1e-9);
+    }
 }
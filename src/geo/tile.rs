@@ -0,0 +1,235 @@
+//! Web Mercator tile math
+//!
+//! Conversions between longitude/latitude, Web Mercator meters (EPSG:3857)
+//! and the global pixel / tile grid used by slippy-map basemaps. Overlays
+//! drawn with [`tile_projection`] land on the same pixels as the basemap
+//! tiles, and [`ground_resolution`] gives the meters per pixel needed for
+//! scale bars.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::geo::{lonlat_to_pixel, lonlat_to_tile, tile_projection, Projection, TileCoord};
+//!
+//! // Null Island sits at the center of the world at every zoom level
+//! let (px, py) = lonlat_to_pixel(0.0, 0.0, 1.0);
+//! assert!((px - 256.0).abs() < 1e-9 && (py - 256.0).abs() < 1e-9);
+//! assert_eq!(lonlat_to_tile(0.0, 0.0, 1), TileCoord::new(1, 1, 1));
+//!
+//! // The matching projection reproduces the tile pixel coordinates
+//! let (x, y) = tile_projection(3.0).project(-122.4, 37.8);
+//! let (px, py) = lonlat_to_pixel(-122.4, 37.8, 3.0);
+//! assert!((x - px).abs() < 1e-9 && (y - py).abs() < 1e-9);
+//! ```
+
+use std::f64::consts::PI;
+
+use super::projection::{MercatorProjection, ProjectionBuilder};
+
+/// WGS84 semi-major axis in meters, the sphere radius of EPSG:3857
+pub const EARTH_RADIUS: f64 = 6_378_137.0;
+
+/// Edge length of a basemap tile in pixels
+pub const TILE_SIZE: f64 = 256.0;
+
+/// Latitude where the Web Mercator world becomes square (atan(sinh(π)))
+pub const MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// Half the world's width in Web Mercator meters
+const ORIGIN_SHIFT: f64 = PI * EARTH_RADIUS;
+
+/// A tile in the XYZ (slippy map) scheme
+///
+/// `x` grows eastward from the antimeridian and `y` grows southward from
+/// the top edge of the world, as used by OpenStreetMap and most basemaps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TileCoord {
+    /// Column
+    pub x: u32,
+    /// Row
+    pub y: u32,
+    /// Zoom level
+    pub z: u8,
+}
+
+impl TileCoord {
+    /// Create a tile coordinate
+    pub fn new(x: u32, y: u32, z: u8) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Number of tiles along each axis at this zoom level
+    pub fn tiles_per_axis(&self) -> u32 {
+        1u32 << self.z
+    }
+
+    /// Geographic bounds as [[west, south], [east, north]] in degrees
+    pub fn bounds(&self) -> [[f64; 2]; 2] {
+        let zoom = self.z as f64;
+        let (west, north) = pixel_to_lonlat(self.x as f64 * TILE_SIZE, self.y as f64 * TILE_SIZE, zoom);
+        let (east, south) =
+            pixel_to_lonlat((self.x + 1) as f64 * TILE_SIZE, (self.y + 1) as f64 * TILE_SIZE, zoom);
+        [[west, south], [east, north]]
+    }
+
+    /// Top-left corner in global pixel coordinates
+    pub fn pixel_origin(&self) -> (f64, f64) {
+        (self.x as f64 * TILE_SIZE, self.y as f64 * TILE_SIZE)
+    }
+
+    /// Quadkey string as used by Bing Maps
+    pub fn quadkey(&self) -> String {
+        (1..=self.z)
+            .rev()
+            .map(|i| {
+                let mask = 1u32 << (i - 1);
+                let digit = (self.x & mask != 0) as u8 + 2 * (self.y & mask != 0) as u8;
+                (b'0' + digit) as char
+            })
+            .collect()
+    }
+}
+
+/// World width (and height) in pixels at a zoom level
+pub fn world_size(zoom: f64) -> f64 {
+    TILE_SIZE * zoom.exp2()
+}
+
+/// Convert longitude/latitude in degrees to Web Mercator meters
+///
+/// Latitude is clamped to ±[`MAX_LATITUDE`].
+pub fn lonlat_to_meters(lon: f64, lat: f64) -> (f64, f64) {
+    let phi = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = lon.to_radians() * EARTH_RADIUS;
+    let y = (PI / 4.0 + phi / 2.0).tan().ln() * EARTH_RADIUS;
+    (x, y)
+}
+
+/// Convert Web Mercator meters to longitude/latitude in degrees
+pub fn meters_to_lonlat(x: f64, y: f64) -> (f64, f64) {
+    let lon = (x / EARTH_RADIUS).to_degrees();
+    let lat = (2.0 * (y / EARTH_RADIUS).exp().atan() - PI / 2.0).to_degrees();
+    (lon, lat)
+}
+
+/// Convert Web Mercator meters to global pixel coordinates at a zoom level
+///
+/// Pixel (0, 0) is the north-west corner of the world and y grows southward.
+pub fn meters_to_pixel(x: f64, y: f64, zoom: f64) -> (f64, f64) {
+    let size = world_size(zoom);
+    let px = (x + ORIGIN_SHIFT) / (2.0 * ORIGIN_SHIFT) * size;
+    let py = (ORIGIN_SHIFT - y) / (2.0 * ORIGIN_SHIFT) * size;
+    (px, py)
+}
+
+/// Convert global pixel coordinates at a zoom level to Web Mercator meters
+pub fn pixel_to_meters(px: f64, py: f64, zoom: f64) -> (f64, f64) {
+    let size = world_size(zoom);
+    let x = px / size * 2.0 * ORIGIN_SHIFT - ORIGIN_SHIFT;
+    let y = ORIGIN_SHIFT - py / size * 2.0 * ORIGIN_SHIFT;
+    (x, y)
+}
+
+/// Convert longitude/latitude in degrees to global pixel coordinates
+pub fn lonlat_to_pixel(lon: f64, lat: f64, zoom: f64) -> (f64, f64) {
+    let (x, y) = lonlat_to_meters(lon, lat);
+    meters_to_pixel(x, y, zoom)
+}
+
+/// Convert global pixel coordinates to longitude/latitude in degrees
+pub fn pixel_to_lonlat(px: f64, py: f64, zoom: f64) -> (f64, f64) {
+    let (x, y) = pixel_to_meters(px, py, zoom);
+    meters_to_lonlat(x, y)
+}
+
+/// Tile containing a location at an integer zoom level
+///
+/// Locations outside the world (beyond ±180° or ±[`MAX_LATITUDE`]) map to
+/// the nearest edge tile.
+pub fn lonlat_to_tile(lon: f64, lat: f64, zoom: u8) -> TileCoord {
+    let (px, py) = lonlat_to_pixel(lon, lat, zoom as f64);
+    let max = ((1u64 << zoom) - 1) as f64;
+    let column = (px / TILE_SIZE).floor().clamp(0.0, max);
+    let row = (py / TILE_SIZE).floor().clamp(0.0, max);
+    TileCoord::new(column as u32, row as u32, zoom)
+}
+
+/// Ground distance in meters covered by one pixel at a latitude and zoom
+///
+/// Web Mercator stretches distances by 1/cos(latitude), so a scale bar must
+/// be sized for the latitude it is drawn at.
+pub fn ground_resolution(lat: f64, zoom: f64) -> f64 {
+    let lat = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE);
+    lat.to_radians().cos() * 2.0 * ORIGIN_SHIFT / world_size(zoom)
+}
+
+/// Mercator projection whose output is global pixel coordinates at a zoom level
+///
+/// Equivalent to d3's `geoMercator().scale(256 * 2^z / (2π)).translate([128 * 2^z, 128 * 2^z])`;
+/// subtract a viewport's pixel origin to draw onto a tiled basemap.
+pub fn tile_projection(zoom: f64) -> MercatorProjection {
+    let size = world_size(zoom);
+    MercatorProjection::new()
+        .max_lat(MAX_LATITUDE)
+        .scale(size / (2.0 * PI))
+        .translate(size / 2.0, size / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::Projection;
+
+    #[test]
+    fn test_meters_known_values() {
+        let (x, y) = lonlat_to_meters(180.0, MAX_LATITUDE);
+        assert!((x - 20_037_508.342_789_244).abs() < 1e-6);
+        assert!((y - 20_037_508.342_789_244).abs() < 1e-3);
+
+        let (x, y) = lonlat_to_meters(-122.4, 37.8);
+        let (lon, lat) = meters_to_lonlat(x, y);
+        assert!((lon + 122.4).abs() < 1e-9);
+        assert!((lat - 37.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pixels_and_tiles() {
+        assert_eq!(world_size(0.0), 256.0);
+        let (px, py) = lonlat_to_pixel(-180.0, 0.0, 2.0);
+        assert!(px.abs() < 1e-9 && (py - 512.0).abs() < 1e-9);
+
+        let (px, py) = lonlat_to_pixel(2.35, 48.85, 10.0);
+        let (lon, lat) = pixel_to_lonlat(px, py, 10.0);
+        assert!((lon - 2.35).abs() < 1e-9 && (lat - 48.85).abs() < 1e-9);
+
+        // Paris at zoom 10 is OSM tile 518/352
+        let tile = lonlat_to_tile(2.35, 48.85, 10);
+        assert_eq!(tile, TileCoord::new(518, 352, 10));
+        let [[west, south], [east, north]] = tile.bounds();
+        assert!(west <= 2.35 && 2.35 <= east && south <= 48.85 && 48.85 <= north);
+
+        // Out-of-world locations snap to edge tiles
+        assert_eq!(lonlat_to_tile(180.0, -90.0, 3), TileCoord::new(7, 7, 3));
+        assert_eq!(TileCoord::new(3, 5, 3).quadkey(), "213");
+    }
+
+    #[test]
+    fn test_ground_resolution() {
+        // 156543.03 m/px at the equator, zoom 0
+        assert!((ground_resolution(0.0, 0.0) - 156_543.033_928).abs() < 1e-3);
+        assert!((ground_resolution(60.0, 0.0) - 156_543.033_928 / 2.0).abs() < 1e-3);
+        assert!((ground_resolution(0.0, 1.0) * 2.0 - ground_resolution(0.0, 0.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tile_projection_matches_pixels() {
+        let projection = tile_projection(4.5);
+        for &(lon, lat) in &[(0.0, 0.0), (-122.4, 37.8), (151.2, -33.9), (179.0, 80.0)] {
+            let (x, y) = projection.project(lon, lat);
+            let (px, py) = lonlat_to_pixel(lon, lat, 4.5);
+            assert!((x - px).abs() < 1e-6 && (y - py).abs() < 1e-6);
+            let (lon2, lat2) = projection.invert(x, y);
+            assert!((lon2 - lon).abs() < 1e-9 && (lat2 - lat).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Projection round-trip checks
+//!
+//! Projecting a location and inverting the result should land back on the
+//! same location. [`verify_roundtrip`] samples a projection's valid domain
+//! and reports the worst great-circle error, which catches sign slips,
+//! swapped axes and wrong constants in hand-written projection math.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::geo::{verify_roundtrip, MercatorProjection, ProjectionBuilder};
+//!
+//! let projection = MercatorProjection::new().scale(150.0).translate(480.0, 250.0);
+//! let report = verify_roundtrip(&projection, 1e-6).unwrap();
+//! assert!(report.samples > 1000);
+//! assert!(report.max_error < 1e-6);
+//! ```
+
+use crate::error::{D3Error, D3Result};
+
+use super::projection::Projection;
+
+/// Grid spacing in degrees used by [`verify_roundtrip`]
+const GRID_STEP: f64 = 5.0;
+
+/// Latitude limit used by [`verify_roundtrip`] (inside Mercator's clip)
+const MAX_LAT: f64 = 85.0;
+
+/// Result of a round-trip check
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoundTripReport {
+    /// Number of locations checked (invisible ones are skipped)
+    pub samples: usize,
+    /// Largest error in degrees of arc
+    pub max_error: f64,
+    /// Location with the largest error as (lon, lat)
+    pub worst: Option<(f64, f64)>,
+}
+
+impl RoundTripReport {
+    /// Check `points` (lon, lat in degrees) against a projection
+    ///
+    /// Locations the projection does not show, or projects to non-finite
+    /// coordinates, are skipped.
+    pub fn measure(projection: &dyn Projection, points: impl IntoIterator<Item = (f64, f64)>) -> Self {
+        let mut report = Self::default();
+        for (lon, lat) in points {
+            let Some(error) = roundtrip_error(projection, lon, lat) else {
+                continue;
+            };
+            report.samples += 1;
+            // NaN errors count as failures
+            let error = if error.is_nan() { f64::INFINITY } else { error };
+            if error > report.max_error || report.worst.is_none() {
+                report.max_error = error;
+                report.worst = Some((lon, lat));
+            }
+        }
+        report
+    }
+
+    /// Whether every sample was within `tolerance` degrees
+    pub fn is_within(&self, tolerance: f64) -> bool {
+        self.max_error <= tolerance
+    }
+}
+
+/// Great-circle distance in degrees between a location and its round trip
+///
+/// Returns None if the location is not visible in the projection or projects
+/// to non-finite coordinates. Measuring on the sphere keeps poles and the
+/// antimeridian (where longitude is ambiguous) from reporting false errors.
+pub fn roundtrip_error(projection: &dyn Projection, lon: f64, lat: f64) -> Option<f64> {
+    if !projection.is_visible(lon, lat) {
+        return None;
+    }
+    let (x, y) = projection.project(lon, lat);
+    if !x.is_finite() || !y.is_finite() {
+        return None;
+    }
+    let (lon2, lat2) = projection.invert(x, y);
+    Some(angular_distance((lon, lat), (lon2, lat2)))
+}
+
+/// Check a projection over a global grid of locations
+///
+/// Samples every 5° of longitude and latitude within ±85° latitude, skipping
+/// locations the projection does not show. Returns the report, or an error
+/// naming the worst location if any error exceeds `tolerance` degrees.
+pub fn verify_roundtrip(projection: &dyn Projection, tolerance: f64) -> D3Result<RoundTripReport> {
+    let steps = (360.0 / GRID_STEP) as i32;
+    let lat_steps = (2.0 * MAX_LAT / GRID_STEP) as i32;
+    let grid = (0..steps).flat_map(|i| {
+        (0..=lat_steps).map(move |j| (-180.0 + i as f64 * GRID_STEP, -MAX_LAT + j as f64 * GRID_STEP))
+    });
+    verify_roundtrip_points(projection, grid, tolerance)
+}
+
+/// Check a projection at the given locations (lon, lat in degrees)
+pub fn verify_roundtrip_points(
+    projection: &dyn Projection,
+    points: impl IntoIterator<Item = (f64, f64)>,
+    tolerance: f64,
+) -> D3Result<RoundTripReport> {
+    let report = RoundTripReport::measure(projection, points);
+    if report.samples == 0 {
+        return Err(D3Error::ConfigError {
+            message: format!("{} projection shows none of the sampled locations", projection.projection_type()),
+        });
+    }
+    match report.worst {
+        Some((lon, lat)) if !report.is_within(tolerance) => Err(D3Error::ConfigError {
+            message: format!(
+                "{} projection round trip off by {:.3e}° at ({}, {})",
+                projection.projection_type(),
+                report.max_error,
+                lon,
+                lat
+            ),
+        }),
+        _ => Ok(report),
+    }
+}
+
+/// Central angle in degrees between two (lon, lat) locations
+fn angular_distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (lon1, lat1) = (a.0.to_radians(), a.1.to_radians());
+    let (lon2, lat2) = (b.0.to_radians(), b.1.to_radians());
+    // Haversine is well conditioned for the tiny distances of interest
+    let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+        + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+    (2.0 * h.sqrt().min(1.0).asin()).to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geo::{
+        AlbersProjection, AlbersUsaProjection, ConicConformalProjection, ConicEqualAreaProjection,
+        EquirectangularProjection, MercatorProjection, NaturalEarth1Projection, OrthographicProjection,
+        ProjectionBuilder,
+    };
+
+    const TOLERANCE: f64 = 1e-6;
+
+    #[test]
+    fn test_all_projections_roundtrip() {
+        let projections: Vec<Box<dyn Projection>> = vec![
+            Box::new(MercatorProjection::new().scale(150.0).translate(480.0, 250.0).center(10.0, 20.0)),
+            Box::new(EquirectangularProjection::new().scale(150.0).translate(480.0, 250.0)),
+            Box::new(OrthographicProjection::new().scale(250.0).rotate(-20.0, -40.0, 0.0)),
+            Box::new(AlbersProjection::usa()),
+            Box::new(ConicConformalProjection::new().parallels(30.0, 60.0).rotate(-10.0, 0.0, 0.0)),
+            Box::new(ConicEqualAreaProjection::new().parallels(20.0, 50.0).rotate(100.0, 0.0, 0.0)),
+            Box::new(NaturalEarth1Projection::new().scale(175.0)),
+        ];
+        for projection in &projections {
+            let report = verify_roundtrip(projection.as_ref(), TOLERANCE)
+                .unwrap_or_else(|e| panic!("{}", e));
+            assert!(report.samples > 100, "{}", projection.projection_type());
+        }
+    }
+
+    #[test]
+    fn test_albers_usa_roundtrip_in_insets() {
+        let projection = AlbersUsaProjection::new().scale(1070.0).translate(480.0, 250.0);
+        let points = [(-122.4, 37.8), (-74.0, 40.7), (-149.9, 61.2), (-157.8, 21.3), (-80.2, 25.8)];
+        let report = verify_roundtrip_points(&projection, points, TOLERANCE).unwrap();
+        assert_eq!(report.samples, points.len());
+
+        // Paris is outside every inset, so nothing is left to check
+        assert!(verify_roundtrip_points(&projection, [(2.35, 48.85)], TOLERANCE).is_err());
+    }
+
+    #[test]
+    fn test_detects_broken_inverse() {
+        struct Broken;
+        impl Projection for Broken {
+            fn project(&self, lon: f64, lat: f64) -> (f64, f64) {
+                (lon, lat)
+            }
+            fn invert(&self, x: f64, y: f64) -> (f64, f64) {
+                (x, -y)
+            }
+            fn projection_type(&self) -> &'static str {
+                "broken"
+            }
+        }
+        let report = RoundTripReport::measure(&Broken, [(0.0, 0.0), (10.0, 30.0)]);
+        assert_eq!(report.worst, Some((10.0, 30.0)));
+        assert!((report.max_error - 60.0).abs() < 1e-9);
+        let err = verify_roundtrip(&Broken, TOLERANCE).unwrap_err();
+        assert!(err.to_string().contains("broken projection round trip"));
+
+        // Antimeridian and pole wrap-arounds are not errors
+        assert!(angular_distance((180.0, 10.0), (-180.0, 10.0)) < 1e-9);
+        assert!(angular_distance((45.0, 90.0), (-120.0, 90.0)) < 1e-9);
+    }
+}
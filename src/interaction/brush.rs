@@ -1,9 +1,31 @@
 //! Brush selection behavior for data filtering
 //!
 //! Provides rectangular selection for filtering data in visualizations.
+//!
+//! Selections are made in pixel space. To keep a selection on the same data
+//! when a scale's domain changes (e.g. new data arrives), store it in domain
+//! space with [`BrushBehavior::store_domain`] and call
+//! [`BrushBehavior::reproject`] after the scales update; a
+//! [`BrushDomainPolicy`] decides what happens when the stored range no longer
+//! fits the new domain.
+//!
+//! An existing selection is edited by dragging its edge and corner handles
+//! ([`BrushHandle`]) or its interior, or nudged with the keyboard. Edges can
+//! snap to tick positions or band boundaries on release ([`BrushSnap`]).
+//! Every interaction queues [`BrushEvent`]s for the host widget to drain
+//! with [`BrushBehavior::poll_event`].
+//!
+//! A brush bound to a [`BandScale`] ([`BrushBehavior::bands`]) selects whole
+//! categories, e.g. bars: it snaps to band boundaries and reports the
+//! selected categories, and a drag started with a modifier held toggles the
+//! brushed categories into the existing set.
+
+use std::collections::VecDeque;
 
 use serde::{Deserialize, Serialize};
 
+use crate::scale::{BandScale, DiscreteScale, Scale, TickOptions};
+
 /// Type of brush selection
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BrushType {
@@ -16,6 +38,56 @@ pub enum BrushType {
     XY,
 }
 
+/// What to do with a stored selection that falls outside a new scale domain
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BrushDomainPolicy {
+    /// Clip the selection to the new domain; clear it if nothing remains
+    #[default]
+    Clamp,
+    /// Shift the selection back inside the domain, keeping its width
+    PreserveWidth,
+    /// Clear the selection unless it lies entirely within the new domain
+    Clear,
+}
+
+impl BrushDomainPolicy {
+    /// Fit a domain range `(lo, hi)` into `domain`, or None to clear
+    pub fn apply(&self, range: (f64, f64), domain: (f64, f64)) -> Option<(f64, f64)> {
+        let (lo, hi) = (range.0.min(range.1), range.0.max(range.1));
+        let (d0, d1) = (domain.0.min(domain.1), domain.0.max(domain.1));
+        if lo >= d0 && hi <= d1 {
+            return Some((lo, hi));
+        }
+
+        match self {
+            BrushDomainPolicy::Clamp => {
+                let (lo, hi) = (lo.max(d0), hi.min(d1));
+                (lo < hi).then_some((lo, hi))
+            }
+            BrushDomainPolicy::PreserveWidth => {
+                let width = (hi - lo).min(d1 - d0);
+                if lo < d0 {
+                    Some((d0, d0 + width))
+                } else {
+                    Some((d1 - width, d1))
+                }
+            }
+            BrushDomainPolicy::Clear => None,
+        }
+    }
+}
+
+/// A brush selection stored in data (domain) coordinates
+///
+/// Axes the brush does not constrain are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainSelection {
+    /// Selected x-domain range (lo, hi)
+    pub x: Option<(f64, f64)>,
+    /// Selected y-domain range (lo, hi)
+    pub y: Option<(f64, f64)>,
+}
+
 /// A rectangular selection area
 ///
 /// Represents the currently selected region in data or pixel coordinates.
@@ -166,6 +238,181 @@ impl BrushSelection {
     }
 }
 
+/// Edge or corner handle of a selection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrushHandle {
+    /// Top edge
+    N,
+    /// Bottom edge
+    S,
+    /// Right edge
+    E,
+    /// Left edge
+    W,
+    /// Top-right corner
+    NE,
+    /// Top-left corner
+    NW,
+    /// Bottom-right corner
+    SE,
+    /// Bottom-left corner
+    SW,
+}
+
+impl BrushHandle {
+    /// Cursor hint for hovering or dragging this handle
+    pub fn cursor(&self) -> BrushCursor {
+        match self {
+            BrushHandle::N | BrushHandle::S => BrushCursor::NSResize,
+            BrushHandle::E | BrushHandle::W => BrushCursor::EWResize,
+            BrushHandle::NE | BrushHandle::SW => BrushCursor::NESWResize,
+            BrushHandle::NW | BrushHandle::SE => BrushCursor::NWSEResize,
+        }
+    }
+
+    /// Whether dragging this handle moves the left, right, top, bottom edges
+    fn edges(&self) -> (bool, bool, bool, bool) {
+        match self {
+            BrushHandle::N => (false, false, true, false),
+            BrushHandle::S => (false, false, false, true),
+            BrushHandle::E => (false, true, false, false),
+            BrushHandle::W => (true, false, false, false),
+            BrushHandle::NE => (false, true, true, false),
+            BrushHandle::NW => (true, false, true, false),
+            BrushHandle::SE => (false, true, false, true),
+            BrushHandle::SW => (true, false, false, true),
+        }
+    }
+}
+
+/// What an interaction does to the selection
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrushMode {
+    /// Drawing a new selection
+    Select,
+    /// Dragging the selection interior
+    Move,
+    /// Dragging an edge or corner handle
+    Resize(BrushHandle),
+    /// Keyboard nudge
+    Nudge,
+}
+
+/// Phase of a brush interaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrushEventKind {
+    /// Pointer down (or nudge started)
+    Start,
+    /// Selection changed during the interaction
+    Move,
+    /// Interaction finished; the selection is final (snapped)
+    End,
+}
+
+/// Notification queued by [`BrushBehavior`] for the host widget
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BrushEvent {
+    /// Interaction phase
+    pub kind: BrushEventKind,
+    /// What the interaction does
+    pub mode: BrushMode,
+    /// Selection after the event
+    pub selection: Option<BrushSelection>,
+}
+
+/// Snapping of selection edges along one axis, applied on release
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum BrushSnap {
+    /// No snapping
+    #[default]
+    None,
+    /// Snap each edge to the nearest pixel position (e.g. tick positions)
+    Positions(Vec<f64>),
+    /// Snap the low edge to the nearest band start and the high edge to the
+    /// nearest band end, so the selection covers whole bands
+    Bands(Vec<(f64, f64)>),
+}
+
+impl BrushSnap {
+    /// Snap to the tick positions of a scale
+    pub fn ticks(scale: &dyn Scale, count: usize) -> Self {
+        let options = TickOptions::new().with_count(count);
+        let mut positions: Vec<f64> = scale.ticks(&options).iter().map(|t| t.position).collect();
+        positions.sort_by(f64::total_cmp);
+        BrushSnap::Positions(positions)
+    }
+
+    /// Snap to the band boundaries of a band scale
+    pub fn bands(scale: &BandScale) -> Self {
+        BrushSnap::Bands(band_extents(scale))
+    }
+
+    /// Snap a `lo <= hi` pixel range
+    pub fn snap_range(&self, lo: f64, hi: f64) -> (f64, f64) {
+        let nearest = |values: &mut dyn Iterator<Item = f64>, target: f64| {
+            values.min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()))
+        };
+        match self {
+            BrushSnap::None => (lo, hi),
+            BrushSnap::Positions(positions) => (
+                nearest(&mut positions.iter().copied(), lo).unwrap_or(lo),
+                nearest(&mut positions.iter().copied(), hi).unwrap_or(hi),
+            ),
+            BrushSnap::Bands(bands) => {
+                let Some(start) = nearest(&mut bands.iter().map(|b| b.0), lo) else {
+                    return (lo, hi);
+                };
+                let end = nearest(&mut bands.iter().map(|b| b.1).filter(|&e| e > start), hi);
+                (start, end.unwrap_or(hi.max(start)))
+            }
+        }
+    }
+}
+
+/// Pixel (start, end) of every band of a band scale
+fn band_extents(scale: &BandScale) -> Vec<(f64, f64)> {
+    let width = scale.bandwidth();
+    (0..scale.len())
+        .map(|i| {
+            let start = scale.scale_index(i);
+            (start, start + width)
+        })
+        .collect()
+}
+
+/// Categories of a brush bound to a band scale
+#[derive(Clone, Debug, Default, PartialEq)]
+struct BandSelection {
+    /// Category labels in band order
+    labels: Vec<String>,
+    /// Pixel (start, end) of each band
+    extents: Vec<(f64, f64)>,
+    /// Selected band indices, ascending
+    selected: Vec<usize>,
+    /// Selection kept from before the current additive drag
+    base: Vec<usize>,
+}
+
+impl BandSelection {
+    fn new(scale: &BandScale) -> Self {
+        Self {
+            labels: scale.domain_values().to_vec(),
+            extents: band_extents(scale),
+            ..Default::default()
+        }
+    }
+
+    /// Bands whose center lies in the `lo..=hi` pixel range
+    fn brushed(&self, lo: f64, hi: f64) -> Vec<usize> {
+        self.extents
+            .iter()
+            .enumerate()
+            .filter(|(_, (start, end))| (lo..=hi).contains(&((start + end) / 2.0)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
 /// Brush behavior state
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 enum BrushState {
@@ -173,14 +420,7 @@ enum BrushState {
     Idle,
     Selecting,
     Moving,
-    ResizingN,
-    ResizingS,
-    ResizingE,
-    ResizingW,
-    ResizingNE,
-    ResizingNW,
-    ResizingSE,
-    ResizingSW,
+    Resizing(BrushHandle),
 }
 
 /// Brush behavior for rectangular selection
@@ -206,6 +446,53 @@ enum BrushState {
 ///     println!("Selected region: ({}, {}) to ({}, {})", sel.x0, sel.y0, sel.x1, sel.y1);
 /// }
 /// ```
+///
+/// # Editing Example
+///
+/// ```
+/// use makepad_d3::interaction::{BrushBehavior, BrushEventKind, BrushSnap};
+///
+/// let mut brush = BrushBehavior::x()
+///     .with_extent(0.0, 0.0, 500.0, 100.0)
+///     .with_snap_x(BrushSnap::Positions(vec![0.0, 100.0, 200.0, 300.0, 400.0, 500.0]));
+/// brush.handle_start(90.0, 50.0);
+/// brush.handle_move(310.0, 50.0);
+/// brush.handle_end();
+/// assert_eq!(brush.selection().unwrap().x_range(), (100.0, 300.0));
+///
+/// // Drag the right edge handle, then nudge the whole selection
+/// brush.handle_start(300.0, 50.0);
+/// brush.handle_move(390.0, 50.0);
+/// brush.handle_end();
+/// brush.nudge(-100.0, 0.0);
+/// assert_eq!(brush.selection().unwrap().x_range(), (0.0, 300.0));
+///
+/// let events: Vec<_> = std::iter::from_fn(|| brush.poll_event()).collect();
+/// assert_eq!(events.last().unwrap().kind, BrushEventKind::End);
+/// ```
+///
+/// # Band Example
+///
+/// ```
+/// use makepad_d3::interaction::BrushBehavior;
+/// use makepad_d3::scale::BandScale;
+///
+/// let scale = BandScale::new().domain(["Q1", "Q2", "Q3", "Q4"]).range(0.0, 400.0);
+/// let mut brush = BrushBehavior::bands(&scale).with_extent(0.0, 0.0, 400.0, 100.0);
+///
+/// // Edges snap out to whole bars
+/// brush.handle_start(120.0, 50.0);
+/// brush.handle_move(260.0, 50.0);
+/// brush.handle_end();
+/// assert_eq!(brush.selected_categories(), vec!["Q2", "Q3"]);
+/// assert_eq!(brush.selection().unwrap().x_range(), (100.0, 300.0));
+///
+/// // With the modifier held, brushed bars toggle into the selection
+/// brush.handle_start_additive(330.0, 50.0);
+/// brush.handle_move(390.0, 50.0);
+/// brush.handle_end();
+/// assert_eq!(brush.selected_bands(), &[1, 2, 3]);
+/// ```
 #[derive(Clone, Debug)]
 pub struct BrushBehavior {
     /// Type of brush
@@ -226,6 +513,20 @@ pub struct BrushBehavior {
     min_size: f64,
     /// Whether keyboard modifiers affect behavior
     keyboard_modifiers: bool,
+    /// Selection in domain space, kept across scale domain changes
+    domain_selection: Option<DomainSelection>,
+    /// Policy for stored selections outside a new domain
+    domain_policy: BrushDomainPolicy,
+    /// Snapping of the x edges on release
+    snap_x: BrushSnap,
+    /// Snapping of the y edges on release
+    snap_y: BrushSnap,
+    /// Events not yet polled by the host
+    events: VecDeque<BrushEvent>,
+    /// Categories, when bound to a band scale
+    bands: Option<BandSelection>,
+    /// Whether the current drag toggles bands into the existing selection
+    additive: bool,
 }
 
 impl Default for BrushBehavior {
@@ -247,7 +548,84 @@ impl BrushBehavior {
             handle_size: 8.0,
             min_size: 1.0,
             keyboard_modifiers: true,
+            domain_selection: None,
+            domain_policy: BrushDomainPolicy::default(),
+            snap_x: BrushSnap::None,
+            snap_y: BrushSnap::None,
+            events: VecDeque::new(),
+            bands: None,
+            additive: false,
+        }
+    }
+
+    /// Create a brush selecting whole categories of a horizontal band scale
+    ///
+    /// The brush runs along x and snaps to band boundaries.
+    pub fn bands(scale: &BandScale) -> Self {
+        let mut brush = Self::x();
+        brush.set_bands(scale);
+        brush
+    }
+
+    /// Create a brush selecting whole categories of a vertical band scale
+    pub fn bands_y(scale: &BandScale) -> Self {
+        let mut brush = Self::y();
+        brush.set_bands(scale);
+        brush
+    }
+
+    /// Bind to a band scale, or rebind after its domain or range changed
+    ///
+    /// Selected categories still in the domain stay selected and the pixel
+    /// selection is moved to cover them.
+    pub fn set_bands(&mut self, scale: &BandScale) {
+        let previous: Vec<String> = self.selected_categories().into_iter().map(str::to_string).collect();
+        let mut bands = BandSelection::new(scale);
+        bands.selected = previous.iter().filter_map(|label| scale.index_of(label)).collect();
+        bands.selected.sort_unstable();
+        bands.base = bands.selected.clone();
+
+        let span = bands
+            .selected
+            .iter()
+            .map(|&i| bands.extents[i])
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)));
+        if self.bands.is_some() || self.selection.is_some() {
+            self.selection = span.and_then(|(lo, hi)| {
+                let mut sel = self.selection.or(self.extent)?.normalized();
+                if self.brush_type == BrushType::Y {
+                    (sel.y0, sel.y1) = (lo, hi);
+                } else {
+                    (sel.x0, sel.x1) = (lo, hi);
+                }
+                Some(self.constrain(sel))
+            });
+        }
+
+        let snap = BrushSnap::Bands(bands.extents.clone());
+        if self.brush_type == BrushType::Y {
+            self.snap_y = snap;
+        } else {
+            self.snap_x = snap;
         }
+        self.bands = Some(bands);
+    }
+
+    /// Selected band indices in band order (empty without a band scale)
+    pub fn selected_bands(&self) -> &[usize] {
+        self.bands.as_ref().map(|b| b.selected.as_slice()).unwrap_or(&[])
+    }
+
+    /// Selected category labels in band order
+    pub fn selected_categories(&self) -> Vec<&str> {
+        self.bands.as_ref().map_or_else(Vec::new, |b| {
+            b.selected.iter().map(|&i| b.labels[i].as_str()).collect()
+        })
+    }
+
+    /// Check if the band at `index` is selected
+    pub fn is_band_selected(&self, index: usize) -> bool {
+        self.selected_bands().binary_search(&index).is_ok()
     }
 
     /// Create an X-axis only brush
@@ -283,11 +661,139 @@ impl BrushBehavior {
         self
     }
 
+    /// Set the policy for stored selections outside a new domain
+    pub fn with_domain_policy(mut self, policy: BrushDomainPolicy) -> Self {
+        self.domain_policy = policy;
+        self
+    }
+
+    /// Snap the x edges of the selection on release
+    pub fn with_snap_x(mut self, snap: BrushSnap) -> Self {
+        self.snap_x = snap;
+        self
+    }
+
+    /// Snap the y edges of the selection on release
+    pub fn with_snap_y(mut self, snap: BrushSnap) -> Self {
+        self.snap_y = snap;
+        self
+    }
+
+    /// Replace the x snapping (e.g. after the scale's ticks changed)
+    pub fn set_snap_x(&mut self, snap: BrushSnap) {
+        self.snap_x = snap;
+    }
+
+    /// Replace the y snapping
+    pub fn set_snap_y(&mut self, snap: BrushSnap) {
+        self.snap_y = snap;
+    }
+
+    /// Take the oldest queued event
+    ///
+    /// Hosts should drain the queue every frame. Consecutive `Move` events
+    /// are merged, so an undrained drag keeps only its latest selection.
+    pub fn poll_event(&mut self) -> Option<BrushEvent> {
+        self.events.pop_front()
+    }
+
     /// Get the current selection
     pub fn selection(&self) -> Option<BrushSelection> {
         self.selection
     }
 
+    /// Get the selection stored in domain space
+    pub fn domain_selection(&self) -> Option<DomainSelection> {
+        self.domain_selection
+    }
+
+    /// Get the domain policy
+    pub fn domain_policy(&self) -> BrushDomainPolicy {
+        self.domain_policy
+    }
+
+    /// Store the current pixel selection in domain space
+    ///
+    /// Pass the scales of the axes the brush constrains (`None` for an axis
+    /// the brush does not use). Call after [`handle_end`](Self::handle_end).
+    pub fn store_domain(&mut self, x_scale: Option<&dyn Scale>, y_scale: Option<&dyn Scale>) {
+        self.domain_selection = self.selection.map(|sel| {
+            let sel = sel.normalized();
+            let invert = |scale: &dyn Scale, a: f64, b: f64| {
+                let (a, b) = (scale.invert(a), scale.invert(b));
+                (a.min(b), a.max(b))
+            };
+            DomainSelection {
+                x: x_scale
+                    .filter(|_| self.brush_type != BrushType::Y)
+                    .map(|s| invert(s, sel.x0, sel.x1)),
+                y: y_scale
+                    .filter(|_| self.brush_type != BrushType::X)
+                    .map(|s| invert(s, sel.y0, sel.y1)),
+            }
+        });
+    }
+
+    /// Set the domain-space selection directly (e.g. restoring saved state)
+    ///
+    /// Call [`reproject`](Self::reproject) to update the pixel selection.
+    pub fn set_domain_selection(&mut self, selection: Option<DomainSelection>) {
+        self.domain_selection = selection;
+    }
+
+    /// Recompute the pixel selection from the stored domain selection
+    ///
+    /// Call after the scales' domain or range changed. Ranges outside the new
+    /// domain are handled by the [`BrushDomainPolicy`]; axes without a stored
+    /// range or scale keep their current pixel extent. Returns the new
+    /// pixel selection.
+    pub fn reproject(
+        &mut self,
+        x_scale: Option<&dyn Scale>,
+        y_scale: Option<&dyn Scale>,
+    ) -> Option<BrushSelection> {
+        let Some(stored) = self.domain_selection else {
+            return self.selection;
+        };
+
+        let policy = self.domain_policy;
+        let fit = |range: Option<(f64, f64)>, scale: Option<&dyn Scale>| match (range, scale) {
+            (Some(range), Some(scale)) => match policy.apply(range, scale.domain()) {
+                Some(fitted) => Ok(Some(fitted)),
+                None => Err(()),
+            },
+            (range, _) => Ok(range),
+        };
+
+        let (Ok(x), Ok(y)) = (fit(stored.x, x_scale), fit(stored.y, y_scale)) else {
+            self.domain_selection = None;
+            self.selection = None;
+            self.update_bands();
+            return None;
+        };
+        self.domain_selection = Some(DomainSelection { x, y });
+
+        let mut sel = self
+            .selection
+            .map(|s| s.normalized())
+            .or_else(|| self.extent.map(|e| e.normalized()))
+            .unwrap_or_default();
+        let project = |scale: &dyn Scale, (lo, hi): (f64, f64)| {
+            let (a, b) = (scale.scale(lo), scale.scale(hi));
+            (a.min(b), a.max(b))
+        };
+        if let (Some(range), Some(scale)) = (x, x_scale) {
+            (sel.x0, sel.x1) = project(scale, range);
+        }
+        if let (Some(range), Some(scale)) = (y, y_scale) {
+            (sel.y0, sel.y1) = project(scale, range);
+        }
+
+        self.selection = Some(self.constrain(sel));
+        self.update_bands();
+        self.selection
+    }
+
     /// Get the brush type
     pub fn brush_type(&self) -> BrushType {
         self.brush_type
@@ -305,17 +811,15 @@ impl BrushBehavior {
 
     /// Check if currently resizing
     pub fn is_resizing(&self) -> bool {
-        matches!(
-            self.state,
-            BrushState::ResizingN
-                | BrushState::ResizingS
-                | BrushState::ResizingE
-                | BrushState::ResizingW
-                | BrushState::ResizingNE
-                | BrushState::ResizingNW
-                | BrushState::ResizingSE
-                | BrushState::ResizingSW
-        )
+        matches!(self.state, BrushState::Resizing(_))
+    }
+
+    /// Handle being dragged, if resizing
+    pub fn active_handle(&self) -> Option<BrushHandle> {
+        match self.state {
+            BrushState::Resizing(handle) => Some(handle),
+            _ => None,
+        }
     }
 
     /// Check if interaction is active
@@ -326,44 +830,68 @@ impl BrushBehavior {
     /// Clear the selection
     pub fn clear(&mut self) {
         self.selection = None;
+        self.domain_selection = None;
         self.state = BrushState::Idle;
+        self.additive = false;
+        if let Some(bands) = &mut self.bands {
+            bands.selected.clear();
+            bands.base.clear();
+        }
     }
 
     /// Set the selection programmatically
     pub fn set_selection(&mut self, selection: Option<BrushSelection>) {
         self.selection = selection.map(|s| self.constrain(s));
+        self.update_bands();
     }
 
     /// Handle the start of an interaction (mouse down)
     pub fn handle_start(&mut self, x: f64, y: f64) {
-        self.start_pos = (x, y);
+        self.additive = false;
+        self.start(x, y);
+    }
 
-        // Check if clicking on existing selection handles
-        if let Some(sel) = &self.selection {
-            let resize_state = self.detect_resize_handle(x, y, sel);
-            if resize_state != BrushState::Idle {
-                self.state = resize_state;
-                self.original_selection = self.selection;
-                return;
-            }
+    /// Handle the start of an interaction with the add modifier held
+    ///
+    /// Always draws a new selection. On a band brush, bands brushed by this
+    /// drag are toggled: unselected ones are added to the selected
+    /// categories and selected ones removed. Other brushes behave as
+    /// [`handle_start`](Self::handle_start) outside the current selection.
+    pub fn handle_start_additive(&mut self, x: f64, y: f64) {
+        self.additive = true;
+        self.start(x, y);
+    }
 
-            // Check if clicking inside selection (for move)
-            if sel.contains(x, y) {
-                self.state = BrushState::Moving;
-                self.original_selection = self.selection;
-                return;
-            }
+    fn start(&mut self, x: f64, y: f64) {
+        self.start_pos = (x, y);
+        self.original_selection = self.selection;
+        if let Some(bands) = &mut self.bands {
+            bands.base = bands.selected.clone();
         }
 
-        // Start new selection
-        self.state = BrushState::Selecting;
-        self.selection = Some(BrushSelection::from_point(x, y));
-        self.original_selection = None;
+        if self.additive {
+            // Additive drags always draw a new selection
+            self.state = BrushState::Selecting;
+            self.selection = Some(BrushSelection::from_point(x, y));
+            self.original_selection = None;
+        } else if let Some(handle) = self.handle_at(x, y) {
+            // Check if clicking on existing selection handles
+            self.state = BrushState::Resizing(handle);
+        } else if self.selection.is_some_and(|sel| sel.normalized().contains(x, y)) {
+            // Check if clicking inside selection (for move)
+            self.state = BrushState::Moving;
+        } else {
+            // Start new selection
+            self.state = BrushState::Selecting;
+            self.selection = Some(BrushSelection::from_point(x, y));
+            self.original_selection = None;
+        }
+        self.emit(BrushEventKind::Start);
     }
 
     /// Handle movement during interaction (mouse move)
     pub fn handle_move(&mut self, x: f64, y: f64) -> bool {
-        match self.state {
+        let changed = match self.state {
             BrushState::Idle => false,
             BrushState::Selecting => {
                 if let Some(mut sel) = self.selection {
@@ -394,68 +922,121 @@ impl BrushBehavior {
                 true
             }
             BrushState::Moving => {
-                if let Some(orig) = &self.original_selection {
-                    let dx = x - self.start_pos.0;
-                    let dy = y - self.start_pos.1;
-                    let moved = BrushSelection::new(
-                        orig.x0 + dx,
-                        orig.y0 + dy,
-                        orig.x1 + dx,
-                        orig.y1 + dy,
-                    );
-                    self.selection = Some(self.constrain(moved));
+                if let Some(orig) = self.original_selection {
+                    self.selection = Some(self.translate(orig, x - self.start_pos.0, y - self.start_pos.1));
                 }
                 true
             }
-            _ => {
-                // Handle resize states
-                self.handle_resize(x, y)
-            }
+            BrushState::Resizing(handle) => self.handle_resize(handle, x, y),
+        };
+        if changed {
+            self.emit(BrushEventKind::Move);
         }
+        changed
     }
 
-    /// Handle resize during interaction
-    fn handle_resize(&mut self, x: f64, y: f64) -> bool {
-        if let Some(orig) = &self.original_selection {
-            let mut sel = *orig;
-            let dx = x - self.start_pos.0;
-            let dy = y - self.start_pos.1;
-
-            match self.state {
-                BrushState::ResizingN => sel.y0 = orig.y0 + dy,
-                BrushState::ResizingS => sel.y1 = orig.y1 + dy,
-                BrushState::ResizingE => sel.x1 = orig.x1 + dx,
-                BrushState::ResizingW => sel.x0 = orig.x0 + dx,
-                BrushState::ResizingNE => {
-                    sel.y0 = orig.y0 + dy;
-                    sel.x1 = orig.x1 + dx;
-                }
-                BrushState::ResizingNW => {
-                    sel.y0 = orig.y0 + dy;
-                    sel.x0 = orig.x0 + dx;
-                }
-                BrushState::ResizingSE => {
-                    sel.y1 = orig.y1 + dy;
-                    sel.x1 = orig.x1 + dx;
-                }
-                BrushState::ResizingSW => {
-                    sel.y1 = orig.y1 + dy;
-                    sel.x0 = orig.x0 + dx;
+    /// Selection shifted by a pixel offset, keeping its size inside the extent
+    ///
+    /// One-dimensional brushes only move along their axis.
+    fn translate(&self, sel: BrushSelection, dx: f64, dy: f64) -> BrushSelection {
+        let mut dx = if self.brush_type == BrushType::Y { 0.0 } else { dx };
+        let mut dy = if self.brush_type == BrushType::X { 0.0 } else { dy };
+        let norm = sel.normalized();
+        if let Some(ext) = &self.extent {
+            let e = ext.normalized();
+            let fit = |d: f64, lo: f64, hi: f64, min: f64, max: f64| {
+                if hi - lo >= max - min {
+                    min - lo
+                } else {
+                    d.clamp(min - lo, max - hi)
                 }
-                _ => return false,
-            }
+            };
+            dx = fit(dx, norm.x0, norm.x1, e.x0, e.x1);
+            dy = fit(dy, norm.y0, norm.y1, e.y0, e.y1);
+        }
+        BrushSelection::new(norm.x0 + dx, norm.y0 + dy, norm.x1 + dx, norm.y1 + dy)
+    }
 
-            self.selection = Some(self.constrain(sel));
-            true
+    /// Move the selection by a pixel offset (keyboard arrows)
+    ///
+    /// The move is kept inside the extent, snapped, and reported as a
+    /// complete start/move/end event sequence. Returns false without a
+    /// selection or during a pointer interaction.
+    pub fn nudge(&mut self, dx: f64, dy: f64) -> bool {
+        let Some(sel) = self.selection else {
+            return false;
+        };
+        if self.is_active() {
+            return false;
+        }
+        self.push_event(BrushEventKind::Start, BrushMode::Nudge);
+        let moved = self.snap(self.translate(sel, dx, dy));
+        // Snapping may pull a small nudge back; step to the next snap position instead
+        let moved = if moved == sel.normalized() && (dx != 0.0 || dy != 0.0) {
+            self.snap_step(moved, dx, dy)
         } else {
-            false
+            moved
+        };
+        self.selection = Some(moved);
+        self.push_event(BrushEventKind::Move, BrushMode::Nudge);
+        self.push_event(BrushEventKind::End, BrushMode::Nudge);
+        true
+    }
+
+    /// Move a snapped selection so its low edge reaches the next snap
+    /// position in the nudge direction
+    fn snap_step(&self, sel: BrushSelection, dx: f64, dy: f64) -> BrushSelection {
+        let step = |snap: &BrushSnap, lo: f64, d: f64| -> f64 {
+            let starts: Vec<f64> = match snap {
+                BrushSnap::None => return d,
+                BrushSnap::Positions(positions) => positions.clone(),
+                BrushSnap::Bands(bands) => bands.iter().map(|b| b.0).collect(),
+            };
+            let next = if d > 0.0 {
+                starts.into_iter().filter(|&p| p > lo + 1e-9).min_by(f64::total_cmp)
+            } else if d < 0.0 {
+                starts.into_iter().filter(|&p| p < lo - 1e-9).max_by(f64::total_cmp)
+            } else {
+                None
+            };
+            next.map_or(0.0, |p| p - lo)
+        };
+        let dx = step(&self.snap_x, sel.x0, dx);
+        let dy = step(&self.snap_y, sel.y0, dy);
+        self.snap(self.translate(sel, dx, dy))
+    }
+
+    /// Handle resize during interaction
+    fn handle_resize(&mut self, handle: BrushHandle, x: f64, y: f64) -> bool {
+        let Some(orig) = self.original_selection.map(|s| s.normalized()) else {
+            return false;
+        };
+        let mut sel = orig;
+        let dx = x - self.start_pos.0;
+        let dy = y - self.start_pos.1;
+        let (left, right, top, bottom) = handle.edges();
+        if left {
+            sel.x0 = orig.x0 + dx;
+        }
+        if right {
+            sel.x1 = orig.x1 + dx;
         }
+        if top {
+            sel.y0 = orig.y0 + dy;
+        }
+        if bottom {
+            sel.y1 = orig.y1 + dy;
+        }
+
+        self.selection = Some(self.constrain(sel));
+        true
     }
 
     /// Handle the end of an interaction (mouse up)
+    ///
+    /// Snaps the selection edges and queues the end event.
     pub fn handle_end(&mut self) -> Option<BrushSelection> {
-        self.state = BrushState::Idle;
-        self.original_selection = None;
+        let was_active = self.is_active();
 
         // Check if selection is too small
         if let Some(sel) = &self.selection {
@@ -463,44 +1044,126 @@ impl BrushBehavior {
                 self.selection = None;
             }
         }
+        self.selection = self.selection.map(|sel| self.snap(sel));
 
+        if was_active {
+            self.emit(BrushEventKind::End);
+        } else {
+            self.update_bands();
+        }
+        self.state = BrushState::Idle;
+        self.original_selection = None;
+        self.additive = false;
         self.selection
     }
 
-    /// Detect which resize handle is under the cursor
-    fn detect_resize_handle(&self, x: f64, y: f64, sel: &BrushSelection) -> BrushState {
-        let norm = sel.normalized();
-        let h = self.handle_size;
+    /// Recompute the selected bands from the pixel selection
+    fn update_bands(&mut self) {
+        let Some(bands) = &mut self.bands else {
+            return;
+        };
+        let brushed = match self.selection.map(|s| s.normalized()) {
+            Some(sel) if self.brush_type == BrushType::Y => bands.brushed(sel.y0, sel.y1),
+            Some(sel) => bands.brushed(sel.x0, sel.x1),
+            None => Vec::new(),
+        };
+        bands.selected = if self.additive {
+            // Symmetric difference: brushed bands flip their state
+            let mut toggled: Vec<usize> = bands.base.iter().copied().filter(|i| !brushed.contains(i)).collect();
+            toggled.extend(brushed.into_iter().filter(|i| !bands.base.contains(i)));
+            toggled.sort_unstable();
+            toggled
+        } else {
+            brushed
+        };
+    }
 
-        // Check corners first
-        if (x - norm.x0).abs() <= h && (y - norm.y0).abs() <= h {
-            return BrushState::ResizingNW;
-        }
-        if (x - norm.x1).abs() <= h && (y - norm.y0).abs() <= h {
-            return BrushState::ResizingNE;
-        }
-        if (x - norm.x0).abs() <= h && (y - norm.y1).abs() <= h {
-            return BrushState::ResizingSW;
+    /// Normalized selection with both axes snapped, kept inside the extent
+    fn snap(&self, sel: BrushSelection) -> BrushSelection {
+        let mut sel = sel.normalized();
+        if self.brush_type != BrushType::Y {
+            (sel.x0, sel.x1) = self.snap_x.snap_range(sel.x0, sel.x1);
         }
-        if (x - norm.x1).abs() <= h && (y - norm.y1).abs() <= h {
-            return BrushState::ResizingSE;
+        if self.brush_type != BrushType::X {
+            (sel.y0, sel.y1) = self.snap_y.snap_range(sel.y0, sel.y1);
         }
+        self.constrain(sel)
+    }
 
-        // Check edges
-        if (y - norm.y0).abs() <= h && x >= norm.x0 && x <= norm.x1 {
-            return BrushState::ResizingN;
-        }
-        if (y - norm.y1).abs() <= h && x >= norm.x0 && x <= norm.x1 {
-            return BrushState::ResizingS;
-        }
-        if (x - norm.x0).abs() <= h && y >= norm.y0 && y <= norm.y1 {
-            return BrushState::ResizingW;
-        }
-        if (x - norm.x1).abs() <= h && y >= norm.y0 && y <= norm.y1 {
-            return BrushState::ResizingE;
+    /// Queue an event for the current interaction
+    fn emit(&mut self, kind: BrushEventKind) {
+        let mode = match self.state {
+            BrushState::Idle | BrushState::Selecting => BrushMode::Select,
+            BrushState::Moving => BrushMode::Move,
+            BrushState::Resizing(handle) => BrushMode::Resize(handle),
+        };
+        self.push_event(kind, mode);
+    }
+
+    fn push_event(&mut self, kind: BrushEventKind, mode: BrushMode) {
+        self.update_bands();
+        let event = BrushEvent {
+            kind,
+            mode,
+            selection: self.selection,
+        };
+        match self.events.back_mut() {
+            Some(last) if kind == BrushEventKind::Move && last.kind == kind && last.mode == mode => *last = event,
+            _ => self.events.push_back(event),
         }
+    }
 
-        BrushState::Idle
+    /// Handle of the current selection under the pointer
+    ///
+    /// Corners are checked before edges. One-dimensional brushes only
+    /// expose the handles along their axis.
+    pub fn handle_at(&self, x: f64, y: f64) -> Option<BrushHandle> {
+        let norm = self.selection?.normalized();
+        let h = self.handle_size;
+        let near = |a: f64, b: f64| (a - b).abs() <= h;
+        let within = |v: f64, lo: f64, hi: f64| v >= lo - h && v <= hi + h;
+
+        match self.brush_type {
+            BrushType::X => {
+                if !within(y, norm.y0, norm.y1) {
+                    None
+                } else if near(x, norm.x0) {
+                    Some(BrushHandle::W)
+                } else if near(x, norm.x1) {
+                    Some(BrushHandle::E)
+                } else {
+                    None
+                }
+            }
+            BrushType::Y => {
+                if !within(x, norm.x0, norm.x1) {
+                    None
+                } else if near(y, norm.y0) {
+                    Some(BrushHandle::N)
+                } else if near(y, norm.y1) {
+                    Some(BrushHandle::S)
+                } else {
+                    None
+                }
+            }
+            BrushType::XY => {
+                // Check corners first
+                let (w, e, n, s) = (near(x, norm.x0), near(x, norm.x1), near(y, norm.y0), near(y, norm.y1));
+                let handle = match (w, e, n, s) {
+                    (true, _, true, _) => BrushHandle::NW,
+                    (_, true, true, _) => BrushHandle::NE,
+                    (true, _, _, true) => BrushHandle::SW,
+                    (_, true, _, true) => BrushHandle::SE,
+                    // Check edges
+                    _ if n && x >= norm.x0 && x <= norm.x1 => BrushHandle::N,
+                    _ if s && x >= norm.x0 && x <= norm.x1 => BrushHandle::S,
+                    _ if w && y >= norm.y0 && y <= norm.y1 => BrushHandle::W,
+                    _ if e && y >= norm.y0 && y <= norm.y1 => BrushHandle::E,
+                    _ => return None,
+                };
+                Some(handle)
+            }
+        }
     }
 
     /// Constrain selection to extent
@@ -517,18 +1180,10 @@ impl BrushBehavior {
 
     /// Get cursor style for current position
     pub fn cursor_at(&self, x: f64, y: f64) -> BrushCursor {
-        if let Some(sel) = &self.selection {
-            let state = self.detect_resize_handle(x, y, sel);
-            match state {
-                BrushState::ResizingN | BrushState::ResizingS => BrushCursor::NSResize,
-                BrushState::ResizingE | BrushState::ResizingW => BrushCursor::EWResize,
-                BrushState::ResizingNE | BrushState::ResizingSW => BrushCursor::NESWResize,
-                BrushState::ResizingNW | BrushState::ResizingSE => BrushCursor::NWSEResize,
-                _ if sel.contains(x, y) => BrushCursor::Move,
-                _ => BrushCursor::Crosshair,
-            }
-        } else {
-            BrushCursor::Crosshair
+        match (self.handle_at(x, y), self.selection) {
+            (Some(handle), _) => handle.cursor(),
+            (None, Some(sel)) if sel.normalized().contains(x, y) => BrushCursor::Move,
+            _ => BrushCursor::Crosshair,
         }
     }
 }
@@ -554,6 +1209,54 @@ pub enum BrushCursor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scale::{LinearScale, ScaleExt};
+
+    fn x_scale(d0: f64, d1: f64) -> LinearScale {
+        LinearScale::new().with_domain(d0, d1).with_range(0.0, 500.0)
+    }
+
+    #[test]
+    fn test_brush_domain_reprojection() {
+        let mut brush = BrushBehavior::x().with_extent(0.0, 0.0, 500.0, 300.0);
+        brush.set_selection(Some(BrushSelection::new(100.0, 0.0, 200.0, 300.0)));
+
+        let scale = x_scale(0.0, 100.0);
+        brush.store_domain(Some(&scale), None);
+        assert_eq!(brush.domain_selection().unwrap().x, Some((20.0, 40.0)));
+        assert_eq!(brush.domain_selection().unwrap().y, None);
+
+        // New data doubles the domain: the same data range is half as wide
+        let sel = brush.reproject(Some(&x_scale(0.0, 200.0)), None).unwrap();
+        assert!((sel.x0 - 50.0).abs() < 1e-9 && (sel.x1 - 100.0).abs() < 1e-9);
+        assert_eq!((sel.y0, sel.y1), (0.0, 300.0));
+    }
+
+    #[test]
+    fn test_brush_domain_policies() {
+        let domain = (30.0, 100.0);
+        let range = (20.0, 40.0);
+        assert_eq!(BrushDomainPolicy::Clamp.apply(range, domain), Some((30.0, 40.0)));
+        assert_eq!(BrushDomainPolicy::PreserveWidth.apply(range, domain), Some((30.0, 50.0)));
+        assert_eq!(BrushDomainPolicy::Clear.apply(range, domain), None);
+        assert_eq!(BrushDomainPolicy::Clear.apply((40.0, 50.0), domain), Some((40.0, 50.0)));
+        assert_eq!(BrushDomainPolicy::Clamp.apply((0.0, 10.0), domain), None);
+        assert_eq!(
+            BrushDomainPolicy::PreserveWidth.apply((90.0, 120.0), domain),
+            Some((70.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn test_brush_reproject_clears_outside_domain() {
+        let mut brush = BrushBehavior::x().with_domain_policy(BrushDomainPolicy::Clear);
+        brush.set_selection(Some(BrushSelection::new(100.0, 0.0, 200.0, 50.0)));
+        brush.store_domain(Some(&x_scale(0.0, 100.0)), None);
+
+        // Sliding window moved past the selection
+        assert_eq!(brush.reproject(Some(&x_scale(30.0, 130.0)), None), None);
+        assert_eq!(brush.selection(), None);
+        assert_eq!(brush.domain_selection(), None);
+    }
 
     #[test]
     fn test_brush_selection_new() {
@@ -738,6 +1441,131 @@ mod tests {
         assert_eq!(brush.cursor_at(50.0, 50.0), BrushCursor::Move);
     }
 
+    #[test]
+    fn test_brush_handles_and_move_at_extent() {
+        let mut brush = BrushBehavior::x().with_extent(0.0, 0.0, 500.0, 100.0);
+        brush.set_selection(Some(BrushSelection::new(100.0, 0.0, 200.0, 100.0)));
+        // X brushes only expose the left and right handles, even at corners
+        assert_eq!(brush.handle_at(101.0, 0.0), Some(BrushHandle::W));
+        assert_eq!(brush.handle_at(150.0, 0.0), None);
+        assert_eq!(brush.cursor_at(199.0, 50.0), BrushCursor::EWResize);
+
+        brush.handle_start(201.0, 50.0);
+        assert_eq!(brush.active_handle(), Some(BrushHandle::E));
+        brush.handle_move(251.0, 90.0);
+        assert_eq!(brush.selection().unwrap().x_range(), (100.0, 250.0));
+        brush.handle_end();
+
+        // Moving past the extent keeps the width instead of squashing
+        brush.handle_start(150.0, 50.0);
+        brush.handle_move(-500.0, 0.0);
+        let sel = brush.selection().unwrap();
+        assert_eq!(sel.x_range(), (0.0, 150.0));
+        assert_eq!(sel.y_range(), (0.0, 100.0));
+    }
+
+    #[test]
+    fn test_brush_band_snapping() {
+        let bands = BandScale::new().domain(["a", "b", "c", "d"]).range(0.0, 400.0).padding(0.2);
+        let snap = BrushSnap::bands(&bands);
+        let mut brush = BrushBehavior::x().with_extent(0.0, 0.0, 400.0, 50.0).with_snap_x(snap.clone());
+
+        brush.handle_start(120.0, 10.0);
+        brush.handle_move(260.0, 10.0);
+        let sel = brush.handle_end().unwrap();
+        let (b0, b1) = match &snap {
+            BrushSnap::Bands(b) => (b[1], b[2]),
+            _ => unreachable!(),
+        };
+        assert_eq!(sel.x_range(), (b0.0, b1.1));
+
+        // A one-pixel nudge steps to the next band
+        assert!(brush.nudge(1.0, 0.0));
+        let moved = brush.selection().unwrap();
+        assert!((moved.x0 - snap.snap_range(b1.0, b1.0).0).abs() < 1e-9);
+        assert_eq!(BrushSnap::Positions(vec![0.0, 50.0]).snap_range(20.0, 30.0), (0.0, 50.0));
+    }
+
+    #[test]
+    fn test_brush_band_categories() {
+        let scale = BandScale::new().domain(["a", "b", "c", "d", "e"]).range(0.0, 500.0);
+        let mut brush = BrushBehavior::bands(&scale).with_extent(0.0, 0.0, 500.0, 50.0);
+        assert!(brush.selected_categories().is_empty());
+
+        brush.handle_start(110.0, 10.0);
+        brush.handle_move(290.0, 10.0);
+        assert_eq!(brush.selected_bands(), &[1, 2]);
+        brush.handle_end();
+
+        // Brushing a selected band with the modifier removes it
+        brush.handle_start_additive(160.0, 10.0);
+        brush.handle_move(240.0, 10.0);
+        brush.handle_end();
+        assert_eq!(brush.selected_categories(), vec!["b"]);
+        assert!(brush.is_band_selected(1) && !brush.is_band_selected(2));
+
+        // Rebinding keeps categories by label
+        let scale = BandScale::new().domain(["e", "b", "x"]).range(0.0, 300.0);
+        brush.set_bands(&scale);
+        assert_eq!(brush.selected_bands(), &[1]);
+        assert_eq!(brush.selection().unwrap().x_range(), (100.0, 200.0));
+
+        // A plain click outside the selection clears it
+        brush.handle_start(20.0, 10.0);
+        brush.handle_end();
+        assert!(brush.selected_bands().is_empty());
+
+        let scale = BandScale::new().domain(["p", "q"]).range(0.0, 200.0);
+        let mut brush = BrushBehavior::bands_y(&scale).with_extent(0.0, 0.0, 100.0, 200.0);
+        brush.handle_start(50.0, 10.0);
+        brush.handle_move(50.0, 90.0);
+        brush.handle_end();
+        assert_eq!(brush.selected_categories(), vec!["p"]);
+        brush.clear();
+        assert!(brush.selected_categories().is_empty());
+        assert!(BrushBehavior::x().selected_bands().is_empty());
+    }
+
+    #[test]
+    fn test_brush_events() {
+        let mut brush = BrushBehavior::xy();
+        brush.handle_start(0.0, 0.0);
+        brush.handle_move(50.0, 50.0);
+        brush.handle_end();
+        brush.handle_start(25.0, 25.0);
+        brush.handle_move(30.0, 30.0);
+        brush.handle_end();
+
+        let events: Vec<BrushEvent> = std::iter::from_fn(|| brush.poll_event()).collect();
+        let kinds: Vec<(BrushEventKind, BrushMode)> = events.iter().map(|e| (e.kind, e.mode)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (BrushEventKind::Start, BrushMode::Select),
+                (BrushEventKind::Move, BrushMode::Select),
+                (BrushEventKind::End, BrushMode::Select),
+                (BrushEventKind::Start, BrushMode::Move),
+                (BrushEventKind::Move, BrushMode::Move),
+                (BrushEventKind::End, BrushMode::Move),
+            ]
+        );
+        assert_eq!(events[5].selection, Some(BrushSelection::new(5.0, 5.0, 55.0, 55.0)));
+        assert!(brush.poll_event().is_none());
+    }
+
+    #[test]
+    fn test_brush_merges_queued_moves() {
+        let mut brush = BrushBehavior::xy();
+        brush.handle_start(0.0, 0.0);
+        for i in 1..=100 {
+            brush.handle_move(i as f64, i as f64);
+        }
+        let events: Vec<BrushEvent> = std::iter::from_fn(|| brush.poll_event()).collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].kind, BrushEventKind::Move);
+        assert_eq!(events[1].selection, Some(BrushSelection::new(0.0, 0.0, 100.0, 100.0)));
+    }
+
     #[test]
     fn test_brush_type() {
         assert_eq!(BrushBehavior::x().brush_type(), BrushType::X);
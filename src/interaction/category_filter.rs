@@ -0,0 +1,332 @@
+//! Categorical filter selection state
+//!
+//! Tracks which categories are selected for linked filtering, with bulk
+//! operations, prefix search for filter lists, and index sets for data keyed
+//! by category.
+
+use std::collections::HashMap;
+
+/// Multi-select filter over a fixed, ordered set of categories
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::interaction::CategoryFilter;
+///
+/// let mut filter = CategoryFilter::new(["Apples", "Apricots", "Bananas", "Cherries"]);
+/// assert!(filter.is_all_selected());
+///
+/// filter.select_none();
+/// filter.toggle("Bananas");
+/// assert_eq!(filter.selected(), vec!["Bananas"]);
+///
+/// // Search narrows the visible list; bulk actions apply to matches
+/// filter.set_query("ap");
+/// filter.select_matching();
+/// assert_eq!(filter.selected(), vec!["Apples", "Apricots", "Bananas"]);
+///
+/// let keys = ["Bananas", "Cherries", "Apples"];
+/// assert_eq!(filter.filtered_indices(&keys), vec![0, 2]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CategoryFilter {
+    /// Categories in display order
+    categories: Vec<String>,
+    /// Selection flag per category
+    selected: Vec<bool>,
+    /// Lookup from category to position
+    index: HashMap<String, usize>,
+    /// Current search query
+    query: String,
+}
+
+impl CategoryFilter {
+    /// Create a filter with every category selected
+    ///
+    /// Duplicate categories are ignored; the first occurrence keeps its place.
+    pub fn new<I, S>(categories: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut filter = Self::default();
+        for category in categories {
+            filter.add_category(category);
+        }
+        filter
+    }
+
+    /// Add a category at the end
+    ///
+    /// New categories start selected if every existing category is selected.
+    /// Returns false if the category already exists.
+    pub fn add_category(&mut self, category: impl Into<String>) -> bool {
+        let category = category.into();
+        if self.index.contains_key(&category) {
+            return false;
+        }
+        let selected = self.is_all_selected();
+        self.index.insert(category.clone(), self.categories.len());
+        self.categories.push(category);
+        self.selected.push(selected);
+        true
+    }
+
+    /// All categories in order
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// Number of categories
+    pub fn len(&self) -> usize {
+        self.categories.len()
+    }
+
+    /// Check if there are no categories
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Position of a category
+    pub fn position(&self, category: &str) -> Option<usize> {
+        self.index.get(category).copied()
+    }
+
+    /// Check if a category is selected (unknown categories are not)
+    pub fn is_selected(&self, category: &str) -> bool {
+        self.position(category).is_some_and(|i| self.selected[i])
+    }
+
+    /// Selected categories in order
+    pub fn selected(&self) -> Vec<&str> {
+        self.categories
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, &s)| s)
+            .map(|(c, _)| c.as_str())
+            .collect()
+    }
+
+    /// Number of selected categories
+    pub fn selected_count(&self) -> usize {
+        self.selected.iter().filter(|&&s| s).count()
+    }
+
+    /// Check if every category is selected
+    pub fn is_all_selected(&self) -> bool {
+        self.selected.iter().all(|&s| s)
+    }
+
+    /// Check if no category is selected
+    pub fn is_none_selected(&self) -> bool {
+        !self.selected.iter().any(|&s| s)
+    }
+
+    /// Select every category
+    pub fn select_all(&mut self) {
+        self.selected.iter_mut().for_each(|s| *s = true);
+    }
+
+    /// Deselect every category
+    pub fn select_none(&mut self) {
+        self.selected.iter_mut().for_each(|s| *s = false);
+    }
+
+    /// Invert the selection
+    pub fn invert(&mut self) {
+        self.selected.iter_mut().for_each(|s| *s = !*s);
+    }
+
+    /// Toggle a category; returns false if it is unknown
+    pub fn toggle(&mut self, category: &str) -> bool {
+        match self.position(category) {
+            Some(i) => {
+                self.selected[i] = !self.selected[i];
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the selection state of a category; returns false if it is unknown
+    pub fn set_selected(&mut self, category: &str, selected: bool) -> bool {
+        match self.position(category) {
+            Some(i) => {
+                self.selected[i] = selected;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Select only the given category
+    pub fn select_only(&mut self, category: &str) -> bool {
+        if self.position(category).is_none() {
+            return false;
+        }
+        self.select_none();
+        self.set_selected(category, true)
+    }
+
+    /// Set the search query
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+    }
+
+    /// Current search query
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Clear the search query
+    pub fn clear_query(&mut self) {
+        self.query.clear();
+    }
+
+    /// Check if a category matches the query (case-insensitive prefix)
+    ///
+    /// Matches either the start of the label or the start of any word in it.
+    pub fn matches_query(&self, category: &str) -> bool {
+        let query = self.query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        let label = category.to_lowercase();
+        label.starts_with(&query)
+            || label
+                .split(|c: char| !c.is_alphanumeric())
+                .any(|word| word.starts_with(&query))
+    }
+
+    /// Categories matching the query, as (position, label), in order
+    pub fn matching(&self) -> Vec<(usize, &str)> {
+        self.categories
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| self.matches_query(c))
+            .map(|(i, c)| (i, c.as_str()))
+            .collect()
+    }
+
+    /// Select every category matching the query
+    pub fn select_matching(&mut self) {
+        self.set_matching(true);
+    }
+
+    /// Deselect every category matching the query
+    pub fn deselect_matching(&mut self) {
+        self.set_matching(false);
+    }
+
+    fn set_matching(&mut self, selected: bool) {
+        let positions: Vec<usize> = self.matching().into_iter().map(|(i, _)| i).collect();
+        for i in positions {
+            self.selected[i] = selected;
+        }
+    }
+
+    /// Selection flag for each key (unknown keys are excluded)
+    pub fn mask<S: AsRef<str>>(&self, keys: &[S]) -> Vec<bool> {
+        keys.iter().map(|k| self.is_selected(k.as_ref())).collect()
+    }
+
+    /// Indices of keys whose category is selected
+    ///
+    /// Use with [`ChartData::labels`](crate::data::ChartData) or any
+    /// per-record category column.
+    pub fn filtered_indices<S: AsRef<str>>(&self, keys: &[S]) -> Vec<usize> {
+        keys.iter()
+            .enumerate()
+            .filter(|(_, k)| self.is_selected(k.as_ref()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fruits() -> CategoryFilter {
+        CategoryFilter::new(["Apple", "Banana", "Blueberry", "Cherry"])
+    }
+
+    #[test]
+    fn test_new_all_selected_and_deduplicated() {
+        let filter = CategoryFilter::new(["A", "B", "A"]);
+        assert_eq!(filter.len(), 2);
+        assert!(filter.is_all_selected());
+        assert_eq!(filter.categories(), &["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn test_bulk_operations() {
+        let mut filter = fruits();
+        filter.select_none();
+        assert!(filter.is_none_selected());
+        filter.toggle("Cherry");
+        filter.invert();
+        assert_eq!(filter.selected(), vec!["Apple", "Banana", "Blueberry"]);
+        filter.select_all();
+        assert_eq!(filter.selected_count(), 4);
+    }
+
+    #[test]
+    fn test_toggle_unknown() {
+        let mut filter = fruits();
+        assert!(!filter.toggle("Durian"));
+        assert!(!filter.is_selected("Durian"));
+        assert!(!filter.select_only("Durian"));
+        assert!(filter.is_all_selected());
+    }
+
+    #[test]
+    fn test_select_only() {
+        let mut filter = fruits();
+        filter.select_only("Banana");
+        assert_eq!(filter.selected(), vec!["Banana"]);
+    }
+
+    #[test]
+    fn test_search_prefix() {
+        let mut filter = CategoryFilter::new(["North America", "South America", "Europe", "Asia"]);
+        filter.set_query("am");
+        let labels: Vec<&str> = filter.matching().iter().map(|(_, c)| *c).collect();
+        assert_eq!(labels, vec!["North America", "South America"]);
+
+        filter.set_query("EU");
+        assert_eq!(filter.matching(), vec![(2, "Europe")]);
+
+        filter.clear_query();
+        assert_eq!(filter.matching().len(), 4);
+    }
+
+    #[test]
+    fn test_bulk_on_matches() {
+        let mut filter = fruits();
+        filter.set_query("b");
+        filter.deselect_matching();
+        assert_eq!(filter.selected(), vec!["Apple", "Cherry"]);
+    }
+
+    #[test]
+    fn test_add_category_inherits_all_selected() {
+        let mut filter = fruits();
+        assert!(filter.add_category("Date"));
+        assert!(filter.is_selected("Date"));
+        assert!(!filter.add_category("Date"));
+
+        filter.toggle("Apple");
+        filter.add_category("Elderberry");
+        assert!(!filter.is_selected("Elderberry"));
+    }
+
+    #[test]
+    fn test_filtered_indices_and_mask() {
+        let mut filter = fruits();
+        filter.set_selected("Banana", false);
+        let keys = vec!["Banana", "Apple", "Unknown", "Cherry", "Banana"];
+        assert_eq!(filter.filtered_indices(&keys), vec![1, 3]);
+        assert_eq!(filter.mask(&keys), vec![false, true, false, true, false]);
+    }
+}
@@ -5,9 +5,17 @@
 //!
 //! # Behaviors
 //!
-//! - [`ZoomBehavior`]: Zoom and pan with scale constraints
-//! - [`BrushBehavior`]: Rectangular selection for filtering data
+//! - [`ZoomBehavior`]: Zoom and pan with scale and translate constraints, plus
+//!   smooth programmatic transitions ([`ZoomTransition`])
+//! - [`BrushBehavior`]: Rectangular selection for filtering data, with resize handles,
+//!   snapping and events, kept across domain changes
+//! - [`RangeSlider`]: Value-domain range selection with histogram feedback
+//! - [`CategoryFilter`]: Multi-select category filter with search
+//! - [`SelectionModel`]: Hovered and selected series with per-key emphasis
 //! - [`TooltipContent`]: Data structure for tooltip display
+//! - [`InteractionSpec`]: Declarative zoom/brush/tooltip configuration
+//! - [`InteractionRecorder`]: Domain-space recording and deterministic replay
+//!   of pointer, wheel and key events
 //!
 //! # Example
 //!
@@ -27,8 +35,23 @@
 
 mod zoom;
 mod brush;
+mod range_slider;
+mod category_filter;
+mod selection;
 mod tooltip;
+mod spec;
+pub mod recorder;
 
-pub use zoom::{ZoomTransform, ZoomBehavior};
-pub use brush::{BrushType, BrushBehavior, BrushSelection};
+pub use zoom::{ZoomTransform, ZoomBehavior, ZoomTransition, Extent};
+pub use brush::{
+    BrushType, BrushBehavior, BrushSelection, BrushDomainPolicy, DomainSelection,
+    BrushCursor, BrushHandle, BrushMode, BrushEvent, BrushEventKind, BrushSnap,
+};
+pub use range_slider::{RangeSlider, SliderHandle, SliderBin};
+pub use category_filter::CategoryFilter;
+pub use selection::{Emphasis, SelectionChange, SelectionModel};
 pub use tooltip::{TooltipContent, TooltipItem, TooltipPosition, TooltipState};
+pub use recorder::{InteractionEvent, InteractionKey, InteractionRecorder, RecordedEvent, ScaleFrame};
+pub use spec::{
+    InteractionSpec, CompiledInteractions, ZoomSpec, BrushSpec, TooltipSpec, TooltipMode,
+};
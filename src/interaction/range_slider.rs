@@ -0,0 +1,501 @@
+//! Value-domain range slider for filter controls
+//!
+//! A one-dimensional range selection in data space, with min/max handle
+//! dragging, step snapping, and optional histogram counts for drawing the
+//! distribution behind the slider. Unlike [`BrushBehavior`](super::BrushBehavior),
+//! which works in pixel coordinates, all positions here are domain values;
+//! convert pointer positions with the axis scale's `invert` first.
+
+/// Part of the slider under the pointer or being dragged
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SliderHandle {
+    /// The lower bound handle
+    Min,
+    /// The upper bound handle
+    Max,
+    /// The selected range between the handles
+    Range,
+}
+
+/// A histogram bin over the slider domain
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliderBin {
+    /// Lower bound of the bin
+    pub x0: f64,
+    /// Upper bound of the bin
+    pub x1: f64,
+    /// Number of values in the bin
+    pub count: usize,
+    /// Whether the bin lies within the selected range
+    pub selected: bool,
+}
+
+/// Range slider over a value domain
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::interaction::{RangeSlider, SliderHandle};
+///
+/// let mut slider = RangeSlider::new(0.0, 100.0)
+///     .with_step(5.0)
+///     .with_histogram(10);
+/// slider.set_values(&[3.0, 12.0, 15.0, 48.0, 51.0, 97.0]);
+///
+/// // Drag the lower handle from 0 to 22 (snaps to 20)
+/// slider.handle_start(0.0);
+/// slider.handle_move(22.0);
+/// slider.handle_end();
+///
+/// assert_eq!(slider.range(), (20.0, 100.0));
+/// assert_eq!(slider.selected_count(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RangeSlider {
+    /// Full value domain
+    domain: (f64, f64),
+    /// Selected range (lo <= hi)
+    range: (f64, f64),
+    /// Snapping step (None = continuous)
+    step: Option<f64>,
+    /// Minimum span between the handles
+    min_span: f64,
+    /// Hit distance for handles, in domain units
+    handle_tolerance: f64,
+    /// Whether the hit distance was set explicitly rather than from the domain
+    custom_tolerance: bool,
+    /// Part currently being dragged
+    active: Option<SliderHandle>,
+    /// Value where the drag started
+    start_value: f64,
+    /// Range when the drag started
+    original_range: (f64, f64),
+    /// Histogram bin counts over the domain
+    bins: Vec<usize>,
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        Self::new(0.0, 1.0)
+    }
+}
+
+impl RangeSlider {
+    /// Create a slider over a domain with the full domain selected
+    pub fn new(min: f64, max: f64) -> Self {
+        let domain = (min.min(max), min.max(max));
+        Self {
+            domain,
+            range: domain,
+            step: None,
+            min_span: 0.0,
+            handle_tolerance: default_tolerance(domain),
+            custom_tolerance: false,
+            active: None,
+            start_value: 0.0,
+            original_range: domain,
+            bins: Vec::new(),
+        }
+    }
+
+    /// Set the snapping step
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = if step > 0.0 { Some(step) } else { None };
+        self.range = self.constrain(self.range);
+        self
+    }
+
+    /// Set the minimum span between the handles
+    pub fn with_min_span(mut self, span: f64) -> Self {
+        self.min_span = span.clamp(0.0, self.domain.1 - self.domain.0);
+        self.range = self.constrain(self.range);
+        self
+    }
+
+    /// Set the handle hit distance in domain units
+    pub fn with_handle_tolerance(mut self, tolerance: f64) -> Self {
+        self.handle_tolerance = tolerance.max(0.0);
+        self.custom_tolerance = true;
+        self
+    }
+
+    /// Use a histogram with the given number of equal-width bins
+    pub fn with_histogram(mut self, bins: usize) -> Self {
+        self.bins = vec![0; bins];
+        self
+    }
+
+    /// Get the domain
+    pub fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    /// Get the selected range
+    pub fn range(&self) -> (f64, f64) {
+        self.range
+    }
+
+    /// Get the snapping step
+    pub fn step(&self) -> Option<f64> {
+        self.step
+    }
+
+    /// Check if the whole domain is selected
+    pub fn is_full(&self) -> bool {
+        self.range == self.domain
+    }
+
+    /// Check if a value lies within the selected range
+    pub fn contains(&self, value: f64) -> bool {
+        value >= self.range.0 && value <= self.range.1
+    }
+
+    /// Part currently being dragged
+    pub fn active_handle(&self) -> Option<SliderHandle> {
+        self.active
+    }
+
+    /// Check if a drag is in progress
+    pub fn is_active(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Change the domain, keeping as much of the selection as fits
+    ///
+    /// The handle hit distance follows the new span unless it was set with
+    /// [`with_handle_tolerance`](Self::with_handle_tolerance). Histogram counts
+    /// are cleared; call [`set_values`](Self::set_values) to refill them.
+    pub fn set_domain(&mut self, min: f64, max: f64) {
+        let was_full = self.is_full();
+        self.domain = (min.min(max), min.max(max));
+        self.min_span = self.min_span.min(self.domain.1 - self.domain.0);
+        if !self.custom_tolerance {
+            self.handle_tolerance = default_tolerance(self.domain);
+        }
+        self.bins.iter_mut().for_each(|c| *c = 0);
+        self.range = if was_full { self.domain } else { self.constrain(self.range) };
+    }
+
+    /// Set the selected range programmatically
+    pub fn set_range(&mut self, lo: f64, hi: f64) {
+        self.range = self.constrain((lo.min(hi), lo.max(hi)));
+    }
+
+    /// Select the whole domain
+    pub fn reset(&mut self) {
+        self.range = self.domain;
+        self.active = None;
+    }
+
+    /// Snap a value to the step grid and clamp it to the domain
+    pub fn snap(&self, value: f64) -> f64 {
+        let (d0, d1) = self.domain;
+        let snapped = match self.step {
+            Some(step) => d0 + ((value - d0) / step).round() * step,
+            None => value,
+        };
+        snapped.clamp(d0, d1)
+    }
+
+    /// Part of the slider at a value, if any
+    pub fn hit_test(&self, value: f64) -> Option<SliderHandle> {
+        let (lo, hi) = self.range;
+        let d_lo = (value - lo).abs();
+        let d_hi = (value - hi).abs();
+        if d_lo <= self.handle_tolerance || d_hi <= self.handle_tolerance {
+            // Prefer the closer handle; when the handles coincide, pick the
+            // one on the pointer's side, or the one that can still move
+            let handle = if d_lo != d_hi {
+                if d_lo < d_hi { SliderHandle::Min } else { SliderHandle::Max }
+            } else if value < lo || (value == lo && hi >= self.domain.1) {
+                SliderHandle::Min
+            } else {
+                SliderHandle::Max
+            };
+            return Some(handle);
+        }
+        if value > lo && value < hi {
+            Some(SliderHandle::Range)
+        } else {
+            None
+        }
+    }
+
+    /// Handle the start of a drag (pointer down) at a domain value
+    ///
+    /// Pressing outside the selection jumps the nearest handle to the value.
+    pub fn handle_start(&mut self, value: f64) {
+        let handle = self.hit_test(value).unwrap_or_else(|| {
+            let handle = if value < self.range.0 {
+                SliderHandle::Min
+            } else {
+                SliderHandle::Max
+            };
+            self.move_handle(handle, value);
+            handle
+        });
+        self.active = Some(handle);
+        self.start_value = value;
+        self.original_range = self.range;
+    }
+
+    /// Handle pointer movement during a drag; returns whether the range changed
+    pub fn handle_move(&mut self, value: f64) -> bool {
+        let Some(handle) = self.active else {
+            return false;
+        };
+        let before = self.range;
+        match handle {
+            SliderHandle::Min | SliderHandle::Max => self.move_handle(handle, value),
+            SliderHandle::Range => {
+                let (lo, hi) = self.original_range;
+                let span = hi - lo;
+                let (d0, d1) = self.domain;
+                let new_lo = self.snap(lo + value - self.start_value).clamp(d0, (d1 - span).max(d0));
+                self.range = (new_lo, new_lo + span);
+            }
+        }
+        self.range != before
+    }
+
+    /// Handle the end of a drag (pointer up); returns the selected range
+    pub fn handle_end(&mut self) -> (f64, f64) {
+        self.active = None;
+        self.range
+    }
+
+    /// Move a handle by a number of steps (e.g. for keyboard control)
+    ///
+    /// Without a step, moves by 1% of the domain per step.
+    pub fn nudge(&mut self, handle: SliderHandle, steps: i32) {
+        let step = self.step.unwrap_or((self.domain.1 - self.domain.0) * 0.01);
+        let delta = step * steps as f64;
+        match handle {
+            SliderHandle::Min => self.move_handle(handle, self.range.0 + delta),
+            SliderHandle::Max => self.move_handle(handle, self.range.1 + delta),
+            SliderHandle::Range => {
+                let span = self.range.1 - self.range.0;
+                let (d0, d1) = self.domain;
+                let lo = self.snap(self.range.0 + delta).clamp(d0, (d1 - span).max(d0));
+                self.range = (lo, lo + span);
+            }
+        }
+    }
+
+    /// Move one handle, keeping it on its side of the other
+    fn move_handle(&mut self, handle: SliderHandle, value: f64) {
+        let v = self.snap(value);
+        let (lo, hi) = self.range;
+        self.range = match handle {
+            SliderHandle::Min => (v.min(self.snap_down(hi - self.min_span)), hi),
+            SliderHandle::Max => (lo, v.max(self.snap_up(lo + self.min_span))),
+            SliderHandle::Range => self.range,
+        };
+    }
+
+    fn snap_down(&self, value: f64) -> f64 {
+        match self.step {
+            Some(step) => {
+                let d0 = self.domain.0;
+                (d0 + ((value - d0) / step + 1e-9).floor() * step).clamp(d0, self.domain.1)
+            }
+            None => value.clamp(self.domain.0, self.domain.1),
+        }
+    }
+
+    fn snap_up(&self, value: f64) -> f64 {
+        match self.step {
+            Some(step) => {
+                let d0 = self.domain.0;
+                (d0 + ((value - d0) / step - 1e-9).ceil() * step).clamp(d0, self.domain.1)
+            }
+            None => value.clamp(self.domain.0, self.domain.1),
+        }
+    }
+
+    /// Snap, order and clamp a range, enforcing the minimum span
+    fn constrain(&self, (lo, hi): (f64, f64)) -> (f64, f64) {
+        let lo = self.snap(lo);
+        let mut hi = self.snap(hi).max(lo);
+        if hi - lo < self.min_span {
+            hi = self.snap_up(lo + self.min_span);
+            if hi - lo < self.min_span {
+                return (self.snap_down(hi - self.min_span), hi);
+            }
+        }
+        (lo, hi)
+    }
+
+    /// Recompute histogram counts from raw values
+    ///
+    /// Values outside the domain and NaN are ignored.
+    pub fn set_values(&mut self, values: &[f64]) {
+        let n = self.bins.len();
+        self.bins.iter_mut().for_each(|c| *c = 0);
+        let (d0, d1) = self.domain;
+        if n == 0 || d1 <= d0 {
+            return;
+        }
+        for &v in values {
+            if v.is_nan() || v < d0 || v > d1 {
+                continue;
+            }
+            let bin = (((v - d0) / (d1 - d0)) * n as f64).floor() as usize;
+            self.bins[bin.min(n - 1)] += 1;
+        }
+    }
+
+    /// Set precomputed histogram counts (equal-width bins over the domain)
+    pub fn set_bin_counts(&mut self, counts: Vec<usize>) {
+        self.bins = counts;
+    }
+
+    /// Histogram bins with their selection state
+    pub fn histogram(&self) -> Vec<SliderBin> {
+        let n = self.bins.len();
+        let (d0, d1) = self.domain;
+        let width = if n > 0 { (d1 - d0) / n as f64 } else { 0.0 };
+        self.bins
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let x0 = d0 + i as f64 * width;
+                let x1 = if i + 1 == n { d1 } else { x0 + width };
+                SliderBin {
+                    x0,
+                    x1,
+                    count,
+                    selected: x0 >= self.range.0 - 1e-9 && x1 <= self.range.1 + 1e-9,
+                }
+            })
+            .collect()
+    }
+
+    /// Largest bin count (for scaling bar heights)
+    pub fn max_bin_count(&self) -> usize {
+        self.bins.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Total count of values in bins within the selected range
+    pub fn selected_count(&self) -> usize {
+        self.histogram().iter().filter(|b| b.selected).map(|b| b.count).sum()
+    }
+}
+
+/// Handle hit distance of 2% of the domain span
+fn default_tolerance(domain: (f64, f64)) -> f64 {
+    (domain.1 - domain.0) * 0.02
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_selects_full_domain() {
+        let slider = RangeSlider::new(10.0, 0.0);
+        assert_eq!(slider.domain(), (0.0, 10.0));
+        assert_eq!(slider.range(), (0.0, 10.0));
+        assert!(slider.is_full());
+    }
+
+    #[test]
+    fn test_drag_handles() {
+        let mut slider = RangeSlider::new(0.0, 100.0);
+        slider.handle_start(100.0);
+        assert_eq!(slider.active_handle(), Some(SliderHandle::Max));
+        assert!(slider.handle_move(60.0));
+        assert_eq!(slider.handle_end(), (0.0, 60.0));
+
+        // Max handle cannot cross the min handle
+        slider.handle_start(0.0);
+        assert_eq!(slider.active_handle(), Some(SliderHandle::Min));
+        slider.handle_move(80.0);
+        assert_eq!(slider.range(), (60.0, 60.0));
+        slider.handle_end();
+    }
+
+    #[test]
+    fn test_drag_range_clamped_to_domain() {
+        let mut slider = RangeSlider::new(0.0, 100.0);
+        slider.set_range(20.0, 40.0);
+        slider.handle_start(30.0);
+        assert_eq!(slider.active_handle(), Some(SliderHandle::Range));
+        slider.handle_move(100.0);
+        assert_eq!(slider.range(), (80.0, 100.0));
+        slider.handle_move(-50.0);
+        assert_eq!(slider.range(), (0.0, 20.0));
+    }
+
+    #[test]
+    fn test_press_outside_jumps_nearest_handle() {
+        let mut slider = RangeSlider::new(0.0, 100.0);
+        slider.set_range(40.0, 60.0);
+        slider.handle_start(10.0);
+        assert_eq!(slider.range(), (10.0, 60.0));
+        assert_eq!(slider.active_handle(), Some(SliderHandle::Min));
+        slider.handle_end();
+        slider.handle_start(90.0);
+        assert_eq!(slider.range(), (10.0, 90.0));
+    }
+
+    #[test]
+    fn test_step_snapping() {
+        let mut slider = RangeSlider::new(0.0, 10.0).with_step(2.0);
+        slider.set_range(1.2, 6.9);
+        assert_eq!(slider.range(), (2.0, 6.0));
+        slider.nudge(SliderHandle::Max, 1);
+        assert_eq!(slider.range(), (2.0, 8.0));
+        slider.nudge(SliderHandle::Range, 5);
+        assert_eq!(slider.range(), (4.0, 10.0));
+    }
+
+    #[test]
+    fn test_min_span() {
+        let mut slider = RangeSlider::new(0.0, 100.0).with_min_span(10.0);
+        slider.set_range(50.0, 52.0);
+        assert_eq!(slider.range(), (50.0, 60.0));
+        slider.set_range(95.0, 100.0);
+        assert_eq!(slider.range(), (90.0, 100.0));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let mut slider = RangeSlider::new(0.0, 10.0).with_histogram(5);
+        slider.set_values(&[0.5, 1.0, 3.0, 9.9, 10.0, 11.0, f64::NAN]);
+        let bins = slider.histogram();
+        assert_eq!(bins.iter().map(|b| b.count).collect::<Vec<_>>(), vec![2, 1, 0, 0, 2]);
+        assert_eq!(slider.max_bin_count(), 2);
+        assert_eq!(slider.selected_count(), 5);
+
+        slider.set_range(2.0, 8.0);
+        let selected: Vec<bool> = slider.histogram().iter().map(|b| b.selected).collect();
+        assert_eq!(selected, vec![false, true, true, true, false]);
+        assert_eq!(slider.selected_count(), 1);
+    }
+
+    #[test]
+    fn test_set_domain() {
+        let mut slider = RangeSlider::new(0.0, 10.0);
+        slider.set_domain(0.0, 20.0);
+        assert!(slider.is_full());
+
+        slider.set_range(5.0, 15.0);
+        slider.set_domain(0.0, 12.0);
+        assert_eq!(slider.range(), (5.0, 12.0));
+    }
+
+    #[test]
+    fn test_set_domain_rescales_tolerance_and_clears_bins() {
+        let mut slider = RangeSlider::new(0.0, 1.0).with_histogram(4);
+        slider.set_values(&[0.1, 0.6]);
+        slider.set_domain(0.0, 10000.0);
+        assert_eq!(slider.hit_test(9900.0), Some(SliderHandle::Max));
+        assert_eq!(slider.max_bin_count(), 0);
+        assert_eq!(slider.histogram().len(), 4);
+
+        let mut fixed = RangeSlider::new(0.0, 1.0).with_handle_tolerance(0.05);
+        fixed.set_domain(0.0, 10000.0);
+        assert_eq!(fixed.hit_test(9900.0), Some(SliderHandle::Range));
+    }
+}
@@ -0,0 +1,438 @@
+//! Recording and deterministic replay of chart interactions
+//!
+//! An [`InteractionRecorder`] captures pointer, wheel and key events with
+//! their timestamps. Pointer positions are stored in domain space (through
+//! the chart's scales, see [`ScaleFrame`]), so a recording made at one chart
+//! size replays correctly at another. Recordings serialize to JSON, which
+//! makes them usable as test fixtures and as attachments to bug reports.
+//!
+//! Replaying feeds the events, mapped back to pixels, into a
+//! [`BrushBehavior`], a [`ZoomBehavior`] or a [`TooltipState`] in order,
+//! with no timing involved, so the outcome is the same on every run.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::interaction::{BrushBehavior, InteractionEvent, InteractionRecorder, ScaleFrame};
+//! use makepad_d3::scale::{LinearScale, ScaleExt};
+//!
+//! let y = LinearScale::new().with_domain(0.0, 1.0).with_range(100.0, 0.0);
+//! let small = LinearScale::new().with_domain(0.0, 10.0).with_range(0.0, 500.0);
+//! let mut recorder = InteractionRecorder::new();
+//! let frame = ScaleFrame::new(&small, &y);
+//! recorder.record(0.0, InteractionEvent::PointerDown { x: 100.0, y: 50.0, additive: false }, &frame);
+//! recorder.record(16.0, InteractionEvent::PointerMove { x: 200.0, y: 50.0 }, &frame);
+//! recorder.record(32.0, InteractionEvent::PointerUp, &frame);
+//!
+//! // Replay on a chart twice as wide
+//! let recording = InteractionRecorder::from_json(&recorder.to_json()).unwrap();
+//! let wide = LinearScale::new().with_domain(0.0, 10.0).with_range(0.0, 1000.0);
+//! let mut brush = BrushBehavior::x();
+//! recording.replay_brush(&mut brush, &ScaleFrame::new(&wide, &y));
+//! assert_eq!(brush.selection().unwrap().x_range(), (200.0, 400.0));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::{BrushBehavior, TooltipContent, TooltipState, ZoomBehavior, ZoomTransform};
+use crate::error::{D3Error, D3Result};
+use crate::scale::Scale;
+
+/// Pixel distance an arrow key nudges a brush selection on replay
+pub const KEY_NUDGE_PX: f64 = 10.0;
+
+/// Keys that drive chart interactions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InteractionKey {
+    /// Left arrow
+    Left,
+    /// Right arrow
+    Right,
+    /// Up arrow
+    Up,
+    /// Down arrow
+    Down,
+    /// Escape
+    Escape,
+}
+
+/// A chart input event
+///
+/// Positions are in pixels when passed to [`InteractionRecorder::record`]
+/// and in domain space once recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InteractionEvent {
+    /// Button pressed
+    PointerDown {
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+        /// Whether the add-to-selection modifier was held
+        additive: bool,
+    },
+    /// Pointer moved, pressed or not
+    PointerMove {
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+    },
+    /// Button released
+    PointerUp,
+    /// Pointer left the chart
+    PointerLeave,
+    /// Wheel scrolled at a position
+    Wheel {
+        /// Horizontal position
+        x: f64,
+        /// Vertical position
+        y: f64,
+        /// Wheel delta (positive = zoom in)
+        delta: f64,
+    },
+    /// Pinch gesture centered at a position
+    Pinch {
+        /// Horizontal center
+        x: f64,
+        /// Vertical center
+        y: f64,
+        /// Scale factor (> 1 = zoom in)
+        scale: f64,
+    },
+    /// Key pressed
+    Key(InteractionKey),
+}
+
+impl InteractionEvent {
+    /// Position of the event, if it has one
+    pub fn position(&self) -> Option<(f64, f64)> {
+        match *self {
+            InteractionEvent::PointerDown { x, y, .. }
+            | InteractionEvent::PointerMove { x, y }
+            | InteractionEvent::Wheel { x, y, .. }
+            | InteractionEvent::Pinch { x, y, .. } => Some((x, y)),
+            _ => None,
+        }
+    }
+
+    /// Copy with the position mapped through `f`
+    pub fn map_position(self, f: impl Fn(f64, f64) -> (f64, f64)) -> Self {
+        match self {
+            InteractionEvent::PointerDown { x, y, additive } => {
+                let (x, y) = f(x, y);
+                InteractionEvent::PointerDown { x, y, additive }
+            }
+            InteractionEvent::PointerMove { x, y } => {
+                let (x, y) = f(x, y);
+                InteractionEvent::PointerMove { x, y }
+            }
+            InteractionEvent::Wheel { x, y, delta } => {
+                let (x, y) = f(x, y);
+                InteractionEvent::Wheel { x, y, delta }
+            }
+            InteractionEvent::Pinch { x, y, scale } => {
+                let (x, y) = f(x, y);
+                InteractionEvent::Pinch { x, y, scale }
+            }
+            other => other,
+        }
+    }
+}
+
+/// An event with the time it happened
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Milliseconds since an arbitrary start
+    pub time_ms: f64,
+    /// The event, positions in domain space
+    pub event: InteractionEvent,
+}
+
+/// The x and y scales that map domain space to chart pixels
+///
+/// Record and replay with the chart's base (unzoomed) scales, so zooming
+/// during the recording does not move later pointer positions.
+#[derive(Clone, Copy)]
+pub struct ScaleFrame<'a> {
+    /// Horizontal scale
+    pub x: &'a dyn Scale,
+    /// Vertical scale
+    pub y: &'a dyn Scale,
+}
+
+impl<'a> ScaleFrame<'a> {
+    /// Create a frame from two scales
+    pub fn new(x: &'a dyn Scale, y: &'a dyn Scale) -> Self {
+        Self { x, y }
+    }
+
+    /// Pixel position to domain values
+    pub fn to_domain(&self, px: f64, py: f64) -> (f64, f64) {
+        (self.x.invert(px), self.y.invert(py))
+    }
+
+    /// Domain values to pixel position
+    pub fn to_pixel(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.x.scale(x), self.y.scale(y))
+    }
+}
+
+/// Recorded sequence of chart interactions
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InteractionRecorder {
+    /// Events in time order
+    events: Vec<RecordedEvent>,
+}
+
+impl InteractionRecorder {
+    /// Create an empty recording
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a pixel-space event, storing its position in domain space
+    ///
+    /// Times earlier than the previous event are raised to it, so the
+    /// recording stays in order.
+    pub fn record(&mut self, time_ms: f64, event: InteractionEvent, frame: &ScaleFrame) {
+        self.record_domain(time_ms, event.map_position(|x, y| frame.to_domain(x, y)));
+    }
+
+    /// Record an event whose position is already in domain space
+    pub fn record_domain(&mut self, time_ms: f64, event: InteractionEvent) {
+        let time_ms = self.events.last().map_or(time_ms, |last| time_ms.max(last.time_ms));
+        self.events.push(RecordedEvent { time_ms, event });
+    }
+
+    /// Recorded events in time order
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// Number of recorded events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check if nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Remove all events
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// Time between the first and last event
+    pub fn duration_ms(&self) -> f64 {
+        match (self.events.first(), self.events.last()) {
+            (Some(first), Some(last)) => last.time_ms - first.time_ms,
+            _ => 0.0,
+        }
+    }
+
+    /// Serialize as compact JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Load a recording saved with [`to_json`](Self::to_json)
+    ///
+    /// # Errors
+    ///
+    /// [`D3Error::ParseError`] if the JSON is not a recording.
+    pub fn from_json(json: &str) -> D3Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| D3Error::parse_error(format!("interaction recording: {}", e)))
+    }
+
+    /// Events with positions mapped to pixels in `frame`
+    pub fn pixel_events(&self, frame: &ScaleFrame) -> Vec<RecordedEvent> {
+        self.events
+            .iter()
+            .map(|e| RecordedEvent {
+                time_ms: e.time_ms,
+                event: e.event.map_position(|x, y| frame.to_pixel(x, y)),
+            })
+            .collect()
+    }
+
+    /// Replay against a brush
+    ///
+    /// Pointer drags draw, move and resize the selection, arrow keys nudge
+    /// it by [`KEY_NUDGE_PX`] and Escape clears it.
+    pub fn replay_brush(&self, brush: &mut BrushBehavior, frame: &ScaleFrame) {
+        let mut pressed = false;
+        for recorded in self.pixel_events(frame) {
+            match recorded.event {
+                InteractionEvent::PointerDown { x, y, additive } => {
+                    pressed = true;
+                    if additive {
+                        brush.handle_start_additive(x, y);
+                    } else {
+                        brush.handle_start(x, y);
+                    }
+                }
+                InteractionEvent::PointerMove { x, y } if pressed => {
+                    brush.handle_move(x, y);
+                }
+                InteractionEvent::PointerUp if pressed => {
+                    pressed = false;
+                    brush.handle_end();
+                }
+                InteractionEvent::Key(key) => {
+                    let (dx, dy) = match key {
+                        InteractionKey::Left => (-KEY_NUDGE_PX, 0.0),
+                        InteractionKey::Right => (KEY_NUDGE_PX, 0.0),
+                        InteractionKey::Up => (0.0, -KEY_NUDGE_PX),
+                        InteractionKey::Down => (0.0, KEY_NUDGE_PX),
+                        InteractionKey::Escape => {
+                            brush.clear();
+                            continue;
+                        }
+                    };
+                    brush.nudge(dx, dy);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Replay against a zoom behavior, updating `transform`
+    ///
+    /// Pointer drags pan, wheel and pinch events zoom about their position
+    /// and keys are ignored.
+    pub fn replay_zoom(&self, zoom: &ZoomBehavior, transform: &mut ZoomTransform, frame: &ScaleFrame) {
+        let mut drag: Option<(f64, f64)> = None;
+        for recorded in self.pixel_events(frame) {
+            match recorded.event {
+                InteractionEvent::PointerDown { x, y, .. } => drag = Some((x, y)),
+                InteractionEvent::PointerMove { x, y } => {
+                    if let Some((x0, y0)) = drag {
+                        zoom.handle_pan(transform, x - x0, y - y0);
+                        drag = Some((x, y));
+                    }
+                }
+                InteractionEvent::PointerUp | InteractionEvent::PointerLeave => drag = None,
+                InteractionEvent::Wheel { x, y, delta } => {
+                    zoom.handle_wheel(transform, delta, x, y);
+                }
+                InteractionEvent::Pinch { x, y, scale } => {
+                    zoom.handle_pinch(transform, scale, x, y);
+                }
+                InteractionEvent::Key(_) => {}
+            }
+        }
+    }
+
+    /// Replay hover against a tooltip
+    ///
+    /// On every pointer move `content` is asked for the tooltip at the
+    /// domain position; the tooltip shows it at the pixel position, or
+    /// hides for None. Leaving the chart hides the tooltip.
+    pub fn replay_tooltip<F>(&self, tooltip: &mut TooltipState, frame: &ScaleFrame, mut content: F)
+    where
+        F: FnMut(f64, f64) -> Option<TooltipContent>,
+    {
+        for recorded in &self.events {
+            match recorded.event {
+                InteractionEvent::PointerMove { x, y } => match content(x, y) {
+                    Some(c) => {
+                        let (px, py) = frame.to_pixel(x, y);
+                        tooltip.show(px, py, c);
+                    }
+                    None => tooltip.hide(),
+                },
+                InteractionEvent::PointerLeave => tooltip.hide(),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::{LinearScale, ScaleExt};
+
+    fn linear(d1: f64, r1: f64) -> LinearScale {
+        LinearScale::new().with_domain(0.0, d1).with_range(0.0, r1)
+    }
+
+    #[test]
+    fn test_recorder_normalizes_and_orders() {
+        let (x, y) = (linear(10.0, 100.0), linear(1.0, 50.0));
+        let frame = ScaleFrame::new(&x, &y);
+        let mut recorder = InteractionRecorder::new();
+        recorder.record(10.0, InteractionEvent::Wheel { x: 50.0, y: 25.0, delta: 2.0 }, &frame);
+        recorder.record(5.0, InteractionEvent::Key(InteractionKey::Escape), &frame);
+
+        let wheel = InteractionEvent::Wheel { x: 5.0, y: 0.5, delta: 2.0 };
+        assert_eq!(recorder.events()[0].event, wheel);
+        assert_eq!(recorder.events()[1].time_ms, 10.0);
+        assert_eq!(recorder.duration_ms(), 0.0);
+        assert!(matches!(InteractionRecorder::from_json("[1]"), Err(D3Error::ParseError { .. })));
+    }
+
+    #[test]
+    fn test_replay_zoom_is_deterministic() {
+        let (x, y) = (linear(100.0, 400.0), linear(100.0, 400.0));
+        let frame = ScaleFrame::new(&x, &y);
+        let mut recorder = InteractionRecorder::new();
+        for (t, event) in [
+            InteractionEvent::Wheel { x: 0.0, y: 0.0, delta: 100.0 },
+            InteractionEvent::PointerMove { x: 40.0, y: 40.0 },
+            InteractionEvent::PointerDown { x: 40.0, y: 40.0, additive: false },
+            InteractionEvent::PointerMove { x: 60.0, y: 20.0 },
+            InteractionEvent::PointerUp,
+            InteractionEvent::PointerMove { x: 100.0, y: 100.0 },
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            recorder.record(t as f64, event, &frame);
+        }
+
+        let zoom = ZoomBehavior::new().wheel_delta(0.01);
+        let replay = |recording: &InteractionRecorder| {
+            let mut transform = ZoomTransform::identity();
+            recording.replay_zoom(&zoom, &mut transform, &frame);
+            transform
+        };
+        let first = replay(&recorder);
+        assert_eq!(first, ZoomTransform::new(2.0, 20.0, -20.0));
+        assert_eq!(replay(&InteractionRecorder::from_json(&recorder.to_json()).unwrap()), first);
+    }
+
+    #[test]
+    fn test_replay_brush_keys_and_tooltip() {
+        let (x, y) = (linear(100.0, 100.0), linear(100.0, 100.0));
+        let frame = ScaleFrame::new(&x, &y);
+        let mut recorder = InteractionRecorder::new();
+        recorder.record_domain(0.0, InteractionEvent::PointerDown { x: 10.0, y: 10.0, additive: false });
+        recorder.record_domain(1.0, InteractionEvent::PointerMove { x: 30.0, y: 40.0 });
+        recorder.record_domain(2.0, InteractionEvent::PointerUp);
+        recorder.record_domain(3.0, InteractionEvent::Key(InteractionKey::Right));
+
+        let mut brush = BrushBehavior::xy().with_extent(0.0, 0.0, 100.0, 100.0);
+        recorder.replay_brush(&mut brush, &frame);
+        let sel = brush.selection().unwrap();
+        assert_eq!((sel.x_range(), sel.y_range()), ((20.0, 40.0), (10.0, 40.0)));
+
+        recorder.record_domain(4.0, InteractionEvent::Key(InteractionKey::Escape));
+        recorder.replay_brush(&mut brush, &frame);
+        assert!(brush.selection().is_none());
+
+        let mut tooltip = TooltipState::new();
+        recorder.replay_tooltip(&mut tooltip, &frame, |x, _| {
+            (x > 20.0).then(|| TooltipContent::new(format!("{}", x)))
+        });
+        assert!(tooltip.is_visible());
+        assert_eq!(tooltip.content.title, "30");
+        recorder.record_domain(5.0, InteractionEvent::PointerLeave);
+        recorder.replay_tooltip(&mut tooltip, &frame, |_, _| None);
+        assert!(!tooltip.is_visible());
+    }
+}
@@ -0,0 +1,210 @@
+//! Hover and selection state shared by chart widgets
+//!
+//! A [`SelectionModel`] records which series (or categories) are hovered and
+//! selected, keyed by name. Widgets write pointer interactions into it and
+//! read back an [`Emphasis`] per key, so linked views and highlight
+//! animations agree on what is in focus.
+
+use std::collections::VecDeque;
+
+/// How a key should be drawn given the current focus
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Emphasis {
+    /// Nothing is in focus
+    #[default]
+    Normal,
+    /// The key is hovered or selected
+    Highlighted,
+    /// Another key is in focus
+    Dimmed,
+}
+
+/// A change recorded by [`SelectionModel`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SelectionChange {
+    /// The hovered key changed (None = pointer left)
+    Hovered(Option<String>),
+    /// A key was added to the selection
+    Selected(String),
+    /// A key was removed from the selection
+    Deselected(String),
+    /// The selection was emptied
+    Cleared,
+}
+
+/// Hovered and selected keys
+///
+/// Keys in focus (the hovered key plus every selected key) are highlighted
+/// and all others dimmed; with nothing in focus every key is normal.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::interaction::{Emphasis, SelectionChange, SelectionModel};
+///
+/// let mut model = SelectionModel::new();
+/// assert_eq!(model.emphasis("a"), Emphasis::Normal);
+///
+/// model.hover(Some("a"));
+/// assert_eq!(model.emphasis("a"), Emphasis::Highlighted);
+/// assert_eq!(model.emphasis("b"), Emphasis::Dimmed);
+///
+/// model.toggle("b");
+/// model.hover(None);
+/// assert_eq!(model.emphasis("a"), Emphasis::Dimmed);
+/// assert_eq!(model.selected(), &["b".to_string()]);
+/// assert_eq!(model.poll_change(), Some(SelectionChange::Hovered(Some("a".into()))));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SelectionModel {
+    /// Key under the pointer
+    hovered: Option<String>,
+    /// Selected keys in selection order
+    selected: Vec<String>,
+    /// Pending changes
+    changes: VecDeque<SelectionChange>,
+    /// Change counter (incremented on each change)
+    version: u64,
+}
+
+impl SelectionModel {
+    /// Create a model with nothing hovered or selected
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the hovered key; returns false if it was already hovered
+    pub fn hover(&mut self, key: Option<&str>) -> bool {
+        if self.hovered.as_deref() == key {
+            return false;
+        }
+        self.hovered = key.map(str::to_string);
+        self.record(SelectionChange::Hovered(self.hovered.clone()));
+        true
+    }
+
+    /// Add a key to the selection; returns false if it was already selected
+    pub fn select(&mut self, key: &str) -> bool {
+        if self.is_selected(key) {
+            return false;
+        }
+        self.selected.push(key.to_string());
+        self.record(SelectionChange::Selected(key.to_string()));
+        true
+    }
+
+    /// Remove a key from the selection; returns false if it was not selected
+    pub fn deselect(&mut self, key: &str) -> bool {
+        let Some(i) = self.selected.iter().position(|k| k == key) else {
+            return false;
+        };
+        self.selected.remove(i);
+        self.record(SelectionChange::Deselected(key.to_string()));
+        true
+    }
+
+    /// Select a key if unselected, otherwise deselect it
+    pub fn toggle(&mut self, key: &str) {
+        if !self.deselect(key) {
+            self.select(key);
+        }
+    }
+
+    /// Empty the selection (the hover is kept)
+    pub fn clear_selection(&mut self) {
+        if !self.selected.is_empty() {
+            self.selected.clear();
+            self.record(SelectionChange::Cleared);
+        }
+    }
+
+    /// Key under the pointer
+    pub fn hovered(&self) -> Option<&str> {
+        self.hovered.as_deref()
+    }
+
+    /// Selected keys in selection order
+    pub fn selected(&self) -> &[String] {
+        &self.selected
+    }
+
+    /// Check if a key is selected
+    pub fn is_selected(&self, key: &str) -> bool {
+        self.selected.iter().any(|k| k == key)
+    }
+
+    /// Check if a key is hovered or selected
+    pub fn is_focused(&self, key: &str) -> bool {
+        self.hovered.as_deref() == Some(key) || self.is_selected(key)
+    }
+
+    /// Check if any key is hovered or selected
+    pub fn has_focus(&self) -> bool {
+        self.hovered.is_some() || !self.selected.is_empty()
+    }
+
+    /// How a key should be drawn
+    pub fn emphasis(&self, key: &str) -> Emphasis {
+        if !self.has_focus() {
+            Emphasis::Normal
+        } else if self.is_focused(key) {
+            Emphasis::Highlighted
+        } else {
+            Emphasis::Dimmed
+        }
+    }
+
+    /// Change counter, incremented on every change
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Poll for the next change
+    pub fn poll_change(&mut self) -> Option<SelectionChange> {
+        self.changes.pop_front()
+    }
+
+    /// Get all pending changes and clear queue
+    pub fn drain_changes(&mut self) -> Vec<SelectionChange> {
+        self.changes.drain(..).collect()
+    }
+
+    fn record(&mut self, change: SelectionChange) {
+        self.changes.push_back(change);
+        self.version += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_model_changes() {
+        let mut model = SelectionModel::new();
+        assert!(model.hover(Some("a")));
+        assert!(!model.hover(Some("a")));
+        assert!(model.select("b"));
+        assert!(!model.select("b"));
+        model.toggle("b");
+        model.toggle("c");
+        model.clear_selection();
+        model.clear_selection();
+        assert!(model.hover(None));
+
+        assert_eq!(model.version(), 6);
+        assert_eq!(
+            model.drain_changes(),
+            vec![
+                SelectionChange::Hovered(Some("a".into())),
+                SelectionChange::Selected("b".into()),
+                SelectionChange::Deselected("b".into()),
+                SelectionChange::Selected("c".into()),
+                SelectionChange::Cleared,
+                SelectionChange::Hovered(None),
+            ]
+        );
+        assert!(!model.has_focus());
+        assert_eq!(model.emphasis("a"), Emphasis::Normal);
+    }
+}
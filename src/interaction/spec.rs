@@ -0,0 +1,455 @@
+//! Declarative interaction specification
+//!
+//! Describes which interactions a chart enables (zoom, brush, tooltip) and
+//! their parameters as plain data, so that a dashboard definition can be
+//! serialized and later compiled into configured behaviors.
+//!
+//! There is no `ChartSpec` in this crate yet; [`InteractionSpec`] is designed
+//! to be embedded as its `interactions` section.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::interaction::{InteractionSpec, ZoomSpec, BrushSpec, TooltipSpec, TooltipMode};
+//!
+//! let spec = InteractionSpec::new()
+//!     .with_zoom(ZoomSpec::new().with_scale_extent(1.0, 8.0).x_only())
+//!     .with_brush(BrushSpec::x())
+//!     .with_tooltip(TooltipSpec::new(TooltipMode::Axis));
+//!
+//! let compiled = spec.compile().unwrap();
+//! assert_eq!(compiled.zoom.unwrap().get_scale_extent(), (1.0, 8.0));
+//! assert!(compiled.brush.is_some());
+//! assert_eq!(compiled.tooltip_mode, Some(TooltipMode::Axis));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::brush::{BrushBehavior, BrushType};
+use super::zoom::{Extent, ZoomBehavior};
+use crate::component::{TooltipAnchor, TooltipConfig, TooltipFollowMode, TooltipWidget};
+use crate::error::{D3Error, D3Result};
+
+/// Zoom and pan parameters
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ZoomSpec {
+    /// Minimum and maximum scale factors
+    pub scale_extent: (f64, f64),
+    /// Optional bounds for translation
+    pub translate_extent: Option<Extent>,
+    /// Whether the X axis zooms
+    pub x: bool,
+    /// Whether the Y axis zooms
+    pub y: bool,
+    /// Whether panning is enabled
+    pub pan: bool,
+    /// Multiplier for wheel delta
+    pub wheel_delta: f64,
+}
+
+impl Default for ZoomSpec {
+    fn default() -> Self {
+        Self {
+            scale_extent: (0.1, 10.0),
+            translate_extent: None,
+            x: true,
+            y: true,
+            pan: true,
+            wheel_delta: 0.002,
+        }
+    }
+}
+
+impl ZoomSpec {
+    /// Create a zoom spec with default parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the minimum and maximum scale factors
+    pub fn with_scale_extent(mut self, min: f64, max: f64) -> Self {
+        self.scale_extent = (min, max);
+        self
+    }
+
+    /// Set the translation bounds
+    pub fn with_translate_extent(mut self, extent: Extent) -> Self {
+        self.translate_extent = Some(extent);
+        self
+    }
+
+    /// Zoom along the X axis only
+    pub fn x_only(mut self) -> Self {
+        self.x = true;
+        self.y = false;
+        self
+    }
+
+    /// Zoom along the Y axis only
+    pub fn y_only(mut self) -> Self {
+        self.x = false;
+        self.y = true;
+        self
+    }
+
+    /// Enable or disable panning
+    pub fn with_pan(mut self, enabled: bool) -> Self {
+        self.pan = enabled;
+        self
+    }
+
+    /// Set the wheel delta multiplier
+    pub fn with_wheel_delta(mut self, delta: f64) -> Self {
+        self.wheel_delta = delta;
+        self
+    }
+
+    /// Build a configured zoom behavior
+    pub fn compile(&self) -> D3Result<ZoomBehavior> {
+        let (min, max) = self.scale_extent;
+        if !(min.is_finite() && max.is_finite()) || min <= 0.0 || min > max {
+            return Err(D3Error::config_error(format!(
+                "zoom scale extent must satisfy 0 < min <= max, got ({}, {})",
+                min, max
+            )));
+        }
+        if !self.x && !self.y {
+            return Err(D3Error::config_error("zoom must enable at least one axis"));
+        }
+
+        let mut zoom = ZoomBehavior::new()
+            .scale_extent(min, max)
+            .wheel_delta(self.wheel_delta)
+            .zoom_x(self.x)
+            .zoom_y(self.y)
+            .pan_enabled(self.pan);
+        if let Some(extent) = self.translate_extent {
+            check_extent("zoom translate extent", &extent)?;
+            zoom = zoom.translate_extent(extent);
+        }
+        Ok(zoom)
+    }
+}
+
+/// Brush selection parameters
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BrushSpec {
+    /// Axes the brush selects along
+    pub axes: BrushType,
+    /// Optional bounds for the selection
+    pub extent: Option<Extent>,
+    /// Handle size for resize detection
+    pub handle_size: f64,
+    /// Minimum selection size
+    pub min_size: f64,
+}
+
+impl Default for BrushSpec {
+    fn default() -> Self {
+        Self {
+            axes: BrushType::XY,
+            extent: None,
+            handle_size: 8.0,
+            min_size: 1.0,
+        }
+    }
+}
+
+impl BrushSpec {
+    /// Create a brush spec for the given axes
+    pub fn new(axes: BrushType) -> Self {
+        Self {
+            axes,
+            ..Self::default()
+        }
+    }
+
+    /// X-axis brush
+    pub fn x() -> Self {
+        Self::new(BrushType::X)
+    }
+
+    /// Y-axis brush
+    pub fn y() -> Self {
+        Self::new(BrushType::Y)
+    }
+
+    /// Two-dimensional brush
+    pub fn xy() -> Self {
+        Self::new(BrushType::XY)
+    }
+
+    /// Set the selection bounds
+    pub fn with_extent(mut self, extent: Extent) -> Self {
+        self.extent = Some(extent);
+        self
+    }
+
+    /// Set the handle size
+    pub fn with_handle_size(mut self, size: f64) -> Self {
+        self.handle_size = size;
+        self
+    }
+
+    /// Set the minimum selection size
+    pub fn with_min_size(mut self, size: f64) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Build a configured brush behavior
+    pub fn compile(&self) -> D3Result<BrushBehavior> {
+        let mut brush = BrushBehavior::new(self.axes)
+            .with_handle_size(self.handle_size)
+            .with_min_size(self.min_size);
+        if let Some(e) = self.extent {
+            check_extent("brush extent", &e)?;
+            brush = brush.with_extent(e.x0, e.y0, e.x1, e.y1);
+        }
+        Ok(brush)
+    }
+}
+
+/// What a tooltip reports when hovering
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TooltipMode {
+    /// The single item under the cursor
+    #[default]
+    Item,
+    /// All series at the hovered axis position
+    Axis,
+}
+
+/// Tooltip parameters
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TooltipSpec {
+    /// What the tooltip reports
+    pub mode: TooltipMode,
+    /// How the tooltip follows the cursor
+    pub follow: TooltipFollowMode,
+    /// Anchor point of the tooltip box
+    pub anchor: TooltipAnchor,
+    /// Offset from the anchor point (x, y)
+    pub offset: (f64, f64),
+    /// Use the light theme instead of the dark one
+    pub light: bool,
+    /// Show and hide delays in milliseconds
+    pub delays: (u32, u32),
+}
+
+impl Default for TooltipSpec {
+    fn default() -> Self {
+        let config = TooltipConfig::default();
+        Self {
+            mode: TooltipMode::Item,
+            follow: config.follow_mode,
+            anchor: config.anchor,
+            offset: (config.offset_x, config.offset_y),
+            light: false,
+            delays: (config.show_delay, config.hide_delay),
+        }
+    }
+}
+
+impl TooltipSpec {
+    /// Create a tooltip spec with the given mode
+    pub fn new(mode: TooltipMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    /// Set the follow mode
+    pub fn with_follow(mut self, follow: TooltipFollowMode) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    /// Set the anchor point
+    pub fn with_anchor(mut self, anchor: TooltipAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Set the offset from the anchor point
+    pub fn with_offset(mut self, x: f64, y: f64) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    /// Use the light theme
+    pub fn light(mut self) -> Self {
+        self.light = true;
+        self
+    }
+
+    /// Set show and hide delays in milliseconds
+    pub fn with_delays(mut self, show: u32, hide: u32) -> Self {
+        self.delays = (show, hide);
+        self
+    }
+
+    /// Build a configured tooltip widget
+    pub fn compile(&self) -> TooltipWidget {
+        let base = if self.light {
+            TooltipConfig::light()
+        } else {
+            TooltipConfig::dark()
+        };
+        let config = base
+            .follow_mode(self.follow)
+            .anchor(self.anchor)
+            .offset(self.offset.0, self.offset.1)
+            .delays(self.delays.0, self.delays.1);
+        TooltipWidget::new(config)
+    }
+}
+
+/// Interactions enabled on a chart
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InteractionSpec {
+    /// Zoom and pan, if enabled
+    pub zoom: Option<ZoomSpec>,
+    /// Brush selection, if enabled
+    pub brush: Option<BrushSpec>,
+    /// Tooltip, if enabled
+    pub tooltip: Option<TooltipSpec>,
+}
+
+impl InteractionSpec {
+    /// Create a spec with no interactions enabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable zoom and pan
+    pub fn with_zoom(mut self, zoom: ZoomSpec) -> Self {
+        self.zoom = Some(zoom);
+        self
+    }
+
+    /// Enable brush selection
+    pub fn with_brush(mut self, brush: BrushSpec) -> Self {
+        self.brush = Some(brush);
+        self
+    }
+
+    /// Enable tooltips
+    pub fn with_tooltip(mut self, tooltip: TooltipSpec) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// Check whether any interaction is enabled
+    pub fn is_empty(&self) -> bool {
+        self.zoom.is_none() && self.brush.is_none() && self.tooltip.is_none()
+    }
+
+    /// Compile into configured behaviors
+    ///
+    /// Fails with a configuration error if any section has invalid parameters.
+    pub fn compile(&self) -> D3Result<CompiledInteractions> {
+        Ok(CompiledInteractions {
+            zoom: self.zoom.as_ref().map(ZoomSpec::compile).transpose()?,
+            brush: self.brush.as_ref().map(BrushSpec::compile).transpose()?,
+            tooltip: self.tooltip.as_ref().map(TooltipSpec::compile),
+            tooltip_mode: self.tooltip.as_ref().map(|t| t.mode),
+        })
+    }
+}
+
+/// Behaviors built from an [`InteractionSpec`]
+#[derive(Clone, Debug)]
+pub struct CompiledInteractions {
+    /// Configured zoom behavior
+    pub zoom: Option<ZoomBehavior>,
+    /// Configured brush behavior
+    pub brush: Option<BrushBehavior>,
+    /// Configured tooltip widget
+    pub tooltip: Option<TooltipWidget>,
+    /// What the tooltip reports
+    pub tooltip_mode: Option<TooltipMode>,
+}
+
+fn check_extent(what: &str, e: &Extent) -> D3Result<()> {
+    let finite = [e.x0, e.y0, e.x1, e.y1].iter().all(|v| v.is_finite());
+    if !finite || e.x0 > e.x1 || e.y0 > e.y1 {
+        return Err(D3Error::config_error(format!(
+            "{} must be finite with x0 <= x1 and y0 <= y1",
+            what
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_spec() {
+        let spec = InteractionSpec::new();
+        assert!(spec.is_empty());
+        let compiled = spec.compile().unwrap();
+        assert!(compiled.zoom.is_none());
+        assert!(compiled.brush.is_none());
+        assert!(compiled.tooltip.is_none());
+    }
+
+    #[test]
+    fn test_zoom_compile() {
+        let extent = Extent::new(0.0, 0.0, 800.0, 600.0);
+        let zoom = ZoomSpec::new()
+            .with_scale_extent(0.5, 4.0)
+            .with_translate_extent(extent)
+            .compile()
+            .unwrap();
+        assert_eq!(zoom.get_scale_extent(), (0.5, 4.0));
+        assert_eq!(zoom.get_translate_extent(), Some(&extent));
+    }
+
+    #[test]
+    fn test_zoom_invalid() {
+        assert!(ZoomSpec::new().with_scale_extent(4.0, 1.0).compile().is_err());
+        assert!(ZoomSpec::new().with_scale_extent(0.0, 1.0).compile().is_err());
+        let mut spec = ZoomSpec::new();
+        spec.x = false;
+        spec.y = false;
+        assert!(spec.compile().is_err());
+        let bad = ZoomSpec::new().with_translate_extent(Extent::new(10.0, 0.0, 0.0, 10.0));
+        assert!(bad.compile().is_err());
+    }
+
+    #[test]
+    fn test_brush_compile() {
+        let brush = BrushSpec::y()
+            .with_extent(Extent::new(0.0, 0.0, 100.0, 100.0))
+            .compile()
+            .unwrap();
+        assert_eq!(brush.brush_type(), BrushType::Y);
+
+        let bad = BrushSpec::x().with_extent(Extent::new(0.0, f64::NAN, 1.0, 1.0));
+        assert!(bad.compile().is_err());
+    }
+
+    #[test]
+    fn test_tooltip_compile() {
+        let widget = TooltipSpec::new(TooltipMode::Axis)
+            .with_follow(TooltipFollowMode::DataPoint)
+            .with_offset(4.0, 6.0)
+            .light()
+            .compile();
+        assert_eq!(widget.config.follow_mode, TooltipFollowMode::DataPoint);
+        assert_eq!((widget.config.offset_x, widget.config.offset_y), (4.0, 6.0));
+        assert_eq!(widget.config.background, TooltipConfig::light().background);
+    }
+
+    #[test]
+    fn test_invalid_section_fails_whole_spec() {
+        let spec = InteractionSpec::new()
+            .with_brush(BrushSpec::xy())
+            .with_zoom(ZoomSpec::new().with_scale_extent(2.0, 1.0));
+        assert!(matches!(spec.compile(), Err(D3Error::ConfigError { .. })));
+    }
+}
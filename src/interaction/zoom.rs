@@ -4,6 +4,10 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::animation::Easing;
+use crate::error::{D3Error, D3Result};
+use crate::scale::Scale;
+
 /// A 2D point for interaction coordinates
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Point2D {
@@ -147,6 +151,17 @@ impl ZoomTransform {
         ((x - self.x) / self.k, (y - self.y) / self.k)
     }
 
+    /// Invert the transform, failing if the scale factor is zero or non-finite
+    pub fn try_invert(&self, x: f64, y: f64) -> D3Result<(f64, f64)> {
+        if !self.k.is_finite() || self.k == 0.0 {
+            return Err(D3Error::singular_matrix(format!(
+                "zoom transform with scale factor {} cannot be inverted",
+                self.k
+            )));
+        }
+        Ok(self.invert(x, y))
+    }
+
     /// Invert only the X coordinate
     pub fn invert_x(&self, x: f64) -> f64 {
         (x - self.x) / self.k
@@ -181,6 +196,36 @@ impl ZoomTransform {
         )
     }
 
+    /// Copy of an x scale whose domain shows the zoomed view (`transform.rescaleX`)
+    ///
+    /// The new domain is the old scale's inverse of the range endpoints
+    /// after undoing the transform, so log, pow and time scales zoom in
+    /// their own space rather than linearly.
+    ///
+    /// ```
+    /// use makepad_d3::interaction::ZoomTransform;
+    /// use makepad_d3::scale::{LinearScale, Scale, ScaleExt};
+    ///
+    /// let x = LinearScale::new().with_domain(0.0, 100.0).with_range(0.0, 500.0);
+    /// let zoomed = ZoomTransform::new(2.0, -250.0, 0.0).rescale_x_scale(&x);
+    /// assert_eq!(zoomed.domain(), (25.0, 75.0));
+    /// assert_eq!(zoomed.range(), (0.0, 500.0));
+    /// ```
+    pub fn rescale_x_scale<S: Scale + Clone>(&self, scale: &S) -> S {
+        let (r0, r1) = scale.range();
+        let mut rescaled = scale.clone();
+        rescaled.set_domain(scale.invert(self.invert_x(r0)), scale.invert(self.invert_x(r1)));
+        rescaled
+    }
+
+    /// Copy of a y scale whose domain shows the zoomed view (`transform.rescaleY`)
+    pub fn rescale_y_scale<S: Scale + Clone>(&self, scale: &S) -> S {
+        let (r0, r1) = scale.range();
+        let mut rescaled = scale.clone();
+        rescaled.set_domain(scale.invert(self.invert_y(r0)), scale.invert(self.invert_y(r1)));
+        rescaled
+    }
+
     /// Compose this transform with another (this * other)
     pub fn compose(&self, other: &ZoomTransform) -> ZoomTransform {
         ZoomTransform {
@@ -216,8 +261,10 @@ impl ZoomTransform {
 pub struct ZoomBehavior {
     /// Minimum and maximum scale factors
     scale_extent: (f64, f64),
-    /// Optional bounds for translation
+    /// Optional bounds for translation, in untransformed content coordinates
     translate_extent: Option<Extent>,
+    /// Viewport extent; defaults to the translate extent
+    extent: Option<Extent>,
     /// Multiplier for wheel delta
     wheel_delta: f64,
     /// Whether X-axis zooming is enabled
@@ -242,6 +289,7 @@ impl ZoomBehavior {
         Self {
             scale_extent: (0.1, 10.0),
             translate_extent: None,
+            extent: None,
             wheel_delta: 0.002,
             zoom_x: true,
             zoom_y: true,
@@ -263,6 +311,15 @@ impl ZoomBehavior {
         self
     }
 
+    /// Set the viewport extent used for constraining and transitions
+    ///
+    /// Defaults to the translate extent, so content fills the viewport
+    /// exactly at scale 1.
+    pub fn extent(mut self, extent: Extent) -> Self {
+        self.extent = Some(extent);
+        self
+    }
+
     /// Set the wheel delta multiplier
     pub fn wheel_delta(mut self, delta: f64) -> Self {
         self.wheel_delta = delta;
@@ -297,6 +354,11 @@ impl ZoomBehavior {
         self.translate_extent.as_ref()
     }
 
+    /// Get the viewport extent, falling back to the translate extent
+    pub fn get_extent(&self) -> Option<&Extent> {
+        self.extent.as_ref().or(self.translate_extent.as_ref())
+    }
+
     /// Handle mouse wheel event
     ///
     /// # Arguments
@@ -405,20 +467,40 @@ impl ZoomBehavior {
 
     /// Constrain transform to extent bounds
     fn constrain(&self, transform: &mut ZoomTransform) {
+        *transform = self.constrained(*transform);
+    }
+
+    /// Transform adjusted so the viewport stays inside the translate extent
+    ///
+    /// Follows d3's default constrain: when the visible content is smaller
+    /// than the viewport along an axis it is centered, otherwise it is
+    /// shifted until no gap shows at the edges.
+    pub fn constrained(&self, transform: ZoomTransform) -> ZoomTransform {
+        let (Some(bounds), Some(view)) = (self.translate_extent.as_ref(), self.get_extent()) else {
+            return transform;
+        };
         if !self.constrain_to_extent {
-            return;
+            return transform;
         }
 
-        if let Some(extent) = &self.translate_extent {
-            // Ensure content stays within bounds
-            let max_x = 0.0;
-            let min_x = extent.width() * (1.0 - transform.k);
-            let max_y = 0.0;
-            let min_y = extent.height() * (1.0 - transform.k);
-
-            transform.x = transform.x.clamp(min_x, max_x);
-            transform.y = transform.y.clamp(min_y, max_y);
-        }
+        let shift = |d0: f64, d1: f64| {
+            if d1 > d0 {
+                (d0 + d1) / 2.0
+            } else if d0 < 0.0 {
+                d0
+            } else {
+                d1.max(0.0)
+            }
+        };
+        let dx = shift(
+            transform.invert_x(view.x0) - bounds.x0,
+            transform.invert_x(view.x1) - bounds.x1,
+        );
+        let dy = shift(
+            transform.invert_y(view.y0) - bounds.y0,
+            transform.invert_y(view.y1) - bounds.y1,
+        );
+        ZoomTransform::new(transform.k, transform.x + transform.k * dx, transform.y + transform.k * dy)
     }
 
     /// Programmatically zoom to a specific scale
@@ -449,12 +531,220 @@ impl ZoomBehavior {
         transform.y = y;
         self.constrain(transform);
     }
+
+    /// Zoom to scale `k` around the viewport center (`zoom.scaleTo`)
+    pub fn scale_to(&self, transform: &mut ZoomTransform, k: f64) {
+        let (cx, cy) = self.viewport_center();
+        self.zoom_to(transform, k, cx, cy);
+    }
+
+    /// Multiply the scale by `factor` around the viewport center (`zoom.scaleBy`)
+    pub fn scale_by(&self, transform: &mut ZoomTransform, factor: f64) {
+        let k = transform.k * factor;
+        self.scale_to(transform, k);
+    }
+
+    /// Jump to `target`, clamped to the scale extent and constrained (`zoom.transform`)
+    pub fn set_transform(&self, transform: &mut ZoomTransform, target: ZoomTransform) {
+        let k = target.k.clamp(self.scale_extent.0, self.scale_extent.1);
+        *transform = self.constrained(ZoomTransform::new(k, target.x, target.y));
+    }
+
+    /// Animated transition from `from` to `target` over `duration_ms`
+    ///
+    /// The target is clamped and constrained like [`set_transform`](Self::set_transform).
+    /// The path is d3's smooth zoom (van Wijk and Nuij), which zooms out while
+    /// travelling long distances; a negative duration selects the path's
+    /// recommended duration.
+    pub fn zoom_transition(&self, from: &ZoomTransform, target: ZoomTransform, duration_ms: f64) -> ZoomTransition {
+        let mut to = *from;
+        self.set_transform(&mut to, target);
+        let view = self.get_extent().copied().unwrap_or(Extent::new(0.0, 0.0, 1.0, 1.0));
+        ZoomTransition::new(*from, to, view, duration_ms)
+    }
+
+    fn viewport_center(&self) -> (f64, f64) {
+        self.get_extent()
+            .map(|e| ((e.x0 + e.x1) / 2.0, (e.y0 + e.y1) / 2.0))
+            .unwrap_or((0.0, 0.0))
+    }
+}
+
+/// Smooth zoom path between two transforms
+///
+/// Sample it from an animation loop with [`sample`](Self::sample) or
+/// [`at`](Self::at); the final sample equals the target exactly.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::interaction::{Extent, ZoomBehavior, ZoomTransform};
+///
+/// let zoom = ZoomBehavior::new().extent(Extent::new(0.0, 0.0, 800.0, 600.0));
+/// let start = ZoomTransform::identity();
+/// let transition = zoom.zoom_transition(&start, ZoomTransform::new(4.0, -1200.0, -900.0), 500.0);
+///
+/// assert_eq!(transition.sample(0.0), start);
+/// assert!(transition.sample(250.0).k > 1.0);
+/// assert!(transition.is_finished(500.0));
+/// assert_eq!(transition.sample(600.0), transition.target());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoomTransition {
+    from: ZoomTransform,
+    to: ZoomTransform,
+    /// Viewport center
+    center: (f64, f64),
+    /// Viewport size used as the view width
+    width: f64,
+    /// Start view (center x, center y, visible width)
+    view0: [f64; 3],
+    /// End view
+    view1: [f64; 3],
+    /// Path length in van Wijk units
+    path_length: f64,
+    duration_ms: f64,
+    easing: Easing,
+}
+
+const ZOOM_RHO: f64 = std::f64::consts::SQRT_2;
+
+impl ZoomTransition {
+    /// Transition between two transforms within a viewport
+    pub fn new(from: ZoomTransform, to: ZoomTransform, viewport: Extent, duration_ms: f64) -> Self {
+        let center = ((viewport.x0 + viewport.x1) / 2.0, (viewport.y0 + viewport.y1) / 2.0);
+        let width = viewport.width().abs().max(viewport.height().abs()).max(1e-9);
+        let view = |t: &ZoomTransform| {
+            let (x, y) = t.invert(center.0, center.1);
+            [x, y, width / t.k]
+        };
+        let (view0, view1) = (view(&from), view(&to));
+        let mut transition = Self {
+            from,
+            to,
+            center,
+            width,
+            view0,
+            view1,
+            path_length: 0.0,
+            duration_ms: 0.0,
+            easing: Easing::default(),
+        };
+        transition.path_length = transition.path().0;
+        transition.duration_ms = if duration_ms < 0.0 {
+            transition.recommended_duration()
+        } else {
+            duration_ms
+        };
+        transition
+    }
+
+    /// Set the easing curve (default cubic in-out, as d3 transitions)
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Starting transform
+    pub fn source(&self) -> ZoomTransform {
+        self.from
+    }
+
+    /// Final transform
+    pub fn target(&self) -> ZoomTransform {
+        self.to
+    }
+
+    /// Duration in milliseconds
+    pub fn duration(&self) -> f64 {
+        self.duration_ms
+    }
+
+    /// Duration d3 would pick for this path, in milliseconds
+    pub fn recommended_duration(&self) -> f64 {
+        self.path_length * 1000.0 * ZOOM_RHO / std::f64::consts::SQRT_2
+    }
+
+    /// Whether the transition has ended after `elapsed_ms`
+    pub fn is_finished(&self, elapsed_ms: f64) -> bool {
+        elapsed_ms >= self.duration_ms
+    }
+
+    /// Transform after `elapsed_ms`, eased
+    pub fn sample(&self, elapsed_ms: f64) -> ZoomTransform {
+        if self.duration_ms <= 0.0 {
+            return self.to;
+        }
+        self.at(self.easing.apply((elapsed_ms / self.duration_ms).clamp(0.0, 1.0)))
+    }
+
+    /// Transform at path progress `t` in `[0, 1]` (no easing)
+    pub fn at(&self, t: f64) -> ZoomTransform {
+        if t <= 0.0 {
+            return self.from;
+        }
+        if t >= 1.0 {
+            return self.to;
+        }
+        let [ux, uy, w] = self.path().1(t);
+        let k = self.width / w;
+        ZoomTransform::new(k, self.center.0 - ux * k, self.center.1 - uy * k)
+    }
+
+    /// Transforms sampled every `frame_ms` up to and including the target
+    pub fn frames(&self, frame_ms: f64) -> Vec<ZoomTransform> {
+        if frame_ms.is_nan() || frame_ms <= 0.0 || self.duration_ms <= 0.0 {
+            return vec![self.to];
+        }
+        let steps = (self.duration_ms / frame_ms).ceil() as usize;
+        (1..=steps).map(|i| self.sample(i as f64 * frame_ms)).collect()
+    }
+
+    /// Path length and view interpolator (`d3.interpolateZoom`)
+    fn path(&self) -> (f64, Box<dyn Fn(f64) -> [f64; 3]>) {
+        let [ux0, uy0, w0] = self.view0;
+        let [ux1, uy1, w1] = self.view1;
+        let (dx, dy) = (ux1 - ux0, uy1 - uy0);
+        let d2 = dx * dx + dy * dy;
+        let rho = ZOOM_RHO;
+        let (rho2, rho4) = (rho * rho, rho.powi(4));
+
+        if d2 < 1e-12 {
+            // Pure zoom: interpolate the width exponentially
+            let s = (w1 / w0).ln() / rho;
+            let path = move |t: f64| [ux0 + t * dx, uy0 + t * dy, w0 * (rho * t * s).exp()];
+            return (s.abs(), Box::new(path));
+        }
+
+        let d1 = d2.sqrt();
+        let b0 = (w1 * w1 - w0 * w0 + rho4 * d2) / (2.0 * w0 * rho2 * d1);
+        let b1 = (w1 * w1 - w0 * w0 - rho4 * d2) / (2.0 * w1 * rho2 * d1);
+        let r0 = ((b0 * b0 + 1.0).sqrt() - b0).ln();
+        let r1 = ((b1 * b1 + 1.0).sqrt() - b1).ln();
+        let s = (r1 - r0) / rho;
+        let path = move |t: f64| {
+            let si = t * s;
+            let u = w0 / (rho2 * d1) * (r0.cosh() * (rho * si + r0).tanh() - r0.sinh());
+            [ux0 + u * dx, uy0 + u * dy, w0 * r0.cosh() / (rho * si + r0).cosh()]
+        };
+        (s, Box::new(path))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_zoom_transform_try_invert() {
+        let t = ZoomTransform::new(2.0, 10.0, 0.0);
+        assert_eq!(t.try_invert(30.0, 4.0).unwrap(), (10.0, 2.0));
+        assert!(matches!(
+            ZoomTransform::scale(0.0).try_invert(1.0, 1.0),
+            Err(D3Error::SingularMatrix { .. })
+        ));
+    }
+
     #[test]
     fn test_zoom_transform_identity() {
         let t = ZoomTransform::identity();
@@ -583,6 +873,31 @@ mod tests {
         assert!(new_domain.0 < domain.0 || new_domain.1 > domain.1);
     }
 
+    #[test]
+    fn test_rescale_scales() {
+        use crate::scale::{LinearScale, LogScale, ScaleExt, TimeScale};
+
+        let t = ZoomTransform::new(2.0, -100.0, -50.0);
+        let y = LinearScale::new().with_domain(0.0, 10.0).with_range(200.0, 0.0);
+        let zoomed = t.rescale_y_scale(&y);
+        assert_eq!(zoomed.domain(), (3.75, 8.75));
+        assert_eq!(zoomed.range(), (200.0, 0.0));
+        // Rescaled domain matches the tuple form for linear scales
+        let (d0, d1) = t.rescale_y((0.0, 10.0), (200.0, 0.0));
+        assert!((zoomed.domain().0 - d0).abs() < 1e-12 && (zoomed.domain().1 - d1).abs() < 1e-12);
+
+        // Log scales zoom in log space: the view spans one decade of two
+        let log = LogScale::new().with_domain(1.0, 100.0).with_range(0.0, 100.0);
+        let zoomed = ZoomTransform::new(2.0, -50.0, 0.0).rescale_x_scale(&log);
+        let (a, b) = zoomed.domain();
+        assert!((a - 10f64.sqrt()).abs() < 1e-9 && (b - 1000f64.sqrt()).abs() < 1e-9);
+
+        let time = TimeScale::new().with_domain_ms(0, 1_000_000).with_range(0.0, 1000.0);
+        let zoomed = ZoomTransform::new(4.0, -2000.0, 0.0).rescale_x_scale(&time);
+        assert_eq!(zoomed.domain(), (500_000.0, 750_000.0));
+        assert_eq!(ZoomTransform::identity().rescale_x_scale(&time).domain(), time.domain());
+    }
+
     #[test]
     fn test_extent() {
         let e = Extent::new(0.0, 0.0, 100.0, 50.0);
@@ -592,6 +907,71 @@ mod tests {
         assert!(!e.contains(150.0, 25.0));
     }
 
+    #[test]
+    fn test_translate_extent_constrains_pan() {
+        let zoom = ZoomBehavior::new().translate_extent(Extent::new(0.0, 0.0, 400.0, 300.0));
+        let mut transform = ZoomTransform::new(2.0, 0.0, 0.0);
+
+        // Dragging right would reveal empty space at the left edge
+        assert!(!zoom.handle_pan(&mut transform, 50.0, 0.0));
+        assert!(zoom.handle_pan(&mut transform, -1000.0, -1000.0));
+        assert_eq!((transform.x, transform.y), (-400.0, -300.0));
+
+        // Content smaller than the viewport is centered instead
+        let mut small = ZoomTransform::identity();
+        zoom.set_transform(&mut small, ZoomTransform::new(0.5, 0.0, 0.0));
+        assert_eq!((small.k, small.x, small.y), (0.5, 100.0, 75.0));
+
+        // A larger viewport over the same content
+        let wide = ZoomBehavior::new()
+            .translate_extent(Extent::new(0.0, 0.0, 400.0, 300.0))
+            .extent(Extent::new(0.0, 0.0, 800.0, 300.0));
+        let mut t = ZoomTransform::identity();
+        wide.translate_to(&mut t, -30.0, 20.0);
+        assert_eq!((t.x, t.y), (200.0, 0.0));
+    }
+
+    #[test]
+    fn test_scale_to_viewport_center() {
+        let zoom = ZoomBehavior::new().scale_extent(0.5, 8.0).extent(Extent::new(0.0, 0.0, 200.0, 100.0));
+        let mut transform = ZoomTransform::identity();
+        zoom.scale_to(&mut transform, 4.0);
+        assert_eq!(transform, ZoomTransform::new(4.0, -300.0, -150.0));
+        zoom.scale_by(&mut transform, 4.0);
+        assert_eq!(transform.k, 8.0);
+        // The viewport center stays fixed
+        assert_eq!(transform.invert(100.0, 50.0), (100.0, 50.0));
+    }
+
+    #[test]
+    fn test_zoom_transition() {
+        let zoom = ZoomBehavior::new().scale_extent(0.5, 4.0).extent(Extent::new(0.0, 0.0, 100.0, 100.0));
+        let start = ZoomTransform::identity();
+        let transition = zoom.zoom_transition(&start, ZoomTransform::new(10.0, -900.0, -900.0), 300.0);
+        // Target is clamped to the scale extent
+        assert_eq!(transition.target().k, 4.0);
+        assert_eq!(transition.at(0.0), start);
+        assert_eq!(transition.at(1.0), transition.target());
+
+        let frames = transition.frames(16.0);
+        assert_eq!(frames.len(), 19);
+        assert_eq!(*frames.last().unwrap(), transition.target());
+        // Travelling far, the path zooms out before zooming in
+        assert!(frames.iter().any(|t| t.k < 1.0));
+
+        // Zooming about the viewport center keeps that point fixed and
+        // interpolates the scale geometrically
+        let centered = zoom.zoom_transition(&start, ZoomTransform::new(4.0, -150.0, -150.0), 300.0);
+        let mid = centered.at(0.5);
+        assert!((mid.k - 2.0).abs() < 1e-9);
+        let (x, y) = mid.invert(50.0, 50.0);
+        assert!((x - 50.0).abs() < 1e-9 && (y - 50.0).abs() < 1e-9);
+
+        let auto = zoom.zoom_transition(&start, ZoomTransform::new(2.0, -50.0, -50.0), -1.0);
+        assert!(auto.duration() > 0.0);
+        assert_eq!(auto.duration(), auto.recommended_duration());
+    }
+
     #[test]
     fn test_point2d() {
         let p = Point2D::new(10.0, 20.0);
@@ -0,0 +1,568 @@
+//! Chord diagram layout
+//!
+//! Port of [d3-chord](https://github.com/d3/d3-chord). [`ChordLayout`]
+//! turns a square flow matrix into group arcs around a circle and chords
+//! connecting them; [`RibbonGenerator`] draws each chord as a ribbon.
+//!
+//! Angles follow [`ArcGenerator`](crate::shape::ArcGenerator): radians,
+//! 0 at 12 o'clock, increasing clockwise, so groups can be drawn directly as
+//! donut arcs.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::layout::chord::{ChordLayout, ChordSort, RibbonGenerator};
+//! use makepad_d3::shape::ArcGenerator;
+//!
+//! let matrix = vec![
+//!     vec![11975.0, 5871.0, 8916.0, 2868.0],
+//!     vec![1951.0, 10048.0, 2060.0, 6171.0],
+//!     vec![8010.0, 16145.0, 8090.0, 8045.0],
+//!     vec![1013.0, 990.0, 940.0, 6907.0],
+//! ];
+//!
+//! let chords = ChordLayout::new()
+//!     .pad_angle(0.05)
+//!     .sort_subgroups(ChordSort::Descending)
+//!     .compute(&matrix)
+//!     .unwrap();
+//!
+//! for group in &chords.groups {
+//!     let arc = ArcGenerator::new()
+//!         .inner_radius(180.0)
+//!         .outer_radius(200.0)
+//!         .start_angle(group.start_angle)
+//!         .end_angle(group.end_angle)
+//!         .generate();
+//!     assert!(!arc.is_empty());
+//! }
+//!
+//! let ribbon = RibbonGenerator::new().radius(180.0);
+//! for chord in &chords.chords {
+//!     let path = ribbon.generate(chord);
+//!     assert!(!path.is_empty());
+//! }
+//! ```
+
+use std::cmp::Ordering;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use crate::error::{D3Error, D3Result};
+use crate::shape::{PathSegment, Point};
+
+/// Sort order for groups, subgroups or chords
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChordSort {
+    /// Keep matrix order
+    #[default]
+    None,
+    /// Smallest value first
+    Ascending,
+    /// Largest value first
+    Descending,
+}
+
+impl ChordSort {
+    fn compare(self, a: f64, b: f64) -> Ordering {
+        match self {
+            ChordSort::None => Ordering::Equal,
+            ChordSort::Ascending => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            ChordSort::Descending => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+/// Arc of the circle covering one matrix row
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChordGroup {
+    /// Row index in the matrix
+    pub index: usize,
+    /// Start angle in radians
+    pub start_angle: f64,
+    /// End angle in radians
+    pub end_angle: f64,
+    /// Total flow of the group
+    pub value: f64,
+}
+
+impl ChordGroup {
+    /// Angular span of the group
+    pub fn angle(&self) -> f64 {
+        self.end_angle - self.start_angle
+    }
+
+    /// Middle angle, e.g. for labels
+    pub fn mid_angle(&self) -> f64 {
+        (self.start_angle + self.end_angle) / 2.0
+    }
+}
+
+/// One end of a chord: a slice of a group's arc
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChordSubgroup {
+    /// Group index
+    pub index: usize,
+    /// Start angle in radians
+    pub start_angle: f64,
+    /// End angle in radians
+    pub end_angle: f64,
+    /// Matrix value for this end
+    pub value: f64,
+}
+
+/// Flow between two groups
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Chord {
+    /// Source end
+    pub source: ChordSubgroup,
+    /// Target end
+    pub target: ChordSubgroup,
+}
+
+/// Groups and chords computed from a matrix
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChordSet {
+    /// One group per matrix row, in matrix order
+    pub groups: Vec<ChordGroup>,
+    /// Non-empty chords
+    pub chords: Vec<Chord>,
+}
+
+/// Chord layout
+///
+/// [`new`](ChordLayout::new) matches `d3.chord()`: `matrix[i][j]` and
+/// `matrix[j][i]` are merged into one chord whose source is the larger
+/// side. [`directed`](ChordLayout::directed) matches `d3.chordDirected()`:
+/// each nonzero entry becomes its own chord, and every group holds its
+/// incoming subgroups followed by its outgoing ones.
+#[derive(Clone, Debug)]
+pub struct ChordLayout {
+    /// Directed mode
+    directed: bool,
+    /// Read `matrix[j][i]` instead of `matrix[i][j]`
+    transpose: bool,
+    /// Gap between adjacent groups
+    pad_angle: f64,
+    /// Group order around the circle
+    sort_groups: ChordSort,
+    /// Subgroup order within a group
+    sort_subgroups: ChordSort,
+    /// Order of the returned chords
+    sort_chords: ChordSort,
+}
+
+impl Default for ChordLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChordLayout {
+    /// Create an undirected chord layout
+    pub fn new() -> Self {
+        Self {
+            directed: false,
+            transpose: false,
+            pad_angle: 0.0,
+            sort_groups: ChordSort::None,
+            sort_subgroups: ChordSort::None,
+            sort_chords: ChordSort::None,
+        }
+    }
+
+    /// Create a directed chord layout
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create a directed layout over the transposed matrix (`d3.chordTranspose`)
+    pub fn transposed() -> Self {
+        Self {
+            transpose: true,
+            ..Self::directed()
+        }
+    }
+
+    /// Set the gap between adjacent groups
+    pub fn pad_angle(mut self, angle: f64) -> Self {
+        self.pad_angle = angle.max(0.0);
+        self
+    }
+
+    /// Set the group order around the circle, by total value
+    pub fn sort_groups(mut self, order: ChordSort) -> Self {
+        self.sort_groups = order;
+        self
+    }
+
+    /// Set the subgroup order within each group, by value
+    pub fn sort_subgroups(mut self, order: ChordSort) -> Self {
+        self.sort_subgroups = order;
+        self
+    }
+
+    /// Set the order of the returned chords, by combined value
+    ///
+    /// Affects drawing order only, not angles.
+    pub fn sort_chords(mut self, order: ChordSort) -> Self {
+        self.sort_chords = order;
+        self
+    }
+
+    /// Compute groups and chords from a square matrix
+    ///
+    /// Returns an error if the matrix is not square or holds negative or
+    /// non-finite values.
+    pub fn compute(&self, matrix: &[Vec<f64>]) -> D3Result<ChordSet> {
+        let n = matrix.len();
+        for (i, row) in matrix.iter().enumerate() {
+            if row.len() != n {
+                return Err(D3Error::invalid_data(format!(
+                    "chord matrix row {} has {} columns, expected {}",
+                    i,
+                    row.len(),
+                    n
+                )));
+            }
+            for &v in row {
+                if !v.is_finite() {
+                    return Err(D3Error::non_finite("chord matrix value", v));
+                }
+                if v < 0.0 {
+                    return Err(D3Error::invalid_data(format!("chord matrix has negative value {}", v)));
+                }
+            }
+        }
+        if n == 0 {
+            return Ok(ChordSet::default());
+        }
+
+        let m = |i: usize, j: usize| if self.transpose { matrix[j][i] } else { matrix[i][j] };
+
+        let sums: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| m(i, j) + if self.directed { m(j, i) } else { 0.0 }).sum())
+            .collect();
+        let total: f64 = sums.iter().sum();
+        let k = if total > 0.0 {
+            (TAU - self.pad_angle * n as f64).max(0.0) / total
+        } else {
+            0.0
+        };
+        let dx = if k > 0.0 { self.pad_angle } else { TAU / n as f64 };
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| self.sort_groups.compare(sums[a], sums[b]));
+
+        // Chord ends, indexed by source * n + target
+        let mut slots: Vec<(Option<ChordSubgroup>, Option<ChordSubgroup>)> = vec![(None, None); n * n];
+        let mut groups = vec![ChordGroup::default(); n];
+        let mut x = 0.0;
+        let subgroup = |index: usize, value: f64, x: &mut f64| {
+            let start_angle = *x;
+            *x += value * k;
+            ChordSubgroup {
+                index,
+                start_angle,
+                end_angle: *x,
+                value,
+            }
+        };
+
+        for &i in &order {
+            let x0 = x;
+            if self.directed {
+                // Incoming ends as (source, None), outgoing as (None, target)
+                let mut ends: Vec<(Option<usize>, Option<usize>, f64)> = (0..n)
+                    .rev()
+                    .filter(|&s| m(s, i) != 0.0)
+                    .map(|s| (Some(s), None, -m(s, i)))
+                    .chain((0..n).filter(|&t| m(i, t) != 0.0).map(|t| (None, Some(t), m(i, t))))
+                    .collect();
+                ends.sort_by(|a, b| self.sort_subgroups.compare(a.2, b.2));
+                for end in ends {
+                    match end {
+                        (Some(s), _, _) => slots[s * n + i].1 = Some(subgroup(i, m(s, i), &mut x)),
+                        (_, Some(t), _) => slots[i * n + t].0 = Some(subgroup(i, m(i, t), &mut x)),
+                        _ => {}
+                    }
+                }
+            } else {
+                let mut ends: Vec<usize> = (0..n).filter(|&j| m(i, j) != 0.0 || m(j, i) != 0.0).collect();
+                ends.sort_by(|&a, &b| self.sort_subgroups.compare(m(i, a), m(i, b)));
+                for j in ends {
+                    let end = subgroup(i, m(i, j), &mut x);
+                    let slot = if i < j {
+                        let slot = &mut slots[i * n + j];
+                        slot.0 = Some(end);
+                        slot
+                    } else {
+                        let slot = &mut slots[j * n + i];
+                        slot.1 = Some(end);
+                        if i == j {
+                            slot.0 = Some(end);
+                        }
+                        slot
+                    };
+                    if let (Some(source), Some(target)) = *slot {
+                        if source.value < target.value {
+                            *slot = (Some(target), Some(source));
+                        }
+                    }
+                }
+            }
+            groups[i] = ChordGroup {
+                index: i,
+                start_angle: x0,
+                end_angle: x,
+                value: sums[i],
+            };
+            x += dx;
+        }
+
+        let mut chords: Vec<Chord> = slots
+            .into_iter()
+            .filter_map(|slot| match slot {
+                (Some(source), Some(target)) => Some(Chord { source, target }),
+                _ => None,
+            })
+            .collect();
+        chords.sort_by(|a, b| {
+            self.sort_chords
+                .compare(a.source.value + a.target.value, b.source.value + b.target.value)
+        });
+
+        Ok(ChordSet { groups, chords })
+    }
+}
+
+/// Ribbon generator for chords
+///
+/// Matches `d3.ribbon()`, or `d3.ribbonArrow()` when a head radius is set.
+/// The ribbon is centered at the origin.
+#[derive(Clone, Debug)]
+pub struct RibbonGenerator {
+    /// Radius at the source end
+    source_radius: f64,
+    /// Radius at the target end
+    target_radius: f64,
+    /// Angle trimmed from each end
+    pad_angle: f64,
+    /// Arrowhead length for directed ribbons
+    head_radius: Option<f64>,
+}
+
+impl Default for RibbonGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RibbonGenerator {
+    /// Create a new ribbon generator
+    pub fn new() -> Self {
+        Self {
+            source_radius: 100.0,
+            target_radius: 100.0,
+            pad_angle: 0.0,
+            head_radius: None,
+        }
+    }
+
+    /// Set both the source and target radius
+    pub fn radius(mut self, radius: f64) -> Self {
+        self.source_radius = radius;
+        self.target_radius = radius;
+        self
+    }
+
+    /// Set the source radius
+    pub fn source_radius(mut self, radius: f64) -> Self {
+        self.source_radius = radius;
+        self
+    }
+
+    /// Set the target radius
+    pub fn target_radius(mut self, radius: f64) -> Self {
+        self.target_radius = radius;
+        self
+    }
+
+    /// Set the pad angle trimmed from both sides of each end
+    pub fn pad_angle(mut self, angle: f64) -> Self {
+        self.pad_angle = angle.max(0.0);
+        self
+    }
+
+    /// Draw an arrowhead of this length at the target end (`d3.ribbonArrow`)
+    pub fn head_radius(mut self, radius: f64) -> Self {
+        self.head_radius = Some(radius);
+        self
+    }
+
+    /// Generate path segments for a chord
+    pub fn generate(&self, chord: &Chord) -> Vec<PathSegment> {
+        let (sr, tr) = (self.source_radius, self.target_radius);
+        let (sa0, sa1) = self.padded(&chord.source);
+        let (ta0, ta1) = self.padded(&chord.target);
+        let at = |r: f64, a: f64| Point::new(r * a.cos(), r * a.sin());
+
+        let mut path = vec![
+            PathSegment::MoveTo(at(sr, sa0)),
+            PathSegment::arc_to(0.0, 0.0, sr, sa0, sa1, false),
+        ];
+        if sa0 != ta0 || sa1 != ta1 {
+            match self.head_radius {
+                Some(hr) => {
+                    let tr2 = tr - hr;
+                    let ta2 = (ta0 + ta1) / 2.0;
+                    let p = at(tr2, ta0);
+                    path.push(PathSegment::quad_to(0.0, 0.0, p.x, p.y));
+                    path.push(PathSegment::LineTo(at(tr, ta2)));
+                    path.push(PathSegment::LineTo(at(tr2, ta1)));
+                }
+                None => {
+                    let p = at(tr, ta0);
+                    path.push(PathSegment::quad_to(0.0, 0.0, p.x, p.y));
+                    path.push(PathSegment::arc_to(0.0, 0.0, tr, ta0, ta1, false));
+                }
+            }
+        }
+        let p = at(sr, sa0);
+        path.push(PathSegment::quad_to(0.0, 0.0, p.x, p.y));
+        path.push(PathSegment::ClosePath);
+        path
+    }
+
+    /// End angles in path coordinates (0 = 3 o'clock), trimmed by the pad angle
+    fn padded(&self, end: &ChordSubgroup) -> (f64, f64) {
+        let mut a0 = end.start_angle - FRAC_PI_2;
+        let mut a1 = end.end_angle - FRAC_PI_2;
+        let ap = self.pad_angle / 2.0;
+        if ap > 0.0 {
+            if (a1 - a0).abs() > ap * 2.0 + 1e-6 {
+                if a1 > a0 {
+                    a0 += ap;
+                    a1 -= ap;
+                } else {
+                    a0 -= ap;
+                    a1 += ap;
+                }
+            } else {
+                a0 = (a0 + a1) / 2.0;
+                a1 = a0;
+            }
+        }
+        (a0, a1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn test_undirected_merges_pairs() {
+        let matrix = vec![vec![0.0, 3.0], vec![1.0, 0.0]];
+        let set = ChordLayout::new().compute(&matrix).unwrap();
+
+        assert_eq!(set.groups.len(), 2);
+        assert!((set.groups[0].angle() - 1.5 * PI).abs() < EPS);
+        assert!((set.groups[1].end_angle - TAU).abs() < EPS);
+
+        assert_eq!(set.chords.len(), 1);
+        let chord = set.chords[0];
+        assert_eq!((chord.source.index, chord.target.index), (0, 1));
+        assert_eq!((chord.source.value, chord.target.value), (3.0, 1.0));
+
+        // The larger side becomes the source even when it comes second
+        let swapped = ChordLayout::new().compute(&[vec![0.0, 1.0], vec![3.0, 0.0]]).unwrap();
+        assert_eq!(swapped.chords[0].source.index, 1);
+    }
+
+    #[test]
+    fn test_directed_incoming_then_outgoing() {
+        let matrix = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        let set = ChordLayout::directed().compute(&matrix).unwrap();
+
+        assert_eq!(set.chords.len(), 2);
+        let forward = set.chords[0];
+        assert_eq!((forward.source.index, forward.target.index), (0, 1));
+        // Group 0 holds the incoming end first, then the outgoing one
+        assert!((forward.source.start_angle - FRAC_PI_2).abs() < EPS);
+        assert!((forward.target.start_angle - PI).abs() < EPS);
+        assert!((set.chords[1].target.start_angle - 0.0).abs() < EPS);
+
+        let transposed = ChordLayout::transposed().compute(&[vec![0.0, 2.0], vec![0.0, 0.0]]).unwrap();
+        assert_eq!(transposed.chords[0].source.index, 1);
+    }
+
+    #[test]
+    fn test_padding_and_sorting() {
+        let matrix = vec![vec![1.0, 0.0, 0.0], vec![0.0, 5.0, 0.0], vec![0.0, 0.0, 2.0]];
+        let set = ChordLayout::new()
+            .pad_angle(0.1)
+            .sort_groups(ChordSort::Descending)
+            .sort_chords(ChordSort::Ascending)
+            .compute(&matrix)
+            .unwrap();
+
+        // Largest group first, groups still indexed by row
+        assert!(set.groups[1].start_angle.abs() < EPS);
+        assert!((set.groups[2].start_angle - (set.groups[1].end_angle + 0.1)).abs() < EPS);
+        let spanned: f64 = set.groups.iter().map(ChordGroup::angle).sum();
+        assert!((spanned - (TAU - 0.3)).abs() < EPS);
+
+        let values: Vec<f64> = set.chords.iter().map(|c| c.source.value).collect();
+        assert_eq!(values, vec![1.0, 2.0, 5.0]);
+    }
+
+    #[test]
+    fn test_invalid_and_empty_matrices() {
+        assert!(ChordLayout::new().compute(&[vec![1.0, 2.0]]).is_err());
+        assert!(ChordLayout::new().compute(&[vec![-1.0]]).is_err());
+        assert!(ChordLayout::new().compute(&[vec![f64::NAN]]).is_err());
+        assert_eq!(ChordLayout::new().compute(&[]).unwrap(), ChordSet::default());
+
+        // All-zero matrix spreads groups evenly with no chords
+        let zeros = ChordLayout::new().compute(&[vec![0.0; 2], vec![0.0; 2]]).unwrap();
+        assert!(zeros.chords.is_empty());
+        assert!((zeros.groups[1].start_angle - PI).abs() < EPS);
+    }
+
+    #[test]
+    fn test_ribbon_shapes() {
+        let chord = Chord {
+            source: ChordSubgroup { index: 0, start_angle: 0.0, end_angle: FRAC_PI_2, value: 1.0 },
+            target: ChordSubgroup { index: 1, start_angle: PI, end_angle: 1.5 * PI, value: 1.0 },
+        };
+
+        let ribbon = RibbonGenerator::new().radius(10.0).generate(&chord);
+        assert_eq!(ribbon.len(), 6);
+        // Starts at 12 o'clock on the source radius
+        match ribbon[0] {
+            PathSegment::MoveTo(p) => assert!(p.x.abs() < EPS && (p.y + 10.0).abs() < EPS),
+            _ => panic!("expected MoveTo"),
+        }
+
+        let arrow = RibbonGenerator::new().radius(10.0).head_radius(2.0).generate(&chord);
+        assert_eq!(arrow.len(), 7);
+        // Arrow tip touches the target radius at the middle angle
+        match arrow[3] {
+            PathSegment::LineTo(p) => {
+                let tip = 0.75 * PI;
+                assert!((p.x - 10.0 * tip.cos()).abs() < EPS && (p.y - 10.0 * tip.sin()).abs() < EPS);
+            }
+            _ => panic!("expected LineTo"),
+        }
+
+        // Self-chords skip the target arc
+        let own = Chord { source: chord.source, target: chord.source };
+        assert_eq!(RibbonGenerator::new().generate(&own).len(), 4);
+    }
+}
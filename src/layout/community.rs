@@ -0,0 +1,382 @@
+//! Community detection for network layouts
+//!
+//! [`Louvain`] partitions a graph into densely connected communities by
+//! greedy modularity optimization (Blondel et al. 2008): nodes repeatedly
+//! move to the neighbouring community with the largest modularity gain, then
+//! communities are merged into single nodes and the process repeats. The
+//! node visiting order is shuffled from a fixed seed, so results are
+//! reproducible across runs.
+//!
+//! Community ids are renumbered in order of each community's lowest node
+//! index, which keeps colors stable when the graph is recomputed.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::layout::community::Louvain;
+//! use makepad_d3::layout::SimulationLink;
+//!
+//! // Two triangles joined by a single bridge
+//! let links: Vec<SimulationLink> = [(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]
+//!     .into_iter()
+//!     .map(SimulationLink::from)
+//!     .collect();
+//!
+//! let communities = Louvain::new().seed(7).detect(6, &links);
+//!
+//! assert_eq!(communities.count(), 2);
+//! assert_eq!(communities.assignment, vec![0, 0, 0, 1, 1, 1]);
+//! assert!(communities.modularity > 0.3);
+//! ```
+
+use std::collections::HashMap;
+
+use super::force::SimulationLink;
+use super::graph::WeightedGraph;
+use crate::rng::seeded_shuffle;
+
+/// Community assignment for every node
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Communities {
+    /// Community id per node, numbered from 0 by lowest member index
+    pub assignment: Vec<usize>,
+    /// Modularity of the partition on the input graph
+    pub modularity: f64,
+}
+
+impl Communities {
+    /// Number of communities
+    pub fn count(&self) -> usize {
+        self.assignment.iter().map(|&c| c + 1).max().unwrap_or(0)
+    }
+
+    /// Community of a node
+    pub fn community_of(&self, node: usize) -> Option<usize> {
+        self.assignment.get(node).copied()
+    }
+
+    /// Nodes of a community in index order
+    pub fn members(&self, community: usize) -> Vec<usize> {
+        self.assignment
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == community)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Node lists of every community
+    pub fn groups(&self) -> Vec<Vec<usize>> {
+        let mut groups = vec![Vec::new(); self.count()];
+        for (node, &c) in self.assignment.iter().enumerate() {
+            groups[c].push(node);
+        }
+        groups
+    }
+}
+
+/// Louvain modularity community detection
+#[derive(Clone, Debug)]
+pub struct Louvain {
+    /// Resolution; above 1 favours smaller communities
+    resolution: f64,
+    /// Seed for the node visiting order
+    seed: u64,
+    /// Maximum number of aggregation levels
+    max_levels: usize,
+}
+
+impl Default for Louvain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Undirected weighted adjacency; `loops[i]` holds `A_ii`
+struct Adjacency {
+    neighbors: Vec<Vec<(usize, f64)>>,
+    loops: Vec<f64>,
+}
+
+impl Adjacency {
+    fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    fn degree(&self, i: usize) -> f64 {
+        self.loops[i] + self.neighbors[i].iter().map(|&(_, w)| w).sum::<f64>()
+    }
+
+    /// Sum of `A_ij` over all ordered pairs
+    fn total(&self) -> f64 {
+        (0..self.len()).map(|i| self.degree(i)).sum()
+    }
+}
+
+impl Louvain {
+    /// Create a detector with resolution 1 and a fixed seed
+    pub fn new() -> Self {
+        Self {
+            resolution: 1.0,
+            seed: 12345,
+            max_levels: 32,
+        }
+    }
+
+    /// Set the resolution (default 1); higher values give more, smaller communities
+    pub fn resolution(mut self, resolution: f64) -> Self {
+        self.resolution = resolution.max(0.0);
+        self
+    }
+
+    /// Set the seed for the node visiting order
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the maximum number of aggregation levels
+    pub fn max_levels(mut self, levels: usize) -> Self {
+        self.max_levels = levels.max(1);
+        self
+    }
+
+    /// Detect communities among `node_count` nodes
+    ///
+    /// Links are undirected and weighted by their strength; links to
+    /// nodes outside `0..node_count` are ignored.
+    pub fn detect(&self, node_count: usize, links: &[SimulationLink]) -> Communities {
+        let edges: Vec<(usize, usize, f64)> = links
+            .iter()
+            .map(|l| (l.source, l.target, l.strength))
+            .collect();
+        self.detect_edges(node_count, &edges)
+    }
+
+    /// Detect communities of a collapsed graph, weighted by edge weight
+    pub fn detect_graph(&self, graph: &WeightedGraph) -> Communities {
+        let edges: Vec<(usize, usize, f64)> = graph.to_flows();
+        self.detect_edges(graph.node_count, &edges)
+    }
+
+    fn detect_edges(&self, node_count: usize, edges: &[(usize, usize, f64)]) -> Communities {
+        let mut adjacency = Adjacency {
+            neighbors: vec![Vec::new(); node_count],
+            loops: vec![0.0; node_count],
+        };
+        let mut pairs: HashMap<(usize, usize), f64> = HashMap::new();
+        for &(s, t, w) in edges {
+            if s >= node_count || t >= node_count || !w.is_finite() || w <= 0.0 {
+                continue;
+            }
+            if s == t {
+                adjacency.loops[s] += 2.0 * w;
+            } else {
+                *pairs.entry((s.min(t), s.max(t))).or_insert(0.0) += w;
+            }
+        }
+        let mut pairs: Vec<_> = pairs.into_iter().collect();
+        pairs.sort_by_key(|&(key, _)| key);
+        for ((s, t), w) in pairs {
+            adjacency.neighbors[s].push((t, w));
+            adjacency.neighbors[t].push((s, w));
+        }
+
+        let original_total = adjacency.total();
+        // Community of each original node
+        let mut assignment: Vec<usize> = (0..node_count).collect();
+        let mut graph = adjacency;
+        let mut state = self.seed;
+
+        if original_total > 0.0 {
+            for _ in 0..self.max_levels {
+                let (level, moved) = self.move_nodes(&graph, &mut state);
+                if !moved {
+                    break;
+                }
+                let (level, count) = renumber(&level);
+                for c in assignment.iter_mut() {
+                    *c = level[*c];
+                }
+                graph = aggregate(&graph, &level, count);
+            }
+        }
+
+        let (assignment, _) = renumber(&assignment);
+        let modularity = self.modularity_of(&assignment, node_count, edges);
+        Communities {
+            assignment,
+            modularity,
+        }
+    }
+
+    /// One local-moving phase; returns the community per node and whether any node moved
+    fn move_nodes(&self, graph: &Adjacency, state: &mut u64) -> (Vec<usize>, bool) {
+        let n = graph.len();
+        let m2 = graph.total();
+        let degrees: Vec<f64> = (0..n).map(|i| graph.degree(i)).collect();
+        let mut community: Vec<usize> = (0..n).collect();
+        let mut totals = degrees.clone();
+
+        let mut order: Vec<usize> = (0..n).collect();
+        seeded_shuffle(&mut order, state);
+
+        let mut moved_any = false;
+        let mut weights: HashMap<usize, f64> = HashMap::new();
+        loop {
+            let mut moved = false;
+            for &i in &order {
+                let current = community[i];
+                let k = degrees[i];
+                weights.clear();
+                let mut candidates = vec![current];
+                for &(j, w) in &graph.neighbors[i] {
+                    let c = community[j];
+                    if !weights.contains_key(&c) && c != current {
+                        candidates.push(c);
+                    }
+                    *weights.entry(c).or_insert(0.0) += w;
+                }
+
+                totals[current] -= k;
+                let gain = |c: usize| {
+                    weights.get(&c).copied().unwrap_or(0.0) - self.resolution * totals[c] * k / m2
+                };
+                let mut best = current;
+                let mut best_gain = gain(current);
+                for &c in &candidates[1..] {
+                    let g = gain(c);
+                    if g > best_gain + 1e-12 {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+                totals[best] += k;
+                if best != current {
+                    community[i] = best;
+                    moved = true;
+                    moved_any = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+        (community, moved_any)
+    }
+
+    fn modularity_of(&self, assignment: &[usize], node_count: usize, edges: &[(usize, usize, f64)]) -> f64 {
+        let count = assignment.iter().map(|&c| c + 1).max().unwrap_or(0);
+        let mut internal = vec![0.0; count];
+        let mut totals = vec![0.0; count];
+        let mut m2 = 0.0;
+        for &(s, t, w) in edges {
+            if s >= node_count || t >= node_count || !w.is_finite() || w <= 0.0 {
+                continue;
+            }
+            m2 += 2.0 * w;
+            totals[assignment[s]] += w;
+            totals[assignment[t]] += w;
+            if assignment[s] == assignment[t] {
+                internal[assignment[s]] += 2.0 * w;
+            }
+        }
+        if m2 == 0.0 {
+            return 0.0;
+        }
+        (0..count)
+            .map(|c| internal[c] / m2 - self.resolution * (totals[c] / m2).powi(2))
+            .sum()
+    }
+}
+
+/// Renumber ids densely in order of first appearance
+fn renumber(ids: &[usize]) -> (Vec<usize>, usize) {
+    let mut map: HashMap<usize, usize> = HashMap::new();
+    let renumbered = ids
+        .iter()
+        .map(|&id| {
+            let next = map.len();
+            *map.entry(id).or_insert(next)
+        })
+        .collect();
+    (renumbered, map.len())
+}
+
+/// Collapse each community into a single node
+fn aggregate(graph: &Adjacency, community: &[usize], count: usize) -> Adjacency {
+    let mut loops = vec![0.0; count];
+    let mut pairs: HashMap<(usize, usize), f64> = HashMap::new();
+    for i in 0..graph.len() {
+        let ci = community[i];
+        loops[ci] += graph.loops[i];
+        for &(j, w) in &graph.neighbors[i] {
+            let cj = community[j];
+            if ci == cj {
+                loops[ci] += w;
+            } else if ci < cj {
+                *pairs.entry((ci, cj)).or_insert(0.0) += w;
+            }
+        }
+    }
+    let mut neighbors = vec![Vec::new(); count];
+    let mut pairs: Vec<_> = pairs.into_iter().collect();
+    pairs.sort_by_key(|&(key, _)| key);
+    for ((a, b), w) in pairs {
+        neighbors[a].push((b, w));
+        neighbors[b].push((a, w));
+    }
+    Adjacency { neighbors, loops }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::graph::GraphCollapse;
+
+    fn links(pairs: &[(usize, usize)]) -> Vec<SimulationLink> {
+        pairs.iter().map(|&p| SimulationLink::from(p)).collect()
+    }
+
+    #[test]
+    fn test_cliques_are_separated() {
+        // Three 4-cliques in a ring of single bridges
+        let mut pairs = Vec::new();
+        for base in [0, 4, 8] {
+            for a in 0..4 {
+                for b in a + 1..4 {
+                    pairs.push((base + a, base + b));
+                }
+            }
+        }
+        pairs.extend([(3, 4), (7, 8), (11, 0)]);
+        let communities = Louvain::new().detect(12, &links(&pairs));
+        assert_eq!(communities.count(), 3);
+        assert_eq!(communities.groups(), vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9, 10, 11]]);
+        assert!(communities.modularity > 0.5);
+    }
+
+    #[test]
+    fn test_deterministic_for_seed() {
+        let pairs: Vec<(usize, usize)> = (0..30).map(|i| (i, (i * 7 + 3) % 30)).chain((0..30).map(|i| (i, (i + 1) % 30))).collect();
+        let a = Louvain::new().seed(42).detect(30, &links(&pairs));
+        let b = Louvain::new().seed(42).detect(30, &links(&pairs));
+        assert_eq!(a, b);
+        assert_eq!(a.community_of(0), Some(0));
+    }
+
+    #[test]
+    fn test_isolated_nodes_and_weights() {
+        let empty = Louvain::new().detect(3, &[]);
+        assert_eq!(empty.assignment, vec![0, 1, 2]);
+        assert_eq!(empty.modularity, 0.0);
+
+        // The heavy pairs win over the light cross edges
+        let graph = GraphCollapse::new()
+            .collapse(&[(0, 1, 10.0), (2, 3, 10.0), (1, 2, 1.0), (0, 3, 1.0)])
+            .unwrap();
+        let communities = Louvain::new().detect_graph(&graph);
+        assert_eq!(communities.assignment, vec![0, 0, 1, 1]);
+        assert_eq!(communities.members(1), vec![2, 3]);
+    }
+}
@@ -2,7 +2,16 @@
 //!
 //! Various forces that can be applied to nodes in a simulation.
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use super::quadtree::QuadTree;
 use super::simulation::{SimulationNode, SimulationLink};
+use crate::error::{D3Error, D3Result};
 
 /// Trait for forces that can be applied to nodes
 pub trait Force: Send + Sync {
@@ -17,6 +26,11 @@ pub trait Force: Send + Sync {
 ///
 /// Positive strength attracts, negative strength repels.
 ///
+/// Large graphs use the Barnes-Hut approximation: a quadtree groups distant
+/// nodes and a cell whose width over its distance is below `theta` acts as
+/// one charge at its center of mass, making each tick O(n log n). Set
+/// `theta` to 0 to sum every pair exactly.
+///
 /// # Example
 ///
 /// ```
@@ -27,6 +41,10 @@ pub trait Force: Send + Sync {
 ///
 /// // Attracting force
 /// let attract = ManyBodyForce::new().strength(10.0);
+///
+/// // Coarser approximation, ignoring nodes further than 200px away
+/// let fast = ManyBodyForce::new().theta(1.2).distance_max(200.0);
+/// assert_eq!(fast.get_theta(), 1.2);
 /// ```
 #[derive(Clone, Debug)]
 pub struct ManyBodyForce {
@@ -36,7 +54,7 @@ pub struct ManyBodyForce {
     distance_min: f64,
     /// Maximum distance for force calculation
     distance_max: f64,
-    /// Theta for Barnes-Hut approximation (not used in simple impl)
+    /// Theta for Barnes-Hut approximation (0 = exact)
     theta: f64,
 }
 
@@ -47,6 +65,9 @@ impl Default for ManyBodyForce {
 }
 
 impl ManyBodyForce {
+    /// Below this many nodes the exact sum is cheaper than building a quadtree
+    pub const BARNES_HUT_THRESHOLD: usize = 64;
+
     /// Create a new many-body force
     pub fn new() -> Self {
         Self {
@@ -75,48 +96,130 @@ impl ManyBodyForce {
         self
     }
 
+    /// Set the Barnes-Hut accuracy (d3 default 0.9; 0 = exact)
+    pub fn theta(mut self, theta: f64) -> Self {
+        self.theta = theta.max(0.0);
+        self
+    }
+
+    /// Sum every pair exactly (theta = 0)
+    pub fn exact(self) -> Self {
+        self.theta(0.0)
+    }
+
     /// Get the strength
     pub fn get_strength(&self) -> f64 {
         self.strength
     }
-}
 
-impl Force for ManyBodyForce {
-    fn apply(&self, nodes: &mut [SimulationNode], alpha: f64) {
-        let n = nodes.len();
-        if n < 2 {
-            return;
+    /// Get the Barnes-Hut theta
+    pub fn get_theta(&self) -> f64 {
+        self.theta
+    }
+
+    /// Get the minimum distance
+    pub fn get_distance_min(&self) -> f64 {
+        self.distance_min
+    }
+
+    /// Get the maximum distance
+    pub fn get_distance_max(&self) -> f64 {
+        self.distance_max
+    }
+
+    /// Whether `apply` uses the quadtree for this many nodes
+    pub fn uses_barnes_hut(&self, node_count: usize) -> bool {
+        self.theta > 0.0 && node_count >= Self::BARNES_HUT_THRESHOLD
+    }
+
+    /// Velocity change from `weight` nodes at offset (dx, dy), if in range
+    fn pull(&self, dx: f64, dy: f64, weight: f64, alpha: f64) -> Option<(f64, f64)> {
+        let min_sq = self.distance_min * self.distance_min;
+        let dist_sq = (dx * dx + dy * dy).max(min_sq);
+        if dist_sq > self.distance_max * self.distance_max {
+            return None;
         }
+        let dist = dist_sq.sqrt();
+        let force = self.strength * alpha * weight / dist_sq;
+        Some((dx / dist * force, dy / dist * force))
+    }
 
-        // O(n²) implementation - for large graphs, would use Barnes-Hut
+    fn apply_exact(&self, nodes: &mut [SimulationNode], alpha: f64) {
+        let n = nodes.len();
         for i in 0..n {
             for j in (i + 1)..n {
                 let dx = nodes[j].x - nodes[i].x;
                 let dy = nodes[j].y - nodes[i].y;
+                if let Some((fx, fy)) = self.pull(dx, dy, 1.0, alpha) {
+                    nodes[i].vx += fx;
+                    nodes[i].vy += fy;
+                    nodes[j].vx -= fx;
+                    nodes[j].vy -= fy;
+                }
+            }
+        }
+    }
 
-                let mut dist_sq = dx * dx + dy * dy;
+    fn apply_barnes_hut(&self, nodes: &mut [SimulationNode], alpha: f64) {
+        let tree = QuadTree::build(nodes);
+        let theta_sq = self.theta * self.theta;
+        let max_sq = self.distance_max * self.distance_max;
+        let positions: &[SimulationNode] = nodes;
 
-                // Clamp to minimum distance
-                if dist_sq < self.distance_min * self.distance_min {
-                    dist_sq = self.distance_min * self.distance_min;
+        let accumulate = |i: usize| -> (f64, f64) {
+            let (x, y) = (positions[i].x, positions[i].y);
+            let (mut vx, mut vy) = (0.0, 0.0);
+            let mut add = |delta: Option<(f64, f64)>| {
+                if let Some((fx, fy)) = delta {
+                    vx += fx;
+                    vy += fy;
                 }
-
-                // Skip if beyond maximum distance
-                if dist_sq > self.distance_max * self.distance_max {
-                    continue;
+            };
+            let mut stack = vec![0];
+            while let Some(id) = stack.pop() {
+                let cell = &tree.cells[id];
+                let (dx, dy) = (cell.cx - x, cell.cy - y);
+                let dist_sq = dx * dx + dy * dy;
+
+                if cell.is_leaf() {
+                    for &j in &tree.order[cell.start..cell.end] {
+                        if j != i {
+                            add(self.pull(positions[j].x - x, positions[j].y - y, 1.0, alpha));
+                        }
+                    }
+                } else if cell.size * cell.size < theta_sq * dist_sq && !cell.contains(x, y) {
+                    // Far enough to treat as one charge
+                    if dist_sq <= max_sq {
+                        add(self.pull(dx, dy, cell.count as f64, alpha));
+                    }
+                } else {
+                    stack.extend(cell.children.iter().flatten());
                 }
+            }
+            (vx, vy)
+        };
 
-                let dist = dist_sq.sqrt();
-                let force = self.strength * alpha / dist_sq;
+        #[cfg(feature = "parallel")]
+        let deltas: Vec<(f64, f64)> = (0..positions.len()).into_par_iter().map(accumulate).collect();
+        #[cfg(not(feature = "parallel"))]
+        let deltas: Vec<(f64, f64)> = (0..positions.len()).map(accumulate).collect();
 
-                let fx = dx / dist * force;
-                let fy = dy / dist * force;
+        for (node, (vx, vy)) in nodes.iter_mut().zip(deltas) {
+            node.vx += vx;
+            node.vy += vy;
+        }
+    }
+}
 
-                nodes[i].vx += fx;
-                nodes[i].vy += fy;
-                nodes[j].vx -= fx;
-                nodes[j].vy -= fy;
-            }
+impl Force for ManyBodyForce {
+    fn apply(&self, nodes: &mut [SimulationNode], alpha: f64) {
+        if nodes.len() < 2 {
+            return;
+        }
+        if self.uses_barnes_hut(nodes.len()) {
+            self.apply_barnes_hut(nodes, alpha);
+        } else {
+            self.apply_exact(nodes, alpha);
         }
     }
 
@@ -127,6 +230,12 @@ impl Force for ManyBodyForce {
 
 /// Link force for spring-like connections between nodes
 ///
+/// By default each link's strength is `1 / min(degree(source), degree(target))`
+/// and the correction is split between its ends by degree, so hubs move
+/// less than leaves, as in D3. Links built with
+/// [`SimulationLink::with_strength`] keep their own strength, and setting a
+/// strength on the force replaces the default.
+///
 /// # Example
 ///
 /// ```
@@ -136,6 +245,15 @@ impl Force for ManyBodyForce {
 /// let force = LinkForce::new(links)
 ///     .distance(50.0)
 ///     .strength(0.5);
+///
+/// // Links by node key, with per-link distances
+/// let ids = ["api", "db", "cache"];
+/// let force = LinkForce::from_ids(&ids, [("api", "db"), ("api", "cache"), ("db", "cache")])
+///     .unwrap()
+///     .distance_by(|link| if link.target == 1 { 80.0 } else { 40.0 });
+/// assert_eq!(force.links()[0].distance, 80.0);
+/// // Every node has two links, so each link gets half strength
+/// assert_eq!(force.effective_strength(0), Some(0.5));
 /// ```
 #[derive(Clone, Debug)]
 pub struct LinkForce {
@@ -143,12 +261,27 @@ pub struct LinkForce {
     links: Vec<SimulationLink>,
     /// Default link distance
     distance: f64,
-    /// Default link strength
-    strength: f64,
+    /// How link strengths are chosen
+    strength: LinkStrength,
+    /// Degree-based default strength per link
+    degree_strengths: Vec<f64>,
+    /// Share of each link's correction applied to its target
+    bias: Vec<f64>,
     /// Number of iterations per tick
     iterations: usize,
 }
 
+/// Source of link strengths
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LinkStrength {
+    /// 1 / min degree of the link's ends
+    Degree,
+    /// Same strength for every link
+    Uniform(f64),
+    /// Each link's own `strength` field
+    PerLink,
+}
+
 impl Default for LinkForce {
     fn default() -> Self {
         Self::new(Vec::<(usize, usize)>::new())
@@ -157,6 +290,8 @@ impl Default for LinkForce {
 
 impl LinkForce {
     /// Create a new link force
+    ///
+    /// Uses the degree-based default unless some link carries its own strength.
     pub fn new<L: Into<SimulationLink>>(links: Vec<L>) -> Self {
         let links: Vec<SimulationLink> = links
             .into_iter()
@@ -167,13 +302,69 @@ impl LinkForce {
                 link
             })
             .collect();
+        let strength = if links.iter().any(has_custom_strength) {
+            LinkStrength::PerLink
+        } else {
+            LinkStrength::Degree
+        };
 
-        Self {
+        let mut force = Self {
             links,
             distance: 30.0,
-            strength: 1.0,
+            strength,
+            degree_strengths: Vec::new(),
+            bias: Vec::new(),
             iterations: 1,
+        };
+        force.count_degrees();
+        force
+    }
+
+    /// Create a link force from links between node keys
+    ///
+    /// `ids[i]` is the key of node `i`; keys can be strings, numbers or any
+    /// hashable type.
+    ///
+    /// # Errors
+    ///
+    /// [`D3Error::InvalidData`] for a duplicate key in `ids` or a link to a
+    /// key that is not in `ids`.
+    pub fn from_ids<K, L>(ids: &[K], links: L) -> D3Result<Self>
+    where
+        K: Eq + Hash + Debug,
+        L: IntoIterator<Item = (K, K)>,
+    {
+        let mut index = HashMap::with_capacity(ids.len());
+        for (i, id) in ids.iter().enumerate() {
+            if index.insert(id, i).is_some() {
+                return Err(D3Error::invalid_data(format!("link force: duplicate node id {:?}", id)));
+            }
         }
+        let resolve = |id: &K| {
+            index
+                .get(id)
+                .copied()
+                .ok_or_else(|| D3Error::invalid_data(format!("link force: unknown node id {:?}", id)))
+        };
+
+        let links = links
+            .into_iter()
+            .map(|(source, target)| Ok((resolve(&source)?, resolve(&target)?)))
+            .collect::<D3Result<Vec<_>>>()?;
+        Ok(Self::new(links))
+    }
+
+    /// Create a link force from links between [`SimulationNode::id`]s
+    ///
+    /// # Errors
+    ///
+    /// As [`from_ids`](Self::from_ids).
+    pub fn from_node_ids<L>(nodes: &[SimulationNode], links: L) -> D3Result<Self>
+    where
+        L: IntoIterator<Item = (usize, usize)>,
+    {
+        let ids: Vec<usize> = nodes.iter().map(|n| n.id).collect();
+        Self::from_ids(&ids, links)
     }
 
     /// Set the default link distance
@@ -186,15 +377,40 @@ impl LinkForce {
         self
     }
 
-    /// Set the default link strength
+    /// Set each link's distance from the link
+    pub fn distance_by(mut self, distance: impl Fn(&SimulationLink) -> f64) -> Self {
+        for link in &mut self.links {
+            link.distance = distance(link).max(0.0);
+        }
+        self
+    }
+
+    /// Set the same strength for every link
     pub fn strength(mut self, strength: f64) -> Self {
-        self.strength = strength.clamp(0.0, 1.0);
+        let strength = strength.clamp(0.0, 1.0);
+        self.strength = LinkStrength::Uniform(strength);
+        for link in &mut self.links {
+            link.strength = strength;
+        }
+        self
+    }
+
+    /// Set each link's strength from the link
+    pub fn strength_by(mut self, strength: impl Fn(&SimulationLink) -> f64) -> Self {
+        self.strength = LinkStrength::PerLink;
         for link in &mut self.links {
-            link.strength = self.strength;
+            link.strength = strength(link).clamp(0.0, 1.0);
         }
         self
     }
 
+    /// Use the strength stored on each link, such as
+    /// [`WeightedGraph::to_links`](crate::layout::WeightedGraph::to_links) weights
+    pub fn per_link_strength(mut self) -> Self {
+        self.strength = LinkStrength::PerLink;
+        self
+    }
+
     /// Set the number of iterations per tick
     pub fn iterations(mut self, iterations: usize) -> Self {
         self.iterations = iterations.max(1);
@@ -206,20 +422,60 @@ impl LinkForce {
         &self.links
     }
 
+    /// Strength the link at `index` is applied with
+    pub fn effective_strength(&self, index: usize) -> Option<f64> {
+        let link = self.links.get(index)?;
+        Some(match self.strength {
+            LinkStrength::Degree => self.degree_strengths[index],
+            LinkStrength::Uniform(strength) => strength,
+            LinkStrength::PerLink => link.strength,
+        })
+    }
+
     /// Add a link
     pub fn add_link<L: Into<SimulationLink>>(&mut self, link: L) {
         let mut link = link.into();
         link.index = self.links.len();
         link.distance = self.distance;
-        link.strength = self.strength;
+        match self.strength {
+            LinkStrength::Uniform(strength) => link.strength = strength,
+            LinkStrength::Degree if has_custom_strength(&link) => self.strength = LinkStrength::PerLink,
+            _ => {}
+        }
         self.links.push(link);
+        self.count_degrees();
     }
+
+    /// Recompute degree-based strengths and biases
+    fn count_degrees(&mut self) {
+        let size = self.links.iter().map(|l| l.source.max(l.target) + 1).max().unwrap_or(0);
+        let mut degree = vec![0usize; size];
+        for link in &self.links {
+            degree[link.source] += 1;
+            degree[link.target] += 1;
+        }
+        self.degree_strengths = self
+            .links
+            .iter()
+            .map(|l| 1.0 / degree[l.source].min(degree[l.target]) as f64)
+            .collect();
+        self.bias = self
+            .links
+            .iter()
+            .map(|l| degree[l.source] as f64 / (degree[l.source] + degree[l.target]) as f64)
+            .collect();
+    }
+}
+
+/// Whether a link's strength differs from the [`SimulationLink`] default
+fn has_custom_strength(link: &SimulationLink) -> bool {
+    link.strength != SimulationLink::new(0, 0).strength
 }
 
 impl Force for LinkForce {
     fn apply(&self, nodes: &mut [SimulationNode], alpha: f64) {
         for _ in 0..self.iterations {
-            for link in &self.links {
+            for (i, link) in self.links.iter().enumerate() {
                 let source = link.source;
                 let target = link.target;
 
@@ -230,14 +486,15 @@ impl Force for LinkForce {
                 let dx = nodes[target].x - nodes[source].x;
                 let dy = nodes[target].y - nodes[source].y;
 
+                let strength = self.effective_strength(i).unwrap_or(0.0);
                 let dist = (dx * dx + dy * dy).sqrt().max(0.001);
-                let force = (dist - link.distance) / dist * alpha * link.strength;
+                let force = (dist - link.distance) / dist * alpha * strength;
 
                 let fx = dx * force;
                 let fy = dy * force;
 
-                // Bias based on node degree (simplified - use 0.5 for equal)
-                let bias = 0.5;
+                // The better-connected end moves less
+                let bias = self.bias[i];
 
                 nodes[target].vx -= fx * bias;
                 nodes[target].vy -= fy * bias;
@@ -674,6 +931,63 @@ mod tests {
         assert!(nodes[1].vx < 0.0); // Second node pulled left
     }
 
+    fn scattered_nodes(n: usize) -> Vec<SimulationNode> {
+        // Deterministic spiral with uneven density
+        (0..n)
+            .map(|i| {
+                let t = i as f64 * 0.7;
+                SimulationNode::at(i, t.cos() * (5.0 + 3.0 * t), t.sin() * (5.0 + 2.0 * t))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_many_body_barnes_hut_matches_exact() {
+        let mut exact = scattered_nodes(300);
+        let mut approx = exact.clone();
+        let exact_force = ManyBodyForce::new().exact();
+        let approx_force = ManyBodyForce::new().theta(0.5);
+        assert!(!exact_force.uses_barnes_hut(300));
+        assert!(approx_force.uses_barnes_hut(300));
+
+        exact_force.apply(&mut exact, 1.0);
+        approx_force.apply(&mut approx, 1.0);
+
+        let error: f64 = exact
+            .iter()
+            .zip(&approx)
+            .map(|(a, b)| (a.vx - b.vx).hypot(a.vy - b.vy))
+            .sum();
+        let magnitude: f64 = exact.iter().map(|a| a.vx.hypot(a.vy)).sum();
+        assert!(error / magnitude < 0.05);
+    }
+
+    #[test]
+    fn test_many_body_barnes_hut_distance_max() {
+        // Two clusters far apart: with a short reach only the local one counts
+        let mut nodes = scattered_nodes(100);
+        nodes.extend(scattered_nodes(100).into_iter().map(|mut n| {
+            n.x += 10_000.0;
+            n
+        }));
+        let mut near = nodes.clone();
+        ManyBodyForce::new().theta(0.5).distance_max(5000.0).apply(&mut near, 1.0);
+        let mut local = nodes[..100].to_vec();
+        ManyBodyForce::new().exact().apply(&mut local, 1.0);
+
+        let error: f64 = near.iter().zip(&local).map(|(a, b)| (a.vx - b.vx).hypot(a.vy - b.vy)).sum();
+        let magnitude: f64 = local.iter().map(|a| a.vx.hypot(a.vy)).sum();
+        assert!(error / magnitude < 0.05);
+    }
+
+    #[test]
+    fn test_many_body_small_graphs_stay_exact() {
+        let force = ManyBodyForce::new();
+        assert!(!force.uses_barnes_hut(ManyBodyForce::BARNES_HUT_THRESHOLD - 1));
+        assert_eq!(force.get_distance_min(), 1.0);
+        assert_eq!(force.get_distance_max(), f64::INFINITY);
+    }
+
     #[test]
     fn test_link_force_new() {
         let links = vec![(0, 1), (1, 2)];
@@ -697,6 +1011,81 @@ mod tests {
         assert!(nodes[1].vx < 0.0);
     }
 
+    #[test]
+    fn test_link_force_degree_strength() {
+        // Degrees 3, 2, 2, 1: strength is set by the less-connected end
+        let force = LinkForce::new(vec![(0, 1), (0, 2), (0, 3), (1, 2)]);
+        assert_eq!(force.effective_strength(0), Some(0.5));
+        assert_eq!(force.effective_strength(2), Some(1.0));
+        assert_eq!(force.effective_strength(3), Some(0.5));
+        assert_eq!(force.effective_strength(4), None);
+
+        // The hub end of a link moves less than the leaf end
+        let mut nodes = vec![
+            SimulationNode::at(0, 0.0, 0.0),
+            SimulationNode::at(1, 0.0, 100.0),
+            SimulationNode::at(2, 0.0, -100.0),
+            SimulationNode::at(3, 100.0, 0.0),
+        ];
+        let star = LinkForce::new(vec![(0, 1), (0, 2), (0, 3)]);
+        star.apply(&mut nodes, 1.0);
+        assert!((nodes[3].vx + 52.5).abs() < 1e-9);
+        assert!((nodes[0].vx - 17.5).abs() < 1e-9);
+
+        let mut force = force.strength(0.3);
+        force.add_link((3, 4));
+        assert_eq!(force.effective_strength(4), Some(0.3));
+        assert_eq!(force.links()[4].strength, 0.3);
+    }
+
+    #[test]
+    fn test_link_force_per_link_accessors() {
+        let links = vec![SimulationLink::new(0, 1).with_strength(0.2), SimulationLink::new(1, 2)];
+        let force = LinkForce::new(links.clone()).per_link_strength();
+        assert_eq!(force.effective_strength(0), Some(0.2));
+        assert_eq!(force.effective_strength(1), Some(1.0));
+
+        let force = LinkForce::new(links)
+            .strength_by(|l| 0.1 * (l.index + 1) as f64)
+            .distance_by(|l| (l.source + l.target) as f64 * 10.0);
+        assert_eq!(force.effective_strength(1), Some(0.2));
+        assert_eq!(force.links()[1].distance, 30.0);
+    }
+
+    #[test]
+    fn test_link_force_keeps_custom_link_strength() {
+        let force = LinkForce::new(vec![SimulationLink::new(0, 1).with_strength(0.2), SimulationLink::new(1, 2)]);
+        assert_eq!(force.effective_strength(0), Some(0.2));
+        assert_eq!(force.effective_strength(1), Some(1.0));
+
+        let mut nodes = vec![SimulationNode::at(0, 0.0, 0.0), SimulationNode::at(1, 100.0, 0.0)];
+        LinkForce::new(vec![SimulationLink::new(0, 1).with_strength(0.2)]).apply(&mut nodes, 1.0);
+        // (100 - 30) * 0.2, split evenly between the two ends
+        assert!((nodes[1].vx + 7.0).abs() < 1e-9);
+        assert!((nodes[0].vx - 7.0).abs() < 1e-9);
+
+        let mut force = LinkForce::new(vec![(0, 1), (1, 2)]);
+        assert_eq!(force.effective_strength(0), Some(1.0));
+        force.add_link(SimulationLink::new(2, 3).with_strength(0.4));
+        assert_eq!(force.effective_strength(2), Some(0.4));
+    }
+
+    #[test]
+    fn test_link_force_from_ids() {
+        let ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let force = LinkForce::from_ids(&ids, vec![("c".to_string(), "a".to_string())]).unwrap();
+        assert_eq!((force.links()[0].source, force.links()[0].target), (2, 0));
+
+        let unknown = LinkForce::from_ids(&ids, vec![("a".to_string(), "z".to_string())]);
+        assert!(matches!(unknown, Err(D3Error::InvalidData { .. })));
+        assert!(LinkForce::from_ids(&[1u64, 1], Vec::new()).is_err());
+
+        let nodes = vec![SimulationNode::new(10), SimulationNode::new(20)];
+        let force = LinkForce::from_node_ids(&nodes, [(20, 10)]).unwrap();
+        assert_eq!((force.links()[0].source, force.links()[0].target), (1, 0));
+        assert!(LinkForce::from_node_ids(&nodes, [(10, 30)]).is_err());
+    }
+
     #[test]
     fn test_collide_force_new() {
         let force = CollideForce::new();
@@ -13,6 +13,11 @@
 //! - **Center**: Pulls nodes toward center
 //! - **Position**: Pulls nodes toward target positions
 //!
+//! [`SimulationStats`] reports kinetic energy, per-tick displacement and work
+//! counters; a [`Convergence`] criterion stops the simulation once it settles.
+//! Alpha follows d3's schedule (`alpha_min`, `alpha_decay`, `alpha_target`),
+//! and the simulation queues [`SimulationEvent`]s for widgets to poll.
+//!
 //! # Example
 //!
 //! ```
@@ -31,8 +36,11 @@
 
 mod simulation;
 mod forces;
+mod quadtree;
 
-pub use simulation::{ForceSimulation, SimulationNode, SimulationLink};
+pub use simulation::{
+    Convergence, ForceSimulation, SimulationEvent, SimulationLink, SimulationNode, SimulationStats,
+};
 pub use forces::{
     Force, ManyBodyForce, LinkForce, CollideForce, CenterForce, PositionForce, RadialForce,
 };
@@ -0,0 +1,185 @@
+//! Point quadtree with per-cell centers of mass
+//!
+//! Built once per tick by [`ManyBodyForce`](super::ManyBodyForce) for the
+//! Barnes-Hut approximation.
+
+use super::simulation::SimulationNode;
+
+/// Cells deeper than this stay leaves, so coincident nodes cannot recurse forever
+const MAX_DEPTH: usize = 32;
+
+/// One square cell of the tree
+#[derive(Clone, Debug)]
+pub(crate) struct Cell {
+    /// Left edge
+    pub x0: f64,
+    /// Top edge
+    pub y0: f64,
+    /// Side length
+    pub size: f64,
+    /// Number of nodes inside
+    pub count: usize,
+    /// Center of mass x
+    pub cx: f64,
+    /// Center of mass y
+    pub cy: f64,
+    /// Start of this cell's nodes in [`QuadTree::order`]
+    pub start: usize,
+    /// End (exclusive) of this cell's nodes in [`QuadTree::order`]
+    pub end: usize,
+    /// Non-empty child cells; all None for a leaf
+    pub children: [Option<usize>; 4],
+}
+
+impl Cell {
+    /// Whether the cell has no children
+    pub fn is_leaf(&self) -> bool {
+        self.children.iter().all(Option::is_none)
+    }
+
+    /// Whether the point lies inside the cell
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x0 && x <= self.x0 + self.size && y >= self.y0 && y <= self.y0 + self.size
+    }
+}
+
+/// Quadtree over node positions
+#[derive(Clone, Debug, Default)]
+pub(crate) struct QuadTree {
+    /// Cells; the root is at index 0
+    pub cells: Vec<Cell>,
+    /// Node indices, grouped so every cell covers a contiguous range
+    pub order: Vec<usize>,
+}
+
+impl QuadTree {
+    /// Build the tree over all nodes
+    pub fn build(nodes: &[SimulationNode]) -> Self {
+        let mut tree = Self {
+            cells: Vec::with_capacity(nodes.len() * 2),
+            order: (0..nodes.len()).collect(),
+        };
+        if nodes.is_empty() {
+            return tree;
+        }
+
+        let (mut x0, mut y0) = (f64::INFINITY, f64::INFINITY);
+        let (mut x1, mut y1) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for node in nodes {
+            x0 = x0.min(node.x);
+            y0 = y0.min(node.y);
+            x1 = x1.max(node.x);
+            y1 = y1.max(node.y);
+        }
+        let size = (x1 - x0).max(y1 - y0).max(f64::EPSILON);
+        tree.subdivide(nodes, 0, nodes.len(), x0, y0, size, 0);
+        tree
+    }
+
+    /// Create the cell covering `order[start..end]` and its descendants
+    #[allow(clippy::too_many_arguments)]
+    fn subdivide(
+        &mut self,
+        nodes: &[SimulationNode],
+        start: usize,
+        end: usize,
+        x0: f64,
+        y0: f64,
+        size: f64,
+        depth: usize,
+    ) -> usize {
+        let count = end - start;
+        let (sx, sy) = self.order[start..end]
+            .iter()
+            .fold((0.0, 0.0), |(sx, sy), &i| (sx + nodes[i].x, sy + nodes[i].y));
+        let id = self.cells.len();
+        self.cells.push(Cell {
+            x0,
+            y0,
+            size,
+            count,
+            cx: sx / count as f64,
+            cy: sy / count as f64,
+            start,
+            end,
+            children: [None; 4],
+        });
+        if count <= 1 || depth >= MAX_DEPTH {
+            return id;
+        }
+
+        // Split top/bottom, then each half left/right
+        let half = size / 2.0;
+        let (mx, my) = (x0 + half, y0 + half);
+        let order = &mut self.order[start..end];
+        let mid = start + partition(order, |i| nodes[i].y < my);
+        let left_top = start + partition(&mut self.order[start..mid], |i| nodes[i].x < mx);
+        let left_bottom = mid + partition(&mut self.order[mid..end], |i| nodes[i].x < mx);
+
+        let quadrants = [
+            (start, left_top, x0, y0),
+            (left_top, mid, mx, y0),
+            (mid, left_bottom, x0, my),
+            (left_bottom, end, mx, my),
+        ];
+        for (q, (s, e, qx, qy)) in quadrants.into_iter().enumerate() {
+            if s < e {
+                let child = self.subdivide(nodes, s, e, qx, qy, half, depth + 1);
+                self.cells[id].children[q] = Some(child);
+            }
+        }
+        id
+    }
+}
+
+/// Move entries matching `pred` to the front, returning how many matched
+fn partition(items: &mut [usize], pred: impl Fn(usize) -> bool) -> usize {
+    let mut split = 0;
+    for i in 0..items.len() {
+        if pred(items[i]) {
+            items.swap(split, i);
+            split += 1;
+        }
+    }
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadtree_mass_and_ranges() {
+        let nodes = vec![
+            SimulationNode::at(0, 0.0, 0.0),
+            SimulationNode::at(1, 10.0, 0.0),
+            SimulationNode::at(2, 0.0, 10.0),
+            SimulationNode::at(3, 10.0, 10.0),
+            SimulationNode::at(4, 9.0, 9.0),
+        ];
+        let tree = QuadTree::build(&nodes);
+        let root = &tree.cells[0];
+        assert_eq!(root.count, 5);
+        assert!((root.cx - 5.8).abs() < 1e-12);
+        assert!((root.cy - 5.8).abs() < 1e-12);
+
+        // Every leaf holds one node, and together they cover all of them
+        let mut seen: Vec<usize> = tree
+            .cells
+            .iter()
+            .filter(|c| c.is_leaf())
+            .flat_map(|c| tree.order[c.start..c.end].to_vec())
+            .collect();
+        seen.sort_unstable();
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+        assert!(tree.cells.iter().filter(|c| c.is_leaf()).all(|c| c.count == 1));
+    }
+
+    #[test]
+    fn test_quadtree_coincident_nodes() {
+        let nodes: Vec<SimulationNode> = (0..3).map(|i| SimulationNode::at(i, 1.0, 1.0)).collect();
+        let tree = QuadTree::build(&nodes);
+        assert_eq!(tree.cells[0].count, 3);
+        assert!(tree.cells.len() <= MAX_DEPTH + 2);
+    }
+}
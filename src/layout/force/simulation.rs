@@ -2,9 +2,10 @@
 //!
 //! The simulation engine that manages nodes and applies forces.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use serde::{Deserialize, Serialize};
 use super::forces::Force;
+use crate::rng::SimpleRng;
 
 /// A node in the force simulation
 ///
@@ -151,6 +152,97 @@ impl From<(usize, usize)> for SimulationLink {
     }
 }
 
+/// Energy and work counters collected while a simulation runs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimulationStats {
+    /// Total ticks performed
+    pub ticks: usize,
+    /// Total force applications (forces × ticks)
+    pub force_evaluations: usize,
+    /// Total node position updates
+    pub node_updates: usize,
+    /// Kinetic energy after the last tick (Σ ½·v² over all nodes)
+    pub kinetic_energy: f64,
+    /// Largest distance any node moved during the last tick
+    pub max_displacement: f64,
+    /// Tick at which the convergence criterion was first met
+    pub converged_at: Option<usize>,
+}
+
+/// Criterion for stopping a simulation before alpha decays to its minimum
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Convergence {
+    /// Stop when kinetic energy falls below this value
+    pub energy: Option<f64>,
+    /// Stop when no node moves further than this per tick
+    pub displacement: Option<f64>,
+    /// Number of consecutive ticks the thresholds must hold
+    pub patience: usize,
+}
+
+impl Default for Convergence {
+    fn default() -> Self {
+        Self {
+            energy: None,
+            displacement: Some(0.01),
+            patience: 3,
+        }
+    }
+}
+
+impl Convergence {
+    /// Converge when kinetic energy stays below `threshold`
+    pub fn energy(threshold: f64) -> Self {
+        Self {
+            energy: Some(threshold.max(0.0)),
+            displacement: None,
+            ..Default::default()
+        }
+    }
+
+    /// Converge when the maximum displacement stays below `threshold`
+    pub fn displacement(threshold: f64) -> Self {
+        Self {
+            displacement: Some(threshold.max(0.0)),
+            ..Default::default()
+        }
+    }
+
+    /// Set the number of consecutive ticks the thresholds must hold
+    pub fn patience(mut self, ticks: usize) -> Self {
+        self.patience = ticks.max(1);
+        self
+    }
+
+    /// Check whether the given tick metrics satisfy every set threshold
+    pub fn is_met(&self, kinetic_energy: f64, max_displacement: f64) -> bool {
+        if self.energy.is_none() && self.displacement.is_none() {
+            return false;
+        }
+        self.energy.map_or(true, |e| kinetic_energy < e)
+            && self.displacement.map_or(true, |d| max_displacement < d)
+    }
+}
+
+/// Notification queued by [`ForceSimulation`] for the host widget
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SimulationEvent {
+    /// A tick ran; consecutive ticks that were not polled are coalesced
+    Tick {
+        /// Total ticks performed so far
+        tick: usize,
+        /// Alpha after the tick
+        alpha: f64,
+    },
+    /// The simulation became stable
+    End {
+        /// Tick at which the simulation settled
+        tick: usize,
+        /// Whether the convergence criterion (rather than alpha) ended it
+        converged: bool,
+    },
+}
+
 /// Force simulation for positioning nodes
 ///
 /// The simulation runs iteratively, applying forces to nodes and
@@ -173,6 +265,46 @@ impl From<(usize, usize)> for SimulationLink {
 ///     sim.tick();
 /// }
 /// ```
+///
+/// With a convergence criterion the simulation stops as soon as the layout
+/// settles, instead of always running until alpha reaches its minimum:
+///
+/// ```
+/// use makepad_d3::layout::force::{Convergence, ForceSimulation, SimulationNode, ManyBodyForce};
+///
+/// let nodes: Vec<SimulationNode> = (0..10).map(SimulationNode::new).collect();
+/// let mut sim = ForceSimulation::new(nodes)
+///     .add_force("charge", ManyBodyForce::new())
+///     .convergence(Convergence::displacement(0.05));
+///
+/// let ticks = sim.run(1000);
+/// assert!(sim.is_stable());
+/// assert_eq!(sim.stats().ticks, ticks);
+/// ```
+///
+/// Interactive widgets reheat the simulation while the user drags a node by
+/// raising the alpha target, and poll [`SimulationEvent`]s to know when to
+/// redraw and when to stop requesting frames:
+///
+/// ```
+/// use makepad_d3::layout::force::{ForceSimulation, SimulationEvent, SimulationNode, ManyBodyForce};
+///
+/// let nodes: Vec<SimulationNode> = (0..4).map(SimulationNode::new).collect();
+/// let mut sim = ForceSimulation::new(nodes).add_force("charge", ManyBodyForce::new());
+/// sim.run(1000);
+/// assert!(matches!(sim.drain_events().last(), Some(SimulationEvent::End { .. })));
+///
+/// // Drag start: keep the layout warm until the drag ends
+/// sim.set_alpha_target(0.3);
+/// assert!(!sim.is_stable());
+/// sim.tick_n(10);
+/// assert!(matches!(sim.poll_event(), Some(SimulationEvent::Tick { alpha, .. }) if alpha > 0.05));
+///
+/// // Drag end: let it cool down again
+/// sim.set_alpha_target(0.0);
+/// sim.run(1000);
+/// assert!(sim.is_stable());
+/// ```
 pub struct ForceSimulation {
     /// Nodes in the simulation
     nodes: Vec<SimulationNode>,
@@ -190,6 +322,16 @@ pub struct ForceSimulation {
     velocity_decay: f64,
     /// Random seed for initial positions
     random_seed: u64,
+    /// Early-stop criterion (None = run until alpha_min)
+    convergence: Option<Convergence>,
+    /// Consecutive ticks meeting the convergence criterion
+    calm_ticks: usize,
+    /// Instrumentation counters
+    stats: SimulationStats,
+    /// Queued notifications for the host
+    events: VecDeque<SimulationEvent>,
+    /// Whether the End event has been queued since the last reheat
+    ended: bool,
 }
 
 impl ForceSimulation {
@@ -217,6 +359,11 @@ impl ForceSimulation {
             alpha_target: 0.0,
             velocity_decay: 0.4,
             random_seed: 12345,
+            convergence: None,
+            calm_ticks: 0,
+            stats: SimulationStats::default(),
+            events: VecDeque::new(),
+            ended: false,
         }
     }
 
@@ -260,20 +407,123 @@ impl ForceSimulation {
         self
     }
 
+    /// Set the alpha decay so alpha reaches its minimum after `iterations` ticks
+    ///
+    /// Matches d3's default of `1 - alpha_min^(1/300)` for 300 iterations.
+    /// Call after [`alpha_min`](Self::alpha_min).
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        let n = iterations.max(1) as f64;
+        self.alpha_decay = if self.alpha_min > 0.0 {
+            1.0 - self.alpha_min.powf(1.0 / n)
+        } else {
+            1.0
+        };
+        self
+    }
+
     /// Set the velocity decay (friction)
     pub fn velocity_decay(mut self, decay: f64) -> Self {
         self.velocity_decay = decay.clamp(0.0, 1.0);
         self
     }
 
+    /// Set a convergence criterion for stopping early
+    pub fn convergence(mut self, convergence: Convergence) -> Self {
+        self.convergence = Some(convergence);
+        self
+    }
+
     /// Get the current alpha
     pub fn get_alpha(&self) -> f64 {
         self.alpha
     }
 
+    /// Get the minimum alpha
+    pub fn get_alpha_min(&self) -> f64 {
+        self.alpha_min
+    }
+
+    /// Get the alpha decay rate
+    pub fn get_alpha_decay(&self) -> f64 {
+        self.alpha_decay
+    }
+
+    /// Get the alpha target
+    pub fn get_alpha_target(&self) -> f64 {
+        self.alpha_target
+    }
+
+    /// Get the velocity decay
+    pub fn get_velocity_decay(&self) -> f64 {
+        self.velocity_decay
+    }
+
+    /// Set the current alpha on a running simulation
+    pub fn set_alpha(&mut self, alpha: f64) {
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self.reset_convergence();
+    }
+
+    /// Set the alpha target on a running simulation
+    ///
+    /// While the target is at or above the minimum alpha the simulation never
+    /// becomes stable, so a widget can keep it warm for the length of a drag
+    /// and set the target back to 0 on release.
+    pub fn set_alpha_target(&mut self, target: f64) {
+        self.alpha_target = target.clamp(0.0, 1.0);
+        self.reset_convergence();
+    }
+
+    /// Get the convergence criterion
+    pub fn get_convergence(&self) -> Option<Convergence> {
+        self.convergence
+    }
+
     /// Check if simulation has stabilized
+    ///
+    /// True once alpha drops below its minimum or the convergence
+    /// criterion has been met, unless the alpha target keeps it warm.
     pub fn is_stable(&self) -> bool {
-        self.alpha < self.alpha_min
+        self.alpha_target < self.alpha_min && (self.alpha < self.alpha_min || self.is_converged())
+    }
+
+    /// Check if the convergence criterion has been met since the last restart
+    pub fn is_converged(&self) -> bool {
+        self.convergence
+            .is_some_and(|c| self.calm_ticks >= c.patience.max(1))
+    }
+
+    /// Get the instrumentation counters
+    pub fn stats(&self) -> &SimulationStats {
+        &self.stats
+    }
+
+    /// Take the oldest queued event
+    pub fn poll_event(&mut self) -> Option<SimulationEvent> {
+        self.events.pop_front()
+    }
+
+    /// Take all queued events
+    pub fn drain_events(&mut self) -> Vec<SimulationEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Reset the instrumentation counters
+    pub fn reset_stats(&mut self) {
+        self.stats = SimulationStats::default();
+    }
+
+    /// Kinetic energy of the current node velocities (Σ ½·v²)
+    pub fn kinetic_energy(&self) -> f64 {
+        self.nodes
+            .iter()
+            .map(|n| 0.5 * (n.vx * n.vx + n.vy * n.vy))
+            .sum()
+    }
+
+    /// Largest distance any node moved during the last tick
+    pub fn max_displacement(&self) -> f64 {
+        self.stats.max_displacement
     }
 
     /// Get the nodes
@@ -306,18 +556,32 @@ impl ForceSimulation {
         // Decay alpha
         self.alpha += (self.alpha_target - self.alpha) * self.alpha_decay;
 
+        // Forces see pinned nodes where they were pinned, even mid-drag
+        for node in &mut self.nodes {
+            if let Some(fx) = node.fx {
+                node.x = fx;
+            }
+            if let Some(fy) = node.fy {
+                node.y = fy;
+            }
+        }
+
         // Apply forces
         let force_names: Vec<String> = self.forces.keys().cloned().collect();
         for name in force_names {
             if let Some(force) = self.forces.get(&name) {
                 let alpha = self.alpha;
                 force.apply(&mut self.nodes, alpha);
+                self.stats.force_evaluations += 1;
             }
         }
 
         // Update positions
         let decay = 1.0 - self.velocity_decay;
+        let mut max_displacement: f64 = 0.0;
         for node in &mut self.nodes {
+            let (x0, y0) = (node.x, node.y);
+
             // Apply velocity decay
             node.vx *= decay;
             node.vy *= decay;
@@ -336,6 +600,43 @@ impl ForceSimulation {
             } else {
                 node.y += node.vy;
             }
+
+            max_displacement = max_displacement.max((node.x - x0).hypot(node.y - y0));
+        }
+
+        self.record_tick(max_displacement);
+    }
+
+    /// Update counters and the convergence state after a tick
+    fn record_tick(&mut self, max_displacement: f64) {
+        let kinetic_energy = self.kinetic_energy();
+        self.stats.ticks += 1;
+        self.stats.node_updates += self.nodes.len();
+        self.stats.kinetic_energy = kinetic_energy;
+        self.stats.max_displacement = max_displacement;
+
+        if let Some(convergence) = self.convergence {
+            if convergence.is_met(kinetic_energy, max_displacement) {
+                self.calm_ticks += 1;
+            } else {
+                self.calm_ticks = 0;
+            }
+            if self.is_converged() && self.stats.converged_at.is_none() {
+                self.stats.converged_at = Some(self.stats.ticks);
+            }
+        }
+
+        let tick = SimulationEvent::Tick { tick: self.stats.ticks, alpha: self.alpha };
+        match self.events.back_mut() {
+            Some(last) if matches!(last, SimulationEvent::Tick { .. }) => *last = tick,
+            _ => self.events.push_back(tick),
+        }
+        if !self.ended && self.is_stable() {
+            self.ended = true;
+            self.events.push_back(SimulationEvent::End {
+                tick: self.stats.ticks,
+                converged: self.is_converged(),
+            });
         }
     }
 
@@ -356,14 +657,27 @@ impl ForceSimulation {
         iterations
     }
 
-    /// Restart the simulation
+    /// Restart the simulation at full alpha
     pub fn restart(&mut self) {
         self.alpha = 1.0;
+        self.reset_convergence();
+    }
+
+    /// Clear the convergence state so the simulation runs again
+    fn reset_convergence(&mut self) {
+        self.calm_ticks = 0;
+        self.stats.converged_at = None;
+        self.ended = false;
     }
 
     /// Stop the simulation
+    ///
+    /// Drops alpha and the alpha target to 0 so it is stable immediately.
+    /// No End event is queued.
     pub fn stop(&mut self) {
         self.alpha = 0.0;
+        self.alpha_target = 0.0;
+        self.ended = true;
     }
 
     /// Find node nearest to a point
@@ -372,6 +686,30 @@ impl ForceSimulation {
     }
 
     /// Find node nearest to a point within a radius
+    ///
+    /// Together with [`fix_node`](Self::fix_node) and a warm alpha target
+    /// this is all a widget needs for d3-drag style node dragging:
+    ///
+    /// ```
+    /// use makepad_d3::layout::force::{ForceSimulation, ManyBodyForce, SimulationNode};
+    ///
+    /// let nodes = vec![SimulationNode::at(0, 0.0, 0.0), SimulationNode::at(1, 50.0, 0.0)];
+    /// let mut sim = ForceSimulation::new(nodes).add_force("charge", ManyBodyForce::new());
+    ///
+    /// // Pointer down near node 1: pick it and keep the layout warm
+    /// let id = sim.find_within(48.0, 2.0, 10.0).map(|n| n.id).unwrap();
+    /// sim.set_alpha_target(0.3);
+    ///
+    /// // Pointer move: the node follows the pointer, the rest reacts
+    /// sim.fix_node(id, 80.0, 20.0);
+    /// sim.tick_n(5);
+    /// assert_eq!((sim.nodes()[1].x, sim.nodes()[1].y), (80.0, 20.0));
+    ///
+    /// // Pointer up: release it and let the layout cool down
+    /// sim.unfix_node(id);
+    /// sim.set_alpha_target(0.0);
+    /// assert!(!sim.nodes()[1].is_fixed());
+    /// ```
     pub fn find_within(&self, x: f64, y: f64, radius: f64) -> Option<&SimulationNode> {
         let mut closest: Option<&SimulationNode> = None;
         let mut closest_dist = radius * radius;
@@ -389,9 +727,42 @@ impl ForceSimulation {
         closest
     }
 
+    /// Position of the node with the given ID
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.nodes.iter().position(|n| n.id == id)
+    }
+
+    /// Pin the node with the given ID at (x, y)
+    ///
+    /// The node moves there immediately and stays until
+    /// [`unfix_node`](Self::unfix_node). Returns false if no node has the ID.
+    pub fn fix_node(&mut self, id: usize, x: f64, y: f64) -> bool {
+        let Some(node) = self.nodes.iter_mut().find(|n| n.id == id) else {
+            return false;
+        };
+        node.fix_at(x, y);
+        node.x = x;
+        node.y = y;
+        node.vx = 0.0;
+        node.vy = 0.0;
+        true
+    }
+
+    /// Release the node with the given ID; returns false if no node has the ID
+    pub fn unfix_node(&mut self, id: usize) -> bool {
+        match self.nodes.iter_mut().find(|n| n.id == id) {
+            Some(node) => {
+                node.unfix();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Reheat the simulation (useful after adding/removing nodes)
     pub fn reheat(&mut self) {
         self.alpha = 1.0;
+        self.reset_convergence();
     }
 
     /// Add a node to the simulation
@@ -415,26 +786,6 @@ impl ForceSimulation {
     }
 }
 
-/// Simple pseudo-random number generator
-struct SimpleRng {
-    state: u64,
-}
-
-impl SimpleRng {
-    fn new(seed: u64) -> Self {
-        Self { state: seed }
-    }
-
-    fn next(&mut self) -> u64 {
-        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
-        self.state
-    }
-
-    fn next_f64(&mut self) -> f64 {
-        (self.next() as f64) / (u64::MAX as f64)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -617,6 +968,39 @@ mod tests {
         assert_eq!(node0.y, 0.0);
     }
 
+    #[test]
+    fn test_force_simulation_fix_node_by_id() {
+        let nodes = vec![
+            SimulationNode::at(10, 0.0, 0.0),
+            SimulationNode::at(20, 30.0, 0.0),
+            SimulationNode::at(30, 0.0, 30.0),
+        ];
+        let mut sim = ForceSimulation::new(nodes)
+            .add_force("charge", crate::layout::force::ManyBodyForce::new())
+            .add_force("center", crate::layout::force::CenterForce::new());
+        assert_eq!(sim.index_of(20), Some(1));
+        assert!(!sim.fix_node(99, 0.0, 0.0));
+
+        // Pinned immediately, and held against every force
+        assert!(sim.fix_node(20, 200.0, -50.0));
+        assert_eq!((sim.nodes()[1].x, sim.nodes()[1].y), (200.0, -50.0));
+        sim.tick_n(20);
+        let pinned = sim.node(1).unwrap();
+        assert_eq!((pinned.x, pinned.y), (200.0, -50.0));
+        assert_eq!((pinned.vx, pinned.vy), (0.0, 0.0));
+        assert_eq!(sim.find_within(199.0, -49.0, 5.0).map(|n| n.id), Some(20));
+
+        // A node pinned directly through its fields is honored too
+        sim.node_mut(2).unwrap().fx = Some(-40.0);
+        sim.tick();
+        assert_eq!(sim.nodes()[2].x, -40.0);
+
+        assert!(sim.unfix_node(20));
+        assert!(!sim.unfix_node(99));
+        sim.tick_n(5);
+        assert_ne!((sim.nodes()[1].x, sim.nodes()[1].y), (200.0, -50.0));
+    }
+
     #[test]
     fn test_force_simulation_add_remove_node() {
         let nodes = vec![SimulationNode::new(0)];
@@ -643,4 +1027,137 @@ mod tests {
 
         assert_eq!(sim.get_alpha(), 0.5);
     }
+
+    #[test]
+    fn test_force_simulation_stats() {
+        let nodes: Vec<SimulationNode> = (0..4).map(SimulationNode::new).collect();
+        let mut sim = ForceSimulation::new(nodes)
+            .add_force("charge", crate::layout::force::ManyBodyForce::new())
+            .add_force("center", crate::layout::force::CenterForce::new());
+        sim.tick_n(5);
+
+        let stats = *sim.stats();
+        assert_eq!(stats.ticks, 5);
+        assert_eq!(stats.force_evaluations, 10);
+        assert_eq!(stats.node_updates, 20);
+        assert!(stats.max_displacement > 0.0);
+        assert!((stats.kinetic_energy - sim.kinetic_energy()).abs() < 1e-12);
+        assert_eq!(stats.converged_at, None);
+
+        sim.reset_stats();
+        assert_eq!(sim.stats().ticks, 0);
+    }
+
+    #[test]
+    fn test_force_simulation_converges_early() {
+        let nodes: Vec<SimulationNode> = (0..6).map(SimulationNode::new).collect();
+        let mut baseline = ForceSimulation::new(nodes.clone())
+            .add_force("charge", crate::layout::force::ManyBodyForce::new());
+        let full = baseline.run(1000);
+
+        let mut sim = ForceSimulation::new(nodes)
+            .add_force("charge", crate::layout::force::ManyBodyForce::new())
+            .convergence(Convergence::displacement(0.5).patience(2));
+        let early = sim.run(1000);
+
+        assert!(sim.is_converged());
+        assert!(sim.is_stable());
+        assert!(early < full);
+        assert_eq!(sim.stats().converged_at, Some(early));
+        assert!(sim.max_displacement() < 0.5);
+
+        sim.restart();
+        assert!(!sim.is_converged());
+        assert_eq!(sim.stats().converged_at, None);
+    }
+
+    #[test]
+    fn test_force_simulation_alpha_schedule() {
+        let nodes = vec![SimulationNode::new(0)];
+        let sim = ForceSimulation::new(nodes.clone()).iterations(300);
+        // d3's default decay
+        assert!((sim.get_alpha_decay() - 0.0228).abs() < 1e-4);
+
+        let mut sim = ForceSimulation::new(nodes)
+            .alpha_min(0.01)
+            .iterations(50)
+            .velocity_decay(0.3)
+            .alpha_target(0.0);
+        assert_eq!(sim.get_alpha_min(), 0.01);
+        assert_eq!(sim.get_velocity_decay(), 0.3);
+        assert_eq!(sim.get_alpha_target(), 0.0);
+        assert!((50..=51).contains(&sim.run(1000)));
+    }
+
+    #[test]
+    fn test_force_simulation_events() {
+        let nodes: Vec<SimulationNode> = (0..3).map(SimulationNode::new).collect();
+        let mut sim = ForceSimulation::new(nodes).iterations(20);
+        assert_eq!(sim.poll_event(), None);
+
+        sim.tick_n(5);
+        // Unpolled ticks collapse into the latest one
+        let events = sim.drain_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], SimulationEvent::Tick { tick: 5, .. }));
+
+        let ticks = sim.run(1000);
+        let events = sim.drain_events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1], SimulationEvent::End { tick: 5 + ticks, converged: false });
+
+        // Ticking a stable simulation does not repeat End
+        sim.tick();
+        assert!(matches!(sim.drain_events()[..], [SimulationEvent::Tick { .. }]));
+
+        sim.reheat();
+        sim.run(1000);
+        assert!(matches!(sim.drain_events().last(), Some(SimulationEvent::End { .. })));
+
+        sim.restart();
+        sim.stop();
+        sim.tick();
+        assert!(matches!(sim.drain_events()[..], [SimulationEvent::Tick { .. }]));
+    }
+
+    #[test]
+    fn test_force_simulation_alpha_target_keeps_warm() {
+        let nodes: Vec<SimulationNode> = (0..3).map(SimulationNode::new).collect();
+        let mut sim = ForceSimulation::new(nodes)
+            .convergence(Convergence::displacement(1e6).patience(1));
+        sim.tick();
+        assert!(sim.is_stable());
+
+        sim.set_alpha_target(0.3);
+        assert!(!sim.is_converged());
+        sim.tick_n(500);
+        assert!(!sim.is_stable());
+        assert!((sim.get_alpha() - 0.3).abs() < 1e-3);
+
+        sim.set_alpha_target(0.0);
+        sim.tick();
+        assert!(sim.is_stable());
+
+        sim.set_alpha(0.5);
+        assert_eq!(sim.get_alpha(), 0.5);
+        assert!(!sim.is_stable());
+        sim.stop();
+        assert!(sim.is_stable());
+    }
+
+    #[test]
+    fn test_convergence_criterion() {
+        let both = Convergence {
+            energy: Some(1.0),
+            displacement: Some(0.1),
+            patience: 1,
+        };
+        assert!(both.is_met(0.5, 0.05));
+        assert!(!both.is_met(2.0, 0.05));
+        assert!(!both.is_met(0.5, 0.2));
+        assert!(Convergence::energy(1.0).is_met(0.5, 100.0));
+
+        let none = Convergence { energy: None, displacement: None, patience: 1 };
+        assert!(!none.is_met(0.0, 0.0));
+    }
 }
@@ -0,0 +1,515 @@
+//! Graph preprocessing for network layouts
+//!
+//! Raw network data often repeats edges: one row per email, trade or
+//! transfer. [`GraphCollapse`] merges parallel edges into a single weighted
+//! edge, drops self-loops and weak edges, and returns a [`WeightedGraph`]
+//! with per-node degree and strength metrics and converters for
+//! [`LinkForce`](super::LinkForce), [`ChordLayout`](super::ChordLayout) and
+//! [`SankeyLayout`](super::SankeyLayout).
+//!
+//! For dense "hairball" graphs, [`WeightedGraph::minimum_spanning_tree`],
+//! [`WeightedGraph::maximum_spanning_tree`] and
+//! [`WeightedGraph::disparity_backbone`] reduce the edge set to its
+//! structural skeleton before layout.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::layout::graph::{EdgeAggregation, GraphCollapse};
+//!
+//! let edges = [(0, 1, 2.0), (1, 0, 3.0), (0, 1, 1.0), (1, 2, 0.5), (2, 2, 4.0)];
+//!
+//! let graph = GraphCollapse::new()
+//!     .aggregation(EdgeAggregation::Sum)
+//!     .min_weight(1.0)
+//!     .collapse(&edges)
+//!     .unwrap();
+//!
+//! // Undirected: the three 0-1 edges merge; the weak edge and the loop are dropped
+//! assert_eq!(graph.edges.len(), 1);
+//! assert_eq!(graph.edges[0].weight, 6.0);
+//! assert_eq!(graph.edges[0].count, 3);
+//! assert_eq!(graph.node_count, 3);
+//! assert_eq!(graph.metrics(2).degree, 0);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::error::{D3Error, D3Result};
+
+use super::force::SimulationLink;
+
+/// How the weights of parallel edges are combined
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeAggregation {
+    /// Total weight
+    #[default]
+    Sum,
+    /// Average weight
+    Mean,
+    /// Largest weight
+    Max,
+    /// Smallest weight
+    Min,
+    /// Number of parallel edges, ignoring weights
+    Count,
+}
+
+/// A collapsed edge
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphEdge {
+    /// Source node index (the smaller index for undirected graphs)
+    pub source: usize,
+    /// Target node index
+    pub target: usize,
+    /// Aggregated weight
+    pub weight: f64,
+    /// Number of input edges merged into this one
+    pub count: usize,
+}
+
+/// Degree and strength of a node
+///
+/// Degree counts collapsed edges; strength sums their weights. For
+/// undirected graphs the in and out values equal the totals.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NodeMetrics {
+    /// Number of incident edges
+    pub degree: usize,
+    /// Number of incoming edges
+    pub in_degree: usize,
+    /// Number of outgoing edges
+    pub out_degree: usize,
+    /// Total weight of incident edges
+    pub strength: f64,
+    /// Total weight of incoming edges
+    pub in_strength: f64,
+    /// Total weight of outgoing edges
+    pub out_strength: f64,
+}
+
+/// A graph without parallel edges
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WeightedGraph {
+    /// Number of nodes (indices `0..node_count`)
+    pub node_count: usize,
+    /// Collapsed edges in order of first appearance
+    pub edges: Vec<GraphEdge>,
+    /// Whether edge direction is significant
+    pub directed: bool,
+}
+
+impl WeightedGraph {
+    /// Graph from simulation links, weighted by link strength
+    ///
+    /// Parallel links are summed; use [`GraphCollapse`] for other rules.
+    pub fn from_links(node_count: usize, links: &[SimulationLink], directed: bool) -> Self {
+        let edges: Vec<(usize, usize, f64)> = links
+            .iter()
+            .filter(|l| l.source < node_count && l.target < node_count)
+            .map(|l| (l.source, l.target, l.strength))
+            .collect();
+        GraphCollapse::new()
+            .directed(directed)
+            .self_loops(true)
+            .node_count(node_count)
+            .collapse(&edges)
+            .unwrap_or_default()
+    }
+
+    /// Degree and strength of one node
+    pub fn metrics(&self, node: usize) -> NodeMetrics {
+        self.all_metrics().get(node).copied().unwrap_or_default()
+    }
+
+    /// Degree and strength of every node
+    pub fn all_metrics(&self) -> Vec<NodeMetrics> {
+        let mut metrics = vec![NodeMetrics::default(); self.node_count];
+        for edge in &self.edges {
+            let out = &mut metrics[edge.source];
+            out.out_degree += 1;
+            out.out_strength += edge.weight;
+            let inc = &mut metrics[edge.target];
+            inc.in_degree += 1;
+            inc.in_strength += edge.weight;
+            if !self.directed && edge.source != edge.target {
+                let out = &mut metrics[edge.target];
+                out.out_degree += 1;
+                out.out_strength += edge.weight;
+                let inc = &mut metrics[edge.source];
+                inc.in_degree += 1;
+                inc.in_strength += edge.weight;
+            }
+        }
+        for m in &mut metrics {
+            if self.directed {
+                m.degree = m.in_degree + m.out_degree;
+                m.strength = m.in_strength + m.out_strength;
+            } else {
+                m.degree = m.out_degree;
+                m.strength = m.out_strength;
+            }
+        }
+        metrics
+    }
+
+    /// Largest edge weight, 0 if there are no edges
+    pub fn max_weight(&self) -> f64 {
+        self.edges.iter().map(|e| e.weight).fold(0.0, f64::max)
+    }
+
+    /// Copy of the graph without edges lighter than `min_weight`
+    pub fn filter(&self, min_weight: f64) -> WeightedGraph {
+        self.retain(|_, e| e.weight >= min_weight)
+    }
+
+    /// Minimum spanning forest (Kruskal), treating edges as undirected
+    ///
+    /// Use when weights are distances or costs. Ties keep edge order.
+    pub fn minimum_spanning_tree(&self) -> WeightedGraph {
+        self.spanning_tree(false)
+    }
+
+    /// Maximum spanning forest, treating edges as undirected
+    ///
+    /// Use when weights are strengths or similarities: keeps the strongest
+    /// connection that links each node into its component.
+    pub fn maximum_spanning_tree(&self) -> WeightedGraph {
+        self.spanning_tree(true)
+    }
+
+    fn spanning_tree(&self, maximum: bool) -> WeightedGraph {
+        let mut order: Vec<usize> = (0..self.edges.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (wa, wb) = (self.edges[a].weight, self.edges[b].weight);
+            if maximum {
+                wb.total_cmp(&wa)
+            } else {
+                wa.total_cmp(&wb)
+            }
+        });
+
+        // Union-find with path halving
+        let mut parent: Vec<usize> = (0..self.node_count).collect();
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        let mut keep = vec![false; self.edges.len()];
+        for i in order {
+            let e = &self.edges[i];
+            let (a, b) = (find(&mut parent, e.source), find(&mut parent, e.target));
+            if a != b {
+                parent[a] = b;
+                keep[i] = true;
+            }
+        }
+        self.retain(|i, _| keep[i])
+    }
+
+    /// Backbone by the disparity filter (Serrano, Boguñá & Vespignani 2009)
+    ///
+    /// An edge is kept if its share of either endpoint's strength is
+    /// unlikely under a uniform split of that node's weight (significance
+    /// `(1 - w / s)^(k - 1) < alpha`). Lower `alpha` keeps fewer edges;
+    /// 0.05 is typical. Edges of degree-one nodes are always kept, so the
+    /// backbone does not strand leaves. Directed graphs test the source's
+    /// outgoing and the target's incoming edges.
+    pub fn disparity_backbone(&self, alpha: f64) -> WeightedGraph {
+        let metrics = self.all_metrics();
+        let significant = |weight: f64, degree: usize, strength: f64| {
+            if degree <= 1 {
+                return true;
+            }
+            if strength <= 0.0 {
+                return false;
+            }
+            (1.0 - weight / strength).powi(degree as i32 - 1) < alpha
+        };
+        self.retain(|_, e| {
+            let (s, t) = (&metrics[e.source], &metrics[e.target]);
+            significant(e.weight, s.out_degree, s.out_strength)
+                || significant(e.weight, t.in_degree, t.in_strength)
+        })
+    }
+
+    fn retain(&self, keep: impl Fn(usize, &GraphEdge) -> bool) -> WeightedGraph {
+        WeightedGraph {
+            edges: self
+                .edges
+                .iter()
+                .enumerate()
+                .filter(|&(i, e)| keep(i, e))
+                .map(|(_, e)| e.clone())
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Links for [`LinkForce`](super::LinkForce), strength proportional to weight
+    ///
+    /// The heaviest edge gets strength 1. [`LinkForce::new`](super::LinkForce::new)
+    /// keeps these strengths when any edge is lighter; when all weights are
+    /// equal, add [`per_link_strength`](super::LinkForce::per_link_strength)
+    /// to use them instead of the degree-based default.
+    pub fn to_links(&self) -> Vec<SimulationLink> {
+        let max = self.max_weight();
+        self.edges
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let strength = if max > 0.0 { e.weight / max } else { 1.0 };
+                let mut link = SimulationLink::new(e.source, e.target).with_strength(strength);
+                link.index = i;
+                link
+            })
+            .collect()
+    }
+
+    /// Square flow matrix for [`ChordLayout`](super::ChordLayout)
+    ///
+    /// Undirected edges are entered symmetrically.
+    pub fn to_matrix(&self) -> Vec<Vec<f64>> {
+        let mut matrix = vec![vec![0.0; self.node_count]; self.node_count];
+        for e in &self.edges {
+            matrix[e.source][e.target] += e.weight;
+            if !self.directed && e.source != e.target {
+                matrix[e.target][e.source] += e.weight;
+            }
+        }
+        matrix
+    }
+
+    /// `(source, target, value)` links for [`SankeyLayout`](super::SankeyLayout)
+    pub fn to_flows(&self) -> Vec<(usize, usize, f64)> {
+        self.edges.iter().map(|e| (e.source, e.target, e.weight)).collect()
+    }
+}
+
+/// Collapses parallel edges into a [`WeightedGraph`]
+#[derive(Clone, Debug)]
+pub struct GraphCollapse {
+    /// Weight combination rule
+    aggregation: EdgeAggregation,
+    /// Keep `a -> b` and `b -> a` separate
+    directed: bool,
+    /// Drop collapsed edges lighter than this
+    min_weight: Option<f64>,
+    /// Keep edges from a node to itself
+    self_loops: bool,
+    /// Fixed node count, otherwise the largest index + 1
+    node_count: Option<usize>,
+}
+
+impl Default for GraphCollapse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphCollapse {
+    /// Create an undirected collapse that sums weights and drops self-loops
+    pub fn new() -> Self {
+        Self {
+            aggregation: EdgeAggregation::Sum,
+            directed: false,
+            min_weight: None,
+            self_loops: false,
+            node_count: None,
+        }
+    }
+
+    /// Set how parallel edge weights are combined
+    pub fn aggregation(mut self, aggregation: EdgeAggregation) -> Self {
+        self.aggregation = aggregation;
+        self
+    }
+
+    /// Treat `a -> b` and `b -> a` as different edges
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = directed;
+        self
+    }
+
+    /// Drop collapsed edges whose aggregated weight is below `weight`
+    pub fn min_weight(mut self, weight: f64) -> Self {
+        self.min_weight = Some(weight);
+        self
+    }
+
+    /// Keep edges from a node to itself (dropped by default)
+    pub fn self_loops(mut self, keep: bool) -> Self {
+        self.self_loops = keep;
+        self
+    }
+
+    /// Fix the number of nodes so isolated trailing nodes are kept
+    pub fn node_count(mut self, count: usize) -> Self {
+        self.node_count = Some(count);
+        self
+    }
+
+    /// Collapse `(source, target, weight)` edges
+    ///
+    /// Returns an error for non-finite weights or, with a fixed node count,
+    /// out-of-range node indices.
+    pub fn collapse(&self, edges: &[(usize, usize, f64)]) -> D3Result<WeightedGraph> {
+        let mut node_count = self.node_count.unwrap_or(0);
+        let mut index: HashMap<(usize, usize), usize> = HashMap::new();
+        // Per collapsed edge: (source, target, running aggregate, count)
+        let mut merged: Vec<(usize, usize, f64, usize)> = Vec::new();
+
+        for &(source, target, weight) in edges {
+            if !weight.is_finite() {
+                return Err(D3Error::non_finite("edge weight", weight));
+            }
+            match self.node_count {
+                Some(n) if source >= n || target >= n => {
+                    return Err(D3Error::invalid_data(format!(
+                        "edge {} -> {} references a node outside 0..{}",
+                        source, target, n
+                    )));
+                }
+                Some(_) => {}
+                None => node_count = node_count.max(source + 1).max(target + 1),
+            }
+            if source == target && !self.self_loops {
+                continue;
+            }
+
+            let key = if self.directed || source <= target {
+                (source, target)
+            } else {
+                (target, source)
+            };
+            match index.get(&key) {
+                Some(&i) => {
+                    let entry = &mut merged[i];
+                    entry.2 = match self.aggregation {
+                        EdgeAggregation::Sum | EdgeAggregation::Mean => entry.2 + weight,
+                        EdgeAggregation::Max => entry.2.max(weight),
+                        EdgeAggregation::Min => entry.2.min(weight),
+                        EdgeAggregation::Count => entry.2 + 1.0,
+                    };
+                    entry.3 += 1;
+                }
+                None => {
+                    let initial = match self.aggregation {
+                        EdgeAggregation::Count => 1.0,
+                        _ => weight,
+                    };
+                    index.insert(key, merged.len());
+                    merged.push((key.0, key.1, initial, 1));
+                }
+            }
+        }
+
+        let edges = merged
+            .into_iter()
+            .map(|(source, target, total, count)| GraphEdge {
+                source,
+                target,
+                weight: match self.aggregation {
+                    EdgeAggregation::Mean => total / count as f64,
+                    _ => total,
+                },
+                count,
+            })
+            .filter(|e| self.min_weight.map_or(true, |min| e.weight >= min))
+            .collect();
+
+        Ok(WeightedGraph {
+            node_count,
+            edges,
+            directed: self.directed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDGES: [(usize, usize, f64); 4] = [(0, 1, 2.0), (1, 0, 4.0), (1, 2, 1.0), (0, 1, 3.0)];
+
+    #[test]
+    fn test_aggregations() {
+        let weight = |aggregation| {
+            let graph = GraphCollapse::new().aggregation(aggregation).collapse(&EDGES).unwrap();
+            graph.edges[0].weight
+        };
+        assert_eq!(weight(EdgeAggregation::Sum), 9.0);
+        assert_eq!(weight(EdgeAggregation::Mean), 3.0);
+        assert_eq!(weight(EdgeAggregation::Max), 4.0);
+        assert_eq!(weight(EdgeAggregation::Min), 2.0);
+        assert_eq!(weight(EdgeAggregation::Count), 3.0);
+
+        let directed = GraphCollapse::new().directed(true).collapse(&EDGES).unwrap();
+        assert_eq!(directed.to_flows(), vec![(0, 1, 5.0), (1, 0, 4.0), (1, 2, 1.0)]);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let graph = GraphCollapse::new().collapse(&EDGES).unwrap();
+        let hub = graph.metrics(1);
+        assert_eq!((hub.degree, hub.strength), (2, 10.0));
+        assert_eq!(graph.metrics(2).in_degree, 1);
+
+        let directed = GraphCollapse::new().directed(true).collapse(&EDGES).unwrap();
+        let m = directed.metrics(1);
+        assert_eq!((m.in_degree, m.out_degree, m.degree), (1, 2, 3));
+        assert_eq!((m.in_strength, m.out_strength), (5.0, 5.0));
+    }
+
+    #[test]
+    fn test_conversions_and_errors() {
+        let graph = GraphCollapse::new().node_count(4).collapse(&EDGES).unwrap();
+        let matrix = graph.to_matrix();
+        assert_eq!(matrix.len(), 4);
+        assert_eq!((matrix[0][1], matrix[1][0]), (9.0, 9.0));
+
+        let links = graph.to_links();
+        assert_eq!(links[0].strength, 1.0);
+        assert!((links[1].strength - 1.0 / 9.0).abs() < 1e-12);
+        assert_eq!(graph.filter(2.0).edges.len(), 1);
+
+        assert!(GraphCollapse::new().node_count(2).collapse(&EDGES).is_err());
+        assert!(GraphCollapse::new().collapse(&[(0, 1, f64::NAN)]).is_err());
+    }
+
+    #[test]
+    fn test_spanning_trees() {
+        // Square with one diagonal
+        let edges = [(0, 1, 1.0), (1, 2, 2.0), (2, 3, 1.0), (3, 0, 3.0), (0, 2, 5.0)];
+        let graph = GraphCollapse::new().collapse(&edges).unwrap();
+
+        let min = graph.minimum_spanning_tree();
+        assert_eq!(min.to_flows(), vec![(0, 1, 1.0), (1, 2, 2.0), (2, 3, 1.0)]);
+        let max = graph.maximum_spanning_tree();
+        assert_eq!(max.to_flows(), vec![(1, 2, 2.0), (0, 3, 3.0), (0, 2, 5.0)]);
+
+        // Disconnected graphs give a forest
+        let forest = GraphCollapse::new().collapse(&[(0, 1, 1.0), (2, 3, 1.0), (3, 4, 1.0), (2, 4, 1.0)]).unwrap();
+        assert_eq!(forest.minimum_spanning_tree().edges.len(), 3);
+    }
+
+    #[test]
+    fn test_disparity_backbone_from_links() {
+        // Hub 0 with one dominant edge and many weak ones
+        let mut links = vec![SimulationLink::new(0, 1).with_strength(1.0)];
+        for i in 2..12 {
+            links.push(SimulationLink::new(0, i).with_strength(0.05));
+            links.push(SimulationLink::new(1, i).with_strength(0.05));
+        }
+        let graph = WeightedGraph::from_links(12, &links, false);
+        assert_eq!(graph.edges.len(), 21);
+
+        let backbone = graph.disparity_backbone(0.05);
+        assert_eq!(backbone.to_flows(), vec![(0, 1, 1.0)]);
+        assert_eq!(graph.disparity_backbone(1.0).edges.len(), 21);
+    }
+}
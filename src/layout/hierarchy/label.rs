@@ -0,0 +1,348 @@
+//! Label fitting for treemap and icicle cells
+//!
+//! Decides, per cell, whether its label is drawn in full, abbreviated,
+//! replaced by the value, or left out, and where the text goes. The
+//! renderer only draws the resulting lines.
+
+use crate::axis::{break_opportunities, graphemes, truncate_end, wrap_text, wrap_text_clamped, NumberFormat, TextMeasurer};
+
+use super::node::HierarchyNode;
+use super::partition::PartitionNode;
+
+/// How much of a label fits in its cell, best first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LabelFit {
+    /// The whole label, possibly wrapped over several lines
+    Full,
+    /// The label cut short with an ellipsis
+    Abbreviated,
+    /// Only the formatted value
+    ValueOnly,
+    /// Nothing is drawn
+    Hidden,
+}
+
+/// Placement decision for one cell's label
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellLabel {
+    /// What was kept
+    pub fit: LabelFit,
+    /// Lines to draw, top to bottom; empty when hidden
+    pub lines: Vec<String>,
+    /// Text origin x
+    pub x: f64,
+    /// Text origin y
+    pub y: f64,
+    /// Whether the text runs bottom to top
+    ///
+    /// Horizontal text starts at the top-left of the padded cell. Rotated
+    /// text starts at its bottom-left, which is the pivot for a -90°
+    /// rotation.
+    pub rotated: bool,
+    /// Font size to draw with
+    pub font_size: f64,
+    /// Distance between line tops
+    pub line_height: f64,
+}
+
+impl CellLabel {
+    fn hidden() -> Self {
+        Self {
+            fit: LabelFit::Hidden,
+            lines: Vec::new(),
+            x: 0.0,
+            y: 0.0,
+            rotated: false,
+            font_size: 0.0,
+            line_height: 0.0,
+        }
+    }
+
+    /// Whether anything is drawn
+    pub fn is_visible(&self) -> bool {
+        self.fit != LabelFit::Hidden
+    }
+}
+
+/// Label fitting policy for rectangular hierarchy cells
+///
+/// # Example
+/// ```
+/// use makepad_d3::axis::ApproxTextMeasurer;
+/// use makepad_d3::layout::hierarchy::{CellLabeler, LabelFit};
+///
+/// let m = ApproxTextMeasurer::default();
+/// let labeler = CellLabeler::new().font_size(10.0).padding(2.0);
+///
+/// // 6 px per character at 10 px
+/// assert_eq!(labeler.fit("Storage", 1200.0, 0.0, 0.0, 60.0, 20.0, &m).fit, LabelFit::Full);
+/// assert_eq!(labeler.fit("Storage", 1200.0, 0.0, 0.0, 34.0, 20.0, &m).lines, vec!["Stor…"]);
+/// assert_eq!(labeler.fit("Storage", 1200.0, 0.0, 0.0, 8.0, 8.0, &m).fit, LabelFit::Hidden);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CellLabeler {
+    /// Font size of the labels
+    pub font_size: f64,
+    /// Inset from every cell edge
+    pub padding: f64,
+    /// Most lines a wrapped label may take
+    pub max_lines: usize,
+    /// Fewest label characters worth showing before falling back to the value
+    pub min_chars: usize,
+    /// Whether tall, thin cells may take rotated labels
+    pub rotate: bool,
+    /// Whether a fully shown label gets the value on an extra line when there is room
+    pub show_value: bool,
+    /// Height of the header band that parent cells are labelled in
+    ///
+    /// Usually the treemap's top padding. Zero hides parent labels.
+    pub header_height: f64,
+    /// Value formatting
+    pub value_format: NumberFormat,
+}
+
+impl Default for CellLabeler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CellLabeler {
+    /// Create a labeler with 11 px text and 4 px padding
+    pub fn new() -> Self {
+        Self {
+            font_size: 11.0,
+            padding: 4.0,
+            max_lines: 3,
+            min_chars: 3,
+            rotate: false,
+            show_value: false,
+            header_height: 0.0,
+            value_format: NumberFormat::SI,
+        }
+    }
+
+    /// Set the font size
+    pub fn font_size(mut self, size: f64) -> Self {
+        self.font_size = size.max(0.0);
+        self
+    }
+
+    /// Set the inset from every cell edge
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding.max(0.0);
+        self
+    }
+
+    /// Set the most lines a label may wrap onto
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = lines.max(1);
+        self
+    }
+
+    /// Set the fewest label characters worth showing abbreviated
+    pub fn min_chars(mut self, chars: usize) -> Self {
+        self.min_chars = chars;
+        self
+    }
+
+    /// Allow rotated labels in cells taller than they are wide
+    pub fn rotate(mut self, rotate: bool) -> Self {
+        self.rotate = rotate;
+        self
+    }
+
+    /// Add the value under fully shown labels when there is room
+    pub fn show_value(mut self, show: bool) -> Self {
+        self.show_value = show;
+        self
+    }
+
+    /// Label parent cells in a header band of this height
+    pub fn header_height(mut self, height: f64) -> Self {
+        self.header_height = height.max(0.0);
+        self
+    }
+
+    /// Set the value format
+    pub fn value_format(mut self, format: NumberFormat) -> Self {
+        self.value_format = format;
+        self
+    }
+
+    /// Fit a label into the cell at (x, y) with the given size
+    #[allow(clippy::too_many_arguments)]
+    pub fn fit(
+        &self,
+        label: &str,
+        value: f64,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        measurer: &dyn TextMeasurer,
+    ) -> CellLabel {
+        let (w, h) = (width - 2.0 * self.padding, height - 2.0 * self.padding);
+        let value = self.value_format.format(value);
+
+        let mut best = self.fit_box(label, &value, w, h, measurer).map(|(fit, lines)| (fit, lines, false));
+        let tall = height > width;
+        if self.rotate && tall && best.as_ref().map_or(true, |b| b.0 != LabelFit::Full) {
+            if let Some((fit, lines)) = self.fit_box(label, &value, h, w, measurer) {
+                if best.as_ref().map_or(true, |b| fit < b.0) {
+                    best = Some((fit, lines, true));
+                }
+            }
+        }
+
+        match best {
+            Some((fit, lines, rotated)) => CellLabel {
+                fit,
+                lines,
+                x: x + self.padding,
+                y: if rotated { y + height - self.padding } else { y + self.padding },
+                rotated,
+                font_size: self.font_size,
+                line_height: measurer.line_height(self.font_size),
+            },
+            None => CellLabel::hidden(),
+        }
+    }
+
+    /// Labels for every node of a positioned treemap, in pre-order
+    ///
+    /// Leaves are fitted into their whole cell; parents into the header
+    /// band along their top edge.
+    pub fn label_treemap<T: ToString>(&self, root: &HierarchyNode<T>, measurer: &dyn TextMeasurer) -> Vec<CellLabel> {
+        root.iter()
+            .map(|node| {
+                let height = if node.is_leaf() {
+                    node.rect_height
+                } else if self.header_height > 0.0 {
+                    self.header_height.min(node.rect_height)
+                } else {
+                    return CellLabel::hidden();
+                };
+                let label = node.data.to_string();
+                self.fit(&label, node.value, node.x, node.y, node.width, height, measurer)
+            })
+            .collect()
+    }
+
+    /// Labels for every node of a rectangular (icicle) partition, in pre-order
+    pub fn label_partition<T: Clone>(&self, root: &PartitionNode<T>, measurer: &dyn TextMeasurer) -> Vec<CellLabel> {
+        root.iter()
+            .map(|node| {
+                self.fit(&node.name, node.value, node.x0, node.y0, node.x1 - node.x0, node.y1 - node.y0, measurer)
+            })
+            .collect()
+    }
+
+    /// Best fit inside a `w` × `h` text box, reading along `w`
+    fn fit_box(&self, label: &str, value: &str, w: f64, h: f64, measurer: &dyn TextMeasurer) -> Option<(LabelFit, Vec<String>)> {
+        let line_height = measurer.line_height(self.font_size);
+        if w <= 0.0 || h < line_height {
+            return None;
+        }
+        let room = ((h / line_height).floor() as usize).min(self.max_lines);
+        let fits = |line: &str| measurer.measure(line, self.font_size) <= w;
+        let value_only = || fits(value).then(|| (LabelFit::ValueOnly, vec![value.to_string()]));
+        if label.trim().is_empty() {
+            return value_only();
+        }
+
+        // Splitting a word across lines reads worse than an abbreviation
+        let words_fit = label.split('\n').flat_map(break_opportunities).all(|(word, _)| fits(word));
+        let mut lines = wrap_text(label, w, self.font_size, measurer);
+        if words_fit && lines.len() <= room {
+            if self.show_value && lines.len() < room && fits(value) {
+                lines.push(value.to_string());
+            }
+            return Some((LabelFit::Full, lines));
+        }
+
+        let lines = if room > 1 && words_fit {
+            wrap_text_clamped(label, w, room, self.font_size, measurer)
+        } else {
+            vec![truncate_end(label, w, self.font_size, measurer)]
+        };
+        // Count what is left of the label, not the ellipsis
+        let kept: usize = lines.iter().map(|l| graphemes(l).len()).sum::<usize>().saturating_sub(1);
+        if kept >= self.min_chars && lines.iter().all(|l| fits(l)) {
+            return Some((LabelFit::Abbreviated, lines));
+        }
+
+        value_only()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::ApproxTextMeasurer;
+    use crate::layout::hierarchy::{PartitionLayout, TreemapLayout};
+
+    fn labeler() -> CellLabeler {
+        // 6 px per character, 12 px lines
+        CellLabeler::new().font_size(10.0).padding(2.0).value_format(NumberFormat::Fixed(0))
+    }
+
+    #[test]
+    fn test_fit_rotates_tall_cells() {
+        let m = ApproxTextMeasurer::default();
+        let upright = labeler().fit("Storage", 1200.0, 10.0, 0.0, 20.0, 100.0, &m);
+        assert_eq!(upright.fit, LabelFit::Hidden);
+
+        let label = labeler().rotate(true).fit("Storage", 1200.0, 10.0, 0.0, 20.0, 100.0, &m);
+        assert_eq!(label.fit, LabelFit::Full);
+        assert!(label.rotated);
+        assert_eq!((label.x, label.y), (12.0, 98.0));
+    }
+
+    #[test]
+    fn test_fit_value_fallbacks() {
+        let m = ApproxTextMeasurer::default();
+        let full = labeler().show_value(true).fit("Storage", 1200.0, 0.0, 0.0, 60.0, 40.0, &m);
+        assert_eq!(full.lines, vec!["Storage", "1200"]);
+
+        // Three characters survive the ellipsis, which is just enough
+        let short = labeler().fit("Internationalization", 1200.0, 0.0, 0.0, 30.0, 20.0, &m);
+        assert_eq!((short.fit, short.lines), (LabelFit::Abbreviated, vec!["Int…".to_string()]));
+        let value = labeler().min_chars(5).fit("Internationalization", 1200.0, 0.0, 0.0, 30.0, 20.0, &m);
+        assert_eq!((value.fit, value.lines), (LabelFit::ValueOnly, vec!["1200".to_string()]));
+    }
+
+    #[test]
+    fn test_fit_wraps_before_abbreviating() {
+        let m = ApproxTextMeasurer::default();
+        let wrapped = labeler().fit("Cold storage tier", 1.0, 0.0, 0.0, 84.0, 40.0, &m);
+        assert_eq!((wrapped.fit, wrapped.lines.len()), (LabelFit::Full, 2));
+
+        let clamped = labeler().max_lines(1).fit("Cold storage tier", 1.0, 0.0, 0.0, 84.0, 40.0, &m);
+        assert_eq!(clamped.fit, LabelFit::Abbreviated);
+        assert_eq!(clamped.lines.len(), 1);
+    }
+
+    #[test]
+    fn test_label_treemap_and_partition() {
+        let m = ApproxTextMeasurer::default();
+        let mut root = HierarchyNode::from_label("root", 0.0);
+        root.add_child(HierarchyNode::from_label("a", 6.0));
+        root.add_child(HierarchyNode::from_label("b", 4.0));
+
+        let treemap = TreemapLayout::new().size(100.0, 50.0).padding_top(16.0).layout(&root);
+        let labels = labeler().label_treemap(&treemap, &m);
+        assert_eq!(labels.len(), 3);
+        assert!(!labels[0].is_visible());
+        assert!(labels[1..].iter().all(|l| l.fit == LabelFit::Full));
+
+        let labels = labeler().header_height(16.0).label_treemap(&treemap, &m);
+        assert_eq!((labels[0].lines.clone(), labels[0].y), (vec!["root".to_string()], 2.0));
+
+        let icicle = PartitionLayout::new().size(100.0, 60.0).layout(&root);
+        let labels = labeler().label_partition(&icicle, &m);
+        assert_eq!(labels.len(), 3);
+        assert!(labels.iter().all(|l| l.fit == LabelFit::Full));
+    }
+}
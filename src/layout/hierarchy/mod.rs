@@ -1,6 +1,7 @@
 //! Hierarchical layout algorithms
 //!
-//! Layouts for tree-structured data including trees, treemaps, and circle packing.
+//! Layouts for tree-structured data including trees, dendrograms, treemaps,
+//! and circle packing.
 //!
 //! # Example
 //!
@@ -22,9 +23,13 @@ mod tree;
 mod treemap;
 mod pack;
 mod partition;
+mod stratify;
+mod label;
 
 pub use node::HierarchyNode;
-pub use tree::TreeLayout;
+pub use tree::{point_radial, ClusterLayout, TreeLayout};
 pub use treemap::{TreemapLayout, TilingMethod};
 pub use pack::{PackLayout, PackStrategy};
 pub use partition::{PartitionLayout, PartitionNode};
+pub use stratify::Stratify;
+pub use label::{CellLabel, CellLabeler, LabelFit};
@@ -119,6 +119,13 @@ impl<T> HierarchyNode<T> {
         self.parent.is_none() && self.depth == 0
     }
 
+    /// Cartesian position of a node from a radial tree or cluster layout
+    ///
+    /// Reads x as the angle and y as the radius, relative to the center.
+    pub fn radial_point(&self) -> (f64, f64) {
+        super::tree::point_radial(self.x, self.y)
+    }
+
     /// Get the number of children
     pub fn child_count(&self) -> usize {
         self.children.len()
@@ -150,11 +157,47 @@ impl<T> HierarchyNode<T> {
         }
     }
 
+    /// Sum values with an accessor, like d3's `node.sum(value)`
+    ///
+    /// Each node's value becomes `value(&data)` plus the values of its
+    /// descendants, so internal nodes may carry weight of their own.
+    pub fn sum_by<F>(&mut self, value: F) -> f64
+    where
+        F: Fn(&T) -> f64,
+    {
+        self.sum_by_ref(&value)
+    }
+
+    fn sum_by_ref(&mut self, value: &dyn Fn(&T) -> f64) -> f64 {
+        let own = value(&self.data);
+        self.value = own + self.children.iter_mut().map(|c| c.sum_by_ref(value)).sum::<f64>();
+        self.value
+    }
+
+    /// Set every node's value to its number of leaves, like d3's `node.count()`
+    ///
+    /// Sizes treemaps and packs by leaf count. [`count`](Self::count) still
+    /// returns the number of nodes.
+    pub fn sum_count(&mut self) -> f64 {
+        self.value = if self.is_leaf() {
+            1.0
+        } else {
+            self.children.iter_mut().map(|c| c.sum_count()).sum()
+        };
+        self.value
+    }
+
     /// Calculate depth and height for all nodes
-    pub fn each_before(&mut self) {
+    pub fn update_depth_height(&mut self) {
         self.compute_depth_height(0);
     }
 
+    /// Calculate depth and height for all nodes
+    #[deprecated(note = "use `update_depth_height`; for d3's pre-order `eachBefore` use `each_before_visit`")]
+    pub fn each_before(&mut self) {
+        self.update_depth_height();
+    }
+
     /// Internal: compute depth and height recursively
     fn compute_depth_height(&mut self, depth: usize) -> usize {
         self.depth = depth;
@@ -181,6 +224,21 @@ impl<T> HierarchyNode<T> {
         }
     }
 
+    /// Sort the children of every node with a comparator, like d3's `node.sort`
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: Fn(&HierarchyNode<T>, &HierarchyNode<T>) -> std::cmp::Ordering,
+    {
+        self.sort_by_ref(&compare);
+    }
+
+    fn sort_by_ref(&mut self, compare: &dyn Fn(&HierarchyNode<T>, &HierarchyNode<T>) -> std::cmp::Ordering) {
+        self.children.sort_by(compare);
+        for child in &mut self.children {
+            child.sort_by_ref(compare);
+        }
+    }
+
     /// Sort children by height (ascending)
     pub fn sort_by_height(&mut self) {
         self.children.sort_by_key(|c| c.height);
@@ -201,6 +259,76 @@ impl<T> HierarchyNode<T> {
         self.iter().filter(|n| n.is_leaf()).collect()
     }
 
+    /// Visit every node breadth-first, like d3's `node.each`
+    pub fn each<'a, F: FnMut(&'a HierarchyNode<T>)>(&'a self, mut f: F) {
+        let mut queue = std::collections::VecDeque::from([self]);
+        while let Some(node) = queue.pop_front() {
+            f(node);
+            queue.extend(&node.children);
+        }
+    }
+
+    /// Visit every node in pre-order, parents before children
+    ///
+    /// This is d3's `node.eachBefore`; the `_visit` suffix keeps it apart from
+    /// the deprecated [`each_before`](Self::each_before), which computed
+    /// depth and height.
+    pub fn each_before_visit<'a, F: FnMut(&'a HierarchyNode<T>)>(&'a self, mut f: F) {
+        for node in self.iter() {
+            f(node);
+        }
+    }
+
+    /// Visit every node in post-order, children before parents
+    pub fn each_after<'a, F: FnMut(&'a HierarchyNode<T>)>(&'a self, mut f: F) {
+        fn visit<'a, T>(node: &'a HierarchyNode<T>, f: &mut dyn FnMut(&'a HierarchyNode<T>)) {
+            for child in &node.children {
+                visit(child, f);
+            }
+            f(node);
+        }
+        visit(self, &mut f);
+    }
+
+    /// This node and all its descendants, breadth-first like d3
+    pub fn descendants(&self) -> Vec<&HierarchyNode<T>> {
+        let mut nodes = Vec::with_capacity(self.count());
+        self.each(|n| nodes.push(n));
+        nodes
+    }
+
+    /// The first node (pre-order) matching `target` and its ancestors, ending at this node
+    ///
+    /// Nodes don't point to their parents, so the path is looked up from
+    /// the root. Empty if nothing matches.
+    pub fn ancestors<F>(&self, target: F) -> Vec<&HierarchyNode<T>>
+    where
+        F: Fn(&HierarchyNode<T>) -> bool,
+    {
+        fn find<'a, T>(
+            node: &'a HierarchyNode<T>,
+            target: &dyn Fn(&HierarchyNode<T>) -> bool,
+            path: &mut Vec<&'a HierarchyNode<T>>,
+        ) -> bool {
+            if target(node) || node.children.iter().any(|c| find(c, target, path)) {
+                path.push(node);
+                true
+            } else {
+                false
+            }
+        }
+        let mut path = Vec::new();
+        find(self, &target, &mut path);
+        path
+    }
+
+    /// Parent-child pairs as (source, target), breadth-first like d3's `node.links`
+    pub fn links(&self) -> Vec<(&HierarchyNode<T>, &HierarchyNode<T>)> {
+        let mut links = Vec::new();
+        self.each(|parent| links.extend(parent.children.iter().map(|c| (parent, c))));
+        links
+    }
+
     /// Get ancestors from this node to root
     pub fn path_to_root<'a>(&self, all_nodes: &'a [HierarchyNode<T>]) -> Vec<&'a HierarchyNode<T>> {
         let mut path = Vec::new();
@@ -378,7 +506,7 @@ mod tests {
     #[test]
     fn test_hierarchy_node_depth_height() {
         let mut tree = make_tree();
-        tree.each_before();
+        tree.update_depth_height();
 
         assert_eq!(tree.depth, 0);
         assert_eq!(tree.height, 2);
@@ -408,6 +536,47 @@ mod tests {
         assert_eq!(nodes[0].data, "root");
     }
 
+    #[test]
+    fn test_hierarchy_node_traversal_orders() {
+        let tree = make_tree();
+        let names = |nodes: Vec<&HierarchyNode<String>>| nodes.iter().map(|n| n.data.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names(tree.descendants()), ["root", "child1", "child2", "leaf1", "leaf2"]);
+        let mut before = Vec::new();
+        tree.each_before_visit(|n| before.push(n));
+        assert_eq!(names(before), ["root", "child1", "leaf1", "leaf2", "child2"]);
+        let mut after = Vec::new();
+        tree.each_after(|n| after.push(n));
+        assert_eq!(names(after), ["leaf1", "leaf2", "child1", "child2", "root"]);
+
+        assert_eq!(names(tree.ancestors(|n| n.data == "leaf2")), ["leaf2", "child1", "root"]);
+        assert!(tree.ancestors(|n| n.data == "missing").is_empty());
+
+        let links = tree.links();
+        assert_eq!(links.len(), 4);
+        assert_eq!((links[2].0.data.as_str(), links[2].1.data.as_str()), ("child1", "leaf1"));
+    }
+
+    #[test]
+    fn test_hierarchy_node_sum_by_count_and_sort() {
+        let mut tree = make_tree();
+        let own = |d: &String| match d.as_str() {
+            "root" => 5.0,
+            "leaf1" | "leaf2" => 1.0,
+            _ => 0.0,
+        };
+        assert_eq!(tree.sum_by(own), 7.0);
+        assert_eq!(tree.children[0].value, 2.0);
+
+        assert_eq!(tree.sum_count(), 3.0);
+        assert_eq!(tree.children[0].value, 2.0);
+        assert_eq!(tree.children[1].value, 1.0);
+
+        tree.sort_by(|a, b| b.data.cmp(&a.data));
+        assert_eq!(tree.children[0].data, "child2");
+        assert_eq!(tree.children[1].children[0].data, "leaf2");
+    }
+
     #[test]
     fn test_hierarchy_node_leaves() {
         let tree = make_tree();
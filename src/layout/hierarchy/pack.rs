@@ -93,7 +93,7 @@ impl PackLayout {
 
         // Sum values and compute depths
         tree.sum();
-        tree.each_before();
+        tree.update_depth_height();
 
         // Compute radii for all nodes
         self.compute_radii(&mut tree);
@@ -6,8 +6,15 @@
 //! For each node, computes:
 //! - x0, x1: Angular extent (for sunburst) or horizontal position (for icicle)
 //! - y0, y1: Radial extent (for sunburst) or vertical position (for icicle)
+//!
+//! [`PartitionLayout::radial`] sets up a sunburst, after which
+//! [`PartitionNode::arc`] yields each node's angles and radii as an
+//! [`ArcDatum`] ready for drawing.
+
+use std::f64::consts::TAU;
 
 use super::HierarchyNode;
+use crate::shape::ArcDatum;
 
 /// A positioned node from the partition layout
 #[derive(Clone, Debug)]
@@ -47,6 +54,18 @@ impl<T: Clone> PartitionNode<T> {
         PartitionIter { stack: vec![self] }
     }
 
+    /// Angles and radii of the node for radial layouts
+    ///
+    /// Maps x0/x1 to start/end angle and y0/y1 to inner/outer radius, using
+    /// the same angle convention as [`ArcGenerator`](crate::shape::ArcGenerator).
+    pub fn arc(&self) -> ArcDatum {
+        ArcDatum {
+            inner_radius: self.y0,
+            outer_radius: self.y1,
+            ..ArcDatum::new(self.x0, self.x1)
+        }
+    }
+
     /// Get all nodes as a flat vector (excluding root if specified)
     pub fn descendants(&self, include_root: bool) -> Vec<&PartitionNode<T>> {
         let mut result: Vec<_> = self.iter().collect();
@@ -85,21 +104,23 @@ impl<'a, T> Iterator for PartitionIter<'a, T> {
 ///
 /// # Example
 ///
-/// ```ignore
+/// ```
 /// use makepad_d3::layout::hierarchy::{HierarchyNode, PartitionLayout};
-/// use std::f64::consts::PI;
 ///
 /// let mut root = HierarchyNode::new("root".to_string(), 0.0);
 /// root.add_child(HierarchyNode::new("a".to_string(), 10.0));
 /// root.add_child(HierarchyNode::new("b".to_string(), 20.0));
 ///
-/// let layout = PartitionLayout::new()
-///     .size(2.0 * PI, 400.0);  // angles 0 to 2π, radius 0 to 400
+/// // Sunburst: angles 0 to 2π, radius 0 to 400
+/// let result = PartitionLayout::new().radial(400.0).layout(&root);
 ///
-/// let result = layout.layout(&root);
-/// // result.children[0].x0, x1 = angle range for "a"
-/// // result.children[0].y0, y1 = radius range for "a"
+/// for node in result.descendants(false) {
+///     let arc = node.arc();
+///     assert!(arc.end_angle > arc.start_angle);
+///     assert!(arc.outer_radius > arc.inner_radius);
+/// }
 /// ```
+#[derive(Clone, Debug)]
 pub struct PartitionLayout {
     /// Size in x dimension (2π for full circle sunburst)
     pub x_size: f64,
@@ -138,13 +159,24 @@ impl PartitionLayout {
         self
     }
 
+    /// Lay out a sunburst of the given radius
+    ///
+    /// Shorthand for `size(2π, radius)`: x0/x1 become angles and y0/y1
+    /// radii, see [`PartitionNode::arc`].
+    pub fn radial(self, radius: f64) -> Self {
+        self.size(TAU, radius)
+    }
+
     /// Set padding between siblings
+    ///
+    /// Trimmed from the far x and y edge of every node, in layout units
+    /// (radians and radius units for radial layouts), as in d3.
     pub fn padding(mut self, padding: f64) -> Self {
         self.padding = padding;
         self
     }
 
-    /// Enable coordinate rounding
+    /// Enable rounding coordinates to whole layout units
     pub fn round(mut self, round: bool) -> Self {
         self.round = round;
         self
@@ -155,7 +187,7 @@ impl PartitionLayout {
         // First, sum values and compute depth/height
         let mut tree = root.clone();
         tree.sum();
-        tree.each_before();
+        tree.update_depth_height();
         tree.sort_by_value();
 
         let max_depth = self.find_max_depth(&tree);
@@ -168,8 +200,8 @@ impl PartitionLayout {
             self.y_size
         };
 
-        // Layout recursively
-        self.layout_node(&tree, 0.0, self.x_size, 0, y_per_depth, total_value, 0)
+        // Layout recursively; the root starts inset by the padding, as in d3
+        self.layout_node(&tree, self.padding, self.x_size, 0, y_per_depth, total_value, 0)
     }
 
     fn find_max_depth<T>(&self, node: &HierarchyNode<T>) -> usize {
@@ -190,8 +222,8 @@ impl PartitionLayout {
         parent_value: f64,
         color_index: usize,
     ) -> PartitionNode<T> {
-        let y0 = depth as f64 * y_per_depth;
-        let y1 = y0 + y_per_depth;
+        let y0 = if depth == 0 { self.padding } else { depth as f64 * y_per_depth };
+        let y1 = (depth + 1) as f64 * y_per_depth;
 
         // Layout children
         let mut children = Vec::new();
@@ -229,13 +261,18 @@ impl PartitionLayout {
             }
         }
 
+        // Children are diced over the unpadded extent; then trim this node
+        let (x0, x1) = self.pad(x0, x1);
+        let (y0, y1) = self.pad(y0, y1);
+        let r = |v: f64| if self.round { v.round() } else { v };
+
         PartitionNode {
             data: node.data.clone(),
             value: node.value,
-            x0,
-            x1,
-            y0,
-            y1,
+            x0: r(x0),
+            x1: r(x1),
+            y0: r(y0),
+            y1: r(y1),
             depth,
             height: node.height,
             children,
@@ -243,6 +280,17 @@ impl PartitionLayout {
             name: node.data.to_string(),
         }
     }
+
+    /// Trim the padding from the far edge, collapsing to the midpoint if too small
+    fn pad(&self, v0: f64, v1: f64) -> (f64, f64) {
+        let v1p = v1 - self.padding;
+        if v1p < v0 {
+            let mid = (v0 + v1p) / 2.0;
+            (mid, mid)
+        } else {
+            (v0, v1p)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -274,6 +322,40 @@ mod tests {
         assert!((a.x1 - a.x0 - 2.0 * PI * 1.0 / 3.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_partition_padding_and_round() {
+        let mut root = HierarchyNode::new("root".to_string(), 0.0);
+        root.add_child(HierarchyNode::new("a".to_string(), 10.0));
+        root.add_child(HierarchyNode::new("b".to_string(), 30.0));
+
+        let result = PartitionLayout::new().size(100.0, 20.0).padding(2.0).layout(&root);
+
+        // Root inset by the padding on all sides
+        assert_eq!((result.x0, result.x1, result.y0, result.y1), (2.0, 98.0, 2.0, 8.0));
+        // Siblings split [2, 100] and each loses the padding on its far edge
+        let (b, a) = (&result.children[0], &result.children[1]);
+        assert!((b.x0 - 2.0).abs() < 1e-9 && (b.x1 - 73.5).abs() < 1e-9);
+        assert!((a.x0 - 75.5).abs() < 1e-9 && (a.x1 - 98.0).abs() < 1e-9);
+        assert!((a.y0 - 10.0).abs() < 1e-9 && (a.y1 - 18.0).abs() < 1e-9);
+
+        let rounded = PartitionLayout::new().size(100.0, 20.0).padding(2.0).round(true).layout(&root);
+        assert_eq!(rounded.children[0].x1, 74.0);
+    }
+
+    #[test]
+    fn test_partition_radial_arcs() {
+        let mut root = HierarchyNode::new("root".to_string(), 0.0);
+        root.add_child(HierarchyNode::new("a".to_string(), 1.0));
+        root.add_child(HierarchyNode::new("b".to_string(), 1.0));
+
+        let result = PartitionLayout::new().radial(300.0).layout(&root);
+        let arc = result.children[1].arc();
+        assert!((arc.start_angle - PI).abs() < 1e-9);
+        assert!((arc.end_angle - 2.0 * PI).abs() < 1e-9);
+        assert_eq!((arc.inner_radius, arc.outer_radius), (150.0, 300.0));
+        assert!(!arc.generate().is_empty());
+    }
+
     #[test]
     fn test_partition_depth() {
         let mut root = HierarchyNode::new("root".to_string(), 0.0);
@@ -0,0 +1,431 @@
+//! Build a hierarchy from flat (id, parent id) rows
+//!
+//! Equivalent to D3's `d3.stratify()`: tabular data where each row names its
+//! parent is turned into a [`HierarchyNode`] tree. Malformed input is
+//! reported as a [`D3Error`] instead of producing a partial tree.
+
+use std::collections::{HashMap, HashSet};
+
+use super::HierarchyNode;
+use crate::error::{D3Error, D3Result};
+
+/// Builder turning parent-linked rows into a hierarchy
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::layout::hierarchy::Stratify;
+///
+/// let root = Stratify::new()
+///     .row("Company", None, 0.0)
+///     .row("Sales", Some("Company"), 40.0)
+///     .row("Engineering", Some("Company"), 0.0)
+///     .row("Backend", Some("Engineering"), 120.0)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(root.data, "Company");
+/// assert_eq!(root.count(), 4);
+/// assert_eq!(root.height, 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Stratify {
+    rows: Vec<(String, Option<String>, f64)>,
+}
+
+impl Stratify {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create from (id, parent id, value) rows
+    pub fn from_rows<I, S>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = (S, Option<S>, f64)>,
+        S: Into<String>,
+    {
+        Self {
+            rows: rows
+                .into_iter()
+                .map(|(id, parent, value)| (id.into(), parent.map(Into::into), value))
+                .collect(),
+        }
+    }
+
+    /// Add a row; a `None` parent marks the root
+    pub fn row(mut self, id: impl Into<String>, parent: Option<&str>, value: f64) -> Self {
+        self.rows.push((id.into(), parent.map(str::to_string), value));
+        self
+    }
+
+    /// Build the hierarchy
+    ///
+    /// Children keep row order. Depth and height are computed.
+    ///
+    /// # Errors
+    ///
+    /// - [`D3Error::InvalidData`] for no rows, a duplicate id, an unknown
+    ///   parent, or more than one root
+    /// - [`D3Error::NonFiniteValue`] for a NaN or infinite value
+    /// - [`D3Error::CycleDetected`] if parent links loop
+    pub fn build(&self) -> D3Result<HierarchyNode<String>> {
+        for (id, _, value) in &self.rows {
+            if !value.is_finite() {
+                return Err(D3Error::non_finite(format!("stratify value of '{}'", id), *value));
+            }
+        }
+        let ids: Vec<&str> = self.rows.iter().map(|r| r.0.as_str()).collect();
+        let parents: Vec<Option<&str>> = self.rows.iter().map(|r| r.1.as_deref()).collect();
+        let (root, children) = link(&ids, &parents)?;
+
+        let mut rows: Vec<Option<(String, f64)>> = self.rows.iter().map(|(id, _, v)| Some((id.clone(), *v))).collect();
+        let mut root = assemble(root, &children, &mut |i| {
+            let (id, value) = rows[i].take().unwrap_or_default();
+            HierarchyNode::new(id, value)
+        });
+        root.update_depth_height();
+        Ok(root)
+    }
+}
+
+impl<T> HierarchyNode<T> {
+    /// Build a hierarchy from rows that name their own id and their parent's
+    ///
+    /// The generic form of [`Stratify`], like d3's
+    /// `d3.stratify().id(id).parentId(parent_id)(rows)`. A row whose parent
+    /// id is `None` or empty is the root. Each row becomes a node's data;
+    /// values start at zero, so follow with [`sum_by`](Self::sum_by) or
+    /// [`sum_count`](Self::sum_count) before sizing layouts.
+    ///
+    /// # Errors
+    ///
+    /// As [`Stratify::build`]: no rows, a duplicate id, an unknown parent,
+    /// more than one root, or a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use makepad_d3::layout::hierarchy::HierarchyNode;
+    ///
+    /// let rows = vec![("eve", ""), ("cain", "eve"), ("abel", "eve"), ("enoch", "cain")];
+    /// let root = HierarchyNode::stratify(rows, |r| r.0.to_string(), |r| Some(r.1.to_string())).unwrap();
+    ///
+    /// assert_eq!(root.data.0, "eve");
+    /// assert_eq!(root.children[0].children[0].data.0, "enoch");
+    /// ```
+    pub fn stratify<I, F, P>(rows: I, id: F, parent_id: P) -> D3Result<Self>
+    where
+        I: IntoIterator<Item = T>,
+        F: Fn(&T) -> String,
+        P: Fn(&T) -> Option<String>,
+    {
+        let mut rows: Vec<Option<T>> = rows.into_iter().map(Some).collect();
+        let ids: Vec<String> = rows.iter().flatten().map(&id).collect();
+        let parents: Vec<Option<String>> = rows
+            .iter()
+            .flatten()
+            .map(|row| parent_id(row).filter(|p| !p.is_empty()))
+            .collect();
+        let id_refs: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let parent_refs: Vec<Option<&str>> = parents.iter().map(|p| p.as_deref()).collect();
+        let (root, children) = link(&id_refs, &parent_refs)?;
+
+        let mut root = assemble(root, &children, &mut |i| {
+            // Each index is visited once, so every row is still there
+            let data = rows[i].take().expect("stratify: row visited twice");
+            HierarchyNode::new(data, 0.0)
+        });
+        root.update_depth_height();
+        Ok(root)
+    }
+}
+
+impl HierarchyNode<String> {
+    /// Build a hierarchy from delimited paths such as `"flare/vis/Axis"`
+    ///
+    /// Like d3's `stratify().path()`: every path is a node, and missing
+    /// intermediate paths are created as internal nodes. Each node's data is
+    /// its last path segment; values start at zero. Leading and trailing
+    /// delimiters are ignored. If the paths don't share a single top-level
+    /// segment, an unnamed root (`""`) is added above them.
+    ///
+    /// # Errors
+    ///
+    /// [`D3Error::InvalidData`] for no paths, an empty path, or a path
+    /// listed twice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use makepad_d3::layout::hierarchy::HierarchyNode;
+    ///
+    /// let root = HierarchyNode::from_path_strings(
+    ///     ["flare/vis/Axis", "flare/vis/Legend", "flare/util/Stats"],
+    ///     '/',
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(root.data, "flare");
+    /// assert_eq!(root.count(), 6);
+    /// assert_eq!(root.children[1].children[0].data, "Stats");
+    /// ```
+    pub fn from_path_strings<I, S>(paths: I, delimiter: char) -> D3Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::from_path_values(paths.into_iter().map(|p| (p, 0.0)), delimiter)
+    }
+
+    /// Build a hierarchy from (path, value) pairs, as [`from_path_strings`](Self::from_path_strings)
+    ///
+    /// Implied intermediate nodes get value zero.
+    pub fn from_path_values<I, S>(entries: I, delimiter: char) -> D3Result<Self>
+    where
+        I: IntoIterator<Item = (S, f64)>,
+        S: AsRef<str>,
+    {
+        // Arena of (name, value, children); index 0 is a provisional unnamed root
+        let mut nodes: Vec<(String, f64, Vec<usize>)> = vec![(String::new(), 0.0, Vec::new())];
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut listed = HashSet::new();
+
+        for (path, value) in entries {
+            let path = path.as_ref();
+            let segments: Vec<&str> = path.trim_matches(delimiter).split(delimiter).collect();
+            if segments.iter().all(|s| s.is_empty()) {
+                return Err(D3Error::invalid_data(format!("stratify: empty path {:?}", path)));
+            }
+
+            let mut parent = 0;
+            let mut key = String::new();
+            for (depth, segment) in segments.iter().enumerate() {
+                if depth > 0 {
+                    key.push(delimiter);
+                }
+                key.push_str(segment);
+                parent = match index.get(&key) {
+                    Some(&i) => i,
+                    None => {
+                        nodes.push((segment.to_string(), 0.0, Vec::new()));
+                        let i = nodes.len() - 1;
+                        nodes[parent].2.push(i);
+                        index.insert(key.clone(), i);
+                        i
+                    }
+                };
+            }
+            if !listed.insert(parent) {
+                return Err(D3Error::invalid_data(format!("stratify: duplicate path {:?}", path)));
+            }
+            nodes[parent].1 = value;
+        }
+        if listed.is_empty() {
+            return Err(D3Error::invalid_data("stratify: no paths"));
+        }
+
+        let children: Vec<Vec<usize>> = nodes.iter().map(|n| n.2.clone()).collect();
+        let top = match children[0].as_slice() {
+            [single] => *single,
+            _ => 0,
+        };
+        let mut root = assemble(top, &children, &mut |i| {
+            HierarchyNode::new(std::mem::take(&mut nodes[i].0), nodes[i].1)
+        });
+        root.update_depth_height();
+        Ok(root)
+    }
+}
+
+/// Resolve parent links to (root index, child indices per row)
+///
+/// Children keep row order.
+fn link(ids: &[&str], parent_ids: &[Option<&str>]) -> D3Result<(usize, Vec<Vec<usize>>)> {
+    if ids.is_empty() {
+        return Err(D3Error::invalid_data("stratify: no rows"));
+    }
+
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, &id) in ids.iter().enumerate() {
+        if index.insert(id, i).is_some() {
+            return Err(D3Error::invalid_data(format!("stratify: duplicate id '{}'", id)));
+        }
+    }
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); ids.len()];
+    let mut parents: Vec<Option<usize>> = vec![None; ids.len()];
+    let mut roots = Vec::new();
+    for (i, parent) in parent_ids.iter().enumerate() {
+        match parent {
+            None => roots.push(i),
+            Some(parent) => {
+                let &p = index.get(parent).ok_or_else(|| {
+                    D3Error::invalid_data(format!(
+                        "stratify: '{}' has missing parent '{}'",
+                        ids[i], parent
+                    ))
+                })?;
+                children[p].push(i);
+                parents[i] = Some(p);
+            }
+        }
+    }
+
+    if roots.len() > 1 {
+        return Err(D3Error::invalid_data(format!(
+            "stratify: multiple roots '{}' and '{}'",
+            ids[roots[0]], ids[roots[1]]
+        )));
+    }
+
+    // Nodes unreachable from the root can only be on (or hang off) a cycle
+    let mut reached = vec![false; ids.len()];
+    if let Some(&root) = roots.first() {
+        let mut stack = vec![root];
+        while let Some(i) = stack.pop() {
+            reached[i] = true;
+            stack.extend(&children[i]);
+        }
+    }
+    if let Some(start) = reached.iter().position(|&r| !r) {
+        return Err(D3Error::cycle_detected(ids[find_cycle(&parents, start)]));
+    }
+
+    Ok((roots[0], children))
+}
+
+/// Build the tree below `i`, making each node with `make`
+fn assemble<T>(
+    i: usize,
+    children: &[Vec<usize>],
+    make: &mut dyn FnMut(usize) -> HierarchyNode<T>,
+) -> HierarchyNode<T> {
+    let node = make(i);
+    let kids = children[i].iter().map(|&c| assemble(c, children, make)).collect();
+    node.with_children(kids)
+}
+
+/// Follow parent links from `start` until a node repeats; that node is on the cycle
+fn find_cycle(parents: &[Option<usize>], start: usize) -> usize {
+    let mut seen = HashSet::new();
+    let mut i = start;
+    while seen.insert(i) {
+        match parents[i] {
+            Some(p) => i = p,
+            None => break,
+        }
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stratify_builds_tree() {
+        let root = Stratify::from_rows(vec![
+            ("root", None, 0.0),
+            ("a", Some("root"), 1.0),
+            ("b", Some("root"), 2.0),
+            ("a1", Some("a"), 3.0),
+        ])
+        .build()
+        .unwrap();
+
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].data, "a");
+        assert_eq!(root.children[0].children[0].data, "a1");
+        assert_eq!(root.children[0].children[0].depth, 2);
+        assert_eq!(root.height, 2);
+    }
+
+    #[test]
+    fn test_stratify_rows_out_of_order() {
+        let root = Stratify::new()
+            .row("leaf", Some("mid"), 5.0)
+            .row("mid", Some("top"), 0.0)
+            .row("top", None, 0.0)
+            .build()
+            .unwrap();
+        assert_eq!(root.data, "top");
+        assert_eq!(root.leaf_count(), 1);
+    }
+
+    #[test]
+    fn test_stratify_errors() {
+        assert!(matches!(Stratify::new().build(), Err(D3Error::InvalidData { .. })));
+
+        let dup = Stratify::new().row("a", None, 0.0).row("a", None, 0.0).build();
+        assert!(dup.unwrap_err().to_string().contains("duplicate id 'a'"));
+
+        let missing = Stratify::new().row("a", None, 0.0).row("b", Some("x"), 0.0).build();
+        assert!(missing.unwrap_err().to_string().contains("missing parent 'x'"));
+
+        let roots = Stratify::new().row("a", None, 0.0).row("b", None, 0.0).build();
+        assert!(roots.unwrap_err().to_string().contains("multiple roots"));
+
+        let nan = Stratify::new().row("a", None, f64::NAN).build();
+        assert!(matches!(nan, Err(D3Error::NonFiniteValue { .. })));
+    }
+
+    #[test]
+    fn test_stratify_cycle() {
+        let err = Stratify::new()
+            .row("root", None, 0.0)
+            .row("a", Some("c"), 0.0)
+            .row("b", Some("a"), 0.0)
+            .row("c", Some("b"), 0.0)
+            .row("tail", Some("a"), 0.0)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, D3Error::CycleDetected { .. }));
+
+        // Every node has a parent: no root at all
+        let err = Stratify::new().row("x", Some("y"), 0.0).row("y", Some("x"), 0.0).build();
+        assert_eq!(err.unwrap_err(), D3Error::cycle_detected("x"));
+    }
+
+    #[test]
+    fn test_stratify_generic_rows() {
+        #[derive(Debug)]
+        struct Row {
+            name: &'static str,
+            boss: Option<&'static str>,
+            salary: f64,
+        }
+        let rows = vec![
+            Row { name: "cto", boss: Some("ceo"), salary: 3.0 },
+            Row { name: "ceo", boss: None, salary: 5.0 },
+            Row { name: "dev", boss: Some("cto"), salary: 1.0 },
+        ];
+        let mut root = HierarchyNode::stratify(rows, |r| r.name.to_string(), |r| r.boss.map(str::to_string)).unwrap();
+        assert_eq!(root.data.name, "ceo");
+        assert_eq!(root.height, 2);
+        assert_eq!(root.sum_by(|r| r.salary), 9.0);
+
+        let cycle = HierarchyNode::stratify(vec![("a", "b"), ("b", "a")], |r| r.0.to_string(), |r| Some(r.1.to_string()));
+        assert!(matches!(cycle, Err(D3Error::CycleDetected { .. })));
+    }
+
+    #[test]
+    fn test_from_path_values() {
+        let root = HierarchyNode::from_path_values(
+            vec![("/a/x/", 2.0), ("/b", 3.0), ("a/y", 4.0), ("a", 1.0)],
+            '/',
+        )
+        .unwrap();
+        // Two top-level segments get an unnamed root
+        assert_eq!(root.data, "");
+        assert_eq!(root.children.iter().map(|c| c.data.as_str()).collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(root.children[0].value, 1.0);
+        assert_eq!(root.children[0].children[1].value, 4.0);
+        assert_eq!(root.height, 2);
+
+        let dup = HierarchyNode::from_path_strings(["a/b", "a/b/"], '/');
+        assert!(dup.unwrap_err().to_string().contains("duplicate path"));
+        assert!(HierarchyNode::from_path_strings(["//"], '/').is_err());
+        assert!(HierarchyNode::from_path_strings(Vec::<&str>::new(), '/').is_err());
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Implements a tidy tree layout based on the Reingold-Tilford algorithm.
 
+use std::f64::consts::TAU;
+
 use super::node::HierarchyNode;
 
 /// Tree layout for hierarchical data
@@ -37,6 +39,8 @@ pub struct TreeLayout {
     separation_cousins: f64,
     /// Node size (width, height) - if set, overrides separation
     node_size: Option<(f64, f64)>,
+    /// Whether x is an angle and y a radius
+    radial: bool,
 }
 
 impl Default for TreeLayout {
@@ -54,6 +58,7 @@ impl TreeLayout {
             separation_siblings: 1.0,
             separation_cousins: 2.0,
             node_size: None,
+            radial: false,
         }
     }
 
@@ -62,15 +67,35 @@ impl TreeLayout {
         self.width = width;
         self.height = height;
         self.node_size = None;
+        self.radial = false;
         self
     }
 
     /// Set fixed node size (alternative to size)
     pub fn node_size(mut self, width: f64, height: f64) -> Self {
         self.node_size = Some((width, height));
+        self.radial = false;
+        self
+    }
+
+    /// Lay out a radial tidy tree of the given radius
+    ///
+    /// Shorthand for `size(2π, radius)`, except that the first and last
+    /// leaves keep a gap between them instead of meeting at the top. x
+    /// becomes the angle in radians (0 at twelve o'clock, clockwise) and y
+    /// the distance from the center; convert with
+    /// [`HierarchyNode::radial_point`] to draw.
+    pub fn radial(mut self, radius: f64) -> Self {
+        self = self.size(TAU, radius);
+        self.radial = true;
         self
     }
 
+    /// Whether the layout produces angles and radii
+    pub fn is_radial(&self) -> bool {
+        self.radial
+    }
+
     /// Set the separation between siblings
     pub fn separation_siblings(mut self, sep: f64) -> Self {
         self.separation_siblings = sep.max(0.0);
@@ -86,14 +111,24 @@ impl TreeLayout {
     /// Apply the layout to a hierarchy
     pub fn layout<T: Clone>(&self, root: &HierarchyNode<T>) -> HierarchyNode<T> {
         let mut tree = root.clone_tree();
-        tree.each_before();
+        tree.update_depth_height();
 
         // First pass: assign preliminary x coordinates
         let mut next_x = vec![0.0; tree.height + 1];
         self.first_walk(&mut tree, &mut next_x);
 
         // Second pass: compute final coordinates
-        let (min_x, max_x) = self.get_x_extent(&tree);
+        let (mut min_x, mut max_x) = self.get_x_extent(&tree);
+        if self.radial && self.node_size.is_none() {
+            // Half a separation either side so the ends don't meet at 0 and 2π
+            let margin = if tree.children.iter().all(|c| c.is_leaf()) {
+                self.separation_siblings / 2.0
+            } else {
+                self.separation_cousins / 2.0
+            };
+            min_x -= margin;
+            max_x += margin;
+        }
         self.second_walk(&mut tree, min_x, max_x);
 
         tree
@@ -183,15 +218,42 @@ impl TreeLayout {
     }
 }
 
-/// Cluster layout - variant of tree that aligns leaves
+/// Cluster (dendrogram) layout - variant of tree that aligns leaves
+///
+/// Follows d3-cluster: leaves are spaced evenly in traversal order and all
+/// sit at the same depth, whatever their distance from the root; each parent
+/// is centered on the mean of its children.
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::layout::hierarchy::{ClusterLayout, HierarchyNode};
+///
+/// let mut branch = HierarchyNode::new("branch", 0.0);
+/// branch.add_child(HierarchyNode::new("deep", 1.0));
+/// let mut root = HierarchyNode::new("root", 0.0);
+/// root.add_child(HierarchyNode::new("shallow", 1.0));
+/// root.add_child(branch);
+///
+/// let positioned = ClusterLayout::new().size(100.0, 50.0).layout(&root);
+/// // Leaves line up at the bottom even though their depths differ
+/// assert_eq!(positioned.children[0].y, 50.0);
+/// assert_eq!(positioned.children[1].children[0].y, 50.0);
+/// ```
 #[derive(Clone, Debug)]
 pub struct ClusterLayout {
     /// Layout width
     width: f64,
     /// Layout height
     height: f64,
-    /// Separation between nodes
-    separation: f64,
+    /// Separation between sibling leaves
+    separation_siblings: f64,
+    /// Separation between leaves with different parents
+    separation_cousins: f64,
+    /// Node size (width, height) - if set, overrides size
+    node_size: Option<(f64, f64)>,
+    /// Whether x is an angle and y a radius
+    radial: bool,
 }
 
 impl Default for ClusterLayout {
@@ -206,7 +268,10 @@ impl ClusterLayout {
         Self {
             width: 1.0,
             height: 1.0,
-            separation: 1.0,
+            separation_siblings: 1.0,
+            separation_cousins: 2.0,
+            node_size: None,
+            radial: false,
         }
     }
 
@@ -214,81 +279,143 @@ impl ClusterLayout {
     pub fn size(mut self, width: f64, height: f64) -> Self {
         self.width = width;
         self.height = height;
+        self.node_size = None;
+        self.radial = false;
+        self
+    }
+
+    /// Set fixed node size (alternative to size)
+    ///
+    /// The root is placed at the origin, so x may be negative.
+    pub fn node_size(mut self, width: f64, height: f64) -> Self {
+        self.node_size = Some((width, height));
+        self.radial = false;
+        self
+    }
+
+    /// Lay out a radial dendrogram of the given radius
+    ///
+    /// Shorthand for `size(2π, radius)`: x becomes the angle in radians
+    /// (0 at twelve o'clock, clockwise) and y the distance from the center.
+    /// Convert with [`HierarchyNode::radial_point`] to draw.
+    pub fn radial(mut self, radius: f64) -> Self {
+        self = self.size(TAU, radius);
+        self.radial = true;
         self
     }
 
-    /// Set the separation
+    /// Set the separation between sibling leaves
     pub fn separation(mut self, sep: f64) -> Self {
-        self.separation = sep.max(0.0);
+        self.separation_siblings = sep.max(0.0);
         self
     }
 
+    /// Set the separation between leaves with different parents
+    pub fn separation_cousins(mut self, sep: f64) -> Self {
+        self.separation_cousins = sep.max(0.0);
+        self
+    }
+
+    /// Whether the layout produces angles and radii
+    pub fn is_radial(&self) -> bool {
+        self.radial
+    }
+
     /// Apply the layout
     pub fn layout<T: Clone>(&self, root: &HierarchyNode<T>) -> HierarchyNode<T> {
         let mut tree = root.clone_tree();
-        tree.each_before();
+        tree.update_depth_height();
 
-        // Position leaves first
-        let leaves = self.position_leaves(&mut tree);
+        // Leaves in order; parents at the mean x and one above the highest child
+        let mut walk = ClusterWalk::default();
+        self.place(&mut tree, 0, &mut walk);
 
-        // Then position internal nodes
-        self.position_internals(&mut tree);
-
-        // Normalize to layout size
-        self.normalize(&mut tree, leaves);
+        let (root_x, root_y) = (tree.x, tree.y);
+        if let Some((node_w, node_h)) = self.node_size {
+            Self::transform(&mut tree, &|x, y| ((x - root_x) * node_w, (root_y - y) * node_h));
+        } else {
+            // Half a separation of margin at either end, as in d3
+            let (first, last) = (walk.first_parent.unwrap_or(0), walk.prev_parent.unwrap_or(0));
+            let margin = self.separation_between(first, last) / 2.0;
+            let (x0, x1) = (-margin, walk.x + margin);
+            let x_range = if x1 > x0 { x1 - x0 } else { 1.0 };
+            let (width, height) = (self.width, self.height);
+            Self::transform(&mut tree, &|x, y| {
+                let depth = if root_y > 0.0 { y / root_y } else { 1.0 };
+                ((x - x0) / x_range * width, (1.0 - depth) * height)
+            });
+        }
 
         tree
     }
 
-    fn position_leaves<T>(&self, node: &mut HierarchyNode<T>) -> usize {
-        let mut leaf_count = 0;
+    fn separation_between(&self, a: usize, b: usize) -> f64 {
+        if a == b {
+            self.separation_siblings
+        } else {
+            self.separation_cousins
+        }
+    }
 
+    /// Post-order placement; y counts levels up from the leaves
+    fn place<T>(&self, node: &mut HierarchyNode<T>, parent: usize, walk: &mut ClusterWalk) {
         if node.is_leaf() {
-            node.x = leaf_count as f64 * self.separation;
-            leaf_count = 1;
+            node.x = match walk.prev_parent {
+                Some(prev) => walk.x + self.separation_between(prev, parent),
+                None => {
+                    walk.first_parent = Some(parent);
+                    0.0
+                }
+            };
+            node.y = 0.0;
+            walk.x = node.x;
+            walk.prev_parent = Some(parent);
         } else {
+            walk.next_id += 1;
+            let id = walk.next_id;
             for child in &mut node.children {
-                leaf_count += self.position_leaves(child);
+                self.place(child, id, walk);
             }
-            // Center over children
-            let first_x = node.children.first().map(|c| c.x).unwrap_or(0.0);
-            let last_x = node.children.last().map(|c| c.x).unwrap_or(0.0);
-            node.x = (first_x + last_x) / 2.0;
+            let n = node.children.len() as f64;
+            node.x = node.children.iter().map(|c| c.x).sum::<f64>() / n;
+            node.y = 1.0 + node.children.iter().map(|c| c.y).fold(0.0, f64::max);
         }
-
-        leaf_count
     }
 
-    fn position_internals<T>(&self, node: &mut HierarchyNode<T>) {
-        node.y = node.depth as f64;
+    fn transform<T>(node: &mut HierarchyNode<T>, f: &dyn Fn(f64, f64) -> (f64, f64)) {
+        (node.x, node.y) = f(node.x, node.y);
         for child in &mut node.children {
-            self.position_internals(child);
+            Self::transform(child, f);
         }
     }
+}
 
-    fn normalize<T>(&self, node: &mut HierarchyNode<T>, leaf_count: usize) {
-        let max_depth = self.find_max_depth(node) as f64;
-        let max_x = (leaf_count.saturating_sub(1)) as f64 * self.separation;
-
-        self.normalize_node(node, max_x.max(1.0), max_depth.max(1.0));
-    }
-
-    fn find_max_depth<T>(&self, node: &HierarchyNode<T>) -> usize {
-        let mut max = node.depth;
-        for child in &node.children {
-            max = max.max(self.find_max_depth(child));
-        }
-        max
-    }
-
-    fn normalize_node<T>(&self, node: &mut HierarchyNode<T>, max_x: f64, max_depth: f64) {
-        node.x = node.x / max_x * self.width;
-        node.y = node.y / max_depth * self.height;
+/// Leaf cursor for the cluster placement walk
+#[derive(Default)]
+struct ClusterWalk {
+    /// x of the last leaf placed
+    x: f64,
+    /// Parent id of the last leaf placed
+    prev_parent: Option<usize>,
+    /// Parent id of the first leaf
+    first_parent: Option<usize>,
+    /// Last internal node id handed out
+    next_id: usize,
+}
 
-        for child in &mut node.children {
-            self.normalize_node(child, max_x, max_depth);
-        }
-    }
+/// Cartesian point for an angle (radians, 0 at twelve o'clock, clockwise)
+/// and a radius, as d3's `pointRadial`
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::layout::hierarchy::point_radial;
+///
+/// let (x, y) = point_radial(0.0, 10.0);
+/// assert_eq!((x, y), (0.0, -10.0));
+/// ```
+pub fn point_radial(angle: f64, radius: f64) -> (f64, f64) {
+    (radius * angle.sin(), -radius * angle.cos())
 }
 
 #[cfg(test)]
@@ -401,4 +528,72 @@ mod tests {
         assert!((leaf2_y - 100.0).abs() < 0.1);
         assert!((leaf3_y - 100.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_cluster_layout_positions() {
+        let positioned = ClusterLayout::new().size(100.0, 100.0).layout(&make_tree());
+        // Pre-order: root, child1, leaf1, leaf2, child2, leaf3
+        let expected = [55.0, 30.0, 20.0, 40.0, 80.0, 80.0];
+        for (node, x) in positioned.iter().zip(expected) {
+            assert!((node.x - x).abs() < 1e-9);
+        }
+        assert_eq!(positioned.children[1].y, 50.0);
+    }
+
+    #[test]
+    fn test_cluster_layout_aligns_uneven_leaves() {
+        let mut mid = HierarchyNode::from_label("mid", 0.0);
+        mid.add_child(HierarchyNode::from_label("deep", 1.0));
+        let mut root = HierarchyNode::from_label("root", 0.0);
+        root.add_child(HierarchyNode::from_label("shallow", 1.0));
+        root.add_child(mid);
+
+        let positioned = ClusterLayout::new().size(100.0, 100.0).layout(&root);
+        assert_eq!((positioned.x, positioned.y), (50.0, 0.0));
+        assert_eq!((positioned.children[0].x, positioned.children[0].y), (25.0, 100.0));
+        assert_eq!((positioned.children[1].x, positioned.children[1].y), (75.0, 50.0));
+        assert_eq!(positioned.children[1].children[0].y, 100.0);
+    }
+
+    #[test]
+    fn test_cluster_layout_node_size() {
+        let positioned = ClusterLayout::new().node_size(10.0, 20.0).layout(&make_tree());
+        assert_eq!((positioned.x, positioned.y), (0.0, 0.0));
+        assert_eq!(positioned.children[0].children[0].x, -17.5);
+        assert_eq!(positioned.children[0].children[0].y, 40.0);
+    }
+
+    #[test]
+    fn test_cluster_layout_radial() {
+        let positioned = ClusterLayout::new().radial(100.0).layout(&make_tree());
+        let leaf1 = &positioned.children[0].children[0];
+        assert!((leaf1.x - 0.2 * TAU).abs() < 1e-12);
+        assert_eq!(leaf1.y, 100.0);
+
+        let (x, y) = leaf1.radial_point();
+        assert!((x - 100.0 * (0.2 * TAU).sin()).abs() < 1e-12);
+        assert!((y + 100.0 * (0.2 * TAU).cos()).abs() < 1e-12);
+        // The root sits at the center
+        assert_eq!(positioned.radial_point(), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_tree_layout_radial_keeps_ends_apart() {
+        let layout = TreeLayout::new().radial(100.0);
+        assert!(layout.is_radial());
+        let positioned = layout.layout(&make_tree());
+        let leaves = positioned.leaves();
+        let first = leaves.first().unwrap().x;
+        let last = leaves.last().unwrap().x;
+        assert!(first > 0.0 && last < TAU);
+        assert!(leaves.iter().all(|l| l.y == 100.0));
+        assert!(!TreeLayout::new().radial(100.0).size(10.0, 10.0).is_radial());
+    }
+
+    #[test]
+    fn test_point_radial() {
+        let (x, y) = point_radial(std::f64::consts::FRAC_PI_2, 10.0);
+        assert!((x - 10.0).abs() < 1e-12);
+        assert!(y.abs() < 1e-12);
+    }
 }
@@ -2,27 +2,46 @@
 //!
 //! Space-filling visualization for hierarchical data using nested rectangles.
 
+use std::collections::HashMap;
+
 use super::node::HierarchyNode;
 
 /// Tiling method for treemap layout
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum TilingMethod {
-    /// Squarified tiling (default) - produces square-ish rectangles
+    /// Squarified tiling (default) - rectangles near the target aspect ratio
     #[default]
     Squarify,
-    /// Binary tiling - alternates horizontal/vertical splits
+    /// Binary tiling - recursive splits into halves of about equal value
     Binary,
-    /// Slice tiling - horizontal slices
+    /// Slice tiling - children stacked top to bottom
     Slice,
-    /// Dice tiling - vertical slices
+    /// Dice tiling - children side by side left to right
     Dice,
-    /// Slice-and-dice - alternates by depth
+    /// Slice-and-dice - dice at even depths, slice at odd depths
     SliceDice,
 }
 
+/// Golden ratio, d3's default squarify aspect ratio
+const PHI: f64 = 1.618_033_988_749_895;
+
+/// A run of consecutive children laid out in one strip by squarify
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SquarifyRow {
+    /// First child index
+    start: usize,
+    /// One past the last child index
+    end: usize,
+    /// Whether the strip runs horizontally (children side by side)
+    dice: bool,
+}
+
 /// Treemap layout for hierarchical data
 ///
-/// Creates space-filling visualizations where area represents value.
+/// Creates space-filling visualizations where area represents value. Tiling
+/// and padding follow d3-hierarchy's `treemap`: squarify keeps children in
+/// their given order, so sort the hierarchy first (e.g. with
+/// [`HierarchyNode::sort_by_value`]) for the usual largest-first layout.
 ///
 /// # Example
 ///
@@ -46,22 +65,55 @@ pub enum TilingMethod {
 ///         node.data, node.x, node.y, node.width, node.rect_height);
 /// }
 /// ```
+///
+/// # Sticky updates
+///
+/// [`layout_sticky`](Self::layout_sticky) remembers how squarify grouped
+/// each node's children into strips and reuses that grouping while the
+/// child counts stay the same (d3's `treemapResquarify`), so animated
+/// treemaps resize cells in place instead of reshuffling them.
+///
+/// ```
+/// use makepad_d3::layout::hierarchy::{HierarchyNode, TreemapLayout};
+///
+/// let tree = |a: f64| {
+///     let mut root = HierarchyNode::new("root", 0.0);
+///     for (name, value) in [("a", a), ("b", 6.0), ("c", 4.0), ("d", 3.0)] {
+///         root.add_child(HierarchyNode::new(name, value));
+///     }
+///     root
+/// };
+///
+/// let mut layout = TreemapLayout::new().size(600.0, 400.0);
+/// let before = layout.layout_sticky(&tree(6.0));
+/// let after = layout.layout_sticky(&tree(6.5));
+/// // Cells grow or shrink but keep their place
+/// assert_eq!(before.children[3].y, after.children[3].y);
+/// ```
 #[derive(Clone, Debug)]
 pub struct TreemapLayout {
     /// Layout width
     width: f64,
     /// Layout height
     height: f64,
-    /// Padding between siblings
-    padding: f64,
-    /// Padding at top for labels
+    /// Padding between sibling cells
+    padding_inner: f64,
+    /// Padding inside the top edge of parent cells (room for headers)
     padding_top: f64,
-    /// Padding around the root
-    padding_outer: f64,
+    /// Padding inside the right edge of parent cells
+    padding_right: f64,
+    /// Padding inside the bottom edge of parent cells
+    padding_bottom: f64,
+    /// Padding inside the left edge of parent cells
+    padding_left: f64,
     /// Tiling method
     tiling: TilingMethod,
+    /// Target aspect ratio for squarified cells
+    ratio: f64,
     /// Whether to round coordinates to pixels
     round: bool,
+    /// Squarify strips per parent (by child index path) for sticky layouts
+    rows: HashMap<Vec<usize>, Vec<SquarifyRow>>,
 }
 
 impl Default for TreemapLayout {
@@ -76,11 +128,15 @@ impl TreemapLayout {
         Self {
             width: 1.0,
             height: 1.0,
-            padding: 0.0,
+            padding_inner: 0.0,
             padding_top: 0.0,
-            padding_outer: 0.0,
+            padding_right: 0.0,
+            padding_bottom: 0.0,
+            padding_left: 0.0,
             tiling: TilingMethod::Squarify,
+            ratio: PHI,
             round: false,
+            rows: HashMap::new(),
         }
     }
 
@@ -91,21 +147,46 @@ impl TreemapLayout {
         self
     }
 
-    /// Set padding between siblings
-    pub fn padding(mut self, padding: f64) -> Self {
-        self.padding = padding.max(0.0);
+    /// Set both inner and outer padding
+    pub fn padding(self, padding: f64) -> Self {
+        self.padding_inner(padding).padding_outer(padding)
+    }
+
+    /// Set padding between sibling cells
+    pub fn padding_inner(mut self, padding: f64) -> Self {
+        self.padding_inner = padding.max(0.0);
         self
     }
 
-    /// Set padding at top (for labels)
+    /// Set padding inside all four edges of parent cells
+    pub fn padding_outer(self, padding: f64) -> Self {
+        self.padding_top(padding)
+            .padding_right(padding)
+            .padding_bottom(padding)
+            .padding_left(padding)
+    }
+
+    /// Set padding inside the top edge of parent cells (for headers)
     pub fn padding_top(mut self, padding: f64) -> Self {
         self.padding_top = padding.max(0.0);
         self
     }
 
-    /// Set padding around root
-    pub fn padding_outer(mut self, padding: f64) -> Self {
-        self.padding_outer = padding.max(0.0);
+    /// Set padding inside the right edge of parent cells
+    pub fn padding_right(mut self, padding: f64) -> Self {
+        self.padding_right = padding.max(0.0);
+        self
+    }
+
+    /// Set padding inside the bottom edge of parent cells
+    pub fn padding_bottom(mut self, padding: f64) -> Self {
+        self.padding_bottom = padding.max(0.0);
+        self
+    }
+
+    /// Set padding inside the left edge of parent cells
+    pub fn padding_left(mut self, padding: f64) -> Self {
+        self.padding_left = padding.max(0.0);
         self
     }
 
@@ -115,6 +196,16 @@ impl TreemapLayout {
         self
     }
 
+    /// Set the target aspect ratio for squarified cells (at least 1)
+    ///
+    /// Defaults to the golden ratio, as in d3. Changing it forgets any
+    /// sticky strips.
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        self.ratio = if ratio > 1.0 { ratio } else { 1.0 };
+        self.rows.clear();
+        self
+    }
+
     /// Enable rounding to whole pixels
     pub fn round(mut self, round: bool) -> Self {
         self.round = round;
@@ -123,398 +214,456 @@ impl TreemapLayout {
 
     /// Apply the layout to a hierarchy
     pub fn layout<T: Clone>(&self, root: &HierarchyNode<T>) -> HierarchyNode<T> {
+        self.run(root, None)
+    }
+
+    /// Apply the layout, keeping squarify strips from the previous call
+    ///
+    /// Parents whose child count changed are squarified afresh.
+    pub fn layout_sticky<T: Clone>(&mut self, root: &HierarchyNode<T>) -> HierarchyNode<T> {
+        let mut rows = std::mem::take(&mut self.rows);
+        let tree = self.run(root, Some(&mut rows));
+        self.rows = rows;
+        tree
+    }
+
+    /// Forget the strips remembered by [`layout_sticky`](Self::layout_sticky)
+    pub fn reset_sticky(&mut self) {
+        self.rows.clear();
+    }
+
+    fn run<T: Clone>(
+        &self,
+        root: &HierarchyNode<T>,
+        mut rows: Option<&mut HashMap<Vec<usize>, Vec<SquarifyRow>>>,
+    ) -> HierarchyNode<T> {
         let mut tree = root.clone_tree();
 
         // Sum values if not already done
         tree.sum();
-        tree.each_before();
-
-        // Set root dimensions
-        tree.x = self.padding_outer;
-        tree.y = self.padding_outer;
-        tree.width = self.width - 2.0 * self.padding_outer;
-        tree.rect_height = self.height - 2.0 * self.padding_outer;
+        tree.update_depth_height();
 
-        // Apply tiling recursively
-        self.tile_node(&mut tree);
+        set_rect(&mut tree, 0.0, 0.0, self.width, self.height);
+        self.position_node(&mut tree, 0.0, &mut Vec::new(), &mut rows);
 
         // Round if requested
         if self.round {
-            self.round_coords(&mut tree);
+            round_coords(&mut tree);
         }
 
         tree
     }
 
-    /// Tile a node and its children
-    fn tile_node<T>(&self, node: &mut HierarchyNode<T>) {
+    /// Inset a node by its share of the parent's inner padding, then tile
+    /// its children inside its own padding
+    fn position_node<T>(
+        &self,
+        node: &mut HierarchyNode<T>,
+        inset: f64,
+        path: &mut Vec<usize>,
+        rows: &mut Option<&mut HashMap<Vec<usize>, Vec<SquarifyRow>>>,
+    ) {
+        let (x0, y0, x1, y1) = collapse(
+            node.x + inset,
+            node.y + inset,
+            node.x + node.width - inset,
+            node.y + node.rect_height - inset,
+        );
+        set_rect(node, x0, y0, x1, y1);
         if node.children.is_empty() {
             return;
         }
 
-        // Calculate available area for children
-        let x0 = node.x + self.padding;
-        let y0 = node.y + self.padding + self.padding_top;
-        let x1 = node.x + node.width - self.padding;
-        let y1 = node.y + node.rect_height - self.padding;
-
-        if x1 <= x0 || y1 <= y0 {
-            return;
+        // Half the inner padding on each side of every child
+        let half = self.padding_inner / 2.0;
+        let (x0, y0, x1, y1) = collapse(
+            x0 + self.padding_left - half,
+            y0 + self.padding_top - half,
+            x1 - (self.padding_right - half),
+            y1 - (self.padding_bottom - half),
+        );
+        self.tile(node, x0, y0, x1, y1, path, rows);
+
+        for (i, child) in node.children.iter_mut().enumerate() {
+            path.push(i);
+            self.position_node(child, half, path, rows);
+            path.pop();
         }
+    }
 
-        // Apply appropriate tiling method
+    /// Tile a node's children into the given rectangle
+    #[allow(clippy::too_many_arguments)]
+    fn tile<T>(
+        &self,
+        node: &mut HierarchyNode<T>,
+        x0: f64,
+        y0: f64,
+        x1: f64,
+        y1: f64,
+        path: &[usize],
+        rows: &mut Option<&mut HashMap<Vec<usize>, Vec<SquarifyRow>>>,
+    ) {
+        let value = node.value;
+        let children = &mut node.children;
         match self.tiling {
-            TilingMethod::Squarify => self.tile_squarify(node, x0, y0, x1, y1),
-            TilingMethod::Binary => self.tile_binary(node, x0, y0, x1, y1),
-            TilingMethod::Slice => self.tile_slice(node, x0, y0, x1, y1),
-            TilingMethod::Dice => self.tile_dice(node, x0, y0, x1, y1),
+            TilingMethod::Squarify => match rows {
+                Some(rows) => {
+                    let n = children.len();
+                    match rows.get(path) {
+                        Some(strips) if strips.last().is_some_and(|r| r.end == n) => {
+                            resquarify(strips, children, value, x0, y0, x1, y1);
+                        }
+                        _ => {
+                            let strips = squarify(self.ratio, children, value, x0, y0, x1, y1);
+                            rows.insert(path.to_vec(), strips);
+                        }
+                    }
+                }
+                None => {
+                    squarify(self.ratio, children, value, x0, y0, x1, y1);
+                }
+            },
+            TilingMethod::Binary => binary(children, x0, y0, x1, y1),
+            TilingMethod::Slice => slice(children, value, x0, y0, x1, y1),
+            TilingMethod::Dice => dice(children, value, x0, y0, x1, y1),
             TilingMethod::SliceDice => {
-                if node.depth % 2 == 0 {
-                    self.tile_slice(node, x0, y0, x1, y1);
+                if node.depth % 2 == 1 {
+                    slice(children, value, x0, y0, x1, y1);
                 } else {
-                    self.tile_dice(node, x0, y0, x1, y1);
+                    dice(children, value, x0, y0, x1, y1);
                 }
             }
         }
-
-        // Recursively tile children
-        for child in &mut node.children {
-            self.tile_node(child);
-        }
     }
+}
 
-    /// Slice tiling (horizontal slices)
-    fn tile_slice<T>(&self, node: &mut HierarchyNode<T>, x0: f64, y0: f64, x1: f64, y1: f64) {
-        let total = node.value;
-        if total <= 0.0 {
-            return;
-        }
-
-        let height = y1 - y0;
-        let mut y = y0;
+/// Set a node's rectangle from its edges
+fn set_rect<T>(node: &mut HierarchyNode<T>, x0: f64, y0: f64, x1: f64, y1: f64) {
+    node.x = x0;
+    node.y = y0;
+    node.width = x1 - x0;
+    node.rect_height = y1 - y0;
+}
 
-        for child in &mut node.children {
-            let h = (child.value / total) * height;
-            child.x = x0;
-            child.y = y;
-            child.width = x1 - x0;
-            child.rect_height = h;
-            y += h;
-        }
+/// Collapse inverted edges to their midpoint
+fn collapse(mut x0: f64, mut y0: f64, mut x1: f64, mut y1: f64) -> (f64, f64, f64, f64) {
+    if x1 < x0 {
+        x0 = (x0 + x1) / 2.0;
+        x1 = x0;
     }
+    if y1 < y0 {
+        y0 = (y0 + y1) / 2.0;
+        y1 = y0;
+    }
+    (x0, y0, x1, y1)
+}
 
-    /// Dice tiling (vertical slices)
-    fn tile_dice<T>(&self, node: &mut HierarchyNode<T>, x0: f64, y0: f64, x1: f64, y1: f64) {
-        let total = node.value;
-        if total <= 0.0 {
-            return;
-        }
-
-        let width = x1 - x0;
-        let mut x = x0;
-
-        for child in &mut node.children {
-            let w = (child.value / total) * width;
-            child.x = x;
-            child.y = y0;
-            child.width = w;
-            child.rect_height = y1 - y0;
-            x += w;
-        }
+/// Stack nodes top to bottom, heights proportional to value
+fn slice<T>(nodes: &mut [HierarchyNode<T>], value: f64, x0: f64, y0: f64, x1: f64, y1: f64) {
+    let k = if value != 0.0 { (y1 - y0) / value } else { 0.0 };
+    let mut y = y0;
+    for node in nodes {
+        let next = y + node.value * k;
+        set_rect(node, x0, y, x1, next);
+        y = next;
     }
+}
 
-    /// Binary tiling (alternating splits)
-    fn tile_binary<T>(&self, node: &mut HierarchyNode<T>, x0: f64, y0: f64, x1: f64, y1: f64) {
-        let n = node.children.len();
-        if n == 0 {
-            return;
-        }
+/// Place nodes left to right, widths proportional to value
+fn dice<T>(nodes: &mut [HierarchyNode<T>], value: f64, x0: f64, y0: f64, x1: f64, y1: f64) {
+    let k = if value != 0.0 { (x1 - x0) / value } else { 0.0 };
+    let mut x = x0;
+    for node in nodes {
+        let next = x + node.value * k;
+        set_rect(node, x, y0, next, y1);
+        x = next;
+    }
+}
 
-        if n == 1 {
-            node.children[0].x = x0;
-            node.children[0].y = y0;
-            node.children[0].width = x1 - x0;
-            node.children[0].rect_height = y1 - y0;
-            return;
+/// Squarified tiling (d3's `treemapSquarify.ratio`), returning the strips
+#[allow(clippy::too_many_arguments)]
+fn squarify<T>(
+    ratio: f64,
+    nodes: &mut [HierarchyNode<T>],
+    mut value: f64,
+    mut x0: f64,
+    mut y0: f64,
+    x1: f64,
+    y1: f64,
+) -> Vec<SquarifyRow> {
+    let n = nodes.len();
+    let mut rows = Vec::new();
+    let (mut i0, mut i1) = (0, 0);
+
+    while i0 < n {
+        let (dx, dy) = (x1 - x0, y1 - y0);
+
+        // Find the next non-empty node
+        let mut sum_value;
+        loop {
+            sum_value = nodes[i1].value;
+            i1 += 1;
+            if sum_value != 0.0 || i1 >= n {
+                break;
+            }
         }
-
-        // Find best split point
-        let total = node.value;
-        let target = total / 2.0;
-        let mut sum = 0.0;
-        let mut split_index = 0;
-
-        for (i, child) in node.children.iter().enumerate() {
-            sum += child.value;
-            if sum >= target {
-                split_index = i;
+        let (mut min_value, mut max_value) = (sum_value, sum_value);
+        let alpha = (dy / dx).max(dx / dy) / (value * ratio);
+        let mut beta = sum_value * sum_value * alpha;
+        let mut min_ratio = (max_value / beta).max(beta / min_value);
+
+        // Keep adding nodes while the aspect ratio maintains or improves
+        while i1 < n {
+            let node_value = nodes[i1].value;
+            sum_value += node_value;
+            min_value = min_value.min(node_value);
+            max_value = max_value.max(node_value);
+            beta = sum_value * sum_value * alpha;
+            let new_ratio = (max_value / beta).max(beta / min_value);
+            if new_ratio > min_ratio {
+                sum_value -= node_value;
                 break;
             }
+            min_ratio = new_ratio;
+            i1 += 1;
         }
-        split_index = split_index.max(0).min(n - 2);
-
-        let left_sum: f64 = node.children[..=split_index].iter().map(|c| c.value).sum();
-        let ratio = if total > 0.0 { left_sum / total } else { 0.5 };
 
-        // Split horizontally or vertically based on aspect ratio
-        let width = x1 - x0;
-        let height = y1 - y0;
-
-        if width > height {
-            // Vertical split
-            let split_x = x0 + width * ratio;
-            self.tile_binary_range(node, 0, split_index + 1, x0, y0, split_x, y1);
-            self.tile_binary_range(node, split_index + 1, n, split_x, y0, x1, y1);
-        } else {
-            // Horizontal split
-            let split_y = y0 + height * ratio;
-            self.tile_binary_range(node, 0, split_index + 1, x0, y0, x1, split_y);
-            self.tile_binary_range(node, split_index + 1, n, x0, split_y, x1, y1);
-        }
+        let row = SquarifyRow { start: i0, end: i1, dice: dx < dy };
+        place_row(row, nodes, sum_value, value, &mut x0, &mut y0, x1, y1);
+        rows.push(row);
+        value -= sum_value;
+        i0 = i1;
     }
+    rows
+}
 
-    fn tile_binary_range<T>(
-        &self,
-        node: &mut HierarchyNode<T>,
-        start: usize,
-        end: usize,
-        x0: f64,
-        y0: f64,
-        x1: f64,
-        y1: f64,
-    ) {
-        let count = end - start;
-        if count == 0 {
-            return;
-        }
+/// Re-tile with previously chosen strips (d3's `treemapResquarify`)
+fn resquarify<T>(
+    rows: &[SquarifyRow],
+    nodes: &mut [HierarchyNode<T>],
+    mut value: f64,
+    mut x0: f64,
+    mut y0: f64,
+    x1: f64,
+    y1: f64,
+) {
+    for &row in rows {
+        let row_value: f64 = nodes[row.start..row.end].iter().map(|c| c.value).sum();
+        place_row(row, nodes, row_value, value, &mut x0, &mut y0, x1, y1);
+        value -= row_value;
+    }
+}
 
-        if count == 1 {
-            node.children[start].x = x0;
-            node.children[start].y = y0;
-            node.children[start].width = x1 - x0;
-            node.children[start].rect_height = y1 - y0;
-            return;
+/// Lay out one strip along the top or left of the remaining space,
+/// shrinking the space past it
+#[allow(clippy::too_many_arguments)]
+fn place_row<T>(
+    row: SquarifyRow,
+    nodes: &mut [HierarchyNode<T>],
+    row_value: f64,
+    value: f64,
+    x0: &mut f64,
+    y0: &mut f64,
+    x1: f64,
+    y1: f64,
+) {
+    let strip = &mut nodes[row.start..row.end];
+    if row.dice {
+        let y = if value != 0.0 { *y0 + (y1 - *y0) * row_value / value } else { y1 };
+        dice(strip, row_value, *x0, *y0, x1, y);
+        if value != 0.0 {
+            *y0 = y;
         }
-
-        let total: f64 = node.children[start..end].iter().map(|c| c.value).sum();
-        let target = total / 2.0;
-        let mut sum = 0.0;
-        let mut split_index = start;
-
-        for i in start..end {
-            sum += node.children[i].value;
-            if sum >= target {
-                split_index = i;
-                break;
-            }
+    } else {
+        let x = if value != 0.0 { *x0 + (x1 - *x0) * row_value / value } else { x1 };
+        slice(strip, row_value, *x0, *y0, x, y1);
+        if value != 0.0 {
+            *x0 = x;
         }
+    }
+}
 
-        let left_sum: f64 = node.children[start..=split_index].iter().map(|c| c.value).sum();
-        let ratio = if total > 0.0 { left_sum / total } else { 0.5 };
+/// Binary tiling (d3's `treemapBinary`)
+fn binary<T>(nodes: &mut [HierarchyNode<T>], x0: f64, y0: f64, x1: f64, y1: f64) {
+    if nodes.is_empty() {
+        return;
+    }
+    let mut sums = Vec::with_capacity(nodes.len() + 1);
+    sums.push(0.0);
+    for node in nodes.iter() {
+        sums.push(sums[sums.len() - 1] + node.value);
+    }
+    let total = sums[nodes.len()];
+    let n = nodes.len();
+    partition(nodes, &sums, 0, n, total, x0, y0, x1, y1);
+}
 
-        let width = x1 - x0;
-        let height = y1 - y0;
+/// Split nodes `i..j` into two groups of about equal value, recursively
+#[allow(clippy::too_many_arguments)]
+fn partition<T>(
+    nodes: &mut [HierarchyNode<T>],
+    sums: &[f64],
+    i: usize,
+    j: usize,
+    value: f64,
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+) {
+    if i + 1 >= j {
+        set_rect(&mut nodes[i], x0, y0, x1, y1);
+        return;
+    }
 
-        if width > height {
-            let split_x = x0 + width * ratio;
-            self.tile_binary_range(node, start, split_index + 1, x0, y0, split_x, y1);
-            self.tile_binary_range(node, split_index + 1, end, split_x, y0, x1, y1);
+    let value_offset = sums[i];
+    let value_target = value / 2.0 + value_offset;
+    let (mut k, mut hi) = (i + 1, j - 1);
+    while k < hi {
+        let mid = (k + hi) / 2;
+        if sums[mid] < value_target {
+            k = mid + 1;
         } else {
-            let split_y = y0 + height * ratio;
-            self.tile_binary_range(node, start, split_index + 1, x0, y0, x1, split_y);
-            self.tile_binary_range(node, split_index + 1, end, x0, split_y, x1, y1);
+            hi = mid;
         }
     }
+    if value_target - sums[k - 1] < sums[k] - value_target && i + 1 < k {
+        k -= 1;
+    }
 
-    /// Squarified tiling (produces square-ish rectangles)
-    fn tile_squarify<T>(&self, node: &mut HierarchyNode<T>, x0: f64, y0: f64, x1: f64, y1: f64) {
-        let total = node.value;
-        if total <= 0.0 || node.children.is_empty() {
-            return;
-        }
+    let value_left = sums[k] - value_offset;
+    let value_right = value - value_left;
+    if x1 - x0 > y1 - y0 {
+        let xk = if value != 0.0 { (x0 * value_right + x1 * value_left) / value } else { x1 };
+        partition(nodes, sums, i, k, value_left, x0, y0, xk, y1);
+        partition(nodes, sums, k, j, value_right, xk, y0, x1, y1);
+    } else {
+        let yk = if value != 0.0 { (y0 * value_right + y1 * value_left) / value } else { y1 };
+        partition(nodes, sums, i, k, value_left, x0, y0, x1, yk);
+        partition(nodes, sums, k, j, value_right, x0, yk, x1, y1);
+    }
+}
 
-        // Sort children by value (descending) for better squarification
-        let mut indices: Vec<usize> = (0..node.children.len()).collect();
-        indices.sort_by(|&a, &b| {
-            node.children[b]
-                .value
-                .partial_cmp(&node.children[a].value)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        let mut remaining_area = (x1 - x0) * (y1 - y0);
-        let mut remaining_value = total;
-
-        let mut curr_x = x0;
-        let mut curr_y = y0;
-        let mut curr_w = x1 - x0;
-        let mut curr_h = y1 - y0;
-
-        let mut row_start = 0;
-        let mut row_values: Vec<f64> = Vec::new();
-        let mut row_sum = 0.0;
-
-        for (pos, &idx) in indices.iter().enumerate() {
-            let value = node.children[idx].value;
-
-            if row_values.is_empty() {
-                row_values.push(value);
-                row_sum = value;
-                continue;
-            }
+/// Round cell edges to whole pixels, so neighbors stay flush
+fn round_coords<T>(node: &mut HierarchyNode<T>) {
+    let (x0, y0) = (node.x.round(), node.y.round());
+    let (x1, y1) = ((node.x + node.width).round(), (node.y + node.rect_height).round());
+    set_rect(node, x0, y0, x1, y1);
 
-            // Calculate aspect ratio with and without this item
-            let short_side = curr_w.min(curr_h);
-            let row_area = row_sum / remaining_value * remaining_area;
-            let row_length = row_area / short_side;
-
-            let worst_current = self.worst_ratio(&row_values, row_length);
-
-            row_values.push(value);
-            row_sum += value;
-            let new_row_area = row_sum / remaining_value * remaining_area;
-            let new_row_length = new_row_area / short_side;
-            let worst_with_new = self.worst_ratio(&row_values, new_row_length);
-
-            if worst_with_new > worst_current && row_values.len() > 1 {
-                // Adding this item makes it worse, so finalize current row
-                row_values.pop();
-                row_sum -= value;
-
-                // Layout the row
-                let row_area = row_sum / remaining_value * remaining_area;
-                let (new_x, new_y, new_w, new_h) = self.layout_row(
-                    node,
-                    &indices[row_start..pos],
-                    &row_values,
-                    row_area,
-                    curr_x,
-                    curr_y,
-                    curr_w,
-                    curr_h,
-                );
-
-                remaining_area -= row_area;
-                remaining_value -= row_sum;
-                curr_x = new_x;
-                curr_y = new_y;
-                curr_w = new_w;
-                curr_h = new_h;
-
-                // Start new row with this item
-                row_start = pos;
-                row_values = vec![value];
-                row_sum = value;
-            }
-        }
+    for child in &mut node.children {
+        round_coords(child);
+    }
+}
 
-        // Layout final row
-        if !row_values.is_empty() {
-            let row_area = remaining_area;
-            self.layout_row(
-                node,
-                &indices[row_start..],
-                &row_values,
-                row_area,
-                curr_x,
-                curr_y,
-                curr_w,
-                curr_h,
-            );
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_tree() -> HierarchyNode<String> {
+        let mut root = HierarchyNode::from_label("root", 0.0);
+        root.add_child(HierarchyNode::from_label("A", 30.0));
+        root.add_child(HierarchyNode::from_label("B", 20.0));
+        root.add_child(HierarchyNode::from_label("C", 50.0));
+        root
     }
 
-    /// Calculate worst aspect ratio in a row
-    fn worst_ratio(&self, values: &[f64], length: f64) -> f64 {
-        if values.is_empty() || length <= 0.0 {
-            return f64::INFINITY;
+    fn leaves(values: &[f64]) -> HierarchyNode<String> {
+        let mut root = HierarchyNode::from_label("root", 0.0);
+        for (i, &v) in values.iter().enumerate() {
+            root.add_child(HierarchyNode::from_label(&i.to_string(), v));
         }
+        root
+    }
 
-        let sum: f64 = values.iter().sum();
-        let length_sq = length * length;
+    fn edges(node: &HierarchyNode<String>) -> [f64; 4] {
+        [node.x, node.x + node.width, node.y, node.y + node.rect_height]
+    }
 
-        let mut worst = 0.0_f64;
-        for &v in values {
-            let w = v / sum * length;
-            let h = v / w;
-            let ratio = (w / h).max(h / w);
-            worst = worst.max(ratio);
+    fn assert_edges(node: &HierarchyNode<String>, expected: [f64; 4]) {
+        for (a, b) in edges(node).iter().zip(expected) {
+            assert!((a - b).abs() < 0.01, "{:?} != {:?}", edges(node), expected);
         }
-        worst
     }
 
-    /// Layout a row of nodes
-    fn layout_row<T>(
-        &self,
-        node: &mut HierarchyNode<T>,
-        indices: &[usize],
-        values: &[f64],
-        area: f64,
-        x: f64,
-        y: f64,
-        w: f64,
-        h: f64,
-    ) -> (f64, f64, f64, f64) {
-        let sum: f64 = values.iter().sum();
-        if sum <= 0.0 {
-            return (x, y, w, h);
+    #[test]
+    fn test_squarify_matches_d3() {
+        // d3-hierarchy's squarify test: golden ratio, 6x4
+        let tree = leaves(&[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0]);
+        let positioned = TreemapLayout::new().size(6.0, 4.0).layout(&tree);
+        let expected = [
+            [0.00, 3.00, 0.00, 2.00],
+            [0.00, 3.00, 2.00, 4.00],
+            [3.00, 4.71, 0.00, 2.33],
+            [4.71, 6.00, 0.00, 2.33],
+            [3.00, 5.40, 2.33, 3.17],
+            [3.00, 5.40, 3.17, 4.00],
+            [5.40, 6.00, 2.33, 4.00],
+        ];
+        for (child, e) in positioned.children.iter().zip(expected) {
+            assert_edges(child, e);
         }
 
-        let horizontal = w >= h;
-        let length = if horizontal { area / h } else { area / w };
-
-        let mut pos = if horizontal { x } else { y };
-
-        for (i, &idx) in indices.iter().enumerate() {
-            let v = values[i];
-            let size = if sum > 0.0 { v / sum * (if horizontal { h } else { w }) } else { 0.0 };
-
-            if horizontal {
-                node.children[idx].x = pos;
-                node.children[idx].y = y;
-                node.children[idx].width = length;
-                node.children[idx].rect_height = size;
-                pos += size;
-            } else {
-                node.children[idx].x = x;
-                node.children[idx].y = pos;
-                node.children[idx].width = size;
-                node.children[idx].rect_height = length;
-                pos += size;
-            }
-        }
+        // A square target ratio splits the last strips differently
+        let square = TreemapLayout::new().size(6.0, 4.0).ratio(1.0).layout(&tree);
+        assert_edges(&square.children[4], [3.00, 4.20, 2.33, 4.00]);
+        assert_edges(&square.children[5], [4.20, 5.40, 2.33, 4.00]);
+    }
 
-        // Return remaining area
-        if horizontal {
-            (x + length, y, w - length, h)
-        } else {
-            (x, y + length, w, h - length)
-        }
+    #[test]
+    fn test_sticky_keeps_strips() {
+        let mut layout = TreemapLayout::new().size(6.0, 4.0);
+        layout.layout_sticky(&leaves(&[6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0]));
+        let shrunk = leaves(&[2.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0]);
+
+        // Fresh squarify regroups the small cells side by side...
+        let fresh = layout.layout(&shrunk);
+        assert_edges(&fresh.children[5], [3.84, 5.28, 2.33, 4.00]);
+
+        // ...while the sticky layout keeps them stacked in their strip
+        let sticky = layout.layout_sticky(&shrunk);
+        assert_edges(&sticky.children[0], [0.00, 2.40, 0.00, 1.00]);
+        assert_edges(&sticky.children[4], [2.40, 5.28, 2.33, 3.17]);
+        assert_edges(&sticky.children[5], [2.40, 5.28, 3.17, 4.00]);
+
+        layout.reset_sticky();
+        assert_edges(&layout.layout_sticky(&shrunk).children[5], [3.84, 5.28, 2.33, 4.00]);
     }
 
-    /// Round coordinates to whole pixels
-    fn round_coords<T>(&self, node: &mut HierarchyNode<T>) {
-        node.x = node.x.round();
-        node.y = node.y.round();
-        node.width = node.width.round();
-        node.rect_height = node.rect_height.round();
+    #[test]
+    fn test_padding_hierarchy() {
+        let mut root = HierarchyNode::from_label("root", 0.0);
+        let mut group = HierarchyNode::from_label("group", 0.0);
+        group.add_child(HierarchyNode::from_label("a", 1.0));
+        group.add_child(HierarchyNode::from_label("b", 1.0));
+        root.add_child(group);
 
-        for child in &mut node.children {
-            self.round_coords(child);
-        }
+        let positioned = TreemapLayout::new()
+            .size(100.0, 100.0)
+            .tiling(TilingMethod::Dice)
+            .padding_inner(4.0)
+            .padding_outer(2.0)
+            .padding_top(20.0)
+            .layout(&root);
+
+        // Inside the root: 20px header, 2px outer padding on the other sides
+        let group = &positioned.children[0];
+        assert_edges(group, [2.0, 98.0, 20.0, 98.0]);
+        // The same again inside the group, with 4px between siblings
+        assert_edges(&group.children[0], [4.0, 48.0, 40.0, 96.0]);
+        assert_edges(&group.children[1], [52.0, 96.0, 40.0, 96.0]);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    fn make_tree() -> HierarchyNode<String> {
-        let mut root = HierarchyNode::from_label("root", 0.0);
-        root.add_child(HierarchyNode::from_label("A", 30.0));
-        root.add_child(HierarchyNode::from_label("B", 20.0));
-        root.add_child(HierarchyNode::from_label("C", 50.0));
-        root
+    #[test]
+    fn test_binary_matches_d3() {
+        // Split at the value midpoint, along the longer side
+        let tree = leaves(&[1.0, 1.0, 2.0]);
+        let positioned = TreemapLayout::new()
+            .size(8.0, 4.0)
+            .tiling(TilingMethod::Binary)
+            .layout(&tree);
+        assert_edges(&positioned.children[0], [0.0, 4.0, 0.0, 2.0]);
+        assert_edges(&positioned.children[1], [0.0, 4.0, 2.0, 4.0]);
+        assert_edges(&positioned.children[2], [4.0, 8.0, 0.0, 4.0]);
     }
 
     #[test]
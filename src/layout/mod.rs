@@ -20,8 +20,27 @@
 //!
 //! - [`HierarchyNode`]: Node structure for hierarchical data
 //! - [`TreeLayout`]: Tidy tree layout (Reingold-Tilford)
+//! - [`ClusterLayout`]: Dendrogram layout with leaves at the same depth
 //! - [`TreemapLayout`]: Space-filling rectangle layout
 //! - [`PackLayout`]: Circle packing layout
+//! - [`PartitionLayout`]: Icicle and sunburst (radial) partition layout
+//!
+//! # Flow Layouts
+//!
+//! - [`SankeyLayout`]: Sankey diagram layout (d3-sankey)
+//! - [`ChordLayout`]: Chord diagram groups and chords (d3-chord)
+//!
+//! # Network Preprocessing
+//!
+//! - [`GraphCollapse`]: Merge parallel edges, filter weak ones, compute node
+//!   degree and strength
+//! - [`WeightedGraph`]: Spanning trees and disparity-filter backbones for dense graphs
+//! - [`Louvain`]: Community detection for coloring and grouping nodes
+//!
+//! # Ranking Layouts
+//!
+//! - [`SlopeLayout`]: Two-point slope charts with dodged end labels
+//! - [`BumpLayout`]: Rank-over-time bump charts with stable tie ordering
 //!
 //! # Example
 //!
@@ -52,13 +71,27 @@
 
 pub mod force;
 pub mod hierarchy;
+pub mod chord;
+pub mod sankey;
+pub mod slope;
+pub mod graph;
+pub mod community;
 
 pub use force::{
-    ForceSimulation, SimulationNode, SimulationLink,
+    ForceSimulation, SimulationNode, SimulationLink, SimulationStats, SimulationEvent, Convergence,
     Force, ManyBodyForce, LinkForce, CollideForce, CenterForce, PositionForce, RadialForce,
 };
 
 pub use hierarchy::{
-    HierarchyNode, TreeLayout, TreemapLayout, PackLayout,
-    TilingMethod, PackStrategy,
+    HierarchyNode, TreeLayout, ClusterLayout, TreemapLayout, PackLayout, PartitionLayout,
+    PartitionNode, TilingMethod, PackStrategy, point_radial,
+};
+
+pub use chord::{Chord, ChordGroup, ChordLayout, ChordSet, ChordSort, ChordSubgroup, RibbonGenerator};
+pub use sankey::{SankeyAlign, SankeyGraph, SankeyLayout, SankeyLink, SankeyNode};
+pub use graph::{EdgeAggregation, GraphCollapse, GraphEdge, NodeMetrics, WeightedGraph};
+pub use community::{Communities, Louvain};
+pub use slope::{
+    BumpChart, BumpLayout, BumpPoint, BumpSeries, RankOrder,
+    SlopeChart, SlopeDirection, SlopeLayout, SlopeLine, SlopeScale,
 };
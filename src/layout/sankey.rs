@@ -0,0 +1,721 @@
+//! Sankey flow diagram layout
+//!
+//! Port of [d3-sankey](https://github.com/d3/d3-sankey). Nodes are assigned
+//! to columns by an alignment rule, sized by the flow through them, and
+//! spread vertically by iterative relaxation so links run as straight as
+//! possible. Links are then stacked along each node in breadth order.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::layout::sankey::{SankeyAlign, SankeyLayout};
+//!
+//! let names = ["Coal", "Gas", "Electricity", "Homes", "Industry"];
+//! let links = [
+//!     (0, 2, 80.0),
+//!     (1, 2, 50.0),
+//!     (1, 4, 30.0),
+//!     (2, 3, 60.0),
+//!     (2, 4, 70.0),
+//! ];
+//!
+//! let graph = SankeyLayout::new()
+//!     .size(600.0, 400.0)
+//!     .node_width(20.0)
+//!     .node_padding(10.0)
+//!     .align(SankeyAlign::Justify)
+//!     .layout(&names, &links)
+//!     .unwrap();
+//!
+//! let electricity = &graph.nodes[2];
+//! assert_eq!(electricity.layer, 1);
+//! assert_eq!(electricity.value, 130.0);
+//!
+//! // Each link is drawn as a horizontal curve stroked with its width
+//! let path = graph.link_path(0).unwrap();
+//! assert!(!path.is_empty());
+//! assert!(graph.links[0].width > 0.0);
+//! ```
+
+use crate::error::{D3Error, D3Result};
+use crate::shape::{LinkGenerator, Path, Point};
+
+/// Rule for assigning nodes to columns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SankeyAlign {
+    /// Like `Left`, but sinks are moved to the last column (default)
+    #[default]
+    Justify,
+    /// Nodes at their depth from the sources
+    Left,
+    /// Nodes at their height from the sinks, counted from the last column
+    Right,
+    /// Like `Left`, but sources are moved next to their nearest target
+    Center,
+}
+
+/// A positioned node of a sankey diagram
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SankeyNode {
+    /// Node label
+    pub name: String,
+    /// Flow through the node: the larger of its incoming and outgoing totals
+    pub value: f64,
+    /// Longest path length from a source
+    pub depth: usize,
+    /// Longest path length to a sink
+    pub height: usize,
+    /// Column index after alignment
+    pub layer: usize,
+    /// Left edge
+    pub x0: f64,
+    /// Right edge
+    pub x1: f64,
+    /// Top edge
+    pub y0: f64,
+    /// Bottom edge
+    pub y1: f64,
+    /// Indices of outgoing links, sorted top to bottom
+    pub source_links: Vec<usize>,
+    /// Indices of incoming links, sorted top to bottom
+    pub target_links: Vec<usize>,
+}
+
+impl SankeyNode {
+    /// Vertical center of the node
+    pub fn center_y(&self) -> f64 {
+        (self.y0 + self.y1) / 2.0
+    }
+
+    /// Check if a point is inside the node rectangle
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x0 && x <= self.x1 && y >= self.y0 && y <= self.y1
+    }
+}
+
+/// A positioned link of a sankey diagram
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SankeyLink {
+    /// Index in the input link list
+    pub index: usize,
+    /// Source node index
+    pub source: usize,
+    /// Target node index
+    pub target: usize,
+    /// Flow value
+    pub value: f64,
+    /// Breadth of the link band
+    pub width: f64,
+    /// Vertical center of the band at the source node
+    pub y0: f64,
+    /// Vertical center of the band at the target node
+    pub y1: f64,
+}
+
+/// Result of a sankey layout
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SankeyGraph {
+    /// Nodes in input order
+    pub nodes: Vec<SankeyNode>,
+    /// Links in input order
+    pub links: Vec<SankeyLink>,
+}
+
+impl SankeyGraph {
+    /// Number of columns
+    pub fn layer_count(&self) -> usize {
+        self.nodes.iter().map(|n| n.layer + 1).max().unwrap_or(0)
+    }
+
+    /// Horizontal centerline of a link, to be stroked with its width
+    ///
+    /// Matches `d3.sankeyLinkHorizontal()`.
+    pub fn link_path(&self, index: usize) -> Option<Path> {
+        let link = self.links.get(index)?;
+        let (source, target) = self.link_endpoints(link);
+        Some(LinkGenerator::horizontal().generate(source, target).into_iter().collect())
+    }
+
+    /// Closed outline of a link band, to be filled
+    pub fn link_ribbon(&self, index: usize) -> Option<Path> {
+        let link = self.links.get(index)?;
+        let (source, target) = self.link_endpoints(link);
+        let ribbon = LinkGenerator::horizontal().ribbon(source, target, link.width, link.width);
+        Some(ribbon.into_iter().collect())
+    }
+
+    /// Where a link leaves its source node and enters its target node
+    fn link_endpoints(&self, link: &SankeyLink) -> (Point, Point) {
+        (
+            Point::new(self.nodes[link.source].x1, link.y0),
+            Point::new(self.nodes[link.target].x0, link.y1),
+        )
+    }
+
+    /// Find the node under a point (e.g. for hover)
+    pub fn node_at(&self, x: f64, y: f64) -> Option<usize> {
+        self.nodes.iter().position(|n| n.contains(x, y))
+    }
+}
+
+/// Sankey layout
+///
+/// Mirrors `d3.sankey()`: configure the extent, node width and padding,
+/// alignment and relaxation iterations, then call
+/// [`layout`](SankeyLayout::layout) with node names and
+/// `(source, target, value)` links.
+#[derive(Clone, Debug)]
+pub struct SankeyLayout {
+    /// Layout extent as (x0, y0, x1, y1)
+    extent: (f64, f64, f64, f64),
+    /// Width of node rectangles
+    node_width: f64,
+    /// Vertical gap between nodes in a column
+    node_padding: f64,
+    /// Column assignment rule
+    align: SankeyAlign,
+    /// Number of relaxation passes
+    iterations: usize,
+}
+
+impl Default for SankeyLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SankeyLayout {
+    /// Create a new sankey layout with d3's defaults
+    pub fn new() -> Self {
+        Self {
+            extent: (0.0, 0.0, 1.0, 1.0),
+            node_width: 24.0,
+            node_padding: 8.0,
+            align: SankeyAlign::Justify,
+            iterations: 6,
+        }
+    }
+
+    /// Set the layout extent
+    pub fn extent(mut self, x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+        self.extent = (x0, y0, x1, y1);
+        self
+    }
+
+    /// Set the layout size, with the origin at (0, 0)
+    pub fn size(mut self, width: f64, height: f64) -> Self {
+        self.extent = (0.0, 0.0, width, height);
+        self
+    }
+
+    /// Set the node width
+    pub fn node_width(mut self, width: f64) -> Self {
+        self.node_width = width;
+        self
+    }
+
+    /// Set the vertical padding between nodes
+    pub fn node_padding(mut self, padding: f64) -> Self {
+        self.node_padding = padding;
+        self
+    }
+
+    /// Set the column alignment
+    pub fn align(mut self, align: SankeyAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the number of relaxation iterations
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Compute the layout
+    ///
+    /// Links are `(source, target, value)` with indices into `names`.
+    /// Returns an error for out-of-range indices, negative or non-finite
+    /// values, and circular links.
+    pub fn layout<S: AsRef<str>>(&self, names: &[S], links: &[(usize, usize, f64)]) -> D3Result<SankeyGraph> {
+        let mut graph = SankeyGraph {
+            nodes: names
+                .iter()
+                .map(|name| SankeyNode {
+                    name: name.as_ref().to_string(),
+                    ..SankeyNode::default()
+                })
+                .collect(),
+            links: Vec::with_capacity(links.len()),
+        };
+
+        let n = graph.nodes.len();
+        for (index, &(source, target, value)) in links.iter().enumerate() {
+            if source >= n || target >= n {
+                return Err(D3Error::invalid_data(format!(
+                    "sankey link {} references missing node ({} -> {})",
+                    index, source, target
+                )));
+            }
+            if !value.is_finite() {
+                return Err(D3Error::non_finite("sankey link value", value));
+            }
+            if value < 0.0 {
+                return Err(D3Error::invalid_data(format!("sankey link {} has negative value {}", index, value)));
+            }
+            graph.nodes[source].source_links.push(index);
+            graph.nodes[target].target_links.push(index);
+            graph.links.push(SankeyLink {
+                index,
+                source,
+                target,
+                value,
+                ..SankeyLink::default()
+            });
+        }
+
+        compute_node_values(&mut graph);
+        compute_node_depths(&mut graph)?;
+        let columns = self.compute_node_layers(&mut graph);
+        self.compute_node_breadths(&mut graph, columns);
+        compute_link_breadths(&mut graph);
+        Ok(graph)
+    }
+
+    /// Assign columns and x positions; returns node indices per column
+    fn compute_node_layers(&self, graph: &mut SankeyGraph) -> Vec<Vec<usize>> {
+        let (x0, _, x1, _) = self.extent;
+        let column_count = graph.nodes.iter().map(|n| n.depth + 1).max().unwrap_or(0);
+        let kx = if column_count > 1 {
+            (x1 - x0 - self.node_width) / (column_count - 1) as f64
+        } else {
+            0.0
+        };
+
+        let mut columns = vec![Vec::new(); column_count];
+        for i in 0..graph.nodes.len() {
+            let layer = self.align_node(graph, i, column_count).min(column_count.saturating_sub(1));
+            let node = &mut graph.nodes[i];
+            node.layer = layer;
+            node.x0 = x0 + layer as f64 * kx;
+            node.x1 = node.x0 + self.node_width;
+            columns[layer].push(i);
+        }
+        columns
+    }
+
+    /// Column of a node according to the alignment rule
+    fn align_node(&self, graph: &SankeyGraph, i: usize, n: usize) -> usize {
+        let node = &graph.nodes[i];
+        match self.align {
+            SankeyAlign::Left => node.depth,
+            SankeyAlign::Right => (n - 1).saturating_sub(node.height),
+            SankeyAlign::Justify => {
+                if node.source_links.is_empty() {
+                    n - 1
+                } else {
+                    node.depth
+                }
+            }
+            SankeyAlign::Center => {
+                if !node.target_links.is_empty() {
+                    node.depth
+                } else {
+                    node.source_links
+                        .iter()
+                        .map(|&l| graph.nodes[graph.links[l].target].depth)
+                        .min()
+                        .map_or(0, |d| d.saturating_sub(1))
+                }
+            }
+        }
+    }
+
+    /// Position nodes vertically and relax them toward their neighbours
+    fn compute_node_breadths(&self, graph: &mut SankeyGraph, mut columns: Vec<Vec<usize>>) {
+        let (_, y0, _, y1) = self.extent;
+        let max_len = columns.iter().map(Vec::len).max().unwrap_or(0);
+        let py = if max_len > 1 {
+            self.node_padding.min((y1 - y0) / (max_len - 1) as f64)
+        } else {
+            self.node_padding
+        };
+
+        // Initial breadths: one global scale, each column centered
+        let ky = columns
+            .iter()
+            .filter_map(|c| {
+                let total: f64 = c.iter().map(|&i| graph.nodes[i].value).sum();
+                (total > 0.0).then(|| (y1 - y0 - (c.len() as f64 - 1.0) * py) / total)
+            })
+            .fold(f64::INFINITY, f64::min);
+        let ky = if ky.is_finite() { ky.max(0.0) } else { 0.0 };
+
+        for column in &columns {
+            let mut y = y0;
+            for &i in column {
+                let node = &mut graph.nodes[i];
+                node.y0 = y;
+                node.y1 = y + node.value * ky;
+                y = node.y1 + py;
+                for &l in &node.source_links {
+                    graph.links[l].width = graph.links[l].value * ky;
+                }
+            }
+            let shift = (y1 - y + py) / (column.len() + 1) as f64;
+            for (k, &i) in column.iter().enumerate() {
+                let node = &mut graph.nodes[i];
+                node.y0 += shift * (k + 1) as f64;
+                node.y1 += shift * (k + 1) as f64;
+            }
+        }
+        reorder_links(graph);
+
+        let relax = Relax { y0, y1, py };
+        for i in 0..self.iterations {
+            let alpha = 0.99f64.powi(i as i32);
+            let beta = (1.0 - alpha).max((i + 1) as f64 / self.iterations as f64);
+            relax.right_to_left(graph, &mut columns, alpha, beta);
+            relax.left_to_right(graph, &mut columns, alpha, beta);
+        }
+    }
+}
+
+/// Node value is the larger of total inflow and total outflow
+fn compute_node_values(graph: &mut SankeyGraph) {
+    for node in &mut graph.nodes {
+        let out: f64 = node.source_links.iter().map(|&l| graph.links[l].value).sum();
+        let inc: f64 = node.target_links.iter().map(|&l| graph.links[l].value).sum();
+        node.value = out.max(inc);
+    }
+}
+
+/// Breadth-first depths from sources and heights from sinks
+fn compute_node_depths(graph: &mut SankeyGraph) -> D3Result<()> {
+    let n = graph.nodes.len();
+    for forward in [true, false] {
+        let mut current: Vec<usize> = (0..n).collect();
+        let mut level = 0;
+        while !current.is_empty() {
+            let mut next = Vec::new();
+            let mut seen = vec![false; n];
+            for &i in &current {
+                let node = &mut graph.nodes[i];
+                let links = if forward {
+                    node.depth = level;
+                    &node.source_links
+                } else {
+                    node.height = level;
+                    &node.target_links
+                };
+                for &l in links {
+                    let link = &graph.links[l];
+                    let other = if forward { link.target } else { link.source };
+                    if !seen[other] {
+                        seen[other] = true;
+                        next.push(other);
+                    }
+                }
+            }
+            level += 1;
+            if level > n {
+                return Err(D3Error::cycle_detected(graph.nodes[next[0]].name.clone()));
+            }
+            current = next;
+        }
+    }
+    Ok(())
+}
+
+/// Sort every node's links by the position of the node at the other end
+fn reorder_links(graph: &mut SankeyGraph) {
+    for i in 0..graph.nodes.len() {
+        sort_links(graph, i);
+    }
+}
+
+fn sort_links(graph: &mut SankeyGraph, i: usize) {
+    let SankeyGraph { nodes, links } = graph;
+    let key = |other: usize, l: usize| (nodes[other].y0, l);
+    let mut out = nodes[i].source_links.clone();
+    out.sort_by(|&a, &b| key(links[a].target, a).partial_cmp(&key(links[b].target, b)).unwrap());
+    let mut inc = nodes[i].target_links.clone();
+    inc.sort_by(|&a, &b| key(links[a].source, a).partial_cmp(&key(links[b].source, b)).unwrap());
+    nodes[i].source_links = out;
+    nodes[i].target_links = inc;
+}
+
+/// After moving a node, re-sort the links of its neighbours
+fn reorder_node_links(graph: &mut SankeyGraph, i: usize) {
+    let mut neighbours: Vec<usize> = graph.nodes[i].target_links.iter().map(|&l| graph.links[l].source).collect();
+    neighbours.extend(graph.nodes[i].source_links.iter().map(|&l| graph.links[l].target));
+    neighbours.sort_unstable();
+    neighbours.dedup();
+    for other in neighbours {
+        sort_links(graph, other);
+    }
+}
+
+/// Stack link bands along their source and target nodes
+fn compute_link_breadths(graph: &mut SankeyGraph) {
+    let SankeyGraph { nodes, links } = graph;
+    for node in nodes.iter() {
+        let mut y = node.y0;
+        for &l in &node.source_links {
+            links[l].y0 = y + links[l].width / 2.0;
+            y += links[l].width;
+        }
+        let mut y = node.y0;
+        for &l in &node.target_links {
+            links[l].y1 = y + links[l].width / 2.0;
+            y += links[l].width;
+        }
+    }
+}
+
+/// Relaxation and collision passes over the columns
+struct Relax {
+    y0: f64,
+    y1: f64,
+    py: f64,
+}
+
+impl Relax {
+    /// Move nodes toward the weighted center of their incoming links
+    fn left_to_right(&self, graph: &mut SankeyGraph, columns: &mut [Vec<usize>], alpha: f64, beta: f64) {
+        for column in columns.iter_mut().skip(1) {
+            for &target in column.iter() {
+                let (mut y, mut w) = (0.0, 0.0);
+                for &l in &graph.nodes[target].target_links {
+                    let link = &graph.links[l];
+                    let v = link.value * (graph.nodes[target].layer as f64 - graph.nodes[link.source].layer as f64);
+                    y += self.target_top(graph, l) * v;
+                    w += v;
+                }
+                if w > 0.0 {
+                    self.shift(graph, target, (y / w - graph.nodes[target].y0) * alpha);
+                }
+            }
+            self.sort_and_resolve(graph, column, beta);
+        }
+    }
+
+    /// Move nodes toward the weighted center of their outgoing links
+    fn right_to_left(&self, graph: &mut SankeyGraph, columns: &mut [Vec<usize>], alpha: f64, beta: f64) {
+        for c in (0..columns.len().saturating_sub(1)).rev() {
+            for &source in &columns[c] {
+                let (mut y, mut w) = (0.0, 0.0);
+                for &l in &graph.nodes[source].source_links {
+                    let link = &graph.links[l];
+                    let v = link.value * (graph.nodes[link.target].layer as f64 - graph.nodes[source].layer as f64);
+                    y += self.source_top(graph, l) * v;
+                    w += v;
+                }
+                if w > 0.0 {
+                    self.shift(graph, source, (y / w - graph.nodes[source].y0) * alpha);
+                }
+            }
+            self.sort_and_resolve(graph, &mut columns[c], beta);
+        }
+    }
+
+    fn shift(&self, graph: &mut SankeyGraph, i: usize, dy: f64) {
+        graph.nodes[i].y0 += dy;
+        graph.nodes[i].y1 += dy;
+        reorder_node_links(graph, i);
+    }
+
+    fn sort_and_resolve(&self, graph: &mut SankeyGraph, column: &mut [usize], beta: f64) {
+        column.sort_by(|&a, &b| graph.nodes[a].y0.partial_cmp(&graph.nodes[b].y0).unwrap());
+        self.resolve_collisions(graph, column, beta);
+    }
+
+    /// Push overlapping nodes apart, outward from the middle node
+    fn resolve_collisions(&self, graph: &mut SankeyGraph, column: &[usize], alpha: f64) {
+        if column.is_empty() {
+            return;
+        }
+        let mid = column.len() / 2;
+        let subject = &graph.nodes[column[mid]];
+        let (top, bottom) = (subject.y0 - self.py, subject.y1 + self.py);
+        self.bottom_to_top(graph, column, top, mid as isize - 1, alpha);
+        self.top_to_bottom(graph, column, bottom, mid + 1, alpha);
+        self.bottom_to_top(graph, column, self.y1, column.len() as isize - 1, alpha);
+        self.top_to_bottom(graph, column, self.y0, 0, alpha);
+    }
+
+    /// Push nodes down so none starts above `y`
+    fn top_to_bottom(&self, graph: &mut SankeyGraph, column: &[usize], mut y: f64, from: usize, alpha: f64) {
+        for &i in column.iter().skip(from) {
+            let dy = (y - graph.nodes[i].y0) * alpha;
+            if dy > 1e-6 {
+                graph.nodes[i].y0 += dy;
+                graph.nodes[i].y1 += dy;
+            }
+            y = graph.nodes[i].y1 + self.py;
+        }
+    }
+
+    /// Push nodes up so none ends below `y`
+    fn bottom_to_top(&self, graph: &mut SankeyGraph, column: &[usize], mut y: f64, from: isize, alpha: f64) {
+        if from < 0 {
+            return;
+        }
+        for &i in column[..=from as usize].iter().rev() {
+            let dy = (graph.nodes[i].y1 - y) * alpha;
+            if dy > 1e-6 {
+                graph.nodes[i].y0 -= dy;
+                graph.nodes[i].y1 -= dy;
+            }
+            y = graph.nodes[i].y0 - self.py;
+        }
+    }
+
+    /// Where a link's target node should start for the link to run straight
+    fn target_top(&self, graph: &SankeyGraph, l: usize) -> f64 {
+        let link = &graph.links[l];
+        let (source, target) = (&graph.nodes[link.source], &graph.nodes[link.target]);
+        let mut y = source.y0 - (source.source_links.len() as f64 - 1.0) * self.py / 2.0;
+        for &other in source.source_links.iter().take_while(|&&o| o != l) {
+            y += graph.links[other].width + self.py;
+        }
+        for &other in target.target_links.iter().take_while(|&&o| o != l) {
+            y -= graph.links[other].width;
+        }
+        y
+    }
+
+    /// Where a link's source node should start for the link to run straight
+    fn source_top(&self, graph: &SankeyGraph, l: usize) -> f64 {
+        let link = &graph.links[l];
+        let (source, target) = (&graph.nodes[link.source], &graph.nodes[link.target]);
+        let mut y = target.y0 - (target.target_links.len() as f64 - 1.0) * self.py / 2.0;
+        for &other in target.target_links.iter().take_while(|&&o| o != l) {
+            y += graph.links[other].width + self.py;
+        }
+        for &other in source.source_links.iter().take_while(|&&o| o != l) {
+            y -= graph.links[other].width;
+        }
+        y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn energy() -> (Vec<&'static str>, Vec<(usize, usize, f64)>) {
+        (
+            vec!["Coal", "Gas", "Electricity", "Heat", "Homes", "Industry", "Export"],
+            vec![
+                (0, 2, 80.0),
+                (0, 3, 20.0),
+                (1, 2, 50.0),
+                (1, 3, 30.0),
+                (2, 4, 60.0),
+                (2, 5, 70.0),
+                (3, 4, 50.0),
+                (1, 6, 10.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_values_depths_and_alignment() {
+        let (names, links) = energy();
+        let justify = SankeyLayout::new().size(300.0, 200.0).layout(&names, &links).unwrap();
+
+        assert_eq!(justify.nodes[1].value, 90.0);
+        assert_eq!(justify.nodes[2].value, 130.0);
+        assert_eq!(justify.nodes[6].depth, 1);
+        assert_eq!(justify.nodes[1].height, 2);
+        assert_eq!(justify.layer_count(), 3);
+        // Justify sends the sink "Export" to the last column
+        assert_eq!(justify.nodes[6].layer, 2);
+        assert_eq!(justify.nodes[6].x1, 300.0);
+
+        let left = SankeyLayout::new().size(300.0, 200.0).align(SankeyAlign::Left).layout(&names, &links).unwrap();
+        assert_eq!(left.nodes[6].layer, 1);
+
+        let right = SankeyLayout::new().size(300.0, 200.0).align(SankeyAlign::Right).layout(&names, &links).unwrap();
+        assert_eq!(right.nodes[6].layer, 2);
+        assert_eq!(right.nodes[0].layer, 0);
+    }
+
+    #[test]
+    fn test_center_moves_sources_next_to_targets() {
+        let names = ["A", "B", "C", "Late"];
+        let links = [(0, 1, 5.0), (1, 2, 5.0), (3, 2, 2.0)];
+        let graph = SankeyLayout::new().align(SankeyAlign::Center).layout(&names, &links).unwrap();
+        assert_eq!(graph.nodes[3].layer, 1);
+
+        let graph = SankeyLayout::new().align(SankeyAlign::Left).layout(&names, &links).unwrap();
+        assert_eq!(graph.nodes[3].layer, 0);
+    }
+
+    #[test]
+    fn test_nodes_fit_extent_without_overlap() {
+        let (names, links) = energy();
+        let graph = SankeyLayout::new()
+            .extent(10.0, 20.0, 310.0, 220.0)
+            .node_padding(10.0)
+            .iterations(32)
+            .layout(&names, &links)
+            .unwrap();
+
+        for layer in 0..graph.layer_count() {
+            let mut column: Vec<&SankeyNode> = graph.nodes.iter().filter(|n| n.layer == layer).collect();
+            column.sort_by(|a, b| a.y0.partial_cmp(&b.y0).unwrap());
+            for node in &column {
+                assert!(node.y0 >= 20.0 - 1e-6 && node.y1 <= 220.0 + 1e-6, "{:?}", node);
+            }
+            for pair in column.windows(2) {
+                assert!(pair[1].y0 - pair[0].y1 >= 10.0 - 1e-6);
+            }
+        }
+
+        // Node heights share one scale
+        let ky = graph.links[0].width / graph.links[0].value;
+        for node in &graph.nodes {
+            assert!((node.y1 - node.y0 - node.value * ky).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_link_bands_stack_within_nodes() {
+        let (names, links) = energy();
+        let graph = SankeyLayout::new().size(300.0, 200.0).layout(&names, &links).unwrap();
+
+        for node in &graph.nodes {
+            let mut y = node.y0;
+            for &l in &node.source_links {
+                let link = &graph.links[l];
+                assert!((link.y0 - (y + link.width / 2.0)).abs() < 1e-9);
+                y += link.width;
+            }
+            assert!(y <= node.y1 + 1e-9);
+        }
+
+        let ribbon = graph.link_ribbon(2).unwrap();
+        assert_eq!(ribbon.len(), 5);
+        assert!(graph.link_path(99).is_none());
+
+        let node = &graph.nodes[2];
+        assert_eq!(graph.node_at((node.x0 + node.x1) / 2.0, node.center_y()), Some(2));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let names = ["A", "B"];
+        assert!(SankeyLayout::new().layout(&names, &[(0, 2, 1.0)]).is_err());
+        assert!(SankeyLayout::new().layout(&names, &[(0, 1, -1.0)]).is_err());
+        assert!(SankeyLayout::new().layout(&names, &[(0, 1, f64::NAN)]).is_err());
+        assert!(matches!(
+            SankeyLayout::new().layout(&names, &[(0, 1, 1.0), (1, 0, 1.0)]),
+            Err(D3Error::CycleDetected { .. })
+        ));
+
+        let empty = SankeyLayout::new().layout::<&str>(&[], &[]).unwrap();
+        assert!(empty.nodes.is_empty());
+    }
+}
@@ -0,0 +1,516 @@
+//! Slope chart and bump chart layouts
+//!
+//! A slope chart compares categories at two points in time, connecting each
+//! category's start and end value with a line and labelling both ends. A
+//! bump chart follows each category's rank over many steps. Both layouts
+//! place labels so they don't collide, and share the [`rank`] helper for
+//! turning values into positions.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::layout::slope::{BumpLayout, SlopeLayout, SlopeDirection};
+//!
+//! // Slope chart: (2020, 2024) values per category
+//! let chart = SlopeLayout::new()
+//!     .size(300.0, 200.0)
+//!     .label_spacing(12.0)
+//!     .compute(&[(10.0, 30.0), (25.0, 20.0), (26.0, 21.0)]);
+//! assert_eq!(chart.lines[0].direction, SlopeDirection::Up);
+//! let ends: Vec<f64> = chart.lines.iter().map(|l| l.end_label_y).collect();
+//! assert!((ends[1] - ends[2]).abs() >= 12.0);
+//!
+//! // Bump chart: values[category][step], highest value ranks first
+//! let bump = BumpLayout::new().size(300.0, 90.0).compute(&[
+//!     vec![5.0, 3.0, 1.0],
+//!     vec![3.0, 4.0, 6.0],
+//!     vec![1.0, 5.0, 2.0],
+//! ]);
+//! assert_eq!(bump.series[0].points[0].rank, 0);
+//! assert_eq!(bump.series[1].points[2].rank, 0);
+//! assert!(!bump.series[0].path().is_empty());
+//! ```
+
+use crate::shape::{Path, Point};
+
+/// Which end of the value range ranks first
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RankOrder {
+    /// Highest value is rank 0
+    #[default]
+    Descending,
+    /// Lowest value is rank 0
+    Ascending,
+}
+
+/// Competition ranks of values: ties share the lowest rank ("1224" ranking)
+///
+/// Non-finite values have no rank.
+pub fn rank(values: &[f64], order: RankOrder) -> Vec<Option<usize>> {
+    let sorted = sorted_indices(values, order, |_, _| std::cmp::Ordering::Equal);
+    let mut ranks = vec![None; values.len()];
+    for (position, &i) in sorted.iter().enumerate() {
+        let tied = position > 0 && values[sorted[position - 1]] == values[i];
+        ranks[i] = if tied { ranks[sorted[position - 1]] } else { Some(position) };
+    }
+    ranks
+}
+
+/// Finite value indices in rank order, ties broken by `tie`
+fn sorted_indices(
+    values: &[f64],
+    order: RankOrder,
+    tie: impl Fn(usize, usize) -> std::cmp::Ordering,
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..values.len()).filter(|&i| values[i].is_finite()).collect();
+    indices.sort_by(|&a, &b| {
+        let by_value = match order {
+            RankOrder::Descending => values[b].total_cmp(&values[a]),
+            RankOrder::Ascending => values[a].total_cmp(&values[b]),
+        };
+        by_value.then_with(|| tie(a, b)).then(a.cmp(&b))
+    });
+    indices
+}
+
+/// Spread label positions at least `spacing` apart, keeping them in
+/// `[min, max]` where possible and preserving their order
+fn dodge(ys: &mut [f64], spacing: f64, min: f64, max: f64) {
+    let mut order: Vec<usize> = (0..ys.len()).collect();
+    order.sort_by(|&a, &b| ys[a].total_cmp(&ys[b]).then(a.cmp(&b)));
+    for k in 1..order.len() {
+        ys[order[k]] = ys[order[k]].max(ys[order[k - 1]] + spacing);
+    }
+    if let Some(&last) = order.last() {
+        ys[last] = ys[last].min(max);
+    }
+    for k in (0..order.len().saturating_sub(1)).rev() {
+        ys[order[k]] = ys[order[k]].min(ys[order[k + 1]] - spacing);
+    }
+    if let Some(&first) = order.first() {
+        if ys[first] < min {
+            let shift = min - ys[first];
+            for &i in &order {
+                ys[i] += shift;
+            }
+        }
+    }
+}
+
+/// How a slope chart maps values to height
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SlopeScale {
+    /// Linear in value over the combined extent of both ends
+    #[default]
+    Value,
+    /// Evenly spaced by rank at each end
+    Rank,
+}
+
+/// Direction of change between the two ends of a slope line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlopeDirection {
+    /// End value is higher
+    Up,
+    /// End value is lower
+    Down,
+    /// Values are equal
+    Flat,
+}
+
+/// One category's line in a slope chart
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlopeLine {
+    /// Index of the category in the input
+    pub index: usize,
+    /// Value at the first time point
+    pub start_value: f64,
+    /// Value at the second time point
+    pub end_value: f64,
+    /// Rank at the first time point
+    pub start_rank: usize,
+    /// Rank at the second time point
+    pub end_rank: usize,
+    /// Left endpoint
+    pub start: Point,
+    /// Right endpoint
+    pub end: Point,
+    /// Label y on the left side, dodged against the other start labels
+    pub start_label_y: f64,
+    /// Label y on the right side, dodged against the other end labels
+    pub end_label_y: f64,
+    /// Direction of change
+    pub direction: SlopeDirection,
+}
+
+impl SlopeLine {
+    /// Straight path from start to end
+    pub fn path(&self) -> Path {
+        let mut path = Path::new();
+        path.move_to(self.start.x, self.start.y).line_to(self.end.x, self.end.y);
+        path
+    }
+}
+
+/// Result of a slope chart layout
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SlopeChart {
+    /// Lines for categories with two finite values, in input order
+    pub lines: Vec<SlopeLine>,
+    /// Value extent mapped to the height (value scale)
+    pub domain: (f64, f64),
+}
+
+/// Slope chart layout
+#[derive(Clone, Debug)]
+pub struct SlopeLayout {
+    extent: (f64, f64, f64, f64),
+    scale: SlopeScale,
+    order: RankOrder,
+    label_spacing: f64,
+}
+
+impl Default for SlopeLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlopeLayout {
+    /// Create a slope layout over a unit extent
+    pub fn new() -> Self {
+        Self {
+            extent: (0.0, 0.0, 1.0, 1.0),
+            scale: SlopeScale::Value,
+            order: RankOrder::Descending,
+            label_spacing: 0.0,
+        }
+    }
+
+    /// Set the layout extent; the two ends sit on its left and right edges
+    pub fn extent(mut self, x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+        self.extent = (x0, y0, x1, y1);
+        self
+    }
+
+    /// Set the layout size, with the origin at (0, 0)
+    pub fn size(mut self, width: f64, height: f64) -> Self {
+        self.extent = (0.0, 0.0, width, height);
+        self
+    }
+
+    /// Set how values map to height
+    pub fn scale(mut self, scale: SlopeScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set which end of the value range ranks first (and sits on top)
+    pub fn order(mut self, order: RankOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Set the minimum vertical distance between labels on the same side
+    pub fn label_spacing(mut self, spacing: f64) -> Self {
+        self.label_spacing = spacing.max(0.0);
+        self
+    }
+
+    /// Lay out `(start, end)` values per category
+    ///
+    /// Categories with a non-finite value at either end are left out.
+    pub fn compute(&self, values: &[(f64, f64)]) -> SlopeChart {
+        let (x0, y0, x1, y1) = self.extent;
+        let valid: Vec<usize> = (0..values.len())
+            .filter(|&i| values[i].0.is_finite() && values[i].1.is_finite())
+            .collect();
+        let starts: Vec<f64> = valid.iter().map(|&i| values[i].0).collect();
+        let ends: Vec<f64> = valid.iter().map(|&i| values[i].1).collect();
+        let start_ranks = rank(&starts, self.order);
+        let end_ranks = rank(&ends, self.order);
+
+        let (lo, hi) = starts
+            .iter()
+            .chain(&ends)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        let domain = if lo.is_finite() { (lo, hi) } else { (0.0, 1.0) };
+
+        let n = valid.len();
+        let y_of = |value: f64, rank: usize| match self.scale {
+            SlopeScale::Value => {
+                let t = if domain.1 > domain.0 { (value - domain.0) / (domain.1 - domain.0) } else { 0.5 };
+                match self.order {
+                    RankOrder::Descending => y1 - t * (y1 - y0),
+                    RankOrder::Ascending => y0 + t * (y1 - y0),
+                }
+            }
+            SlopeScale::Rank => y0 + (rank as f64 + 0.5) * (y1 - y0) / n as f64,
+        };
+
+        let mut lines: Vec<SlopeLine> = valid
+            .iter()
+            .enumerate()
+            .map(|(k, &index)| {
+                let (start_value, end_value) = values[index];
+                let start_rank = start_ranks[k].unwrap_or(0);
+                let end_rank = end_ranks[k].unwrap_or(0);
+                let start = Point::new(x0, y_of(start_value, start_rank));
+                let end = Point::new(x1, y_of(end_value, end_rank));
+                SlopeLine {
+                    index,
+                    start_value,
+                    end_value,
+                    start_rank,
+                    end_rank,
+                    start,
+                    end,
+                    start_label_y: start.y,
+                    end_label_y: end.y,
+                    direction: if end_value > start_value {
+                        SlopeDirection::Up
+                    } else if end_value < start_value {
+                        SlopeDirection::Down
+                    } else {
+                        SlopeDirection::Flat
+                    },
+                }
+            })
+            .collect();
+
+        if self.label_spacing > 0.0 {
+            let mut start_ys: Vec<f64> = lines.iter().map(|l| l.start_label_y).collect();
+            let mut end_ys: Vec<f64> = lines.iter().map(|l| l.end_label_y).collect();
+            dodge(&mut start_ys, self.label_spacing, y0, y1);
+            dodge(&mut end_ys, self.label_spacing, y0, y1);
+            for (line, (s, e)) in lines.iter_mut().zip(start_ys.into_iter().zip(end_ys)) {
+                line.start_label_y = s;
+                line.end_label_y = e;
+            }
+        }
+
+        SlopeChart { lines, domain }
+    }
+}
+
+/// A ranked position of one category at one step
+#[derive(Clone, Debug, PartialEq)]
+pub struct BumpPoint {
+    /// Step (column) index
+    pub step: usize,
+    /// Rank at this step (0 on top)
+    pub rank: usize,
+    /// Value at this step
+    pub value: f64,
+    /// Screen x
+    pub x: f64,
+    /// Screen y
+    pub y: f64,
+}
+
+/// One category's track through a bump chart
+#[derive(Clone, Debug, PartialEq)]
+pub struct BumpSeries {
+    /// Index of the category in the input
+    pub index: usize,
+    /// Ranked points at steps where the category has a finite value
+    pub points: Vec<BumpPoint>,
+}
+
+impl BumpSeries {
+    /// Point at which to anchor a left-side label
+    pub fn start_label(&self) -> Option<Point> {
+        self.points.first().map(|p| Point::new(p.x, p.y))
+    }
+
+    /// Point at which to anchor a right-side label
+    pub fn end_label(&self) -> Option<Point> {
+        self.points.last().map(|p| Point::new(p.x, p.y))
+    }
+
+    /// Smooth path through the points, with horizontal tangents at each
+    /// step (like `d3.curveBumpX`); missing steps break the path
+    pub fn path(&self) -> Path {
+        let mut path = Path::new();
+        for (k, p) in self.points.iter().enumerate() {
+            match k.checked_sub(1).map(|j| &self.points[j]) {
+                Some(prev) if prev.step + 1 == p.step => {
+                    let mx = (prev.x + p.x) / 2.0;
+                    path.curve_to(mx, prev.y, mx, p.y, p.x, p.y);
+                }
+                _ => {
+                    path.move_to(p.x, p.y);
+                }
+            }
+        }
+        path
+    }
+}
+
+/// Result of a bump chart layout
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BumpChart {
+    /// One series per input category
+    pub series: Vec<BumpSeries>,
+    /// Number of steps
+    pub steps: usize,
+    /// Category index at each rank, per step (`order[step][rank]`)
+    pub order: Vec<Vec<usize>>,
+}
+
+/// Bump chart layout
+///
+/// Ranks are unique at every step, so each category gets its own row and
+/// end labels never overlap. Tied values keep the order they had at the
+/// previous step, so lines don't cross without a real change.
+#[derive(Clone, Debug)]
+pub struct BumpLayout {
+    extent: (f64, f64, f64, f64),
+    order: RankOrder,
+}
+
+impl Default for BumpLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BumpLayout {
+    /// Create a bump layout over a unit extent
+    pub fn new() -> Self {
+        Self {
+            extent: (0.0, 0.0, 1.0, 1.0),
+            order: RankOrder::Descending,
+        }
+    }
+
+    /// Set the layout extent
+    pub fn extent(mut self, x0: f64, y0: f64, x1: f64, y1: f64) -> Self {
+        self.extent = (x0, y0, x1, y1);
+        self
+    }
+
+    /// Set the layout size, with the origin at (0, 0)
+    pub fn size(mut self, width: f64, height: f64) -> Self {
+        self.extent = (0.0, 0.0, width, height);
+        self
+    }
+
+    /// Set which end of the value range ranks first
+    pub fn order(mut self, order: RankOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Lay out values indexed as `values[category][step]`
+    ///
+    /// Rows may be ragged; missing and non-finite values leave the category
+    /// unranked at that step.
+    pub fn compute(&self, values: &[Vec<f64>]) -> BumpChart {
+        let (x0, y0, x1, y1) = self.extent;
+        let n = values.len();
+        let steps = values.iter().map(Vec::len).max().unwrap_or(0);
+        let row_height = if n > 0 { (y1 - y0) / n as f64 } else { 0.0 };
+        let x_of = |step: usize| {
+            if steps > 1 {
+                x0 + (x1 - x0) * step as f64 / (steps - 1) as f64
+            } else {
+                (x0 + x1) / 2.0
+            }
+        };
+
+        let mut series: Vec<BumpSeries> = (0..n)
+            .map(|index| BumpSeries {
+                index,
+                points: Vec::new(),
+            })
+            .collect();
+        let mut order = Vec::with_capacity(steps);
+        let mut previous: Vec<Option<usize>> = vec![None; n];
+
+        for step in 0..steps {
+            let column: Vec<f64> = values
+                .iter()
+                .map(|row| row.get(step).copied().unwrap_or(f64::NAN))
+                .collect();
+            let prev = previous.clone();
+            let ranked = sorted_indices(&column, self.order, |a, b| match (prev[a], prev[b]) {
+                (Some(ra), Some(rb)) => ra.cmp(&rb),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            });
+
+            previous = vec![None; n];
+            for (r, &category) in ranked.iter().enumerate() {
+                previous[category] = Some(r);
+                series[category].points.push(BumpPoint {
+                    step,
+                    rank: r,
+                    value: column[category],
+                    x: x_of(step),
+                    y: y0 + (r as f64 + 0.5) * row_height,
+                });
+            }
+            order.push(ranked);
+        }
+
+        BumpChart { series, steps, order }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_competition_ties() {
+        let values = [3.0, 5.0, 3.0, f64::NAN, 1.0];
+        assert_eq!(rank(&values, RankOrder::Descending), vec![Some(1), Some(0), Some(1), None, Some(3)]);
+        assert_eq!(rank(&values, RankOrder::Ascending), vec![Some(1), Some(3), Some(1), None, Some(0)]);
+    }
+
+    #[test]
+    fn test_slope_value_and_rank_scales() {
+        let values = [(0.0, 10.0), (10.0, 5.0), (f64::NAN, 1.0)];
+        let chart = SlopeLayout::new().size(100.0, 50.0).compute(&values);
+        assert_eq!(chart.lines.len(), 2);
+        assert_eq!(chart.domain, (0.0, 10.0));
+        let a = &chart.lines[0];
+        assert_eq!((a.start, a.end), (Point::new(0.0, 50.0), Point::new(100.0, 0.0)));
+        assert_eq!((a.start_rank, a.end_rank, a.direction), (1, 0, SlopeDirection::Up));
+        assert_eq!(chart.lines[1].end.y, 25.0);
+
+        let ranked = SlopeLayout::new().size(100.0, 40.0).scale(SlopeScale::Rank).compute(&values);
+        assert_eq!(ranked.lines[0].start.y, 30.0);
+        assert_eq!(ranked.lines[1].start.y, 10.0);
+    }
+
+    #[test]
+    fn test_slope_labels_dodge_within_extent() {
+        let values = [(1.0, 100.0), (2.0, 99.0), (3.0, 98.5), (0.0, 0.0)];
+        let chart = SlopeLayout::new().size(100.0, 100.0).label_spacing(10.0).compute(&values);
+        let mut ends: Vec<f64> = chart.lines.iter().map(|l| l.end_label_y).collect();
+        // Order is preserved and labels stay inside the extent
+        assert!(ends[0] < ends[1] && ends[1] < ends[2]);
+        assert!(ends[0] >= 0.0);
+        ends.sort_by(f64::total_cmp);
+        assert!(ends.windows(2).all(|w| w[1] - w[0] >= 10.0 - 1e-9));
+        assert_eq!(chart.lines[3].end_label_y, 100.0);
+    }
+
+    #[test]
+    fn test_bump_ties_keep_previous_order() {
+        let values = vec![vec![1.0, 4.0, 4.0], vec![2.0, 4.0, 5.0], vec![0.0, f64::NAN]];
+        let bump = BumpLayout::new().size(20.0, 30.0).compute(&values);
+        assert_eq!(bump.steps, 3);
+        assert_eq!(bump.order, vec![vec![1, 0, 2], vec![1, 0], vec![1, 0]]);
+
+        let c = &bump.series[2];
+        assert_eq!(c.points.len(), 1);
+        assert_eq!(c.end_label(), Some(Point::new(0.0, 25.0)));
+        assert_eq!(bump.series[0].points[2].x, 20.0);
+        // One move plus one curve per consecutive step
+        assert_eq!(bump.series[0].path().len(), 3);
+    }
+}
@@ -37,8 +37,16 @@
 //! - [`interaction`]: Interactive behaviors (zoom, brush, tooltip)
 //! - [`layout`]: Layout algorithms (force simulation, tree, treemap, pack)
 //! - [`geo`]: Geographic projections and GeoJSON support
+//! - [`contour`]: Contour lines and density estimation (marching squares, KDE)
 //! - [`component`]: Reusable UI components (legend, tooltip, crosshair, annotation)
+//! - [`animation`]: Animation timing helpers (transitions, easing, staggered reveals, snapshot playback)
+//! - [`diagnostics`]: Configuration checks reported as structured warnings
+//! - [`cache`]: Content-keyed LRU cache for derived artifacts (meshes, tick layouts)
+//! - [`render`]: Render backend trait and path tessellation into triangle lists
+//! - [`time`]: Calendar interval arithmetic (d3-time equivalent, UTC and local)
+//! - [`stats`]: Summary statistics, ticks and grouping over arrays (d3-array equivalent)
 //! - [`error`]: Error types
+//! - [`prelude`]: Umbrella and per-subsystem imports
 //!
 //! # Features
 //!
@@ -58,62 +66,17 @@ pub mod color;
 pub mod interaction;
 pub mod layout;
 pub mod geo;
+pub mod contour;
 pub mod component;
+pub mod animation;
+pub mod diagnostics;
+pub mod cache;
+pub mod render;
+pub mod time;
+pub mod stats;
+pub mod prelude;
 
-/// Prelude module for convenient imports
-pub mod prelude {
-    pub use crate::error::{D3Error, D3Result};
-    pub use crate::data::{DataPoint, Dataset, PointStyle, ChartData, Color};
-    pub use crate::scale::{
-        Scale, ContinuousScale, DiscreteScale, ScaleExt,
-        LinearScale, CategoryScale,
-        TimeScale, TimeTick, TimeInterval,
-        LogScale, PowScale, SymlogScale,
-        Tick, TickOptions,
-        nice_step, nice_bounds, format_number,
-    };
-    pub use crate::axis::{
-        Axis, AxisConfig, AxisLayout, AxisOrientation, AxisTick,
-        NumberFormat, DurationFormat, format_si,
-    };
-    pub use crate::shape::{
-        Path, PathSegment, Point,
-        LineGenerator, AreaGenerator,
-        ArcGenerator, ArcDatum,
-        PieLayout, PieSlice, PieSort,
-        StackGenerator, StackedSeries, StackPoint, StackOrder, StackOffset,
-    };
-    pub use crate::color::{
-        Rgba, Hsl,
-        ColorScale, SequentialScale, DivergingScale, CategoricalScale,
-        lerp_color, hex, rgb, rgba, hsl,
-    };
-    pub use crate::interaction::{
-        ZoomTransform, ZoomBehavior,
-        BrushType, BrushBehavior, BrushSelection,
-        TooltipContent,
-    };
-    pub use crate::layout::{
-        ForceSimulation, SimulationNode, SimulationLink,
-        Force, ManyBodyForce, LinkForce, CollideForce, CenterForce, PositionForce, RadialForce,
-        HierarchyNode, TreeLayout, TreemapLayout, PackLayout,
-        TilingMethod, PackStrategy,
-    };
-    pub use crate::geo::{
-        Projection, ProjectionBuilder,
-        MercatorProjection, EquirectangularProjection, OrthographicProjection, AlbersProjection,
-        GeoJson, Feature, FeatureCollection, Geometry, GeometryType,
-        Position, BoundingBox, Properties,
-        GeoPath, GeoPathSegment,
-    };
-    pub use crate::component::{
-        Legend, LegendItem, LegendOrientation, LegendPosition,
-        TooltipWidget, TooltipConfig,
-        Crosshair, CrosshairMode,
-        Annotation, AnnotationLayer, AnnotationType,
-        ReferenceLine, ReferenceLineSet,
-    };
-}
+mod rng;
 
 // Re-export Color from data module at crate root for convenience
 pub use data::Color;
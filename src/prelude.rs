@@ -0,0 +1,99 @@
+//! Prelude modules for convenient imports
+//!
+//! `use makepad_d3::prelude::*` brings in the everyday types of every
+//! subsystem. Downstream code that only needs part of the library can import
+//! a narrower prelude instead and keep its namespace small:
+//!
+//! - [`base`]: Data, scales, axes, shapes, colors and errors
+//! - [`layout`]: Force simulation, hierarchies, chord and sankey layouts
+//! - [`geo`]: Projections, GeoJSON, geographic paths and contours
+//! - [`interactive`]: Zoom, brush, selection, UI components and animation
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::prelude::base::*;
+//!
+//! let y = LinearScale::new().with_domain(0.0, 100.0).with_range(300.0, 0.0);
+//! assert_eq!(y.scale(50.0), 150.0);
+//! ```
+
+/// Data, scales, axes, shapes, colors and errors
+pub mod base {
+    pub use crate::error::{D3Error, D3Result};
+    pub use crate::data::{DataPoint, Dataset, PointStyle, ChartData, Color};
+    pub use crate::scale::{
+        Scale, ContinuousScale, DiscreteScale, ScaleExt,
+        LinearScale, CategoryScale,
+        TimeScale, TimeTick, TimeInterval,
+        LogScale, PowScale, SymlogScale, RadialScale,
+        Tick, TickOptions,
+        nice_step, nice_bounds, format_number,
+    };
+    pub use crate::axis::{
+        Axis, AxisConfig, AxisLayout, AxisOrientation, AxisTick,
+        NumberFormat, DurationFormat, format_si,
+    };
+    pub use crate::shape::{
+        Path, PathSegment, Point,
+        LineGenerator, AreaGenerator,
+        ArcGenerator, ArcDatum,
+        PieLayout, PieSlice, PieSort,
+        StackGenerator, StackedSeries, StackPoint, StackOrder, StackOffset,
+        LinkGenerator, Symbol, SymbolGenerator,
+    };
+    pub use crate::color::{
+        Rgba, Hsl,
+        ColorScale, SequentialScale, DivergingScale, CategoricalScale,
+        lerp_color, hex, rgb, rgba, hsl,
+    };
+}
+
+/// Force simulation, hierarchies, chord and sankey layouts
+pub mod layout {
+    pub use crate::layout::{
+        ForceSimulation, SimulationNode, SimulationLink, SimulationEvent, Convergence,
+        Force, ManyBodyForce, LinkForce, CollideForce, CenterForce, PositionForce, RadialForce,
+        HierarchyNode, TreeLayout, ClusterLayout, TreemapLayout, PackLayout, PartitionLayout,
+        PartitionNode, TilingMethod, PackStrategy,
+        ChordLayout, ChordSet, RibbonGenerator,
+        SankeyLayout, SankeyGraph, SankeyNode, SankeyLink, SankeyAlign,
+    };
+}
+
+/// Projections, GeoJSON, geographic paths and contours
+pub mod geo {
+    pub use crate::geo::{
+        Projection, ProjectionBuilder,
+        MercatorProjection, EquirectangularProjection, OrthographicProjection, AlbersProjection,
+        GeoJson, Feature, FeatureCollection, Geometry, GeometryType,
+        Position, BoundingBox, Properties,
+        GeoPath, GeoPathSegment,
+    };
+    pub use crate::contour::{Contour, ContourGenerator, ContourDensity, Thresholds};
+}
+
+/// Zoom, brush, selection, UI components and animation
+pub mod interactive {
+    pub use crate::interaction::{
+        ZoomTransform, ZoomBehavior,
+        BrushType, BrushBehavior, BrushSelection,
+        SelectionModel, Emphasis,
+        TooltipContent,
+    };
+    pub use crate::component::{
+        Legend, LegendItem, LegendOrientation, LegendPosition,
+        TooltipWidget, TooltipConfig,
+        Crosshair, CrosshairMode,
+        Annotation, AnnotationLayer, AnnotationType,
+        ReferenceLine, ReferenceLineSet,
+    };
+    pub use crate::animation::{
+        Easing, Transition, TransitionManager, HighlightController, HighlightStyle,
+    };
+}
+
+pub use self::base::*;
+pub use self::geo::*;
+pub use self::interactive::*;
+pub use self::layout::*;
@@ -0,0 +1,381 @@
+//! Frame-budget-aware detail selection
+//!
+//! [`RenderBudget`] decides, per element and per frame, whether to draw at
+//! full detail, decimated, or by reusing a cached artifact (e.g. a mesh from
+//! [`ArtifactCache`](crate::cache::ArtifactCache)) so interaction stays
+//! smooth on slow hardware. Costs are estimated from element sizes with a
+//! [`CostModel`], and the estimates are continuously calibrated against the
+//! measured frame times reported through [`RenderBudget::end_frame`].
+//!
+//! All times are in milliseconds.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::render::{DetailLevel, ElementCost, RenderBudget};
+//!
+//! let mut budget = RenderBudget::new(16.0);
+//!
+//! budget.begin_frame();
+//! let line = budget.plan(&ElementCost::points(50_000));
+//! let map = budget.plan(&ElementCost::features(200).with_segments(400_000));
+//! assert_eq!(line, DetailLevel::Full);
+//! assert_ne!(map, DetailLevel::Full);
+//!
+//! // Report what the frame actually took to calibrate future estimates
+//! budget.end_frame(14.0);
+//! assert!(budget.average_frame_time() > 0.0);
+//! ```
+
+/// How much detail to render an element with this frame
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DetailLevel {
+    /// Render every point and segment
+    #[default]
+    Full,
+    /// Render a reduced version (fewer points, simplified geometry)
+    Decimated,
+    /// Reuse the last rendered artifact without recomputing
+    Cached,
+}
+
+/// Size of an element's rendering work
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ElementCost {
+    /// Data points (markers, line vertices)
+    pub points: usize,
+    /// Path segments (area outlines, projected geometry)
+    pub segments: usize,
+    /// Features (map regions, nodes) with per-feature overhead
+    pub features: usize,
+}
+
+impl ElementCost {
+    /// Cost of an element made of points
+    pub fn points(points: usize) -> Self {
+        Self {
+            points,
+            ..Self::default()
+        }
+    }
+
+    /// Cost of an element made of features
+    pub fn features(features: usize) -> Self {
+        Self {
+            features,
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of points
+    pub fn with_points(mut self, points: usize) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Set the number of path segments
+    pub fn with_segments(mut self, segments: usize) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Set the number of features
+    pub fn with_features(mut self, features: usize) -> Self {
+        self.features = features;
+        self
+    }
+}
+
+/// Estimated milliseconds per unit of work
+///
+/// The defaults are rough figures for a mid-range laptop; calibration in
+/// [`RenderBudget`] corrects them for the actual machine.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostModel {
+    /// Cost per data point
+    pub per_point: f64,
+    /// Cost per path segment
+    pub per_segment: f64,
+    /// Cost per feature
+    pub per_feature: f64,
+    /// Fixed cost per element
+    pub per_element: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            per_point: 0.0001,
+            per_segment: 0.00005,
+            per_feature: 0.01,
+            per_element: 0.05,
+        }
+    }
+}
+
+impl CostModel {
+    /// Uncalibrated cost estimate of an element
+    pub fn estimate(&self, cost: &ElementCost) -> f64 {
+        self.per_element
+            + cost.points as f64 * self.per_point
+            + cost.segments as f64 * self.per_segment
+            + cost.features as f64 * self.per_feature
+    }
+}
+
+/// Per-frame detail planner with rolling frame cost tracking
+#[derive(Clone, Debug)]
+pub struct RenderBudget {
+    /// Time available per frame
+    budget: f64,
+    /// Cost model for estimates
+    model: CostModel,
+    /// Fraction of the full cost a decimated render takes
+    decimation_ratio: f64,
+    /// Weight of the newest frame in rolling averages
+    smoothing: f64,
+    /// Measured / estimated cost, applied to estimates
+    calibration: f64,
+    /// Rolling average of measured frame times
+    average: Option<f64>,
+    /// Estimated cost planned in the current frame
+    planned: f64,
+    /// Frames reported through end_frame
+    frames: u64,
+    /// Frames that exceeded the budget
+    over_budget_frames: u64,
+}
+
+impl RenderBudget {
+    /// Create a budget of `frame_ms` per frame (e.g. 16.0 for 60 fps)
+    pub fn new(frame_ms: f64) -> Self {
+        Self {
+            budget: frame_ms.max(0.0),
+            model: CostModel::default(),
+            decimation_ratio: 0.25,
+            smoothing: 0.2,
+            calibration: 1.0,
+            average: None,
+            planned: 0.0,
+            frames: 0,
+            over_budget_frames: 0,
+        }
+    }
+
+    /// Set the cost model
+    pub fn with_cost_model(mut self, model: CostModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Set the fraction of the full cost a decimated render takes (default 0.25)
+    pub fn with_decimation_ratio(mut self, ratio: f64) -> Self {
+        self.decimation_ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the weight of the newest frame in rolling averages (default 0.2)
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        self.smoothing = smoothing.clamp(0.01, 1.0);
+        self
+    }
+
+    /// Get the per-frame budget
+    pub fn budget(&self) -> f64 {
+        self.budget
+    }
+
+    /// Change the per-frame budget
+    pub fn set_budget(&mut self, frame_ms: f64) {
+        self.budget = frame_ms.max(0.0);
+    }
+
+    /// Get the cost model
+    pub fn cost_model(&self) -> &CostModel {
+        &self.model
+    }
+
+    /// Ratio of measured to estimated cost learned so far
+    pub fn calibration(&self) -> f64 {
+        self.calibration
+    }
+
+    /// Calibrated cost estimate of an element at full detail
+    pub fn estimate(&self, cost: &ElementCost) -> f64 {
+        self.model.estimate(cost) * self.calibration
+    }
+
+    /// Start planning a new frame
+    pub fn begin_frame(&mut self) {
+        self.planned = 0.0;
+    }
+
+    /// Budget left in the current frame after the elements planned so far
+    pub fn remaining(&self) -> f64 {
+        (self.budget - self.planned).max(0.0)
+    }
+
+    /// Choose the detail level for an element and reserve its cost
+    ///
+    /// Elements are planned in priority order: earlier elements get full
+    /// detail first, later ones are decimated or cached once the frame
+    /// budget runs out. Cached elements are assumed to cost nothing.
+    pub fn plan(&mut self, cost: &ElementCost) -> DetailLevel {
+        let full = self.estimate(cost);
+        let remaining = self.remaining();
+        let (level, spent) = if full <= remaining {
+            (DetailLevel::Full, full)
+        } else if full * self.decimation_ratio <= remaining {
+            (DetailLevel::Decimated, full * self.decimation_ratio)
+        } else {
+            (DetailLevel::Cached, 0.0)
+        };
+        self.planned += spent;
+        level
+    }
+
+    /// Number of points that fit in the remaining frame budget
+    ///
+    /// Useful as the target size when decimating a line (e.g. with LTTB).
+    pub fn point_budget(&self) -> usize {
+        let per_point = self.model.per_point * self.calibration;
+        if per_point <= 0.0 {
+            return usize::MAX;
+        }
+        let available = self.remaining() - self.model.per_element * self.calibration;
+        (available.max(0.0) / per_point).floor() as usize
+    }
+
+    /// Report the measured duration of the frame and calibrate estimates
+    pub fn end_frame(&mut self, actual_ms: f64) {
+        if !actual_ms.is_finite() || actual_ms < 0.0 {
+            return;
+        }
+        let a = self.smoothing;
+        self.average = Some(match self.average {
+            Some(avg) => avg + a * (actual_ms - avg),
+            None => actual_ms,
+        });
+
+        // Only full or decimated work was estimated; ignore frames that
+        // were entirely cached
+        if self.planned > 0.0 && actual_ms > 0.0 {
+            let uncalibrated = self.planned / self.calibration;
+            let ratio = (actual_ms / uncalibrated).clamp(0.1, 10.0);
+            self.calibration += a * (ratio - self.calibration);
+        }
+
+        self.frames += 1;
+        if actual_ms > self.budget {
+            self.over_budget_frames += 1;
+        }
+        self.planned = 0.0;
+    }
+
+    /// Rolling average of measured frame times (0 before the first frame)
+    pub fn average_frame_time(&self) -> f64 {
+        self.average.unwrap_or(0.0)
+    }
+
+    /// Check if recent frames are taking longer than the budget
+    pub fn is_over_budget(&self) -> bool {
+        self.average.is_some_and(|avg| avg > self.budget)
+    }
+
+    /// Number of frames reported
+    pub fn frame_count(&self) -> u64 {
+        self.frames
+    }
+
+    /// Fraction of reported frames that exceeded the budget
+    pub fn over_budget_ratio(&self) -> f64 {
+        if self.frames == 0 {
+            0.0
+        } else {
+            self.over_budget_frames as f64 / self.frames as f64
+        }
+    }
+
+    /// Forget calibration and frame history
+    pub fn reset(&mut self) {
+        self.calibration = 1.0;
+        self.average = None;
+        self.planned = 0.0;
+        self.frames = 0;
+        self.over_budget_frames = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> CostModel {
+        CostModel {
+            per_point: 0.001,
+            per_segment: 0.0,
+            per_feature: 0.0,
+            per_element: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_plan_degrades_in_priority_order() {
+        let mut budget = RenderBudget::new(10.0).with_cost_model(model());
+        budget.begin_frame();
+
+        // 6 ms each: first fits, second only decimated (1.5 ms), third neither
+        let cost = ElementCost::points(6_000);
+        assert_eq!(budget.plan(&cost), DetailLevel::Full);
+        assert_eq!(budget.plan(&cost), DetailLevel::Decimated);
+        assert!((budget.remaining() - 2.5).abs() < 1e-9);
+        assert_eq!(budget.plan(&ElementCost::points(20_000)), DetailLevel::Cached);
+        assert_eq!(budget.point_budget(), 2_500);
+
+        // A new frame starts with the full budget again
+        budget.begin_frame();
+        assert_eq!(budget.plan(&cost), DetailLevel::Full);
+    }
+
+    #[test]
+    fn test_calibration_tracks_measured_cost() {
+        let mut budget = RenderBudget::new(16.0).with_cost_model(model()).with_smoothing(1.0);
+        let cost = ElementCost::points(4_000);
+
+        // The machine is twice as slow as the model predicts
+        budget.begin_frame();
+        assert_eq!(budget.plan(&cost), DetailLevel::Full);
+        budget.end_frame(8.0);
+        assert!((budget.calibration() - 2.0).abs() < 1e-9);
+        assert!((budget.estimate(&cost) - 8.0).abs() < 1e-9);
+
+        // Calibrated estimates feed back into planning
+        budget.begin_frame();
+        assert_eq!(budget.plan(&cost), DetailLevel::Full);
+        assert_eq!(budget.plan(&cost), DetailLevel::Full);
+        assert_eq!(budget.plan(&cost), DetailLevel::Cached);
+        budget.end_frame(16.0);
+        assert!((budget.calibration() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rolling_frame_cost() {
+        let mut budget = RenderBudget::new(16.0).with_smoothing(0.5);
+        assert!(!budget.is_over_budget());
+        assert_eq!(budget.average_frame_time(), 0.0);
+
+        budget.end_frame(10.0);
+        budget.end_frame(30.0);
+        assert!((budget.average_frame_time() - 20.0).abs() < 1e-9);
+        assert!(budget.is_over_budget());
+        assert_eq!(budget.frame_count(), 2);
+        assert!((budget.over_budget_ratio() - 0.5).abs() < 1e-9);
+
+        // Invalid measurements are ignored
+        budget.end_frame(f64::NAN);
+        assert_eq!(budget.frame_count(), 2);
+
+        budget.reset();
+        assert_eq!(budget.frame_count(), 0);
+        assert_eq!(budget.calibration(), 1.0);
+    }
+}
@@ -0,0 +1,232 @@
+//! Render backend abstraction for shape output
+//!
+//! Shape generators produce [`Path`]s; this module replays them onto any
+//! canvas-like backend through the [`RenderTarget`] trait. Arcs and
+//! quadratic curves are converted to cubic Beziers on the way, so a backend
+//! only has to implement lines and one curve type.
+//!
+//! [`Tessellator`] is a backend that turns filled and stroked paths into
+//! indexed triangle lists ([`TriangleMesh`]) ready to upload for Makepad
+//! draw calls, replacing hand-written triangulation in chart widgets.
+//!
+//! [`LayoutSnapshot`] is a backend that records geometry and axis ticks so
+//! two renders can be compared with a structured [`SnapshotDiff`] in tests.
+//!
+//! [`RenderBudget`] picks a [`DetailLevel`] per element each frame from
+//! calibrated cost estimates, keeping interaction within a frame time.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::render::{fill_path, stroke_path, Tessellator};
+//! use makepad_d3::shape::{ArcGenerator, Path};
+//! use makepad_d3::color::Rgba;
+//!
+//! let wedge: Path = ArcGenerator::new()
+//!     .inner_radius(40.0)
+//!     .outer_radius(80.0)
+//!     .end_angle(std::f64::consts::FRAC_PI_2)
+//!     .generate()
+//!     .into_iter()
+//!     .collect();
+//!
+//! let mut tess = Tessellator::new();
+//! fill_path(&mut tess, &wedge, Rgba::from_hex(0x4285F4));
+//! stroke_path(&mut tess, &wedge, Rgba::WHITE, 1.0);
+//!
+//! let meshes = tess.into_meshes();
+//! assert_eq!(meshes.len(), 2);
+//! assert!(meshes[0].triangle_count() > 0);
+//! ```
+
+mod budget;
+mod snapshot;
+mod tessellate;
+
+pub use budget::{CostModel, DetailLevel, ElementCost, RenderBudget};
+pub use snapshot::{
+    DiffTolerance, LayoutSnapshot, SnapshotChange, SnapshotDiff, SnapshotPaint, SnapshotShape,
+    SnapshotTick,
+};
+pub use tessellate::{Tessellator, TriangleMesh, DEFAULT_TOLERANCE};
+
+use std::f64::consts::FRAC_PI_2;
+
+use crate::color::Rgba;
+use crate::shape::path::arc_sweep;
+use crate::shape::{Path, PathSegment, Point};
+
+/// A canvas-like drawing backend
+///
+/// Follows HTML canvas semantics: path commands build the current path,
+/// which [`fill`](RenderTarget::fill) and [`stroke`](RenderTarget::stroke)
+/// paint without clearing; [`begin_path`](RenderTarget::begin_path) starts
+/// a new one.
+pub trait RenderTarget {
+    /// Discard the current path
+    fn begin_path(&mut self);
+
+    /// Start a new subpath at a point
+    fn move_to(&mut self, x: f64, y: f64);
+
+    /// Add a straight line to a point
+    fn line_to(&mut self, x: f64, y: f64);
+
+    /// Add a cubic Bezier curve to a point
+    fn bezier_to(&mut self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64);
+
+    /// Close the current subpath
+    fn close(&mut self);
+
+    /// Fill the current path
+    fn fill(&mut self, color: Rgba);
+
+    /// Stroke the current path
+    fn stroke(&mut self, color: Rgba, width: f64);
+}
+
+/// Replay a path onto a target as a new current path
+///
+/// Quadratic curves are raised to cubics and arcs are approximated by one
+/// cubic per quarter turn. An arc that doesn't start at the current point
+/// is joined to it with a line, as in canvas `arc()`.
+pub fn trace_path<T: RenderTarget + ?Sized>(target: &mut T, path: &Path) {
+    target.begin_path();
+    let mut current: Option<Point> = None;
+    let mut subpath_start: Option<Point> = None;
+
+    for segment in path.iter() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                target.move_to(p.x, p.y);
+                current = Some(*p);
+                subpath_start = Some(*p);
+            }
+            PathSegment::LineTo(p) => {
+                target.line_to(p.x, p.y);
+                current = Some(*p);
+            }
+            PathSegment::QuadTo { cp, end } => {
+                let p0 = current.unwrap_or(*cp);
+                let cp1 = p0.lerp(cp, 2.0 / 3.0);
+                let cp2 = end.lerp(cp, 2.0 / 3.0);
+                target.bezier_to(cp1.x, cp1.y, cp2.x, cp2.y, end.x, end.y);
+                current = Some(*end);
+            }
+            PathSegment::CurveTo { cp1, cp2, end } => {
+                target.bezier_to(cp1.x, cp1.y, cp2.x, cp2.y, end.x, end.y);
+                current = Some(*end);
+            }
+            PathSegment::ArcTo {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                counterclockwise,
+            } => {
+                let at = |angle: f64| {
+                    Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin())
+                };
+                let start = at(*start_angle);
+                match current {
+                    None => target.move_to(start.x, start.y),
+                    Some(p) if p.distance(&start) > 1e-9 => target.line_to(start.x, start.y),
+                    Some(_) => {}
+                }
+                if subpath_start.is_none() {
+                    subpath_start = Some(start);
+                }
+
+                let sweep = arc_sweep(*start_angle, *end_angle, *counterclockwise);
+                let pieces = ((sweep.abs() / FRAC_PI_2).ceil() as usize).max(1);
+                let step = sweep / pieces as f64;
+                let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+                for i in 0..pieces {
+                    let a0 = start_angle + step * i as f64;
+                    let a1 = a0 + step;
+                    let (p0, p1) = (at(a0), at(a1));
+                    target.bezier_to(
+                        p0.x - k * a0.sin(),
+                        p0.y + k * a0.cos(),
+                        p1.x + k * a1.sin(),
+                        p1.y - k * a1.cos(),
+                        p1.x,
+                        p1.y,
+                    );
+                }
+                current = Some(at(start_angle + sweep));
+            }
+            PathSegment::ClosePath => {
+                target.close();
+                current = subpath_start;
+            }
+        }
+    }
+}
+
+/// Replay a path onto a target and fill it
+pub fn fill_path<T: RenderTarget + ?Sized>(target: &mut T, path: &Path, color: Rgba) {
+    trace_path(target, path);
+    target.fill(color);
+}
+
+/// Replay a path onto a target and stroke it
+pub fn stroke_path<T: RenderTarget + ?Sized>(target: &mut T, path: &Path, color: Rgba, width: f64) {
+    trace_path(target, path);
+    target.stroke(color, width);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records commands as strings
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl RenderTarget for Recorder {
+        fn begin_path(&mut self) {
+            self.0.push("begin".into());
+        }
+        fn move_to(&mut self, x: f64, y: f64) {
+            self.0.push(format!("M{:.0},{:.0}", x, y));
+        }
+        fn line_to(&mut self, x: f64, y: f64) {
+            self.0.push(format!("L{:.0},{:.0}", x, y));
+        }
+        fn bezier_to(&mut self, _: f64, _: f64, _: f64, _: f64, x: f64, y: f64) {
+            self.0.push(format!("C{:.0},{:.0}", x, y));
+        }
+        fn close(&mut self) {
+            self.0.push("Z".into());
+        }
+        fn fill(&mut self, _: Rgba) {
+            self.0.push("fill".into());
+        }
+        fn stroke(&mut self, _: Rgba, width: f64) {
+            self.0.push(format!("stroke{}", width));
+        }
+    }
+
+    #[test]
+    fn test_trace_arc_as_quarter_beziers() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0);
+        path.push(PathSegment::arc_to(0.0, 0.0, 10.0, 0.0, std::f64::consts::PI, false));
+        path.close();
+
+        let mut rec = Recorder::default();
+        fill_path(&mut rec, &path, Rgba::BLACK);
+        assert_eq!(rec.0, vec!["begin", "M0,0", "L10,0", "C0,10", "C-10,0", "Z", "fill"]);
+    }
+
+    #[test]
+    fn test_trace_quad_raised_to_cubic() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).quad_to(5.0, 10.0, 10.0, 0.0);
+
+        let mut rec = Recorder::default();
+        stroke_path(&mut rec, &path, Rgba::BLACK, 2.0);
+        assert_eq!(rec.0, vec!["begin", "M0,0", "C10,0", "stroke2"]);
+    }
+}
@@ -0,0 +1,551 @@
+//! Geometry-level snapshots of a rendered chart for regression checks
+//!
+//! [`LayoutSnapshot`] is a [`RenderTarget`] that records every filled or
+//! stroked shape (its vertices, paint and color) plus axis tick positions.
+//! Two snapshots, e.g. before and after a refactor, are compared with
+//! [`LayoutSnapshot::diff`], which lists moved ticks, changed vertex counts,
+//! moved shapes and altered colors beyond a [`DiffTolerance`]. Unlike pixel
+//! comparisons the result is exact, explainable and independent of the GPU.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::{fill_path, stroke_path, RenderTarget};
+use crate::axis::AxisLayout;
+use crate::color::Rgba;
+use crate::shape::Path;
+
+/// How a recorded shape was painted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SnapshotPaint {
+    /// Filled
+    Fill,
+    /// Stroked with the given width
+    Stroke(f64),
+}
+
+/// A filled or stroked shape in a snapshot
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotShape {
+    /// Identifier used to match shapes between snapshots
+    pub key: String,
+    /// Fill or stroke
+    pub paint: SnapshotPaint,
+    /// Paint color
+    pub color: Rgba,
+    /// Path vertices (end points of every move, line and curve command)
+    pub points: Vec<(f64, f64)>,
+}
+
+/// An axis tick in a snapshot
+#[derive(Clone, Debug, PartialEq)]
+pub struct SnapshotTick {
+    /// Axis name given to [`LayoutSnapshot::add_axis`]
+    pub axis: String,
+    /// Tick label
+    pub label: String,
+    /// Pixel position along the axis
+    pub position: f64,
+}
+
+/// Thresholds below which differences are ignored
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiffTolerance {
+    /// Maximum distance in pixels a tick or vertex may move
+    pub position: f64,
+    /// Maximum difference of any color channel (0 to 1)
+    pub color: f32,
+}
+
+impl Default for DiffTolerance {
+    fn default() -> Self {
+        Self {
+            position: 1e-6,
+            color: 1e-4,
+        }
+    }
+}
+
+/// A single difference between two snapshots
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnapshotChange {
+    /// A tick exists in both snapshots at different positions
+    TickMoved {
+        /// Axis name
+        axis: String,
+        /// Tick label
+        label: String,
+        /// Position before
+        from: f64,
+        /// Position after
+        to: f64,
+    },
+    /// A tick only exists in the new snapshot
+    TickAdded {
+        /// Axis name
+        axis: String,
+        /// Tick label
+        label: String,
+    },
+    /// A tick only exists in the old snapshot
+    TickRemoved {
+        /// Axis name
+        axis: String,
+        /// Tick label
+        label: String,
+    },
+    /// A shape only exists in the new snapshot
+    ShapeAdded {
+        /// Shape key
+        key: String,
+    },
+    /// A shape only exists in the old snapshot
+    ShapeRemoved {
+        /// Shape key
+        key: String,
+    },
+    /// A shape's vertex count changed
+    PointCountChanged {
+        /// Shape key
+        key: String,
+        /// Vertex count before
+        from: usize,
+        /// Vertex count after
+        to: usize,
+    },
+    /// A shape's vertices moved
+    ShapeMoved {
+        /// Shape key
+        key: String,
+        /// Largest distance any vertex moved
+        max_distance: f64,
+    },
+    /// A shape switched between fill and stroke, or its stroke width changed
+    PaintChanged {
+        /// Shape key
+        key: String,
+        /// Paint before
+        from: SnapshotPaint,
+        /// Paint after
+        to: SnapshotPaint,
+    },
+    /// A shape's color changed
+    ColorChanged {
+        /// Shape key
+        key: String,
+        /// Color before
+        from: Rgba,
+        /// Color after
+        to: Rgba,
+    },
+}
+
+impl fmt::Display for SnapshotChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotChange::TickMoved { axis, label, from, to } => {
+                write!(f, "tick {}:{} moved from {} to {}", axis, label, from, to)
+            }
+            SnapshotChange::TickAdded { axis, label } => write!(f, "tick {}:{} added", axis, label),
+            SnapshotChange::TickRemoved { axis, label } => {
+                write!(f, "tick {}:{} removed", axis, label)
+            }
+            SnapshotChange::ShapeAdded { key } => write!(f, "shape {} added", key),
+            SnapshotChange::ShapeRemoved { key } => write!(f, "shape {} removed", key),
+            SnapshotChange::PointCountChanged { key, from, to } => {
+                write!(f, "shape {} point count changed from {} to {}", key, from, to)
+            }
+            SnapshotChange::ShapeMoved { key, max_distance } => {
+                write!(f, "shape {} moved by up to {}", key, max_distance)
+            }
+            SnapshotChange::PaintChanged { key, from, to } => {
+                write!(f, "shape {} paint changed from {:?} to {:?}", key, from, to)
+            }
+            SnapshotChange::ColorChanged { key, from, to } => {
+                write!(
+                    f,
+                    "shape {} color changed from #{:06X} ({:.2}) to #{:06X} ({:.2})",
+                    key,
+                    from.to_hex(),
+                    from.a,
+                    to.to_hex(),
+                    to.a
+                )
+            }
+        }
+    }
+}
+
+/// Differences between two snapshots
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotDiff {
+    /// Individual changes, ticks first, then shapes in recording order
+    pub changes: Vec<SnapshotChange>,
+}
+
+impl SnapshotDiff {
+    /// Check if the snapshots match within tolerance
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changes
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+}
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Recorded geometry of a chart, comparable with [`diff`](Self::diff)
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::color::Rgba;
+/// use makepad_d3::render::{LayoutSnapshot, SnapshotChange, DiffTolerance};
+/// use makepad_d3::shape::Path;
+///
+/// let bar = |h: f64| {
+///     let mut p = Path::new();
+///     p.move_to(0.0, 100.0).line_to(10.0, 100.0).line_to(10.0, 100.0 - h).close();
+///     p
+/// };
+///
+/// let mut before = LayoutSnapshot::new();
+/// before.add_fill("bar-a", &bar(40.0), Rgba::from_hex(0x4285F4));
+///
+/// let mut after = LayoutSnapshot::new();
+/// after.add_fill("bar-a", &bar(40.2), Rgba::from_hex(0x4285F4));
+///
+/// assert!(before.diff(&after, DiffTolerance { position: 0.5, ..Default::default() }).is_empty());
+///
+/// let diff = before.diff(&after, DiffTolerance::default());
+/// assert!(matches!(diff.changes[0], SnapshotChange::ShapeMoved { .. }));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LayoutSnapshot {
+    /// Recorded shapes in drawing order
+    shapes: Vec<SnapshotShape>,
+    /// Recorded axis ticks
+    ticks: Vec<SnapshotTick>,
+    /// Vertices of the path being traced
+    current: Vec<(f64, f64)>,
+    /// Key for the next painted shape
+    next_key: Option<String>,
+    /// Number of shapes recorded per key, for de-duplication
+    key_counts: HashMap<String, usize>,
+}
+
+impl LayoutSnapshot {
+    /// Create an empty snapshot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the key of the next shape painted through [`RenderTarget`]
+    ///
+    /// Shapes without a key are named `shape0`, `shape1`, ... in drawing
+    /// order; repeated keys get a `#n` suffix.
+    pub fn set_key(&mut self, key: impl Into<String>) {
+        self.next_key = Some(key.into());
+    }
+
+    /// Record a filled path under a key
+    pub fn add_fill(&mut self, key: impl Into<String>, path: &Path, color: Rgba) {
+        self.set_key(key);
+        fill_path(self, path, color);
+    }
+
+    /// Record a stroked path under a key
+    pub fn add_stroke(&mut self, key: impl Into<String>, path: &Path, color: Rgba, width: f64) {
+        self.set_key(key);
+        stroke_path(self, path, color, width);
+    }
+
+    /// Record the major and minor tick positions of an axis layout
+    pub fn add_axis(&mut self, axis: impl Into<String>, layout: &AxisLayout) {
+        let axis = axis.into();
+        for tick in &layout.ticks {
+            self.ticks.push(SnapshotTick {
+                axis: axis.clone(),
+                label: tick.label.clone(),
+                position: tick.position,
+            });
+        }
+    }
+
+    /// Recorded shapes in drawing order
+    pub fn shapes(&self) -> &[SnapshotShape] {
+        &self.shapes
+    }
+
+    /// Recorded ticks
+    pub fn ticks(&self) -> &[SnapshotTick] {
+        &self.ticks
+    }
+
+    /// Compare with a newer snapshot
+    pub fn diff(&self, other: &LayoutSnapshot, tolerance: DiffTolerance) -> SnapshotDiff {
+        let mut changes = Vec::new();
+        diff_ticks(&self.ticks, &other.ticks, tolerance, &mut changes);
+        diff_shapes(&self.shapes, &other.shapes, tolerance, &mut changes);
+        SnapshotDiff { changes }
+    }
+
+    fn unique_key(&mut self, key: String) -> String {
+        let count = self.key_counts.entry(key.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            key
+        } else {
+            format!("{}#{}", key, count)
+        }
+    }
+
+    fn paint(&mut self, paint: SnapshotPaint, color: Rgba) {
+        let key = self
+            .next_key
+            .take()
+            .unwrap_or_else(|| format!("shape{}", self.shapes.len()));
+        let key = self.unique_key(key);
+        self.shapes.push(SnapshotShape {
+            key,
+            paint,
+            color,
+            points: self.current.clone(),
+        });
+    }
+}
+
+impl RenderTarget for LayoutSnapshot {
+    fn begin_path(&mut self) {
+        self.current.clear();
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.current.push((x, y));
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.current.push((x, y));
+    }
+
+    fn bezier_to(&mut self, _cp1x: f64, _cp1y: f64, _cp2x: f64, _cp2y: f64, x: f64, y: f64) {
+        self.current.push((x, y));
+    }
+
+    fn close(&mut self) {}
+
+    fn fill(&mut self, color: Rgba) {
+        self.paint(SnapshotPaint::Fill, color);
+    }
+
+    fn stroke(&mut self, color: Rgba, width: f64) {
+        self.paint(SnapshotPaint::Stroke(width), color);
+    }
+}
+
+/// Key ticks by axis and label, numbering repeated labels
+fn keyed_ticks(ticks: &[SnapshotTick]) -> Vec<((&str, &str, usize), f64)> {
+    let mut seen: HashMap<(&str, &str), usize> = HashMap::new();
+    ticks
+        .iter()
+        .map(|t| {
+            let n = seen.entry((t.axis.as_str(), t.label.as_str())).or_insert(0);
+            *n += 1;
+            ((t.axis.as_str(), t.label.as_str(), *n), t.position)
+        })
+        .collect()
+}
+
+fn diff_ticks(
+    old: &[SnapshotTick],
+    new: &[SnapshotTick],
+    tolerance: DiffTolerance,
+    changes: &mut Vec<SnapshotChange>,
+) {
+    let old = keyed_ticks(old);
+    let new = keyed_ticks(new);
+    let new_map: HashMap<_, f64> = new.iter().copied().collect();
+    let old_keys: HashSet<_> = old.iter().map(|(k, _)| *k).collect();
+
+    for &(key @ (axis, label, _), from) in &old {
+        match new_map.get(&key) {
+            Some(&to) if (to - from).abs() > tolerance.position => {
+                changes.push(SnapshotChange::TickMoved {
+                    axis: axis.to_string(),
+                    label: label.to_string(),
+                    from,
+                    to,
+                });
+            }
+            Some(_) => {}
+            None => changes.push(SnapshotChange::TickRemoved {
+                axis: axis.to_string(),
+                label: label.to_string(),
+            }),
+        }
+    }
+    for &(key @ (axis, label, _), _) in &new {
+        if !old_keys.contains(&key) {
+            changes.push(SnapshotChange::TickAdded {
+                axis: axis.to_string(),
+                label: label.to_string(),
+            });
+        }
+    }
+}
+
+fn diff_shapes(
+    old: &[SnapshotShape],
+    new: &[SnapshotShape],
+    tolerance: DiffTolerance,
+    changes: &mut Vec<SnapshotChange>,
+) {
+    let new_map: HashMap<&str, &SnapshotShape> = new.iter().map(|s| (s.key.as_str(), s)).collect();
+    let old_keys: HashSet<&str> = old.iter().map(|s| s.key.as_str()).collect();
+
+    for a in old {
+        let Some(b) = new_map.get(a.key.as_str()) else {
+            changes.push(SnapshotChange::ShapeRemoved { key: a.key.clone() });
+            continue;
+        };
+
+        if a.paint != b.paint {
+            changes.push(SnapshotChange::PaintChanged {
+                key: a.key.clone(),
+                from: a.paint,
+                to: b.paint,
+            });
+        }
+        if color_distance(&a.color, &b.color) > tolerance.color {
+            changes.push(SnapshotChange::ColorChanged {
+                key: a.key.clone(),
+                from: a.color,
+                to: b.color,
+            });
+        }
+        if a.points.len() != b.points.len() {
+            changes.push(SnapshotChange::PointCountChanged {
+                key: a.key.clone(),
+                from: a.points.len(),
+                to: b.points.len(),
+            });
+        } else {
+            let max_distance = a
+                .points
+                .iter()
+                .zip(&b.points)
+                .map(|(p, q)| (q.0 - p.0).hypot(q.1 - p.1))
+                .fold(0.0, f64::max);
+            if max_distance > tolerance.position {
+                changes.push(SnapshotChange::ShapeMoved {
+                    key: a.key.clone(),
+                    max_distance,
+                });
+            }
+        }
+    }
+    for b in new {
+        if !old_keys.contains(b.key.as_str()) {
+            changes.push(SnapshotChange::ShapeAdded { key: b.key.clone() });
+        }
+    }
+}
+
+fn color_distance(a: &Rgba, b: &Rgba) -> f32 {
+    (a.r - b.r)
+        .abs()
+        .max((a.g - b.g).abs())
+        .max((a.b - b.b).abs())
+        .max((a.a - b.a).abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::{Axis, AxisConfig};
+    use crate::scale::{LinearScale, ScaleExt};
+
+    fn square(x: f64, size: f64) -> Path {
+        let mut p = Path::new();
+        p.move_to(x, 0.0)
+            .line_to(x + size, 0.0)
+            .line_to(x + size, size)
+            .line_to(x, size)
+            .close();
+        p
+    }
+
+    fn axis_layout(max: f64) -> AxisLayout {
+        let scale = LinearScale::new().with_domain(0.0, max).with_range(0.0, 100.0);
+        let mut axis = Axis::with_config(AxisConfig::bottom().with_tick_count(5));
+        axis.set_scale(&scale);
+        axis.compute_layout(0.0)
+    }
+
+    #[test]
+    fn test_identical_snapshots_have_no_diff() {
+        let build = || {
+            let mut s = LayoutSnapshot::new();
+            s.add_axis("x", &axis_layout(10.0));
+            s.add_fill("bar", &square(0.0, 10.0), Rgba::RED);
+            s.add_stroke("bar", &square(0.0, 10.0), Rgba::BLACK, 1.0);
+            s
+        };
+        let (a, b) = (build(), build());
+        assert_eq!(a.shapes()[1].key, "bar#2");
+        assert!(a.diff(&b, DiffTolerance::default()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_tick_changes() {
+        let mut a = LayoutSnapshot::new();
+        a.add_axis("x", &axis_layout(10.0));
+        let mut b = LayoutSnapshot::new();
+        b.add_axis("x", &axis_layout(20.0));
+
+        let diff = a.diff(&b, DiffTolerance::default());
+        assert!(diff.changes.iter().any(|c| matches!(
+            c,
+            SnapshotChange::TickMoved { label, from, to, .. } if label == "10" && *from == 100.0 && *to == 50.0
+        )));
+        assert!(diff.changes.iter().any(|c| matches!(c, SnapshotChange::TickRemoved { label, .. } if label == "2")));
+        assert!(diff.changes.iter().any(|c| matches!(c, SnapshotChange::TickAdded { label, .. } if label == "20")));
+    }
+
+    #[test]
+    fn test_diff_reports_shape_changes() {
+        let mut a = LayoutSnapshot::new();
+        a.add_fill("a", &square(0.0, 10.0), Rgba::RED);
+        a.add_fill("b", &square(20.0, 10.0), Rgba::RED);
+        a.add_fill("gone", &square(40.0, 10.0), Rgba::RED);
+
+        let mut triangle = Path::new();
+        triangle.move_to(20.0, 0.0).line_to(30.0, 0.0).line_to(25.0, 10.0).close();
+        let mut b = LayoutSnapshot::new();
+        b.add_fill("a", &square(0.0, 10.0), Rgba::BLUE);
+        b.add_fill("b", &triangle, Rgba::RED);
+        b.add_stroke("new", &square(40.0, 10.0), Rgba::RED, 2.0);
+
+        let diff = a.diff(&b, DiffTolerance::default());
+        assert_eq!(
+            diff.changes,
+            vec![
+                SnapshotChange::ColorChanged { key: "a".into(), from: Rgba::RED, to: Rgba::BLUE },
+                SnapshotChange::PointCountChanged { key: "b".into(), from: 4, to: 3 },
+                SnapshotChange::ShapeRemoved { key: "gone".into() },
+                SnapshotChange::ShapeAdded { key: "new".into() },
+            ]
+        );
+        assert!(diff.to_string().contains("shape b point count changed from 4 to 3"));
+    }
+}
@@ -0,0 +1,512 @@
+//! Path tessellation into triangle lists
+//!
+//! Curves are flattened to within a pixel tolerance, fills are triangulated
+//! by ear clipping (nested subpaths become holes, even-odd style), and
+//! strokes are expanded into quads with bevel joins.
+
+use super::RenderTarget;
+use crate::color::Rgba;
+use crate::shape::Point;
+
+/// Default maximum distance, in pixels, between a curve and its flattening
+pub const DEFAULT_TOLERANCE: f64 = 0.25;
+
+/// Upper bound on line segments per flattened Bezier
+const MAX_CURVE_STEPS: usize = 256;
+
+/// Indexed triangle list painted in a single color
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TriangleMesh {
+    /// Fill color
+    pub color: Rgba,
+    /// Vertex positions
+    pub vertices: Vec<[f32; 2]>,
+    /// Vertex indices, three per triangle
+    pub indices: Vec<u32>,
+}
+
+impl TriangleMesh {
+    /// Create an empty mesh
+    pub fn new(color: Rgba) -> Self {
+        Self {
+            color,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    /// Number of triangles
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    /// Check if the mesh has no triangles
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Iterate over triangles as vertex positions
+    pub fn triangles(&self) -> impl Iterator<Item = [[f32; 2]; 3]> + '_ {
+        self.indices.chunks_exact(3).map(move |t| {
+            [
+                self.vertices[t[0] as usize],
+                self.vertices[t[1] as usize],
+                self.vertices[t[2] as usize],
+            ]
+        })
+    }
+
+    /// Sum of triangle areas (overlapping triangles count twice)
+    pub fn area(&self) -> f64 {
+        self.triangles()
+            .map(|[a, b, c]| {
+                let cross = (b[0] - a[0]) as f64 * (c[1] - a[1]) as f64
+                    - (b[1] - a[1]) as f64 * (c[0] - a[0]) as f64;
+                cross.abs() / 2.0
+            })
+            .sum()
+    }
+
+    fn push_vertex(&mut self, p: Point) -> u32 {
+        self.vertices.push([p.x as f32, p.y as f32]);
+        (self.vertices.len() - 1) as u32
+    }
+
+    fn push_triangle(&mut self, a: Point, b: Point, c: Point) {
+        let i = self.push_vertex(a);
+        self.push_vertex(b);
+        self.push_vertex(c);
+        self.indices.extend_from_slice(&[i, i + 1, i + 2]);
+    }
+}
+
+/// A flattened subpath
+#[derive(Clone, Debug)]
+struct Subpath {
+    points: Vec<Point>,
+    closed: bool,
+}
+
+/// Render target that tessellates fills and strokes into [`TriangleMesh`]es
+///
+/// Each `fill` or `stroke` call appends one mesh.
+#[derive(Clone, Debug)]
+pub struct Tessellator {
+    /// Curve flattening tolerance in pixels
+    tolerance: f64,
+    /// Current path
+    subpaths: Vec<Subpath>,
+    /// Meshes produced so far
+    meshes: Vec<TriangleMesh>,
+}
+
+impl Default for Tessellator {
+    fn default() -> Self {
+        Self {
+            tolerance: DEFAULT_TOLERANCE,
+            subpaths: Vec::new(),
+            meshes: Vec::new(),
+        }
+    }
+}
+
+impl Tessellator {
+    /// Create a tessellator with the default tolerance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set curve flattening tolerance in pixels
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance.max(1e-3);
+        self
+    }
+
+    /// Get the meshes produced so far
+    pub fn meshes(&self) -> &[TriangleMesh] {
+        &self.meshes
+    }
+
+    /// Consume the tessellator, returning its meshes
+    pub fn into_meshes(self) -> Vec<TriangleMesh> {
+        self.meshes
+    }
+
+    /// Discard the current path and all meshes
+    pub fn clear(&mut self) {
+        self.subpaths.clear();
+        self.meshes.clear();
+    }
+
+    fn current_mut(&mut self) -> Option<&mut Subpath> {
+        self.subpaths.last_mut().filter(|s| !s.closed)
+    }
+
+    fn push_point(&mut self, p: Point) {
+        match self.current_mut() {
+            Some(subpath) => {
+                if subpath.points.last() != Some(&p) {
+                    subpath.points.push(p);
+                }
+            }
+            None => self.subpaths.push(Subpath { points: vec![p], closed: false }),
+        }
+    }
+}
+
+impl RenderTarget for Tessellator {
+    fn begin_path(&mut self) {
+        self.subpaths.clear();
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.subpaths.push(Subpath {
+            points: vec![Point::new(x, y)],
+            closed: false,
+        });
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.push_point(Point::new(x, y));
+    }
+
+    fn bezier_to(&mut self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+        let cp1 = Point::new(cp1x, cp1y);
+        let cp2 = Point::new(cp2x, cp2y);
+        let end = Point::new(x, y);
+        let p0 = match self.current_mut().and_then(|s| s.points.last().copied()) {
+            Some(p) => p,
+            None => {
+                self.push_point(cp1);
+                cp1
+            }
+        };
+
+        // Wang's bound on the number of segments for a cubic
+        let dd = |a: Point, b: Point, c: Point| (a.x - 2.0 * b.x + c.x).hypot(a.y - 2.0 * b.y + c.y);
+        let m = dd(p0, cp1, cp2).max(dd(cp1, cp2, end));
+        let steps = ((0.75 * m / self.tolerance).sqrt().ceil() as usize).clamp(1, MAX_CURVE_STEPS);
+        for i in 1..=steps {
+            let t = i as f64 / steps as f64;
+            let a = p0.lerp(&cp1, t);
+            let b = cp1.lerp(&cp2, t);
+            let c = cp2.lerp(&end, t);
+            self.push_point(a.lerp(&b, t).lerp(&b.lerp(&c, t), t));
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(subpath) = self.current_mut() {
+            subpath.closed = true;
+            // Drawing continues from the start of the closed subpath
+            let start = subpath.points[0];
+            self.subpaths.push(Subpath { points: vec![start], closed: false });
+        }
+    }
+
+    fn fill(&mut self, color: Rgba) {
+        let rings: Vec<Vec<Point>> = self
+            .subpaths
+            .iter()
+            .map(|s| {
+                let mut points = s.points.clone();
+                if points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+                points
+            })
+            .filter(|points| points.len() >= 3)
+            .collect();
+
+        let mut mesh = TriangleMesh::new(color);
+        for polygon in nest_rings(rings) {
+            ear_clip(&polygon, &mut mesh);
+        }
+        if !mesh.is_empty() {
+            self.meshes.push(mesh);
+        }
+    }
+
+    fn stroke(&mut self, color: Rgba, width: f64) {
+        let half = width / 2.0;
+        let mut mesh = TriangleMesh::new(color);
+        for subpath in &self.subpaths {
+            stroke_polyline(&subpath.points, subpath.closed, half, &mut mesh);
+        }
+        if !mesh.is_empty() {
+            self.meshes.push(mesh);
+        }
+    }
+}
+
+/// Twice the signed area of a ring
+fn signed_area(ring: &[Point]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let a = ring[i];
+            let b = ring[(i + 1) % ring.len()];
+            a.x * b.y - b.x * a.y
+        })
+        .sum()
+}
+
+fn cross(o: Point, a: Point, b: Point) -> f64 {
+    (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+}
+
+fn point_in_ring(p: Point, ring: &[Point]) -> bool {
+    let mut inside = false;
+    let mut j = ring.len() - 1;
+    for i in 0..ring.len() {
+        let (a, b) = (ring[i], ring[j]);
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Group rings into outer polygons with their holes bridged in
+///
+/// A ring nested inside an odd number of others is a hole of the ring
+/// directly containing it.
+fn nest_rings(rings: Vec<Vec<Point>>) -> Vec<Vec<Point>> {
+    let contains = |outer: usize, inner: usize| outer != inner && point_in_ring(rings[inner][0], &rings[outer]);
+    let depth: Vec<usize> = (0..rings.len())
+        .map(|i| (0..rings.len()).filter(|&j| contains(j, i)).count())
+        .collect();
+
+    let mut polygons = Vec::new();
+    for outer in (0..rings.len()).filter(|&i| depth[i] % 2 == 0) {
+        let mut polygon = rings[outer].clone();
+        if signed_area(&polygon) < 0.0 {
+            polygon.reverse();
+        }
+        let mut holes: Vec<Vec<Point>> = (0..rings.len())
+            .filter(|&h| depth[h] == depth[outer] + 1 && contains(outer, h))
+            .map(|h| {
+                let mut hole = rings[h].clone();
+                if signed_area(&hole) > 0.0 {
+                    hole.reverse();
+                }
+                hole
+            })
+            .collect();
+        // Bridge rightmost holes first so earlier bridges can't block later ones
+        holes.sort_by(|a, b| max_x(b).total_cmp(&max_x(a)));
+        for (i, hole) in holes.iter().enumerate() {
+            polygon = bridge_hole(polygon, hole, &holes[i + 1..]);
+        }
+        polygons.push(polygon);
+    }
+    polygons
+}
+
+fn max_x(ring: &[Point]) -> f64 {
+    ring.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Check if segments p1-p2 and q1-q2 cross at a point interior to both
+fn segments_cross(p1: Point, p2: Point, q1: Point, q2: Point) -> bool {
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+    d1 * d2 < 0.0 && d3 * d4 < 0.0
+}
+
+/// Splice a hole into a polygon through a bridge edge to a visible vertex
+fn bridge_hole(polygon: Vec<Point>, hole: &[Point], others: &[Vec<Point>]) -> Vec<Point> {
+    let (hi, &h) = hole
+        .iter()
+        .enumerate()
+        .max_by(|a, b| a.1.x.total_cmp(&b.1.x))
+        .expect("hole has vertices");
+
+    let edges = |ring: &[Point]| -> Vec<(Point, Point)> {
+        (0..ring.len()).map(|i| (ring[i], ring[(i + 1) % ring.len()])).collect()
+    };
+    let mut blockers = edges(&polygon);
+    blockers.extend(edges(hole));
+    for other in others {
+        blockers.extend(edges(other));
+    }
+
+    let visible = |v: Point| !blockers.iter().any(|&(a, b)| segments_cross(h, v, a, b));
+    let vi = (0..polygon.len())
+        .filter(|&i| visible(polygon[i]))
+        .min_by(|&a, &b| h.distance(&polygon[a]).total_cmp(&h.distance(&polygon[b])))
+        .unwrap_or(0);
+
+    let mut merged = Vec::with_capacity(polygon.len() + hole.len() + 2);
+    merged.extend_from_slice(&polygon[..=vi]);
+    merged.extend(hole[hi..].iter().chain(&hole[..hi]));
+    merged.push(h);
+    merged.push(polygon[vi]);
+    merged.extend_from_slice(&polygon[vi + 1..]);
+    merged
+}
+
+/// Triangulate a counterclockwise (positive area) polygon by ear clipping
+fn ear_clip(polygon: &[Point], mesh: &mut TriangleMesh) {
+    let mut idx: Vec<usize> = (0..polygon.len()).collect();
+    let mut guard = 0;
+
+    while idx.len() > 3 {
+        let n = idx.len();
+        let mut clipped = false;
+        for i in 0..n {
+            let (a, b, c) = (polygon[idx[(i + n - 1) % n]], polygon[idx[i]], polygon[idx[(i + 1) % n]]);
+            let turn = cross(a, b, c);
+            if turn.abs() < 1e-12 {
+                // Collinear or spike vertex: drop it without a triangle
+                idx.remove(i);
+                clipped = true;
+                break;
+            }
+            if turn < 0.0 {
+                continue;
+            }
+            let blocked = idx.iter().map(|&j| polygon[j]).any(|p| {
+                p != a && p != b && p != c && cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+            });
+            if !blocked {
+                mesh.push_triangle(a, b, c);
+                idx.remove(i);
+                clipped = true;
+                break;
+            }
+        }
+        if !clipped {
+            // Self-intersecting input: clip anyway so the loop terminates
+            guard += 1;
+            let (a, b, c) = (polygon[idx[n - 1]], polygon[idx[0]], polygon[idx[1]]);
+            mesh.push_triangle(a, b, c);
+            idx.remove(0);
+            if guard > polygon.len() {
+                return;
+            }
+        }
+    }
+    if idx.len() == 3 {
+        let (a, b, c) = (polygon[idx[0]], polygon[idx[1]], polygon[idx[2]]);
+        if cross(a, b, c).abs() >= 1e-12 {
+            mesh.push_triangle(a, b, c);
+        }
+    }
+}
+
+/// Expand a polyline into quads, with bevel joins between segments
+fn stroke_polyline(points: &[Point], closed: bool, half: f64, mesh: &mut TriangleMesh) {
+    if points.len() < 2 || half <= 0.0 {
+        return;
+    }
+    let mut segments: Vec<(Point, Point)> = points.windows(2).map(|w| (w[0], w[1])).collect();
+    if closed && points.len() > 2 {
+        segments.push((points[points.len() - 1], points[0]));
+    }
+
+    let normal = |(a, b): (Point, Point)| {
+        let len = a.distance(&b);
+        Point::new(-(b.y - a.y) / len * half, (b.x - a.x) / len * half)
+    };
+    let normals: Vec<Point> = segments.iter().map(|&s| normal(s)).collect();
+
+    for (&(a, b), n) in segments.iter().zip(&normals) {
+        mesh.push_triangle(a.add(n), a.sub(n), b.add(n));
+        mesh.push_triangle(b.add(n), a.sub(n), b.sub(n));
+    }
+
+    let joins = if closed && segments.len() > 2 { segments.len() } else { segments.len() - 1 };
+    for i in 0..joins {
+        let j = (i + 1) % segments.len();
+        let p = segments[i].1;
+        let (n0, n1) = (normals[i], normals[j]);
+        mesh.push_triangle(p, p.add(&n0), p.add(&n1));
+        mesh.push_triangle(p, p.sub(&n0), p.sub(&n1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{fill_path, stroke_path};
+    use crate::shape::Path;
+
+    fn square(path: &mut Path, x: f64, y: f64, size: f64) {
+        path.move_to(x, y)
+            .line_to(x + size, y)
+            .line_to(x + size, y + size)
+            .line_to(x, y + size)
+            .close();
+    }
+
+    #[test]
+    fn test_fill_concave_polygon() {
+        // An L shape with area 3
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0)
+            .line_to(2.0, 0.0)
+            .line_to(2.0, 1.0)
+            .line_to(1.0, 1.0)
+            .line_to(1.0, 2.0)
+            .line_to(0.0, 2.0)
+            .close();
+
+        let mut tess = Tessellator::new();
+        fill_path(&mut tess, &path, Rgba::BLACK);
+        let mesh = &tess.meshes()[0];
+        assert_eq!(mesh.triangle_count(), 4);
+        assert!((mesh.area() - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fill_nested_subpath_is_hole() {
+        let mut path = Path::new();
+        square(&mut path, 0.0, 0.0, 10.0);
+        square(&mut path, 3.0, 3.0, 4.0);
+
+        let mut tess = Tessellator::new();
+        fill_path(&mut tess, &path, Rgba::BLACK);
+        assert!((tess.meshes()[0].area() - 84.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fill_circle_within_tolerance() {
+        let mut path = Path::new();
+        path.push(crate::shape::PathSegment::arc_to(0.0, 0.0, 100.0, 0.0, 2.0 * std::f64::consts::PI, false));
+        path.close();
+
+        let mut tess = Tessellator::new().with_tolerance(0.1);
+        fill_path(&mut tess, &path, Rgba::BLACK);
+        let area = tess.meshes()[0].area();
+        let exact = std::f64::consts::PI * 100.0 * 100.0;
+        assert!((area - exact).abs() / exact < 0.002);
+    }
+
+    #[test]
+    fn test_stroke_open_polyline() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).line_to(10.0, 0.0).line_to(10.0, 10.0);
+
+        let mut tess = Tessellator::new();
+        stroke_path(&mut tess, &path, Rgba::RED, 2.0);
+        let mesh = &tess.meshes()[0];
+        // Two quads plus a bevel join on each side
+        assert_eq!(mesh.triangle_count(), 6);
+        assert_eq!(mesh.color, Rgba::RED);
+        // Quads cover 2 * (10 * 2); each bevel triangle adds half a unit square
+        assert!((mesh.area() - 41.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_empty_and_degenerate_paths() {
+        let mut tess = Tessellator::new();
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).line_to(5.0, 0.0);
+        fill_path(&mut tess, &path, Rgba::BLACK);
+        stroke_path(&mut tess, &Path::new(), Rgba::BLACK, 1.0);
+        assert!(tess.meshes().is_empty());
+    }
+}
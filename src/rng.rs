@@ -0,0 +1,33 @@
+//! Seeded pseudo-random helpers for reproducible layouts and animations
+
+/// Simple pseudo-random number generator
+pub(crate) struct SimpleRng {
+    state: u64,
+}
+
+impl SimpleRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        self.state
+    }
+
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next() as f64) / (u64::MAX as f64)
+    }
+}
+
+/// Fisher-Yates shuffle with a linear congruential generator
+///
+/// Advances `state`, so shuffling again with the same variable continues the
+/// sequence rather than repeating it.
+pub(crate) fn seeded_shuffle<T>(items: &mut [T], state: &mut u64) {
+    for i in (1..items.len()).rev() {
+        *state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let j = ((*state >> 33) % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
@@ -4,6 +4,7 @@
 //! They are useful for bar charts where you need both position and width.
 
 use super::traits::{DiscreteScale, Scale, Tick, TickOptions};
+use super::utils::{snap_down, snap_to_pixel};
 
 /// Scale for mapping discrete domain to continuous bands
 ///
@@ -45,7 +46,10 @@ pub struct BandScale {
     align: f64,
     /// Whether to round output values to integers
     round: bool,
+    /// Device pixels per logical pixel used when rounding
+    pixel_ratio: f64,
     /// Cached computed values
+    cached_start: f64,
     cached_step: f64,
     cached_bandwidth: f64,
 }
@@ -61,6 +65,8 @@ impl BandScale {
             padding_outer: 0.0,
             align: 0.5,
             round: false,
+            pixel_ratio: 1.0,
+            cached_start: 0.0,
             cached_step: 0.0,
             cached_bandwidth: 0.0,
         };
@@ -164,13 +170,45 @@ impl BandScale {
     /// Enable or disable rounding to pixel boundaries
     ///
     /// When enabled, positions and bandwidths are rounded to integers
-    /// for crisper rendering.
+    /// for crisper rendering. As in d3 the step is floored and the space
+    /// this frees up is distributed around the bands according to
+    /// [`align`](Self::align).
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::scale::{BandScale, DiscreteScale};
+    ///
+    /// let scale = BandScale::new()
+    ///     .domain(vec!["A", "B", "C"])
+    ///     .range(0.0, 100.0)
+    ///     .round(true);
+    ///
+    /// // 3 bands of 33 px, the leftover pixel split evenly (align 0.5)
+    /// assert_eq!(scale.step(), 33.0);
+    /// assert_eq!(scale.scale_index(0), 1.0);
+    /// assert_eq!(scale.scale_index(2), 67.0);
+    /// ```
     pub fn round(mut self, round: bool) -> Self {
         self.round = round;
         self.rescale();
         self
     }
 
+    /// Set the range and enable rounding (d3's `rangeRound`)
+    pub fn range_round(self, start: f64, end: f64) -> Self {
+        self.range(start, end).round(true)
+    }
+
+    /// Set device pixels per logical pixel for rounding (default 1)
+    ///
+    /// With a ratio of 2, rounded positions snap to half pixels, which are
+    /// whole pixels on a HiDPI display.
+    pub fn pixel_ratio(mut self, ratio: f64) -> Self {
+        self.pixel_ratio = if ratio.is_finite() && ratio > 0.0 { ratio } else { 1.0 };
+        self.rescale();
+        self
+    }
+
     /// Get the number of bands
     pub fn len(&self) -> usize {
         self.domain_values.len()
@@ -204,14 +242,7 @@ impl BandScale {
             return self.range_start;
         }
 
-        let start = self.range_start + self.padding_outer * self.cached_step * self.align * 2.0;
-        let pos = start + index as f64 * self.cached_step;
-
-        if self.round {
-            pos.round()
-        } else {
-            pos
-        }
+        self.cached_start + index as f64 * self.cached_step
     }
 
     /// Get the band start position for a category by name
@@ -236,8 +267,7 @@ impl BandScale {
             return None;
         }
 
-        let start = self.range_start + self.padding_outer * self.cached_step * self.align * 2.0;
-        let relative = pixel - start;
+        let relative = pixel - self.cached_start;
 
         if relative < 0.0 {
             return Some(0);
@@ -256,6 +286,7 @@ impl BandScale {
     /// Recalculate cached values when scale parameters change
     fn rescale(&mut self) {
         let n = self.domain_values.len();
+        self.cached_start = self.range_start;
         if n == 0 {
             self.cached_step = 0.0;
             self.cached_bandwidth = 0.0;
@@ -276,11 +307,19 @@ impl BandScale {
         }
 
         self.cached_step = range / divisor;
+        if self.round {
+            self.cached_step = snap_down(self.cached_step, self.pixel_ratio);
+        }
+
+        // Space not taken by the bands and inner padding is split around
+        // them by `align` (just the outer padding unless rounding)
+        let leftover = range - self.cached_step * (n_f - self.padding_inner);
+        self.cached_start = self.range_start + leftover * self.align;
         self.cached_bandwidth = self.cached_step * (1.0 - self.padding_inner);
 
         if self.round {
-            self.cached_step = self.cached_step.floor();
-            self.cached_bandwidth = self.cached_bandwidth.floor();
+            self.cached_start = snap_to_pixel(self.cached_start, self.pixel_ratio);
+            self.cached_bandwidth = snap_to_pixel(self.cached_bandwidth, self.pixel_ratio);
         }
     }
 }
@@ -352,6 +391,7 @@ impl Scale for BandScale {
         self.padding_outer = other.padding_outer;
         self.align = other.align;
         self.round = other.round;
+        self.pixel_ratio = other.pixel_ratio;
         self.rescale();
     }
 
@@ -481,6 +521,33 @@ mod tests {
         assert_eq!(bandwidth, bandwidth.floor());
     }
 
+    #[test]
+    fn test_band_scale_round_distributes_leftover() {
+        let left = BandScale::new()
+            .domain(vec!["A", "B", "C"])
+            .range(0.0, 100.0)
+            .padding(0.1)
+            .align(0.0)
+            .round(true);
+        assert_eq!(left.step(), 32.0);
+        assert_eq!(left.bandwidth(), 29.0);
+        assert_eq!(left.scale_index(0), 0.0);
+        let right = left.clone().align(1.0);
+        assert_eq!(right.scale_index(0), 7.0);
+        assert_eq!(right.scale_index(2), 71.0);
+        assert_eq!(right.invert(72.0), Some("C"));
+
+        // Half-pixel snapping for a 2x display
+        let hidpi = BandScale::new()
+            .domain(vec!["A", "B", "C"])
+            .padding(0.1)
+            .pixel_ratio(2.0)
+            .range_round(0.0, 101.0);
+        assert_eq!(hidpi.step(), 32.5);
+        assert_eq!(hidpi.bandwidth(), 29.5);
+        assert_eq!(hidpi.scale_index(0), 3.5);
+    }
+
     #[test]
     fn test_band_scale_center() {
         let scale = BandScale::new()
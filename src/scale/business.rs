@@ -0,0 +1,417 @@
+//! Business calendars: trading days, session hours and holidays
+//!
+//! A [`BusinessCalendar`] describes when a market or office is open. It
+//! answers working-time questions (is it open, how much open time lies
+//! between two instants, when does a task of a given working duration end)
+//! and backs [`BusinessTimeInterval`], a [`TimeInterval`] whose boundaries
+//! skip closed periods — nights, weekends and holidays. All times are UTC.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::scale::{BusinessCalendar, BusinessTimeInterval, TimeInterval};
+//! use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+//!
+//! let calendar = BusinessCalendar::new()
+//!     .hours(NaiveTime::from_hms_opt(9, 0, 0).unwrap(), NaiveTime::from_hms_opt(17, 0, 0).unwrap())
+//!     .holiday(NaiveDate::from_ymd_opt(2024, 7, 4).unwrap());
+//!
+//! // Wednesday July 3rd, 4 pm: one open hour left, then Friday opens at 9
+//! let t = Utc.with_ymd_and_hms(2024, 7, 3, 16, 0, 0).unwrap();
+//! assert_eq!(calendar.add_business_time(t, Duration::hours(2)), Utc.with_ymd_and_hms(2024, 7, 5, 10, 0, 0).unwrap());
+//!
+//! // Trading-day ticks skip the holiday and the weekend
+//! let days = BusinessTimeInterval::new(TimeInterval::Day(1), calendar);
+//! let end = Utc.with_ymd_and_hms(2024, 7, 9, 0, 0, 0).unwrap();
+//! let opens: Vec<u32> = days.range(t, end, 1).iter().map(|d| d.format("%d").to_string().parse().unwrap()).collect();
+//! assert_eq!(opens, vec![5, 8]);
+//! ```
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc, Weekday};
+
+use super::interval::TimeInterval;
+
+const SECONDS_PER_DAY: u32 = 86_400;
+
+/// How far to look for the next trading day before giving up
+const MAX_SEARCH_DAYS: i64 = 3_660;
+
+/// Trading days, daily session hours and holidays
+///
+/// Defaults to Monday–Friday, open around the clock, with no holidays.
+/// Sessions do not cross midnight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BusinessCalendar {
+    /// Open weekdays, indexed from Monday
+    trading_days: [bool; 7],
+    /// Session open in seconds from midnight
+    open: u32,
+    /// Session close in seconds from midnight (86400 = midnight)
+    close: u32,
+    /// Closed dates
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for BusinessCalendar {
+    fn default() -> Self {
+        Self {
+            trading_days: [true, true, true, true, true, false, false],
+            open: 0,
+            close: SECONDS_PER_DAY,
+            holidays: BTreeSet::new(),
+        }
+    }
+}
+
+impl BusinessCalendar {
+    /// Monday–Friday calendar open all day
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the open weekdays
+    pub fn trading_days(mut self, days: &[Weekday]) -> Self {
+        self.trading_days = [false; 7];
+        for day in days {
+            self.trading_days[day.num_days_from_monday() as usize] = true;
+        }
+        self
+    }
+
+    /// Set the daily session hours
+    ///
+    /// A close at or before the open means the session runs until midnight.
+    pub fn hours(mut self, open: NaiveTime, close: NaiveTime) -> Self {
+        self.open = open.num_seconds_from_midnight();
+        let close = close.num_seconds_from_midnight();
+        self.close = if close > self.open { close } else { SECONDS_PER_DAY };
+        self
+    }
+
+    /// Add a closed date
+    pub fn holiday(mut self, date: NaiveDate) -> Self {
+        self.holidays.insert(date);
+        self
+    }
+
+    /// Add several closed dates
+    pub fn holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(dates);
+        self
+    }
+
+    /// Length of one session
+    pub fn session_length(&self) -> Duration {
+        Duration::seconds((self.close - self.open) as i64)
+    }
+
+    /// Check if the market trades on `date`
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        self.trading_days[date.weekday().num_days_from_monday() as usize] && !self.holidays.contains(&date)
+    }
+
+    /// Open and close of the session on `date`, if it trades
+    pub fn session(&self, date: NaiveDate) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        if !self.is_trading_day(date) {
+            return None;
+        }
+        let midnight = date.and_hms_opt(0, 0, 0)?.and_utc();
+        Some((
+            midnight + Duration::seconds(self.open as i64),
+            midnight + Duration::seconds(self.close as i64),
+        ))
+    }
+
+    /// Check if the market is open at `time` (open inclusive, close exclusive)
+    pub fn is_open(&self, time: DateTime<Utc>) -> bool {
+        self.session(time.date_naive())
+            .is_some_and(|(open, close)| time >= open && time < close)
+    }
+
+    /// First trading day on or after `date`
+    pub fn next_trading_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.search(date, 1)
+    }
+
+    /// Last trading day on or before `date`
+    pub fn previous_trading_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.search(date, -1)
+    }
+
+    /// `time` if the market is open, otherwise the next session open
+    pub fn next_open(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.is_open(time) {
+            return Some(time);
+        }
+        let mut date = time.date_naive();
+        if self.session(date).is_some_and(|(open, _)| time >= open) {
+            date = date.succ_opt()?;
+        }
+        self.session(self.next_trading_day(date)?).map(|(open, _)| open)
+    }
+
+    /// Open time between `start` and `end` (negative if `end` is earlier)
+    pub fn business_duration(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Duration {
+        if end < start {
+            return -self.business_duration(end, start);
+        }
+        let mut total = Duration::zero();
+        let mut date = start.date_naive();
+        while date <= end.date_naive() {
+            if let Some((open, close)) = self.session(date) {
+                let (from, to) = (open.max(start), close.min(end));
+                if to > from {
+                    total += to - from;
+                }
+            }
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        total
+    }
+
+    /// Instant reached after `amount` of open time from `start`
+    ///
+    /// Negative amounts count backwards. Returns `start` when the calendar
+    /// never opens.
+    pub fn add_business_time(&self, start: DateTime<Utc>, amount: Duration) -> DateTime<Utc> {
+        let forward = amount >= Duration::zero();
+        let mut remaining = if forward { amount } else { -amount };
+        let mut date = start.date_naive();
+        for _ in 0..MAX_SEARCH_DAYS * 10 {
+            if let Some((open, close)) = self.session(date) {
+                let (from, to) = if forward {
+                    (open.max(start), close)
+                } else {
+                    (open, close.min(start))
+                };
+                if to > from {
+                    if remaining <= to - from {
+                        return if forward { from + remaining } else { to - remaining };
+                    }
+                    remaining -= to - from;
+                }
+            }
+            let next = if forward { date.succ_opt() } else { date.pred_opt() };
+            match next {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+        start
+    }
+
+    fn search(&self, date: NaiveDate, direction: i64) -> Option<NaiveDate> {
+        (0..MAX_SEARCH_DAYS)
+            .map(|i| date + Duration::days(i * direction))
+            .find(|d| self.is_trading_day(*d))
+    }
+}
+
+/// Time interval whose boundaries skip closed periods
+///
+/// Intraday units (milliseconds to hours) keep the boundaries of the
+/// underlying interval that fall inside a session. Day and longer units
+/// place one boundary at the first session open of each period, so
+/// `Day(1)` ticks every trading day and `Month(1)` the first trading day of
+/// each month. Periods without a session have no boundary.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BusinessTimeInterval {
+    interval: TimeInterval,
+    calendar: BusinessCalendar,
+}
+
+impl BusinessTimeInterval {
+    /// Wrap `interval` with a business calendar
+    pub fn new(interval: TimeInterval, calendar: BusinessCalendar) -> Self {
+        Self { interval, calendar }
+    }
+
+    /// Underlying calendar interval
+    pub fn interval(&self) -> TimeInterval {
+        self.interval
+    }
+
+    /// Business calendar
+    pub fn calendar(&self) -> &BusinessCalendar {
+        &self.calendar
+    }
+
+    /// Latest boundary at or before `time`
+    pub fn floor(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut t = time;
+        for _ in 0..MAX_SEARCH_DAYS {
+            let period = self.interval.floor(t);
+            if self.is_intraday() {
+                if self.calendar.is_open(period) {
+                    return Some(period);
+                }
+                // Continue from the end of the previous session
+                let date = period.date_naive();
+                let close = match self.calendar.session(date) {
+                    Some((_, close)) if period >= close => close,
+                    _ => self.calendar.session(self.calendar.previous_trading_day(date.pred_opt()?)?)?.1,
+                };
+                t = close - Duration::milliseconds(1);
+            } else {
+                if let Some(open) = self.first_open(period) {
+                    if open <= time {
+                        return Some(open);
+                    }
+                }
+                t = period - Duration::milliseconds(1);
+            }
+        }
+        None
+    }
+
+    /// Earliest boundary at or after `time`
+    pub fn ceil(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self.floor(time) {
+            Some(floor) if floor == time => Some(time),
+            _ => self.after(time),
+        }
+    }
+
+    /// Move `steps` boundaries from `floor(time)` (negative steps move back)
+    pub fn offset(&self, time: DateTime<Utc>, steps: i64) -> Option<DateTime<Utc>> {
+        let mut t = self.floor(time).or_else(|| self.ceil(time))?;
+        for _ in 0..steps.unsigned_abs() {
+            t = if steps > 0 {
+                self.after(t)?
+            } else {
+                self.floor(t - Duration::milliseconds(1))?
+            };
+        }
+        Some(t)
+    }
+
+    /// Boundaries in `[start, stop)`, keeping every `step`-th one
+    pub fn range(&self, start: DateTime<Utc>, stop: DateTime<Utc>, step: usize) -> Vec<DateTime<Utc>> {
+        let mut out = Vec::new();
+        let mut current = self.ceil(start);
+        while let Some(t) = current.filter(|t| *t < stop) {
+            out.push(t);
+            current = Some(t);
+            for _ in 0..step.max(1) {
+                current = current.and_then(|c| self.after(c));
+            }
+        }
+        out
+    }
+
+    /// Number of boundaries after `start` up to and including `end`
+    pub fn count(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> i64 {
+        if end < start {
+            return -self.count(end, start);
+        }
+        self.range(start + Duration::milliseconds(1), end + Duration::milliseconds(1), 1)
+            .len() as i64
+    }
+
+    fn is_intraday(&self) -> bool {
+        matches!(
+            self.interval,
+            TimeInterval::Millisecond(_) | TimeInterval::Second(_) | TimeInterval::Minute(_) | TimeInterval::Hour(_)
+        )
+    }
+
+    /// First boundary strictly after `time`
+    fn after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.is_intraday() {
+            let mut t = time;
+            for _ in 0..MAX_SEARCH_DAYS {
+                let candidate = self.interval.ceil(t + Duration::milliseconds(1));
+                if self.calendar.is_open(candidate) {
+                    return Some(candidate);
+                }
+                // Skip the closed stretch up to the next session
+                let open = self.calendar.next_open(candidate)?;
+                t = self.interval.ceil(open) - Duration::milliseconds(1);
+            }
+        } else {
+            let mut period = self.interval.floor(time);
+            for _ in 0..MAX_SEARCH_DAYS {
+                if let Some(open) = self.first_open(period) {
+                    if open > time {
+                        return Some(open);
+                    }
+                }
+                period = self.interval.ceil(period + Duration::milliseconds(1));
+            }
+        }
+        None
+    }
+
+    /// First session open within the period starting at `period`
+    fn first_open(&self, period: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let end = self.interval.ceil(period + Duration::milliseconds(1));
+        let date = self.calendar.next_trading_day(period.date_naive())?;
+        let (open, _) = self.calendar.session(date)?;
+        (open >= period && open < end).then_some(open)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 7, d, h, m, 0).unwrap()
+    }
+
+    fn market() -> BusinessCalendar {
+        BusinessCalendar::new()
+            .hours(NaiveTime::from_hms_opt(9, 30, 0).unwrap(), NaiveTime::from_hms_opt(16, 0, 0).unwrap())
+            .holiday(NaiveDate::from_ymd_opt(2024, 7, 4).unwrap())
+    }
+
+    #[test]
+    fn test_calendar_sessions() {
+        let cal = market();
+        // 2024-07-06 is a Saturday
+        assert!(cal.is_open(at(3, 9, 30)));
+        assert!(!cal.is_open(at(3, 16, 0)));
+        assert!(!cal.is_open(at(4, 12, 0)));
+        assert!(!cal.is_open(at(6, 12, 0)));
+        assert_eq!(cal.next_open(at(3, 17, 0)), Some(at(5, 9, 30)));
+        assert_eq!(cal.next_open(at(5, 8, 0)), Some(at(5, 9, 30)));
+        assert_eq!(cal.next_trading_day(NaiveDate::from_ymd_opt(2024, 7, 6).unwrap()), NaiveDate::from_ymd_opt(2024, 7, 8));
+        assert_eq!(cal.session_length(), Duration::minutes(390));
+        assert!(BusinessCalendar::new().trading_days(&[]).next_open(at(1, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_working_time() {
+        let cal = market();
+        // Wed 15:00 → Mon 10:30: 1 h Wed, 6.5 h Fri, 1 h Mon
+        assert_eq!(cal.business_duration(at(3, 15, 0), at(8, 10, 30)), Duration::minutes(510));
+        assert_eq!(cal.business_duration(at(8, 10, 30), at(3, 15, 0)), Duration::minutes(-510));
+        assert_eq!(cal.add_business_time(at(3, 15, 0), Duration::minutes(510)), at(8, 10, 30));
+        assert_eq!(cal.add_business_time(at(8, 10, 30), Duration::minutes(-510)), at(3, 15, 0));
+        // Starting outside a session counts from the next open
+        assert_eq!(cal.add_business_time(at(6, 12, 0), Duration::hours(1)), at(8, 10, 30));
+    }
+
+    #[test]
+    fn test_business_interval_skips_closed_periods() {
+        let days = BusinessTimeInterval::new(TimeInterval::Day(1), market());
+        assert_eq!(days.floor(at(7, 12, 0)), Some(at(5, 9, 30)));
+        assert_eq!(days.ceil(at(3, 12, 0)), Some(at(5, 9, 30)));
+        assert_eq!(days.offset(at(3, 12, 0), 2), Some(at(8, 9, 30)));
+        assert_eq!(days.offset(at(8, 12, 0), -2), Some(at(3, 9, 30)));
+        assert_eq!(days.count(at(1, 0, 0), at(12, 0, 0)), 8);
+
+        let hours = BusinessTimeInterval::new(TimeInterval::Hour(1), market());
+        let ticks = hours.range(at(3, 14, 0), at(5, 12, 0), 1);
+        assert_eq!(ticks, vec![at(3, 14, 0), at(3, 15, 0), at(5, 10, 0), at(5, 11, 0)]);
+        assert_eq!(hours.floor(at(5, 9, 45)), Some(at(3, 15, 0)));
+
+        let months = BusinessTimeInterval::new(TimeInterval::Month(1), market().holiday(NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()));
+        assert_eq!(months.floor(at(20, 0, 0)), Some(at(2, 9, 30)));
+    }
+}
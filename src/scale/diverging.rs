@@ -0,0 +1,264 @@
+//! Diverging scale implementation
+//!
+//! Diverging scales map a three-point domain (low, pivot, high) onto an
+//! interpolator so the pivot always lands on its midpoint, even when the
+//! two sides of the domain have different extents. A [`ScaleTransform`]
+//! gives d3's `scaleDivergingLog`, `scaleDivergingPow` and
+//! `scaleDivergingSymlog` variants.
+
+use super::sequential::{Interpolator, ScaleTransform};
+use super::traits::{Scale, Tick, TickOptions};
+use super::utils::format_number;
+
+/// Scale that maps a domain with a pivot through an interpolator
+///
+/// Values below the pivot map to `[0, 0.5)` and values above it to
+/// `(0.5, 1]`, each side scaled independently.
+///
+/// # D3.js Equivalent
+/// This is equivalent to `d3.scaleDiverging()` in D3.js.
+///
+/// # Example
+/// ```
+/// use makepad_d3::scale::DivergingScale;
+///
+/// // Temperature anomaly: -2 to +8 degrees around zero
+/// let scale = DivergingScale::new(|t| t).domain(-2.0, 0.0, 8.0);
+/// assert_eq!(scale.interpolate(-2.0), 0.0);
+/// assert_eq!(scale.interpolate(-1.0), 0.25);
+/// assert_eq!(scale.interpolate(0.0), 0.5);
+/// assert_eq!(scale.interpolate(4.0), 0.75);
+///
+/// // Skewed data crossing zero (d3.scaleDivergingSymlog)
+/// let symlog = DivergingScale::new(|t| t).domain(-1e6, 0.0, 1e3).symlog(1.0);
+/// assert!(symlog.interpolate(10.0) > 0.6); // 0.505 without the transform
+/// ```
+pub struct DivergingScale<T> {
+    /// Low end, pivot and high end of the domain
+    domain: (f64, f64, f64),
+    /// The interpolator function
+    interpolator: Interpolator<T>,
+    /// Whether to clamp output to [0, 1]
+    clamp: bool,
+    /// Transform applied before normalizing
+    transform: ScaleTransform,
+}
+
+impl<T> DivergingScale<T> {
+    /// Create a new diverging scale with domain [0, 0.5, 1]
+    pub fn new<F>(interpolator: F) -> Self
+    where
+        F: Fn(f64) -> T + Send + Sync + 'static,
+    {
+        Self {
+            domain: (0.0, 0.5, 1.0),
+            interpolator: Box::new(interpolator),
+            clamp: false,
+            transform: ScaleTransform::Linear,
+        }
+    }
+
+    /// Set the domain as low, pivot, high
+    pub fn domain(mut self, low: f64, pivot: f64, high: f64) -> Self {
+        self.domain = (low, pivot, high);
+        self
+    }
+
+    /// Set the domain as low, pivot, high
+    pub fn set_domain(&mut self, low: f64, pivot: f64, high: f64) {
+        self.domain = (low, pivot, high);
+    }
+
+    /// Get the domain as (low, pivot, high)
+    pub fn get_domain(&self) -> (f64, f64, f64) {
+        self.domain
+    }
+
+    /// Get the pivot value
+    pub fn pivot(&self) -> f64 {
+        self.domain.1
+    }
+
+    /// Enable or disable clamping
+    pub fn clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Check if clamping is enabled
+    pub fn is_clamped(&self) -> bool {
+        self.clamp
+    }
+
+    /// Set the domain transform
+    pub fn transform(mut self, transform: ScaleTransform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Logarithmic transform (d3.scaleDivergingLog)
+    pub fn log(self, base: f64) -> Self {
+        self.transform(ScaleTransform::Log(base))
+    }
+
+    /// Power transform (d3.scaleDivergingPow)
+    pub fn pow(self, exponent: f64) -> Self {
+        self.transform(ScaleTransform::Pow(exponent))
+    }
+
+    /// Symmetric log transform (d3.scaleDivergingSymlog)
+    pub fn symlog(self, constant: f64) -> Self {
+        self.transform(ScaleTransform::Symlog(constant))
+    }
+
+    /// Get the domain transform
+    pub fn get_transform(&self) -> ScaleTransform {
+        self.transform
+    }
+
+    /// Set a new interpolator
+    pub fn interpolator<F>(mut self, interpolator: F) -> Self
+    where
+        F: Fn(f64) -> T + Send + Sync + 'static,
+    {
+        self.interpolator = Box::new(interpolator);
+        self
+    }
+
+    /// Normalize a domain value to [0, 1] with the pivot at 0.5
+    pub fn normalize(&self, value: f64) -> f64 {
+        let (low, pivot, high) = self.domain;
+        let t0 = self.transform.apply(low);
+        let t1 = self.transform.apply(pivot);
+        let t2 = self.transform.apply(high);
+        let k10 = if t0 == t1 { 0.0 } else { 0.5 / (t1 - t0) };
+        let k21 = if t1 == t2 { 0.0 } else { 0.5 / (t2 - t1) };
+        let sign = if t1 < t0 { -1.0 } else { 1.0 };
+
+        let x = self.transform.apply(value);
+        let t = 0.5 + (x - t1) * if sign * x < sign * t1 { k10 } else { k21 };
+        if self.clamp {
+            t.clamp(0.0, 1.0)
+        } else {
+            t
+        }
+    }
+
+    /// Map a domain value through the interpolator
+    pub fn interpolate(&self, value: f64) -> T {
+        (self.interpolator)(self.normalize(value))
+    }
+}
+
+impl Scale for DivergingScale<f64> {
+    fn scale_type(&self) -> &'static str {
+        "diverging"
+    }
+
+    /// Sets low and high, keeping the pivot
+    fn set_domain(&mut self, min: f64, max: f64) {
+        self.domain = (min, self.domain.1, max);
+    }
+
+    fn set_range(&mut self, _start: f64, _end: f64) {
+        // Diverging scales use interpolators, not explicit ranges
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (self.domain.0, self.domain.2)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        ((self.interpolator)(0.0), (self.interpolator)(1.0))
+    }
+
+    fn scale(&self, value: f64) -> f64 {
+        self.interpolate(value)
+    }
+
+    fn invert(&self, _value: f64) -> f64 {
+        f64::NAN
+    }
+
+    fn ticks(&self, options: &TickOptions) -> Vec<Tick> {
+        // Evenly spaced on each side so the pivot is always a tick
+        let count = options.count.min(options.max_count).max(options.min_count).max(3);
+        let (low, pivot, high) = self.domain;
+        let per_side = (count - 1) / 2;
+        let mut values: Vec<f64> = (0..per_side)
+            .map(|i| low + (pivot - low) * i as f64 / per_side as f64)
+            .collect();
+        values.extend((0..=per_side).map(|i| pivot + (high - pivot) * i as f64 / per_side as f64));
+
+        values
+            .into_iter()
+            .map(|v| Tick::new(v, format_number(v)).with_position(v))
+            .collect()
+    }
+
+    fn copy_from(&mut self, other: &Self) {
+        self.domain = other.domain;
+        self.clamp = other.clamp;
+        self.transform = other.transform;
+    }
+
+    fn clone_box(&self) -> Box<dyn Scale> {
+        // Interpolators can't be cloned: rebuild from the three anchor outputs
+        let low = (self.interpolator)(0.0);
+        let mid = (self.interpolator)(0.5);
+        let high = (self.interpolator)(1.0);
+        let (a, b, c) = self.domain;
+        Box::new(
+            DivergingScale::new(move |t| {
+                if t < 0.5 {
+                    low + (mid - low) * t * 2.0
+                } else {
+                    mid + (high - mid) * (t - 0.5) * 2.0
+                }
+            })
+            .domain(a, b, c)
+            .clamp(self.clamp)
+            .transform(self.transform),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diverging_asymmetric_domain() {
+        let scale = DivergingScale::new(|t| t * 100.0).domain(-10.0, 0.0, 100.0);
+        assert!((scale.interpolate(-5.0) - 25.0).abs() < 1e-9);
+        assert!((scale.interpolate(50.0) - 75.0).abs() < 1e-9);
+        assert!((scale.interpolate(200.0) - 150.0).abs() < 1e-9);
+        assert!((scale.clamp(true).interpolate(200.0) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diverging_reversed_and_log() {
+        let reversed = DivergingScale::new(|t| t).domain(10.0, 0.0, -10.0);
+        assert_eq!(reversed.interpolate(5.0), 0.25);
+        assert_eq!(reversed.interpolate(-5.0), 0.75);
+
+        // Ratios around 1: 0.01x ... 1x ... 10x
+        let log = DivergingScale::new(|t| t).domain(0.01, 1.0, 10.0).log(10.0);
+        assert!((log.interpolate(0.1) - 0.25).abs() < 1e-9);
+        assert!((log.interpolate(10f64.sqrt()) - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_diverging_scale_trait() {
+        let mut scale = DivergingScale::new(|t| t).domain(-4.0, 1.0, 6.0);
+        let ticks = scale.ticks(&TickOptions::new().with_count(5));
+        let values: Vec<f64> = ticks.iter().map(|t| t.value).collect();
+        assert_eq!(values, vec![-4.0, -1.5, 1.0, 3.5, 6.0]);
+
+        Scale::set_domain(&mut scale, -2.0, 2.0);
+        assert_eq!(scale.pivot(), 1.0);
+        let boxed = scale.clone_box();
+        assert!((boxed.scale(1.0) - 0.5).abs() < 1e-9);
+        assert!((boxed.scale(-0.5) - 0.25).abs() < 1e-9);
+    }
+}
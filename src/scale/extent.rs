@@ -0,0 +1,189 @@
+//! Domains derived from data
+//!
+//! [`DomainPolicy`] turns a data extent into a scale domain with consistent
+//! padding, zero baselines, minimum spans and nice rounding, replacing ad hoc
+//! `max * 1.1` heuristics in chart builders. It backs
+//! [`LinearScale::from_data`](super::LinearScale::from_data) and
+//! [`TimeScale::from_data`](super::TimeScale::from_data).
+//!
+//! # Example
+//! ```
+//! use makepad_d3::scale::DomainPolicy;
+//!
+//! let policy = DomainPolicy::new().with_padding(0.1).with_zero(true);
+//!
+//! // The zero baseline is kept; only the top is padded
+//! assert_eq!(policy.domain([20.0, 80.0, 50.0]), (0.0, 88.0));
+//! ```
+
+use super::utils::nice_bounds;
+
+/// Extent of the finite values, if any
+pub fn extent(values: impl IntoIterator<Item = f64>) -> Option<(f64, f64)> {
+    values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold(None, |acc, v| match acc {
+            Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+            None => Some((v, v)),
+        })
+}
+
+/// Rules for turning a data extent into a scale domain
+///
+/// Applied in order: include zero, enforce the minimum span, pad, then
+/// round to nice values. Padding never moves a bound across zero when
+/// zero is included, so bar baselines stay put.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DomainPolicy {
+    /// Fraction of the span added on each side
+    padding: f64,
+    /// Extend the domain to include zero
+    zero: bool,
+    /// Round the bounds to nice values
+    nice: bool,
+    /// Smallest allowed span
+    min_span: f64,
+}
+
+impl DomainPolicy {
+    /// Plain data extent: no padding, zero, rounding or minimum span
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a fraction of the span on each side (e.g. 0.05 for 5%)
+    pub fn with_padding(mut self, padding: f64) -> Self {
+        self.padding = padding.max(0.0);
+        self
+    }
+
+    /// Extend the domain to include zero (for bar and area charts)
+    pub fn with_zero(mut self, zero: bool) -> Self {
+        self.zero = zero;
+        self
+    }
+
+    /// Round the bounds to nice values
+    pub fn with_nice(mut self, nice: bool) -> Self {
+        self.nice = nice;
+        self
+    }
+
+    /// Enforce a minimum span, grown around the center (or away from zero)
+    ///
+    /// Keeps flat series from producing a zero-width domain or magnifying
+    /// noise.
+    pub fn with_min_span(mut self, span: f64) -> Self {
+        self.min_span = span.max(0.0);
+        self
+    }
+
+    /// Get the padding fraction
+    pub fn padding(&self) -> f64 {
+        self.padding
+    }
+
+    /// Check if zero is included
+    pub fn includes_zero(&self) -> bool {
+        self.zero
+    }
+
+    /// Check if bounds are rounded
+    pub fn is_nice(&self) -> bool {
+        self.nice
+    }
+
+    /// Get the minimum span
+    pub fn min_span(&self) -> f64 {
+        self.min_span
+    }
+
+    /// Domain for the given values; `(0, 1)` if none are finite
+    pub fn domain(&self, values: impl IntoIterator<Item = f64>) -> (f64, f64) {
+        match extent(values) {
+            Some((min, max)) => self.apply(min, max),
+            None => (0.0, 1.0),
+        }
+    }
+
+    /// Apply the policy to an existing extent
+    pub fn apply(&self, min: f64, max: f64) -> (f64, f64) {
+        let (min, max) = self.apply_without_nice(min, max);
+        if self.nice {
+            nice_bounds(min, max)
+        } else {
+            (min, max)
+        }
+    }
+
+    /// Zero, minimum span and padding, leaving rounding to the caller
+    ///
+    /// Time scales round to calendar intervals instead of decimal steps.
+    pub(crate) fn apply_without_nice(&self, min: f64, max: f64) -> (f64, f64) {
+        let (mut min, mut max) = if min <= max { (min, max) } else { (max, min) };
+        if self.zero {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+
+        let span = max - min;
+        if span < self.min_span {
+            let grow = self.min_span - span;
+            if self.zero && min == 0.0 && max > 0.0 {
+                max += grow;
+            } else if self.zero && max == 0.0 && min < 0.0 {
+                min -= grow;
+            } else {
+                min -= grow / 2.0;
+                max += grow / 2.0;
+            }
+        }
+
+        let pad = (max - min) * self.padding;
+        let pinned_min = self.zero && min == 0.0;
+        let pinned_max = self.zero && max == 0.0;
+        if !pinned_min {
+            min -= pad;
+        }
+        if !pinned_max {
+            max += pad;
+        }
+        (min, max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extent_skips_non_finite() {
+        assert_eq!(extent([3.0, f64::NAN, -1.0, f64::INFINITY, 2.0]), Some((-1.0, 3.0)));
+        assert_eq!(extent(Vec::new()), None);
+        assert_eq!(DomainPolicy::new().domain([f64::NAN]), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_padding_and_zero() {
+        let padded = DomainPolicy::new().with_padding(0.1);
+        assert_eq!(padded.domain([10.0, 20.0]), (9.0, 21.0));
+
+        // Negative data keeps its zero ceiling
+        let bars = DomainPolicy::new().with_padding(0.5).with_zero(true);
+        assert_eq!(bars.domain([-40.0, -10.0]), (-60.0, 0.0));
+        // Data on both sides of zero is padded on both sides
+        assert_eq!(bars.domain([-10.0, 10.0]), (-20.0, 20.0));
+    }
+
+    #[test]
+    fn test_min_span_and_nice() {
+        let policy = DomainPolicy::new().with_min_span(10.0);
+        assert_eq!(policy.domain([42.0]), (37.0, 47.0));
+        // Grows away from a zero baseline
+        assert_eq!(policy.with_zero(true).domain([0.0, 2.0]), (0.0, 10.0));
+
+        let nice = DomainPolicy::new().with_padding(0.05).with_nice(true);
+        assert_eq!(nice.domain([3.2, 97.8]), (-20.0, 120.0));
+    }
+}
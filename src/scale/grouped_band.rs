@@ -0,0 +1,406 @@
+//! Grouped (multi-level) band scale
+//!
+//! Business bar charts often nest categories, e.g. months within quarters.
+//! [`GroupedBandScale`] lays out the inner categories as uniform bands and
+//! adds an extra gap between groups, so the axis can show a second label row
+//! for the groups with separators between them (see
+//! [`Axis::compute_grouped_layout`](crate::axis::Axis::compute_grouped_layout)).
+
+use super::traits::{DiscreteScale, Scale, Tick, TickOptions};
+
+/// A named group of inner categories
+#[derive(Clone, Debug, PartialEq)]
+pub struct BandGroup {
+    /// Group label
+    pub name: String,
+    /// Inner category labels
+    pub items: Vec<String>,
+}
+
+/// Band scale with categories nested in groups
+///
+/// All bands share one step and bandwidth. Inner and outer padding work as
+/// in [`BandScale`](super::BandScale); `group_padding` adds that fraction of
+/// a step between the last band of one group and the first of the next
+/// (empty groups add no gap). Flat indices count bands across all groups in
+/// order.
+///
+/// # Example
+/// ```
+/// use makepad_d3::scale::{DiscreteScale, GroupedBandScale};
+///
+/// let scale = GroupedBandScale::new()
+///     .group("Q1", ["Jan", "Feb", "Mar"])
+///     .group("Q2", ["Apr", "May", "Jun"])
+///     .range(0.0, 700.0)
+///     .group_padding(1.0);
+///
+/// // 6 bands plus one step of gap between the quarters
+/// assert_eq!(scale.step(), 100.0);
+/// assert_eq!(scale.scale_item("Q2", "Apr"), Some(400.0));
+/// assert_eq!(scale.separators(), vec![350.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GroupedBandScale {
+    /// Groups in display order
+    groups: Vec<BandGroup>,
+    /// Start of output range
+    range_start: f64,
+    /// End of output range
+    range_end: f64,
+    /// Padding between bands (fraction of step)
+    padding_inner: f64,
+    /// Padding at the edges (fraction of step)
+    padding_outer: f64,
+    /// Extra gap between groups (fraction of step)
+    group_padding: f64,
+    /// Cached step
+    cached_step: f64,
+    /// Cached bandwidth
+    cached_bandwidth: f64,
+}
+
+impl Default for GroupedBandScale {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GroupedBandScale {
+    /// Create an empty scale with range [0, 1] and a half-step group gap
+    pub fn new() -> Self {
+        Self {
+            groups: Vec::new(),
+            range_start: 0.0,
+            range_end: 1.0,
+            padding_inner: 0.0,
+            padding_outer: 0.0,
+            group_padding: 0.5,
+            cached_step: 0.0,
+            cached_bandwidth: 0.0,
+        }
+    }
+
+    /// Append a group of inner categories
+    pub fn group<S: Into<String>>(
+        mut self,
+        name: impl Into<String>,
+        items: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.groups.push(BandGroup {
+            name: name.into(),
+            items: items.into_iter().map(Into::into).collect(),
+        });
+        self.rescale();
+        self
+    }
+
+    /// Replace all groups
+    pub fn set_groups(&mut self, groups: Vec<BandGroup>) {
+        self.groups = groups;
+        self.rescale();
+    }
+
+    /// Get the groups
+    pub fn groups(&self) -> &[BandGroup] {
+        &self.groups
+    }
+
+    /// Set the output range
+    pub fn range(mut self, start: f64, end: f64) -> Self {
+        self.range_start = start;
+        self.range_end = end;
+        self.rescale();
+        self
+    }
+
+    /// Set both inner and outer padding (fraction of step)
+    pub fn padding(mut self, padding: f64) -> Self {
+        let p = padding.clamp(0.0, 1.0);
+        self.padding_inner = p;
+        self.padding_outer = p;
+        self.rescale();
+        self
+    }
+
+    /// Set the padding between bands (fraction of step)
+    pub fn padding_inner(mut self, padding: f64) -> Self {
+        self.padding_inner = padding.clamp(0.0, 1.0);
+        self.rescale();
+        self
+    }
+
+    /// Set the padding at the edges (fraction of step)
+    pub fn padding_outer(mut self, padding: f64) -> Self {
+        self.padding_outer = padding.max(0.0);
+        self.rescale();
+        self
+    }
+
+    /// Set the extra gap between groups (fraction of step)
+    pub fn group_padding(mut self, padding: f64) -> Self {
+        self.group_padding = padding.max(0.0);
+        self.rescale();
+        self
+    }
+
+    /// Number of bands across all groups
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|g| g.items.len()).sum()
+    }
+
+    /// Check if there are no bands
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of groups
+    pub fn group_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Index of a group by name
+    pub fn group_index(&self, group: &str) -> Option<usize> {
+        self.groups.iter().position(|g| g.name == group)
+    }
+
+    /// Flat band index of an item within a group
+    pub fn index_of(&self, group: &str, item: &str) -> Option<usize> {
+        let g = self.group_index(group)?;
+        let i = self.groups[g].items.iter().position(|it| it == item)?;
+        Some(self.group_offset(g) + i)
+    }
+
+    /// Group and in-group indices for a flat band index
+    pub fn locate(&self, index: usize) -> Option<(usize, usize)> {
+        let mut remaining = index;
+        for (g, group) in self.groups.iter().enumerate() {
+            if remaining < group.items.len() {
+                return Some((g, remaining));
+            }
+            remaining -= group.items.len();
+        }
+        None
+    }
+
+    /// Band start for an item within a group
+    pub fn scale_item(&self, group: &str, item: &str) -> Option<f64> {
+        self.index_of(group, item).map(|i| self.scale_index(i))
+    }
+
+    /// Band start for a flat index (the range start if out of bounds)
+    pub fn scale_index(&self, index: usize) -> f64 {
+        match self.locate(index) {
+            Some((g, _)) => {
+                let gaps = self.groups[..g].iter().filter(|g| !g.items.is_empty()).count();
+                self.range_start
+                    + self.cached_step
+                        * (self.padding_outer + index as f64 + gaps as f64 * self.group_padding)
+            }
+            None => self.range_start,
+        }
+    }
+
+    /// Band center for a flat index
+    pub fn center(&self, index: usize) -> f64 {
+        self.scale_index(index) + self.cached_bandwidth / 2.0
+    }
+
+    /// Pixel extent of a group, from its first band start to its last band end
+    pub fn group_extent(&self, group: usize) -> Option<(f64, f64)> {
+        let len = self.groups.get(group)?.items.len();
+        if len == 0 {
+            return None;
+        }
+        let first = self.group_offset(group);
+        let last = first + len - 1;
+        Some((
+            self.scale_index(first),
+            self.scale_index(last) + self.cached_bandwidth,
+        ))
+    }
+
+    /// Center of a group's extent, where its label goes
+    pub fn group_center(&self, group: usize) -> Option<f64> {
+        self.group_extent(group).map(|(a, b)| (a + b) / 2.0)
+    }
+
+    /// Positions halfway between adjacent non-empty groups
+    pub fn separators(&self) -> Vec<f64> {
+        let extents: Vec<(f64, f64)> =
+            (0..self.groups.len()).filter_map(|g| self.group_extent(g)).collect();
+        extents
+            .windows(2)
+            .map(|pair| (pair[0].1 + pair[1].0) / 2.0)
+            .collect()
+    }
+
+    /// Band at a pixel position as (group, item) indices
+    pub fn invert_index(&self, pixel: f64) -> Option<(usize, usize)> {
+        (0..self.len())
+            .min_by(|&a, &b| {
+                let da = (self.center(a) - pixel).abs();
+                let db = (self.center(b) - pixel).abs();
+                da.total_cmp(&db)
+            })
+            .and_then(|i| self.locate(i))
+    }
+
+    /// Band at a pixel position as (group, item) labels
+    pub fn invert(&self, pixel: f64) -> Option<(&str, &str)> {
+        let (g, i) = self.invert_index(pixel)?;
+        let group = &self.groups[g];
+        Some((group.name.as_str(), group.items[i].as_str()))
+    }
+
+    fn group_offset(&self, group: usize) -> usize {
+        self.groups[..group].iter().map(|g| g.items.len()).sum()
+    }
+
+    fn rescale(&mut self) {
+        let n = self.len();
+        if n == 0 {
+            self.cached_step = 0.0;
+            self.cached_bandwidth = 0.0;
+            return;
+        }
+        let gaps = self.groups.iter().filter(|g| !g.items.is_empty()).count().saturating_sub(1);
+        let range = (self.range_end - self.range_start).abs();
+        let divisor = n as f64 - self.padding_inner
+            + self.padding_outer * 2.0
+            + gaps as f64 * self.group_padding;
+        self.cached_step = if divisor > 0.0 { range / divisor } else { range / n as f64 };
+        self.cached_bandwidth = self.cached_step * (1.0 - self.padding_inner);
+    }
+}
+
+impl Scale for GroupedBandScale {
+    fn scale_type(&self) -> &'static str {
+        "grouped-band"
+    }
+
+    fn set_domain(&mut self, _min: f64, _max: f64) {
+        // Discrete domain; use group() or set_groups() instead
+    }
+
+    fn set_range(&mut self, start: f64, end: f64) {
+        self.range_start = start;
+        self.range_end = end;
+        self.rescale();
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (0.0, self.len().saturating_sub(1) as f64)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.range_start, self.range_end)
+    }
+
+    fn scale(&self, value: f64) -> f64 {
+        self.scale_index(value.round().max(0.0) as usize)
+    }
+
+    fn invert(&self, pixel: f64) -> f64 {
+        self.invert_index(pixel)
+            .map(|(g, i)| self.group_offset(g) + i)
+            .unwrap_or(0) as f64
+    }
+
+    /// One tick per inner category, at the band center
+    fn ticks(&self, _options: &TickOptions) -> Vec<Tick> {
+        self.groups
+            .iter()
+            .flat_map(|g| g.items.iter())
+            .enumerate()
+            .map(|(i, label)| Tick::new(i as f64, label.clone()).with_position(self.center(i)))
+            .collect()
+    }
+
+    fn copy_from(&mut self, other: &Self) {
+        *self = other.clone();
+    }
+
+    fn clone_box(&self) -> Box<dyn Scale> {
+        Box::new(self.clone())
+    }
+}
+
+impl DiscreteScale for GroupedBandScale {
+    fn bandwidth(&self) -> f64 {
+        self.cached_bandwidth
+    }
+
+    fn step(&self) -> f64 {
+        self.cached_step
+    }
+
+    fn set_padding(&mut self, padding: f64) {
+        let p = padding.clamp(0.0, 1.0);
+        self.padding_inner = p;
+        self.padding_outer = p;
+        self.rescale();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quarters() -> GroupedBandScale {
+        GroupedBandScale::new()
+            .group("Q1", ["Jan", "Feb", "Mar"])
+            .group("Q2", ["Apr", "May"])
+    }
+
+    #[test]
+    fn test_grouped_band_layout() {
+        // 5 bands - 0.5 inner + 2 * 0.25 outer + 1 gap = 6 steps
+        let scale = quarters()
+            .range(0.0, 600.0)
+            .padding_inner(0.5)
+            .padding_outer(0.25)
+            .group_padding(1.0);
+        assert_eq!(scale.step(), 100.0);
+        assert_eq!(scale.bandwidth(), 50.0);
+        assert_eq!(scale.scale_index(0), 25.0);
+        assert_eq!(scale.scale_item("Q1", "Mar"), Some(225.0));
+        assert_eq!(scale.scale_item("Q2", "Apr"), Some(425.0));
+        assert_eq!(scale.scale_item("Q2", "Jan"), None);
+        assert_eq!(scale.group_extent(0), Some((25.0, 275.0)));
+        assert_eq!(scale.group_extent(1), Some((425.0, 575.0)));
+        assert_eq!(scale.group_center(1), Some(500.0));
+        assert_eq!(scale.separators(), vec![350.0]);
+    }
+
+    #[test]
+    fn test_grouped_band_ticks_and_invert() {
+        let scale = quarters().range(0.0, 550.0).group_padding(0.5);
+        let ticks = scale.ticks(&TickOptions::default());
+        let labels: Vec<_> = ticks.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(labels, ["Jan", "Feb", "Mar", "Apr", "May"]);
+        assert_eq!(ticks[3].position, 400.0);
+
+        assert_eq!(scale.locate(4), Some((1, 1)));
+        assert_eq!(scale.locate(5), None);
+        assert_eq!(scale.invert(420.0), Some(("Q2", "Apr")));
+        assert_eq!(scale.invert(10.0), Some(("Q1", "Jan")));
+        assert_eq!(Scale::invert(&scale, 520.0), 4.0);
+    }
+
+    #[test]
+    fn test_grouped_band_empty_groups() {
+        let scale = GroupedBandScale::new()
+            .group("A", ["a1", "a2"])
+            .group("Empty", Vec::<String>::new())
+            .group("B", ["b1"])
+            .range(0.0, 350.0)
+            .group_padding(0.5);
+        // Empty groups add no gap: 3 bands + 1 gap of half a step
+        assert_eq!(scale.step(), 100.0);
+        assert_eq!(scale.scale_index(2), 250.0);
+        assert_eq!(scale.locate(2), Some((2, 0)));
+        assert_eq!(scale.group_extent(1), None);
+        assert_eq!(scale.separators(), vec![225.0]);
+    }
+}
@@ -0,0 +1,550 @@
+//! Time intervals and calendar arithmetic (`d3-time`)
+//!
+//! A [`TimeInterval`] is a calendar unit with a step multiplier. Besides
+//! choosing tick spacing for [`TimeScale`](super::TimeScale) it supports the
+//! d3-time operations: [`floor`](TimeInterval::floor),
+//! [`ceil`](TimeInterval::ceil), [`round`](TimeInterval::round),
+//! [`offset`](TimeInterval::offset), [`range`](TimeInterval::range) and
+//! [`count`](TimeInterval::count). All arithmetic is in UTC.
+//!
+//! Multipliers behave like d3's `interval.every(n)`: boundaries are aligned
+//! to the parent unit, so `Day(2)` starts on days 1, 3, 5, … of each month
+//! and `Minute(15)` on :00, :15, :30 and :45.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::scale::TimeInterval;
+//! use chrono::{TimeZone, Utc, Weekday};
+//!
+//! let t = Utc.with_ymd_and_hms(2024, 3, 14, 15, 9, 26).unwrap();
+//! assert_eq!(TimeInterval::Day(1).floor(t), Utc.with_ymd_and_hms(2024, 3, 14, 0, 0, 0).unwrap());
+//! assert_eq!(TimeInterval::Month(1).ceil(t), Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap());
+//!
+//! // Sunday-based weeks
+//! let sunday = TimeInterval::WeekStarting(Weekday::Sun, 1);
+//! assert_eq!(sunday.floor(t), Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap());
+//!
+//! let end = Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap();
+//! assert_eq!(TimeInterval::Hour(6).range(t, end, 1).len(), 1);
+//! assert_eq!(TimeInterval::Hour(1).count(t, end), 9);
+//! ```
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Timelike, Utc, Weekday};
+
+use super::locale::TimeZoneRule;
+
+/// Calendar interval with a step multiplier
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeInterval {
+    /// Milliseconds with multiplier
+    Millisecond(u32),
+    /// Seconds with multiplier
+    Second(u32),
+    /// Minutes with multiplier
+    Minute(u32),
+    /// Hours with multiplier
+    Hour(u32),
+    /// Days with multiplier
+    Day(u32),
+    /// Monday-based weeks with multiplier
+    Week(u32),
+    /// Weeks starting on the given weekday, with multiplier
+    WeekStarting(Weekday, u32),
+    /// Months with multiplier
+    Month(u32),
+    /// Years with multiplier
+    Year(u32),
+}
+
+impl TimeInterval {
+    /// Get the approximate duration in milliseconds
+    pub fn duration_ms(&self) -> f64 {
+        match self {
+            Self::Millisecond(n) => *n as f64,
+            Self::Second(n) => *n as f64 * 1000.0,
+            Self::Minute(n) => *n as f64 * 60_000.0,
+            Self::Hour(n) => *n as f64 * 3_600_000.0,
+            Self::Day(n) => *n as f64 * 86_400_000.0,
+            Self::Week(n) | Self::WeekStarting(_, n) => *n as f64 * 604_800_000.0,
+            Self::Month(n) => *n as f64 * 2_592_000_000.0, // ~30 days
+            Self::Year(n) => *n as f64 * 31_536_000_000.0, // 365 days
+        }
+    }
+
+    /// Get a reasonable default format string for this interval
+    pub fn default_format(&self) -> &'static str {
+        match self {
+            Self::Millisecond(_) => "%H:%M:%S.%3f",
+            Self::Second(_) => "%H:%M:%S",
+            Self::Minute(_) | Self::Hour(_) => "%H:%M",
+            Self::Day(_) | Self::Week(_) | Self::WeekStarting(..) => "%b %d",
+            Self::Month(_) => "%b %Y",
+            Self::Year(_) => "%Y",
+        }
+    }
+
+    /// Find the appropriate interval for a given duration and target tick count
+    pub fn for_duration(duration_ms: f64, target_ticks: usize) -> Self {
+        if target_ticks == 0 {
+            return Self::Year(1);
+        }
+
+        let target_interval = duration_ms / target_ticks as f64;
+
+        // Intervals in ascending order of duration
+        let intervals: &[(f64, TimeInterval)] = &[
+            (1.0, Self::Millisecond(1)),
+            (5.0, Self::Millisecond(5)),
+            (10.0, Self::Millisecond(10)),
+            (50.0, Self::Millisecond(50)),
+            (100.0, Self::Millisecond(100)),
+            (500.0, Self::Millisecond(500)),
+            (1000.0, Self::Second(1)),
+            (5000.0, Self::Second(5)),
+            (15000.0, Self::Second(15)),
+            (30000.0, Self::Second(30)),
+            (60000.0, Self::Minute(1)),
+            (300000.0, Self::Minute(5)),
+            (900000.0, Self::Minute(15)),
+            (1800000.0, Self::Minute(30)),
+            (3600000.0, Self::Hour(1)),
+            (10800000.0, Self::Hour(3)),
+            (21600000.0, Self::Hour(6)),
+            (43200000.0, Self::Hour(12)),
+            (86400000.0, Self::Day(1)),
+            (172800000.0, Self::Day(2)),
+            (604800000.0, Self::Week(1)),
+            (1209600000.0, Self::Week(2)),
+            (2592000000.0, Self::Month(1)),
+            (7776000000.0, Self::Month(3)),
+            (15552000000.0, Self::Month(6)),
+            (31536000000.0, Self::Year(1)),
+            (63072000000.0, Self::Year(2)),
+            (157680000000.0, Self::Year(5)),
+            (315360000000.0, Self::Year(10)),
+        ];
+
+        intervals
+            .iter()
+            .find(|(ms, _)| *ms >= target_interval)
+            .map(|(_, interval)| *interval)
+            .unwrap_or(Self::Year(10))
+    }
+}
+
+impl TimeInterval {
+    /// Same unit with a different step multiplier (`interval.every(n)`)
+    pub fn every(self, step: u32) -> Self {
+        match self {
+            Self::Millisecond(_) => Self::Millisecond(step),
+            Self::Second(_) => Self::Second(step),
+            Self::Minute(_) => Self::Minute(step),
+            Self::Hour(_) => Self::Hour(step),
+            Self::Day(_) => Self::Day(step),
+            Self::Week(_) => Self::Week(step),
+            Self::WeekStarting(day, _) => Self::WeekStarting(day, step),
+            Self::Month(_) => Self::Month(step),
+            Self::Year(_) => Self::Year(step),
+        }
+    }
+
+    /// Step multiplier (at least 1)
+    pub fn step(&self) -> u32 {
+        let n = match self {
+            Self::Millisecond(n)
+            | Self::Second(n)
+            | Self::Minute(n)
+            | Self::Hour(n)
+            | Self::Day(n)
+            | Self::Week(n)
+            | Self::WeekStarting(_, n)
+            | Self::Month(n)
+            | Self::Year(n) => *n,
+        };
+        n.max(1)
+    }
+
+    /// Latest interval boundary at or before `time`
+    pub fn floor(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let n = self.step();
+        match *self {
+            Self::Millisecond(_) => {
+                let ms = time.timestamp_millis();
+                from_millis(ms - ms.rem_euclid(n as i64))
+            }
+            Self::Second(_) => {
+                let t = truncate_to_second(time);
+                t - Duration::seconds((t.second() % n) as i64)
+            }
+            Self::Minute(_) => {
+                let t = truncate_to_second(time) - Duration::seconds(time.second() as i64);
+                t - Duration::minutes((t.minute() % n) as i64)
+            }
+            Self::Hour(_) => {
+                let t = start_of_day(time) + Duration::hours(time.hour() as i64);
+                t - Duration::hours((t.hour() % n) as i64)
+            }
+            Self::Day(_) => {
+                let t = start_of_day(time);
+                t - Duration::days(((t.day() - 1) % n) as i64)
+            }
+            Self::Week(_) => Self::WeekStarting(Weekday::Mon, n).floor(time),
+            Self::WeekStarting(first, _) => {
+                let t = start_of_day(time);
+                let back = (7 + t.weekday().num_days_from_monday() - first.num_days_from_monday()) % 7;
+                let t = t - Duration::days(back as i64);
+                t - Duration::weeks(week_index(t, first).rem_euclid(n as i64))
+            }
+            Self::Month(_) => {
+                let month = (time.month() - 1) / n * n + 1;
+                ymd(time.year(), month, 1)
+            }
+            Self::Year(_) => {
+                let year = time.year() - time.year().rem_euclid(n as i32);
+                ymd(year, 1, 1)
+            }
+        }
+    }
+
+    /// Earliest interval boundary at or after `time`
+    pub fn ceil(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let floor = self.floor(time);
+        if floor == time {
+            time
+        } else {
+            self.next(floor)
+        }
+    }
+
+    /// Nearest interval boundary to `time`, rounding ties up
+    pub fn round(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let floor = self.floor(time);
+        let ceil = self.ceil(time);
+        if time - floor < ceil - time {
+            floor
+        } else {
+            ceil
+        }
+    }
+
+    /// Move `time` by `steps` intervals (negative steps move backwards)
+    ///
+    /// The time is not floored first. Month and year offsets clamp the day
+    /// to the end of shorter months, so Jan 31 + 1 month is Feb 28/29.
+    pub fn offset(&self, time: DateTime<Utc>, steps: i64) -> DateTime<Utc> {
+        let k = steps * self.step() as i64;
+        match self {
+            Self::Millisecond(_) => time + Duration::milliseconds(k),
+            Self::Second(_) => time + Duration::seconds(k),
+            Self::Minute(_) => time + Duration::minutes(k),
+            Self::Hour(_) => time + Duration::hours(k),
+            Self::Day(_) => time + Duration::days(k),
+            Self::Week(_) | Self::WeekStarting(..) => time + Duration::weeks(k),
+            Self::Month(_) => add_months(time, k),
+            Self::Year(_) => add_months(time, k * 12),
+        }
+    }
+
+    /// Boundaries in `[start, stop)`, keeping every `step`-th one
+    ///
+    /// Starts at `ceil(start)` like d3's `interval.range`; a `step` of 0 is
+    /// treated as 1.
+    pub fn range(&self, start: DateTime<Utc>, stop: DateTime<Utc>, step: usize) -> Vec<DateTime<Utc>> {
+        let mut out = Vec::new();
+        let mut current = self.ceil(start);
+        while current < stop {
+            out.push(current);
+            for _ in 0..step.max(1) {
+                current = self.next(current);
+            }
+        }
+        out
+    }
+
+    /// Number of boundaries after `start` up to and including `end`
+    ///
+    /// Negative when `end` is before `start`.
+    pub fn count(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> i64 {
+        if end < start {
+            return -self.count(end, start);
+        }
+        let (a, b) = (self.floor(start), self.floor(end));
+        if self.step() == 1 {
+            match self {
+                Self::Month(_) => return months_between(a, b),
+                Self::Year(_) => return (b.year() - a.year()) as i64,
+                _ => return (b - a).num_milliseconds() / self.duration_ms() as i64,
+            }
+        }
+        let mut count = 0;
+        let mut current = a;
+        while current < b {
+            current = self.next(current);
+            count += 1;
+        }
+        count
+    }
+
+    /// Evaluate this interval on local wall-clock time in `zone`
+    pub fn in_zone(self, zone: TimeZoneRule) -> ZonedInterval {
+        ZonedInterval::new(self, zone)
+    }
+
+    /// Whether the unit is shorter than a day (fixed length in any zone)
+    fn is_sub_daily(&self) -> bool {
+        matches!(self, Self::Millisecond(_) | Self::Second(_) | Self::Minute(_) | Self::Hour(_))
+    }
+
+    /// Boundary following the boundary `time`
+    fn next(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        // Aligned steps restart at each parent unit (day 31 + 2 days floors
+        // to the 1st), so re-floor after offsetting
+        self.floor(self.offset(time, 1))
+    }
+}
+
+/// A [`TimeInterval`] aligned to local wall-clock time
+///
+/// The local counterpart of the UTC arithmetic on [`TimeInterval`], like
+/// d3's `timeDay` next to `utcDay`: days start at local midnight and hours
+/// on the local hour (which matters for half-hour offsets). Inputs and
+/// results are UTC instants.
+///
+/// # Example
+/// ```
+/// use makepad_d3::scale::{TimeInterval, TimeZoneRule};
+/// use chrono::{TimeZone, Utc};
+///
+/// let new_york = TimeInterval::Day(1).in_zone(TimeZoneRule::us_eastern());
+/// let t = Utc.with_ymd_and_hms(2024, 3, 10, 12, 0, 0).unwrap();
+/// // Local midnight in EST
+/// assert_eq!(new_york.floor(t), Utc.with_ymd_and_hms(2024, 3, 10, 5, 0, 0).unwrap());
+/// // The DST switch makes this local day 23 hours long
+/// assert_eq!(new_york.ceil(t), Utc.with_ymd_and_hms(2024, 3, 11, 4, 0, 0).unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZonedInterval {
+    interval: TimeInterval,
+    zone: TimeZoneRule,
+}
+
+impl ZonedInterval {
+    /// Evaluate `interval` in `zone`
+    pub fn new(interval: TimeInterval, zone: TimeZoneRule) -> Self {
+        Self { interval, zone }
+    }
+
+    /// Underlying interval
+    pub fn interval(&self) -> TimeInterval {
+        self.interval
+    }
+
+    /// Time zone
+    pub fn zone(&self) -> &TimeZoneRule {
+        &self.zone
+    }
+
+    /// Latest local boundary at or before `time`
+    pub fn floor(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let local = self.interval.floor(self.zone.to_local(time).and_utc());
+        self.zone.to_utc(local.naive_utc())
+    }
+
+    /// Earliest local boundary at or after `time`
+    pub fn ceil(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let floor = self.floor(time);
+        if floor == time {
+            time
+        } else {
+            self.next(floor)
+        }
+    }
+
+    /// Nearest local boundary to `time`, rounding ties up
+    pub fn round(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let (floor, ceil) = (self.floor(time), self.ceil(time));
+        if time - floor < ceil - time {
+            floor
+        } else {
+            ceil
+        }
+    }
+
+    /// Move `time` by `steps` intervals
+    ///
+    /// Units up to hours add elapsed time; days and longer keep the local
+    /// time of day across daylight-saving changes.
+    pub fn offset(&self, time: DateTime<Utc>, steps: i64) -> DateTime<Utc> {
+        if self.interval.is_sub_daily() {
+            return self.interval.offset(time, steps);
+        }
+        let local = self.interval.offset(self.zone.to_local(time).and_utc(), steps);
+        self.zone.to_utc(local.naive_utc())
+    }
+
+    /// Local boundaries in `[start, stop)`, keeping every `step`-th one
+    pub fn range(&self, start: DateTime<Utc>, stop: DateTime<Utc>, step: usize) -> Vec<DateTime<Utc>> {
+        let mut out = Vec::new();
+        let mut current = self.ceil(start);
+        while current < stop {
+            out.push(current);
+            for _ in 0..step.max(1) {
+                current = self.next(current);
+            }
+        }
+        out
+    }
+
+    /// Number of local boundaries after `start` up to and including `end`
+    pub fn count(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> i64 {
+        if self.zone == TimeZoneRule::Utc {
+            return self.interval.count(start, end);
+        }
+        if end < start {
+            return -self.count(end, start);
+        }
+        let mut count = 0;
+        let mut current = self.floor(start);
+        let last = self.floor(end);
+        while current < last {
+            current = self.next(current);
+            count += 1;
+        }
+        count
+    }
+
+    /// Boundary following the boundary `time`
+    pub(crate) fn next(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        // When clocks fall back a local hour repeats and one step can floor
+        // back onto `time`; step further until the boundary moves
+        (1..=4)
+            .map(|k| self.floor(self.offset(time, k)))
+            .find(|&t| t > time)
+            .unwrap_or_else(|| self.interval.offset(time, 1))
+    }
+}
+
+fn from_millis(ms: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(ms).unwrap_or_default()
+}
+
+fn truncate_to_second(time: DateTime<Utc>) -> DateTime<Utc> {
+    from_millis(time.timestamp() * 1000)
+}
+
+fn start_of_day(time: DateTime<Utc>) -> DateTime<Utc> {
+    truncate_to_second(time) - Duration::seconds(time.num_seconds_from_midnight() as i64)
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+        .unwrap_or_default()
+}
+
+/// Whole weeks between the first `first`-weekday after the epoch and `time`
+fn week_index(time: DateTime<Utc>, first: Weekday) -> i64 {
+    // 1970-01-01 was a Thursday
+    let lead = (7 + first.num_days_from_monday() - Weekday::Thu.num_days_from_monday()) % 7;
+    let days = time.timestamp().div_euclid(86_400) - lead as i64;
+    days.div_euclid(7)
+}
+
+fn add_months(time: DateTime<Utc>, months: i64) -> DateTime<Utc> {
+    let shifted = if months >= 0 {
+        time.checked_add_months(Months::new(months as u32))
+    } else {
+        time.checked_sub_months(Months::new(months.unsigned_abs() as u32))
+    };
+    shifted.unwrap_or(time)
+}
+
+fn months_between(a: DateTime<Utc>, b: DateTime<Utc>) -> i64 {
+    (b.year() - a.year()) as i64 * 12 + b.month() as i64 - a.month() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap()
+    }
+
+    #[test]
+    fn test_floor_ceil_round() {
+        let t = at(2024, 2, 29, 13, 47, 12) + Duration::milliseconds(345);
+        assert_eq!(TimeInterval::Millisecond(100).floor(t), at(2024, 2, 29, 13, 47, 12) + Duration::milliseconds(300));
+        assert_eq!(TimeInterval::Second(1).floor(t), at(2024, 2, 29, 13, 47, 12));
+        assert_eq!(TimeInterval::Minute(15).floor(t), at(2024, 2, 29, 13, 45, 0));
+        assert_eq!(TimeInterval::Hour(6).ceil(t), at(2024, 2, 29, 18, 0, 0));
+        assert_eq!(TimeInterval::Day(1).round(t), at(2024, 3, 1, 0, 0, 0));
+        assert_eq!(TimeInterval::Month(3).floor(t), at(2024, 1, 1, 0, 0, 0));
+        assert_eq!(TimeInterval::Year(10).floor(t), at(2020, 1, 1, 0, 0, 0));
+        // 2024-02-29 is a Thursday
+        assert_eq!(TimeInterval::Week(1).floor(t), at(2024, 2, 26, 0, 0, 0));
+        assert_eq!(TimeInterval::WeekStarting(Weekday::Sun, 1).floor(t), at(2024, 2, 25, 0, 0, 0));
+        assert_eq!(TimeInterval::WeekStarting(Weekday::Fri, 1).ceil(t), at(2024, 3, 1, 0, 0, 0));
+        // Boundaries are fixed points
+        let midnight = at(2024, 3, 1, 0, 0, 0);
+        assert_eq!(TimeInterval::Day(1).ceil(midnight), midnight);
+        assert_eq!(TimeInterval::Month(1).round(midnight), midnight);
+    }
+
+    #[test]
+    fn test_offset_and_range() {
+        let jan31 = at(2023, 1, 31, 8, 0, 0);
+        assert_eq!(TimeInterval::Month(1).offset(jan31, 1), at(2023, 2, 28, 8, 0, 0));
+        assert_eq!(TimeInterval::Year(1).offset(jan31, -2), at(2021, 1, 31, 8, 0, 0));
+        assert_eq!(TimeInterval::Hour(1).offset(jan31, -9), at(2023, 1, 30, 23, 0, 0));
+
+        let days = TimeInterval::Day(1).range(at(2023, 1, 30, 12, 0, 0), at(2023, 2, 3, 0, 0, 0), 1);
+        assert_eq!(days, vec![at(2023, 1, 31, 0, 0, 0), at(2023, 2, 1, 0, 0, 0), at(2023, 2, 2, 0, 0, 0)]);
+
+        // Aligned steps restart each month: days 29, 31, then the 1st
+        let odd = TimeInterval::Day(2).range(at(2023, 1, 28, 0, 0, 0), at(2023, 2, 4, 0, 0, 0), 1);
+        assert_eq!(odd, vec![at(2023, 1, 29, 0, 0, 0), at(2023, 1, 31, 0, 0, 0), at(2023, 2, 1, 0, 0, 0), at(2023, 2, 3, 0, 0, 0)]);
+
+        let quarters = TimeInterval::Month(1).range(at(2023, 1, 1, 0, 0, 0), at(2024, 1, 1, 0, 0, 0), 3);
+        assert_eq!(quarters.len(), 4);
+        assert_eq!(quarters[3], at(2023, 10, 1, 0, 0, 0));
+        assert!(TimeInterval::Day(1).range(at(2023, 2, 1, 0, 0, 0), at(2023, 1, 1, 0, 0, 0), 1).is_empty());
+    }
+
+    #[test]
+    fn test_count() {
+        let start = at(2023, 1, 1, 0, 0, 0);
+        let end = at(2023, 12, 31, 23, 0, 0);
+        assert_eq!(TimeInterval::Day(1).count(start, end), 364);
+        assert_eq!(TimeInterval::Month(1).count(start, end), 11);
+        assert_eq!(TimeInterval::Year(1).count(start, end), 0);
+        assert_eq!(TimeInterval::Hour(1).count(end, start), -(364 * 24 + 23));
+        // 2023-01-01 is a Sunday: 52 further Sundays follow in the year
+        assert_eq!(TimeInterval::WeekStarting(Weekday::Sun, 1).count(start, end), 52);
+        assert_eq!(TimeInterval::Month(3).count(start, end), 3);
+        assert_eq!(TimeInterval::Month(2).every(3), TimeInterval::Month(3));
+    }
+
+    #[test]
+    fn test_zoned_interval() {
+        let india = TimeInterval::Hour(1).in_zone(TimeZoneRule::fixed_hours(5.5));
+        // Local hours start at :30 UTC
+        assert_eq!(india.floor(at(2024, 1, 1, 10, 10, 0)), at(2024, 1, 1, 9, 30, 0));
+        assert_eq!(india.range(at(2024, 1, 1, 0, 0, 0), at(2024, 1, 1, 3, 0, 0), 1).len(), 3);
+
+        // Berlin days across the October DST change are 23, 25 and 24 hours
+        let berlin = TimeInterval::Day(1).in_zone(TimeZoneRule::central_european());
+        let days = berlin.range(at(2024, 10, 26, 0, 0, 0), at(2024, 10, 29, 0, 0, 0), 1);
+        assert_eq!(days, vec![at(2024, 10, 26, 22, 0, 0), at(2024, 10, 27, 23, 0, 0), at(2024, 10, 28, 23, 0, 0)]);
+        assert_eq!(berlin.count(at(2024, 10, 1, 12, 0, 0), at(2024, 11, 1, 12, 0, 0)), 31);
+        assert_eq!(berlin.offset(at(2024, 10, 26, 22, 0, 0), 1), at(2024, 10, 27, 23, 0, 0));
+
+        // Hourly steps through the repeated hour still advance
+        let hours = TimeInterval::Hour(1).in_zone(TimeZoneRule::central_european());
+        let ticks = hours.range(at(2024, 10, 26, 23, 0, 0), at(2024, 10, 27, 3, 0, 0), 1);
+        assert!(ticks.windows(2).all(|w| w[1] > w[0]));
+        assert_eq!(ticks.first(), Some(&at(2024, 10, 26, 23, 0, 0)));
+    }
+}
@@ -1,7 +1,8 @@
 //! Linear scale implementation
 
 use super::traits::{Scale, ContinuousScale, ScaleExt, Tick, TickOptions};
-use super::utils::{nice_step, nice_bounds, format_number};
+use super::utils::{nice_step, nice_bounds, format_number, interpolate_range};
+use super::extent::DomainPolicy;
 
 /// Linear scale for continuous numeric data
 ///
@@ -27,6 +28,7 @@ pub struct LinearScale {
     range_start: f64,
     range_end: f64,
     clamp: bool,
+    round: bool,
     nice: bool,
 }
 
@@ -39,6 +41,7 @@ impl LinearScale {
             range_start: 0.0,
             range_end: 1.0,
             clamp: false,
+            round: false,
             nice: false,
         }
     }
@@ -60,6 +63,17 @@ impl LinearScale {
         self
     }
 
+    /// Round output positions to whole pixels (d3's `rangeRound`)
+    pub fn with_round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Check if output positions are rounded
+    pub fn is_rounded(&self) -> bool {
+        self.round
+    }
+
     /// Extend domain to start at zero (for bar charts)
     pub fn with_zero(mut self) -> Self {
         if self.domain_min > 0.0 {
@@ -75,6 +89,26 @@ impl LinearScale {
     pub fn from_extent(min: f64, max: f64) -> Self {
         Self::new().with_domain(min, max)
     }
+
+    /// Create with the extent of the finite values as domain
+    pub fn from_data(values: impl IntoIterator<Item = f64>) -> Self {
+        Self::from_data_with(values, &DomainPolicy::new())
+    }
+
+    /// Create with a domain derived from values by a [`DomainPolicy`]
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::scale::{DomainPolicy, LinearScale, Scale};
+    ///
+    /// let policy = DomainPolicy::new().with_zero(true).with_padding(0.05).with_nice(true);
+    /// let scale = LinearScale::from_data_with([12.0, 47.0, 33.0], &policy);
+    /// assert_eq!(scale.domain(), (0.0, 50.0));
+    /// ```
+    pub fn from_data_with(values: impl IntoIterator<Item = f64>, policy: &DomainPolicy) -> Self {
+        let (min, max) = policy.domain(values);
+        Self::new().with_domain(min, max)
+    }
 }
 
 impl Default for LinearScale {
@@ -123,7 +157,7 @@ impl Scale for LinearScale {
         };
 
         let t = self.normalize(value);
-        self.range_start + t * (self.range_end - self.range_start)
+        interpolate_range((self.range_start, self.range_end), t, self.round)
     }
 
     fn invert(&self, pixel: f64) -> f64 {
@@ -133,6 +167,7 @@ impl Scale for LinearScale {
         }
 
         let t = (pixel - self.range_start) / range_span;
+        let t = if self.clamp { t.clamp(0.0, 1.0) } else { t };
         self.domain_min + t * (self.domain_max - self.domain_min)
     }
 
@@ -193,6 +228,7 @@ impl Scale for LinearScale {
         self.range_start = other.range_start;
         self.range_end = other.range_end;
         self.clamp = other.clamp;
+        self.round = other.round;
         self.nice = other.nice;
     }
 
@@ -223,6 +259,16 @@ impl ScaleExt for LinearScale {}
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_linear_scale_from_data() {
+        let scale = LinearScale::from_data([5.0, f64::NAN, -3.0, 8.0]);
+        assert_eq!(scale.domain(), (-3.0, 8.0));
+
+        let policy = DomainPolicy::new().with_padding(0.1).with_min_span(1.0);
+        let flat = LinearScale::from_data_with([2.0, 2.0], &policy);
+        assert_eq!(flat.domain(), (1.4, 2.6));
+    }
+
     #[test]
     fn test_linear_scale_new() {
         let scale = LinearScale::new();
@@ -272,6 +318,8 @@ mod tests {
 
         assert_eq!(scale.scale(-50.0), 0.0);
         assert_eq!(scale.scale(150.0), 500.0);
+        assert_eq!(scale.invert(-100.0), 0.0);
+        assert_eq!(scale.invert(600.0), 100.0);
     }
 
     #[test]
@@ -359,6 +407,19 @@ mod tests {
         assert_eq!(scale.normalize(100.0), 1.0);
     }
 
+    #[test]
+    fn test_linear_scale_round() {
+        let scale = LinearScale::new()
+            .with_domain(0.0, 3.0)
+            .with_range(0.0, 100.0)
+            .with_round(true);
+        assert!(scale.is_rounded());
+        assert_eq!(scale.scale(1.0), 33.0);
+        assert_eq!(scale.scale(2.0), 67.0);
+        // Inversion stays exact
+        assert_eq!(scale.invert(50.0), 1.5);
+    }
+
     #[test]
     fn test_linear_scale_from_extent() {
         let scale = LinearScale::from_extent(10.0, 90.0);
@@ -0,0 +1,419 @@
+//! Time locales and time zone rules for time axes
+//!
+//! [`TimeLocale`] mirrors a d3-time-format locale definition: month and
+//! weekday names, AM/PM markers, the `%c` / `%x` / `%X` date and time
+//! patterns, plus the first day of the week used for weekly ticks.
+//! [`TimeZoneRule`] converts between UTC and local wall-clock time, either
+//! at a fixed offset or with yearly daylight-saving transitions, so that
+//! ticks fall on local midnights and labels show local hours.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::scale::{TimeLocale, TimeZoneRule};
+//! use chrono::{TimeZone, Utc};
+//!
+//! let berlin = TimeZoneRule::central_european();
+//! let t = Utc.with_ymd_and_hms(2024, 7, 14, 22, 30, 0).unwrap();
+//! let local = berlin.to_local(t);
+//!
+//! let de = TimeLocale::de_de();
+//! assert_eq!(de.format(local, "%A, %e. %B %H:%M"), "Montag, 15. Juli 00:30");
+//! assert_eq!(de.format(local, "%x"), "15.07.2024");
+//! assert_eq!(berlin.to_utc(local), t);
+//! ```
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Timelike, Utc, Weekday};
+
+/// Month and weekday names, date patterns and week start for a language
+///
+/// Weekday arrays start on Sunday, as in d3 locale definitions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeLocale {
+    /// Pattern for `%c`
+    pub date_time: String,
+    /// Pattern for `%x`
+    pub date: String,
+    /// Pattern for `%X`
+    pub time: String,
+    /// AM and PM markers for `%p`
+    pub periods: [String; 2],
+    /// Full weekday names for `%A`, starting on Sunday
+    pub days: [String; 7],
+    /// Abbreviated weekday names for `%a`, starting on Sunday
+    pub short_days: [String; 7],
+    /// Full month names for `%B`
+    pub months: [String; 12],
+    /// Abbreviated month names for `%b`
+    pub short_months: [String; 12],
+    /// First day of the week for weekly ticks
+    pub first_day_of_week: Weekday,
+}
+
+fn owned<const N: usize>(names: [&str; N]) -> [String; N] {
+    names.map(String::from)
+}
+
+impl Default for TimeLocale {
+    fn default() -> Self {
+        Self::en_us()
+    }
+}
+
+impl TimeLocale {
+    /// English (United States)
+    pub fn en_us() -> Self {
+        Self {
+            date_time: "%x, %X".into(),
+            date: "%-m/%-d/%Y".into(),
+            time: "%-I:%M:%S %p".into(),
+            periods: owned(["AM", "PM"]),
+            days: owned(["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"]),
+            short_days: owned(["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"]),
+            months: owned([
+                "January", "February", "March", "April", "May", "June",
+                "July", "August", "September", "October", "November", "December",
+            ]),
+            short_months: owned(["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"]),
+            first_day_of_week: Weekday::Sun,
+        }
+    }
+
+    /// English (United Kingdom)
+    pub fn en_gb() -> Self {
+        Self {
+            date_time: "%a %e %b %X %Y".into(),
+            date: "%d/%m/%Y".into(),
+            time: "%H:%M:%S".into(),
+            first_day_of_week: Weekday::Mon,
+            ..Self::en_us()
+        }
+    }
+
+    /// German (Germany)
+    pub fn de_de() -> Self {
+        Self {
+            date_time: "%A, der %e. %B %Y, %X".into(),
+            date: "%d.%m.%Y".into(),
+            time: "%H:%M:%S".into(),
+            periods: owned(["AM", "PM"]),
+            days: owned(["Sonntag", "Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag"]),
+            short_days: owned(["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"]),
+            months: owned([
+                "Januar", "Februar", "März", "April", "Mai", "Juni",
+                "Juli", "August", "September", "Oktober", "November", "Dezember",
+            ]),
+            short_months: owned(["Jan", "Feb", "Mrz", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"]),
+            first_day_of_week: Weekday::Mon,
+        }
+    }
+
+    /// French (France)
+    pub fn fr_fr() -> Self {
+        Self {
+            date_time: "%A %e %B %Y à %X".into(),
+            date: "%d/%m/%Y".into(),
+            time: "%H:%M:%S".into(),
+            periods: owned(["AM", "PM"]),
+            days: owned(["dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi"]),
+            short_days: owned(["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."]),
+            months: owned([
+                "janvier", "février", "mars", "avril", "mai", "juin",
+                "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+            ]),
+            short_months: owned([
+                "janv.", "févr.", "mars", "avr.", "mai", "juin",
+                "juil.", "août", "sept.", "oct.", "nov.", "déc.",
+            ]),
+            first_day_of_week: Weekday::Mon,
+        }
+    }
+
+    /// Spanish (Spain)
+    pub fn es_es() -> Self {
+        Self {
+            date_time: "%A, %e de %B de %Y, %X".into(),
+            date: "%d/%m/%Y".into(),
+            time: "%H:%M:%S".into(),
+            periods: owned(["AM", "PM"]),
+            days: owned(["domingo", "lunes", "martes", "miércoles", "jueves", "viernes", "sábado"]),
+            short_days: owned(["dom", "lun", "mar", "mié", "jue", "vie", "sáb"]),
+            months: owned([
+                "enero", "febrero", "marzo", "abril", "mayo", "junio",
+                "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre",
+            ]),
+            short_months: owned(["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic"]),
+            first_day_of_week: Weekday::Mon,
+        }
+    }
+
+    /// Format a local time with a strftime pattern
+    ///
+    /// Names (`%a %A %b %B %p`) and the `%c %x %X` patterns come from the
+    /// locale; all other specifiers are formatted by chrono.
+    pub fn format(&self, local: NaiveDateTime, pattern: &str) -> String {
+        let expanded = self.localize(local, pattern, 0);
+        local.format(&expanded).to_string()
+    }
+
+    /// Format a UTC instant as local time in `zone`
+    pub fn format_utc(&self, time: DateTime<Utc>, zone: &TimeZoneRule, pattern: &str) -> String {
+        self.format(zone.to_local(time), pattern)
+    }
+
+    /// Replace locale-dependent specifiers with escaped literal text
+    fn localize(&self, local: NaiveDateTime, pattern: &str, depth: u8) -> String {
+        let weekday = local.weekday().num_days_from_sunday() as usize;
+        let month = local.month0() as usize;
+        let mut out = String::with_capacity(pattern.len());
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            // Flags, widths and the `.3f` style precision end at a letter
+            let mut spec = String::new();
+            while let Some(&next) = chars.peek() {
+                spec.push(next);
+                chars.next();
+                if next.is_ascii_alphabetic() || next == '%' {
+                    break;
+                }
+            }
+            let literal = match spec.as_str() {
+                "a" => &self.short_days[weekday],
+                "A" => &self.days[weekday],
+                "b" | "h" => &self.short_months[month],
+                "B" => &self.months[month],
+                "p" => &self.periods[usize::from(local.hour() >= 12)],
+                // `%c` usually refers to `%x` and `%X`; the depth limit stops cycles
+                "c" | "x" | "X" if depth < 2 => {
+                    let nested = match spec.as_str() {
+                        "c" => &self.date_time,
+                        "x" => &self.date,
+                        _ => &self.time,
+                    };
+                    out.push_str(&self.localize(local, nested, depth + 1));
+                    continue;
+                }
+                _ => {
+                    out.push('%');
+                    out.push_str(&spec);
+                    continue;
+                }
+            };
+            out.push_str(&literal.replace('%', "%%"));
+        }
+        out
+    }
+}
+
+/// Yearly daylight-saving transition, like a POSIX TZ rule
+///
+/// The transition happens on the `week`-th `weekday` of `month` (week 5
+/// means the last one) at `hour` local time, read on the clock in effect
+/// before the change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DstTransition {
+    /// Month (1-12)
+    pub month: u32,
+    /// Occurrence of the weekday in the month (1-4, 5 = last)
+    pub week: u32,
+    /// Day of the week
+    pub weekday: Weekday,
+    /// Local hour of the change
+    pub hour: u32,
+}
+
+impl DstTransition {
+    /// Transition on the `week`-th `weekday` of `month` at `hour`
+    pub fn new(month: u32, week: u32, weekday: Weekday, hour: u32) -> Self {
+        Self {
+            month: month.clamp(1, 12),
+            week: week.clamp(1, 5),
+            weekday,
+            hour: hour.min(23),
+        }
+    }
+
+    /// Local date and time of the transition in `year`
+    pub fn local_time(&self, year: i32) -> Option<NaiveDateTime> {
+        let first = NaiveDate::from_ymd_opt(year, self.month, 1)?;
+        let lead = (7 + self.weekday.num_days_from_monday() - first.weekday().num_days_from_monday()) % 7;
+        let mut date = first + Duration::days((lead + 7 * (self.week - 1)) as i64);
+        while date.month() != self.month {
+            date -= Duration::days(7);
+        }
+        date.and_hms_opt(self.hour, 0, 0)
+    }
+}
+
+/// Conversion between UTC and local wall-clock time
+///
+/// Offsets are in seconds east of UTC. There is no time zone database:
+/// use a fixed offset, or a standard/daylight pair with yearly transitions
+/// (presets cover central Europe and US Eastern time).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum TimeZoneRule {
+    /// Coordinated Universal Time
+    #[default]
+    Utc,
+    /// Fixed offset in seconds east of UTC
+    Fixed(i32),
+    /// Standard time with yearly daylight-saving time
+    Daylight {
+        /// Standard offset in seconds east of UTC
+        standard: i32,
+        /// Daylight-saving offset in seconds east of UTC
+        daylight: i32,
+        /// Switch to daylight time
+        start: DstTransition,
+        /// Switch back to standard time
+        end: DstTransition,
+    },
+}
+
+impl TimeZoneRule {
+    /// Fixed offset in hours east of UTC (e.g. 5.5 for India)
+    pub fn fixed_hours(hours: f64) -> Self {
+        Self::Fixed((hours * 3600.0).round() as i32)
+    }
+
+    /// Central European Time (CET/CEST, e.g. Berlin, Paris)
+    pub fn central_european() -> Self {
+        Self::Daylight {
+            standard: 3600,
+            daylight: 7200,
+            start: DstTransition::new(3, 5, Weekday::Sun, 2),
+            end: DstTransition::new(10, 5, Weekday::Sun, 3),
+        }
+    }
+
+    /// US Eastern Time (EST/EDT, e.g. New York)
+    pub fn us_eastern() -> Self {
+        Self::Daylight {
+            standard: -18_000,
+            daylight: -14_400,
+            start: DstTransition::new(3, 2, Weekday::Sun, 2),
+            end: DstTransition::new(11, 1, Weekday::Sun, 2),
+        }
+    }
+
+    /// Offset in seconds east of UTC at the given instant
+    pub fn offset_at(&self, time: DateTime<Utc>) -> i32 {
+        match *self {
+            Self::Utc => 0,
+            Self::Fixed(offset) => offset,
+            Self::Daylight { standard, daylight, start, end } => {
+                let year = (time.naive_utc() + Duration::seconds(standard as i64)).year();
+                let bounds = start
+                    .local_time(year)
+                    .zip(end.local_time(year))
+                    .map(|(s, e)| {
+                        (
+                            s.and_utc() - Duration::seconds(standard as i64),
+                            e.and_utc() - Duration::seconds(daylight as i64),
+                        )
+                    });
+                let Some((dst_start, dst_end)) = bounds else {
+                    return standard;
+                };
+                let in_dst = if dst_start <= dst_end {
+                    time >= dst_start && time < dst_end
+                } else {
+                    // Southern hemisphere: daylight time spans the new year
+                    time >= dst_start || time < dst_end
+                };
+                if in_dst {
+                    daylight
+                } else {
+                    standard
+                }
+            }
+        }
+    }
+
+    /// Local wall-clock time of a UTC instant
+    pub fn to_local(&self, time: DateTime<Utc>) -> NaiveDateTime {
+        time.naive_utc() + Duration::seconds(self.offset_at(time) as i64)
+    }
+
+    /// UTC instant of a local wall-clock time
+    ///
+    /// Times repeated when clocks fall back resolve to the earlier instant;
+    /// times skipped when clocks spring forward use the standard offset.
+    pub fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let offsets = match *self {
+            Self::Utc => [0, 0],
+            Self::Fixed(offset) => [offset, offset],
+            Self::Daylight { standard, daylight, .. } => [daylight.max(standard), daylight.min(standard)],
+        };
+        for offset in offsets {
+            let candidate = local.and_utc() - Duration::seconds(offset as i64);
+            if self.offset_at(candidate) == offset {
+                return candidate;
+            }
+        }
+        local.and_utc() - Duration::seconds(offsets[1] as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_locale_names_and_patterns() {
+        let t = utc(2024, 3, 5, 15, 4).naive_utc();
+        let en = TimeLocale::en_us();
+        assert_eq!(en.format(t, "%c"), "3/5/2024, 3:04:00 PM");
+        assert_eq!(en.format(t, "%a %b %d"), "Tue Mar 05");
+        let fr = TimeLocale::fr_fr();
+        assert_eq!(fr.format(t, "%A %e %B"), "mardi  5 mars");
+        assert_eq!(fr.format(t, "%c"), "mardi  5 mars 2024 à 15:04:00");
+        // Literal percent signs in names are escaped
+        let mut custom = TimeLocale::en_gb();
+        custom.short_months[2] = "M%r".into();
+        assert_eq!(custom.format(t, "%b %%"), "M%r %");
+        assert_eq!(TimeLocale::es_es().format(t, "%a %.3f"), "mar .000");
+    }
+
+    #[test]
+    fn test_dst_transitions() {
+        let cet = TimeZoneRule::central_european();
+        // Clocks go forward on 2024-03-31 at 01:00 UTC, back on 2024-10-27 at 01:00 UTC
+        assert_eq!(cet.offset_at(utc(2024, 3, 31, 0, 59)), 3600);
+        assert_eq!(cet.offset_at(utc(2024, 3, 31, 1, 0)), 7200);
+        assert_eq!(cet.offset_at(utc(2024, 10, 27, 0, 59)), 7200);
+        assert_eq!(cet.offset_at(utc(2024, 10, 27, 1, 0)), 3600);
+
+        let est = TimeZoneRule::us_eastern();
+        // 2024-03-10 07:00 UTC and 2024-11-03 06:00 UTC
+        assert_eq!(est.offset_at(utc(2024, 3, 10, 6, 59)), -18_000);
+        assert_eq!(est.offset_at(utc(2024, 3, 10, 7, 0)), -14_400);
+        assert_eq!(est.offset_at(utc(2024, 11, 3, 6, 0)), -18_000);
+        assert_eq!(
+            DstTransition::new(11, 1, Weekday::Sun, 2).local_time(2024),
+            NaiveDate::from_ymd_opt(2024, 11, 3).unwrap().and_hms_opt(2, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_local_round_trip() {
+        let cet = TimeZoneRule::central_european();
+        let local = |d: u32, h: u32| NaiveDate::from_ymd_opt(2024, 10, d).unwrap().and_hms_opt(h, 30, 0).unwrap();
+        assert_eq!(cet.to_utc(local(1, 12)), utc(2024, 10, 1, 10, 30));
+        // 02:30 happens twice on 2024-10-27: the first (CEST) one wins
+        assert_eq!(cet.to_utc(local(27, 2)), utc(2024, 10, 27, 0, 30));
+        assert_eq!(cet.to_local(utc(2024, 10, 27, 1, 30)), local(27, 2));
+
+        let india = TimeZoneRule::fixed_hours(5.5);
+        assert_eq!(india.to_local(utc(2024, 1, 1, 0, 0)).hour(), 5);
+        assert_eq!(TimeZoneRule::Utc.to_utc(local(1, 0)), utc(2024, 10, 1, 0, 30));
+    }
+}
@@ -1,7 +1,10 @@
 //! Logarithmic scale implementation
 
+use super::linear::LinearScale;
 use super::traits::{Scale, ContinuousScale, ScaleExt, Tick, TickOptions};
-use super::utils::format_number;
+use super::utils::{format_number, interpolate_range};
+use crate::axis::NumberFormat;
+use crate::error::{D3Error, D3Result};
 
 /// Logarithmic scale for exponential data
 ///
@@ -22,6 +25,20 @@ use super::utils::format_number;
 /// assert!((scale.scale(100.0) - 200.0).abs() < 0.01);
 /// assert!((scale.scale(1000.0) - 300.0).abs() < 0.01);
 /// ```
+///
+/// Memory and performance charts read best in powers of two:
+///
+/// ```
+/// use makepad_d3::scale::{Scale, LogScale, ScaleExt, TickOptions};
+///
+/// let scale = LogScale::bytes()
+///     .with_domain(1.0, 1_073_741_824.0)
+///     .with_range(0.0, 600.0);
+///
+/// let ticks = scale.ticks(&TickOptions::new().with_count(4));
+/// let labels: Vec<&str> = ticks.iter().map(|t| t.label.as_str()).collect();
+/// assert_eq!(labels, vec!["1 B", "1 KiB", "1 MiB", "1 GiB"]);
+/// ```
 #[derive(Clone, Debug)]
 pub struct LogScale {
     domain_min: f64,
@@ -30,6 +47,8 @@ pub struct LogScale {
     range_end: f64,
     base: f64,
     clamp: bool,
+    round: bool,
+    tick_format: Option<NumberFormat>,
 }
 
 impl LogScale {
@@ -42,6 +61,8 @@ impl LogScale {
             range_end: 1.0,
             base: 10.0,
             clamp: false,
+            round: false,
+            tick_format: None,
         }
     }
 
@@ -50,6 +71,11 @@ impl LogScale {
         Self::new().with_base(2.0)
     }
 
+    /// Create a base-2 log scale labelling ticks as byte sizes (KiB, MiB, ...)
+    pub fn bytes() -> Self {
+        Self::base2().with_tick_format(NumberFormat::bytes())
+    }
+
     /// Create a log scale with natural log (base e)
     pub fn ln() -> Self {
         Self::new().with_base(std::f64::consts::E)
@@ -61,12 +87,85 @@ impl LogScale {
         self
     }
 
+    /// Set the domain, rejecting values a log scale cannot represent
+    ///
+    /// Unlike [`ScaleExt::with_domain`], which nudges non-positive bounds up
+    /// to `f64::EPSILON`, this returns an error for non-finite bounds, equal
+    /// bounds, zero, or a domain that crosses zero.
+    ///
+    /// ```
+    /// use makepad_d3::scale::LogScale;
+    /// use makepad_d3::error::D3Error;
+    ///
+    /// assert!(LogScale::new().try_with_domain(1.0, 1000.0).is_ok());
+    /// assert!(matches!(
+    ///     LogScale::new().try_with_domain(0.0, 100.0),
+    ///     Err(D3Error::InvalidDomain { .. })
+    /// ));
+    /// ```
+    pub fn try_with_domain(mut self, min: f64, max: f64) -> D3Result<Self> {
+        for value in [min, max] {
+            if !value.is_finite() {
+                return Err(D3Error::non_finite("log scale domain", value));
+            }
+        }
+        if min == max {
+            return Err(D3Error::empty_domain(format!(
+                "log scale domain [{}, {}] has zero width",
+                min, max
+            )));
+        }
+        if min == 0.0 || max == 0.0 {
+            return Err(D3Error::invalid_domain(format!(
+                "log scale domain [{}, {}] includes zero",
+                min, max
+            )));
+        }
+        if (min < 0.0) != (max < 0.0) {
+            return Err(D3Error::invalid_domain(format!(
+                "log scale domain [{}, {}] crosses zero",
+                min, max
+            )));
+        }
+        if min < 0.0 {
+            return Err(D3Error::invalid_domain(format!(
+                "log scale domain [{}, {}] is negative",
+                min, max
+            )));
+        }
+        self.domain_min = min;
+        self.domain_max = max;
+        Ok(self)
+    }
+
+    /// Format tick labels with `format` instead of the built-in labels
+    pub fn with_tick_format(mut self, format: NumberFormat) -> Self {
+        self.tick_format = Some(format);
+        self
+    }
+
+    /// Get the tick label format, if one was set
+    pub fn tick_format(&self) -> Option<&NumberFormat> {
+        self.tick_format.as_ref()
+    }
+
     /// Enable clamping
     pub fn with_clamp(mut self, clamp: bool) -> Self {
         self.clamp = clamp;
         self
     }
 
+    /// Round output positions to whole pixels (d3's `rangeRound`)
+    pub fn with_round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Check if output positions are rounded
+    pub fn is_rounded(&self) -> bool {
+        self.round
+    }
+
     /// Get the current base
     pub fn base(&self) -> f64 {
         self.base
@@ -76,6 +175,11 @@ impl LogScale {
     fn log(&self, x: f64) -> f64 {
         if x <= 0.0 {
             f64::NEG_INFINITY
+        } else if self.base == 2.0 {
+            // Exact at powers of the base, so ticks land on them
+            x.log2()
+        } else if self.base == 10.0 {
+            x.log10()
         } else {
             x.ln() / self.base.ln()
         }
@@ -88,6 +192,16 @@ impl LogScale {
 
     /// Format a value for tick labels
     fn format_tick(&self, value: f64) -> String {
+        if let Some(format) = &self.tick_format {
+            return format.format(value);
+        }
+        // Powers of other integer bases are whole numbers worth spelling out
+        if self.base != 10.0 && value.fract() == 0.0 && (1.0..1e15).contains(&value) {
+            return format!("{:.0}", value);
+        }
+        if self.base.fract() != 0.0 {
+            return format_number(value);
+        }
         if value >= 1e6 || value < 1e-3 {
             format!("{:.0e}", value)
         } else if value >= 1000.0 {
@@ -98,6 +212,36 @@ impl LogScale {
             format!("{:.3}", value)
         }
     }
+
+    /// Multiples of each power that make useful minor ticks
+    ///
+    /// 2× and 5× for base 10, every multiple for other small integer bases,
+    /// and none for base 2 (whose multiples are powers) or fractional bases.
+    fn subdivisions(&self) -> Vec<f64> {
+        if self.base == 10.0 {
+            vec![2.0, 5.0]
+        } else if self.base.fract() == 0.0 && self.base <= 16.0 {
+            (2..self.base as usize).map(|k| k as f64).collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Step between labelled exponents so about `count` powers are shown
+///
+/// Steps follow 1, 2, 5, 10, 20, ... so base-2 axes land on KiB/MiB/GiB.
+fn power_stride(powers: usize, count: usize) -> i32 {
+    let target = powers.div_ceil(count.max(1)).max(1);
+    let mut magnitude = 1;
+    loop {
+        for step in [1, 2, 5] {
+            if step * magnitude >= target {
+                return (step * magnitude) as i32;
+            }
+        }
+        magnitude *= 10;
+    }
 }
 
 impl Default for LogScale {
@@ -145,11 +289,11 @@ impl Scale for LogScale {
         let log_val = self.log(value);
 
         if (log_max - log_min).abs() < f64::EPSILON {
-            return self.range_start;
+            return interpolate_range((self.range_start, self.range_end), 0.0, self.round);
         }
 
         let t = (log_val - log_min) / (log_max - log_min);
-        self.range_start + t * (self.range_end - self.range_start)
+        interpolate_range((self.range_start, self.range_end), t, self.round)
     }
 
     fn invert(&self, pixel: f64) -> f64 {
@@ -159,6 +303,7 @@ impl Scale for LogScale {
         }
 
         let t = (pixel - self.range_start) / range_span;
+        let t = if self.clamp { t.clamp(0.0, 1.0) } else { t };
         let log_min = self.log(self.domain_min);
         let log_max = self.log(self.domain_max);
 
@@ -167,14 +312,21 @@ impl Scale for LogScale {
 
     fn ticks(&self, options: &TickOptions) -> Vec<Tick> {
         let mut ticks = Vec::new();
+        let lo = self.domain_min.min(self.domain_max);
+        let hi = self.domain_min.max(self.domain_max);
 
-        let log_min = self.log(self.domain_min).floor() as i32;
-        let log_max = self.log(self.domain_max).ceil() as i32;
+        let log_min = self.log(lo).floor() as i32;
+        let log_max = self.log(hi).ceil() as i32;
+        let powers = (log_max - log_min + 1).max(1) as usize;
+        let stride = power_stride(powers, options.count);
 
-        // Generate ticks at powers of base
+        // Generate ticks at powers of base, thinned on long domains
         for exp in log_min..=log_max {
+            if exp.rem_euclid(stride) != 0 {
+                continue;
+            }
             let value = self.pow(exp as f64);
-            if value >= self.domain_min && value <= self.domain_max {
+            if value >= lo && value <= hi {
                 let pos = self.scale(value);
                 ticks.push(Tick::new(value, self.format_tick(value)).with_position(pos));
             }
@@ -188,9 +340,9 @@ impl Scale for LogScale {
             let mut extra_ticks = Vec::new();
             for exp in log_min..log_max {
                 let base_val = self.pow(exp as f64);
-                for mult in [2.0, 5.0] {
+                for mult in self.subdivisions() {
                     let value = base_val * mult;
-                    if value > self.domain_min && value < self.domain_max {
+                    if value > lo && value < hi {
                         let pos = self.scale(value);
                         extra_ticks.push(Tick::new(value, self.format_tick(value)).with_position(pos));
                     }
@@ -200,6 +352,31 @@ impl Scale for LogScale {
             ticks.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap());
         }
 
+        // Too narrow for powers: fall back to linear ticks, as d3 does
+        if ticks.len() < options.min_count {
+            let linear = LinearScale::new().with_domain(lo, hi);
+            // Step accumulation can overshoot the ends (4.000000000000001); snap and clip
+            let eps = (hi - lo) * 1e-9;
+            let fallback: Vec<Tick> = linear
+                .ticks(options)
+                .into_iter()
+                .map(|t| {
+                    if (t.value - lo).abs() <= eps {
+                        lo
+                    } else if (t.value - hi).abs() <= eps {
+                        hi
+                    } else {
+                        t.value
+                    }
+                })
+                .filter(|&v| v > 0.0 && (lo..=hi).contains(&v))
+                .map(|v| Tick::new(v, self.format_tick(v)).with_position(self.scale(v)))
+                .collect();
+            if fallback.len() > ticks.len() {
+                ticks = fallback;
+            }
+        }
+
         ticks
     }
 
@@ -210,6 +387,8 @@ impl Scale for LogScale {
         self.range_end = other.range_end;
         self.base = other.base;
         self.clamp = other.clamp;
+        self.round = other.round;
+        self.tick_format = other.tick_format.clone();
     }
 
     fn clone_box(&self) -> Box<dyn Scale> {
@@ -333,6 +512,85 @@ mod tests {
         assert_eq!(boxed.scale_type(), "log");
     }
 
+    #[test]
+    fn test_log_scale_try_with_domain() {
+        let scale = LogScale::new().try_with_domain(10.0, 1000.0).unwrap();
+        assert_eq!(scale.domain(), (10.0, 1000.0));
+
+        assert!(matches!(
+            LogScale::new().try_with_domain(0.0, 10.0),
+            Err(D3Error::InvalidDomain { .. })
+        ));
+        assert!(matches!(
+            LogScale::new().try_with_domain(-1.0, 10.0),
+            Err(D3Error::InvalidDomain { .. })
+        ));
+        assert!(matches!(
+            LogScale::new().try_with_domain(5.0, 5.0),
+            Err(D3Error::EmptyDomain { .. })
+        ));
+        assert!(matches!(
+            LogScale::new().try_with_domain(1.0, f64::NAN),
+            Err(D3Error::NonFiniteValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_log_scale_base2_ticks() {
+        let scale = LogScale::base2().with_domain(1.0, 64.0).with_range(0.0, 600.0);
+        let ticks = scale.ticks(&TickOptions::default());
+        let values: Vec<f64> = ticks.iter().map(|t| t.value).collect();
+        assert_eq!(values, vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0]);
+        assert_eq!(ticks[6].label, "64");
+        assert!((ticks[3].position - 300.0).abs() < 1e-9);
+
+        // 41 powers of two thin to every fifth exponent
+        let long = LogScale::base2().with_domain(1.0, 2f64.powi(40));
+        let values: Vec<f64> = long.ticks(&TickOptions::default()).iter().map(|t| t.value).collect();
+        assert_eq!(values.len(), 9);
+        assert_eq!(values[2], 1024.0);
+
+        // No power-of-two multiples are duplicated as minor ticks
+        let narrow = LogScale::base2().with_domain(1.0, 4.0);
+        let ticks = narrow.ticks(&TickOptions::new().with_min_count(4));
+        assert!(ticks.len() >= 4);
+        assert!(ticks.windows(2).all(|w| w[0].value < w[1].value));
+    }
+
+    #[test]
+    fn test_log_scale_natural_ticks() {
+        let scale = LogScale::ln().with_domain(1.0, 100.0);
+        let ticks = scale.ticks(&TickOptions::default());
+        assert_eq!(ticks.len(), 5);
+        assert!((ticks[1].value - std::f64::consts::E).abs() < 1e-12);
+        assert_eq!(ticks[1].label, "2.72");
+    }
+
+    #[test]
+    fn test_log_scale_narrow_domain_falls_back_to_linear() {
+        let scale = LogScale::new().with_domain(3.0, 4.0);
+        let ticks = scale.ticks(&TickOptions::default());
+        assert!(ticks.len() >= 2);
+        assert!(ticks.iter().all(|t| (3.0..=4.0).contains(&t.value)));
+    }
+
+    #[test]
+    fn test_log_scale_bytes() {
+        let scale = LogScale::bytes().with_domain(512.0, 4.0 * 1_048_576.0);
+        assert_eq!(scale.base(), 2.0);
+        let labels: Vec<String> = scale
+            .ticks(&TickOptions::new().with_count(7))
+            .into_iter()
+            .map(|t| t.label)
+            .collect();
+        // Every other exponent, so 512 B (2^9) is skipped
+        assert_eq!(labels, vec!["1 KiB", "4 KiB", "16 KiB", "64 KiB", "256 KiB", "1 MiB", "4 MiB"]);
+
+        let mut copy = LogScale::new();
+        copy.copy_from(&scale);
+        assert!(copy.tick_format().is_some());
+    }
+
     #[test]
     fn test_log_scale_with_base() {
         let scale = LogScale::new().with_base(2.0);
@@ -6,15 +6,32 @@
 //! - [`LinearScale`]: Linear interpolation between domain and range
 //! - [`CategoryScale`]: Maps discrete categories to continuous bands
 //! - [`BandScale`]: Maps discrete categories to bands with configurable padding (D3-compatible)
+//! - [`GroupedBandScale`]: Bands nested in labelled groups (e.g. months within quarters)
 //! - [`PointScale`]: Maps discrete categories to evenly spaced points (zero bandwidth)
 //! - [`QuantizeScale`]: Maps continuous domain to discrete range (equal-sized segments)
 //! - [`QuantileScale`]: Maps continuous domain to discrete range (equal-count segments based on data)
 //! - [`ThresholdScale`]: Maps continuous domain to discrete range (custom breakpoints)
 //! - [`SequentialScale`]: Maps continuous domain through an interpolator (for color gradients)
+//! - [`DivergingScale`]: Maps a domain with a pivot through an interpolator (pivot at the midpoint)
+//! - [`ScaleTransform`]: Log/pow/symlog transforms for sequential and diverging scales
 //! - [`TimeScale`]: Maps DateTime values to continuous range
-//! - [`LogScale`]: Logarithmic interpolation for exponential data
+//! - [`TimeInterval`]: Calendar intervals with floor/ceil/offset/range/count (d3-time)
+//! - [`BusinessTimeInterval`]: Intervals skipping the closed periods of a [`BusinessCalendar`]
+//! - [`TimeLocale`] / [`TimeZoneRule`]: Localized names and local-time tick alignment
+//! - [`LogScale`]: Logarithmic interpolation for exponential data (base 10, 2 or e)
 //! - [`PowScale`]: Power/polynomial interpolation
 //! - [`SymlogScale`]: Symmetric log for data crossing zero
+//! - [`RadialScale`]: Radius scale with area proportional to value (radial bars, bubbles)
+//!
+//! [`DomainPolicy`] derives domains from data with padding, zero baselines,
+//! minimum spans and nice rounding (see [`LinearScale::from_data_with`]).
+//!
+//! Continuous scales can round output positions to whole pixels (`with_round`,
+//! d3's `rangeRound`) to avoid anti-aliased edges on bars and gridlines;
+//! `invert` is unaffected. They round to logical pixels; only [`BandScale`]
+//! and [`PointScale`] take a device pixel ratio.
+//!
+//! Clamped scales (`with_clamp`) keep `invert` within the domain too, as in d3.
 //!
 //! # Example
 //! ```
@@ -29,30 +46,44 @@
 
 mod traits;
 mod utils;
+mod extent;
 mod linear;
 mod category;
 mod band;
+mod grouped_band;
 mod point;
 mod quantize;
 mod quantile;
 mod threshold;
 mod sequential;
+mod diverging;
+mod interval;
+mod business;
+mod locale;
 mod time;
 mod log;
 mod pow;
 mod symlog;
+mod radial;
 
 pub use traits::{Scale, ContinuousScale, DiscreteScale, ScaleExt, Tick, TickOptions};
-pub use utils::{nice_step, nice_bounds, format_number};
+pub use utils::{nice_step, nice_bounds, format_number, snap_to_pixel};
+pub use extent::{extent, DomainPolicy};
 pub use linear::LinearScale;
 pub use category::CategoryScale;
 pub use band::BandScale;
+pub use grouped_band::{BandGroup, GroupedBandScale};
 pub use point::PointScale;
 pub use quantize::QuantizeScale;
 pub use quantile::QuantileScale;
 pub use threshold::ThresholdScale;
-pub use sequential::{SequentialScale, interpolators};
-pub use time::{TimeScale, TimeTick, TimeInterval};
+pub use sequential::{SequentialScale, ScaleTransform, interpolators};
+pub use diverging::DivergingScale;
+pub use interval::{TimeInterval, ZonedInterval};
+pub use business::{BusinessCalendar, BusinessTimeInterval};
+pub use locale::{TimeLocale, TimeZoneRule, DstTransition};
+pub use time::{TimeScale, TimeTick};
 pub use log::LogScale;
 pub use pow::PowScale;
 pub use symlog::SymlogScale;
+pub use radial::RadialScale;
@@ -4,6 +4,7 @@
 //! They are useful for scatter plots and dot plots with discrete categories.
 
 use super::traits::{DiscreteScale, Scale, Tick, TickOptions};
+use super::utils::{snap_down, snap_to_pixel};
 
 /// Scale for mapping discrete domain to evenly spaced points
 ///
@@ -42,6 +43,10 @@ pub struct PointScale {
     align: f64,
     /// Whether to round output values to integers
     round: bool,
+    /// Device pixels per logical pixel used when rounding
+    pixel_ratio: f64,
+    /// Cached position of the first point
+    cached_start: f64,
     /// Cached computed step value
     cached_step: f64,
 }
@@ -56,6 +61,8 @@ impl PointScale {
             padding: 0.0,
             align: 0.5,
             round: false,
+            pixel_ratio: 1.0,
+            cached_start: 0.0,
             cached_step: 0.0,
         };
         scale.rescale();
@@ -138,12 +145,26 @@ impl PointScale {
     /// Enable or disable rounding to pixel boundaries
     ///
     /// When enabled, positions are rounded to integers for crisper rendering.
+    /// The step is floored and the freed space distributed by
+    /// [`align`](Self::align), as in d3.
     pub fn round(mut self, round: bool) -> Self {
         self.round = round;
         self.rescale();
         self
     }
 
+    /// Set the range and enable rounding (d3's `rangeRound`)
+    pub fn range_round(self, start: f64, end: f64) -> Self {
+        self.range(start, end).round(true)
+    }
+
+    /// Set device pixels per logical pixel for rounding (default 1)
+    pub fn pixel_ratio(mut self, ratio: f64) -> Self {
+        self.pixel_ratio = if ratio.is_finite() && ratio > 0.0 { ratio } else { 1.0 };
+        self.rescale();
+        self
+    }
+
     /// Get the number of points
     pub fn len(&self) -> usize {
         self.domain_values.len()
@@ -181,15 +202,7 @@ impl PointScale {
             return self.range_start;
         }
 
-        // Calculate start position with padding and alignment
-        let start = self.range_start + self.padding * self.cached_step * self.align * 2.0;
-        let pos = start + index as f64 * self.cached_step;
-
-        if self.round {
-            pos.round()
-        } else {
-            pos
-        }
+        self.cached_start + index as f64 * self.cached_step
     }
 
     /// Get the category at a pixel position (nearest point)
@@ -202,8 +215,7 @@ impl PointScale {
             };
         }
 
-        let start = self.range_start + self.padding * self.cached_step * self.align * 2.0;
-        let relative = pixel - start;
+        let relative = pixel - self.cached_start;
 
         // Find nearest point
         let index = (relative / self.cached_step + 0.5).floor() as i64;
@@ -220,6 +232,7 @@ impl PointScale {
     /// Recalculate cached values when scale parameters change
     fn rescale(&mut self) {
         let n = self.domain_values.len();
+        self.cached_start = self.range_start;
         if n == 0 {
             self.cached_step = 0.0;
             return;
@@ -230,21 +243,22 @@ impl PointScale {
         if n == 1 {
             // Single point goes at the center (considering alignment)
             self.cached_step = range;
-            return;
-        }
-
-        // D3's formula for point scale:
-        // step = range / (n - 1 + padding * 2)
-        let divisor = (n - 1) as f64 + self.padding * 2.0;
-        if divisor <= 0.0 {
-            self.cached_step = 0.0;
-            return;
+            self.cached_start = self.range_start + self.padding * range * self.align * 2.0;
+        } else {
+            // D3's formula for point scale:
+            // step = range / (n - 1 + padding * 2)
+            let divisor = (n - 1) as f64 + self.padding * 2.0;
+            self.cached_step = range / divisor;
+            if self.round {
+                self.cached_step = snap_down(self.cached_step, self.pixel_ratio);
+            }
+            // Space outside the points is split by `align`
+            let leftover = range - self.cached_step * (n - 1) as f64;
+            self.cached_start = self.range_start + leftover * self.align;
         }
 
-        self.cached_step = range / divisor;
-
         if self.round {
-            self.cached_step = self.cached_step.floor();
+            self.cached_start = snap_to_pixel(self.cached_start, self.pixel_ratio);
         }
     }
 }
@@ -314,6 +328,7 @@ impl Scale for PointScale {
         self.padding = other.padding;
         self.align = other.align;
         self.round = other.round;
+        self.pixel_ratio = other.pixel_ratio;
         self.rescale();
     }
 
@@ -510,6 +525,17 @@ mod tests {
         assert_eq!(scale.scale_index(0), 0.0);
     }
 
+    #[test]
+    fn test_point_scale_round_distributes_leftover() {
+        let scale = PointScale::new()
+            .domain(vec!["A", "B", "C", "D"])
+            .range_round(0.0, 100.0);
+        let positions: Vec<f64> = (0..4).map(|i| scale.scale_index(i)).collect();
+        assert_eq!(positions, vec![1.0, 34.0, 67.0, 100.0]);
+        assert_eq!(scale.clone().align(0.0).scale_index(3), 99.0);
+        assert_eq!(scale.invert(50.0), Some("B"));
+    }
+
     #[test]
     fn test_point_scale_single_item() {
         let scale = PointScale::new()
@@ -1,7 +1,7 @@
 //! Power scale implementation
 
 use super::traits::{Scale, ContinuousScale, ScaleExt, Tick, TickOptions};
-use super::utils::{nice_step, nice_bounds, format_number};
+use super::utils::{nice_step, nice_bounds, format_number, interpolate_range};
 
 /// Power scale for polynomial interpolation
 ///
@@ -29,6 +29,7 @@ pub struct PowScale {
     range_end: f64,
     exponent: f64,
     clamp: bool,
+    round: bool,
 }
 
 impl PowScale {
@@ -41,6 +42,7 @@ impl PowScale {
             range_end: 1.0,
             exponent: 1.0,
             clamp: false,
+            round: false,
         }
     }
 
@@ -73,6 +75,17 @@ impl PowScale {
         self
     }
 
+    /// Round output positions to whole pixels (d3's `rangeRound`)
+    pub fn with_round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Check if output positions are rounded
+    pub fn is_rounded(&self) -> bool {
+        self.round
+    }
+
     /// Get the current exponent
     pub fn exponent(&self) -> f64 {
         self.exponent
@@ -144,11 +157,11 @@ impl Scale for PowScale {
 
         let domain_span = pow_max - pow_min;
         if domain_span.abs() < f64::EPSILON {
-            return self.range_start;
+            return interpolate_range((self.range_start, self.range_end), 0.0, self.round);
         }
 
         let t = (pow_val - pow_min) / domain_span;
-        self.range_start + t * (self.range_end - self.range_start)
+        interpolate_range((self.range_start, self.range_end), t, self.round)
     }
 
     fn invert(&self, pixel: f64) -> f64 {
@@ -158,6 +171,7 @@ impl Scale for PowScale {
         }
 
         let t = (pixel - self.range_start) / range_span;
+        let t = if self.clamp { t.clamp(0.0, 1.0) } else { t };
 
         let pow_min = self.pow_transform(self.domain_min);
         let pow_max = self.pow_transform(self.domain_max);
@@ -205,6 +219,7 @@ impl Scale for PowScale {
         self.range_end = other.range_end;
         self.exponent = other.exponent;
         self.clamp = other.clamp;
+        self.round = other.round;
     }
 
     fn clone_box(&self) -> Box<dyn Scale> {
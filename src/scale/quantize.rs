@@ -3,7 +3,9 @@
 //! Quantize scales map a continuous domain to a discrete range by dividing
 //! the domain into uniform segments. Each segment maps to one range value.
 
+use super::threshold::class_label;
 use super::traits::{Scale, Tick, TickOptions};
+use crate::axis::NumberFormat;
 
 /// Scale that maps continuous input to discrete output values
 ///
@@ -222,6 +224,21 @@ impl<T: Clone> QuantizeScale<T> {
             .map(|i| self.invert_extent(i))
     }
 
+    /// Classify a value as `(class_index, label, output)`
+    ///
+    /// The label is the class interval formatted with `format`, e.g.
+    /// "25–50". Values outside the domain fall into the first or last class.
+    /// Returns `None` for an empty range or a NaN value.
+    pub fn classify(&self, value: f64, format: &NumberFormat) -> Option<(usize, String, T)> {
+        if value.is_nan() {
+            return None;
+        }
+        let output = self.scale_to_value(value)?.clone();
+        let index = self.scale_to_index(value);
+        let (x0, x1) = self.invert_extent(index);
+        Some((index, class_label(x0, x1, format), output))
+    }
+
     /// Recalculate thresholds when domain or range changes
     fn rescale(&mut self) {
         let n = self.range_values.len();
@@ -579,4 +596,15 @@ mod tests {
         assert_eq!(scale.scale_to_value(0.5), Some(&"#6baed6"));
         assert_eq!(scale.scale_to_value(1.0), Some(&"#084594"));
     }
+
+    #[test]
+    fn test_quantize_classify() {
+        let scale = QuantizeScale::new().domain(0.0, 100.0).range(vec!["a", "b", "c", "d"]);
+        let format = NumberFormat::Fixed(0);
+        assert_eq!(scale.classify(30.0, &format), Some((1, "25–50".to_string(), "b")));
+        assert_eq!(scale.classify(-5.0, &format).unwrap().1, "0–25");
+        assert_eq!(scale.classify(120.0, &format).unwrap().0, 3);
+        assert_eq!(scale.classify(f64::NAN, &format), None);
+        assert_eq!(QuantizeScale::<&str>::new().classify(1.0, &format), None);
+    }
 }
@@ -0,0 +1,256 @@
+//! Radial scale implementation
+
+use super::traits::{Scale, ContinuousScale, ScaleExt, Tick, TickOptions};
+use super::utils::{nice_step, nice_bounds, format_number};
+
+/// Radial scale for radii whose enclosed area is linear in the value
+///
+/// A linear scale applied to a radius makes area grow with the square of
+/// the value, exaggerating large values in radial bar charts and bubble
+/// plots. This scale interpolates linearly in squared-radius space, so the
+/// area of a circle (or of an annular bar starting at the inner radius)
+/// is proportional to the value.
+///
+/// # D3.js Equivalent
+/// This is equivalent to `d3.scaleRadial()` in D3.js.
+///
+/// # Example
+/// ```
+/// use makepad_d3::scale::{Scale, RadialScale, ScaleExt};
+///
+/// let scale = RadialScale::new()
+///     .with_domain(0.0, 100.0)
+///     .with_range(0.0, 50.0);
+///
+/// // A quarter of the value covers a quarter of the area: half the radius
+/// assert!((scale.scale(25.0) - 25.0).abs() < 1e-9);
+/// assert!((scale.invert(25.0) - 25.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RadialScale {
+    domain_min: f64,
+    domain_max: f64,
+    range_start: f64,
+    range_end: f64,
+    clamp: bool,
+    round: bool,
+}
+
+impl RadialScale {
+    /// Create a new radial scale with domain and range [0, 1]
+    pub fn new() -> Self {
+        Self {
+            domain_min: 0.0,
+            domain_max: 1.0,
+            range_start: 0.0,
+            range_end: 1.0,
+            clamp: false,
+            round: false,
+        }
+    }
+
+    /// Enable clamping
+    pub fn with_clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Round output radii to whole pixels (d3's `rangeRound`)
+    pub fn with_round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Check if output radii are rounded
+    pub fn is_rounded(&self) -> bool {
+        self.round
+    }
+
+    /// Signed square, keeping negative radii negative
+    fn square(r: f64) -> f64 {
+        r.signum() * r * r
+    }
+
+    /// Signed square root
+    fn sqrt(r: f64) -> f64 {
+        r.signum() * r.abs().sqrt()
+    }
+}
+
+impl Default for RadialScale {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scale for RadialScale {
+    fn scale_type(&self) -> &'static str {
+        "radial"
+    }
+
+    fn set_domain(&mut self, min: f64, max: f64) {
+        self.domain_min = min;
+        self.domain_max = max;
+    }
+
+    fn set_range(&mut self, start: f64, end: f64) {
+        self.range_start = start;
+        self.range_end = end;
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        (self.domain_min, self.domain_max)
+    }
+
+    fn range(&self) -> (f64, f64) {
+        (self.range_start, self.range_end)
+    }
+
+    fn scale(&self, value: f64) -> f64 {
+        let value = if self.clamp {
+            value.clamp(
+                self.domain_min.min(self.domain_max),
+                self.domain_min.max(self.domain_max),
+            )
+        } else {
+            value
+        };
+
+        let domain_span = self.domain_max - self.domain_min;
+        let t = if domain_span.abs() < f64::EPSILON {
+            0.0
+        } else {
+            (value - self.domain_min) / domain_span
+        };
+
+        let (sq_start, sq_end) = (Self::square(self.range_start), Self::square(self.range_end));
+        let radius = Self::sqrt(sq_start + t * (sq_end - sq_start));
+        if self.round {
+            radius.round()
+        } else {
+            radius
+        }
+    }
+
+    fn invert(&self, radius: f64) -> f64 {
+        let (sq_start, sq_end) = (Self::square(self.range_start), Self::square(self.range_end));
+        let range_span = sq_end - sq_start;
+        if range_span.abs() < f64::EPSILON {
+            return self.domain_min;
+        }
+
+        let t = (Self::square(radius) - sq_start) / range_span;
+        let t = if self.clamp { t.clamp(0.0, 1.0) } else { t };
+        self.domain_min + t * (self.domain_max - self.domain_min)
+    }
+
+    fn ticks(&self, options: &TickOptions) -> Vec<Tick> {
+        let (lo, hi) = if self.domain_min <= self.domain_max {
+            (self.domain_min, self.domain_max)
+        } else {
+            (self.domain_max, self.domain_min)
+        };
+
+        let step = options.step_size.unwrap_or_else(|| nice_step(hi - lo, options.count));
+        if step <= 0.0 || !step.is_finite() {
+            return vec![];
+        }
+
+        let epsilon = step * 0.0001;
+        let mut ticks = Vec::new();
+        let mut value = (lo / step).ceil() * step;
+        while value <= hi + epsilon && ticks.len() < options.max_count {
+            ticks.push(Tick::new(value, format_number(value)).with_position(self.scale(value)));
+            value += step;
+        }
+
+        ticks
+    }
+
+    fn copy_from(&mut self, other: &Self) {
+        self.domain_min = other.domain_min;
+        self.domain_max = other.domain_max;
+        self.range_start = other.range_start;
+        self.range_end = other.range_end;
+        self.clamp = other.clamp;
+        self.round = other.round;
+    }
+
+    fn clone_box(&self) -> Box<dyn Scale> {
+        Box::new(self.clone())
+    }
+}
+
+impl ContinuousScale for RadialScale {
+    fn nice(&mut self) {
+        let (nice_min, nice_max) = nice_bounds(self.domain_min, self.domain_max);
+        self.domain_min = nice_min;
+        self.domain_max = nice_max;
+    }
+
+    fn is_clamped(&self) -> bool {
+        self.clamp
+    }
+
+    fn set_clamp(&mut self, clamp: bool) {
+        self.clamp = clamp;
+    }
+}
+
+impl ScaleExt for RadialScale {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_radial_area_proportional() {
+        let scale = RadialScale::new()
+            .with_domain(0.0, 10.0)
+            .with_range(0.0, 100.0);
+
+        let area = |v: f64| PI * scale.scale(v).powi(2);
+        assert!((area(2.0) / area(1.0) - 2.0).abs() < 1e-9);
+        assert!((area(10.0) / area(5.0) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_radial_inner_radius() {
+        // Annular bars: the ring area between inner radius and the bar end
+        // grows linearly with the value
+        let scale = RadialScale::new()
+            .with_domain(0.0, 100.0)
+            .with_range(20.0, 120.0);
+
+        let ring = |v: f64| PI * (scale.scale(v).powi(2) - 20.0f64.powi(2));
+        assert_eq!(scale.scale(0.0), 20.0);
+        assert!((scale.scale(100.0) - 120.0).abs() < 1e-9);
+        assert!((ring(50.0) * 2.0 - ring(100.0)).abs() < 1e-6);
+
+        for &v in &[0.0, 12.5, 50.0, 100.0] {
+            assert!((scale.invert(scale.scale(v)) - v).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_radial_nice_ticks_round_clamp() {
+        let mut scale = RadialScale::new()
+            .with_domain(0.0, 97.3)
+            .with_range(0.0, 200.0)
+            .with_round(true)
+            .with_clamp(true);
+        scale.nice();
+        assert_eq!(scale.domain(), (0.0, 100.0));
+
+        let ticks = scale.ticks(&TickOptions::new().with_count(5));
+        assert_eq!(ticks.first().unwrap().value, 0.0);
+        assert_eq!(ticks.last().unwrap().value, 100.0);
+        // Positions follow the square root: 25 maps to half the radius
+        let quarter = ticks.iter().find(|t| t.value == 25.0 || t.value == 20.0).unwrap();
+        assert_eq!(quarter.position, scale.scale(quarter.value));
+        assert_eq!(scale.scale(25.0), 100.0);
+        assert_eq!(scale.scale(150.0), 200.0);
+        assert_eq!(scale.clone_box().scale_type(), "radial");
+    }
+}
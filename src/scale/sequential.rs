@@ -2,13 +2,50 @@
 //!
 //! Sequential scales map a continuous domain to a continuous range using
 //! an interpolator function. They are commonly used for color gradients
-//! and heat maps.
+//! and heat maps. A [`ScaleTransform`] bends the domain first, like d3's
+//! `scaleSequentialLog`, `scaleSequentialPow` and `scaleSequentialSymlog`.
 
 use super::traits::{Scale, Tick, TickOptions};
 
 /// An interpolator function that maps t ∈ [0, 1] to an output value
 pub type Interpolator<T> = Box<dyn Fn(f64) -> T + Send + Sync>;
 
+/// Transform applied to domain values before they are interpolated
+///
+/// Shared by [`SequentialScale`] and
+/// [`DivergingScale`](super::DivergingScale) so skewed data can be spread
+/// evenly across a color ramp.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ScaleTransform {
+    /// No transform
+    #[default]
+    Linear,
+    /// Logarithm with the given base (negative domains are mirrored)
+    Log(f64),
+    /// Sign-preserving power with the given exponent
+    Pow(f64),
+    /// Symmetric log with the given constant, defined through zero
+    Symlog(f64),
+}
+
+impl ScaleTransform {
+    /// Square root transform
+    pub fn sqrt() -> Self {
+        Self::Pow(0.5)
+    }
+
+    /// Apply the transform
+    pub fn apply(&self, x: f64) -> f64 {
+        match *self {
+            Self::Linear => x,
+            Self::Log(base) if x < 0.0 => -(-x).ln() / base.ln(),
+            Self::Log(base) => x.ln() / base.ln(),
+            Self::Pow(exponent) => x.signum() * x.abs().powf(exponent),
+            Self::Symlog(constant) => x.signum() * (x.abs() / constant).ln_1p(),
+        }
+    }
+}
+
 /// Scale that maps continuous input through an interpolator function
 ///
 /// Sequential scales are designed for mapping continuous data to colors
@@ -28,6 +65,10 @@ pub type Interpolator<T> = Box<dyn Fn(f64) -> T + Send + Sync>;
 ///
 /// assert_eq!(scale.interpolate(0.0), "rgb(0,0,0)");
 /// assert_eq!(scale.interpolate(100.0), "rgb(255,255,255)");
+///
+/// // Log transform (d3.scaleSequentialLog) for data spanning decades
+/// let log = SequentialScale::new(|t| t).domain(1.0, 1000.0).log(10.0);
+/// assert!((log.interpolate(10.0) - 1.0 / 3.0).abs() < 1e-9);
 /// ```
 pub struct SequentialScale<T> {
     /// Start of input domain
@@ -38,6 +79,8 @@ pub struct SequentialScale<T> {
     interpolator: Interpolator<T>,
     /// Whether to clamp input to domain
     clamp: bool,
+    /// Transform applied before normalizing
+    transform: ScaleTransform,
 }
 
 impl<T> SequentialScale<T> {
@@ -59,6 +102,7 @@ impl<T> SequentialScale<T> {
             domain_max: 1.0,
             interpolator: Box::new(interpolator),
             clamp: false,
+            transform: ScaleTransform::Linear,
         }
     }
 
@@ -107,6 +151,32 @@ impl<T> SequentialScale<T> {
         self.clamp
     }
 
+    /// Set the domain transform
+    pub fn transform(mut self, transform: ScaleTransform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Logarithmic transform (d3.scaleSequentialLog)
+    pub fn log(self, base: f64) -> Self {
+        self.transform(ScaleTransform::Log(base))
+    }
+
+    /// Power transform (d3.scaleSequentialPow)
+    pub fn pow(self, exponent: f64) -> Self {
+        self.transform(ScaleTransform::Pow(exponent))
+    }
+
+    /// Symmetric log transform (d3.scaleSequentialSymlog)
+    pub fn symlog(self, constant: f64) -> Self {
+        self.transform(ScaleTransform::Symlog(constant))
+    }
+
+    /// Get the domain transform
+    pub fn get_transform(&self) -> ScaleTransform {
+        self.transform
+    }
+
     /// Set a new interpolator
     pub fn interpolator<F>(mut self, interpolator: F) -> Self
     where
@@ -118,12 +188,13 @@ impl<T> SequentialScale<T> {
 
     /// Normalize a domain value to [0, 1]
     fn normalize(&self, value: f64) -> f64 {
-        let span = self.domain_max - self.domain_min;
-        if span.abs() < f64::EPSILON {
+        let t0 = self.transform.apply(self.domain_min);
+        let span = self.transform.apply(self.domain_max) - t0;
+        if span.abs() < f64::EPSILON || !span.is_finite() {
             return 0.5;
         }
 
-        let t = (value - self.domain_min) / span;
+        let t = (self.transform.apply(value) - t0) / span;
 
         if self.clamp {
             t.clamp(0.0, 1.0)
@@ -198,6 +269,7 @@ impl Scale for SequentialScale<f64> {
         self.domain_min = other.domain_min;
         self.domain_max = other.domain_max;
         self.clamp = other.clamp;
+        self.transform = other.transform;
         // Note: We can't copy the interpolator as it's a Box<dyn Fn>
     }
 
@@ -206,13 +278,15 @@ impl Scale for SequentialScale<f64> {
         let min = self.domain_min;
         let max = self.domain_max;
         let clamp = self.clamp;
+        let transform = self.transform;
         let range_start = (self.interpolator)(0.0);
         let range_end = (self.interpolator)(1.0);
 
         Box::new(
             SequentialScale::new(move |t| range_start + t * (range_end - range_start))
                 .domain(min, max)
-                .clamp(clamp),
+                .clamp(clamp)
+                .transform(transform),
         )
     }
 }
@@ -578,6 +652,23 @@ mod tests {
         assert_eq!(boxed.scale_type(), "sequential");
     }
 
+    #[test]
+    fn test_sequential_scale_transforms() {
+        let sqrt = SequentialScale::new(|t| t).domain(0.0, 100.0).transform(ScaleTransform::sqrt());
+        assert!((sqrt.interpolate(25.0) - 0.5).abs() < 1e-9);
+
+        let symlog = SequentialScale::new(|t| t).domain(-100.0, 100.0).symlog(1.0);
+        assert!((symlog.interpolate(0.0) - 0.5).abs() < 1e-9);
+        assert!(symlog.interpolate(10.0) > 0.75);
+
+        // Negative log domains are mirrored
+        let log = SequentialScale::new(|t| t).domain(-1000.0, -1.0).log(10.0);
+        assert!((log.interpolate(-10.0) - 2.0 / 3.0).abs() < 1e-9);
+
+        let boxed = log.clone_box();
+        assert!((boxed.scale(-100.0) - 1.0 / 3.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_linear_interpolator() {
         let interp = interpolators::linear(0.0, 100.0);
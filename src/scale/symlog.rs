@@ -1,7 +1,7 @@
 //! Symmetric logarithmic scale implementation
 
 use super::traits::{Scale, ContinuousScale, ScaleExt, Tick, TickOptions};
-use super::utils::{nice_step, format_number};
+use super::utils::{nice_step, format_number, interpolate_range};
 
 /// Symmetric logarithmic scale for data that crosses zero
 ///
@@ -34,6 +34,7 @@ pub struct SymlogScale {
     /// Constant that determines where linear transitions to log
     constant: f64,
     clamp: bool,
+    round: bool,
 }
 
 impl SymlogScale {
@@ -46,6 +47,7 @@ impl SymlogScale {
             range_end: 1.0,
             constant: 1.0,
             clamp: false,
+            round: false,
         }
     }
 
@@ -64,6 +66,17 @@ impl SymlogScale {
         self
     }
 
+    /// Round output positions to whole pixels (d3's `rangeRound`)
+    pub fn with_round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Check if output positions are rounded
+    pub fn is_rounded(&self) -> bool {
+        self.round
+    }
+
     /// Get the current constant
     pub fn constant(&self) -> f64 {
         self.constant
@@ -125,11 +138,11 @@ impl Scale for SymlogScale {
 
         let domain_span = sym_max - sym_min;
         if domain_span.abs() < f64::EPSILON {
-            return self.range_start;
+            return interpolate_range((self.range_start, self.range_end), 0.0, self.round);
         }
 
         let t = (sym_val - sym_min) / domain_span;
-        self.range_start + t * (self.range_end - self.range_start)
+        interpolate_range((self.range_start, self.range_end), t, self.round)
     }
 
     fn invert(&self, pixel: f64) -> f64 {
@@ -139,6 +152,7 @@ impl Scale for SymlogScale {
         }
 
         let t = (pixel - self.range_start) / range_span;
+        let t = if self.clamp { t.clamp(0.0, 1.0) } else { t };
 
         let sym_min = self.symlog_transform(self.domain_min);
         let sym_max = self.symlog_transform(self.domain_max);
@@ -188,6 +202,7 @@ impl Scale for SymlogScale {
         self.range_end = other.range_end;
         self.constant = other.constant;
         self.clamp = other.clamp;
+        self.round = other.round;
     }
 
     fn clone_box(&self) -> Box<dyn Scale> {
@@ -6,6 +6,18 @@
 //! breakpoints.
 
 use super::traits::{Scale, Tick, TickOptions};
+use crate::axis::NumberFormat;
+
+/// Format a class interval for display: "50–75", or "< 50" / "≥ 75" when
+/// one side is unbounded
+pub(crate) fn class_label(x0: f64, x1: f64, format: &NumberFormat) -> String {
+    match (x0.is_finite(), x1.is_finite()) {
+        (true, true) => format!("{}–{}", format.format(x0), format.format(x1)),
+        (false, true) => format!("< {}", format.format(x1)),
+        (true, false) => format!("≥ {}", format.format(x0)),
+        (false, false) => "All".to_string(),
+    }
+}
 
 /// Scale that maps continuous input to discrete output using explicit thresholds
 ///
@@ -233,6 +245,35 @@ impl<T: Clone> ThresholdScale<T> {
             .position(|v| v == value)
             .map(|i| self.invert_extent(i))
     }
+
+    /// Classify a value as `(class_index, label, output)`
+    ///
+    /// The label is the class interval formatted with `format`, so tooltips
+    /// and tables can name the class the same way the chart colors it.
+    /// Returns `None` for an empty range or a NaN value.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::axis::NumberFormat;
+    /// use makepad_d3::scale::ThresholdScale;
+    ///
+    /// let scale = ThresholdScale::new()
+    ///     .domain(vec![50.0, 75.0])
+    ///     .range(vec!["low", "mid", "high"]);
+    ///
+    /// let (index, label, color) = scale.classify(60.0, &NumberFormat::Fixed(0)).unwrap();
+    /// assert_eq!((index, label.as_str(), color), (1, "50–75", "mid"));
+    /// assert_eq!(scale.classify(10.0, &NumberFormat::Fixed(0)).unwrap().1, "< 50");
+    /// ```
+    pub fn classify(&self, value: f64, format: &NumberFormat) -> Option<(usize, String, T)> {
+        if value.is_nan() {
+            return None;
+        }
+        let output = self.scale_to_value(value)?.clone();
+        let index = self.scale_to_index(value);
+        let (x0, x1) = self.invert_extent(index);
+        Some((index, class_label(x0, x1, format), output))
+    }
 }
 
 impl<T: Clone> Default for ThresholdScale<T> {
@@ -607,4 +648,20 @@ mod tests {
         assert_eq!(scale.scale_to_value(55.0), Some(&"50-60"));
         assert_eq!(scale.scale_to_value(95.0), Some(&"90+"));
     }
+
+    #[test]
+    fn test_threshold_classify() {
+        let scale = ThresholdScale::new()
+            .domain(vec![0.5, 0.75])
+            .range(vec![1.0, 2.0, 3.0]);
+        let format = NumberFormat::Percent;
+        let (index, label, output) = scale.classify(0.8, &format).unwrap();
+        assert_eq!((index, output), (2, 3.0));
+        assert_eq!(label, format!("≥ {}", format.format(0.75)));
+        assert_eq!(
+            scale.classify(0.6, &format).unwrap().1,
+            format!("{}–{}", format.format(0.5), format.format(0.75))
+        );
+        assert_eq!(scale.classify(f64::NAN, &format), None);
+    }
 }
@@ -1,104 +1,11 @@
 //! Time scale implementation
 
 use super::traits::{Scale, ContinuousScale, Tick, TickOptions};
-use chrono::{DateTime, Utc, Duration, Datelike, Timelike};
-
-/// Time interval for tick generation
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum TimeInterval {
-    /// Milliseconds with multiplier
-    Millisecond(u32),
-    /// Seconds with multiplier
-    Second(u32),
-    /// Minutes with multiplier
-    Minute(u32),
-    /// Hours with multiplier
-    Hour(u32),
-    /// Days with multiplier
-    Day(u32),
-    /// Weeks with multiplier
-    Week(u32),
-    /// Months with multiplier
-    Month(u32),
-    /// Years with multiplier
-    Year(u32),
-}
-
-impl TimeInterval {
-    /// Get the approximate duration in milliseconds
-    pub fn duration_ms(&self) -> f64 {
-        match self {
-            Self::Millisecond(n) => *n as f64,
-            Self::Second(n) => *n as f64 * 1000.0,
-            Self::Minute(n) => *n as f64 * 60_000.0,
-            Self::Hour(n) => *n as f64 * 3_600_000.0,
-            Self::Day(n) => *n as f64 * 86_400_000.0,
-            Self::Week(n) => *n as f64 * 604_800_000.0,
-            Self::Month(n) => *n as f64 * 2_592_000_000.0, // ~30 days
-            Self::Year(n) => *n as f64 * 31_536_000_000.0, // 365 days
-        }
-    }
-
-    /// Get a reasonable default format string for this interval
-    pub fn default_format(&self) -> &'static str {
-        match self {
-            Self::Millisecond(_) => "%H:%M:%S.%3f",
-            Self::Second(_) => "%H:%M:%S",
-            Self::Minute(_) | Self::Hour(_) => "%H:%M",
-            Self::Day(_) | Self::Week(_) => "%b %d",
-            Self::Month(_) => "%b %Y",
-            Self::Year(_) => "%Y",
-        }
-    }
-
-    /// Find the appropriate interval for a given duration and target tick count
-    pub fn for_duration(duration_ms: f64, target_ticks: usize) -> Self {
-        if target_ticks == 0 {
-            return Self::Year(1);
-        }
-
-        let target_interval = duration_ms / target_ticks as f64;
-
-        // Intervals in ascending order of duration
-        let intervals: &[(f64, TimeInterval)] = &[
-            (1.0, Self::Millisecond(1)),
-            (5.0, Self::Millisecond(5)),
-            (10.0, Self::Millisecond(10)),
-            (50.0, Self::Millisecond(50)),
-            (100.0, Self::Millisecond(100)),
-            (500.0, Self::Millisecond(500)),
-            (1000.0, Self::Second(1)),
-            (5000.0, Self::Second(5)),
-            (15000.0, Self::Second(15)),
-            (30000.0, Self::Second(30)),
-            (60000.0, Self::Minute(1)),
-            (300000.0, Self::Minute(5)),
-            (900000.0, Self::Minute(15)),
-            (1800000.0, Self::Minute(30)),
-            (3600000.0, Self::Hour(1)),
-            (10800000.0, Self::Hour(3)),
-            (21600000.0, Self::Hour(6)),
-            (43200000.0, Self::Hour(12)),
-            (86400000.0, Self::Day(1)),
-            (172800000.0, Self::Day(2)),
-            (604800000.0, Self::Week(1)),
-            (1209600000.0, Self::Week(2)),
-            (2592000000.0, Self::Month(1)),
-            (7776000000.0, Self::Month(3)),
-            (15552000000.0, Self::Month(6)),
-            (31536000000.0, Self::Year(1)),
-            (63072000000.0, Self::Year(2)),
-            (157680000000.0, Self::Year(5)),
-            (315360000000.0, Self::Year(10)),
-        ];
-
-        intervals
-            .iter()
-            .find(|(ms, _)| *ms >= target_interval)
-            .map(|(_, interval)| *interval)
-            .unwrap_or(Self::Year(10))
-    }
-}
+use super::extent::{extent, DomainPolicy};
+use super::interval::{TimeInterval, ZonedInterval};
+use super::locale::{TimeLocale, TimeZoneRule};
+use super::utils::interpolate_range;
+use chrono::{DateTime, Utc, Duration};
 
 /// A tick mark with time information
 #[derive(Clone, Debug)]
@@ -141,7 +48,10 @@ pub struct TimeScale {
     range_start: f64,
     range_end: f64,
     clamp: bool,
+    round: bool,
     format: Option<String>,
+    locale: Option<TimeLocale>,
+    time_zone: TimeZoneRule,
 }
 
 impl TimeScale {
@@ -154,7 +64,10 @@ impl TimeScale {
             range_start: 0.0,
             range_end: 100.0,
             clamp: false,
+            round: false,
             format: None,
+            locale: None,
+            time_zone: TimeZoneRule::Utc,
         }
     }
 
@@ -174,6 +87,29 @@ impl TimeScale {
         self
     }
 
+    /// Create with the extent of timestamps (milliseconds since epoch) as domain
+    pub fn from_data(timestamps: impl IntoIterator<Item = f64>) -> Self {
+        Self::from_data_with(timestamps, &DomainPolicy::new())
+    }
+
+    /// Create with a domain derived from timestamps by a [`DomainPolicy`]
+    ///
+    /// Padding and minimum span are in milliseconds of the data span; nice
+    /// rounding snaps to calendar intervals. Zero inclusion is ignored.
+    /// Without finite timestamps the domain is the next 24 hours, like
+    /// [`TimeScale::new`].
+    pub fn from_data_with(timestamps: impl IntoIterator<Item = f64>, policy: &DomainPolicy) -> Self {
+        let Some((min, max)) = extent(timestamps) else {
+            return Self::new();
+        };
+        let (min, max) = policy.with_zero(false).apply_without_nice(min, max);
+        let mut scale = Self::new().with_domain_ms(min.round() as i64, max.round() as i64);
+        if policy.is_nice() {
+            scale.nice();
+        }
+        scale
+    }
+
     /// Set the range
     pub fn with_range(mut self, start: f64, end: f64) -> Self {
         self.range_start = start;
@@ -187,12 +123,43 @@ impl TimeScale {
         self
     }
 
+    /// Round output positions to whole pixels (d3's `rangeRound`)
+    pub fn with_round(mut self, round: bool) -> Self {
+        self.round = round;
+        self
+    }
+
+    /// Check if output positions are rounded
+    pub fn is_rounded(&self) -> bool {
+        self.round
+    }
+
     /// Set custom tick format
     pub fn with_format(mut self, format: impl Into<String>) -> Self {
         self.format = Some(format.into());
         self
     }
 
+    /// Set the locale for tick labels and the first day of weekly ticks
+    pub fn with_locale(mut self, locale: TimeLocale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Set the time zone ticks are aligned to and labelled in (default UTC)
+    ///
+    /// Day ticks fall on local midnights and hour labels show local time;
+    /// tick times and positions stay in UTC.
+    pub fn with_time_zone(mut self, zone: TimeZoneRule) -> Self {
+        self.time_zone = zone;
+        self
+    }
+
+    /// Get the time zone
+    pub fn time_zone(&self) -> &TimeZoneRule {
+        &self.time_zone
+    }
+
     /// Get domain start
     pub fn domain_start(&self) -> DateTime<Utc> {
         self.domain_start
@@ -223,7 +190,7 @@ impl TimeScale {
         };
 
         let t = self.normalize_time(time);
-        self.range_start + t * (self.range_end - self.range_start)
+        interpolate_range((self.range_start, self.range_end), t, self.round)
     }
 
     /// Invert pixel position to DateTime
@@ -234,6 +201,7 @@ impl TimeScale {
         }
 
         let t = (pixel - self.range_start) / range_span;
+        let t = if self.clamp { t.clamp(0.0, 1.0) } else { t };
         let duration = self.domain_end - self.domain_start;
         let offset_ms = (duration.num_milliseconds() as f64 * t) as i64;
 
@@ -258,12 +226,13 @@ impl TimeScale {
             return vec![];
         }
 
-        let interval = TimeInterval::for_duration(duration_ms, options.count);
+        let interval = self.local_interval(TimeInterval::for_duration(duration_ms, options.count));
         let format = self.format.as_deref()
             .unwrap_or_else(|| interval.default_format());
 
+        let zoned = ZonedInterval::new(interval, self.time_zone.clone());
         let mut ticks = Vec::new();
-        let mut current = self.floor_to_interval(self.domain_start, interval);
+        let mut current = zoned.floor(self.domain_start);
 
         let (domain_min, domain_max) = if self.domain_start <= self.domain_end {
             (self.domain_start, self.domain_end)
@@ -274,7 +243,11 @@ impl TimeScale {
         while current <= domain_max && ticks.len() < options.max_count {
             if current >= domain_min {
                 let pos = self.scale_time(current);
-                let label = current.format(format).to_string();
+                let label = match (&self.locale, &self.time_zone) {
+                    (Some(locale), zone) => locale.format(zone.to_local(current), format),
+                    (None, TimeZoneRule::Utc) => current.format(format).to_string(),
+                    (None, zone) => zone.to_local(current).format(format).to_string(),
+                };
                 ticks.push(TimeTick {
                     time: current,
                     value: current.timestamp_millis() as f64,
@@ -282,115 +255,17 @@ impl TimeScale {
                     position: pos,
                 });
             }
-            current = self.add_interval(current, interval);
+            current = zoned.next(current);
         }
 
         ticks
     }
 
-    /// Floor datetime to interval boundary
-    fn floor_to_interval(&self, time: DateTime<Utc>, interval: TimeInterval) -> DateTime<Utc> {
-        match interval {
-            TimeInterval::Millisecond(n) => {
-                let ms = time.timestamp_subsec_millis();
-                let floored = (ms / n) * n;
-                time - Duration::milliseconds((ms - floored) as i64)
-            }
-            TimeInterval::Second(n) => {
-                let s = time.second();
-                let floored = (s / n) * n;
-                time.with_second(floored)
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-            TimeInterval::Minute(n) => {
-                let m = time.minute();
-                let floored = (m / n) * n;
-                time.with_minute(floored)
-                    .and_then(|t| t.with_second(0))
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-            TimeInterval::Hour(n) => {
-                let h = time.hour();
-                let floored = (h / n) * n;
-                time.with_hour(floored)
-                    .and_then(|t| t.with_minute(0))
-                    .and_then(|t| t.with_second(0))
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-            TimeInterval::Day(n) => {
-                let d = time.day();
-                let floored = ((d - 1) / n) * n + 1;
-                time.with_day(floored)
-                    .and_then(|t| t.with_hour(0))
-                    .and_then(|t| t.with_minute(0))
-                    .and_then(|t| t.with_second(0))
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-            TimeInterval::Week(_) => {
-                // Floor to start of week (Monday)
-                let weekday = time.weekday().num_days_from_monday();
-                (time - Duration::days(weekday as i64))
-                    .with_hour(0)
-                    .and_then(|t| t.with_minute(0))
-                    .and_then(|t| t.with_second(0))
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-            TimeInterval::Month(n) => {
-                let m = time.month();
-                let floored = ((m - 1) / n) * n + 1;
-                time.with_month(floored)
-                    .and_then(|t| t.with_day(1))
-                    .and_then(|t| t.with_hour(0))
-                    .and_then(|t| t.with_minute(0))
-                    .and_then(|t| t.with_second(0))
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-            TimeInterval::Year(n) => {
-                let y = time.year();
-                let floored = (y / n as i32) * n as i32;
-                time.with_year(floored)
-                    .and_then(|t| t.with_month(1))
-                    .and_then(|t| t.with_day(1))
-                    .and_then(|t| t.with_hour(0))
-                    .and_then(|t| t.with_minute(0))
-                    .and_then(|t| t.with_second(0))
-                    .and_then(|t| t.with_nanosecond(0))
-                    .unwrap_or(time)
-            }
-        }
-    }
-
-    /// Add interval to datetime
-    fn add_interval(&self, time: DateTime<Utc>, interval: TimeInterval) -> DateTime<Utc> {
-        match interval {
-            TimeInterval::Millisecond(n) => time + Duration::milliseconds(n as i64),
-            TimeInterval::Second(n) => time + Duration::seconds(n as i64),
-            TimeInterval::Minute(n) => time + Duration::minutes(n as i64),
-            TimeInterval::Hour(n) => time + Duration::hours(n as i64),
-            TimeInterval::Day(n) => time + Duration::days(n as i64),
-            TimeInterval::Week(n) => time + Duration::weeks(n as i64),
-            TimeInterval::Month(n) => {
-                // Handle month addition carefully
-                let mut new_month = time.month() + n;
-                let mut new_year = time.year();
-                while new_month > 12 {
-                    new_month -= 12;
-                    new_year += 1;
-                }
-                time.with_year(new_year)
-                    .and_then(|t| t.with_month(new_month))
-                    .unwrap_or(time + Duration::days(30 * n as i64))
-            }
-            TimeInterval::Year(n) => {
-                time.with_year(time.year() + n as i32)
-                    .unwrap_or(time + Duration::days(365 * n as i64))
-            }
+    /// Use the locale's first day of the week for weekly intervals
+    fn local_interval(&self, interval: TimeInterval) -> TimeInterval {
+        match (interval, &self.locale) {
+            (TimeInterval::Week(n), Some(locale)) => TimeInterval::WeekStarting(locale.first_day_of_week, n),
+            (interval, _) => interval,
         }
     }
 }
@@ -458,7 +333,10 @@ impl Scale for TimeScale {
         self.range_start = other.range_start;
         self.range_end = other.range_end;
         self.clamp = other.clamp;
+        self.round = other.round;
         self.format = other.format.clone();
+        self.locale = other.locale.clone();
+        self.time_zone = other.time_zone.clone();
     }
 
     fn clone_box(&self) -> Box<dyn Scale> {
@@ -468,12 +346,10 @@ impl Scale for TimeScale {
 
 impl ContinuousScale for TimeScale {
     fn nice(&mut self) {
-        let interval = TimeInterval::for_duration(self.duration_ms().abs(), 10);
-        self.domain_start = self.floor_to_interval(self.domain_start, interval);
-        self.domain_end = self.add_interval(
-            self.floor_to_interval(self.domain_end, interval),
-            interval
-        );
+        let interval = self.local_interval(TimeInterval::for_duration(self.duration_ms().abs(), 10));
+        let zoned = ZonedInterval::new(interval, self.time_zone.clone());
+        self.domain_start = zoned.floor(self.domain_start);
+        self.domain_end = zoned.next(zoned.floor(self.domain_end));
     }
 
     fn is_clamped(&self) -> bool {
@@ -490,6 +366,23 @@ mod tests {
     use super::*;
     use chrono::TimeZone;
 
+    #[test]
+    fn test_time_scale_from_data() {
+        let day = 86_400_000.0;
+        let start = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap().timestamp_millis() as f64;
+        let stamps = [start + 2.0 * day, start, start + 8.0 * day];
+
+        let scale = TimeScale::from_data(stamps);
+        assert_eq!(scale.domain(), (start, start + 8.0 * day));
+
+        let padded = TimeScale::from_data_with(stamps, &DomainPolicy::new().with_padding(0.25));
+        assert_eq!(padded.domain(), (start - 2.0 * day, start + 10.0 * day));
+
+        // A single sample gets a minimum span instead of a zero-width domain
+        let single = TimeScale::from_data_with([start], &DomainPolicy::new().with_min_span(day));
+        assert_eq!(single.duration_ms(), day);
+    }
+
     #[test]
     fn test_time_scale_new() {
         let scale = TimeScale::new();
@@ -570,6 +463,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ticks_in_time_zone_and_locale() {
+        use chrono::Timelike;
+
+        // Two days in Berlin summer time: ticks land on local midnights
+        let start = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 7, 8, 0, 0, 0).unwrap();
+        let scale = TimeScale::new()
+            .with_time_domain(start, end)
+            .with_range(0.0, 700.0)
+            .with_time_zone(TimeZoneRule::central_european())
+            .with_locale(TimeLocale::de_de())
+            .with_format("%a %e. %b");
+        let ticks = scale.time_ticks(&TickOptions::new().with_count(7));
+        assert_eq!(ticks.len(), 7);
+        assert!(ticks.iter().all(|t| t.time.hour() == 22));
+        assert_eq!(ticks[0].label, "Di  2. Jul");
+
+        // Weekly ticks start on the locale's first weekday (Sunday in en-US)
+        let month_end = Utc.with_ymd_and_hms(2024, 8, 1, 0, 0, 0).unwrap();
+        let weekly = TimeScale::new()
+            .with_time_domain(start, month_end)
+            .with_locale(TimeLocale::en_us())
+            .with_format("%a %d");
+        let ticks = weekly.time_ticks(&TickOptions::new().with_count(5));
+        assert_eq!(ticks[0].label, "Sun 07");
+    }
+
     #[test]
     fn test_hourly_ticks() {
         let start = Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap();
@@ -626,6 +547,8 @@ mod tests {
             .with_clamp(true);
 
         assert!((scale.scale_time(before) - 0.0).abs() < 1.0);
+        assert_eq!(scale.invert_time(-500.0), start);
+        assert_eq!(scale.invert_time(1500.0), end);
     }
 
     #[test]
@@ -17,6 +17,10 @@ pub struct TickOptions {
 
     /// Custom step size (overrides count)
     pub step_size: Option<f64>,
+
+    /// Target pixel distance between ticks (overrides count once the
+    /// range length is known, see [`TickOptions::for_length`])
+    pub pixel_spacing: Option<f64>,
 }
 
 impl Default for TickOptions {
@@ -27,6 +31,7 @@ impl Default for TickOptions {
             min_count: 2,
             include_bounds: false,
             step_size: None,
+            pixel_spacing: None,
         }
     }
 }
@@ -66,6 +71,43 @@ impl TickOptions {
         self.include_bounds = include;
         self
     }
+
+    /// Aim for one tick every `pixels` along the range (e.g. 80.0)
+    pub fn with_pixel_spacing(mut self, pixels: f64) -> Self {
+        self.pixel_spacing = (pixels.is_finite() && pixels > 0.0).then_some(pixels);
+        self
+    }
+
+    /// Tick count for a range of `length` pixels
+    ///
+    /// With a pixel spacing this is `length / spacing`, clamped to
+    /// `[min_count, max_count]`; otherwise it is `count`.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::scale::TickOptions;
+    ///
+    /// let options = TickOptions::new().with_pixel_spacing(80.0);
+    /// assert_eq!(options.count_for_length(400.0), 5);
+    /// assert_eq!(options.count_for_length(100.0), 2); // min_count
+    /// ```
+    pub fn count_for_length(&self, length: f64) -> usize {
+        match self.pixel_spacing {
+            Some(spacing) if length.is_finite() => {
+                let count = (length.abs() / spacing).floor() as usize;
+                count.clamp(self.min_count, self.max_count.max(self.min_count))
+            }
+            _ => self.count,
+        }
+    }
+
+    /// Options with `count` resolved for a range of `length` pixels
+    pub fn for_length(&self, length: f64) -> Self {
+        Self {
+            count: self.count_for_length(length),
+            ..self.clone()
+        }
+    }
 }
 
 /// A tick mark on a scale
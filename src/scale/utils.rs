@@ -111,6 +111,34 @@ fn trim_trailing_zeros(s: &str) -> String {
     trimmed.to_string()
 }
 
+/// Round a position to the nearest device pixel
+///
+/// `pixel_ratio` is device pixels per logical pixel (2 on most HiDPI
+/// screens); positions snap to multiples of `1 / pixel_ratio`.
+pub fn snap_to_pixel(value: f64, pixel_ratio: f64) -> f64 {
+    let ratio = if pixel_ratio > 0.0 { pixel_ratio } else { 1.0 };
+    (value * ratio).round() / ratio
+}
+
+/// Map normalized `t` onto a continuous scale's range
+///
+/// With `round` set the result snaps to whole logical pixels; continuous
+/// scales have no pixel ratio, unlike [`BandScale::pixel_ratio`](super::BandScale::pixel_ratio).
+pub(crate) fn interpolate_range(range: (f64, f64), t: f64, round: bool) -> f64 {
+    let pixel = lerp(range.0, range.1, t);
+    if round {
+        snap_to_pixel(pixel, 1.0)
+    } else {
+        pixel
+    }
+}
+
+/// Round a length down to whole device pixels
+pub(crate) fn snap_down(value: f64, pixel_ratio: f64) -> f64 {
+    let ratio = if pixel_ratio > 0.0 { pixel_ratio } else { 1.0 };
+    (value * ratio).floor() / ratio
+}
+
 /// Linear interpolation between two values
 pub fn lerp(a: f64, b: f64, t: f64) -> f64 {
     a + (b - a) * t
@@ -37,6 +37,8 @@ pub struct ArcGenerator {
     corner_radius: f64,
     /// Padding angle between adjacent arcs
     pad_angle: f64,
+    /// Distance the arc is pushed outward along its mid angle
+    explode: f64,
 }
 
 impl Default for ArcGenerator {
@@ -55,6 +57,7 @@ impl ArcGenerator {
             end_angle: TAU,
             corner_radius: 0.0,
             pad_angle: 0.0,
+            explode: 0.0,
         }
     }
 
@@ -94,6 +97,49 @@ impl ArcGenerator {
         self
     }
 
+    /// Set the explode offset: the arc is translated outward along its
+    /// mid angle by this distance, e.g. to emphasize a selected slice
+    pub fn explode(mut self, distance: f64) -> Self {
+        self.explode = distance.max(0.0);
+        self
+    }
+
+    /// Get the explode offset
+    pub fn get_explode(&self) -> f64 {
+        self.explode
+    }
+
+    /// Unit vector pointing from the center through the arc's mid angle
+    pub fn explode_direction(&self) -> Point {
+        explode_direction(self.start_angle, self.end_angle)
+    }
+
+    /// Translation applied to the arc by the explode offset
+    pub fn explode_offset(&self) -> Point {
+        let dir = self.explode_direction();
+        Point::new(dir.x * self.explode, dir.y * self.explode)
+    }
+
+    /// Snapshot of this generator's radii, angles and padding
+    pub fn to_datum(&self) -> ArcDatum {
+        ArcDatum {
+            inner_radius: self.inner_radius,
+            outer_radius: self.outer_radius,
+            start_angle: self.start_angle,
+            end_angle: self.end_angle,
+            corner_radius: self.corner_radius,
+            pad_angle: self.pad_angle,
+        }
+    }
+
+    /// Intermediate arc between this generator and `to` at `t` in `[0, 1]`
+    ///
+    /// Equivalent to `self.to_datum().interpolate(&to.to_datum(), t)`; angles
+    /// take the short way around the circle.
+    pub fn tween(&self, to: &ArcGenerator, t: f64) -> ArcDatum {
+        self.to_datum().interpolate(&to.to_datum(), t)
+    }
+
     /// Get the inner radius
     pub fn get_inner_radius(&self) -> f64 {
         self.inner_radius
@@ -107,8 +153,9 @@ impl ArcGenerator {
     /// Calculate the centroid of the arc
     ///
     /// Returns the center point of the arc, useful for label placement.
+    /// Includes the explode offset, so labels follow an exploded slice.
     pub fn centroid(&self) -> Point {
-        let r = (self.inner_radius + self.outer_radius) / 2.0;
+        let r = (self.inner_radius + self.outer_radius) / 2.0 + self.explode;
         let a = (self.start_angle + self.end_angle) / 2.0 - PI / 2.0;
         Point::new(r * a.cos(), r * a.sin())
     }
@@ -122,6 +169,16 @@ impl ArcGenerator {
 
     /// Generate path segments for the arc
     pub fn generate(&self) -> Vec<PathSegment> {
+        let path = self.generate_centered();
+        if self.explode == 0.0 {
+            return path;
+        }
+        let offset = self.explode_offset();
+        path.iter().map(|s| s.translated(offset.x, offset.y)).collect()
+    }
+
+    /// Generate path segments around the origin, ignoring the explode offset
+    fn generate_centered(&self) -> Vec<PathSegment> {
         let mut path = Vec::new();
 
         let inner = self.inner_radius;
@@ -277,6 +334,36 @@ impl ArcGenerator {
     }
 }
 
+/// Unit vector along the mid angle of an arc (0 = 12 o'clock, clockwise)
+pub(crate) fn explode_direction(start_angle: f64, end_angle: f64) -> Point {
+    let a = (start_angle + end_angle) / 2.0 - PI / 2.0;
+    Point::new(a.cos(), a.sin())
+}
+
+/// Interpolate an arc's angles, moving its start angle the short way around
+///
+/// The start angle travels by the smallest equivalent rotation while the
+/// angular span is interpolated linearly, so a slice never flips inside out
+/// or spins a full turn. At `t <= 0` and `t >= 1` the inputs are returned
+/// unchanged.
+pub(crate) fn interpolate_angles(from: (f64, f64), to: (f64, f64), t: f64) -> (f64, f64) {
+    if t <= 0.0 {
+        return from;
+    }
+    if t >= 1.0 {
+        return to;
+    }
+    let delta = (to.0 - from.0 + PI).rem_euclid(TAU) - PI;
+    let start = from.0 + delta * t;
+    let span_from = from.1 - from.0;
+    let span_to = to.1 - to.0;
+    (start, start + span_from + (span_to - span_from) * t)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
 /// Builder for creating arcs from pie slice data
 #[derive(Clone, Debug)]
 pub struct ArcDatum {
@@ -327,12 +414,114 @@ impl ArcDatum {
     pub fn centroid(&self) -> Point {
         self.to_generator().centroid()
     }
+
+    /// Intermediate arc between this one and `other` at `t` in `[0, 1]`
+    ///
+    /// Radii and padding are interpolated linearly; angles take the short
+    /// way around the circle.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::shape::ArcDatum;
+    /// use std::f64::consts::PI;
+    ///
+    /// // From just before 12 o'clock to just after: moves through 0, not through PI
+    /// let a = ArcDatum::new(1.9 * PI, 2.1 * PI);
+    /// let b = ArcDatum::new(0.1 * PI, 0.3 * PI);
+    /// let mid = a.interpolate(&b, 0.5);
+    /// assert!((mid.start_angle - 2.0 * PI).abs() < 1e-9);
+    /// assert!((mid.end_angle - 2.2 * PI).abs() < 1e-9);
+    /// ```
+    pub fn interpolate(&self, other: &ArcDatum, t: f64) -> ArcDatum {
+        let (start_angle, end_angle) = interpolate_angles(
+            (self.start_angle, self.end_angle),
+            (other.start_angle, other.end_angle),
+            t,
+        );
+        let t = t.clamp(0.0, 1.0);
+        ArcDatum {
+            inner_radius: lerp(self.inner_radius, other.inner_radius, t),
+            outer_radius: lerp(self.outer_radius, other.outer_radius, t),
+            start_angle,
+            end_angle,
+            corner_radius: lerp(self.corner_radius, other.corner_radius, t),
+            pad_angle: lerp(self.pad_angle, other.pad_angle, t),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_arc_explode_translates_path() {
+        let arc = ArcGenerator::new()
+            .inner_radius(20.0)
+            .outer_radius(100.0)
+            .start_angle(0.0)
+            .end_angle(PI / 2.0);
+        let exploded = arc.clone().explode(8.0);
+
+        // Mid angle is 45° clockwise from 12 o'clock
+        let dir = exploded.explode_direction();
+        let s = std::f64::consts::FRAC_1_SQRT_2;
+        assert!((dir.x - s).abs() < 1e-12 && (dir.y + s).abs() < 1e-12);
+
+        let offset = exploded.explode_offset();
+        let base = arc.generate();
+        let moved = exploded.generate();
+        assert_eq!(base.len(), moved.len());
+        for (a, b) in base.iter().zip(&moved) {
+            if let (Some(p), Some(q)) = (a.end_point(), b.end_point()) {
+                assert!((q.x - p.x - offset.x).abs() < 1e-9);
+                assert!((q.y - p.y - offset.y).abs() < 1e-9);
+            }
+        }
+
+        let c0 = arc.centroid();
+        let c1 = exploded.centroid();
+        assert!((c1.x - c0.x - offset.x).abs() < 1e-9);
+        assert!((c1.y - c0.y - offset.y).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_tween_takes_short_way() {
+        let from = ArcGenerator::new()
+            .inner_radius(10.0)
+            .outer_radius(100.0)
+            .start_angle(0.1)
+            .end_angle(0.5);
+        let to = ArcGenerator::new()
+            .inner_radius(30.0)
+            .outer_radius(100.0)
+            .start_angle(TAU - 0.3)
+            .end_angle(TAU + 0.3);
+
+        let mid = from.tween(&to, 0.5);
+        assert!((mid.start_angle + 0.1).abs() < 1e-12);
+        assert!((mid.end_angle - 0.4).abs() < 1e-12);
+        assert!((mid.inner_radius - 20.0).abs() < 1e-12);
+
+        // Endpoints are returned exactly
+        let end = from.tween(&to, 1.0);
+        assert_eq!((end.start_angle, end.end_angle), (TAU - 0.3, TAU + 0.3));
+        let start = from.tween(&to, 0.0);
+        assert_eq!((start.start_angle, start.end_angle), (0.1, 0.5));
+    }
+
+    #[test]
+    fn test_arc_interpolate_keeps_span_positive() {
+        // Span grows past PI: the end angle must not wrap the short way
+        let a = ArcDatum::new(0.0, 0.5);
+        let b = ArcDatum::new(0.0, 5.0);
+        for i in 0..=10 {
+            let arc = a.interpolate(&b, i as f64 / 10.0);
+            assert!(arc.end_angle >= arc.start_angle);
+        }
+        assert!((a.interpolate(&b, 0.5).end_angle - 2.75).abs() < 1e-12);
+    }
+
     #[test]
     fn test_arc_generator_basic() {
         let arc = ArcGenerator::new()
@@ -3,7 +3,7 @@
 //! Creates closed path segments representing the area between a baseline
 //! and a top line.
 
-use crate::data::DataPoint;
+use crate::data::{DataPoint, Dataset};
 use super::curve::{Curve, LinearCurve};
 use super::path::{PathSegment, Point};
 
@@ -112,12 +112,27 @@ impl AreaGenerator {
 
     /// Generate path segments from data points
     pub fn generate(&self, data: &[DataPoint]) -> Vec<PathSegment> {
+        self.generate_where(data, |_| true)
+    }
+
+    /// Create an area generator using the dataset's curve
+    pub fn for_dataset(dataset: &Dataset) -> Self {
+        Self::new().curve(dataset.curve())
+    }
+
+    /// Generate path segments from a dataset, treating masked-out entries as
+    /// undefined
+    pub fn generate_dataset(&self, dataset: &Dataset) -> Vec<PathSegment> {
+        self.generate_where(&dataset.data, |i| dataset.is_valid(i))
+    }
+
+    fn generate_where(&self, data: &[DataPoint], present: impl Fn(usize) -> bool) -> Vec<PathSegment> {
         // Collect defined points
         let mut top_points: Vec<Point> = Vec::new();
         let mut bottom_points: Vec<Point> = Vec::new();
 
         for (i, d) in data.iter().enumerate() {
-            if (self.defined_fn)(d, i) {
+            if present(i) && (self.defined_fn)(d, i) {
                 let x = (self.x_fn)(d, i);
                 let y0 = (self.y0_fn)(d, i);
                 let y1 = (self.y1_fn)(d, i);
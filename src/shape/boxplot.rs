@@ -0,0 +1,288 @@
+//! Box plot generator
+//!
+//! Summarizes raw samples per category as quartiles, Tukey whiskers and
+//! outliers, and lays each summary out in a band of a [`BandScale`] as a
+//! box, median line, whiskers with caps, and outlier points.
+
+use crate::scale::{BandScale, DiscreteScale, Scale};
+use crate::stats::quantile_sorted;
+
+use super::path::Point;
+
+/// Direction the value axis runs in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DistributionOrientation {
+    /// Categories along x, values along y
+    #[default]
+    Vertical,
+    /// Categories along y, values along x
+    Horizontal,
+}
+
+impl DistributionOrientation {
+    /// Screen point from a category-axis and a value-axis position
+    pub(crate) fn point(&self, band: f64, value: f64) -> Point {
+        match self {
+            DistributionOrientation::Vertical => Point::new(band, value),
+            DistributionOrientation::Horizontal => Point::new(value, band),
+        }
+    }
+}
+
+/// Five-number summary with Tukey whiskers
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxStats {
+    /// Number of finite samples
+    pub count: usize,
+    /// Smallest sample
+    pub min: f64,
+    /// First quartile
+    pub q1: f64,
+    /// Median
+    pub median: f64,
+    /// Third quartile
+    pub q3: f64,
+    /// Largest sample
+    pub max: f64,
+    /// Mean of the samples
+    pub mean: f64,
+    /// Interquartile range (q3 - q1)
+    pub iqr: f64,
+    /// Lower whisker end: smallest sample within q1 - k * IQR
+    pub whisker_low: f64,
+    /// Upper whisker end: largest sample within q3 + k * IQR
+    pub whisker_high: f64,
+    /// Samples beyond the whiskers, ascending
+    pub outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Summarize samples with whiskers reaching `k` IQRs past the quartiles
+    ///
+    /// Non-finite samples are ignored. Returns None if none are left.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::shape::BoxStats;
+    ///
+    /// let stats = BoxStats::from_samples(&[1.0, 2.0, 3.0, 4.0, 5.0, 40.0], 1.5).unwrap();
+    /// assert_eq!(stats.median, 3.5);
+    /// assert_eq!(stats.whisker_high, 5.0);
+    /// assert_eq!(stats.outliers, vec![40.0]);
+    /// ```
+    pub fn from_samples(samples: &[f64], k: f64) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        sorted.sort_by(f64::total_cmp);
+        let (&min, &max) = (sorted.first()?, sorted.last()?);
+
+        let q1 = quantile_sorted(&sorted, 0.25)?;
+        let median = quantile_sorted(&sorted, 0.5)?;
+        let q3 = quantile_sorted(&sorted, 0.75)?;
+        let iqr = q3 - q1;
+        let (fence_low, fence_high) = (q1 - k * iqr, q3 + k * iqr);
+
+        let inside = || sorted.iter().copied().filter(|&v| v >= fence_low && v <= fence_high);
+        Some(Self {
+            count: sorted.len(),
+            min,
+            q1,
+            median,
+            q3,
+            max,
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            iqr,
+            // The quartiles always lie inside the fences, so these exist
+            whisker_low: inside().next().unwrap_or(q1),
+            whisker_high: inside().next_back().unwrap_or(q3),
+            outliers: sorted.iter().copied().filter(|&v| v < fence_low || v > fence_high).collect(),
+        })
+    }
+}
+
+/// Drawable geometry of one box plot
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxPlot {
+    /// Category name
+    pub category: String,
+    /// Summary the geometry was built from
+    pub stats: BoxStats,
+    /// Box from q1 to q3 as (x, y, width, height)
+    pub rect: (f64, f64, f64, f64),
+    /// Median line across the box
+    pub median: (Point, Point),
+    /// Whisker lines from the box to the whisker ends (low, high)
+    pub whiskers: [(Point, Point); 2],
+    /// Caps across the whisker ends (low, high)
+    pub caps: [(Point, Point); 2],
+    /// Outlier positions
+    pub outliers: Vec<Point>,
+}
+
+/// Box plot generator
+///
+/// # Example
+/// ```
+/// use makepad_d3::scale::{BandScale, LinearScale, ScaleExt};
+/// use makepad_d3::shape::BoxPlotGenerator;
+///
+/// let band = BandScale::new().domain(vec!["A", "B"]).range(0.0, 200.0);
+/// let y = LinearScale::new().with_domain(0.0, 100.0).with_range(400.0, 0.0);
+/// let groups = vec![
+///     ("A", vec![10.0, 20.0, 30.0, 40.0, 50.0]),
+///     ("B", vec![40.0, 45.0, 50.0, 55.0, 99.0]),
+/// ];
+///
+/// let plots = BoxPlotGenerator::new().generate(&groups, &band, &y);
+/// assert_eq!(plots.len(), 2);
+/// assert!((plots[0].median.0.y - 280.0).abs() < 1e-9); // median 30
+/// assert_eq!(plots[1].stats.outliers, vec![99.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BoxPlotGenerator {
+    /// Whisker reach in IQRs past the quartiles (Tukey's 1.5)
+    pub whisker_iqr: f64,
+    /// Box width as a fraction of the bandwidth
+    pub box_width: f64,
+    /// Cap width as a fraction of the box width
+    pub cap_width: f64,
+    /// Axis orientation
+    pub orientation: DistributionOrientation,
+}
+
+impl Default for BoxPlotGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoxPlotGenerator {
+    /// Create a vertical generator with 1.5 IQR whiskers
+    pub fn new() -> Self {
+        Self {
+            whisker_iqr: 1.5,
+            box_width: 0.7,
+            cap_width: 0.5,
+            orientation: DistributionOrientation::Vertical,
+        }
+    }
+
+    /// Set the whisker reach in IQRs (infinite = whiskers to min/max)
+    pub fn whisker_iqr(mut self, k: f64) -> Self {
+        self.whisker_iqr = k.max(0.0);
+        self
+    }
+
+    /// Set the box width as a fraction of the bandwidth
+    pub fn box_width(mut self, fraction: f64) -> Self {
+        self.box_width = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the cap width as a fraction of the box width
+    pub fn cap_width(mut self, fraction: f64) -> Self {
+        self.cap_width = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the orientation
+    pub fn orientation(mut self, orientation: DistributionOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Lay out one box plot per (category, samples) group
+    ///
+    /// Categories missing from the band scale and groups with no finite
+    /// samples are skipped.
+    pub fn generate<S: AsRef<str>>(
+        &self,
+        groups: &[(S, Vec<f64>)],
+        band: &BandScale,
+        value_scale: &dyn Scale,
+    ) -> Vec<BoxPlot> {
+        groups
+            .iter()
+            .filter_map(|(category, samples)| {
+                let start = band.band_start(category.as_ref())?;
+                let stats = BoxStats::from_samples(samples, self.whisker_iqr)?;
+                Some(self.layout(category.as_ref(), stats, start, band.bandwidth(), value_scale))
+            })
+            .collect()
+    }
+
+    /// Geometry for a precomputed summary in the band `[band_start, band_start + bandwidth]`
+    pub fn layout(
+        &self,
+        category: &str,
+        stats: BoxStats,
+        band_start: f64,
+        bandwidth: f64,
+        value_scale: &dyn Scale,
+    ) -> BoxPlot {
+        let at = |band: f64, value: f64| self.orientation.point(band, value_scale.scale(value));
+        let center = band_start + bandwidth / 2.0;
+        let half = bandwidth * self.box_width / 2.0;
+        let cap = half * self.cap_width;
+
+        let (c1, c3) = (at(center - half, stats.q1), at(center + half, stats.q3));
+        let rect = (c1.x.min(c3.x), c1.y.min(c3.y), (c3.x - c1.x).abs(), (c3.y - c1.y).abs());
+
+        BoxPlot {
+            category: category.to_string(),
+            rect,
+            median: (at(center - half, stats.median), at(center + half, stats.median)),
+            whiskers: [
+                (at(center, stats.q1), at(center, stats.whisker_low)),
+                (at(center, stats.q3), at(center, stats.whisker_high)),
+            ],
+            caps: [
+                (at(center - cap, stats.whisker_low), at(center + cap, stats.whisker_low)),
+                (at(center - cap, stats.whisker_high), at(center + cap, stats.whisker_high)),
+            ],
+            outliers: stats.outliers.iter().map(|&v| at(center, v)).collect(),
+            stats,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::{LinearScale, ScaleExt};
+
+    #[test]
+    fn test_box_stats() {
+        let stats = BoxStats::from_samples(&[7.0, 1.0, f64::NAN, 3.0, 5.0, -20.0, 9.0], 1.5).unwrap();
+        assert_eq!(stats.count, 6);
+        assert_eq!((stats.min, stats.max), (-20.0, 9.0));
+        assert_eq!((stats.q1, stats.median, stats.q3), (1.5, 4.0, 6.5));
+        assert_eq!(stats.whisker_low, 1.0);
+        assert_eq!(stats.whisker_high, 9.0);
+        assert_eq!(stats.outliers, vec![-20.0]);
+
+        // Infinite reach puts the whiskers at the extremes
+        let all = BoxStats::from_samples(&[7.0, 1.0, 3.0, 5.0, -20.0, 9.0], f64::INFINITY).unwrap();
+        assert_eq!(all.whisker_low, -20.0);
+        assert!(all.outliers.is_empty());
+        assert!(BoxStats::from_samples(&[f64::NAN], 1.5).is_none());
+    }
+
+    #[test]
+    fn test_horizontal_layout() {
+        let band = BandScale::new().domain(vec!["only"]).range(0.0, 100.0);
+        let x = LinearScale::new().with_domain(0.0, 16.0).with_range(0.0, 512.0);
+        let groups = vec![("only", vec![2.0, 4.0, 6.0, 8.0]), ("missing", vec![1.0])];
+        let plots = BoxPlotGenerator::new()
+            .orientation(DistributionOrientation::Horizontal)
+            .box_width(0.5)
+            .generate(&groups, &band, &x);
+
+        assert_eq!(plots.len(), 1);
+        let plot = &plots[0];
+        // q1 = 3.5, q3 = 6.5; the box spans a quarter of the band either side of its center
+        assert_eq!(plot.rect, (112.0, 25.0, 96.0, 50.0));
+        assert_eq!(plot.median.0, Point::new(160.0, 25.0));
+        assert_eq!(plot.whiskers[0].1, Point::new(64.0, 50.0));
+        assert_eq!(plot.caps[1].0, Point::new(256.0, 37.5));
+    }
+}
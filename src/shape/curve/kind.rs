@@ -0,0 +1,93 @@
+//! Serializable curve selection
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    BasisCurve, CardinalCurve, CatmullRomCurve, Curve, LinearCurve, MonotoneCurve, NaturalCurve,
+    PathSegment, Point, StepCurve,
+};
+
+/// A curve picked by name, for declarative series styles
+///
+/// Implements [`Curve`] by delegating to the matching curve type, so it can
+/// be passed straight to [`LineGenerator::curve`](crate::shape::LineGenerator::curve).
+///
+/// # Example
+/// ```
+/// use makepad_d3::shape::curve::{Curve, CurveKind};
+/// use makepad_d3::shape::Point;
+///
+/// let points = vec![Point::new(0.0, 0.0), Point::new(10.0, 10.0)];
+/// // Step-after: across, then up
+/// assert_eq!(CurveKind::StepAfter.generate(&points).len(), 3);
+/// assert_eq!(CurveKind::StepAfter.curve_type(), "step-after");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CurveKind {
+    /// Straight segments
+    #[default]
+    Linear,
+    /// Step at the midpoint between points
+    Step,
+    /// Step at the start of each interval
+    StepBefore,
+    /// Step at the end of each interval
+    StepAfter,
+    /// B-spline (does not pass through the points)
+    Basis,
+    /// Cardinal spline (tension 0 = Catmull-Rom shape, 1 = straight)
+    Cardinal {
+        /// Tension in [0, 1]
+        tension: f64,
+    },
+    /// Catmull-Rom spline (alpha 0.5 = centripetal)
+    CatmullRom {
+        /// Parameterization: 0 = uniform, 0.5 = centripetal, 1 = chordal
+        alpha: f64,
+    },
+    /// Monotone cubic in x
+    Monotone,
+    /// Natural cubic spline
+    Natural,
+}
+
+impl CurveKind {
+    /// Curve for a Chart.js-style line tension (0 = straight, 0.4 = smooth)
+    pub fn from_tension(tension: f64) -> Self {
+        if tension > 0.0 {
+            CurveKind::Cardinal { tension: 1.0 - tension.clamp(0.0, 1.0) }
+        } else {
+            CurveKind::Linear
+        }
+    }
+
+    /// Whether the curve draws horizontal and vertical runs only
+    pub fn is_step(&self) -> bool {
+        matches!(self, CurveKind::Step | CurveKind::StepBefore | CurveKind::StepAfter)
+    }
+
+    /// The concrete curve this kind selects
+    pub fn to_curve(&self) -> Box<dyn Curve> {
+        match *self {
+            CurveKind::Linear => Box::new(LinearCurve),
+            CurveKind::Step => Box::new(StepCurve::middle()),
+            CurveKind::StepBefore => Box::new(StepCurve::before()),
+            CurveKind::StepAfter => Box::new(StepCurve::after()),
+            CurveKind::Basis => Box::new(BasisCurve::new()),
+            CurveKind::Cardinal { tension } => Box::new(CardinalCurve::new(tension)),
+            CurveKind::CatmullRom { alpha } => Box::new(CatmullRomCurve::new(alpha)),
+            CurveKind::Monotone => Box::new(MonotoneCurve::new()),
+            CurveKind::Natural => Box::new(NaturalCurve::new()),
+        }
+    }
+}
+
+impl Curve for CurveKind {
+    fn generate(&self, points: &[Point]) -> Vec<PathSegment> {
+        self.to_curve().generate(points)
+    }
+
+    fn curve_type(&self) -> &'static str {
+        self.to_curve().curve_type()
+    }
+}
@@ -12,6 +12,8 @@
 //! - [`CatmullRomCurve`]: Catmull-Rom spline (passes through all points)
 //! - [`MonotoneCurve`]: Monotone cubic interpolation (preserves monotonicity)
 //! - [`NaturalCurve`]: Natural cubic spline (C2 continuous)
+//!
+//! [`CurveKind`] names any of these as plain data, for serializable series styles.
 
 mod linear;
 mod step;
@@ -20,6 +22,7 @@ mod cardinal;
 mod catmull_rom;
 mod monotone;
 mod natural;
+mod kind;
 
 pub use linear::LinearCurve;
 pub use step::{StepCurve, StepPosition};
@@ -28,6 +31,7 @@ pub use cardinal::CardinalCurve;
 pub use catmull_rom::CatmullRomCurve;
 pub use monotone::MonotoneCurve;
 pub use natural::NaturalCurve;
+pub use kind::CurveKind;
 
 use super::path::{PathSegment, Point};
 
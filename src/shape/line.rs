@@ -3,7 +3,7 @@
 //! Converts data points to drawable path segments using configurable
 //! curve interpolation.
 
-use crate::data::DataPoint;
+use crate::data::{DataPoint, Dataset};
 use super::curve::{Curve, LinearCurve};
 use super::path::{PathSegment, Point};
 
@@ -91,12 +91,27 @@ impl LineGenerator {
 
     /// Generate path segments from data points
     pub fn generate(&self, data: &[DataPoint]) -> Vec<PathSegment> {
+        self.generate_where(data, |_| true)
+    }
+
+    /// Create a line generator using the dataset's curve
+    pub fn for_dataset(dataset: &Dataset) -> Self {
+        Self::new().curve(dataset.curve())
+    }
+
+    /// Generate path segments from a dataset, treating masked-out entries as
+    /// undefined
+    pub fn generate_dataset(&self, dataset: &Dataset) -> Vec<PathSegment> {
+        self.generate_where(&dataset.data, |i| dataset.is_valid(i))
+    }
+
+    fn generate_where(&self, data: &[DataPoint], present: impl Fn(usize) -> bool) -> Vec<PathSegment> {
         // Collect defined points into segments
         let mut segments: Vec<Vec<Point>> = Vec::new();
         let mut current_segment: Vec<Point> = Vec::new();
 
         for (i, d) in data.iter().enumerate() {
-            if (self.defined_fn)(d, i) {
+            if present(i) && (self.defined_fn)(d, i) {
                 let x = (self.x_fn)(d, i);
                 let y = (self.y_fn)(d, i);
                 current_segment.push(Point::new(x, y));
@@ -195,6 +210,29 @@ mod tests {
         ]
     }
 
+    #[test]
+    fn test_line_generate_dataset_breaks_at_mask() {
+        let mut ds = Dataset::new("s").with_data(vec![1.0, 0.0, 2.0, 3.0]);
+        ds.set_missing(2);
+        let gen = LineGenerator::new();
+        let moves = |path: &[PathSegment]| path.iter().filter(|s| matches!(s, PathSegment::MoveTo(_))).count();
+        assert_eq!(moves(&gen.generate(&ds.data)), 1);
+        // The zero stays; the masked entry splits the line
+        assert_eq!(moves(&gen.generate_dataset(&ds)), 2);
+    }
+
+    #[test]
+    fn test_line_generator_for_dataset() {
+        use crate::shape::curve::CurveKind;
+
+        let data = vec![1.0, 3.0, 2.0];
+        let straight = Dataset::new("a").with_data(data.clone());
+        let stepped = Dataset::new("b").with_data(data).with_curve(CurveKind::StepAfter);
+        assert_eq!(LineGenerator::for_dataset(&straight).generate_dataset(&straight).len(), 3);
+        // Two extra corners, one per interval
+        assert_eq!(LineGenerator::for_dataset(&stepped).generate_dataset(&stepped).len(), 5);
+    }
+
     #[test]
     fn test_line_generator_basic() {
         let data = sample_data();
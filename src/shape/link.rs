@@ -0,0 +1,222 @@
+//! Link generator for tree, cluster and flow diagrams
+//!
+//! Port of `d3.linkHorizontal`, `d3.linkVertical` and `d3.linkRadial`: a
+//! cubic Bezier connector leaves the source and enters the target parallel
+//! to the flow direction. [`LinkGenerator::ribbon`] builds the filled band
+//! version used by sankey-style flows.
+
+use std::f64::consts::FRAC_PI_2;
+
+use super::path::{PathSegment, Point};
+
+/// Flow direction of a link
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkOrientation {
+    /// Left to right; tangents are horizontal (`d3.linkHorizontal`)
+    #[default]
+    Horizontal,
+    /// Top to bottom; tangents are vertical (`d3.linkVertical`)
+    Vertical,
+    /// Outward from a center; points are `(angle, radius)` (`d3.linkRadial`)
+    ///
+    /// Angles are in radians, 0 at 12 o'clock and clockwise, like
+    /// [`ArcGenerator`](super::ArcGenerator).
+    Radial,
+}
+
+/// Link generator
+///
+/// # Example
+/// ```
+/// use makepad_d3::shape::{LinkGenerator, PathSegment, Point};
+///
+/// let link = LinkGenerator::horizontal();
+/// let path = link.generate(Point::new(0.0, 0.0), Point::new(100.0, 50.0));
+///
+/// assert_eq!(path[0], PathSegment::move_to(0.0, 0.0));
+/// assert_eq!(path[1], PathSegment::curve_to(50.0, 0.0, 50.0, 50.0, 100.0, 50.0));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LinkGenerator {
+    /// Flow direction
+    orientation: LinkOrientation,
+}
+
+impl LinkGenerator {
+    /// Create a horizontal link generator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a horizontal link generator (`d3.linkHorizontal`)
+    pub fn horizontal() -> Self {
+        Self::new().orientation(LinkOrientation::Horizontal)
+    }
+
+    /// Create a vertical link generator (`d3.linkVertical`)
+    pub fn vertical() -> Self {
+        Self::new().orientation(LinkOrientation::Vertical)
+    }
+
+    /// Create a radial link generator (`d3.linkRadial`)
+    pub fn radial() -> Self {
+        Self::new().orientation(LinkOrientation::Radial)
+    }
+
+    /// Set the flow direction
+    pub fn orientation(mut self, orientation: LinkOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Get the flow direction
+    pub fn get_orientation(&self) -> LinkOrientation {
+        self.orientation
+    }
+
+    /// Control polygon `[start, cp1, cp2, end]` of the connector in screen space
+    pub fn control_points(&self, source: Point, target: Point) -> [Point; 4] {
+        match self.orientation {
+            LinkOrientation::Horizontal => {
+                let xm = (source.x + target.x) / 2.0;
+                [source, Point::new(xm, source.y), Point::new(xm, target.y), target]
+            }
+            LinkOrientation::Vertical => {
+                let ym = (source.y + target.y) / 2.0;
+                [source, Point::new(source.x, ym), Point::new(target.x, ym), target]
+            }
+            LinkOrientation::Radial => {
+                let rm = (source.y + target.y) / 2.0;
+                [
+                    radial_point(source.x, source.y),
+                    radial_point(source.x, rm),
+                    radial_point(target.x, rm),
+                    radial_point(target.x, target.y),
+                ]
+            }
+        }
+    }
+
+    /// Generate the connector from `source` to `target`
+    pub fn generate(&self, source: Point, target: Point) -> Vec<PathSegment> {
+        let [p0, p1, p2, p3] = self.control_points(source, target);
+        vec![
+            PathSegment::MoveTo(p0),
+            PathSegment::CurveTo {
+                cp1: p1,
+                cp2: p2,
+                end: p3,
+            },
+        ]
+    }
+
+    /// Generate a closed band from `source` to `target` for filling
+    ///
+    /// The band is `source_width` thick at the source and `target_width` at
+    /// the target, measured across the flow direction; for radial links the
+    /// widths are arc lengths at the endpoint radii.
+    pub fn ribbon(
+        &self,
+        source: Point,
+        target: Point,
+        source_width: f64,
+        target_width: f64,
+    ) -> Vec<PathSegment> {
+        let (s0, s1) = self.offset(source, source_width / 2.0);
+        let (t0, t1) = self.offset(target, target_width / 2.0);
+        let [a0, a1, a2, a3] = self.control_points(s0, t0);
+        let [b0, b1, b2, b3] = self.control_points(t1, s1);
+        vec![
+            PathSegment::MoveTo(a0),
+            PathSegment::CurveTo {
+                cp1: a1,
+                cp2: a2,
+                end: a3,
+            },
+            PathSegment::LineTo(b0),
+            PathSegment::CurveTo {
+                cp1: b1,
+                cp2: b2,
+                end: b3,
+            },
+            PathSegment::ClosePath,
+        ]
+    }
+
+    /// Endpoint moved to either side across the flow direction, in link coordinates
+    fn offset(&self, p: Point, half: f64) -> (Point, Point) {
+        match self.orientation {
+            LinkOrientation::Horizontal => (Point::new(p.x, p.y - half), Point::new(p.x, p.y + half)),
+            LinkOrientation::Vertical => (Point::new(p.x - half, p.y), Point::new(p.x + half, p.y)),
+            LinkOrientation::Radial => {
+                let da = if p.y > 0.0 { half / p.y } else { 0.0 };
+                (Point::new(p.x - da, p.y), Point::new(p.x + da, p.y))
+            }
+        }
+    }
+}
+
+/// Screen position of `(angle, radius)`, 0 at 12 o'clock and clockwise
+fn radial_point(angle: f64, radius: f64) -> Point {
+    let a = angle - FRAC_PI_2;
+    Point::new(radius * a.cos(), radius * a.sin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn close(a: Point, b: Point) -> bool {
+        a.distance(&b) < 1e-9
+    }
+
+    #[test]
+    fn test_vertical_link() {
+        let path = LinkGenerator::vertical().generate(Point::new(10.0, 0.0), Point::new(30.0, 100.0));
+        assert_eq!(
+            path,
+            vec![
+                PathSegment::move_to(10.0, 0.0),
+                PathSegment::curve_to(10.0, 50.0, 30.0, 50.0, 30.0, 100.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_radial_link() {
+        // From the top at radius 10 to 3 o'clock at radius 30
+        let link = LinkGenerator::radial();
+        let [p0, p1, p2, p3] = link.control_points(Point::new(0.0, 10.0), Point::new(PI / 2.0, 30.0));
+        assert!(close(p0, Point::new(0.0, -10.0)));
+        assert!(close(p1, Point::new(0.0, -20.0)));
+        assert!(close(p2, Point::new(20.0, 0.0)));
+        assert!(close(p3, Point::new(30.0, 0.0)));
+    }
+
+    #[test]
+    fn test_ribbon_band() {
+        let ribbon = LinkGenerator::horizontal().ribbon(
+            Point::new(0.0, 10.0),
+            Point::new(100.0, 50.0),
+            4.0,
+            8.0,
+        );
+        assert_eq!(ribbon.len(), 5);
+        assert_eq!(ribbon[0], PathSegment::move_to(0.0, 8.0));
+        assert_eq!(ribbon[1], PathSegment::curve_to(50.0, 8.0, 50.0, 46.0, 100.0, 46.0));
+        assert_eq!(ribbon[2], PathSegment::line_to(100.0, 54.0));
+        assert_eq!(ribbon[3], PathSegment::curve_to(50.0, 54.0, 50.0, 12.0, 0.0, 12.0));
+        assert_eq!(ribbon[4], PathSegment::ClosePath);
+
+        // Radial widths are arc lengths at each radius
+        let radial = LinkGenerator::radial().ribbon(
+            Point::new(0.0, 10.0),
+            Point::new(0.0, 20.0),
+            2.0,
+            2.0,
+        );
+        let start = radial[0].end_point().unwrap();
+        assert!(close(start, radial_point(-0.1, 10.0)));
+    }
+}
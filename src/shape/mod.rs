@@ -13,8 +13,14 @@
 //! - [`LineGenerator`]: Generate line paths from data points
 //! - [`AreaGenerator`]: Generate filled area paths
 //! - [`ArcGenerator`]: Generate arc paths for pie/donut charts
-//! - [`PieLayout`]: Compute pie slice angles from values
+//! - [`PieLayout`]: Compute pie slice angles from values, with optional explode offsets
+//! - [`WindroseLayout`]: Bin directional data into stacked polar histogram sectors
 //! - [`StackGenerator`]: Compute stacked layouts for bar/area charts
+//! - [`PathInterpolator`]: Morph between arbitrary paths (e.g. bar to pie)
+//! - [`LinkGenerator`]: Bezier connectors and ribbons for tree and flow diagrams
+//! - [`SymbolGenerator`]: Area-sized scatter and legend markers (circle, cross, star, ...)
+//! - [`BoxPlotGenerator`]: Quartile boxes, IQR whiskers and outliers per category
+//! - [`ViolinGenerator`]: Mirrored kernel density outlines per category
 //!
 //! # Example
 //!
@@ -39,12 +45,27 @@ pub mod path;
 mod line;
 mod area;
 mod arc;
+mod link;
 mod pie;
+mod windrose;
 mod stack;
+mod morph;
+mod symbol;
+mod boxplot;
+mod violin;
 
 pub use path::{Path, PathSegment, Point};
 pub use line::LineGenerator;
 pub use area::AreaGenerator;
 pub use arc::{ArcGenerator, ArcDatum};
+pub use link::{LinkGenerator, LinkOrientation};
 pub use pie::{PieLayout, PieSlice, PieSort};
-pub use stack::{StackGenerator, StackedSeries, StackPoint, StackOrder, StackOffset};
+pub use windrose::{Windrose, WindroseLayout, WindroseScale, WindroseSegment};
+pub use stack::{
+    StackGenerator, StackedSeries, StackPoint, StackOrder, StackOffset,
+    PercentLabels, PercentSegment,
+};
+pub use morph::{PathInterpolator, interpolate_path};
+pub use symbol::{Symbol, SymbolGenerator, SymbolType};
+pub use boxplot::{BoxPlot, BoxPlotGenerator, BoxStats, DistributionOrientation};
+pub use violin::{Violin, ViolinGenerator, ViolinScale, kernel_density, silverman_bandwidth};
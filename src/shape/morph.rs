@@ -0,0 +1,502 @@
+//! Shape morphing between arbitrary paths
+//!
+//! Interpolates between two [`Path`]s that may have different segment counts
+//! and segment types (e.g. a bar rectangle and a pie wedge). Both paths are
+//! flattened into polylines, matched subpath by subpath, and resampled so that
+//! every vertex of either shape has a corresponding point on the other at the
+//! same fraction of arc length.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::shape::{Path, PathInterpolator};
+//!
+//! let mut square = Path::new();
+//! square.move_to(0.0, 0.0).line_to(10.0, 0.0).line_to(10.0, 10.0).line_to(0.0, 10.0).close();
+//!
+//! let mut triangle = Path::new();
+//! triangle.move_to(0.0, 0.0).line_to(20.0, 0.0).line_to(10.0, 20.0).close();
+//!
+//! let morph = PathInterpolator::new(&square, &triangle);
+//! let halfway = morph.at(0.5);
+//! assert!(!halfway.is_empty());
+//! ```
+
+use std::f64::consts::PI;
+
+use super::path::{arc_sweep, Path, PathSegment, Point};
+
+/// Steps used to flatten each Bezier curve segment
+const CURVE_STEPS: usize = 16;
+
+/// Maximum angle per step when flattening arcs
+const ARC_STEP: f64 = PI / 32.0;
+
+/// Samples used to find the best starting point of closed rings
+const ALIGN_SAMPLES: usize = 32;
+
+/// A flattened subpath
+#[derive(Clone, Debug)]
+struct Ring {
+    points: Vec<Point>,
+    closed: bool,
+}
+
+impl Ring {
+    /// Cumulative arc length at each vertex, and the total length
+    fn lengths(&self) -> (Vec<f64>, f64) {
+        let mut cumulative = Vec::with_capacity(self.points.len());
+        let mut total = 0.0;
+        for (i, p) in self.points.iter().enumerate() {
+            if i > 0 {
+                total += self.points[i - 1].distance(p);
+            }
+            cumulative.push(total);
+        }
+        if self.closed && self.points.len() > 1 {
+            total += self.points[self.points.len() - 1].distance(&self.points[0]);
+        }
+        (cumulative, total)
+    }
+
+    /// Arc-length fraction of each vertex
+    fn vertex_fractions(&self) -> Vec<f64> {
+        let (cumulative, total) = self.lengths();
+        if total <= 0.0 {
+            return vec![0.0];
+        }
+        cumulative.iter().map(|l| l / total).collect()
+    }
+
+    /// Point at an arc-length fraction (wrapping for closed rings)
+    fn point_at(&self, u: f64) -> Point {
+        let n = self.points.len();
+        if n == 1 {
+            return self.points[0];
+        }
+        let (cumulative, total) = self.lengths();
+        if total <= 0.0 {
+            return self.points[0];
+        }
+        let u = if self.closed { u.rem_euclid(1.0) } else { u.clamp(0.0, 1.0) };
+        let target = u * total;
+
+        let edges = if self.closed { n } else { n - 1 };
+        for i in 0..edges {
+            let a = self.points[i];
+            let b = self.points[(i + 1) % n];
+            let start = cumulative[i];
+            let end = if i + 1 < n { cumulative[i + 1] } else { total };
+            if target <= end || i + 1 == edges {
+                let len = end - start;
+                let f = if len > 0.0 { ((target - start) / len).clamp(0.0, 1.0) } else { 0.0 };
+                return a.lerp(&b, f);
+            }
+        }
+        self.points[n - 1]
+    }
+
+    /// Twice the signed area (closed rings only)
+    fn signed_area(&self) -> f64 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| {
+                let a = self.points[i];
+                let b = self.points[(i + 1) % n];
+                a.x * b.y - b.x * a.y
+            })
+            .sum()
+    }
+
+    fn centroid(&self) -> Point {
+        let n = self.points.len().max(1) as f64;
+        let sum = self.points.iter().fold(Point::zero(), |acc, p| acc.add(p));
+        sum.scale(1.0 / n)
+    }
+
+    /// Rotate a closed ring so it starts at the given vertex
+    fn rotated(&self, start: usize) -> Ring {
+        let mut points = self.points[start..].to_vec();
+        points.extend_from_slice(&self.points[..start]);
+        Ring { points, closed: self.closed }
+    }
+
+    /// Convert a closed ring to an open one that returns to its start
+    fn opened(&self) -> Ring {
+        let mut points = self.points.clone();
+        if self.closed && points.len() > 1 {
+            points.push(points[0]);
+        }
+        Ring { points, closed: false }
+    }
+}
+
+/// Flatten a path into rings of points
+fn flatten(path: &Path) -> Vec<Ring> {
+    let mut rings = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    // Start of the last subpath; drawing after ClosePath continues from here
+    let mut subpath_start: Option<Point> = None;
+
+    for segment in path.iter() {
+        if current.is_empty() && !matches!(segment, PathSegment::MoveTo(_)) {
+            if let Some(p) = subpath_start {
+                current.push(p);
+            }
+        }
+        let last = current.last().copied();
+
+        match segment {
+            PathSegment::MoveTo(p) => {
+                if !current.is_empty() {
+                    rings.push(Ring { points: std::mem::take(&mut current), closed: false });
+                }
+                subpath_start = Some(*p);
+                current.push(*p);
+            }
+            PathSegment::LineTo(p) => push_point(&mut current, *p),
+            PathSegment::QuadTo { cp, end } => {
+                let p0 = last.unwrap_or(*cp);
+                for i in 1..=CURVE_STEPS {
+                    let t = i as f64 / CURVE_STEPS as f64;
+                    let a = p0.lerp(cp, t);
+                    let b = cp.lerp(end, t);
+                    push_point(&mut current, a.lerp(&b, t));
+                }
+            }
+            PathSegment::CurveTo { cp1, cp2, end } => {
+                let p0 = last.unwrap_or(*cp1);
+                for i in 1..=CURVE_STEPS {
+                    let t = i as f64 / CURVE_STEPS as f64;
+                    let a = p0.lerp(cp1, t);
+                    let b = cp1.lerp(cp2, t);
+                    let c = cp2.lerp(end, t);
+                    let ab = a.lerp(&b, t);
+                    let bc = b.lerp(&c, t);
+                    push_point(&mut current, ab.lerp(&bc, t));
+                }
+            }
+            PathSegment::ArcTo {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                counterclockwise,
+            } => {
+                let sweep = arc_sweep(*start_angle, *end_angle, *counterclockwise);
+                let steps = ((sweep.abs() / ARC_STEP).ceil() as usize).max(1);
+                for i in 0..=steps {
+                    let angle = start_angle + sweep * i as f64 / steps as f64;
+                    push_point(
+                        &mut current,
+                        Point::new(center.x + radius * angle.cos(), center.y + radius * angle.sin()),
+                    );
+                }
+            }
+            PathSegment::ClosePath => {
+                // The closing edge is implicit, so drop a repeated start point
+                if current.len() > 1 && current.first() == current.last() {
+                    current.pop();
+                }
+                if !current.is_empty() {
+                    rings.push(Ring { points: std::mem::take(&mut current), closed: true });
+                }
+            }
+        }
+    }
+    if current.len() > 1 || (current.len() == 1 && rings.is_empty()) {
+        rings.push(Ring { points: current, closed: false });
+    }
+    rings
+}
+
+/// Append a point, skipping exact duplicates of the previous one
+fn push_point(points: &mut Vec<Point>, p: Point) {
+    if points.last() != Some(&p) {
+        points.push(p);
+    }
+}
+
+/// Sorted union of fractions, deduplicated
+fn merge_fractions(a: &[f64], b: &[f64], include_end: bool) -> Vec<f64> {
+    let mut all: Vec<f64> = a.iter().chain(b.iter()).copied().collect();
+    if include_end {
+        all.push(1.0);
+    }
+    all.sort_by(f64::total_cmp);
+    all.dedup_by(|x, y| (*x - *y).abs() < 1e-9);
+    all
+}
+
+/// Rotate `to` so that it best lines up with `from` (both closed)
+fn align_closed(from: &Ring, to: &Ring) -> Ring {
+    let mut to = to.clone();
+    if to.points.len() < 2 {
+        return to;
+    }
+    // Match winding direction
+    if from.points.len() > 2 && from.signed_area() * to.signed_area() < 0.0 {
+        to.points.reverse();
+    }
+
+    let reference: Vec<Point> = (0..ALIGN_SAMPLES)
+        .map(|i| from.point_at(i as f64 / ALIGN_SAMPLES as f64))
+        .collect();
+
+    let best = (0..to.points.len())
+        .map(|start| {
+            let candidate = to.rotated(start);
+            let cost: f64 = reference
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let q = candidate.point_at(i as f64 / ALIGN_SAMPLES as f64);
+                    let (dx, dy) = (p.x - q.x, p.y - q.y);
+                    dx * dx + dy * dy
+                })
+                .sum();
+            (start, cost)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(start, _)| start)
+        .unwrap_or(0);
+
+    to.rotated(best)
+}
+
+/// Corresponding point lists for one pair of subpaths
+#[derive(Clone, Debug)]
+struct RingPair {
+    from: Vec<Point>,
+    to: Vec<Point>,
+    closed: bool,
+}
+
+impl RingPair {
+    fn new(from: &Ring, to: &Ring) -> Self {
+        let (from, to) = if from.closed && to.closed {
+            let aligned = align_closed(from, to);
+            (from.clone(), aligned)
+        } else {
+            (from.opened(), to.opened())
+        };
+        let closed = from.closed && to.closed;
+
+        let params = merge_fractions(&from.vertex_fractions(), &to.vertex_fractions(), !closed);
+        Self {
+            from: params.iter().map(|&u| from.point_at(u)).collect(),
+            to: params.iter().map(|&u| to.point_at(u)).collect(),
+            closed,
+        }
+    }
+
+    fn write(&self, path: &mut Path, t: f64) {
+        for (i, (a, b)) in self.from.iter().zip(&self.to).enumerate() {
+            let p = a.lerp(b, t);
+            if i == 0 {
+                path.push(PathSegment::MoveTo(p));
+            } else {
+                path.push(PathSegment::LineTo(p));
+            }
+        }
+        if self.closed {
+            path.push(PathSegment::ClosePath);
+        }
+    }
+}
+
+/// Interpolator between two arbitrary paths
+///
+/// Subpaths are matched by order. A subpath without a counterpart grows from
+/// (or shrinks to) its own centroid. Closed subpaths are only emitted as
+/// closed when both ends are closed; a closed ring paired with an open one
+/// is traversed back to its start point so the shape stays visually the
+/// same at the endpoints.
+#[derive(Clone, Debug)]
+pub struct PathInterpolator {
+    pairs: Vec<RingPair>,
+    from: Path,
+    to: Path,
+}
+
+impl PathInterpolator {
+    /// Prepare an interpolation from one path to another
+    pub fn new(from: &Path, to: &Path) -> Self {
+        let from_rings = flatten(from);
+        let to_rings = flatten(to);
+        let count = from_rings.len().max(to_rings.len());
+
+        let pairs = (0..count)
+            .map(|i| match (from_rings.get(i), to_rings.get(i)) {
+                (Some(a), Some(b)) => RingPair::new(a, b),
+                (Some(a), None) => RingPair::new(a, &collapsed(a)),
+                (None, Some(b)) => RingPair::new(&collapsed(b), b),
+                (None, None) => unreachable!(),
+            })
+            .collect();
+
+        Self {
+            pairs,
+            from: from.clone(),
+            to: to.clone(),
+        }
+    }
+
+    /// Number of points per interpolated subpath
+    pub fn point_counts(&self) -> Vec<usize> {
+        self.pairs.iter().map(|p| p.from.len()).collect()
+    }
+
+    /// Interpolated path at `t` (0 = from, 1 = to)
+    ///
+    /// Returns the original paths exactly at `t <= 0` and `t >= 1`;
+    /// intermediate paths are polylines.
+    pub fn at(&self, t: f64) -> Path {
+        if t <= 0.0 {
+            return self.from.clone();
+        }
+        if t >= 1.0 {
+            return self.to.clone();
+        }
+        let mut path = Path::new();
+        for pair in &self.pairs {
+            pair.write(&mut path, t);
+        }
+        path
+    }
+}
+
+/// A ring collapsed to its centroid, with the same vertex count
+fn collapsed(ring: &Ring) -> Ring {
+    let c = ring.centroid();
+    Ring {
+        points: vec![c; ring.points.len()],
+        closed: ring.closed,
+    }
+}
+
+/// Interpolate between two paths at `t`
+///
+/// Convenience for one-off use; prefer [`PathInterpolator`] when rendering
+/// many frames of the same morph.
+pub fn interpolate_path(from: &Path, to: &Path, t: f64) -> Path {
+    PathInterpolator::new(from, to).at(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Path {
+        let mut p = Path::new();
+        p.move_to(x0, y0).line_to(x1, y0).line_to(x1, y1).line_to(x0, y1).close();
+        p
+    }
+
+    fn points(path: &Path) -> Vec<Point> {
+        path.iter().filter_map(|s| s.end_point()).collect()
+    }
+
+    #[test]
+    fn test_endpoints_exact() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        let b = rect(5.0, 5.0, 20.0, 30.0);
+        let morph = PathInterpolator::new(&a, &b);
+        assert_eq!(morph.at(0.0).segments, a.segments);
+        assert_eq!(morph.at(1.0).segments, b.segments);
+    }
+
+    #[test]
+    fn test_same_topology_is_linear() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        let b = rect(10.0, 10.0, 20.0, 20.0);
+        let mid = PathInterpolator::new(&a, &b).at(0.5);
+        let pts = points(&mid);
+        assert_eq!(pts.len(), 4);
+        assert!(pts.contains(&Point::new(5.0, 5.0)));
+        assert!(pts.contains(&Point::new(15.0, 15.0)));
+        assert_eq!(mid.segments.last(), Some(&PathSegment::ClosePath));
+    }
+
+    #[test]
+    fn test_vertices_of_both_shapes_preserved() {
+        let square = rect(0.0, 0.0, 10.0, 10.0);
+        let mut tri = Path::new();
+        tri.move_to(0.0, 0.0).line_to(10.0, 0.0).line_to(5.0, 10.0).close();
+
+        let morph = PathInterpolator::new(&square, &tri);
+        // Near t = 0 the path traces the square (4 corners + triangle vertices)
+        let near_start = points(&morph.at(1e-9));
+        for corner in points(&square) {
+            assert!(near_start.iter().any(|p| p.distance(&corner) < 1e-6));
+        }
+        let near_end = points(&morph.at(1.0 - 1e-9));
+        for corner in points(&tri) {
+            assert!(near_end.iter().any(|p| p.distance(&corner) < 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_open_paths() {
+        let mut a = Path::new();
+        a.move_to(0.0, 0.0).line_to(10.0, 0.0);
+        let mut b = Path::new();
+        b.move_to(0.0, 10.0).line_to(5.0, 15.0).line_to(10.0, 10.0);
+
+        let mid = interpolate_path(&a, &b, 0.5);
+        let pts = points(&mid);
+        assert_eq!(pts.first(), Some(&Point::new(0.0, 5.0)));
+        assert_eq!(pts.last(), Some(&Point::new(10.0, 5.0)));
+        assert_ne!(mid.segments.last(), Some(&PathSegment::ClosePath));
+    }
+
+    #[test]
+    fn test_closed_to_open_returns_to_start() {
+        let closed = rect(0.0, 0.0, 10.0, 10.0);
+        let mut open = Path::new();
+        open.move_to(0.0, 0.0).line_to(10.0, 0.0);
+
+        let morph = PathInterpolator::new(&closed, &open);
+        let pts = points(&morph.at(1e-9));
+        let first = pts.first().unwrap();
+        let last = pts.last().unwrap();
+        assert!(first.distance(last) < 1e-6);
+        assert_ne!(morph.at(0.5).segments.last(), Some(&PathSegment::ClosePath));
+    }
+
+    #[test]
+    fn test_curves_and_arcs_flattened() {
+        let mut wedge = Path::new();
+        wedge.move_to(0.0, 0.0);
+        wedge.push(PathSegment::arc_to(0.0, 0.0, 10.0, 0.0, PI / 2.0, false));
+        wedge.close();
+
+        let bar = rect(0.0, 0.0, 10.0, 10.0);
+        let morph = PathInterpolator::new(&bar, &wedge);
+        assert!(morph.point_counts()[0] > 4);
+
+        let near_end = points(&morph.at(1.0 - 1e-9));
+        assert!(near_end.iter().all(|p| p.distance(&Point::zero()) <= 10.0 + 1e-6));
+    }
+
+    #[test]
+    fn test_unmatched_subpaths_collapse_to_centroid() {
+        let mut two = rect(0.0, 0.0, 10.0, 10.0);
+        two.extend(rect(20.0, 0.0, 30.0, 10.0));
+        let one = rect(0.0, 0.0, 10.0, 10.0);
+
+        let morph = PathInterpolator::new(&two, &one);
+        assert_eq!(morph.point_counts().len(), 2);
+        let nearly_done = morph.at(1.0 - 1e-9);
+        let second: Vec<Point> = points(&nearly_done).into_iter().skip(4).collect();
+        assert!(second.iter().all(|p| p.distance(&Point::new(25.0, 5.0)) < 1e-6));
+    }
+
+    #[test]
+    fn test_arc_sweep() {
+        assert!((arc_sweep(0.0, PI, false) - PI).abs() < 1e-12);
+        assert!((arc_sweep(PI, 0.0, false) - PI).abs() < 1e-12);
+        assert!((arc_sweep(-PI / 2.0, PI / 2.0, true) + PI).abs() < 1e-12);
+        assert!((arc_sweep(0.0, 3.0 * PI, false) - 2.0 * PI).abs() < 1e-12);
+    }
+}
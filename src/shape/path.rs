@@ -177,6 +177,54 @@ impl PathSegment {
             Self::ClosePath => None,
         }
     }
+
+    /// Return this segment moved by (dx, dy)
+    pub fn translated(&self, dx: f64, dy: f64) -> Self {
+        let shift = |p: &Point| Point::new(p.x + dx, p.y + dy);
+        match self {
+            Self::MoveTo(p) => Self::MoveTo(shift(p)),
+            Self::LineTo(p) => Self::LineTo(shift(p)),
+            Self::QuadTo { cp, end } => Self::QuadTo {
+                cp: shift(cp),
+                end: shift(end),
+            },
+            Self::CurveTo { cp1, cp2, end } => Self::CurveTo {
+                cp1: shift(cp1),
+                cp2: shift(cp2),
+                end: shift(end),
+            },
+            Self::ArcTo {
+                center,
+                radius,
+                start_angle,
+                end_angle,
+                counterclockwise,
+            } => Self::ArcTo {
+                center: shift(center),
+                radius: *radius,
+                start_angle: *start_angle,
+                end_angle: *end_angle,
+                counterclockwise: *counterclockwise,
+            },
+            Self::ClosePath => Self::ClosePath,
+        }
+    }
+}
+
+/// Signed sweep of an arc, following canvas `arc()` semantics
+pub(crate) fn arc_sweep(start: f64, end: f64, counterclockwise: bool) -> f64 {
+    let tau = 2.0 * std::f64::consts::PI;
+    let delta = end - start;
+    if delta.abs() >= tau {
+        return if counterclockwise { -tau } else { tau };
+    }
+    if counterclockwise {
+        if delta > 0.0 { delta - tau } else { delta }
+    } else if delta < 0.0 {
+        delta + tau
+    } else {
+        delta
+    }
 }
 
 /// A complete path consisting of multiple segments
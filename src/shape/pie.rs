@@ -5,6 +5,9 @@
 use std::cmp::Ordering;
 use std::f64::consts::TAU;
 
+use super::arc::{explode_direction, interpolate_angles, ArcGenerator};
+use super::path::Point;
+
 /// A computed pie slice with angle information
 #[derive(Clone, Debug)]
 pub struct PieSlice<T> {
@@ -20,6 +23,8 @@ pub struct PieSlice<T> {
     pub end_angle: f64,
     /// Padding angle between this slice and adjacent slices
     pub pad_angle: f64,
+    /// Distance the slice is pushed outward along its mid angle
+    pub explode: f64,
 }
 
 impl<T> PieSlice<T> {
@@ -27,6 +32,77 @@ impl<T> PieSlice<T> {
     pub fn angle(&self) -> f64 {
         self.end_angle - self.start_angle
     }
+
+    /// Angle halfway between the start and end angle
+    pub fn mid_angle(&self) -> f64 {
+        (self.start_angle + self.end_angle) / 2.0
+    }
+
+    /// Unit vector pointing from the pie center through the mid angle
+    pub fn explode_direction(&self) -> Point {
+        explode_direction(self.start_angle, self.end_angle)
+    }
+
+    /// Translation applied to the slice by its explode offset
+    pub fn explode_offset(&self) -> Point {
+        let dir = self.explode_direction();
+        Point::new(dir.x * self.explode, dir.y * self.explode)
+    }
+
+    /// Label anchor for the slice, including its explode offset
+    pub fn centroid(&self, inner_radius: f64, outer_radius: f64) -> Point {
+        self.arc(inner_radius, outer_radius).centroid()
+    }
+
+    /// Arc generator for this slice, carrying its angles, padding and explode offset
+    pub fn arc(&self, inner_radius: f64, outer_radius: f64) -> ArcGenerator {
+        ArcGenerator::new()
+            .inner_radius(inner_radius)
+            .outer_radius(outer_radius)
+            .start_angle(self.start_angle)
+            .end_angle(self.end_angle)
+            .pad_angle(self.pad_angle)
+            .explode(self.explode)
+    }
+}
+
+impl<T: Clone> PieSlice<T> {
+    /// Intermediate slice between this one and `other` at `t` in `[0, 1]`
+    ///
+    /// Angles take the short way around the circle, so a slice that crosses
+    /// 12 o'clock between two layouts does not sweep backwards through the
+    /// pie. Value, padding and explode offset are interpolated linearly; the
+    /// data and index are taken from `other`, the slice being animated to.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::shape::PieLayout;
+    ///
+    /// let old = PieLayout::new().compute(&[1.0, 1.0]);
+    /// let new = PieLayout::new().compute(&[3.0, 1.0]);
+    ///
+    /// let mid = old[0].interpolate(&new[0], 0.5);
+    /// assert_eq!(mid.value, 2.0);
+    /// assert!((mid.end_angle - 0.625 * std::f64::consts::TAU).abs() < 1e-9);
+    /// ```
+    pub fn interpolate(&self, other: &PieSlice<T>, t: f64) -> PieSlice<T> {
+        let (start_angle, end_angle) = interpolate_angles(
+            (self.start_angle, self.end_angle),
+            (other.start_angle, other.end_angle),
+            t,
+        );
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        PieSlice {
+            data: other.data.clone(),
+            value: lerp(self.value, other.value),
+            index: other.index,
+            start_angle,
+            end_angle,
+            pad_angle: lerp(self.pad_angle, other.pad_angle),
+            explode: lerp(self.explode, other.explode),
+        }
+    }
 }
 
 /// Sort order for pie slices
@@ -71,6 +147,8 @@ pub struct PieLayout {
     pad_angle: f64,
     /// Sort order
     sort: PieSort,
+    /// Explode offsets by original data index
+    explode: Vec<f64>,
 }
 
 impl Default for PieLayout {
@@ -87,6 +165,7 @@ impl PieLayout {
             end_angle: TAU,
             pad_angle: 0.0,
             sort: PieSort::None,
+            explode: Vec::new(),
         }
     }
 
@@ -114,6 +193,21 @@ impl PieLayout {
         self
     }
 
+    /// Set explode offsets by original data index (missing entries are 0)
+    pub fn explode(mut self, offsets: impl IntoIterator<Item = f64>) -> Self {
+        self.explode = offsets.into_iter().map(|d| d.max(0.0)).collect();
+        self
+    }
+
+    /// Explode a single slice by original data index, e.g. the hovered one
+    pub fn explode_index(mut self, index: usize, distance: f64) -> Self {
+        if self.explode.len() <= index {
+            self.explode.resize(index + 1, 0.0);
+        }
+        self.explode[index] = distance.max(0.0);
+        self
+    }
+
     /// Compute pie slices from values
     pub fn compute(&self, values: &[f64]) -> Vec<PieSlice<f64>> {
         self.compute_with_data(values, |&v| v)
@@ -187,6 +281,7 @@ impl PieLayout {
                 start_angle: angle,
                 end_angle: angle + slice_angle,
                 pad_angle: self.pad_angle,
+                explode: self.explode.get(index).copied().unwrap_or(0.0),
             });
 
             angle += slice_angle + self.pad_angle;
@@ -214,6 +309,26 @@ impl PieLayout {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pie_slice_interpolate() {
+        let old = PieLayout::new().start_angle(-1.0).end_angle(TAU - 1.0).compute(&[1.0, 3.0]);
+        let new = PieLayout::new().explode([4.0]).compute(&[1.0, 1.0]);
+
+        // Slice 0 starts at -1 and moves to 0: forward by 1, not back by TAU - 1
+        let mid = old[0].interpolate(&new[0], 0.5);
+        assert!((mid.start_angle + 0.5).abs() < 1e-9);
+        let old_span = old[0].angle();
+        let new_span = new[0].angle();
+        assert!((mid.angle() - (old_span + new_span) / 2.0).abs() < 1e-9);
+        assert!((mid.value - 1.0).abs() < 1e-12);
+        assert!((mid.explode - 2.0).abs() < 1e-12);
+
+        let end = old[1].interpolate(&new[1], 1.0);
+        assert_eq!(end.start_angle, new[1].start_angle);
+        assert_eq!(end.end_angle, new[1].end_angle);
+        assert_eq!(end.data, new[1].data);
+    }
+
     #[test]
     fn test_pie_layout_basic() {
         let values = vec![1.0, 1.0, 1.0, 1.0];
@@ -326,6 +441,32 @@ mod tests {
         assert_eq!(slices[2].data.name, "C");
     }
 
+    #[test]
+    fn test_pie_explode_offsets() {
+        let pie = PieLayout::new()
+            .sort(PieSort::ValueDescending)
+            .explode_index(1, 10.0);
+        let slices = pie.compute(&[1.0, 1.0, 2.0]);
+
+        // The exploded slice keeps its data index after sorting
+        let exploded = slices.iter().find(|s| s.index == 1).unwrap();
+        assert_eq!(exploded.explode, 10.0);
+        assert!(slices.iter().filter(|s| s.index != 1).all(|s| s.explode == 0.0));
+
+        // Index 1 spans 3/4 to 1 turn: mid angle points to 10:30
+        let dir = exploded.explode_direction();
+        let offset = exploded.explode_offset();
+        assert!((dir.x.hypot(dir.y) - 1.0).abs() < 1e-12);
+        assert!(offset.x < 0.0 && offset.y < 0.0);
+        assert!((offset.x.hypot(offset.y) - 10.0).abs() < 1e-9);
+
+        let plain = PieSlice { explode: 0.0, ..exploded.clone() };
+        let c0 = plain.centroid(0.0, 100.0);
+        let c1 = exploded.centroid(0.0, 100.0);
+        assert!((c1.x - c0.x - offset.x).abs() < 1e-9);
+        assert!((c1.y - c0.y - offset.y).abs() < 1e-9);
+    }
+
     #[test]
     fn test_pie_half() {
         let values = vec![1.0, 1.0];
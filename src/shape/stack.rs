@@ -2,6 +2,7 @@
 //!
 //! Computes stacked layouts for bar charts, area charts, and stream graphs.
 
+use crate::color::{readable_text_color, Rgba};
 use crate::data::ChartData;
 
 /// Stack ordering method
@@ -14,7 +15,8 @@ pub enum StackOrder {
     Ascending,
     /// Sort by sum of values descending
     Descending,
-    /// Sort so smallest series are in the middle
+    /// Series peaking earliest in the middle, later peaks alternating
+    /// outward (d3's `stackOrderInsideOut`, for streamgraphs)
     InsideOut,
     /// Reverse the current order
     Reverse,
@@ -30,9 +32,10 @@ pub enum StackOffset {
     Expand,
     /// Center around zero (diverging stacks)
     Diverging,
-    /// Center the baseline to minimize weighted wiggle
+    /// Center the stack around zero (d3's `stackOffsetSilhouette`)
     Silhouette,
-    /// Streamgraph wiggle minimization (Stacked Graph algorithm)
+    /// Shift the baseline to minimize the weighted change in slope
+    /// (d3's `stackOffsetWiggle`, from Byron & Wattenberg's streamgraphs)
     Wiggle,
 }
 
@@ -43,12 +46,14 @@ pub struct StackPoint {
     pub y0: f64,
     /// Upper bound (y1)
     pub y1: f64,
+    /// Whether the value is missing (stacked as zero height)
+    pub missing: bool,
 }
 
 impl StackPoint {
     /// Create a new stack point
     pub fn new(y0: f64, y1: f64) -> Self {
-        Self { y0, y1 }
+        Self { y0, y1, missing: false }
     }
 
     /// Get the height of this stack segment
@@ -79,6 +84,79 @@ impl StackedSeries {
     }
 }
 
+/// Label options for 100% stacked bars
+///
+/// `bar_length` is the pixel length of a full (100%) bar, used to decide
+/// whether a segment is large enough to hold its label.
+#[derive(Clone, Debug)]
+pub struct PercentLabels {
+    /// Pixel length of a full bar
+    pub bar_length: f64,
+    /// Minimum segment length in pixels for an internal label
+    pub min_label_size: f64,
+    /// Decimal places in percentage labels
+    pub decimals: usize,
+    /// Segment colors by series index (overrides dataset colors)
+    pub colors: Vec<Rgba>,
+}
+
+impl PercentLabels {
+    /// Create label options for bars of the given pixel length
+    pub fn new(bar_length: f64) -> Self {
+        Self {
+            bar_length,
+            min_label_size: 16.0,
+            decimals: 0,
+            colors: Vec::new(),
+        }
+    }
+
+    /// Set the minimum segment length for an internal label
+    pub fn with_min_label_size(mut self, size: f64) -> Self {
+        self.min_label_size = size;
+        self
+    }
+
+    /// Set decimal places in percentage labels
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Set segment colors by series index
+    pub fn with_colors(mut self, colors: Vec<Rgba>) -> Self {
+        self.colors = colors;
+        self
+    }
+}
+
+/// A labelled segment of a 100% stacked bar
+#[derive(Clone, Debug)]
+pub struct PercentSegment {
+    /// Series identifier (label)
+    pub key: String,
+    /// Index of the series in the original data
+    pub series: usize,
+    /// Index of the category (bar)
+    pub category: usize,
+    /// Raw value (negative values count as zero)
+    pub value: f64,
+    /// Share of the bar total in [0, 1]
+    pub fraction: f64,
+    /// Normalized lower bound in [0, 1]
+    pub y0: f64,
+    /// Normalized upper bound in [0, 1]
+    pub y1: f64,
+    /// Normalized label anchor (segment center)
+    pub label_position: f64,
+    /// Percentage label, rounded so each bar's labels sum to 100
+    pub label: String,
+    /// Whether the segment is large enough for an internal label
+    pub fits_label: bool,
+    /// Suggested label color for contrast with the segment fill
+    pub text_color: Option<Rgba>,
+}
+
 /// Stack generator for creating stacked layouts
 ///
 /// # Example
@@ -125,6 +203,23 @@ impl StackGenerator {
         }
     }
 
+    /// Streamgraph preset: inside-out order with the wiggle offset
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::shape::{StackGenerator, StackOffset, StackOrder};
+    ///
+    /// let values = vec![vec![1.0, 2.0, 3.0], vec![3.0, 2.0, 1.0]];
+    /// let layers = StackGenerator::streamgraph().compute_from_values(&values, &[]);
+    /// // The later-peaking series goes on top; the baseline starts at zero
+    /// // and drifts to keep the layers' slopes small
+    /// assert_eq!(layers[1].points[0].y0, 0.0);
+    /// assert_eq!(layers[1].points[2].y0, 1.0);
+    /// ```
+    pub fn streamgraph() -> Self {
+        Self::new().order(StackOrder::InsideOut).offset(StackOffset::Wiggle)
+    }
+
     /// Set the stack order
     pub fn order(mut self, order: StackOrder) -> Self {
         self.order = order;
@@ -164,19 +259,19 @@ impl StackGenerator {
         for i in 0..n_points {
             let mut y0 = 0.0;
             for &series_idx in &order {
-                let y = data.datasets[series_idx]
-                    .data
-                    .get(i)
-                    .map(|p| p.y)
-                    .unwrap_or(0.0);
+                let value = data.datasets[series_idx].value(i);
+                let y = value.unwrap_or(0.0);
 
-                result[series_idx].points[i] = StackPoint::new(y0, y0 + y);
+                result[series_idx].points[i] = StackPoint {
+                    missing: value.is_none(),
+                    ..StackPoint::new(y0, y0 + y)
+                };
                 y0 += y;
             }
         }
 
         // Apply offset
-        self.apply_offset(&mut result, n_points);
+        self.apply_offset(&mut result, &order, n_points);
 
         result
     }
@@ -219,93 +314,141 @@ impl StackGenerator {
         }
 
         // Apply offset
-        self.apply_offset(&mut result, n_points);
+        self.apply_offset(&mut result, &order, n_points);
 
         result
     }
 
-    /// Compute the series order
-    fn compute_order(&self, data: &ChartData) -> Vec<usize> {
-        let n = data.datasets.len();
-        let mut indices: Vec<usize> = (0..n).collect();
+    /// Compute a 100% stacked layout with label placement
+    ///
+    /// Uses the configured order; the offset is always normalized. Segments
+    /// are returned bar by bar, bottom to top. Bars whose values sum to zero
+    /// produce empty segments with `0%` labels.
+    ///
+    /// # Example
+    /// ```
+    /// use makepad_d3::data::{ChartData, Dataset};
+    /// use makepad_d3::shape::{PercentLabels, StackGenerator};
+    ///
+    /// let data = ChartData::new()
+    ///     .with_labels(vec!["2023"])
+    ///     .add_dataset(Dataset::new("Yes").with_data(vec![62.0]).with_hex_color(0x1E3A8A))
+    ///     .add_dataset(Dataset::new("No").with_data(vec![35.0]).with_hex_color(0xFDE68A))
+    ///     .add_dataset(Dataset::new("Unsure").with_data(vec![3.0]));
+    ///
+    /// let segments = StackGenerator::new().compute_percent(&data, &PercentLabels::new(300.0));
+    /// assert_eq!(segments[0].label, "62%");
+    /// assert!(segments[0].fits_label);
+    /// assert!(!segments[2].fits_label); // 3% of 300px is too small
+    /// ```
+    pub fn compute_percent(&self, data: &ChartData, labels: &PercentLabels) -> Vec<PercentSegment> {
+        let n_points = data.len();
+        let order = self.compute_order(data);
+        let scale = 10f64.powi(labels.decimals as i32);
+        let mut segments = Vec::with_capacity(n_points * order.len());
 
-        match self.order {
-            StackOrder::None => {}
-            StackOrder::Ascending => {
-                let sums: Vec<f64> = data
-                    .datasets
-                    .iter()
-                    .map(|d| d.data.iter().map(|p| p.y).sum())
-                    .collect();
-                indices.sort_by(|&a, &b| sums[a].partial_cmp(&sums[b]).unwrap());
-            }
-            StackOrder::Descending => {
-                let sums: Vec<f64> = data
-                    .datasets
-                    .iter()
-                    .map(|d| d.data.iter().map(|p| p.y).sum())
-                    .collect();
-                indices.sort_by(|&a, &b| sums[b].partial_cmp(&sums[a]).unwrap());
-            }
-            StackOrder::InsideOut => {
-                // Sort by sum, then interleave smallest to center
-                let sums: Vec<f64> = data
-                    .datasets
-                    .iter()
-                    .map(|d| d.data.iter().map(|p| p.y).sum())
-                    .collect();
-                indices.sort_by(|&a, &b| sums[b].partial_cmp(&sums[a]).unwrap());
+        for category in 0..n_points {
+            let values: Vec<f64> = order
+                .iter()
+                .map(|&s| {
+                    data.datasets[s].value(category).map_or(0.0, |y| y.max(0.0))
+                })
+                .collect();
+            let total: f64 = values.iter().sum();
+            let fractions: Vec<f64> = values
+                .iter()
+                .map(|v| if total > 0.0 { v / total } else { 0.0 })
+                .collect();
+            let rounded = if total > 0.0 {
+                round_to_total(&fractions, 100.0 * scale)
+            } else {
+                vec![0.0; fractions.len()]
+            };
 
-                let mut new_order = Vec::with_capacity(n);
-                let mut top = true;
-                for idx in indices {
-                    if top {
-                        new_order.push(idx);
-                    } else {
-                        new_order.insert(0, idx);
-                    }
-                    top = !top;
-                }
-                indices = new_order;
-            }
-            StackOrder::Reverse => {
-                indices.reverse();
+            let mut y0 = 0.0;
+            for (k, &s) in order.iter().enumerate() {
+                let y1 = y0 + fractions[k];
+                let fill = labels.colors.get(s).copied().or_else(|| {
+                    data.datasets[s]
+                        .background_color
+                        .map(|c| Rgba::new(c.r, c.g, c.b, c.a))
+                });
+                segments.push(PercentSegment {
+                    key: data.datasets[s].label.clone(),
+                    series: s,
+                    category,
+                    value: values[k],
+                    fraction: fractions[k],
+                    y0,
+                    y1,
+                    label_position: (y0 + y1) / 2.0,
+                    label: format!("{:.*}%", labels.decimals, rounded[k] / scale),
+                    fits_label: fractions[k] > 0.0
+                        && fractions[k] * labels.bar_length >= labels.min_label_size,
+                    text_color: fill.as_ref().map(readable_text_color),
+                });
+                y0 = y1;
             }
         }
 
-        indices
+        segments
+    }
+
+    /// Compute the series order
+    fn compute_order(&self, data: &ChartData) -> Vec<usize> {
+        let values: Vec<Vec<f64>> = data
+            .datasets
+            .iter()
+            .map(|d| d.values().into_iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+            .collect();
+        self.compute_order_from_values(&values)
     }
 
-    /// Compute the series order from raw values
+    /// Compute the series order from raw values (NaN = missing)
     fn compute_order_from_values(&self, values: &[Vec<f64>]) -> Vec<usize> {
         let n = values.len();
         let mut indices: Vec<usize> = (0..n).collect();
+        let sums: Vec<f64> = values.iter().map(|v| v.iter().filter(|x| !x.is_nan()).sum()).collect();
 
         match self.order {
             StackOrder::None => {}
             StackOrder::Ascending => {
-                let sums: Vec<f64> = values.iter().map(|v| v.iter().sum()).collect();
-                indices.sort_by(|&a, &b| sums[a].partial_cmp(&sums[b]).unwrap());
+                indices.sort_by(|&a, &b| sums[a].total_cmp(&sums[b]));
             }
             StackOrder::Descending => {
-                let sums: Vec<f64> = values.iter().map(|v| v.iter().sum()).collect();
-                indices.sort_by(|&a, &b| sums[b].partial_cmp(&sums[a]).unwrap());
+                indices.sort_by(|&a, &b| sums[b].total_cmp(&sums[a]));
             }
             StackOrder::InsideOut => {
-                let sums: Vec<f64> = values.iter().map(|v| v.iter().sum()).collect();
-                indices.sort_by(|&a, &b| sums[b].partial_cmp(&sums[a]).unwrap());
+                // By the index of each series' peak, earliest first
+                let peaks: Vec<usize> = values
+                    .iter()
+                    .map(|v| {
+                        let mut peak = (0, f64::NEG_INFINITY);
+                        for (i, &y) in v.iter().enumerate() {
+                            if y > peak.1 {
+                                peak = (i, y);
+                            }
+                        }
+                        peak.0
+                    })
+                    .collect();
+                indices.sort_by_key(|&i| peaks[i]);
 
-                let mut new_order = Vec::with_capacity(n);
-                let mut top = true;
+                // Deal series to whichever side is lighter so far
+                let (mut top, mut bottom) = (0.0, 0.0);
+                let (mut tops, mut bottoms) = (Vec::new(), Vec::new());
                 for idx in indices {
-                    if top {
-                        new_order.push(idx);
+                    if top < bottom {
+                        top += sums[idx];
+                        tops.push(idx);
                     } else {
-                        new_order.insert(0, idx);
+                        bottom += sums[idx];
+                        bottoms.push(idx);
                     }
-                    top = !top;
                 }
-                indices = new_order;
+                bottoms.reverse();
+                bottoms.extend(tops);
+                indices = bottoms;
             }
             StackOrder::Reverse => {
                 indices.reverse();
@@ -316,7 +459,7 @@ impl StackGenerator {
     }
 
     /// Apply offset to stacked series
-    fn apply_offset(&self, series: &mut [StackedSeries], n_points: usize) {
+    fn apply_offset(&self, series: &mut [StackedSeries], order: &[usize], n_points: usize) {
         match self.offset {
             StackOffset::None => {}
             StackOffset::Expand => {
@@ -329,7 +472,7 @@ impl StackGenerator {
                 self.apply_silhouette_offset(series, n_points);
             }
             StackOffset::Wiggle => {
-                self.apply_wiggle_offset(series, n_points);
+                self.apply_wiggle_offset(series, order, n_points);
             }
         }
     }
@@ -367,58 +510,64 @@ impl StackGenerator {
         }
     }
 
-    /// Center baseline (silhouette)
+    /// Center the stack around zero (silhouette)
     fn apply_silhouette_offset(&self, series: &mut [StackedSeries], n_points: usize) {
         for i in 0..n_points {
-            let total: f64 = series
-                .iter()
-                .map(|s| s.points[i].y1)
-                .max_by(|a, b| a.partial_cmp(b).unwrap())
-                .unwrap_or(0.0);
-
-            let offset = -total / 2.0;
-            for s in series.iter_mut() {
-                s.points[i].y0 += offset;
-                s.points[i].y1 += offset;
-            }
+            let total: f64 = series.iter().map(|s| s.points[i].height()).sum();
+            shift_column(series, i, -total / 2.0);
         }
     }
 
     /// Minimize wiggle (streamgraph)
-    fn apply_wiggle_offset(&self, series: &mut [StackedSeries], n_points: usize) {
-        if series.is_empty() || n_points == 0 {
-            return;
-        }
-
-        let n = series.len();
-
-        for i in 0..n_points {
-            // Calculate weighted centroid offset
-            let mut sum = 0.0;
-            let mut total_weight = 0.0;
-
-            for (j, s) in series.iter().enumerate() {
-                let height = s.points[i].height();
-                let weight = (n - j) as f64;
-                sum += weight * height;
-                total_weight += weight;
+    ///
+    /// Follows d3's `stackOffsetWiggle`: the baseline starts at zero and
+    /// each step moves it against the weighted mean slope of the layers,
+    /// each layer measured at its own center line.
+    fn apply_wiggle_offset(&self, series: &mut [StackedSeries], order: &[usize], n_points: usize) {
+        let mut baseline = 0.0;
+        for j in 1..n_points {
+            let (mut total, mut weighted) = (0.0, 0.0);
+            // Change of the layers below, plus half this layer's own change
+            let mut below = 0.0;
+            for &s in order {
+                let (now, before) = (series[s].points[j].height(), series[s].points[j - 1].height());
+                let slope = below + (now - before) / 2.0;
+                below += now - before;
+                total += now;
+                weighted += slope * now;
             }
-
-            let total: f64 = series.iter().map(|s| s.points[i].height()).sum();
-            let offset = if total_weight > 0.0 && total > 0.0 {
-                -sum / (total_weight * 2.0)
-            } else {
-                0.0
-            };
-
-            for s in series.iter_mut() {
-                s.points[i].y0 += offset;
-                s.points[i].y1 += offset;
+            if total != 0.0 {
+                baseline -= weighted / total;
             }
+            shift_column(series, j, baseline);
         }
     }
 }
 
+/// Move every series' point `i` by `offset`
+fn shift_column(series: &mut [StackedSeries], i: usize, offset: f64) {
+    for s in series.iter_mut() {
+        s.points[i].y0 += offset;
+        s.points[i].y1 += offset;
+    }
+}
+
+/// Round fractions to integers summing to `total` (largest remainder method)
+fn round_to_total(fractions: &[f64], total: f64) -> Vec<f64> {
+    let exact: Vec<f64> = fractions.iter().map(|f| f * total).collect();
+    let mut rounded: Vec<f64> = exact.iter().map(|v| v.floor()).collect();
+    let shortfall = (total - rounded.iter().sum::<f64>()).round().max(0.0) as usize;
+
+    let mut by_remainder: Vec<usize> = (0..exact.len()).collect();
+    by_remainder.sort_by(|&a, &b| {
+        (exact[b] - rounded[b]).total_cmp(&(exact[a] - rounded[a])).then(a.cmp(&b))
+    });
+    for &i in by_remainder.iter().take(shortfall) {
+        rounded[i] += 1.0;
+    }
+    rounded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -432,6 +581,69 @@ mod tests {
             .add_dataset(Dataset::new("Series 3").with_data(vec![5.0, 10.0, 5.0, 10.0]))
     }
 
+    #[test]
+    fn test_compute_percent_labels_sum_to_100() {
+        let data = ChartData::new()
+            .with_labels(vec!["A"])
+            .add_dataset(Dataset::new("x").with_data(vec![1.0]))
+            .add_dataset(Dataset::new("y").with_data(vec![1.0]))
+            .add_dataset(Dataset::new("z").with_data(vec![1.0]));
+        let segments = StackGenerator::new().compute_percent(&data, &PercentLabels::new(300.0));
+        let labels: Vec<&str> = segments.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["34%", "33%", "33%"]);
+        assert!((segments[2].y1 - 1.0).abs() < 1e-12);
+        assert!((segments[1].label_position - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_percent_fit_and_colors() {
+        let data = ChartData::new()
+            .with_labels(vec!["A", "B"])
+            .add_dataset(Dataset::new("Dark").with_data(vec![90.0, 0.0]).with_hex_color(0x111827))
+            .add_dataset(Dataset::new("Light").with_data(vec![10.0, 0.0]).with_hex_color(0xF9FAFB));
+        let options = PercentLabels::new(100.0).with_min_label_size(12.0).with_decimals(1);
+        let segments = StackGenerator::new().compute_percent(&data, &options);
+
+        assert_eq!(segments.len(), 4);
+        assert_eq!(segments[0].label, "90.0%");
+        assert!(segments[0].fits_label);
+        assert!(!segments[1].fits_label);
+        assert_eq!(segments[0].text_color, Some(Rgba::WHITE));
+        assert_eq!(segments[1].text_color, Some(Rgba::BLACK));
+
+        // Empty bar
+        assert_eq!(segments[2].label, "0.0%");
+        assert!(!segments[2].fits_label);
+    }
+
+    #[test]
+    fn test_compute_percent_respects_order() {
+        let data = sample_data();
+        let segments = StackGenerator::new()
+            .order(StackOrder::Reverse)
+            .compute_percent(&data, &PercentLabels::new(200.0));
+        assert_eq!(segments[0].key, "Series 3");
+        assert_eq!(segments[0].y0, 0.0);
+        assert!(segments[0].text_color.is_none());
+    }
+
+    #[test]
+    fn test_stack_missing_values() {
+        let mut gappy = Dataset::new("B").with_data(vec![5.0, 7.0, 9.0]);
+        gappy.set_missing(1);
+        let data = ChartData::new()
+            .with_labels(vec!["a", "b", "c"])
+            .add_dataset(Dataset::new("A").with_data(vec![1.0, 2.0, 3.0]))
+            .add_dataset(gappy);
+
+        let stacked = StackGenerator::new().compute(&data);
+        let b = &stacked[1].points[1];
+        assert!(b.missing);
+        assert_eq!((b.y0, b.y1), (2.0, 2.0));
+        assert!(!stacked[0].points[1].missing);
+        assert_eq!(stacked[1].points[2].y1, 12.0);
+    }
+
     #[test]
     fn test_stack_basic() {
         let data = sample_data();
@@ -510,6 +722,58 @@ mod tests {
         assert_eq!(result[1].key, "B");
     }
 
+    fn columns(series: &StackedSeries) -> Vec<(f64, f64)> {
+        series.points.iter().map(|p| (p.y0, p.y1)).collect()
+    }
+
+    #[test]
+    fn test_stack_wiggle_matches_d3() {
+        // d3.stack().offset(d3.stackOffsetWiggle) on [[1, 2, 3], [3, 2, 1]]
+        let values = vec![vec![1.0, 2.0, 3.0], vec![3.0, 2.0, 1.0]];
+        let result = StackGenerator::new().offset(StackOffset::Wiggle).compute_from_values(&values, &[]);
+        assert_eq!(columns(&result[0]), vec![(0.0, 1.0), (-0.5, 1.5), (-1.0, 2.0)]);
+        assert_eq!(columns(&result[1]), vec![(1.0, 4.0), (1.5, 3.5), (2.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_stack_silhouette_matches_d3() {
+        let values = vec![vec![1.0, 2.0, 3.0], vec![3.0, 2.0, 1.0]];
+        let result = StackGenerator::new().offset(StackOffset::Silhouette).compute_from_values(&values, &[]);
+        assert_eq!(columns(&result[0]), vec![(-2.0, -1.0), (-2.0, 0.0), (-2.0, 1.0)]);
+        assert_eq!(columns(&result[1]), vec![(-1.0, 2.0), (0.0, 2.0), (1.0, 2.0)]);
+    }
+
+    #[test]
+    fn test_stack_inside_out_matches_d3() {
+        // Peaks at 1, 0, 2, 1 with sums 7, 6, 8, 7: d3.stackOrderInsideOut gives [3, 1, 0, 2]
+        let values = vec![
+            vec![1.0, 5.0, 1.0],
+            vec![4.0, 1.0, 1.0],
+            vec![1.0, 1.0, 6.0],
+            vec![2.0, 3.0, 2.0],
+        ];
+        let stack = StackGenerator::new().order(StackOrder::InsideOut);
+        assert_eq!(stack.compute_order_from_values(&values), vec![3, 1, 0, 2]);
+
+        let result = stack.compute_from_values(&values, &[]);
+        assert_eq!(columns(&result[3])[0], (0.0, 2.0));
+        assert_eq!(columns(&result[1])[0], (2.0, 6.0));
+        assert_eq!(columns(&result[0])[0], (6.0, 7.0));
+        assert_eq!(columns(&result[2])[0], (7.0, 8.0));
+    }
+
+    #[test]
+    fn test_streamgraph_preset() {
+        let data = sample_data();
+        let result = StackGenerator::streamgraph().compute(&data);
+        // Layers stay contiguous whatever the baseline
+        for i in 0..4 {
+            let mut spans: Vec<(f64, f64)> = result.iter().map(|s| (s.points[i].y0, s.points[i].y1)).collect();
+            spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+            assert!(spans.windows(2).all(|w| (w[0].1 - w[1].0).abs() < 1e-9));
+        }
+    }
+
     #[test]
     fn test_stack_empty() {
         let data = ChartData::new();
@@ -0,0 +1,307 @@
+//! Symbol generator for scatter plot and legend markers
+//!
+//! Port of `d3-symbol`: the seven standard symbols are drawn centered on the
+//! origin and sized by area, so markers of different shapes look equally
+//! heavy at the same size. Custom symbols implement [`SymbolType`], which is
+//! also implemented for closures.
+
+use std::f64::consts::{PI, TAU};
+
+use super::path::{Path, PathSegment};
+
+/// A symbol shape that can draw itself at a given area
+pub trait SymbolType: Send + Sync {
+    /// Append the symbol, centered on the origin with the given area, to `path`
+    fn draw(&self, path: &mut Path, size: f64);
+}
+
+impl<F> SymbolType for F
+where
+    F: Fn(&mut Path, f64) + Send + Sync,
+{
+    fn draw(&self, path: &mut Path, size: f64) {
+        self(path, size)
+    }
+}
+
+/// The standard d3 symbols
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Symbol {
+    /// Circle (`d3.symbolCircle`)
+    #[default]
+    Circle,
+    /// Greek cross with arms of equal length
+    Cross,
+    /// Rhombus, taller than it is wide
+    Diamond,
+    /// Axis-aligned square
+    Square,
+    /// Five-pointed star
+    Star,
+    /// Up-pointing equilateral triangle
+    Triangle,
+    /// Three-armed "Y" shape
+    Wye,
+}
+
+impl Symbol {
+    /// All symbols in d3's `symbolsFill` order
+    pub const ALL: [Symbol; 7] = [
+        Symbol::Circle,
+        Symbol::Cross,
+        Symbol::Diamond,
+        Symbol::Square,
+        Symbol::Star,
+        Symbol::Triangle,
+        Symbol::Wye,
+    ];
+
+    /// Symbol for a series index, cycling through [`ALL`](Self::ALL)
+    pub fn for_index(index: usize) -> Self {
+        Self::ALL[index % Self::ALL.len()]
+    }
+
+    /// Lowercase symbol name (e.g. "circle")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Symbol::Circle => "circle",
+            Symbol::Cross => "cross",
+            Symbol::Diamond => "diamond",
+            Symbol::Square => "square",
+            Symbol::Star => "star",
+            Symbol::Triangle => "triangle",
+            Symbol::Wye => "wye",
+        }
+    }
+}
+
+/// Draw a closed polygon through the given vertices
+fn polygon(path: &mut Path, points: &[(f64, f64)]) {
+    if let Some(&(x, y)) = points.first() {
+        path.move_to(x, y);
+        for &(x, y) in &points[1..] {
+            path.line_to(x, y);
+        }
+        path.close();
+    }
+}
+
+impl SymbolType for Symbol {
+    fn draw(&self, path: &mut Path, size: f64) {
+        let size = size.max(0.0);
+        let sqrt3 = 3f64.sqrt();
+        match self {
+            Symbol::Circle => {
+                let r = (size / PI).sqrt();
+                path.move_to(r, 0.0);
+                path.push(PathSegment::arc_to(0.0, 0.0, r, 0.0, TAU, false));
+                path.close();
+            }
+            Symbol::Cross => {
+                let r = (size / 5.0).sqrt() / 2.0;
+                polygon(
+                    path,
+                    &[
+                        (-3.0 * r, -r),
+                        (-r, -r),
+                        (-r, -3.0 * r),
+                        (r, -3.0 * r),
+                        (r, -r),
+                        (3.0 * r, -r),
+                        (3.0 * r, r),
+                        (r, r),
+                        (r, 3.0 * r),
+                        (-r, 3.0 * r),
+                        (-r, r),
+                        (-3.0 * r, r),
+                    ],
+                );
+            }
+            Symbol::Diamond => {
+                let tan30 = (1.0f64 / 3.0).sqrt();
+                let y = (size / (tan30 * 2.0)).sqrt();
+                let x = y * tan30;
+                polygon(path, &[(0.0, -y), (x, 0.0), (0.0, y), (-x, 0.0)]);
+            }
+            Symbol::Square => {
+                let w = size.sqrt();
+                let h = w / 2.0;
+                polygon(path, &[(-h, -h), (h, -h), (h, h), (-h, h)]);
+            }
+            Symbol::Star => {
+                const KA: f64 = 0.890_813_091_529_285_2;
+                let kr = (PI / 10.0).sin() / (7.0 * PI / 10.0).sin();
+                let kx = (TAU / 10.0).sin() * kr;
+                let ky = -(TAU / 10.0).cos() * kr;
+                let r = (size * KA).sqrt();
+                let (x, y) = (kx * r, ky * r);
+                let mut points = vec![(0.0, -r), (x, y)];
+                for i in 1..5 {
+                    let (s, c) = (TAU * i as f64 / 5.0).sin_cos();
+                    points.push((s * r, -c * r));
+                    points.push((c * x - s * y, s * x + c * y));
+                }
+                polygon(path, &points);
+            }
+            Symbol::Triangle => {
+                let y = -(size / (sqrt3 * 3.0)).sqrt();
+                polygon(path, &[(0.0, y * 2.0), (-sqrt3 * y, -y), (sqrt3 * y, -y)]);
+            }
+            Symbol::Wye => {
+                let (c, s) = (-0.5, sqrt3 / 2.0);
+                let k = 1.0 / 12f64.sqrt();
+                let a = (k / 2.0 + 1.0) * 3.0;
+                let r = (size / a).sqrt();
+                let (x0, y0) = (r / 2.0, r * k);
+                let (x1, y1) = (x0, r * k + r);
+                let (x2, y2) = (-x1, y1);
+                polygon(
+                    path,
+                    &[
+                        (x0, y0),
+                        (x1, y1),
+                        (x2, y2),
+                        (c * x0 - s * y0, s * x0 + c * y0),
+                        (c * x1 - s * y1, s * x1 + c * y1),
+                        (c * x2 - s * y2, s * x2 + c * y2),
+                        (c * x0 + s * y0, c * y0 - s * x0),
+                        (c * x1 + s * y1, c * y1 - s * x1),
+                        (c * x2 + s * y2, c * y2 - s * x2),
+                    ],
+                );
+            }
+        }
+    }
+}
+
+/// Symbol generator
+///
+/// Generates marker paths centered on the origin (or a given point) with a
+/// given area in square pixels; d3's default size is 64 (an 8×8 square).
+///
+/// # Example
+/// ```
+/// use makepad_d3::shape::{Path, Symbol, SymbolGenerator};
+///
+/// // One distinct marker per series
+/// let markers: Vec<_> = (0..3)
+///     .map(|i| SymbolGenerator::new().symbol(Symbol::for_index(i)).size(100.0))
+///     .collect();
+///
+/// let square = SymbolGenerator::new().symbol(Symbol::Square).size(100.0);
+/// let path = square.generate_at(50.0, 20.0);
+/// assert_eq!(path.len(), 5); // 4 corners + close
+///
+/// // Custom symbol: a horizontal bar
+/// let dash = SymbolGenerator::new().symbol(|path: &mut Path, size: f64| {
+///     let w = size.sqrt();
+///     path.move_to(-w, -w / 4.0).line_to(w, -w / 4.0).line_to(w, w / 4.0).line_to(-w, w / 4.0).close();
+/// });
+/// assert!(!dash.generate().is_empty());
+/// ```
+pub struct SymbolGenerator {
+    /// Symbol shape
+    symbol: Box<dyn SymbolType>,
+    /// Symbol area in square pixels
+    size: f64,
+}
+
+impl Default for SymbolGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SymbolGenerator {
+    /// Create a generator drawing circles of size 64
+    pub fn new() -> Self {
+        Self {
+            symbol: Box::new(Symbol::Circle),
+            size: 64.0,
+        }
+    }
+
+    /// Set the symbol type
+    pub fn symbol(mut self, symbol: impl SymbolType + 'static) -> Self {
+        self.symbol = Box::new(symbol);
+        self
+    }
+
+    /// Set the symbol area in square pixels
+    pub fn size(mut self, size: f64) -> Self {
+        self.size = size.max(0.0);
+        self
+    }
+
+    /// Get the symbol area
+    pub fn get_size(&self) -> f64 {
+        self.size
+    }
+
+    /// Generate the symbol centered on the origin
+    pub fn generate(&self) -> Vec<PathSegment> {
+        let mut path = Path::new();
+        self.symbol.draw(&mut path, self.size);
+        path.into_segments()
+    }
+
+    /// Generate the symbol centered on `(x, y)`
+    pub fn generate_at(&self, x: f64, y: f64) -> Vec<PathSegment> {
+        self.generate().iter().map(|s| s.translated(x, y)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shoelace area of a polygonal symbol
+    fn polygon_area(segments: &[PathSegment]) -> f64 {
+        let points: Vec<_> = segments.iter().filter_map(|s| s.end_point()).collect();
+        let mut area = 0.0;
+        for i in 0..points.len() {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            area += a.x * b.y - b.x * a.y;
+        }
+        area.abs() / 2.0
+    }
+
+    #[test]
+    fn test_symbols_have_requested_area() {
+        for symbol in Symbol::ALL.into_iter().filter(|s| *s != Symbol::Circle) {
+            let segments = SymbolGenerator::new().symbol(symbol).size(150.0).generate();
+            let area = polygon_area(&segments);
+            assert!((area - 150.0).abs() < 1e-6, "{} area {}", symbol.name(), area);
+            assert_eq!(segments.last(), Some(&PathSegment::ClosePath));
+        }
+
+        let circle = SymbolGenerator::new().size(PI * 25.0).generate();
+        match circle[1] {
+            PathSegment::ArcTo { radius, .. } => assert!((radius - 5.0).abs() < 1e-12),
+            ref other => panic!("expected arc, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_symbol_vertex_counts() {
+        let count = |symbol: Symbol| SymbolGenerator::new().symbol(symbol).generate().len() - 1;
+        assert_eq!(count(Symbol::Cross), 12);
+        assert_eq!(count(Symbol::Diamond), 4);
+        assert_eq!(count(Symbol::Square), 4);
+        assert_eq!(count(Symbol::Star), 10);
+        assert_eq!(count(Symbol::Triangle), 3);
+        assert_eq!(count(Symbol::Wye), 9);
+    }
+
+    #[test]
+    fn test_generate_at_and_for_index() {
+        let square = SymbolGenerator::new().symbol(Symbol::Square).size(4.0);
+        let path = square.generate_at(10.0, 20.0);
+        assert_eq!(path[0], PathSegment::move_to(9.0, 19.0));
+        assert_eq!(path[2], PathSegment::line_to(11.0, 21.0));
+
+        assert_eq!(Symbol::for_index(0), Symbol::Circle);
+        assert_eq!(Symbol::for_index(8), Symbol::Cross);
+        assert_eq!(SymbolGenerator::new().size(-5.0).get_size(), 0.0);
+    }
+}
@@ -0,0 +1,303 @@
+//! Violin plot generator
+//!
+//! Estimates each category's sample density with a Gaussian kernel and
+//! mirrors the profile about the band center as a closed outline. Widths
+//! can be compared across categories (shared scaling) or fill each band
+//! (individual scaling).
+
+use crate::scale::{BandScale, DiscreteScale, Scale};
+use crate::stats::{deviation, quantile_sorted};
+
+use super::boxplot::DistributionOrientation;
+use super::path::Path;
+
+/// How violin widths are scaled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ViolinScale {
+    /// One density scale for all violins, so widths compare across categories
+    #[default]
+    Shared,
+    /// Each violin's peak fills the available width
+    Individual,
+}
+
+/// Drawable geometry of one violin
+#[derive(Clone, Debug)]
+pub struct Violin {
+    /// Category name
+    pub category: String,
+    /// Kernel bandwidth used, in data units
+    pub bandwidth: f64,
+    /// Density profile as (value, density) pairs, ascending by value
+    pub profile: Vec<(f64, f64)>,
+    /// Peak density of the profile
+    pub max_density: f64,
+    /// Band center on the category axis
+    pub center: f64,
+    /// Closed outline mirrored about the center
+    pub path: Path,
+}
+
+/// Silverman's rule-of-thumb kernel bandwidth for sorted samples
+///
+/// Falls back to 1.0 when the samples have no spread.
+pub fn silverman_bandwidth(sorted: &[f64]) -> f64 {
+    let n = sorted.len() as f64;
+    let sd = deviation(sorted.iter().copied()).unwrap_or(0.0);
+    let iqr = match (quantile_sorted(sorted, 0.25), quantile_sorted(sorted, 0.75)) {
+        (Some(q1), Some(q3)) => q3 - q1,
+        _ => 0.0,
+    };
+    let spread = if iqr > 0.0 { sd.min(iqr / 1.34) } else { sd };
+    let h = 0.9 * spread * n.powf(-0.2);
+    if h.is_finite() && h > 0.0 {
+        h
+    } else {
+        1.0
+    }
+}
+
+/// Gaussian kernel density estimate of `samples` at `x`
+pub fn kernel_density(samples: &[f64], bandwidth: f64, x: f64) -> f64 {
+    if samples.is_empty() || bandwidth <= 0.0 {
+        return 0.0;
+    }
+    let norm = 1.0 / ((2.0 * std::f64::consts::PI).sqrt() * bandwidth * samples.len() as f64);
+    samples
+        .iter()
+        .map(|&s| {
+            let u = (x - s) / bandwidth;
+            (-0.5 * u * u).exp()
+        })
+        .sum::<f64>()
+        * norm
+}
+
+/// Violin plot generator
+///
+/// # Example
+/// ```
+/// use makepad_d3::scale::{BandScale, LinearScale, ScaleExt};
+/// use makepad_d3::shape::ViolinGenerator;
+///
+/// let band = BandScale::new().domain(vec!["A", "B"]).range(0.0, 200.0);
+/// let y = LinearScale::new().with_domain(0.0, 10.0).with_range(300.0, 0.0);
+/// let groups = vec![
+///     ("A", vec![4.0, 5.0, 5.0, 5.5, 6.0]),
+///     ("B", vec![1.0, 2.0, 5.0, 8.0, 9.0]),
+/// ];
+///
+/// let violins = ViolinGenerator::new().resolution(40).generate(&groups, &band, &y);
+/// assert_eq!(violins.len(), 2);
+/// assert_eq!(violins[0].profile.len(), 40);
+/// // The tight group peaks higher than the spread-out one
+/// assert!(violins[0].max_density > violins[1].max_density);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ViolinGenerator {
+    /// Kernel bandwidth in data units; None uses Silverman's rule per violin
+    pub bandwidth: Option<f64>,
+    /// Number of points in each density profile
+    pub resolution: usize,
+    /// Profile extends this many bandwidths past the sample extent
+    pub extend: f64,
+    /// Violin width as a fraction of the band width
+    pub width: f64,
+    /// Width scaling across violins
+    pub scale: ViolinScale,
+    /// Axis orientation
+    pub orientation: DistributionOrientation,
+}
+
+impl Default for ViolinGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ViolinGenerator {
+    /// Create a vertical generator with automatic bandwidth and shared scaling
+    pub fn new() -> Self {
+        Self {
+            bandwidth: None,
+            resolution: 50,
+            extend: 0.0,
+            width: 0.9,
+            scale: ViolinScale::Shared,
+            orientation: DistributionOrientation::Vertical,
+        }
+    }
+
+    /// Use a fixed kernel bandwidth in data units
+    pub fn bandwidth(mut self, bandwidth: f64) -> Self {
+        self.bandwidth = (bandwidth.is_finite() && bandwidth > 0.0).then_some(bandwidth);
+        self
+    }
+
+    /// Set the number of points in each profile (at least 2)
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        self.resolution = resolution.max(2);
+        self
+    }
+
+    /// Extend the profile past the sample extent by this many bandwidths
+    ///
+    /// 0 (the default) trims the violin at the smallest and largest samples.
+    pub fn extend(mut self, bandwidths: f64) -> Self {
+        self.extend = bandwidths.max(0.0);
+        self
+    }
+
+    /// Set the violin width as a fraction of the band width
+    pub fn width(mut self, fraction: f64) -> Self {
+        self.width = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the width scaling
+    pub fn scale(mut self, scale: ViolinScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the orientation
+    pub fn orientation(mut self, orientation: DistributionOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Density profile of the samples, with the bandwidth used
+    ///
+    /// Returns None if there are no finite samples.
+    pub fn profile(&self, samples: &[f64]) -> Option<(Vec<(f64, f64)>, f64)> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        sorted.sort_by(f64::total_cmp);
+        let (&lo, &hi) = (sorted.first()?, sorted.last()?);
+
+        let bandwidth = self.bandwidth.unwrap_or_else(|| silverman_bandwidth(&sorted));
+        let (lo, hi) = if lo == hi {
+            // A single value still gets a visible bump
+            (lo - 3.0 * bandwidth, hi + 3.0 * bandwidth)
+        } else {
+            (lo - self.extend * bandwidth, hi + self.extend * bandwidth)
+        };
+        let steps = (self.resolution - 1) as f64;
+        let profile = (0..self.resolution)
+            .map(|i| {
+                let value = lo + (hi - lo) * i as f64 / steps;
+                (value, kernel_density(&sorted, bandwidth, value))
+            })
+            .collect();
+        Some((profile, bandwidth))
+    }
+
+    /// Lay out one violin per (category, samples) group
+    ///
+    /// Categories missing from the band scale and groups with no finite
+    /// samples are skipped.
+    pub fn generate<S: AsRef<str>>(
+        &self,
+        groups: &[(S, Vec<f64>)],
+        band: &BandScale,
+        value_scale: &dyn Scale,
+    ) -> Vec<Violin> {
+        let profiles: Vec<_> = groups
+            .iter()
+            .filter_map(|(category, samples)| {
+                let start = band.band_start(category.as_ref())?;
+                let (profile, bandwidth) = self.profile(samples)?;
+                let max_density = profile.iter().map(|p| p.1).fold(0.0, f64::max);
+                Some((category.as_ref(), start, profile, bandwidth, max_density))
+            })
+            .collect();
+        let shared_max = profiles.iter().map(|p| p.4).fold(0.0, f64::max);
+        let half_width = band.bandwidth() * self.width / 2.0;
+
+        profiles
+            .into_iter()
+            .map(|(category, start, profile, bandwidth, max_density)| {
+                let peak = match self.scale {
+                    ViolinScale::Shared => shared_max,
+                    ViolinScale::Individual => max_density,
+                };
+                let center = start + band.bandwidth() / 2.0;
+                let offset = |density: f64| if peak > 0.0 { half_width * density / peak } else { 0.0 };
+
+                // One side along increasing values, then back down the other
+                let mut path = Path::new();
+                let side = profile.iter().map(|&(v, d)| (value_scale.scale(v), offset(d)));
+                for (i, (pos, w)) in side.clone().enumerate() {
+                    let p = self.orientation.point(center + w, pos);
+                    if i == 0 {
+                        path.move_to(p.x, p.y);
+                    } else {
+                        path.line_to(p.x, p.y);
+                    }
+                }
+                for (pos, w) in side.rev() {
+                    let p = self.orientation.point(center - w, pos);
+                    path.line_to(p.x, p.y);
+                }
+                path.close();
+
+                Violin {
+                    category: category.to_string(),
+                    bandwidth,
+                    profile,
+                    max_density,
+                    center,
+                    path,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scale::{LinearScale, ScaleExt};
+
+    #[test]
+    fn test_kernel_density_integrates_to_one() {
+        let samples = [1.0, 2.0, 2.5, 7.0];
+        let h = 0.8;
+        let step = 0.01;
+        let total: f64 = (-1000..2000).map(|i| kernel_density(&samples, h, i as f64 * step) * step).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+        assert_eq!(kernel_density(&[], h, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_silverman_bandwidth() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        // sd = 1.58, IQR / 1.34 = 1.49
+        let expected = 0.9 * (2.0 / 1.34) * 5f64.powf(-0.2);
+        assert!((silverman_bandwidth(&sorted) - expected).abs() < 1e-12);
+        assert_eq!(silverman_bandwidth(&[3.0, 3.0]), 1.0);
+    }
+
+    #[test]
+    fn test_violin_shapes() {
+        let band = BandScale::new().domain(vec!["a", "b"]).range(0.0, 100.0);
+        let y = LinearScale::new().with_domain(0.0, 10.0).with_range(100.0, 0.0);
+        let groups = vec![("a", vec![5.0]), ("b", vec![2.0, 3.0, 4.0])];
+
+        let shared = ViolinGenerator::new().bandwidth(1.0).resolution(11).generate(&groups, &band, &y);
+        // A single sample spans three bandwidths either side
+        assert_eq!(shared[0].profile.first().unwrap().0, 2.0);
+        assert_eq!(shared[0].profile.last().unwrap().0, 8.0);
+        assert_eq!(shared[0].center, 25.0);
+
+        // Outline: 11 points up one side, 11 back down the other, then close
+        assert_eq!(shared[1].path.len(), 23);
+
+        let individual = ViolinGenerator::new()
+            .bandwidth(1.0)
+            .resolution(11)
+            .scale(ViolinScale::Individual)
+            .generate(&groups, &band, &y);
+        assert!(shared[1].max_density < shared[0].max_density);
+        assert_eq!(individual[1].max_density, shared[1].max_density);
+    }
+}
@@ -0,0 +1,341 @@
+//! Windrose (polar histogram) layout
+//!
+//! Bins directional records (direction + magnitude) into compass sectors and
+//! magnitude categories, and stacks each sector's categories outward as
+//! annular segments. Every segment converts to an [`ArcDatum`] for drawing.
+
+use std::f64::consts::TAU;
+
+use super::arc::ArcDatum;
+
+/// Radial scale mapping stacked values to radii
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WindroseScale {
+    /// Radius proportional to value
+    #[default]
+    Linear,
+    /// Area proportional to value
+    Sqrt,
+}
+
+/// One stacked segment of a windrose sector
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindroseSegment {
+    /// Sector index (0 = north, increasing clockwise)
+    pub sector: usize,
+    /// Magnitude category index
+    pub category: usize,
+    /// Count (or fraction, when normalized) in this segment
+    pub value: f64,
+    /// Start angle in radians (0 = 12 o'clock, clockwise)
+    pub start_angle: f64,
+    /// End angle in radians
+    pub end_angle: f64,
+    /// Inner radius
+    pub inner_radius: f64,
+    /// Outer radius
+    pub outer_radius: f64,
+    /// Padding angle between adjacent sectors
+    pub pad_angle: f64,
+}
+
+impl WindroseSegment {
+    /// Convert to an arc datum for rendering
+    pub fn arc(&self) -> ArcDatum {
+        ArcDatum {
+            inner_radius: self.inner_radius,
+            outer_radius: self.outer_radius,
+            pad_angle: self.pad_angle,
+            ..ArcDatum::new(self.start_angle, self.end_angle)
+        }
+    }
+}
+
+/// Result of a windrose layout
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Windrose {
+    /// Non-empty segments, sector by sector, innermost category first
+    pub segments: Vec<WindroseSegment>,
+    /// Stacked total per sector
+    pub totals: Vec<f64>,
+    /// Value mapped to the outer radius
+    pub max_value: f64,
+}
+
+impl Windrose {
+    /// Segments of one sector
+    pub fn sector(&self, sector: usize) -> impl Iterator<Item = &WindroseSegment> {
+        self.segments.iter().filter(move |s| s.sector == sector)
+    }
+}
+
+/// Windrose layout generator
+///
+/// # Example
+/// ```
+/// use makepad_d3::shape::{WindroseLayout, WindroseScale};
+///
+/// // (direction in degrees from north, wind speed)
+/// let records = vec![(0.0, 3.0), (10.0, 7.0), (90.0, 12.0), (95.0, 1.0), (270.0, 5.0)];
+///
+/// let rose = WindroseLayout::new()
+///     .sectors(8)
+///     .magnitude_bins(vec![2.0, 5.0, 10.0])
+///     .radius(20.0, 200.0)
+///     .scale(WindroseScale::Sqrt)
+///     .compute(&records);
+///
+/// // North sector holds a 2-5 and a 5-10 segment
+/// assert_eq!(rose.sector(0).count(), 2);
+/// let path = rose.segments[0].arc().generate();
+/// assert!(!path.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct WindroseLayout {
+    /// Number of compass sectors
+    sectors: usize,
+    /// Ascending upper bounds of the magnitude categories
+    magnitude_bins: Vec<f64>,
+    /// Radius of value zero
+    inner_radius: f64,
+    /// Radius of the maximum value
+    outer_radius: f64,
+    /// Radial scale
+    scale: WindroseScale,
+    /// Fixed value for the outer radius (defaults to the largest sector total)
+    max_value: Option<f64>,
+    /// Report fractions of all records instead of counts
+    normalize: bool,
+    /// Padding angle between sectors
+    pad_angle: f64,
+}
+
+impl Default for WindroseLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WindroseLayout {
+    /// Create a windrose layout with 16 sectors and a single category
+    pub fn new() -> Self {
+        Self {
+            sectors: 16,
+            magnitude_bins: Vec::new(),
+            inner_radius: 0.0,
+            outer_radius: 100.0,
+            scale: WindroseScale::Linear,
+            max_value: None,
+            normalize: false,
+            pad_angle: 0.0,
+        }
+    }
+
+    /// Set the number of sectors (at least 1)
+    pub fn sectors(mut self, sectors: usize) -> Self {
+        self.sectors = sectors.max(1);
+        self
+    }
+
+    /// Set the magnitude category bounds
+    ///
+    /// `n` bounds make `n + 1` categories: magnitudes below `bins[0]`, then
+    /// `[bins[0], bins[1])`, and so on up to `bins[n - 1]` and above.
+    pub fn magnitude_bins(mut self, mut bins: Vec<f64>) -> Self {
+        bins.retain(|b| b.is_finite());
+        bins.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        self.magnitude_bins = bins;
+        self
+    }
+
+    /// Set the inner and outer radius
+    pub fn radius(mut self, inner: f64, outer: f64) -> Self {
+        self.inner_radius = inner;
+        self.outer_radius = outer;
+        self
+    }
+
+    /// Set the radial scale
+    pub fn scale(mut self, scale: WindroseScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Fix the value mapped to the outer radius, e.g. to compare roses
+    pub fn max_value(mut self, value: f64) -> Self {
+        self.max_value = Some(value);
+        self
+    }
+
+    /// Report fractions of all records instead of counts
+    pub fn normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    /// Set the padding angle between sectors
+    pub fn pad_angle(mut self, angle: f64) -> Self {
+        self.pad_angle = angle.max(0.0);
+        self
+    }
+
+    /// Number of magnitude categories
+    pub fn category_count(&self) -> usize {
+        self.magnitude_bins.len() + 1
+    }
+
+    /// Sector index of a direction in degrees (sector 0 is centered on north)
+    pub fn sector_of(&self, direction: f64) -> usize {
+        let width = 360.0 / self.sectors as f64;
+        let shifted = (direction + width / 2.0).rem_euclid(360.0);
+        ((shifted / width) as usize).min(self.sectors - 1)
+    }
+
+    /// Category index of a magnitude
+    pub fn category_of(&self, magnitude: f64) -> usize {
+        self.magnitude_bins.partition_point(|&b| b <= magnitude)
+    }
+
+    /// Bin `(direction, magnitude)` records and lay them out
+    ///
+    /// Directions are degrees clockwise from north. Records with a
+    /// non-finite direction or magnitude are skipped.
+    pub fn compute(&self, records: &[(f64, f64)]) -> Windrose {
+        let mut counts = vec![vec![0.0; self.category_count()]; self.sectors];
+        let mut total = 0.0;
+        for &(direction, magnitude) in records {
+            if !direction.is_finite() || !magnitude.is_finite() {
+                continue;
+            }
+            counts[self.sector_of(direction)][self.category_of(magnitude)] += 1.0;
+            total += 1.0;
+        }
+        if self.normalize && total > 0.0 {
+            for value in counts.iter_mut().flatten() {
+                *value /= total;
+            }
+        }
+        self.layout_counts(&counts)
+    }
+
+    /// Lay out pre-binned values, indexed as `counts[sector][category]`
+    ///
+    /// Missing sectors or categories count as zero; extra ones are ignored.
+    pub fn layout_counts(&self, counts: &[Vec<f64>]) -> Windrose {
+        let value = |sector: usize, category: usize| {
+            counts
+                .get(sector)
+                .and_then(|row| row.get(category))
+                .copied()
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .unwrap_or(0.0)
+        };
+        let categories = self.category_count();
+        let totals: Vec<f64> = (0..self.sectors)
+            .map(|s| (0..categories).map(|c| value(s, c)).sum())
+            .collect();
+        let max_value = self
+            .max_value
+            .unwrap_or_else(|| totals.iter().copied().fold(0.0, f64::max));
+
+        let radius_of = |v: f64| {
+            let t = if max_value > 0.0 { v / max_value } else { 0.0 };
+            let t = match self.scale {
+                WindroseScale::Linear => t,
+                WindroseScale::Sqrt => t.max(0.0).sqrt(),
+            };
+            self.inner_radius + (self.outer_radius - self.inner_radius) * t
+        };
+
+        let width = TAU / self.sectors as f64;
+        let mut segments = Vec::new();
+        for sector in 0..self.sectors {
+            let center = sector as f64 * width;
+            let mut stacked = 0.0;
+            for category in 0..categories {
+                let v = value(sector, category);
+                if v == 0.0 {
+                    continue;
+                }
+                segments.push(WindroseSegment {
+                    sector,
+                    category,
+                    value: v,
+                    start_angle: center - width / 2.0,
+                    end_angle: center + width / 2.0,
+                    inner_radius: radius_of(stacked),
+                    outer_radius: radius_of(stacked + v),
+                    pad_angle: self.pad_angle,
+                });
+                stacked += v;
+            }
+        }
+
+        Windrose {
+            segments,
+            totals,
+            max_value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binning() {
+        let layout = WindroseLayout::new().sectors(4).magnitude_bins(vec![5.0, 1.0]);
+        assert_eq!(layout.category_count(), 3);
+        assert_eq!(layout.sector_of(0.0), 0);
+        assert_eq!(layout.sector_of(350.0), 0);
+        assert_eq!(layout.sector_of(-10.0), 0);
+        assert_eq!(layout.sector_of(45.0), 1);
+        assert_eq!(layout.sector_of(269.0), 3);
+        assert_eq!(layout.category_of(0.5), 0);
+        assert_eq!(layout.category_of(1.0), 1);
+        assert_eq!(layout.category_of(7.0), 2);
+    }
+
+    #[test]
+    fn test_stacked_segments() {
+        let records = [(0.0, 0.5), (0.0, 2.0), (0.0, 3.0), (180.0, 9.0), (f64::NAN, 1.0)];
+        let rose = WindroseLayout::new()
+            .sectors(4)
+            .magnitude_bins(vec![1.0, 5.0])
+            .radius(10.0, 110.0)
+            .compute(&records);
+
+        assert_eq!(rose.totals, vec![3.0, 0.0, 1.0, 0.0]);
+        assert_eq!(rose.max_value, 3.0);
+
+        let north: Vec<&WindroseSegment> = rose.sector(0).collect();
+        assert_eq!(north.len(), 2);
+        assert_eq!((north[0].category, north[0].value), (0, 1.0));
+        assert!((north[0].outer_radius - (10.0 + 100.0 / 3.0)).abs() < 1e-9);
+        assert_eq!(north[1].inner_radius, north[0].outer_radius);
+        assert!((north[1].outer_radius - 110.0).abs() < 1e-9);
+        assert!((north[0].start_angle + TAU / 8.0).abs() < 1e-9);
+
+        let south = rose.sector(2).next().unwrap();
+        assert_eq!(south.category, 2);
+        assert!((south.arc().start_angle - TAU * 3.0 / 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sqrt_scale_and_normalize() {
+        let counts = vec![vec![1.0], vec![4.0]];
+        let rose = WindroseLayout::new()
+            .sectors(2)
+            .scale(WindroseScale::Sqrt)
+            .layout_counts(&counts);
+        assert_eq!(rose.segments[0].outer_radius, 50.0);
+
+        let fixed = WindroseLayout::new().sectors(2).max_value(8.0).layout_counts(&counts);
+        assert_eq!(fixed.segments[1].outer_radius, 50.0);
+
+        let records = [(0.0, 1.0), (0.0, 1.0), (180.0, 1.0), (180.0, 1.0)];
+        let rose = WindroseLayout::new().sectors(2).normalize(true).compute(&records);
+        assert_eq!(rose.totals, vec![0.5, 0.5]);
+    }
+}
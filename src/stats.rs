@@ -0,0 +1,417 @@
+//! Statistical summaries over arrays (d3-array equivalent)
+//!
+//! Summary statistics skip non-finite values, as d3 skips `null`, `NaN` and
+//! `undefined`, and return `None` when nothing is left to summarize. [`ticks`]
+//! and [`nice`] follow d3's tick algorithm exactly, so tick values are exact
+//! decimals (`0.3`, not `0.30000000000000004`). [`group`], [`rollup`] and
+//! [`index`] bucket records by key in first-seen order.
+//!
+//! # Example
+//!
+//! ```
+//! use makepad_d3::stats::{extent, mean, quantile, ticks};
+//!
+//! let values = [3.0, 1.0, f64::NAN, 4.0, 1.0, 5.0];
+//! assert_eq!(extent(values), Some((1.0, 5.0)));
+//! assert_eq!(mean(values), Some(2.8));
+//! assert_eq!(quantile(values, 0.5), Some(3.0));
+//! assert_eq!(ticks(0.0, 1.0, 5), vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::error::{D3Error, D3Result};
+
+pub use crate::scale::extent;
+
+/// Finite values, sorted ascending
+fn sorted_finite(values: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let mut sorted: Vec<f64> = values.into_iter().filter(|v| v.is_finite()).collect();
+    sorted.sort_by(f64::total_cmp);
+    sorted
+}
+
+/// Smallest finite value
+pub fn min(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    extent(values).map(|(min, _)| min)
+}
+
+/// Largest finite value
+pub fn max(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    extent(values).map(|(_, max)| max)
+}
+
+/// Sum of the finite values (0 if there are none)
+pub fn sum(values: impl IntoIterator<Item = f64>) -> f64 {
+    values.into_iter().filter(|v| v.is_finite()).sum()
+}
+
+/// Arithmetic mean of the finite values
+pub fn mean(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let (count, total) = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .fold((0usize, 0.0), |(n, s), v| (n + 1, s + v));
+    (count > 0).then(|| total / count as f64)
+}
+
+/// Median of the finite values
+pub fn median(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    quantile(values, 0.5)
+}
+
+/// The `p`-quantile (0-1) of the finite values
+///
+/// Interpolates linearly between the two nearest ranks (d3's R-7 method).
+pub fn quantile(values: impl IntoIterator<Item = f64>, p: f64) -> Option<f64> {
+    quantile_sorted(&sorted_finite(values), p)
+}
+
+/// The `p`-quantile of values already sorted ascending
+///
+/// Avoids re-sorting when computing several quantiles (e.g. quartiles for a
+/// box plot) of the same data.
+pub fn quantile_sorted(sorted: &[f64], p: f64) -> Option<f64> {
+    let n = sorted.len();
+    if n == 0 || p.is_nan() {
+        return None;
+    }
+    if p <= 0.0 || n == 1 {
+        return Some(sorted[0]);
+    }
+    if p >= 1.0 {
+        return Some(sorted[n - 1]);
+    }
+    let i = (n - 1) as f64 * p;
+    let i0 = i.floor() as usize;
+    let (v0, v1) = (sorted[i0], sorted[(i0 + 1).min(n - 1)]);
+    Some(v0 + (v1 - v0) * (i - i0 as f64))
+}
+
+/// Sample variance (n - 1 denominator) of the finite values
+///
+/// None if there are fewer than two values. Uses Welford's algorithm for
+/// numerical stability.
+pub fn variance(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let mut count = 0usize;
+    let mut mean = 0.0;
+    let mut sum_sq = 0.0;
+    for v in values.into_iter().filter(|v| v.is_finite()) {
+        count += 1;
+        let delta = v - mean;
+        mean += delta / count as f64;
+        sum_sq += delta * (v - mean);
+    }
+    (count > 1).then(|| sum_sq / (count - 1) as f64)
+}
+
+/// Sample standard deviation of the finite values
+pub fn deviation(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    variance(values).map(f64::sqrt)
+}
+
+/// Running totals; non-finite values add nothing
+pub fn cumsum(values: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let mut total = 0.0;
+    values
+        .into_iter()
+        .map(|v| {
+            if v.is_finite() {
+                total += v;
+            }
+            total
+        })
+        .collect()
+}
+
+/// Zero-based rank of each value in ascending order
+///
+/// Tied values share the lowest rank; non-finite values have no rank.
+pub fn rank(values: &[f64]) -> Vec<Option<usize>> {
+    let mut order: Vec<usize> = (0..values.len()).filter(|&i| values[i].is_finite()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![None; values.len()];
+    let mut current = 0;
+    for (position, &i) in order.iter().enumerate() {
+        if position == 0 || values[i] != values[order[position - 1]] {
+            current = position;
+        }
+        ranks[i] = Some(current);
+    }
+    ranks
+}
+
+/// Tick spec: integer bounds `i1..=i2` and increment (negative = 1 / step)
+fn tick_spec(start: f64, stop: f64, count: f64) -> (f64, f64, f64) {
+    let step = (stop - start) / count.max(0.0);
+    let power = step.log10().floor();
+    let error = step / 10f64.powf(power);
+    let factor = if error >= 50f64.sqrt() {
+        10.0
+    } else if error >= 10f64.sqrt() {
+        5.0
+    } else if error >= 2f64.sqrt() {
+        2.0
+    } else {
+        1.0
+    };
+
+    let (mut i1, mut i2, inc);
+    if power < 0.0 {
+        let scale = 10f64.powf(-power) / factor;
+        i1 = (start * scale).round();
+        i2 = (stop * scale).round();
+        if i1 / scale < start {
+            i1 += 1.0;
+        }
+        if i2 / scale > stop {
+            i2 -= 1.0;
+        }
+        inc = -scale;
+    } else {
+        let scale = 10f64.powf(power) * factor;
+        i1 = (start / scale).round();
+        i2 = (stop / scale).round();
+        if i1 * scale < start {
+            i1 += 1.0;
+        }
+        if i2 * scale > stop {
+            i2 -= 1.0;
+        }
+        inc = scale;
+    }
+
+    if i2 < i1 && (0.5..2.0).contains(&count) {
+        return tick_spec(start, stop, count * 2.0);
+    }
+    (i1, i2, inc)
+}
+
+/// About `count` evenly spaced nice values within `[start, stop]` (d3's `ticks`)
+///
+/// Ticks are multiples of 1, 2 or 5 × 10ⁿ. If `stop < start` the ticks are
+/// returned in descending order.
+pub fn ticks(start: f64, stop: f64, count: usize) -> Vec<f64> {
+    if count == 0 || !start.is_finite() || !stop.is_finite() {
+        return Vec::new();
+    }
+    if start == stop {
+        return vec![start];
+    }
+    let reverse = stop < start;
+    let (lo, hi) = if reverse { (stop, start) } else { (start, stop) };
+    let (i1, i2, inc) = tick_spec(lo, hi, count as f64);
+    if i2 < i1 {
+        return Vec::new();
+    }
+
+    let n = (i2 - i1 + 1.0) as usize;
+    let tick = |i: usize| {
+        let k = i1 + i as f64;
+        if inc < 0.0 {
+            k / -inc
+        } else {
+            k * inc
+        }
+    };
+    if reverse {
+        (0..n).map(|i| tick(n - 1 - i)).collect()
+    } else {
+        (0..n).map(tick).collect()
+    }
+}
+
+/// Tick step for [`ticks`]; negative values are the inverse of a step below 1
+/// (d3's `tickIncrement`)
+pub fn tick_increment(start: f64, stop: f64, count: usize) -> f64 {
+    tick_spec(start, stop, count as f64).2
+}
+
+/// Tick spacing for [`ticks`], negative if `stop < start` (d3's `tickStep`)
+pub fn tick_step(start: f64, stop: f64, count: usize) -> f64 {
+    let reverse = stop < start;
+    let (lo, hi) = if reverse { (stop, start) } else { (start, stop) };
+    let inc = tick_increment(lo, hi, count);
+    let step = if inc < 0.0 { 1.0 / -inc } else { inc };
+    if reverse {
+        -step
+    } else {
+        step
+    }
+}
+
+/// Extend `[start, stop]` to align with the tick step (d3's `nice`)
+///
+/// Repeats until the step stops changing, so the result is stable.
+pub fn nice(mut start: f64, mut stop: f64, count: usize) -> (f64, f64) {
+    if !(start.is_finite() && stop.is_finite()) || count == 0 || start == stop {
+        return (start, stop);
+    }
+    let mut previous = None;
+    for _ in 0..10 {
+        let step = tick_increment(start, stop, count);
+        if previous == Some(step) {
+            break;
+        }
+        if step > 0.0 {
+            start = (start / step).floor() * step;
+            stop = (stop / step).ceil() * step;
+        } else if step < 0.0 {
+            start = (start * step).ceil() / step;
+            stop = (stop * step).floor() / step;
+        } else {
+            break;
+        }
+        previous = Some(step);
+    }
+    (start, stop)
+}
+
+/// Bucket items by key, in order of each key's first appearance
+///
+/// # Example
+///
+/// ```
+/// use makepad_d3::stats::{group, rollup};
+///
+/// let sales = [("east", 10.0), ("west", 4.0), ("east", 6.0)];
+/// let by_region = group(&sales, |s| s.0);
+/// assert_eq!(by_region[0].0, "east");
+/// assert_eq!(by_region[0].1.len(), 2);
+///
+/// let totals = rollup(&sales, |s| s.0, |group| group.iter().map(|s| s.1).sum::<f64>());
+/// assert_eq!(totals, vec![("east", 16.0), ("west", 4.0)]);
+/// ```
+pub fn group<T, K, F>(items: &[T], key: F) -> Vec<(K, Vec<&T>)>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&T) -> K,
+{
+    let mut positions: HashMap<K, usize> = HashMap::new();
+    let mut groups: Vec<(K, Vec<&T>)> = Vec::new();
+    for item in items {
+        let k = key(item);
+        match positions.get(&k) {
+            Some(&i) => groups[i].1.push(item),
+            None => {
+                positions.insert(k.clone(), groups.len());
+                groups.push((k, vec![item]));
+            }
+        }
+    }
+    groups
+}
+
+/// Group items by key and reduce each group to a value
+pub fn rollup<T, K, R, F, G>(items: &[T], key: F, reduce: G) -> Vec<(K, R)>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&T) -> K,
+    G: Fn(&[&T]) -> R,
+{
+    group(items, key)
+        .into_iter()
+        .map(|(k, members)| {
+            let value = reduce(&members);
+            (k, value)
+        })
+        .collect()
+}
+
+/// Map each key to its single item
+///
+/// Returns an error naming the first key shared by two items.
+pub fn index<T, K, F>(items: &[T], key: F) -> D3Result<HashMap<K, &T>>
+where
+    K: Hash + Eq + std::fmt::Debug,
+    F: Fn(&T) -> K,
+{
+    let mut map = HashMap::with_capacity(items.len());
+    for item in items {
+        let k = key(item);
+        if map.contains_key(&k) {
+            return Err(D3Error::InvalidData {
+                message: format!("duplicate key {:?}", k),
+            });
+        }
+        map.insert(k, item);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summaries_skip_non_finite() {
+        let values = [2.0, f64::NAN, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0, f64::INFINITY];
+        assert_eq!(min(values), Some(2.0));
+        assert_eq!(max(values), Some(9.0));
+        assert_eq!(sum(values), 40.0);
+        assert_eq!(mean(values), Some(5.0));
+        assert_eq!(median(values), Some(4.5));
+        assert!((variance(values).unwrap() - 32.0 / 7.0).abs() < 1e-12);
+        assert!((deviation(values).unwrap() - (32.0f64 / 7.0).sqrt()).abs() < 1e-12);
+
+        assert_eq!(mean([f64::NAN]), None);
+        assert_eq!(variance([1.0]), None);
+        assert_eq!(median(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_quantiles() {
+        // d3.quantile([0, 10, 30], 0.25) interpolates between ranks
+        assert_eq!(quantile([0.0, 10.0, 30.0], 0.25), Some(5.0));
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        assert_eq!(quantile_sorted(&sorted, 0.25), Some(2.75));
+        assert_eq!(quantile_sorted(&sorted, 0.75), Some(6.25));
+        assert_eq!(quantile_sorted(&sorted, -1.0), Some(1.0));
+        assert_eq!(quantile_sorted(&sorted, 2.0), Some(8.0));
+        assert_eq!(quantile_sorted(&sorted, f64::NAN), None);
+    }
+
+    #[test]
+    fn test_cumsum_and_rank() {
+        assert_eq!(cumsum([1.0, f64::NAN, 2.0, 3.0]), vec![1.0, 1.0, 3.0, 6.0]);
+        assert_eq!(
+            rank(&[30.0, 10.0, f64::NAN, 30.0, 20.0]),
+            vec![Some(2), Some(0), None, Some(2), Some(1)]
+        );
+    }
+
+    #[test]
+    fn test_ticks_match_d3() {
+        assert_eq!(ticks(0.0, 10.0, 10), (0..=10).map(f64::from).collect::<Vec<_>>());
+        assert_eq!(ticks(0.0, 1.0, 10)[3], 0.3);
+        assert_eq!(ticks(-0.5, 0.5, 4), vec![-0.4, -0.2, 0.0, 0.2, 0.4]);
+        assert_eq!(ticks(1.0, 0.0, 2), vec![1.0, 0.5, 0.0]);
+        assert_eq!(ticks(5.0, 5.0, 3), vec![5.0]);
+        assert!(ticks(0.0, 1.0, 0).is_empty());
+
+        assert_eq!(tick_step(0.0, 100.0, 5), 20.0);
+        assert_eq!(tick_step(1.0, 0.0, 10), -0.1);
+        assert_eq!(tick_increment(0.0, 1.0, 10), -10.0);
+        assert_eq!(nice(0.2, 9.7, 10), (0.0, 10.0));
+        assert_eq!(nice(1.1, 10.9, 3), (0.0, 15.0));
+        assert_eq!(nice(0.013, 0.981, 5), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_group_rollup_index() {
+        let rows = [("a", 1), ("b", 2), ("a", 3)];
+        let groups = group(&rows, |r| r.0);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].1, vec![&("a", 1), &("a", 3)]);
+
+        let counts = rollup(&rows, |r| r.0, |g| g.len());
+        assert_eq!(counts, vec![("a", 2), ("b", 1)]);
+
+        let by_value = index(&rows, |r| r.1).unwrap();
+        assert_eq!(by_value[&2].0, "b");
+        let err = index(&rows, |r| r.0).unwrap_err();
+        assert!(err.to_string().contains("duplicate key \"a\""));
+    }
+}
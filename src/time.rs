@@ -0,0 +1,156 @@
+//! Calendar interval arithmetic on timestamps (d3-time equivalent)
+//!
+//! The constructors mirror d3-time's interval names and return a
+//! [`TimeInterval`] that works in UTC, like d3's `utc*` intervals. Call
+//! [`TimeInterval::in_zone`] (or [`local`]) for the `time*` variants that
+//! follow local wall-clock time, and [`TimeInterval::every`] to step by
+//! more than one unit. Every interval supports `floor`, `ceil`, `round`,
+//! `offset`, `range` and `count`; these are the same intervals
+//! [`TimeScale`](crate::scale::TimeScale) uses for its ticks.
+//!
+//! # Example
+//! ```
+//! use makepad_d3::time::{self, TimeZoneRule};
+//! use chrono::{TimeZone, Utc};
+//!
+//! let t = Utc.with_ymd_and_hms(2024, 5, 15, 13, 47, 0).unwrap();
+//! assert_eq!(time::hour().floor(t), Utc.with_ymd_and_hms(2024, 5, 15, 13, 0, 0).unwrap());
+//!
+//! // d3's timeWeek starts on Sunday
+//! assert_eq!(time::week().floor(t), Utc.with_ymd_and_hms(2024, 5, 12, 0, 0, 0).unwrap());
+//!
+//! // Every quarter hour in [13:47, 15:00)
+//! let end = Utc.with_ymd_and_hms(2024, 5, 15, 15, 0, 0).unwrap();
+//! assert_eq!(time::minute().every(15).range(t, end, 1).len(), 4);
+//!
+//! // Local days in Berlin begin at 22:00 UTC during summer time
+//! let day = time::local(time::day(), TimeZoneRule::central_european());
+//! assert_eq!(day.floor(t), Utc.with_ymd_and_hms(2024, 5, 14, 22, 0, 0).unwrap());
+//! ```
+
+use chrono::Weekday;
+
+pub use crate::scale::{
+    BusinessCalendar, BusinessTimeInterval, DstTransition, TimeInterval, TimeZoneRule, ZonedInterval,
+};
+
+/// Milliseconds
+pub fn millisecond() -> TimeInterval {
+    TimeInterval::Millisecond(1)
+}
+
+/// Seconds
+pub fn second() -> TimeInterval {
+    TimeInterval::Second(1)
+}
+
+/// Minutes
+pub fn minute() -> TimeInterval {
+    TimeInterval::Minute(1)
+}
+
+/// Hours
+pub fn hour() -> TimeInterval {
+    TimeInterval::Hour(1)
+}
+
+/// Days
+pub fn day() -> TimeInterval {
+    TimeInterval::Day(1)
+}
+
+/// Sunday-based weeks, as d3's `timeWeek`
+pub fn week() -> TimeInterval {
+    sunday()
+}
+
+/// Weeks starting on `weekday`
+pub fn weekday(weekday: Weekday) -> TimeInterval {
+    TimeInterval::WeekStarting(weekday, 1)
+}
+
+/// Sunday-based weeks
+pub fn sunday() -> TimeInterval {
+    weekday(Weekday::Sun)
+}
+
+/// Monday-based weeks
+pub fn monday() -> TimeInterval {
+    weekday(Weekday::Mon)
+}
+
+/// Tuesday-based weeks
+pub fn tuesday() -> TimeInterval {
+    weekday(Weekday::Tue)
+}
+
+/// Wednesday-based weeks
+pub fn wednesday() -> TimeInterval {
+    weekday(Weekday::Wed)
+}
+
+/// Thursday-based weeks
+pub fn thursday() -> TimeInterval {
+    weekday(Weekday::Thu)
+}
+
+/// Friday-based weeks
+pub fn friday() -> TimeInterval {
+    weekday(Weekday::Fri)
+}
+
+/// Saturday-based weeks
+pub fn saturday() -> TimeInterval {
+    weekday(Weekday::Sat)
+}
+
+/// Calendar months
+pub fn month() -> TimeInterval {
+    TimeInterval::Month(1)
+}
+
+/// Calendar years
+pub fn year() -> TimeInterval {
+    TimeInterval::Year(1)
+}
+
+/// Evaluate `interval` on local wall-clock time in `zone`
+pub fn local(interval: TimeInterval, zone: TimeZoneRule) -> ZonedInterval {
+    interval.in_zone(zone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn test_weekday_intervals() {
+        // 2024-05-15 is a Wednesday
+        let t = at(2024, 5, 15, 12, 0);
+        assert_eq!(week().floor(t), at(2024, 5, 12, 0, 0));
+        assert_eq!(monday().floor(t), at(2024, 5, 13, 0, 0));
+        assert_eq!(wednesday().floor(t), at(2024, 5, 15, 0, 0));
+        assert_eq!(saturday().ceil(t), at(2024, 5, 18, 0, 0));
+    }
+
+    #[test]
+    fn test_count_and_offset() {
+        assert_eq!(day().count(at(2024, 1, 1, 0, 0), at(2024, 3, 1, 0, 0)), 60);
+        assert_eq!(month().offset(at(2024, 1, 31, 0, 0), 1), at(2024, 2, 29, 0, 0));
+        assert_eq!(year().every(10).floor(at(2024, 6, 1, 0, 0)), at(2020, 1, 1, 0, 0));
+    }
+
+    #[test]
+    fn test_local_vs_utc() {
+        let t = at(2024, 7, 1, 3, 0);
+        let utc_day = day();
+        let ny_day = local(day(), TimeZoneRule::us_eastern());
+        assert_eq!(utc_day.floor(t), at(2024, 7, 1, 0, 0));
+        assert_eq!(ny_day.floor(t), at(2024, 6, 30, 4, 0));
+    }
+}
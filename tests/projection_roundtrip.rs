@@ -0,0 +1,100 @@
+//! Property tests: project→invert round trips for every projection
+//!
+//! Each projection is configured with random scale, translation and
+//! rotation/parallels, then checked at random visible locations.
+
+use makepad_d3::geo::{
+    roundtrip_error, AlbersProjection, AlbersUsaProjection, ConicConformalProjection,
+    ConicEqualAreaProjection, EquirectangularProjection, MercatorProjection, NaturalEarth1Projection,
+    OrthographicProjection, Projection, ProjectionBuilder,
+};
+use proptest::prelude::*;
+
+const TOLERANCE: f64 = 1e-6;
+
+fn lon() -> impl Strategy<Value = f64> {
+    -180.0..180.0
+}
+
+fn lat() -> impl Strategy<Value = f64> {
+    -85.0..85.0
+}
+
+fn placement() -> impl Strategy<Value = (f64, f64, f64)> {
+    (10.0..2000.0, -1000.0..1000.0, -1000.0..1000.0)
+}
+
+fn assert_roundtrip(projection: &dyn Projection, lon: f64, lat: f64) -> Result<(), TestCaseError> {
+    if let Some(error) = roundtrip_error(projection, lon, lat) {
+        prop_assert!(
+            error < TOLERANCE,
+            "{} round trip off by {}° at ({}, {})",
+            projection.projection_type(),
+            error,
+            lon,
+            lat
+        );
+    }
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn mercator_roundtrip((k, tx, ty) in placement(), center in (lon(), -60.0..60.0f64), lon in lon(), lat in lat()) {
+        let projection = MercatorProjection::new().scale(k).translate(tx, ty).center(center.0, center.1);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn equirectangular_roundtrip((k, tx, ty) in placement(), center in (lon(), lat()), lon in lon(), lat in -90.0..90.0f64) {
+        let projection = EquirectangularProjection::new().scale(k).translate(tx, ty).center(center.0, center.1);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn orthographic_roundtrip((k, tx, ty) in placement(), rotate in (lon(), -90.0..90.0f64), lon in lon(), lat in -90.0..90.0f64) {
+        let projection = OrthographicProjection::new().scale(k).translate(tx, ty).rotate(rotate.0, rotate.1, 0.0);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn albers_roundtrip((k, tx, ty) in placement(), p1 in 10.0..40.0f64, gap in 5.0..30.0f64, lon in lon(), lat in lat()) {
+        let projection = AlbersProjection::with_parallels(p1, p1 + gap).scale(k).translate(tx, ty);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn conic_conformal_roundtrip((k, tx, ty) in placement(), p1 in 10.0..40.0f64, gap in 5.0..30.0f64, rotate in lon(), lon in lon(), lat in 0.0..85.0f64) {
+        // Conformal conics send the far pole to infinity; keep to the near hemisphere
+        let projection = ConicConformalProjection::new()
+            .parallels(p1, p1 + gap)
+            .rotate(rotate, 0.0, 0.0)
+            .scale(k)
+            .translate(tx, ty);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn conic_equal_area_roundtrip((k, tx, ty) in placement(), p1 in -20.0..40.0f64, gap in 5.0..30.0f64, rotate in lon(), lon in lon(), lat in lat()) {
+        let projection = ConicEqualAreaProjection::new()
+            .parallels(p1, p1 + gap)
+            .rotate(rotate, 0.0, 0.0)
+            .scale(k)
+            .translate(tx, ty);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn natural_earth_roundtrip((k, tx, ty) in placement(), lon in lon(), lat in lat()) {
+        let projection = NaturalEarth1Projection::new().scale(k).translate(tx, ty);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+
+    #[test]
+    fn albers_usa_roundtrip((k, tx, ty) in placement(), lon in -124.0..-67.0f64, lat in 33.0..49.0f64) {
+        // Lower 48 only: further south (northern Mexico) the mainland
+        // projection overlaps the Alaska and Hawaii inset boxes
+        let projection = AlbersUsaProjection::new().scale(k).translate(tx, ty);
+        assert_roundtrip(&projection, lon, lat)?;
+    }
+}